@@ -54,7 +54,8 @@ fn benchmark_files_without_partitions(c: &mut Criterion) {
                     sinks::file::FileSinkConfig {
                         path: output.try_into().unwrap(),
                         idle_timeout_secs: None,
-                        encoding: sinks::file::Encoding::Text.into(),
+                        encoding: sinks::util::encoding::StandardEncodings::Text.into(),
+                        framing: None,
                         compression: sinks::file::Compression::None,
                     },
                 );
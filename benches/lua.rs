@@ -63,6 +63,7 @@ fn bench_add_fields(c: &mut Criterion) {
                     stream::iter(buf.into_iter())
                 }))
             }
+            Transform::MultiOutput(_) => unreachable!("Benchmarked transforms have one output."),
             Transform::Task(t) => t.transform(Box::pin(rx)),
         };
 
@@ -148,6 +149,7 @@ fn bench_field_filter(c: &mut Criterion) {
                     stream::iter(buf.into_iter())
                 }))
             }
+            Transform::MultiOutput(_) => unreachable!("Benchmarked transforms have one output."),
             Transform::Task(t) => t.transform(Box::pin(rx)),
         };
 
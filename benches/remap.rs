@@ -47,6 +47,7 @@ fn benchmark_remap(c: &mut Criterion) {
                 "#}
                 .to_string(),
                 drop_on_error: true,
+                reroute_dropped: false,
             })
             .unwrap(),
         );
@@ -107,6 +108,7 @@ fn benchmark_remap(c: &mut Criterion) {
             Remap::new(RemapConfig {
                 source: ".bar = parse_json!(string!(.foo))".to_owned(),
                 drop_on_error: false,
+                reroute_dropped: false,
             })
             .unwrap(),
         );
@@ -133,6 +135,7 @@ fn benchmark_remap(c: &mut Criterion) {
             drop_field: false,
             drop_invalid: false,
             overwrite_target: None,
+            reroute_dropped: false,
         }));
 
         let event = {
@@ -176,6 +179,7 @@ fn benchmark_remap(c: &mut Criterion) {
                 "#}
                 .to_owned(),
                 drop_on_error: true,
+                reroute_dropped: false,
             })
             .unwrap(),
         );
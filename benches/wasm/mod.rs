@@ -84,6 +84,7 @@ pub fn add_fields(criterion: &mut Criterion) {
                     "#}
                     .to_string(),
                     drop_on_error: false,
+                    reroute_dropped: false,
                 })
                 .unwrap(),
             ),
@@ -145,6 +146,7 @@ fn bench_group_transforms_over_parameterized_event_sizes(
                     stream::iter(buf.into_iter())
                 }))
             }
+            Transform::MultiOutput(_) => unreachable!("Benchmarked transforms have one output."),
             Transform::Task(t) => t.transform(Box::pin(rx)),
         };
 
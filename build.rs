@@ -1,9 +1,117 @@
 fn main() {
     println!("cargo:rerun-if-changed=proto/event.proto");
+    println!("cargo:rerun-if-changed=proto/vector.proto");
+    println!("cargo:rerun-if-changed=proto/opentelemetry");
     let mut prost_build = prost_build::Config::new();
     prost_build.btree_map(&["."]);
     prost_build
         .compile_protos(&["proto/event.proto"], &["proto/"])
         .unwrap();
+
+    let mut prost_build = prost_build::Config::new();
+    prost_build.btree_map(&["."]);
+    tonic_build::configure()
+        .extern_path(".event.proto", "crate::event::proto")
+        .compile_with_config(prost_build, &["proto/vector.proto"], &["proto/"])
+        .unwrap();
+
+    compile_opentelemetry_protos();
+
     built::write_built_file().expect("Failed to acquire build-time information");
 }
+
+/// The OTLP types also implement `Deserialize`, following the JSON encoding of OTLP/HTTP.
+fn compile_opentelemetry_protos() {
+    const JSON: &str = "crate::proto::opentelemetry::json";
+    const MESSAGES: &[&str] = &[
+        "common.v1.AnyValue",
+        "common.v1.ArrayValue",
+        "common.v1.KeyValueList",
+        "common.v1.KeyValue",
+        "common.v1.InstrumentationScope",
+        "common.v1.EntityRef",
+        "resource.v1.Resource",
+        "logs.v1.ResourceLogs",
+        "logs.v1.ScopeLogs",
+        "logs.v1.LogRecord",
+        "metrics.v1.ResourceMetrics",
+        "metrics.v1.ScopeMetrics",
+        "metrics.v1.Metric",
+        "metrics.v1.Gauge",
+        "metrics.v1.Sum",
+        "metrics.v1.Histogram",
+        "metrics.v1.ExponentialHistogram",
+        "metrics.v1.Summary",
+        "metrics.v1.NumberDataPoint",
+        "metrics.v1.HistogramDataPoint",
+        "metrics.v1.ExponentialHistogramDataPoint",
+        "metrics.v1.ExponentialHistogramDataPoint.Buckets",
+        "metrics.v1.SummaryDataPoint",
+        "metrics.v1.SummaryDataPoint.ValueAtQuantile",
+        "metrics.v1.Exemplar",
+        "collector.logs.v1.ExportLogsServiceRequest",
+        "collector.metrics.v1.ExportMetricsServiceRequest",
+    ];
+
+    let mut prost_build = prost_build::Config::new();
+    prost_build.type_attribute(
+        ".opentelemetry.proto",
+        "#[derive(::serde::Deserialize)] #[serde(rename_all = \"camelCase\")]",
+    );
+    // Matched by suffix, as prefixes would also match the oneofs of the messages.
+    for message in MESSAGES {
+        prost_build.type_attribute(
+            format!("opentelemetry.proto.{}", message),
+            "#[serde(default)]",
+        );
+    }
+    // Oneofs are encoded as one of their fields, next to the other fields of the message.
+    for oneof in &[
+        "AnyValue.value",
+        "Metric.data",
+        "NumberDataPoint.value",
+        "Exemplar.value",
+    ] {
+        prost_build.field_attribute(oneof, "#[serde(flatten)]");
+    }
+    // 64 bit integers are encoded as strings, and ids as hex instead of base64.
+    for field in &[
+        "time_unix_nano",
+        "start_time_unix_nano",
+        "observed_time_unix_nano",
+        "count",
+        "zero_count",
+        "as_int",
+        "int_value",
+    ] {
+        prost_build.field_attribute(
+            field,
+            format!("#[serde(deserialize_with = \"{}::int\")]", JSON),
+        );
+    }
+    prost_build.field_attribute(
+        "bucket_counts",
+        format!("#[serde(deserialize_with = \"{}::ints\")]", JSON),
+    );
+    for field in &["trace_id", "span_id"] {
+        prost_build.field_attribute(
+            field,
+            format!("#[serde(deserialize_with = \"{}::hex\")]", JSON),
+        );
+    }
+    prost_build.field_attribute(
+        "bytes_value",
+        format!("#[serde(deserialize_with = \"{}::base64\")]", JSON),
+    );
+
+    tonic_build::configure()
+        .compile_with_config(
+            prost_build,
+            &[
+                "proto/opentelemetry/proto/collector/logs/v1/logs_service.proto",
+                "proto/opentelemetry/proto/collector/metrics/v1/metrics_service.proto",
+            ],
+            &["proto/"],
+        )
+        .unwrap();
+}
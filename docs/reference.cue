@@ -51,7 +51,7 @@ _values: {
 // * `deprecated` - The component will be removed in a future version.
 #DevelopmentStatus: "beta" | "stable" | "deprecated"
 
-#EncodingCodec: "json" | "ndjson" | "text"
+#EncodingCodec: "avro" | "csv" | "gelf" | "json" | "logfmt" | "native_protobuf" | "ndjson" | "raw_message" | "text"
 
 #Endpoint: {
 	description: string
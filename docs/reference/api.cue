@@ -58,6 +58,11 @@ api: {
 				description: """
 					Main endpoint for receiving and processing
 					GraphQL queries.
+
+					Mutations allow pausing and resuming sources,
+					reloading the configuration from disk, as when
+					sending `SIGHUP` to Vector, and validating a
+					configuration without applying it.
 					"""
 				responses: {
 					"200": {
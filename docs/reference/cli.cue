@@ -223,6 +223,47 @@ cli: {
 			}
 		}
 
+		"tap": {
+			description: """
+				Print the events output by components as JSON, for a local or remote
+				Vector instance
+				"""
+
+			flags: _default_flags
+
+			options: {
+				"limit": {
+					_short:      "l"
+					description: "Maximum number of events to sample per component, on each interval"
+					type:        "integer"
+					default:     100
+				}
+				"interval": {
+					_short:      "i"
+					description: "Interval to sample events at (in milliseconds)"
+					type:        "integer"
+					default:     500
+				}
+				"filter": {
+					_short:      "f"
+					description: "VRL condition that events must satisfy to be sampled, e.g. `.status == 500`"
+					type:        "string"
+				}
+				"url": {
+					_short:      "u"
+					description: "The URL for the GraphQL endpoint of the running Vector instance"
+					type:        "string"
+				}
+			}
+
+			args: {
+				components: {
+					description: "Names of the components to tap"
+					type:        "list"
+				}
+			}
+		}
+
 		"test": {
 			description: """
 				Run Vector config unit tests, then exit. This command is experimental and
@@ -392,6 +433,49 @@ cli: {
 				}
 			}
 		}
+
+		"vrl test": {
+			description: """
+				Run a directory of VRL test cases, and report the difference between the
+				expected and actual result of each failed case
+				"""
+
+			flags: _default_flags & {
+				"fail-early": {
+					_short:      "f"
+					description: "Stop running test cases after the first failure"
+				}
+				"no-diff": {
+					_short:      "n"
+					description: "Don't print the difference between the expected and actual result"
+				}
+				"verbose": {
+					_short:      "v"
+					description: "Print the result of each test case"
+				}
+			}
+
+			options: {
+				"pattern": {
+					_short:      "p"
+					description: "Only run the test cases with a category or name containing this pattern"
+					type:        "string"
+				}
+			}
+
+			args: {
+				paths: {
+					description: """
+						The directories containing the test cases to run, or individual test case
+						files. Each test case is a `.vrl` file, with leading `# object: ...`,
+						`# result: ...` and optional `# program: ...` comments defining the input
+						event, the expected result or error, and a file containing the program to
+						run, relative to the test case.
+						"""
+					type: "list"
+				}
+			}
+		}
 	}
 
 	// Helpers
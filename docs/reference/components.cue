@@ -165,6 +165,7 @@ components: {
 		let Args = _args
 
 		if Args.kind == "source" {
+			codecs?:   #FeaturesCodecs
 			collect?:  #FeaturesCollect
 			generate?: #FeaturesGenerate
 			multiline: #FeaturesMultiline
@@ -259,6 +260,14 @@ components: {
 		enabled: bool
 	}
 
+	#FeaturesCodecs: {
+		enabled: bool
+
+		// The framing method used unless configured otherwise, or `null` if
+		// the framing can't be configured.
+		default_framing: "bytes" | "newline_delimited" | null
+	}
+
 	#FeaturesEncoding: {
 		enabled: bool
 	}
@@ -343,6 +352,10 @@ components: {
 						enum:    [#EncodingCodec, ...#EncodingCodec] | null
 					}
 				}
+
+				// The framing method used unless configured otherwise, for
+				// the sinks whose framing can be configured.
+				framing?: default: "bytes" | "newline_delimited"
 			}
 		}
 
@@ -875,6 +888,12 @@ components: {
 					}
 				}
 
+				if features.codecs != _|_ {
+					if features.codecs.enabled == true {
+						codecs: "Decodes JSON, syslog and native events without an extra transform."
+					}
+				}
+
 				if features.receive != _|_ {
 					if features.receive.from != _|_ {
 						receive_context: "Enriches data with useful \(features.receive.from.service.name) context."
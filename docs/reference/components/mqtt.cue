@@ -0,0 +1,144 @@
+package metadata
+
+components: _mqtt: {
+	features: {
+		collect: from: {
+			service: services.mqtt
+			interface: {
+				socket: {
+					api: {
+						title: "MQTT protocol"
+						url:   urls.mqtt_specification
+					}
+					direction: "outgoing"
+					port:      1883
+					protocols: ["tcp"]
+					ssl: "optional"
+				}
+			}
+		}
+
+		send: to: {
+			service: services.mqtt
+			interface: {
+				socket: {
+					api: {
+						title: "MQTT protocol"
+						url:   urls.mqtt_specification
+					}
+					direction: "outgoing"
+					protocols: ["tcp"]
+					ssl: "optional"
+				}
+			}
+		}
+
+		tls: {
+			enabled:                true
+			can_enable:             true
+			can_verify_certificate: false
+			can_verify_hostname:    false
+			enabled_default:        false
+		}
+	}
+
+	support: {
+		targets: {
+			"aarch64-unknown-linux-gnu":      true
+			"aarch64-unknown-linux-musl":     true
+			"armv7-unknown-linux-gnueabihf":  true
+			"armv7-unknown-linux-musleabihf": true
+			"x86_64-apple-darwin":            true
+			"x86_64-pc-windows-msv":          true
+			"x86_64-unknown-linux-gnu":       true
+			"x86_64-unknown-linux-musl":      true
+		}
+		requirements: []
+		warnings: []
+		notices: [
+			"""
+				When TLS is enabled, the `tls.ca_file` option is required, the broker's
+				certificate and hostname are always verified, and encrypted keys
+				(`tls.key_pass`) are not supported.
+				""",
+		]
+	}
+
+	configuration: {
+		client_id: {
+			description: "The client identifier sent to the broker. It must be unique among the clients connected to the broker, which disconnects a client when another one connects with the same identifier."
+			required:    true
+			warnings: []
+			type: string: {
+				examples: ["vector", "vector-gateway-1"]
+				syntax: "literal"
+			}
+		}
+		host: {
+			description: "The host name or IP address of the MQTT broker."
+			required:    true
+			warnings: []
+			type: string: {
+				examples: ["127.0.0.1", "mqtt.example.com"]
+				syntax: "literal"
+			}
+		}
+		keep_alive_secs: {
+			common:      false
+			description: "The maximum interval between two packets sent to the broker. The client pings the broker when idle for this long."
+			required:    false
+			warnings: []
+			type: uint: {
+				default: 60
+				unit:    "seconds"
+			}
+		}
+		password: {
+			common:      false
+			description: "The password to authenticate with, along with `user`."
+			required:    false
+			warnings: []
+			type: string: {
+				default: null
+				examples: ["${MQTT_PASSWORD}"]
+				syntax: "literal"
+			}
+		}
+		port: {
+			common:      true
+			description: "The port of the MQTT broker."
+			required:    false
+			warnings: []
+			type: uint: {
+				default: 1883
+				examples: [1883, 8883]
+				unit: null
+			}
+		}
+		qos: {
+			common:      true
+			description: "The quality of service of the messages."
+			required:    false
+			warnings: []
+			type: string: {
+				default: "at_most_once"
+				enum: {
+					at_most_once:  "QoS 0, each message is delivered at most once."
+					at_least_once: "QoS 1, each message is delivered at least once, and acknowledged by the receiver."
+				}
+				syntax: "literal"
+			}
+		}
+		user: {
+			common:      false
+			description: "The user name to authenticate with."
+			required:    false
+			warnings: []
+			type: string: {
+				default: null
+				examples: ["vector"]
+				syntax: "literal"
+			}
+		}
+	}
+}
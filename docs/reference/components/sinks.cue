@@ -70,7 +70,7 @@ components: sinks: [Name=string]: {
 							common:        true
 							description:   "The maximum number of [events][docs.data-model] allowed in the buffer."
 							required:      false
-							relevant_when: "type = \"memory\""
+							relevant_when: "type = \"memory\" or type = \"tiered\""
 							type: uint: {
 								default: 500
 								unit:    "events"
@@ -79,12 +79,37 @@ components: sinks: [Name=string]: {
 						max_size: {
 							description:   "The maximum size of the buffer on the disk."
 							required:      true
-							relevant_when: "type = \"disk\""
+							relevant_when: "type = \"disk\" or type = \"disk_v2\" or type = \"tiered\""
 							type: uint: {
 								examples: [104900000]
 								unit: "bytes"
 							}
 						}
+						sync: {
+							common:        false
+							description:   "When records written to the buffer are forced to the disk."
+							required:      false
+							relevant_when: "type = \"disk_v2\" or type = \"tiered\""
+							type: string: {
+								default: "interval"
+								enum: {
+									always:   "Syncs every time written records are flushed. This is the most durable, but the slowest."
+									interval: "Syncs at most once every `sync_interval_secs`."
+									never:    "Leaves syncing up to the operating system. Data may be lost if the host crashes."
+								}
+								syntax: "literal"
+							}
+						}
+						sync_interval_secs: {
+							common:        false
+							description:   "The minimum interval between syncs when `sync` is `interval`."
+							required:      false
+							relevant_when: "type = \"disk_v2\" or type = \"tiered\""
+							type: uint: {
+								default: 1
+								unit:    "seconds"
+							}
+						}
 						type: {
 							common:      true
 							description: "The buffer's type and storage mechanism."
@@ -92,15 +117,17 @@ components: sinks: [Name=string]: {
 							type: string: {
 								default: "memory"
 								enum: {
-									memory: "Stores the sink's buffer in memory. This is more performant, but less durable. Data will be lost if Vector is restarted forcefully."
-									disk:   "Stores the sink's buffer on disk. This is less performant, but durable. Data will not be lost between restarts."
+									memory:  "Stores the sink's buffer in memory. This is more performant, but less durable. Data will be lost if Vector is restarted forcefully."
+									disk:    "Stores the sink's buffer on disk. This is less performant, but durable. Data will not be lost between restarts."
+									disk_v2: "Stores the sink's buffer on disk in checksummed, append-only segment files. Corrupted records are skipped when recovering from a crash. Unlike `disk`, this is available on every platform."
+									tiered:  "Stores up to `max_events` in memory, and only spills events to disk once that is exceeded. Events are not lost between restarts once they have been spilled, but the order of events is not preserved between the two tiers."
 								}
 								syntax: "literal"
 							}
 						}
 						when_full: {
 							common:      false
-							description: "The behavior when the buffer becomes full."
+							description: "The behavior when the buffer becomes full. For `tiered` buffers, this only applies once the disk tier is full."
 							required:    false
 							type: string: {
 								default: "block"
@@ -206,6 +233,42 @@ components: sinks: [Name=string]: {
 			}
 		}
 
+		if features.send != _|_ {
+			if features.send.encoding.enabled {
+				if features.send.encoding.framing != _|_ {
+					framing: {
+						common:      false
+						description: "Configures how the encoded events are delimited from one another. Defaults to `\(features.send.encoding.framing.default)`."
+						required:    false
+						type: object: options: {
+							delimiter: {
+								description:   "The single byte character following each event."
+								relevant_when: "method = `character_delimited`"
+								required:      true
+								type: string: {
+									examples: [",", "\t"]
+									syntax: "literal"
+								}
+							}
+							method: {
+								description: "The framing method."
+								required:    true
+								type: string: {
+									enum: {
+										bytes:               "Events aren't delimited, as when each is sent in its own message."
+										newline_delimited:   "Events are followed by a newline."
+										character_delimited: "Events are followed by the `delimiter` character."
+										length_delimited:    "Events are prefixed by their length, as a big endian 32 bits integer."
+									}
+									syntax: "literal"
+								}
+							}
+						}
+					}
+				}
+			}
+		}
+
 		if features.healthcheck != _|_ {
 			if features.healthcheck.enabled {
 				healthcheck: {
@@ -403,6 +466,38 @@ components: sinks: [Name=string]: {
 	}
 
 	how_it_works: {
+		if features.send != _|_ {
+			if features.send.encoding.enabled {
+				if features.send.encoding.framing != _|_ {
+					encoding: {
+						title: "Encoding"
+						body:  """
+							Besides the encodings specific to this sink, the `encoding.codec`
+							option accepts the standard codecs shared by the sinks sending
+							events one at a time:
+
+							* `text` sends the `message` field of logs, or the text
+							  representation of metrics. Logs without a `message` field
+							  are sent as an empty payload, except by the `console` and
+							  `http` sinks, which drop them.
+							* `json` (or `ndjson`) sends events as JSON objects.
+							* `logfmt` sends the fields of logs as `key=value` pairs.
+							* `csv` sends the given fields of logs as a CSV record, with
+							  `encoding.codec.csv.fields = ["field", ...]`.
+							* `native_protobuf` sends events in Vector's native protobuf
+							  format, which the `native_protobuf` decoding of sources reads.
+							* `raw_message` sends the `message` field of logs as is.
+
+							Events which fail to encode, such as metrics with a codec only
+							encoding logs, are dropped and counted by the
+							`processing_errors_total` metric. Encoded events are then
+							delimited as configured by the `framing` option.
+							"""
+					}
+				}
+			}
+		}
+
 		if features.buffer.enabled {
 			if features.send != _|_ {
 				if features.send.batch != _|_ {
@@ -34,8 +34,9 @@ components: sinks: aws_s3: components._aws & {
 				codec: {
 					enabled: true
 					default: null
-					enum: ["ndjson", "text"]
+					enum: ["csv", "json", "logfmt", "native_protobuf", "ndjson", "raw_message", "text"]
 				}
+				framing: default: "newline_delimited"
 			}
 			request: {
 				enabled:                    true
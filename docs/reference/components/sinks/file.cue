@@ -28,8 +28,9 @@ components: sinks: file: {
 				codec: {
 					enabled: true
 					default: null
-					enum: ["ndjson", "text"]
+					enum: ["csv", "json", "logfmt", "native_protobuf", "ndjson", "raw_message", "text"]
 				}
+				framing: default: "newline_delimited"
 			}
 			request: enabled: false
 			tls: enabled:     false
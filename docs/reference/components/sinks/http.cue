@@ -34,8 +34,9 @@ components: sinks: http: {
 				codec: {
 					enabled: true
 					default: null
-					enum: ["json", "ndjson", "text"]
+					enum: ["csv", "gelf", "json", "logfmt", "native_protobuf", "ndjson", "raw_message", "text"]
 				}
+				framing: default: "newline_delimited"
 			}
 			request: {
 				enabled:                    true
@@ -127,10 +128,27 @@ components: sinks: http: {
 		metrics: null
 	}
 
+	how_it_works: {
+		gelf: {
+			title: "GELF"
+			body:  """
+				With the `gelf` codec, each event is sent as a
+				[GELF](\(urls.gelf)) message in its own request, as expected by
+				Graylog's GELF HTTP input. Events without a `host` field are
+				dropped, as GELF requires one.
+
+				The `json` codec of this sink sends each batch as a JSON array,
+				rather than as the newline delimited objects of the `ndjson`
+				codec. Neither it nor `gelf` is affected by the `framing` option.
+				"""
+		}
+	}
+
 	telemetry: metrics: {
 		events_discarded_total:  components.sources.internal_metrics.output.metrics.events_discarded_total
 		http_bad_requests_total: components.sources.internal_metrics.output.metrics.http_bad_requests_total
 		processed_bytes_total:   components.sources.internal_metrics.output.metrics.processed_bytes_total
 		processed_events_total:  components.sources.internal_metrics.output.metrics.processed_events_total
+		processing_errors_total: components.sources.internal_metrics.output.metrics.processing_errors_total
 	}
 }
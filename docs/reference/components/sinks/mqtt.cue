@@ -0,0 +1,90 @@
+package metadata
+
+components: sinks: mqtt: {
+	title: "MQTT"
+
+	classes: {
+		commonly_used: false
+		delivery:      "best_effort"
+		development:   "beta"
+		egress_method: "stream"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		buffer: enabled:      false
+		healthcheck: enabled: false
+		send: {
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: {
+					enabled: true
+					default: null
+					enum: ["csv", "json", "logfmt", "native_protobuf", "ndjson", "raw_message", "text"]
+				}
+				framing: default: "bytes"
+			}
+			request: enabled: false
+			tls: components._mqtt.features.tls
+			to:  components._mqtt.features.send.to
+		}
+	}
+
+	support: components._mqtt.support
+
+	configuration: {
+		client_id:       components._mqtt.configuration.client_id
+		host:            components._mqtt.configuration.host
+		keep_alive_secs: components._mqtt.configuration.keep_alive_secs
+		password:        components._mqtt.configuration.password
+		port:            components._mqtt.configuration.port
+		qos:             components._mqtt.configuration.qos & {
+			description: "The quality of service of the published messages."
+		}
+		retain: {
+			common:      false
+			description: "Ask the broker to retain the last message of each topic, and deliver it to the clients subscribing to the topic later on."
+			required:    false
+			warnings: []
+			type: bool: default: false
+		}
+		topic: {
+			description: "The topic to publish messages to."
+			required:    true
+			warnings: []
+			type: string: {
+				examples: ["vector", "logs/{{ host }}"]
+				syntax: "template"
+			}
+		}
+		user: components._mqtt.configuration.user
+	}
+
+	input: {
+		logs:    true
+		metrics: null
+	}
+
+	how_it_works: {
+		acknowledgements: {
+			title: "Acknowledgements"
+			body:  """
+				Events are acknowledged in order once their message is sent to
+				the broker with the `at_most_once` QoS, and once the broker
+				confirms it with the `at_least_once` QoS. Events whose message
+				isn't confirmed when Vector stops aren't acknowledged.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		connection_errors_total: components.sources.internal_metrics.output.metrics.connection_errors_total
+		events_discarded_total:  components.sources.internal_metrics.output.metrics.events_discarded_total
+		processing_errors_total: components.sources.internal_metrics.output.metrics.processing_errors_total
+		processed_bytes_total:   components.sources.internal_metrics.output.metrics.processed_bytes_total
+		processed_events_total:  components.sources.internal_metrics.output.metrics.processed_events_total
+		send_errors_total:       components.sources.internal_metrics.output.metrics.send_errors_total
+	}
+}
@@ -22,8 +22,9 @@ components: sinks: nats: {
 				codec: {
 					enabled: true
 					default: null
-					enum: ["json", "text"]
+					enum: ["csv", "json", "logfmt", "native_protobuf", "ndjson", "raw_message", "text"]
 				}
+				framing: default: "bytes"
 			}
 			request: enabled: false
 			tls: enabled:     false
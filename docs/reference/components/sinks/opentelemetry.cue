@@ -0,0 +1,159 @@
+package metadata
+
+components: sinks: opentelemetry: {
+	title: "OpenTelemetry"
+
+	description: """
+		Exports logs and metrics to an OpenTelemetry collector, or any other
+		receiver of the OpenTelemetry protocol.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		buffer: enabled:      true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_bytes:    10000000
+				max_events:   1000
+				timeout_secs: 1
+			}
+			compression: {
+				enabled: true
+				default: "none"
+				algorithms: ["none", "gzip"]
+				levels: ["none", "fast", "default", "best", 0, 1, 2, 3, 4, 5, 6, 7, 8, 9]
+			}
+			encoding: enabled: false
+			request: {
+				enabled:                    true
+				concurrency:                5
+				rate_limit_duration_secs:   1
+				rate_limit_num:             5
+				retry_initial_backoff_secs: 1
+				retry_max_duration_secs:    10
+				timeout_secs:               60
+				headers:                    false
+			}
+			tls: {
+				enabled:                true
+				can_enable:             false
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+			}
+			to: {
+				service: services.opentelemetry
+
+				interface: {
+					socket: {
+						api: {
+							title: "OpenTelemetry protocol"
+							url:   urls.opentelemetry_otlp
+						}
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		targets: {
+			"aarch64-unknown-linux-gnu":      true
+			"aarch64-unknown-linux-musl":     true
+			"armv7-unknown-linux-gnueabihf":  true
+			"armv7-unknown-linux-musleabihf": true
+			"x86_64-apple-darwin":            true
+			"x86_64-pc-windows-msv":          true
+			"x86_64-unknown-linux-gnu":       true
+			"x86_64-unknown-linux-musl":      true
+		}
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	input: {
+		logs: true
+		metrics: {
+			counter:      true
+			distribution: true
+			gauge:        true
+			histogram:    true
+			summary:      true
+			set:          true
+		}
+	}
+
+	configuration: {
+		endpoint: {
+			description: "The endpoint of the receiver. OTLP/HTTP requests are sent to its `/v1/logs` and `/v1/metrics` paths."
+			required:    true
+			warnings: []
+			type: string: {
+				examples: ["http://127.0.0.1:4318", "https://otel-collector.example.com:4317"]
+				syntax: "literal"
+			}
+		}
+		protocol: {
+			common:      true
+			description: "The transport of the OpenTelemetry protocol used to export the events."
+			required:    false
+			warnings: ["Compression isn't supported by the `grpc` protocol."]
+			type: string: {
+				default: "http"
+				enum: {
+					http: "Export requests are posted over HTTP, encoded as protobuf."
+					grpc: "Export requests are sent to the logs and metrics services over gRPC."
+				}
+				syntax: "literal"
+			}
+		}
+	}
+
+	how_it_works: {
+		resources: {
+			title: "Resources and scopes"
+			body: """
+				Each event is exported as a record of its own resource, and the
+				records of the same resource and scope are merged into a single
+				export request. The `resources` and `scope` fields of logs are
+				mapped to their resource and scope, as set by the
+				[`opentelemetry` source][docs.sources.opentelemetry], while
+				the other fields are added to the attributes of the record.
+				The tags of metrics are mapped to the attributes of their
+				resource, with the `resource.` prefix stripped, except for the
+				`scope.` prefixed tags, which are set on their scope.
+				"""
+		}
+		metric_types: {
+			title: "Metric type interpretation"
+			body: """
+				Counters are exported as monotonic sums, and incremental gauges
+				as non-monotonic sums. Absolute gauges are exported as gauges,
+				and sets as gauges of their number of values. Distributions are
+				aggregated to histograms with a single bucket. Incremental
+				metrics have a delta temporality, while absolute ones are
+				cumulative.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		processed_bytes_total:  components.sources.internal_metrics.output.metrics.processed_bytes_total
+		processed_events_total: components.sources.internal_metrics.output.metrics.processed_events_total
+	}
+}
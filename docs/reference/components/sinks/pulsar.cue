@@ -22,8 +22,9 @@ components: sinks: pulsar: {
 				codec: {
 					enabled: true
 					default: null
-					enum: ["text", "json"]
+					enum: ["avro", "csv", "json", "logfmt", "native_protobuf", "ndjson", "raw_message", "text"]
 				}
+				framing: default: "bytes"
 			}
 			request: enabled: false
 			tls: enabled:     false
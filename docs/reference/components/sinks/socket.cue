@@ -22,8 +22,9 @@ components: sinks: socket: {
 				codec: {
 					enabled: true
 					default: null
-					enum: ["json", "text"]
+					enum: ["csv", "gelf", "json", "logfmt", "native_protobuf", "ndjson", "raw_message", "text"]
 				}
+				framing: default: "newline_delimited"
 			}
 			send_buffer_bytes: {
 				enabled:       true
@@ -109,9 +110,26 @@ components: sinks: socket: {
 		metrics: null
 	}
 
+	how_it_works: {
+		gelf: {
+			title: "GELF"
+			body:  """
+				With the `gelf` codec, events are sent as [GELF](\(urls.gelf))
+				messages: the `message` field becomes the `short_message`, and
+				other fields become additional fields, prefixed with `_`. Events
+				without a `host` field are dropped, as GELF requires one.
+				Messages are delimited by null bytes over TCP and Unix sockets,
+				and sent as single uncompressed datagrams over UDP, so UDP
+				messages larger than the network allows are lost. The `framing`
+				option doesn't apply to GELF messages.
+				"""
+		}
+	}
+
 	telemetry: metrics: {
 		connection_errors_total: components.sources.internal_metrics.output.metrics.connection_errors_total
 		processed_bytes_total:   components.sources.internal_metrics.output.metrics.processed_bytes_total
 		processed_events_total:  components.sources.internal_metrics.output.metrics.processed_events_total
+		processing_errors_total: components.sources.internal_metrics.output.metrics.processing_errors_total
 	}
 }
@@ -20,14 +20,35 @@ components: sinks: vector: {
 		buffer: enabled:      true
 		healthcheck: enabled: true
 		send: {
-			compression: enabled: false
+			batch: {
+				enabled:      true
+				common:       false
+				max_bytes:    10000000
+				max_events:   1000
+				timeout_secs: 1
+			}
+			compression: {
+				enabled: true
+				default: "none"
+				algorithms: ["none", "gzip"]
+				levels: ["none", "fast", "default", "best", 0, 1, 2, 3, 4, 5, 6, 7, 8, 9]
+			}
 			encoding: {
 				enabled: true
 				codec: enabled: false
 			}
 			send_buffer_bytes: enabled: true
 			keepalive: enabled:         true
-			request: enabled:           false
+			request: {
+				enabled:                    true
+				concurrency:                5
+				rate_limit_duration_secs:   1
+				rate_limit_num:             5
+				retry_initial_backoff_secs: 1
+				retry_max_duration_secs:    10
+				timeout_secs:               60
+				headers:                    false
+			}
 			tls: {
 				enabled:                true
 				can_enable:             true
@@ -87,6 +108,20 @@ components: sinks: vector: {
 				syntax: "literal"
 			}
 		}
+		version: {
+			common:      true
+			description: "The version of the protocol spoken with the downstream `vector` source, which must use the same version. The `batch`, `compression` and `request` options are only used by version 2, and `keepalive` and `send_buffer_bytes` only by version 1."
+			required:    false
+			warnings: []
+			type: string: {
+				default: "1"
+				enum: {
+					"1": "Events are streamed over TCP, without acknowledgement."
+					"2": "Batches of events are sent over gRPC, and retried until they're acknowledged."
+				}
+				syntax: "literal"
+			}
+		}
 	}
 
 	how_it_works: components.sources.vector.how_it_works
@@ -71,6 +71,74 @@ components: sources: [Name=string]: {
 			}
 		}
 
+		if features.codecs != _|_ {
+			if features.codecs.enabled {
+				decoding: {
+					common:      false
+					description: "Configures how events are parsed from the frames of the received bytes."
+					required:    false
+					type: object: options: {
+						codec: {
+							description: "The codec used to parse each frame."
+							required:    true
+							type: string: {
+								enum: {
+									bytes:           "The frame is the `message` of the event."
+									json:            "The frame is a JSON object, or an array of objects, whose fields are those of the events."
+									syslog:          "The frame is a syslog message, parsed as by the [`syslog` source](\(urls.vector_syslog_source))."
+									native_protobuf: "The frame is an event encoded with Vector's native protobuf format, which may be a log or a metric."
+								}
+								syntax: "literal"
+							}
+						}
+					}
+				}
+
+				if features.codecs.default_framing != null {
+					framing: {
+						common:      false
+						description: "Configures how the received bytes are split into frames. Defaults to `\(features.codecs.default_framing)`."
+						required:    false
+						type: object: options: {
+							delimiter: {
+								description:   "The single byte character frames are delimited by."
+								relevant_when: "method = `character_delimited`"
+								required:      true
+								type: string: {
+									examples: [",", "\t"]
+									syntax: "literal"
+								}
+							}
+							max_length: {
+								common:        false
+								description:   "The maximum bytes size of a frame. Longer frames are discarded. In the `tcp` mode of the `socket` source, defaults to its `max_length`."
+								relevant_when: "method = `newline_delimited` or `character_delimited` or `length_delimited` or `octet_counting`"
+								required:      false
+								type: uint: {
+									default: null
+									unit:    "bytes"
+								}
+							}
+							method: {
+								description: "The framing method."
+								required:    true
+								type: string: {
+									enum: {
+										bytes:               "The whole input is a single frame, as for a datagram, a message or a request body."
+										newline_delimited:   "Frames are delimited by a newline."
+										character_delimited: "Frames are delimited by the `delimiter` character."
+										length_delimited:    "Frames are prefixed by their length, as a big endian 32 bits integer."
+										octet_counting:      "Frames are prefixed by their length and a space, as per [RFC 6587](\(urls.syslog_6587)), falling back to newline delimited frames otherwise."
+									}
+									syntax: "literal"
+								}
+							}
+						}
+					}
+				}
+			}
+		}
+
 		if features.encoding != _|_ {
 			if features.encoding.enabled {
 				encoding: {
@@ -206,6 +274,21 @@ components: sources: [Name=string]: {
 			}
 		}
 
+		if features.codecs != _|_ {
+			if features.codecs.enabled {
+				decoding: {
+					title: "Decoding"
+					body:  """
+						The `decoding` option parses events out of each frame, so that
+						JSON or syslog inputs don't need an extra transform. Frames
+						which fail to parse are dropped, and counted by the
+						`processing_errors_total` metric. Context fields are only added
+						to logs, since the `native_protobuf` codec may decode metrics.
+						"""
+				}
+			}
+		}
+
 		context: {
 			title: "Context"
 			body:  """
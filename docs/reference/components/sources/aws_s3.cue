@@ -47,6 +47,12 @@ components: sources: aws_s3: components._aws & {
 	}
 
 	configuration: {
+		acknowledgements: {
+			common:      false
+			description: "Only delete an SQS message once every sink has acknowledged the events created from the S3 objects it refers to. Otherwise the message becomes visible again after the visibility timeout and the objects are processed again."
+			required:    false
+			type: bool: default: false
+		}
 		strategy: {
 			common:      false
 			description: "The strategy to use to consume objects from AWS S3."
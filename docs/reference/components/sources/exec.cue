@@ -0,0 +1,187 @@
+package metadata
+
+components: sources: exec: {
+	title: "Exec"
+
+	description: """
+		Runs a command and turns each line of its output into an event,
+		either on a schedule or as a long running process.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		deployment_roles: ["daemon", "sidecar"]
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		multiline: enabled: false
+		generate: {}
+	}
+
+	support: {
+		targets: {
+			"aarch64-unknown-linux-gnu":      true
+			"aarch64-unknown-linux-musl":     true
+			"armv7-unknown-linux-gnueabihf":  true
+			"armv7-unknown-linux-musleabihf": true
+			"x86_64-apple-darwin":            true
+			"x86_64-pc-windows-msv":          true
+			"x86_64-unknown-linux-gnu":       true
+			"x86_64-unknown-linux-musl":      true
+		}
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		command: {
+			description: "The command to run, followed by its arguments. It is run directly, not through a shell."
+			required:    true
+			warnings: []
+			type: array: items: type: string: {
+				examples: ["echo", "Hello World!"]
+				syntax: "literal"
+			}
+		}
+		exec_interval_secs: {
+			common:        true
+			description:   "The interval, in seconds, between runs of the command. Runs that are missed while the command takes longer than the interval are skipped."
+			relevant_when: "mode = `scheduled`"
+			required:      false
+			warnings: []
+			type: uint: {
+				default: 60
+				unit:    "seconds"
+			}
+		}
+		include_stderr: {
+			common:      false
+			description: "Whether the lines written by the command to STDERR are captured too."
+			required:    false
+			warnings: []
+			type: bool: default: true
+		}
+		maximum_buffer_size_bytes: {
+			common:      false
+			description: "The maximum bytes size of a line. Longer lines are discarded."
+			required:    false
+			warnings: []
+			type: uint: {
+				default: 1000000
+				unit:    "bytes"
+			}
+		}
+		mode: {
+			description: "How the command is run."
+			required:    true
+			warnings: []
+			type: string: {
+				enum: {
+					scheduled: "The command is run every `exec_interval_secs`."
+					streaming: "The command is expected to run for a long time, and is respawned when it exits."
+				}
+				syntax: "literal"
+			}
+		}
+		respawn_max_interval_secs: {
+			common:        false
+			description:   "The maximum interval, in seconds, to wait before respawning a command which exited. The interval grows exponentially while the command keeps exiting quickly."
+			relevant_when: "mode = `streaming`"
+			required:      false
+			warnings: []
+			type: uint: {
+				default: 60
+				unit:    "seconds"
+			}
+		}
+		respawn_on_exit: {
+			common:        false
+			description:   "Whether the command is respawned when it exits."
+			relevant_when: "mode = `streaming`"
+			required:      false
+			warnings: []
+			type: bool: default: true
+		}
+		working_directory: {
+			common:      false
+			description: "The directory the command is run in. Defaults to the working directory of Vector."
+			required:    false
+			warnings: []
+			type: string: {
+				default: null
+				examples: ["/var/lib/vector"]
+				syntax: "literal"
+			}
+		}
+	}
+
+	output: logs: line: {
+		description: "A line written by the command."
+		fields: {
+			command: {
+				description: "The command and its arguments."
+				required:    true
+				type: array: items: type: string: {
+					examples: ["echo", "Hello World!"]
+					syntax: "literal"
+				}
+			}
+			host:    fields._local_host
+			message: fields._raw_line
+			pid: {
+				description: "The process ID of the command."
+				required:    true
+				type: uint: {
+					examples: [4242]
+					unit: null
+				}
+			}
+			stream: {
+				description: "The stream the line was written to."
+				required:    true
+				type: string: {
+					enum: {
+						stdout: "STDOUT"
+						stderr: "STDERR"
+					}
+					syntax: "literal"
+				}
+			}
+			timestamp: fields._current_timestamp
+		}
+	}
+
+	how_it_works: {
+		line_delimiters: {
+			title: "Line Delimiters"
+			body: """
+				Each line is read until a new line delimiter, the `0xA` byte, is found.
+				"""
+		}
+		shutdown: {
+			title: "Shutdown"
+			body: """
+				When Vector shuts down, the running command is sent `SIGTERM`,
+				and is killed if it hasn't exited within 5 seconds. The lines it
+				wrote until then are still delivered.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		command_executed_total:  components.sources.internal_metrics.output.metrics.command_executed_total
+		events_in_total:         components.sources.internal_metrics.output.metrics.events_in_total
+		processed_bytes_total:   components.sources.internal_metrics.output.metrics.processed_bytes_total
+		processed_events_total:  components.sources.internal_metrics.output.metrics.processed_events_total
+		processing_errors_total: components.sources.internal_metrics.output.metrics.processing_errors_total
+	}
+}
@@ -15,6 +15,10 @@ components: sources: file: {
 	}
 
 	features: {
+		codecs: {
+			enabled:         true
+			default_framing: null
+		}
 		collect: {
 			checkpoint: enabled: true
 			from: {
@@ -398,7 +402,10 @@ components: sources: file: {
 			body: """
 				Each line is read until a new line delimiter (by default, `\n` i.e.
 				the `0xA` byte) or `EOF` is found. If needed, the default line
-				delimiter can be overriden via the `line_delimiter` option.
+				delimiter can be overriden via the `line_delimiter` option. Lines
+				are framed by the `line_delimiter` and `multiline` options only, so
+				the `framing` option isn't supported, while `decoding` parses each
+				line, once aggregated.
 				"""
 		}
 
@@ -554,5 +561,6 @@ components: sources: file: {
 		files_unwatched_total:         components.sources.internal_metrics.output.metrics.files_unwatched_total
 		fingerprint_read_errors_total: components.sources.internal_metrics.output.metrics.fingerprint_read_errors_total
 		glob_errors_total:             components.sources.internal_metrics.output.metrics.glob_errors_total
+		processing_errors_total:       components.sources.internal_metrics.output.metrics.processing_errors_total
 	}
 }
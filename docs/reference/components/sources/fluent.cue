@@ -0,0 +1,174 @@
+package metadata
+
+components: sources: fluent: {
+	_port: 24224
+
+	title: "Fluent"
+
+	description: """
+		Receives logs from Fluentd and Fluent Bit agents over the Forward
+		protocol.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "best_effort"
+		deployment_roles: ["aggregator"]
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		multiline: enabled: false
+		receive: {
+			from: {
+				service: services.fluent
+
+				interface: socket: {
+					api: {
+						title: "Forward protocol"
+						url:   urls.fluent_forward_protocol
+					}
+					direction: "incoming"
+					port:      _port
+					protocols: ["tcp", "unix"]
+					ssl: "optional"
+				}
+			}
+			receive_buffer_bytes: {
+				enabled:       true
+				relevant_when: "mode = `tcp`"
+			}
+			keepalive: enabled: true
+			tls: sources.socket.features.receive.tls
+		}
+	}
+
+	support: {
+		targets: sources.socket.support.targets
+
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		address: {
+			description:   "The address to listen for connections on, or `systemd#N` to use the Nth socket passed by systemd socket activation. If an address is used it _must_ include a port."
+			relevant_when: "mode = `tcp`"
+			required:      true
+			warnings: []
+			type: string: {
+				examples: ["0.0.0.0:\(_port)", "systemd", "systemd#3"]
+				syntax: "literal"
+			}
+		}
+		mode: {
+			description: "The type of socket to use."
+			required:    true
+			warnings: []
+			type: string: {
+				enum: {
+					tcp:  "TCP socket."
+					unix: "Unix domain stream socket."
+				}
+				syntax: "literal"
+			}
+		}
+		path: {
+			description:   "The unix socket path. *This should be an absolute path*."
+			relevant_when: "mode = `unix`"
+			required:      true
+			warnings: []
+			type: string: {
+				examples: ["/path/to/socket"]
+				syntax: "literal"
+			}
+		}
+	}
+
+	output: logs: line: {
+		description: "An entry of a Forward protocol message."
+		fields: {
+			host: fields._local_host
+			tag: {
+				description: "The tag of the message."
+				required:    true
+				type: string: {
+					examples: ["dummy.0"]
+					syntax: "literal"
+				}
+			}
+			timestamp: {
+				description: "The time of the entry."
+				required:    true
+				type: timestamp: {}
+			}
+			"*": {
+				description: "The fields of the record of the entry are inserted as root level fields."
+				required:    true
+				type: "*": {}
+			}
+		}
+	}
+
+	examples: [
+		{
+			_tag:       "dummy.0"
+			_timestamp: "2021-03-01T12:00:00Z"
+			title:      "Fluent Bit dummy input"
+			configuration: {}
+			input: """
+				```text
+				["\(_tag)", 1614600000, {"message": "dummy"}]
+				```
+				"""
+			output: log: {
+				host:      _values.remote_host
+				message:   "dummy"
+				tag:       _tag
+				timestamp: _timestamp
+			}
+		},
+	]
+
+	how_it_works: {
+		modes: {
+			title: "Message modes"
+			body:  """
+				The [Forward protocol](\(urls.fluent_forward_protocol)) messages
+				are decoded in any of their Message, Forward, PackedForward and
+				CompressedPackedForward modes, each of their entries becoming an
+				event. Both integer times and `EventTime` extensions are
+				supported.
+				"""
+		}
+		acknowledgements: {
+			title: "Acknowledgements"
+			body: """
+				When a message sets the `chunk` option, its acknowledgement is
+				sent back once its entries were accepted, so clients which
+				require acknowledgements can resend the messages that weren't.
+				Messages without entries are acknowledged as well.
+				"""
+		}
+		limits: {
+			title: "Limits"
+			body: """
+				Messages larger than 16 MiB are discarded, as are compressed
+				entries that decompress to more than 64 MiB.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		events_in_total:        components.sources.internal_metrics.output.metrics.events_in_total
+		processed_bytes_total:  components.sources.internal_metrics.output.metrics.processed_bytes_total
+		processed_events_total: components.sources.internal_metrics.output.metrics.processed_events_total
+	}
+}
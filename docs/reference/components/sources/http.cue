@@ -15,6 +15,10 @@ components: sources: http: {
 	}
 
 	features: {
+		codecs: {
+			enabled:         true
+			default_framing: "bytes"
+		}
 		multiline: enabled: false
 		receive: {
 			from: {
@@ -257,6 +261,7 @@ components: sources: http: {
 		events_in_total:         components.sources.internal_metrics.output.metrics.events_in_total
 		http_bad_requests_total: components.sources.internal_metrics.output.metrics.http_bad_requests_total
 		parse_errors_total:      components.sources.internal_metrics.output.metrics.parse_errors_total
+		processing_errors_total: components.sources.internal_metrics.output.metrics.processing_errors_total
 	}
 
 	how_it_works: {
@@ -166,6 +166,18 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _internal_metrics_tags
 		}
+		aggregate_events_recorded_total: {
+			description:       "The number of events recorded by the aggregate transform."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		aggregate_flushes_total: {
+			description:       "The number of flushes done by the aggregate transform."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		checkpoint_write_errors_total: {
 			description:       "The total number of errors writing checkpoints."
 			type:              "counter"
@@ -198,6 +210,15 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _internal_metrics_tags
 		}
+		command_executed_total: {
+			description:       "The total number of times a command has been executed."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				command: _command
+				success: _success
+			}
+		}
 		communication_errors_total: {
 			description:       "The total number of errors stemming from communication with the Docker daemon."
 			type:              "counter"
@@ -736,6 +757,11 @@ components: sources: internal_metrics: {
 			description: "Which collector this metric comes from."
 			required:    true
 		}
+		_command: {
+			description: "The command that was executed."
+			required:    true
+			examples: ["echo Hello World!"]
+		}
 		_component_kind: {
 			description: "The Vector component kind."
 			required:    true
@@ -764,6 +790,7 @@ components: sources: internal_metrics: {
 			description: "The type of the error"
 			required:    true
 			enum: {
+				"command_failed":              "The command could not be executed."
 				"field_missing":               "The event field was missing."
 				"invalid_metric":              "The metric was invalid."
 				"mapping_failed":              "The mapping failed."
@@ -808,5 +835,13 @@ components: sources: internal_metrics: {
 			description: "The path that produced the error."
 			required:    true
 		}
+		_success: {
+			description: "Whether the command exited successfully."
+			required:    true
+			enum: {
+				"true":  "The command exited with a zero status."
+				"false": "The command exited with a non-zero status, or was killed."
+			}
+		}
 	}
 }
@@ -4,6 +4,10 @@ components: sources: kafka: {
 	title: "Kafka"
 
 	features: {
+		codecs: {
+			enabled:         true
+			default_framing: "bytes"
+		}
 		collect: {
 			checkpoint: enabled: false
 			tls: {
@@ -34,6 +38,12 @@ components: sources: kafka: {
 	}
 
 	configuration: {
+		acknowledgements: {
+			common:      false
+			description: "Only commit a message's offset once every sink has acknowledged the events created from it. Once the events of a message could not be delivered, no further offsets of its partition are committed until Vector restarts, so that message and the ones after it are consumed again."
+			required:    false
+			type: bool: default: false
+		}
 		auto_offset_reset: {
 			common:      false
 			description: "If offsets for consumer group do not exist, set them using this strategy. [librdkafka documentation][urls.librdkafka_config] for `auto.offset.reset` option for explanation."
@@ -243,6 +253,7 @@ components: sources: kafka: {
 		events_failed_total:                  components.sources.internal_metrics.output.metrics.events_failed_total
 		processed_bytes_total:                components.sources.internal_metrics.output.metrics.processed_bytes_total
 		processed_events_total:               components.sources.internal_metrics.output.metrics.processed_events_total
+		processing_errors_total:              components.sources.internal_metrics.output.metrics.processing_errors_total
 	}
 
 	how_it_works: components._kafka.how_it_works
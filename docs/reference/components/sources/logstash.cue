@@ -0,0 +1,121 @@
+package metadata
+
+components: sources: logstash: {
+	_port: 5044
+
+	title: "Logstash"
+
+	description: """
+		Receives events from Beats agents, such as Filebeat and Winlogbeat,
+		and from Logstash over the Lumberjack v2 protocol.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "best_effort"
+		deployment_roles: ["aggregator"]
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		multiline: enabled: false
+		receive: {
+			from: {
+				service: services.logstash
+
+				interface: socket: {
+					api: {
+						title: "Lumberjack v2 protocol"
+						url:   urls.lumberjack_v2
+					}
+					direction: "incoming"
+					port:      _port
+					protocols: ["tcp"]
+					ssl: "optional"
+				}
+			}
+			receive_buffer_bytes: enabled: true
+			keepalive: enabled:            true
+			tls: sources.socket.features.receive.tls
+		}
+	}
+
+	support: {
+		targets: sources.socket.support.targets
+
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		address: {
+			description: "The address to listen for connections on, or `systemd#N` to use the Nth socket passed by systemd socket activation. If an address is used it _must_ include a port."
+			required:    true
+			warnings: []
+			type: string: {
+				examples: ["0.0.0.0:\(_port)", "systemd", "systemd#3"]
+				syntax: "literal"
+			}
+		}
+	}
+
+	output: logs: line: {
+		description: "An event sent by a Beats agent."
+		fields: {
+			host: {
+				description: "The `host` field set by the agent, or the address of the agent otherwise."
+				required:    true
+				type: string: {
+					examples: ["127.0.0.1"]
+					syntax: "literal"
+				}
+			}
+			timestamp: {
+				description: "The time of the event, from its `@timestamp` field."
+				required:    true
+				type: timestamp: {}
+			}
+			"*": {
+				description: "The fields of the event, including its `@metadata`, are inserted as root level fields."
+				required:    true
+				type: "*": {}
+			}
+		}
+	}
+
+	how_it_works: {
+		acknowledgements: {
+			title: "Acknowledgements"
+			body: """
+				Agents send their events in windows, whose size is announced
+				before their frames. Each window is acknowledged once all of its
+				events were accepted, so agents resend the windows that weren't.
+				Both JSON and key/value data frames are decoded, along with the
+				compressed frames grouping them.
+				"""
+		}
+		limits: {
+			title: "Limits"
+			body: """
+				Frames larger than 16 MiB are discarded, as are compressed frames
+				that decompress to more than 64 MiB. JSON frames that can't be
+				parsed are skipped, but still count towards their window. Windows
+				of more than 16384 events are acknowledged in parts of that many
+				events.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		events_in_total:        components.sources.internal_metrics.output.metrics.events_in_total
+		processed_bytes_total:  components.sources.internal_metrics.output.metrics.processed_bytes_total
+		processed_events_total: components.sources.internal_metrics.output.metrics.processed_events_total
+	}
+}
@@ -0,0 +1,119 @@
+package metadata
+
+components: sources: mqtt: {
+	title: "MQTT"
+
+	features: {
+		codecs: {
+			enabled:         true
+			default_framing: "bytes"
+		}
+		collect: {
+			checkpoint: enabled: false
+			tls:  components._mqtt.features.tls
+			from: components._mqtt.features.collect.from
+		}
+		multiline: enabled: false
+	}
+
+	classes: {
+		commonly_used: false
+		deployment_roles: ["aggregator"]
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	support: components._mqtt.support
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		client_id:       components._mqtt.configuration.client_id
+		host:            components._mqtt.configuration.host
+		keep_alive_secs: components._mqtt.configuration.keep_alive_secs
+		password:        components._mqtt.configuration.password
+		persistent_session: {
+			common:      false
+			description: "Ask the broker to keep the session of the client when it disconnects. The broker then keeps the subscriptions of the client, and the QoS 1 messages published while the client is away, and delivers them once it reconnects with the same `client_id`."
+			required:    false
+			warnings: []
+			type: bool: default: false
+		}
+		port: components._mqtt.configuration.port
+		qos:  components._mqtt.configuration.qos & {
+			description: "The maximum quality of service of the messages received from the subscribed topics."
+		}
+		topic_key: {
+			common:      false
+			description: "The log field name to use for the topic the message was published to. If unspecified, the topic is not added to the log event."
+			required:    false
+			warnings: []
+			type: string: {
+				default: null
+				examples: ["topic"]
+				syntax: "literal"
+			}
+		}
+		topics: {
+			description: "The topic filters to subscribe to. The `+` and `#` wildcards match one and any number of topic levels."
+			required:    true
+			warnings: []
+			type: array: items: type: string: {
+				examples: ["sensors/+/temperature", "devices/#"]
+				syntax: "literal"
+			}
+		}
+		user: components._mqtt.configuration.user
+	}
+
+	output: logs: record: {
+		description: "An individual MQTT message"
+		fields: {
+			message: {
+				description: "The payload of the MQTT message."
+				required:    true
+				type: string: {
+					examples: ["{\"temperature\": 21.5}"]
+					syntax: "literal"
+				}
+			}
+			timestamp: fields._current_timestamp & {
+				description: "The time the message was received by Vector."
+			}
+			topic: {
+				description: "The topic the message was published to, if `topic_key` is set."
+				required:    false
+				common:      true
+				type: string: {
+					default: null
+					examples: ["sensors/kitchen/temperature"]
+					syntax: "literal"
+				}
+			}
+		}
+	}
+
+	how_it_works: {
+		sessions: {
+			title: "Sessions"
+			body: """
+				Vector subscribes to the `topics` each time it connects to the broker
+				with a new session, and reconnects on its own when the connection is
+				lost. With `persistent_session` enabled, the broker keeps the
+				subscriptions and queues the QoS 1 messages published while Vector is
+				disconnected, so they are delivered once it reconnects.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		connection_errors_total: components.sources.internal_metrics.output.metrics.connection_errors_total
+		events_in_total:         components.sources.internal_metrics.output.metrics.events_in_total
+		processed_bytes_total:   components.sources.internal_metrics.output.metrics.processed_bytes_total
+		processed_events_total:  components.sources.internal_metrics.output.metrics.processed_events_total
+	}
+}
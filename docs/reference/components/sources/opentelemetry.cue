@@ -0,0 +1,256 @@
+package metadata
+
+components: sources: opentelemetry: {
+	_grpc_port: 4317
+	_http_port: 4318
+
+	title: "OpenTelemetry"
+
+	description: """
+		Receives logs and metrics from OpenTelemetry SDKs and collectors over
+		the OpenTelemetry protocol (OTLP).
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		deployment_roles: ["aggregator"]
+		development:   "beta"
+		egress_method: "batch"
+		stateful:      false
+	}
+
+	features: {
+		multiline: enabled: false
+		receive: {
+			from: {
+				service: services.opentelemetry
+
+				interface: socket: {
+					direction: "incoming"
+					port:      _grpc_port
+					protocols: ["http"]
+					ssl: "optional"
+				}
+			}
+
+			tls: enabled: false
+		}
+	}
+
+	support: {
+		targets: {
+			"aarch64-unknown-linux-gnu":      true
+			"aarch64-unknown-linux-musl":     true
+			"armv7-unknown-linux-gnueabihf":  true
+			"armv7-unknown-linux-musleabihf": true
+			"x86_64-apple-darwin":            true
+			"x86_64-pc-windows-msv":          true
+			"x86_64-unknown-linux-gnu":       true
+			"x86_64-unknown-linux-musl":      true
+		}
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		grpc: {
+			common:      true
+			description: "Configures the server receiving OTLP/gRPC requests."
+			required:    false
+			warnings: []
+			type: object: {
+				examples: []
+				options: {
+					address: {
+						common:      true
+						description: "The address to accept gRPC connections on. The address _must_ include a port."
+						required:    false
+						warnings: []
+						type: string: {
+							default: "0.0.0.0:\(_grpc_port)"
+							syntax:  "literal"
+						}
+					}
+					tls: configuration._tls_accept & {_args: {
+						can_enable:             true
+						can_verify_certificate: true
+						enabled_default:        false
+					}}
+				}
+			}
+		}
+		http: {
+			common:      true
+			description: "Configures the server receiving OTLP/HTTP requests on the `/v1/logs` and `/v1/metrics` paths."
+			required:    false
+			warnings: []
+			type: object: {
+				examples: []
+				options: {
+					address: {
+						common:      true
+						description: "The address to accept HTTP connections on. The address _must_ include a port."
+						required:    false
+						warnings: []
+						type: string: {
+							default: "0.0.0.0:\(_http_port)"
+							syntax:  "literal"
+						}
+					}
+					tls: configuration._tls_accept & {_args: {
+						can_enable:             true
+						can_verify_certificate: true
+						enabled_default:        false
+					}}
+				}
+			}
+		}
+	}
+
+	output: {
+		logs: record: {
+			description: "An OpenTelemetry log record"
+			fields: {
+				attributes: {
+					description: "The attributes of the log record."
+					required:    false
+					common:      true
+					type: object: {
+						examples: [{"http.status_code": 200}]
+						options: {}
+					}
+				}
+				flags: {
+					description: "The trace flags of the log record."
+					required:    false
+					common:      false
+					type: uint: {
+						default: null
+						examples: [1]
+						unit: null
+					}
+				}
+				message: {
+					description: "The body of the log record."
+					required:    true
+					type: "*": {}
+				}
+				observed_timestamp: {
+					description: "The time the log record was observed by the collection system."
+					required:    false
+					common:      false
+					type: timestamp: {}
+				}
+				resources: {
+					description: "The attributes of the resource producing the log record."
+					required:    false
+					common:      true
+					type: object: {
+						examples: [{"service.name": "checkout"}]
+						options: {}
+					}
+				}
+				scope: {
+					description: "The `name`, `version` and `attributes` of the instrumentation scope producing the log record."
+					required:    false
+					common:      false
+					type: object: {
+						examples: [{"name": "io.opentelemetry.example", "version": "1.0.0"}]
+						options: {}
+					}
+				}
+				severity_number: {
+					description: "The numerical severity of the log record, between 1 and 24."
+					required:    false
+					common:      true
+					type: uint: {
+						default: null
+						examples: [9]
+						unit: null
+					}
+				}
+				severity_text: {
+					description: "The severity of the log record, as known by its source."
+					required:    false
+					common:      true
+					type: string: {
+						default: null
+						examples: ["INFO"]
+						syntax: "literal"
+					}
+				}
+				span_id: {
+					description: "The hex encoded span id of the log record."
+					required:    false
+					common:      true
+					type: string: {
+						default: null
+						examples: ["eee19b7ec3c1b174"]
+						syntax: "literal"
+					}
+				}
+				timestamp: {
+					description: "The time of the log record, or the time it was observed when unset. Falls back to the time the record was received."
+					required:    true
+					type: timestamp: {}
+				}
+				trace_id: {
+					description: "The hex encoded trace id of the log record."
+					required:    false
+					common:      true
+					type: string: {
+						default: null
+						examples: ["5b8efff798038103d269b633813fc60c"]
+						syntax: "literal"
+					}
+				}
+			}
+		}
+		metrics: {
+			counter:   output._passthrough_counter
+			gauge:     output._passthrough_gauge
+			histogram: output._passthrough_histogram
+			summary:   output._passthrough_summary
+		}
+	}
+
+	how_it_works: {
+		protocol: {
+			title: "Protocol"
+			body:  """
+				This source implements the [OpenTelemetry protocol](\(urls.opentelemetry_otlp)),
+				receiving logs and metrics over gRPC, and over HTTP, encoded as
+				protobuf or as JSON when the `Content-Type` is `application/json`.
+				Requests are only acknowledged once their events were accepted, so
+				exporters retry the requests that failed.
+				"""
+		}
+		metric_types: {
+			title: "Metric type interpretation"
+			body: """
+				Data points are mapped to metrics tagged with their attributes,
+				along with the attributes of their resource prefixed by `resource.`,
+				and the name and version of their scope. Sums are counters when
+				they're monotonic, and gauges otherwise. Delta sums and histograms
+				are incremental, while cumulative ones are absolute. Exponential
+				histograms are converted to histograms, with the bounds of their
+				buckets computed from their scale. Their data points are
+				dropped when the scale is outside of the `-10` to `20` range,
+				or when the bounds of a bucket can't be represented.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		events_in_total:         components.sources.internal_metrics.output.metrics.events_in_total
+		http_bad_requests_total: components.sources.internal_metrics.output.metrics.http_bad_requests_total
+		processed_bytes_total:   components.sources.internal_metrics.output.metrics.processed_bytes_total
+		processed_events_total:  components.sources.internal_metrics.output.metrics.processed_events_total
+	}
+}
@@ -15,6 +15,10 @@ components: sources: socket: {
 	}
 
 	features: {
+		codecs: {
+			enabled:         true
+			default_framing: "newline_delimited"
+		}
 		multiline: enabled: false
 		receive: {
 			from: {
@@ -71,6 +75,21 @@ components: sources: socket: {
 				syntax: "literal"
 			}
 		}
+		codec: {
+			common:        false
+			description:   "How the received bytes are decoded into events."
+			relevant_when: "mode = `tcp` or `udp`"
+			required:      false
+			warnings: []
+			type: string: {
+				default: "lines"
+				enum: {
+					lines: "Each line is decoded as an event."
+					gelf:  "Each [GELF](\(urls.gelf)) message is decoded as an event. Messages are delimited by null bytes over TCP, and may be chunked and compressed with gzip or zlib over UDP."
+				}
+				syntax: "literal"
+			}
+		}
 		host_key: {
 			category:    "Context"
 			common:      false
@@ -158,6 +177,27 @@ components: sources: socket: {
 		},
 	]
 
+	how_it_works: {
+		gelf: {
+			title: "GELF"
+			body:  """
+				With the `gelf` codec, the `short_message` of a
+				[GELF](\(urls.gelf)) message becomes the `message` field, and its
+				`host` and `timestamp` become the `host` and `timestamp` fields.
+				Additional fields lose their `_` prefix, while `full_message`
+				and `level` are kept as is. Chunked messages which aren't
+				completed within 5 seconds are dropped.
+
+				The `framing` and `decoding` options only apply to the `lines`
+				codec, and are rejected along with the `gelf` codec. They aren't
+				supported in the `unix_datagram` and `unix_stream` modes either.
+				In the `tcp` mode, frames are limited to `max_length` unless the
+				`framing` sets a `max_length` of its own, and the `bytes` framing
+				is rejected, as connections would be buffered whole.
+				"""
+		}
+	}
+
 	telemetry: metrics: {
 		events_in_total:              components.sources.internal_metrics.output.metrics.events_in_total
 		connection_errors_total:      components.sources.internal_metrics.output.metrics.connection_errors_total
@@ -166,5 +206,6 @@ components: sources: socket: {
 		connection_failed_total:      components.sources.internal_metrics.output.metrics.connection_failed_total
 		connection_send_errors_total: components.sources.internal_metrics.output.metrics.connection_send_errors_total
 		connection_shutdown_total:    components.sources.internal_metrics.output.metrics.connection_shutdown_total
+		processing_errors_total:      components.sources.internal_metrics.output.metrics.processing_errors_total
 	}
 }
@@ -62,6 +62,12 @@ components: sources: splunk_hec: {
 	}
 
 	configuration: {
+		acknowledgements: {
+			common:      false
+			description: "Only respond to a request once every sink has acknowledged its events. Requests whose events could not be delivered are answered with a `500` response so that the client can retry them."
+			required:    false
+			type: bool: default: false
+		}
 		address: {
 			common:      true
 			description: "The address to accept connections on."
@@ -13,6 +13,10 @@ components: sources: stdin: {
 	}
 
 	features: {
+		codecs: {
+			enabled:         true
+			default_framing: "newline_delimited"
+		}
 		multiline: enabled: false
 		receive: {
 			from: {
@@ -110,6 +114,7 @@ components: sources: stdin: {
 		events_in_total:          components.sources.internal_metrics.output.metrics.events_in_total
 		processed_bytes_total:    components.sources.internal_metrics.output.metrics.processed_bytes_total
 		processed_events_total:   components.sources.internal_metrics.output.metrics.processed_events_total
+		processing_errors_total:  components.sources.internal_metrics.output.metrics.processing_errors_total
 		stdin_reads_failed_total: components.sources.internal_metrics.output.metrics.stdin_reads_failed_total
 	}
 }
@@ -63,6 +63,12 @@ components: sources: vector: {
 	}
 
 	configuration: {
+		acknowledgements: {
+			common:      false
+			description: "Only acknowledge a batch once every sink has delivered its events. Batches whose events could not be delivered are answered with an error so that the `vector` sink can retry them. Only used by version 2 of the protocol."
+			required:    false
+			type: bool: default: false
+		}
 		address: {
 			description: "The TCP address to listen for connections on, or `systemd#N to use the Nth socket passed by systemd socket activation. If an address is used it _must_ include a port."
 			required:    true
@@ -74,7 +80,7 @@ components: sources: vector: {
 		}
 		shutdown_timeout_secs: {
 			common:      false
-			description: "The timeout before a connection is forcefully closed during shutdown."
+			description: "The timeout before a connection is forcefully closed during shutdown. Only used by version 1 of the protocol."
 			required:    false
 			warnings: []
 			type: uint: {
@@ -82,6 +88,20 @@ components: sources: vector: {
 				unit:    "seconds"
 			}
 		}
+		version: {
+			common:      true
+			description: "The version of the protocol spoken with the upstream `vector` sinks, which must use the same version."
+			required:    false
+			warnings: []
+			type: string: {
+				default: "1"
+				enum: {
+					"1": "Events are streamed over TCP, without acknowledgement."
+					"2": "Batches of events are sent over gRPC, and acknowledged once accepted."
+				}
+				syntax: "literal"
+			}
+		}
 	}
 
 	output: {
@@ -116,15 +136,22 @@ components: sources: vector: {
 			title: "Communication Protocol"
 			body: """
 				Upstream Vector instances forward data to downstream Vector
-				instances via the TCP protocol.
+				instances via the TCP protocol. With version 2 of the protocol,
+				batches of events are sent to the `PushEvents` method of the gRPC
+				service defined in [Vector protobuf](\(urls.vector_proto)).
 				"""
 		}
 		message_acknowledgement: {
 			title: "Message Acknowledgement"
 			body: """
-				Currently, Vector does not perform any application level message
-				acknowledgement. While rare, this means the individual message
-				could be lost.
+				With version 1 of the protocol, Vector does not perform any
+				application level message acknowledgement. While rare, this means
+				the individual message could be lost.
+
+				With version 2, the response to each batch acknowledges it, and is
+				only sent once all of its events were accepted, or delivered by the
+				sinks if `acknowledgements` is enabled. Batches that aren't
+				acknowledged are retried by the `vector` sink.
 				"""
 		}
 
@@ -0,0 +1,81 @@
+package metadata
+
+components: transforms: aggregate: {
+	title: "Aggregate"
+
+	description: """
+		Aggregates multiple metric events into a single metric event per series over a
+		configurable interval.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "batch"
+		stateful:      true
+	}
+
+	features: {
+		reduce: {}
+	}
+
+	support: {
+		targets: {
+			"aarch64-unknown-linux-gnu":      true
+			"aarch64-unknown-linux-musl":     true
+			"armv7-unknown-linux-gnueabihf":  true
+			"armv7-unknown-linux-musleabihf": true
+			"x86_64-apple-darwin":            true
+			"x86_64-pc-windows-msv":          true
+			"x86_64-unknown-linux-gnu":       true
+			"x86_64-unknown-linux-musl":      true
+		}
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: {
+		interval_ms: {
+			common: true
+			description: """
+				The interval over which metrics are aggregated in milliseconds. Over this period metrics with the
+				same series data (name, namespace, tags, ...) will be aggregated.
+				"""
+			required: false
+			warnings: []
+			type: uint: {
+				default: 10000
+				unit:    "milliseconds"
+			}
+		}
+	}
+
+	input: {
+		logs: false
+		metrics: {
+			counter:      true
+			distribution: true
+			gauge:        true
+			histogram:    true
+			set:          true
+			summary:      true
+		}
+	}
+
+	how_it_works: {
+		aggregation_behavior: {
+			title: "Aggregation Behavior"
+			body: """
+				Incremental metrics are added together: counters are summed, sets are merged, and the samples of
+				distributions are combined. Absolute metrics are replaced, so only the most recent value of a gauge
+				is kept. At the end of each interval one metric per series is emitted and the state is reset.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		aggregate_events_recorded_total: components.sources.internal_metrics.output.metrics.aggregate_events_recorded_total
+		aggregate_flushes_total:         components.sources.internal_metrics.output.metrics.aggregate_flushes_total
+	}
+}
@@ -83,6 +83,13 @@ components: transforms: grok_parser: {
 				syntax: "literal"
 			}
 		}
+		reroute_dropped: {
+			common:      false
+			description: "If `true`, events that don't match the `pattern` are sent to the `<transform_name>.dropped` output instead of being passed through unparsed. They are annotated with a `dead_letter` object holding the `error` and the `component` that dropped them."
+			required:    false
+			warnings: []
+			type: bool: default: false
+		}
 		timezone: configuration._timezone
 		types:    configuration._types
 	}
@@ -80,6 +80,13 @@ components: transforms: json_parser: {
 			warnings: []
 			type: bool: default: false
 		}
+		reroute_dropped: {
+			common:      false
+			description: "If `true`, invalid events are sent to the `<transform_name>.dropped` output instead of being discarded. They are annotated with a `dead_letter` object holding the `error` and the `component` that dropped them. Requires `drop_invalid` to be `true`."
+			required:    false
+			warnings: []
+			type: bool: default: false
+		}
 		target_field: {
 			common:      false
 			description: "If this setting is present, the parsed JSON will be inserted into the log as a sub-object with this name. If a field with the same name already exists, the parser will fail and produce an error."
@@ -73,6 +73,20 @@ components: transforms: "remap": {
 				default: false
 			}
 		}
+		reroute_dropped: {
+			common:   false
+			required: false
+			description: """
+				Send the events dropped because of a runtime error to the `<transform_name>.dropped`
+				output, instead of discarding them. The changes made by the program are discarded, and
+				the events are annotated with a `dead_letter` object holding the `error` and the
+				`component` that dropped them, or `dead_letter_error` and `dead_letter_component` tags
+				for metrics. Requires `drop_on_error` to be enabled.
+				"""
+			type: bool: {
+				default: false
+			}
+		}
 	}
 
 	input: {
@@ -132,6 +146,23 @@ components: transforms: "remap": {
 				reference](\#(urls.vrl_runtime_errors)).
 				"""#
 		}
+		metric_values: {
+			title: "Metric Values"
+			body:  #"""
+				When processing metrics, the value of the metric is exposed in a field named after
+				its type, shaped like the metric's JSON representation. For example, `.counter.value`
+				holds the value of a counter, while `.aggregated_histogram.buckets` holds the buckets
+				of a histogram as an array of `{"upper_limit": ..., "count": ...}` objects. The
+				fields for the other types are absent.
+
+				Assigning to a field of the current type updates the value in place. Assigning a
+				complete value to the field of another type, such as
+				`.gauge = {"value": .counter.value}`, changes the type of the metric. Assignments
+				that don't match the shape of the value, such as a negative bucket count, fail at
+				runtime. When all of the transform's inputs produce metrics, assignments of the
+				wrong type are rejected when Vector starts instead.
+				"""#
+		}
 	}
 
 	telemetry: metrics: {
@@ -0,0 +1,107 @@
+package metadata
+
+components: transforms: throttle: {
+	title: "Throttle"
+
+	description: """
+		Rate limits one or more log streams to limit load on downstream services, or to enforce usage quotas on users.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      true
+	}
+
+	features: {
+		filter: {}
+	}
+
+	support: {
+		targets: {
+			"aarch64-unknown-linux-gnu":      true
+			"aarch64-unknown-linux-musl":     true
+			"armv7-unknown-linux-gnueabihf":  true
+			"armv7-unknown-linux-musleabihf": true
+			"x86_64-apple-darwin":            true
+			"x86_64-pc-windows-msv":          true
+			"x86_64-unknown-linux-gnu":       true
+			"x86_64-unknown-linux-musl":      true
+		}
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: {
+		key_field: {
+			common: false
+			description: """
+				The value to group events into separate buckets to be rate limited independently. If left
+				unspecified, or if the template fails to render, events share a single bucket.
+				"""
+			required: false
+			warnings: []
+			type: string: {
+				default: null
+				examples: ["{{ message }}", "{{ hostname }}"]
+				syntax: "template"
+			}
+		}
+		reroute_dropped: {
+			common: false
+			description: """
+				Send throttled events to the `<name>.dropped` output instead of discarding them, where `<name>` is
+				the name of this transform.
+				"""
+			required: false
+			warnings: []
+			type: bool: default: false
+		}
+		threshold: {
+			description: """
+				The number of events allowed for a given bucket per configured `window`.
+
+				Each unique key will have its own `threshold`.
+				"""
+			required: true
+			warnings: []
+			type: uint: {
+				examples: [100, 10000]
+				unit: null
+			}
+		}
+		window: {
+			description: """
+				The time frame in which the configured `threshold` is applied.
+				"""
+			required: true
+			warnings: []
+			type: float: {
+				examples: [1.0, 60.0, 86400.0]
+			}
+		}
+	}
+
+	input: {
+		logs:    true
+		metrics: null
+	}
+
+	how_it_works: {
+		rate_limiting: {
+			title: "Rate Limiting"
+			body: """
+				Each bucket is a token bucket holding up to `threshold` tokens, refilled continuously at a rate of
+				`threshold` tokens per `window`. Every event consumes one token from the bucket for its key, and events
+				arriving at an empty bucket are throttled. Buckets that have been idle for a whole `window` are
+				forgotten.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		events_discarded_total: components.sources.internal_metrics.output.metrics.events_discarded_total
+	}
+}
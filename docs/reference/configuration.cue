@@ -23,6 +23,112 @@ configuration: {
 			}
 		}
 
+		enrichment_tables: {
+			common: false
+			description: """
+				Configures tables of external data, such as CSV files, that the
+				[`remap` transform](\(urls.vector_remap_transform)) can look up records in, using the
+				`get_enrichment_table_record` and `find_enrichment_table_records` functions.
+				"""
+			required: false
+			warnings: []
+			type: object: {
+				examples: []
+				options: {
+					"*": {
+						description: "The name of the table, used to refer to it in VRL programs."
+						required:    true
+						warnings: []
+						type: object: {
+							examples: [
+								{
+									type: "file"
+									path: "/etc/vector/hosts.csv"
+									schema: {
+										port: "int"
+									}
+								},
+							]
+							options: {
+								type: {
+									description: "The type of the table."
+									required:    true
+									warnings: []
+									type: string: {
+										enum: {
+											file: "A table loaded from a CSV file."
+										}
+										syntax: "literal"
+									}
+								}
+								path: {
+									description: "The path of the CSV file to load."
+									required:    true
+									warnings: []
+									type: string: {
+										examples: ["/etc/vector/hosts.csv"]
+										syntax: "literal"
+									}
+								}
+								delimiter: {
+									common:      false
+									description: "The character separating the columns of the file."
+									required:    false
+									warnings: []
+									type: string: {
+										default: ","
+										examples: [",", ";", "\t"]
+										syntax: "literal"
+									}
+								}
+								include_headers: {
+									common: false
+									description: """
+										Whether the first row of the file holds the column names. If not, the
+										columns are named after their position, starting at `0`.
+										"""
+									required: false
+									warnings: []
+									type: bool: default: true
+								}
+								schema: {
+									common: true
+									description: """
+										The types to convert the columns to, keyed by column name. Columns
+										without a type are loaded as strings. The available types are `bool`,
+										`bytes`, `float`, `int`, `timestamp`, and `timestamp|FORMAT` using the
+										[time format specifiers](\(urls.chrono_time_formats)) from Rust's
+										`chrono` library.
+										"""
+									required: false
+									warnings: []
+									type: object: {
+										examples: [{port: "int", joined: "timestamp|%F"}]
+										options: {}
+									}
+								}
+								reload_interval_secs: {
+									common: false
+									description: """
+										How often to check the file for changes. The table is reloaded when the
+										file is modified, and keeps its previous data if the new file fails to
+										load. Set to `0` to never reload the file.
+										"""
+									required: false
+									warnings: []
+									type: uint: {
+										default: 60
+										examples: [60, 600]
+										unit: "seconds"
+									}
+								}
+							}
+						}
+					}
+				}
+			}
+		}
+
 		healthchecks: {
 			common: false
 			description: """
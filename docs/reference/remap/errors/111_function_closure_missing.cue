@@ -0,0 +1,25 @@
+package metadata
+
+remap: errors: "111": {
+	title:       "Function closure missing"
+	description: """
+		A [function call expression](\(urls.vrl_expressions)#\(remap.expressions.function_call.anchor)) invokes a
+		function that expects a closure, without passing one.
+		"""
+	resolution: """
+		Add a closure after the function arguments, as documented by the function.
+		"""
+
+	examples: [
+		{
+			"title": title
+			source: #"""
+				map_keys(.)
+				"""#
+			diff: #"""
+				-map_keys(.)
+				+map_keys(.) -> |key| { upcase(key) }
+				"""#
+		},
+	]
+}
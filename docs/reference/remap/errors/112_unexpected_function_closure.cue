@@ -0,0 +1,25 @@
+package metadata
+
+remap: errors: "112": {
+	title:       "Unexpected function closure"
+	description: """
+		A [function call expression](\(urls.vrl_expressions)#\(remap.expressions.function_call.anchor)) passes a
+		closure to a function that doesn't accept one.
+		"""
+	resolution: """
+		Remove the closure to adhere to the function's documented signature.
+		"""
+
+	examples: [
+		{
+			"title": title
+			source: #"""
+				upcase(.message) -> |value| { value }
+				"""#
+			diff: #"""
+				-upcase(.message) -> |value| { value }
+				+upcase(.message)
+				"""#
+		},
+	]
+}
@@ -0,0 +1,26 @@
+package metadata
+
+remap: errors: "113": {
+	title:       "Function closure arity mismatch"
+	description: """
+		A [function call expression](\(urls.vrl_expressions)#\(remap.expressions.function_call.anchor)) passes a
+		closure that defines a different number of variables than the function provides.
+		"""
+	resolution: """
+		Define the closure variables documented by the function. Variables you don't need can be prefixed with an
+		underscore (`_`).
+		"""
+
+	examples: [
+		{
+			"title": title
+			source: #"""
+				for_each(.) -> |value| { log(value) }
+				"""#
+			diff: #"""
+				-for_each(.) -> |value| { log(value) }
+				+for_each(.) -> |_key, value| { log(value) }
+				"""#
+		},
+	]
+}
@@ -0,0 +1,25 @@
+package metadata
+
+remap: errors: "114": {
+	title:       "Function closure return type mismatch"
+	description: """
+		A [function call expression](\(urls.vrl_expressions)#\(remap.expressions.function_call.anchor)) passes a
+		closure that resolves to a type the function doesn't expect.
+		"""
+	resolution: """
+		Ensure the closure resolves to the type documented by the function.
+		"""
+
+	examples: [
+		{
+			"title": title
+			source: #"""
+				filter(.) -> |_key, value| { value }
+				"""#
+			diff: #"""
+				-filter(.) -> |_key, value| { value }
+				+filter(.) -> |_key, value| { value != null }
+				"""#
+		},
+	]
+}
@@ -0,0 +1,26 @@
+package metadata
+
+remap: errors: "115": {
+	title:       "Enrichment table not found"
+	description: """
+		An enrichment table function call looks up records in a table that isn't configured in the
+		[`enrichment_tables`](\(urls.vector_configuration)#enrichment_tables) section.
+		"""
+	resolution: """
+		Use the name of a configured enrichment table, which are listed along with the error, or
+		configure the missing table.
+		"""
+
+	examples: [
+		{
+			"title": title
+			source: #"""
+				get_enrichment_table_record!("team", { "host": .host })
+				"""#
+			diff: #"""
+				-get_enrichment_table_record!("team", { "host": .host })
+				+get_enrichment_table_record!("teams", { "host": .host })
+				"""#
+		},
+	]
+}
@@ -0,0 +1,25 @@
+package metadata
+
+remap: errors: "116": {
+	title:       "Invalid enrichment table lookup"
+	description: """
+		An enrichment table function call looks up records with a condition the table can't be
+		indexed by, such as a field that isn't one of its columns.
+		"""
+	resolution: """
+		Only use the columns of the enrichment table as the fields of the condition.
+		"""
+
+	examples: [
+		{
+			"title": title
+			source: #"""
+				get_enrichment_table_record!("teams", { "hostname": .host })
+				"""#
+			diff: #"""
+				-get_enrichment_table_record!("teams", { "hostname": .host })
+				+get_enrichment_table_record!("teams", { "host": .host })
+				"""#
+		},
+	]
+}
@@ -0,0 +1,32 @@
+package metadata
+
+remap: errors: "642": {
+	title: "Invalid assignment type"
+	description: """
+		You've assigned a value to a path of an event with a fixed shape, such as a metric, and the value can never
+		have the type that path holds.
+		"""
+
+	rationale: """
+		The fields of a metric event have fixed types. For example, the `value` of a counter is always a number and
+		the `kind` of a metric is always a string. Assigning a value of another type to such a field would fail for
+		every metric the program runs on, so VRL rejects it when the program is compiled.
+		"""
+
+	resolution: """
+		Convert the value to the type of the path before assigning it, for example using `to_float` or `to_string`.
+		"""
+
+	examples: [
+		{
+			"title": "\(title)"
+			source: #"""
+				.counter.value = "5"
+				"""#
+			diff: #"""
+				- 	.counter.value = "5"
+				+# 	.counter.value = to_float!("5")
+				"""#
+		},
+	]
+}
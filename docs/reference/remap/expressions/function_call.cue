@@ -15,7 +15,7 @@ remap: expressions: function_call: {
 
 	grammar: {
 		source: """
-			function ~ abort? ~ "(" ~ arguments? ~ ")"
+			function ~ abort? ~ "(" ~ arguments? ~ ")" ~ closure?
 			"""
 		definitions: {
 			function: {
@@ -95,6 +95,20 @@ remap: expressions: function_call: {
 					}
 				}
 			}
+			closure: {
+				description: """
+					The `closure` is an optional block of expressions, preceded by `->` and a pipe-delimited list
+					of variables, that is called by the function:
+
+					```vrl
+					map_values(.) -> |value| { downcase!(value) }
+					```
+
+					Only functions that expect a closure accept one. The variables are provided by the function, and
+					are only visible within the closure block. Variables assigned within the block remain available
+					after the function call.
+					"""
+			}
 		}
 	}
 
@@ -120,5 +134,15 @@ remap: expressions: function_call: {
 				"""#
 			return: ["hello", "world!"]
 		},
+		{
+			title: "Function invocation with a closure"
+			source: #"""
+				map_keys({ "a": 1, "b": 2 }) -> |key| { upcase(key) }
+				"""#
+			return: {
+				"A": 1
+				"B": 2
+			}
+		},
 	]
 }
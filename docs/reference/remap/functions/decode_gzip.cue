@@ -0,0 +1,33 @@
+package metadata
+
+remap: functions: decode_gzip: {
+	category:    "Codec"
+	description: """
+		Decodes the `value` (a [Gzip](\(urls.gzip)) compressed string) into its original string.
+		Concatenated gzip members, as found in CloudWatch Logs subscription payloads, are decoded as a single value.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The [Gzip](\(urls.gzip)) data to decode."
+			required:    true
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: [
+		"`value` isn't a valid encoded Gzip string.",
+		"The decoded value is larger than 64 MiB.",
+	]
+	return: types: ["string"]
+
+	examples: [
+		{
+			title: "Decode Gzip data"
+			source: """
+				decode_gzip!(decode_base64!("H4sIAAAAAAACA6vML1XISCxLVSguTU5OLS5OK83JqVRISU3OT0lNUchNBQD7BGDaIAAAAA=="))
+				"""
+			return: "you have successfully decoded me"
+		},
+	]
+}
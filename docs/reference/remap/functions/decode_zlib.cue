@@ -0,0 +1,32 @@
+package metadata
+
+remap: functions: decode_zlib: {
+	category:    "Codec"
+	description: """
+		Decodes the `value` (a [Zlib](\(urls.zlib)) compressed string) into its original string.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The [Zlib](\(urls.zlib)) data to decode."
+			required:    true
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: [
+		"`value` isn't a valid encoded Zlib string.",
+		"The decoded value is larger than 64 MiB.",
+	]
+	return: types: ["string"]
+
+	examples: [
+		{
+			title: "Decode Zlib data"
+			source: """
+				decode_zlib!(decode_base64!("eJyrzC9VyEgsS1UoLk1OTi0uTivNyalUSElNzk9JTVHITQUAzLIMQQ=="))
+				"""
+			return: "you have successfully decoded me"
+		},
+	]
+}
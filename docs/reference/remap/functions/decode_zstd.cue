@@ -0,0 +1,32 @@
+package metadata
+
+remap: functions: decode_zstd: {
+	category:    "Codec"
+	description: """
+		Decodes the `value` (a [Zstandard](\(urls.zstd)) compressed string) into its original string.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The [Zstandard](\(urls.zstd)) data to decode."
+			required:    true
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: [
+		"`value` isn't a valid encoded Zstandard string.",
+		"The decoded value is larger than 64 MiB.",
+	]
+	return: types: ["string"]
+
+	examples: [
+		{
+			title: "Decode Zstandard data"
+			source: """
+				decode_zstd!(decode_base64!("KLUv/QRYAQEAeW91IGhhdmUgc3VjY2Vzc2Z1bGx5IGRlY29kZWQgbWXDEJVx"))
+				"""
+			return: "you have successfully decoded me"
+		},
+	]
+}
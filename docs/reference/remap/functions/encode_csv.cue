@@ -0,0 +1,59 @@
+package metadata
+
+remap: functions: encode_csv: {
+	category: "Codec"
+	description: """
+		Encodes the `value` into a single CSV formatted row, the inverse of [`parse_csv`](#parse_csv).
+
+		Arrays are encoded in order. Objects are encoded as the values of the fields named in `fields_ordering`, so
+		that every row has the same columns, or as all of their values in alphabetical order of their keys otherwise.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The array or object to encode."
+			required:    true
+			type: ["array", "object"]
+		},
+		{
+			name:        "fields_ordering"
+			description: """
+				The names of the object fields to encode, in the given order. Missing fields are encoded as empty
+				strings.
+				"""
+			required: false
+			type: ["array"]
+		},
+		{
+			name:        "delimiter"
+			description: "The field delimiter to use when encoding. Must be a single-byte UTF-8 character."
+			required:    false
+			default:     ","
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: [
+		"`delimiter` isn't exactly one byte long.",
+		"`fields_ordering` contains a non-string element.",
+		"`value` contains an array or object field.",
+	]
+	return: types: ["string"]
+
+	examples: [
+		{
+			title: "Encode an array"
+			source: #"""
+				encode_csv!(["foo", 1, true, "foo \", bar"])
+				"""#
+			return: #"foo,1,true,"foo "", bar""#
+		},
+		{
+			title: "Encode an object with fields ordering"
+			source: #"""
+				encode_csv!({"host": "web-1", "port": 80, "team": "frontend"}, fields_ordering: ["team", "host"], delimiter: ";")
+				"""#
+			return: "frontend;web-1"
+		},
+	]
+}
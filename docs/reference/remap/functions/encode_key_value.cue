@@ -0,0 +1,77 @@
+package metadata
+
+remap: functions: encode_key_value: {
+	category: "Codec"
+	description: """
+		Encodes the `value` into key/value format, the inverse of [`parse_key_value`](#parse_key_value).
+
+		* Nested objects are flattened into dotted keys (`parent.child`) and arrays into indexed keys (`parent[0]`).
+		* Values containing whitespace, quotes or either delimiter are wrapped in `"`, with `"` and `\\` escaped
+		  using `\\`.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The object to encode."
+			required:    true
+			type: ["object"]
+		},
+		{
+			name:        "fields_ordering"
+			description: """
+				The names of the fields to encode first, in the given order. The remaining fields are encoded
+				afterwards, in alphabetical order.
+				"""
+			required: false
+			type: ["array"]
+		},
+		{
+			name:        "key_value_delimiter"
+			description: "The string that separates the key from the value."
+			required:    false
+			default:     "="
+			type: ["string"]
+		},
+		{
+			name:        "field_delimiter"
+			description: "The string that separates each key/value pair."
+			required:    false
+			default:     " "
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: [
+		"`fields_ordering` contains a non-string element.",
+	]
+	return: types: ["string"]
+
+	examples: [
+		{
+			title: "Encode with default delimiters"
+			source: #"""
+				encode_key_value({"ts": "2021-06-05T17:20:00Z", "msg": "This is a message", "lvl": "info"})
+				"""#
+			return: #"lvl=info msg="This is a message" ts=2021-06-05T17:20:00Z"#
+		},
+		{
+			title: "Encode with custom delimiters and fields ordering"
+			source: #"""
+				encode_key_value!(
+					{"msg": "hello", "lvl": "info", "log_id": 12345},
+					fields_ordering: ["msg", "lvl"],
+					key_value_delimiter: ":",
+					field_delimiter: ","
+				)
+				"""#
+			return: "msg:hello,lvl:info,log_id:12345"
+		},
+		{
+			title: "Encode nested fields"
+			source: #"""
+				encode_key_value({"agent": {"name": "vector"}, "tags": ["a", "b"]})
+				"""#
+			return: "agent.name=vector tags[0]=a tags[1]=b"
+		},
+	]
+}
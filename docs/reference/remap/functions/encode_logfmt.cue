@@ -0,0 +1,49 @@
+package metadata
+
+remap: functions: encode_logfmt: {
+	category: "Codec"
+	description: """
+		Encodes the `value` to [logfmt](\(urls.logfmt)), the inverse of [`parse_logfmt`](#parse_logfmt).
+
+		This is an alias of [`encode_key_value`](#encode_key_value) using `=` and ` ` as delimiters.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The object to encode."
+			required:    true
+			type: ["object"]
+		},
+		{
+			name:        "fields_ordering"
+			description: """
+				The names of the fields to encode first, in the given order. The remaining fields are encoded
+				afterwards, in alphabetical order.
+				"""
+			required: false
+			type: ["array"]
+		},
+	]
+	internal_failure_reasons: [
+		"`fields_ordering` contains a non-string element.",
+	]
+	return: types: ["string"]
+
+	examples: [
+		{
+			title: "Encode to logfmt"
+			source: #"""
+				encode_logfmt({"ts": "2021-06-05T17:20:00Z", "msg": "This is a message", "lvl": "info"})
+				"""#
+			return: #"lvl=info msg="This is a message" ts=2021-06-05T17:20:00Z"#
+		},
+		{
+			title: "Encode to logfmt with fields ordering"
+			source: #"""
+				encode_logfmt!({"ts": "2021-06-05T17:20:00Z", "msg": "This is a message", "lvl": "info"}, ["ts", "lvl"])
+				"""#
+			return: #"ts=2021-06-05T17:20:00Z lvl=info msg="This is a message""#
+		},
+	]
+}
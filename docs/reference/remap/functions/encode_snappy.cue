@@ -0,0 +1,32 @@
+package metadata
+
+remap: functions: encode_snappy: {
+	category:    "Codec"
+	description: """
+		Encodes the `value` to [Snappy](\(urls.snappy)).
+		The value is encoded using the raw Snappy format, without the framing format.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The string to encode."
+			required:    true
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: [
+		"`value` is too large to be compressed.",
+	]
+	return: types: ["string"]
+
+	examples: [
+		{
+			title: "Encode to Snappy and back"
+			source: """
+				decode_snappy!(encode_snappy!("please encode me"))
+				"""
+			return: "please encode me"
+		},
+	]
+}
@@ -0,0 +1,38 @@
+package metadata
+
+remap: functions: encode_zstd: {
+	category:    "Codec"
+	description: """
+		Encodes the `value` to [Zstandard](\(urls.zstd)).
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The string to encode."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "compression_level"
+			description: "The compression level, from 1 (fastest) to 21 (smallest)."
+			required:    false
+			default:     3
+			type: ["integer"]
+		},
+	]
+	internal_failure_reasons: [
+		"`compression_level` isn't between 1 and 21.",
+	]
+	return: types: ["string"]
+
+	examples: [
+		{
+			title: "Encode to Zstandard and back"
+			source: """
+				decode_zstd!(encode_zstd!("please encode me", compression_level: 21))
+				"""
+			return: "please encode me"
+		},
+	]
+}
@@ -0,0 +1,45 @@
+package metadata
+
+remap: functions: filter: {
+	category: "Enumerate"
+	description: #"""
+		Filters the elements of the `value`, keeping the elements for which the closure resolves to `true`.
+
+		The closure receives the key (for objects) or index (for arrays) of the element, and its value.
+		"""#
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The array or object to filter."
+			required:    true
+			type: ["array", "object"]
+		},
+	]
+	internal_failure_reasons: [
+		"The closure doesn't resolve to a boolean.",
+	]
+	return: {
+		types: ["array", "object"]
+		rules: [
+			"The return type matches the `value` type.",
+		]
+	}
+
+	examples: [
+		{
+			title: "Filter an object"
+			source: #"""
+				filter({ "a": 1, "b": 2 }) -> |_key, value| { value > 1 }
+				"""#
+			return: b: 2
+		},
+		{
+			title: "Filter an array"
+			source: #"""
+				filter(["foo", "bar", "baz"]) -> |index, _value| { index != 1 }
+				"""#
+			return: ["foo", "baz"]
+		},
+	]
+}
@@ -0,0 +1,45 @@
+package metadata
+
+remap: functions: for_each: {
+	category: "Enumerate"
+	description: #"""
+		Iterates over the elements of the `value`, calling the closure for each element.
+
+		The closure receives the key (for objects) or index (for arrays) of the element, and its value. Variables
+		assigned in the closure remain available after the function call.
+		"""#
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The array or object to iterate over."
+			required:    true
+			type: ["array", "object"]
+		},
+	]
+	internal_failure_reasons: []
+	return: {
+		types: ["null"]
+	}
+
+	examples: [
+		{
+			title: "Count the values of an object"
+			source: #"""
+				count = 0
+				for_each({ "a": 1, "b": 2 }) -> |_key, value| { count = count + value }
+				count
+				"""#
+			return: 3
+		},
+		{
+			title: "Iterate over an array"
+			source: #"""
+				last = null
+				for_each(["foo", "bar"]) -> |index, _value| { last = index }
+				last
+				"""#
+			return: 1
+		},
+	]
+}
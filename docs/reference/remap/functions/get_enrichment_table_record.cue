@@ -0,0 +1,47 @@
+package metadata
+
+remap: functions: get_enrichment_table_record: {
+	category:    "Enrichment"
+	description: """
+		Returns the single record of the [enrichment table](\(urls.vector_configuration)#enrichment_tables)
+		`table` matching all the fields of `condition`. Records can only be looked up in tables
+		configured in the `enrichment_tables` section.
+
+		An index is created on the fields of `condition` when the program is compiled, so looking up
+		records doesn't require scanning the whole table.
+		"""
+	notices: [
+		"""
+			The `table` and `case_sensitive` arguments, and the field names of `condition`, must be
+			literals, as they're used to index the table when the program is compiled. The values of
+			the `condition` fields can be any expression.
+			""",
+	]
+
+	arguments: [
+		{
+			name:        "table"
+			description: "The name of the enrichment table to look up the record in."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "condition"
+			description: "The fields the record has to match, keyed by column name."
+			required:    true
+			type: ["object"]
+		},
+		{
+			name:        "case_sensitive"
+			description: "Whether string values are compared case sensitively."
+			required:    false
+			default:     true
+			type: ["boolean"]
+		},
+	]
+	internal_failure_reasons: [
+		"no record matches the `condition`",
+		"more than one record matches the `condition`",
+	]
+	return: types: ["object"]
+}
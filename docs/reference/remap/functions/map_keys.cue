@@ -0,0 +1,52 @@
+package metadata
+
+remap: functions: map_keys: {
+	category: "Enumerate"
+	description: #"""
+		Maps the keys of the `value`, replacing each key with the result of the closure.
+
+		The closure receives the key, and must resolve to a string.
+		"""#
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The object to map the keys of."
+			required:    true
+			type: ["object"]
+		},
+		{
+			name:        "recursive"
+			description: "Whether to also map the keys of nested objects, including objects in arrays."
+			required:    false
+			default:     false
+			type: ["boolean"]
+		},
+	]
+	internal_failure_reasons: [
+		"The closure doesn't resolve to a string.",
+	]
+	return: {
+		types: ["object"]
+	}
+
+	examples: [
+		{
+			title: "Upcase the keys of an object"
+			source: #"""
+				map_keys({ "foo": 1, "bar": 2 }) -> |key| { upcase(key) }
+				"""#
+			return: {
+				"FOO": 1
+				"BAR": 2
+			}
+		},
+		{
+			title: "Prefix nested keys"
+			source: #"""
+				map_keys({ "a": { "b": true } }, recursive: true) -> |key| { "x_" + key }
+				"""#
+			return: x_a: x_b: true
+		},
+	]
+}
@@ -0,0 +1,53 @@
+package metadata
+
+remap: functions: map_values: {
+	category: "Enumerate"
+	description: #"""
+		Maps the values of the `value`, replacing each value with the result of the closure.
+
+		The closure receives the value of each element.
+		"""#
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The array or object to map the values of."
+			required:    true
+			type: ["array", "object"]
+		},
+		{
+			name:        "recursive"
+			description: "Whether to map the values nested in arrays and objects, instead of the collections themselves."
+			required:    false
+			default:     false
+			type: ["boolean"]
+		},
+	]
+	internal_failure_reasons: []
+	return: {
+		types: ["array", "object"]
+		rules: [
+			"The return type matches the `value` type.",
+		]
+	}
+
+	examples: [
+		{
+			title: "Multiply the values of an object"
+			source: #"""
+				map_values({ "a": 1, "b": 2 }) -> |value| { value * 10 }
+				"""#
+			return: {
+				a: 10
+				b: 20
+			}
+		},
+		{
+			title: "Upcase nested values"
+			source: #"""
+				map_values(["foo", ["bar"]], recursive: true) -> |value| { upcase!(value) }
+				"""#
+			return: ["FOO", ["BAR"]]
+		},
+	]
+}
@@ -0,0 +1,51 @@
+package metadata
+
+remap: functions: parse_csv: {
+	category:    "Parse"
+	description: """
+		Parses a single CSV formatted row. Only the first row is parsed in case of multiline input value.
+		"""
+	notices: [
+		"""
+			All values are returned as strings. We recommend manually coercing values to desired types as you see fit.
+			""",
+	]
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The string to parse."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "delimiter"
+			description: "The field delimiter to use when parsing. Must be a single-byte UTF-8 character."
+			required:    false
+			default:     ","
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: [
+		"`value` isn't a valid CSV string, as defined by [RFC 4180](\(urls.rfc_4180))",
+		"`delimiter` isn't exactly one byte long",
+	]
+	return: types: ["array"]
+
+	examples: [
+		{
+			title: "Parse a single CSV formatted row"
+			source: #"""
+				parse_csv!("foo,bar,\"foo \"\", bar\"")
+				"""#
+			return: ["foo", "bar", #"foo ", bar"#]
+		},
+		{
+			title: "Parse a single CSV formatted row with custom delimiter"
+			source: #"""
+				parse_csv!("foo bar", delimiter: " ")
+				"""#
+			return: ["foo", "bar"]
+		},
+	]
+}
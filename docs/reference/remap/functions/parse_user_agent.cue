@@ -0,0 +1,45 @@
+package metadata
+
+remap: functions: parse_user_agent: {
+	category:    "Parse"
+	description: """
+		Parses the `value` as a user agent string, returning the browser, operating system, and device category it
+		identifies.
+		"""
+	notices: [
+		"""
+			Any part of the user agent that can't be identified is returned as `null`.
+			""",
+	]
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The user agent string to parse."
+			required:    true
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: []
+	return: types: ["object"]
+
+	examples: [
+		{
+			title: "Parse user agent"
+			source: #"""
+				parse_user_agent("Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/91.0.4472.124 Safari/537.36")
+				"""#
+			return: {
+				browser: {
+					family:  "Chrome"
+					version: "91.0.4472.124"
+				}
+				os: {
+					family:  "Mac OSX"
+					version: "10.15.7"
+				}
+				device: category: "pc"
+			}
+		},
+	]
+}
@@ -0,0 +1,107 @@
+package metadata
+
+remap: functions: parse_xml: {
+	category:    "Parse"
+	description: """
+		Parses the `value` as XML.
+
+		The root element is returned as an object keyed by its tag name. Elements containing only text are returned as
+		that text. All other elements are returned as objects holding their attributes, their child elements, and
+		their text. Child elements sharing the same tag name are grouped into an array.
+		"""
+	notices: [
+		"""
+			Valid XML must contain exactly one root node. Whitespace between elements is ignored.
+			""",
+	]
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The string representation of the XML document to parse."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "include_attr"
+			description: "Include XML tag attributes in the returned object."
+			required:    false
+			default:     true
+			type: ["boolean"]
+		},
+		{
+			name:        "attr_prefix"
+			description: "String prefix to use for XML tag attribute keys."
+			required:    false
+			default:     "@"
+			type: ["string"]
+		},
+		{
+			name:        "text_key"
+			description: "Key name to use for expanded text nodes."
+			required:    false
+			default:     "text"
+			type: ["string"]
+		},
+		{
+			name:        "always_use_text_key"
+			description: "Always return text nodes as `{\"<text_key>\": \"value\"}`."
+			required:    false
+			default:     false
+			type: ["boolean"]
+		},
+		{
+			name:        "parse_bool"
+			description: "Parse `true` and `false` as boolean."
+			required:    false
+			default:     true
+			type: ["boolean"]
+		},
+		{
+			name:        "parse_null"
+			description: "Parse `null` as null."
+			required:    false
+			default:     true
+			type: ["boolean"]
+		},
+		{
+			name:        "parse_number"
+			description: "Parse numbers as integers or floats."
+			required:    false
+			default:     true
+			type: ["boolean"]
+		},
+	]
+	internal_failure_reasons: [
+		"`value` isn't a valid XML document",
+		"`value` has elements nested more than 128 levels deep",
+	]
+	return: types: ["object"]
+
+	examples: [
+		{
+			title: "Parse XML"
+			source: #"""
+				parse_xml!(s'<book category="fiction"><title lang="en">Dune</title><year>1965</year></book>')
+				"""#
+			return: book: {
+				"@category": "fiction"
+				title: {
+					"@lang": "en"
+					text:    "Dune"
+				}
+				year: 1965
+			}
+		},
+		{
+			title: "Parse XML with custom keys"
+			source: #"""
+				parse_xml!(s'<title lang="en">Dune</title>', attr_prefix: "_", text_key: "value")
+				"""#
+			return: title: {
+				"_lang": "en"
+				value:   "Dune"
+			}
+		},
+	]
+}
@@ -0,0 +1,8 @@
+package metadata
+
+services: fluent: {
+	name:     "Fluent"
+	thing:    "a \(name) agent, such as Fluentd or Fluent Bit"
+	url:      urls.fluent
+	versions: null
+}
@@ -0,0 +1,8 @@
+package metadata
+
+services: logstash: {
+	name:     "Logstash"
+	thing:    "a Beats agent or a \(name) instance"
+	url:      urls.logstash
+	versions: null
+}
@@ -0,0 +1,10 @@
+package metadata
+
+services: mqtt: {
+	name:     "MQTT"
+	thing:    "an \(name) broker"
+	url:      urls.mqtt
+	versions: null
+
+	description: "[MQTT](\(urls.mqtt)) is a lightweight publish/subscribe messaging protocol, designed for constrained devices and unreliable networks and widely used by IoT devices. Vector connects to brokers such as [Mosquitto](\(urls.mosquitto)) with MQTT 3.1.1."
+}
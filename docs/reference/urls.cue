@@ -158,8 +158,11 @@ urls: {
 	ffi:                                                      "https://en.wikipedia.org/wiki/Foreign_function_interface"
 	file:                                                     "\(wikipedia)/wiki/Computer_file"
 	file_system:                                              "\(wikipedia)/wiki/File_system"
+	fluent:                                                   "https://www.fluentd.org/"
+	fluent_forward_protocol:                                  "https://github.com/fluent/fluentd/wiki/Forward-Protocol-Specification-v1"
 	freebsd:                                                  "https://www.freebsd.org/"
 	gcp_authentication:                                       "https://cloud.google.com/docs/authentication/"
+	gelf:                                                     "https://docs.graylog.org/en/latest/pages/gelf.html"
 	gcp_authentication_api_key:                               "https://cloud.google.com/docs/authentication/api-keys"
 	gcp_authentication_server_to_server:                      "https://cloud.google.com/docs/authentication/production"
 	gcp_authentication_service_account:                       "https://cloud.google.com/docs/authentication/production#obtaining_and_providing_service_account_credentials_manually"
@@ -249,6 +252,8 @@ urls: {
 	librdkafka_config:                                        "\(github)/edenhill/librdkafka/blob/master/CONFIGURATION.md"
 	logdna:                                                   "https://logdna.com/"
 	logfmt:                                                   "https://brandur.org/logfmt"
+	logstash:                                                 "https://www.elastic.co/logstash"
+	lumberjack_v2:                                            "\(github)/elastic/go-lumber"
 	loki:                                                     "https://grafana.com/oss/loki/"
 	loki_multi_tenancy:                                       "\(github)/grafana/loki/blob/master/docs/operations/multi-tenancy.md"
 	log_event_source:                                         "\(vector_repo)/blob/master/src/event/"
@@ -289,6 +294,9 @@ urls: {
 	mongodb:                                                  "https://www.mongodb.com"
 	mongodb_command_server_status:                            "https://docs.mongodb.com/manual/reference/command/serverStatus/"
 	mongodb_connection_string_uri_format:                     "https://docs.mongodb.com/manual/reference/connection-string/"
+	mosquitto:                                                "https://mosquitto.org/"
+	mqtt:                                                     "https://mqtt.org/"
+	mqtt_specification:                                       "https://docs.oasis-open.org/mqtt/mqtt/v3.1.1/mqtt-v3.1.1.html"
 	musl_builder_docker_image:                                "\(vector_repo)/blob/master/scripts/ci-docker-images/builder-x86_64-unknown-linux-musl/Dockerfile"
 	nats:                                                     "https://nats.io/"
 	new_bug_report:                                           "\(vector_repo)/issues/new?labels=type%3A+bug"
@@ -306,6 +314,8 @@ urls: {
 	nixos:                                                    "https://nixos.org/"
 	nixpkgs_9682:                                             "\(github)/NixOS/nixpkgs/issues/9682"
 	openssl:                                                  "https://www.openssl.org/"
+	opentelemetry:                                            "https://opentelemetry.io/"
+	opentelemetry_otlp:                                       "https://opentelemetry.io/docs/specs/otlp/"
 	order_of_ops:                                             "\(wikipedia)/wiki/Order_of_operations"
 	papertrail:                                               "https://www.papertrail.com/"
 	papertrail_syslog:                                        "https://help.papertrailapp.com/kb/how-it-works/http-api/#submitting-log-messages"
@@ -457,6 +467,7 @@ urls: {
 	vector_performance:                                       "\(vector_website)/#performance"
 	vector_privacy_policy:                                    "\(vector_repo)/blob/master/PRIVACY.md"
 	vector_programmable_transforms:                           "\(vector_website)/components/?functions%5B%5D=program"
+	vector_proto:                                             "\(vector_repo)/blob/master/proto/vector.proto"
 	vector_pull_requests:                                     "\(vector_repo)/pulls"
 	vector_receiving_sources:                                 "\(vector_website)/components/?functions%5B%5D=receive"
 	vector_regex_parser:                                      "\(vector_website)/docs/reference/configuration/transforms/regex_parser/"
@@ -478,6 +489,7 @@ urls: {
 	vector_sources:                                           "\(vector_website)/docs/reference/configuration/sources/"
 	vector_stars:                                             "\(vector_repo)/stargazers"
 	vector_stdin_source:                                      "\(vector_website)/docs/reference/configuration/sources/stdin/"
+	vector_syslog_source:                                     "\(vector_website)/docs/reference/configuration/sources/syslog/"
 	vector_systemd_file:                                      "\(vector_repo)/blob/master/distribution/systemd/vector.service"
 	vector_test_harness:                                      "\(vector_repo)-test-harness/"
 	vector_transform_aws_cloudwatch_logs_subscription_parser: "\(vector_website)/docs/reference/configuration/transforms/aws_cloudwatch_logs_subscription_parser"
@@ -0,0 +1,101 @@
+//! Control mutations, for changing the running topology and validating configs
+
+use async_trait::async_trait;
+use graphql_client::GraphQLQuery;
+
+/// PauseSourceMutation stops a source from forwarding events, until it's resumed
+#[derive(GraphQLQuery, Debug, Copy, Clone)]
+#[graphql(
+    schema_path = "graphql/schema.json",
+    query_path = "graphql/mutations/pause_source.graphql",
+    response_derives = "Debug"
+)]
+pub struct PauseSourceMutation;
+
+/// ResumeSourceMutation resumes a paused source
+#[derive(GraphQLQuery, Debug, Copy, Clone)]
+#[graphql(
+    schema_path = "graphql/schema.json",
+    query_path = "graphql/mutations/resume_source.graphql",
+    response_derives = "Debug"
+)]
+pub struct ResumeSourceMutation;
+
+/// ReloadConfigMutation reloads the config from disk, as sending SIGHUP to Vector does
+#[derive(GraphQLQuery, Debug, Copy, Clone)]
+#[graphql(
+    schema_path = "graphql/schema.json",
+    query_path = "graphql/mutations/reload_config.graphql",
+    response_derives = "Debug"
+)]
+pub struct ReloadConfigMutation;
+
+/// ValidateConfigMutation checks that a config can be loaded, without applying it
+#[derive(GraphQLQuery, Debug, Copy, Clone)]
+#[graphql(
+    schema_path = "graphql/schema.json",
+    query_path = "graphql/mutations/validate_config.graphql",
+    response_derives = "Debug"
+)]
+pub struct ValidateConfigMutation;
+
+/// Extension methods for control mutations
+#[async_trait]
+pub trait ControlMutationExt {
+    /// Executes a pause source mutation
+    async fn pause_source_mutation(&self, name: &str) -> crate::QueryResult<PauseSourceMutation>;
+
+    /// Executes a resume source mutation
+    async fn resume_source_mutation(&self, name: &str) -> crate::QueryResult<ResumeSourceMutation>;
+
+    /// Executes a reload config mutation
+    async fn reload_config_mutation(&self) -> crate::QueryResult<ReloadConfigMutation>;
+
+    /// Executes a validate config mutation, for a `config` in the given `format`
+    async fn validate_config_mutation(
+        &self,
+        config: &str,
+        format: validate_config_mutation::ConfigFormat,
+    ) -> crate::QueryResult<ValidateConfigMutation>;
+}
+
+#[async_trait]
+impl ControlMutationExt for crate::Client {
+    /// Executes a pause source mutation
+    async fn pause_source_mutation(&self, name: &str) -> crate::QueryResult<PauseSourceMutation> {
+        let request_body = PauseSourceMutation::build_query(pause_source_mutation::Variables {
+            name: name.to_owned(),
+        });
+        self.query::<PauseSourceMutation>(&request_body).await
+    }
+
+    /// Executes a resume source mutation
+    async fn resume_source_mutation(&self, name: &str) -> crate::QueryResult<ResumeSourceMutation> {
+        let request_body = ResumeSourceMutation::build_query(resume_source_mutation::Variables {
+            name: name.to_owned(),
+        });
+        self.query::<ResumeSourceMutation>(&request_body).await
+    }
+
+    /// Executes a reload config mutation
+    async fn reload_config_mutation(&self) -> crate::QueryResult<ReloadConfigMutation> {
+        self.query::<ReloadConfigMutation>(&ReloadConfigMutation::build_query(
+            reload_config_mutation::Variables,
+        ))
+        .await
+    }
+
+    /// Executes a validate config mutation, for a `config` in the given `format`
+    async fn validate_config_mutation(
+        &self,
+        config: &str,
+        format: validate_config_mutation::ConfigFormat,
+    ) -> crate::QueryResult<ValidateConfigMutation> {
+        let request_body =
+            ValidateConfigMutation::build_query(validate_config_mutation::Variables {
+                config: config.to_owned(),
+                format,
+            });
+        self.query::<ValidateConfigMutation>(&request_body).await
+    }
+}
@@ -1,11 +1,15 @@
-//! Queries, subscriptions, and extension methods for executing them
+//! Queries, mutations, subscriptions, and extension methods for executing them
 
 mod components;
+mod control;
 mod health;
 mod meta;
 mod metrics;
+mod tap;
 
 pub use self::meta::*;
 pub use components::*;
+pub use control::*;
 pub use health::*;
 pub use metrics::*;
+pub use tap::*;
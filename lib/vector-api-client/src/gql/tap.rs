@@ -0,0 +1,48 @@
+//! Tap subscriptions, for observing the events flowing through components
+
+use crate::BoxedSubscription;
+use graphql_client::GraphQLQuery;
+
+/// OutputEventsSubscription returns a sample of the events output by components
+#[derive(GraphQLQuery, Debug, Copy, Clone)]
+#[graphql(
+    schema_path = "graphql/schema.json",
+    query_path = "graphql/subscriptions/output_events.graphql",
+    response_derives = "Debug"
+)]
+pub struct OutputEventsSubscription;
+
+/// Extension methods for tap subscriptions
+pub trait TapSubscriptionExt {
+    /// Executes an output events subscription, sampling up to `limit` events per component
+    /// every `interval` milliseconds, optionally filtered by a VRL `filter` condition
+    fn output_events_subscription(
+        &self,
+        component_names: Vec<String>,
+        limit: i64,
+        interval: i64,
+        filter: Option<String>,
+    ) -> BoxedSubscription<OutputEventsSubscription>;
+}
+
+impl TapSubscriptionExt for crate::SubscriptionClient {
+    /// Executes an output events subscription, sampling up to `limit` events per component
+    /// every `interval` milliseconds, optionally filtered by a VRL `filter` condition
+    fn output_events_subscription(
+        &self,
+        component_names: Vec<String>,
+        limit: i64,
+        interval: i64,
+        filter: Option<String>,
+    ) -> BoxedSubscription<OutputEventsSubscription> {
+        let request_body =
+            OutputEventsSubscription::build_query(output_events_subscription::Variables {
+                component_names,
+                limit,
+                interval,
+                filter,
+            });
+
+        self.start::<OutputEventsSubscription>(&request_body)
+    }
+}
@@ -1,4 +1,4 @@
-use super::{repl, Error};
+use super::{repl, test_runner, Error};
 use std::collections::BTreeMap;
 use std::fs::File;
 use std::io::{self, Read};
@@ -27,6 +27,16 @@ pub struct Opts {
     /// this flag is equivalent to using `.` as the final expression.
     #[structopt(short = "o", long)]
     print_object: bool,
+
+    #[structopt(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Debug, StructOpt)]
+pub enum Command {
+    /// Run a directory of VRL test cases, and report the difference between the expected and
+    /// actual result of each failed case.
+    Test(test_runner::Opts),
 }
 
 pub fn cmd(opts: &Opts) -> exitcode::ExitCode {
@@ -40,6 +50,10 @@ pub fn cmd(opts: &Opts) -> exitcode::ExitCode {
 }
 
 fn run(opts: &Opts) -> Result<(), Error> {
+    if let Some(Command::Test(opts)) = &opts.command {
+        return test_runner::run(opts);
+    }
+
     // Run the REPL if no program or program file is specified
     if should_open_repl(opts) {
         // If an input file is provided, use that for the REPL objects, otherwise provide a
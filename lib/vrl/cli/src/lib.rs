@@ -1,6 +1,7 @@
 pub mod cmd;
 #[cfg(feature = "repl")]
 mod repl;
+pub mod test_runner;
 
 pub use cmd::{cmd, Opts};
 
@@ -20,4 +21,7 @@ pub enum Error {
 
     #[error("repl feature disabled, program input required")]
     ReplFeature,
+
+    #[error("{0} test case(s) failed")]
+    TestsFailed(usize),
 }
@@ -0,0 +1,136 @@
+use super::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use structopt::StructOpt;
+use vrl_tests::{Outcome, Test};
+
+#[derive(Debug, StructOpt)]
+pub struct Opts {
+    /// The directories containing the test cases to run, or individual test case files.
+    ///
+    /// Each test case is a `.vrl` file, using leading comments to define the input event
+    /// (`# object: ...`), the expected result or error (`# result: ...`), and optionally a file
+    /// containing the program to run (`# program: ...`), relative to the test case.
+    #[structopt(name = "PATH", parse(from_os_str), required = true)]
+    paths: Vec<PathBuf>,
+
+    /// Only run the test cases with a category or name containing this pattern.
+    #[structopt(short, long)]
+    pattern: Option<String>,
+
+    /// Stop running test cases after the first failure.
+    #[structopt(short, long)]
+    fail_early: bool,
+
+    /// Don't print the difference between the expected and actual result of failed test cases.
+    #[structopt(short, long)]
+    no_diff: bool,
+
+    /// Print the result of each test case.
+    #[structopt(short, long)]
+    verbose: bool,
+}
+
+pub(crate) fn run(opts: &Opts) -> Result<(), Error> {
+    let functions = stdlib::all();
+
+    let mut tests = vec![];
+    for path in &opts.paths {
+        // Test categories are relative to the given directory.
+        let root = if path.is_dir() {
+            path.as_path()
+        } else {
+            path.parent().unwrap_or(path)
+        };
+
+        for file in test_files(path)? {
+            tests.push(Test::from_path_in(root, &file));
+        }
+    }
+
+    let mut passed = 0;
+    let mut failed = 0;
+    let mut skipped = 0;
+
+    for mut test in tests {
+        let name = if test.category.is_empty() {
+            test.name.clone()
+        } else {
+            format!("{}/{}", test.category, test.name)
+        };
+
+        if let Some(pattern) = &opts.pattern {
+            if !name.contains(pattern) {
+                continue;
+            }
+        }
+
+        if let Some(err) = &test.error {
+            println!("{} ... INVALID\n{}", name, err);
+            failed += 1;
+        } else if test.skip {
+            println!("{} ... SKIPPED", name);
+            skipped += 1;
+            continue;
+        } else {
+            let outcome = test.run(&functions);
+
+            match &outcome {
+                Outcome::Passed { .. } => {
+                    println!("{} ... OK", name);
+                    passed += 1;
+                }
+                Outcome::Failed { stage, .. } => {
+                    println!("{} ... FAILED ({})", name, stage);
+                    failed += 1;
+
+                    if !opts.no_diff {
+                        println!("{}", outcome.diff().unwrap_or_default());
+                    }
+                }
+            }
+
+            if opts.verbose {
+                println!("{}", outcome.output());
+            }
+        }
+
+        if failed > 0 && opts.fail_early {
+            break;
+        }
+    }
+
+    println!(
+        "\n{} passed, {} failed, {} skipped",
+        passed, failed, skipped
+    );
+
+    if failed > 0 {
+        Err(Error::TestsFailed(failed))
+    } else {
+        Ok(())
+    }
+}
+
+/// Collect the test case files at the given path, recursing into directories.
+fn test_files(path: &Path) -> Result<Vec<PathBuf>, Error> {
+    if !path.is_dir() {
+        return Ok(vec![path.to_owned()]);
+    }
+
+    let mut entries = fs::read_dir(path)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<Vec<_>, _>>()?;
+    entries.sort();
+
+    let mut files = vec![];
+    for entry in entries {
+        if entry.is_dir() {
+            files.append(&mut test_files(&entry)?);
+        } else if entry.extension().map_or(false, |ext| ext == "vrl") {
+            files.push(entry);
+        }
+    }
+
+    Ok(files)
+}
@@ -1,5 +1,5 @@
 use crate::expression::*;
-use crate::{Function, Program, State, Value};
+use crate::{Function, Program, State, TypeDef, Value};
 use chrono::{TimeZone, Utc};
 use diagnostic::DiagnosticError;
 use ordered_float::NotNan;
@@ -325,12 +325,20 @@ impl<'a> Compiler<'a> {
             ident,
             abort_on_error,
             arguments,
+            closure,
         } = node.into_inner();
 
         let arguments = arguments
             .into_iter()
             .map(|node| Node::new(node.span(), self.compile_function_argument(node)))
-            .collect();
+            .collect::<Vec<_>>();
+
+        let closure = closure.map(|node| {
+            let span = node.span();
+            let variables = self.closure_variable_type_defs(&ident, &arguments);
+
+            Node::new(span, self.compile_function_closure(node, variables))
+        });
 
         if abort_on_error {
             self.fallible = true;
@@ -341,6 +349,7 @@ impl<'a> Compiler<'a> {
             ident,
             abort_on_error,
             arguments,
+            closure,
             self.fns,
             self.state,
         )
@@ -350,6 +359,142 @@ impl<'a> Compiler<'a> {
         })
     }
 
+    /// Get the type definitions of the closure variables of a function call.
+    ///
+    /// The variables are typed based on the argument holding the collection
+    /// the closure iterates over. If the function is unknown, or doesn't take a
+    /// closure, no type definitions are returned, and the function call itself
+    /// reports the error.
+    fn closure_variable_type_defs(
+        &self,
+        ident: &Node<ast::Ident>,
+        arguments: &[Node<FunctionArgument>],
+    ) -> Vec<TypeDef> {
+        let function = match self
+            .fns
+            .iter()
+            .find(|f| f.identifier() == ident.as_ref().as_ref())
+        {
+            Some(function) => function,
+            None => return vec![],
+        };
+
+        let definition = match function.closure() {
+            Some(definition) => definition,
+            None => return vec![],
+        };
+
+        // Find the argument passed to the closure parameter, using the same
+        // positional and keyword rules as the function call.
+        let mut index = 0;
+        let collection = arguments
+            .iter()
+            .find(|argument| {
+                let parameter = match argument.keyword() {
+                    None => {
+                        index += 1;
+                        function.parameters().get(index - 1)
+                    }
+                    Some(k) => function
+                        .parameters()
+                        .iter()
+                        .enumerate()
+                        .find(|(_, param)| param.keyword == k)
+                        .map(|(pos, param)| {
+                            if pos == index {
+                                index += 1;
+                            }
+
+                            param
+                        }),
+                };
+
+                parameter.map(|p| p.keyword) == Some(definition.parameter_keyword)
+            })
+            .map(|argument| argument.type_def(self.state))
+            .unwrap_or_default();
+
+        definition
+            .variables
+            .iter()
+            .map(|variable| variable.type_def(&collection))
+            .collect()
+    }
+
+    fn compile_function_closure(
+        &mut self,
+        node: Node<ast::FunctionClosure>,
+        type_defs: Vec<TypeDef>,
+    ) -> Closure {
+        let ast::FunctionClosure { variables, block } = node.into_inner();
+        let variables = variables
+            .into_iter()
+            .map(Node::into_inner)
+            .collect::<Vec<_>>();
+
+        // The closure variables are only visible within the closure block, so
+        // any existing variables they shadow are restored afterwards.
+        let shadowed = variables
+            .iter()
+            .map(|ident| (ident.clone(), self.state.variable(ident).cloned()))
+            .collect::<Vec<_>>();
+
+        // The closure may be called any number of times, including none, so
+        // the variables it assigns may also keep the type they had before.
+        let outer = self.state.variables().clone();
+
+        let mut type_defs = type_defs.into_iter();
+        for ident in &variables {
+            let details = assignment::Details {
+                type_def: type_defs.next().unwrap_or_default(),
+                value: None,
+            };
+
+            self.state.insert_variable(ident.clone(), details);
+        }
+
+        let block = self.compile_block(block);
+        let type_def = block.type_def(self.state);
+
+        for (ident, details) in shadowed {
+            match details {
+                Some(details) => self.state.insert_variable(ident, details),
+                None => self.state.remove_variable(&ident),
+            }
+        }
+
+        let assigned = self
+            .state
+            .variables()
+            .iter()
+            .filter(|(ident, details)| {
+                outer
+                    .get(ident)
+                    .map(|outer| (&outer.type_def, &outer.value))
+                    != Some((&details.type_def, &details.value))
+            })
+            .map(|(ident, details)| (ident.clone(), details.clone()))
+            .collect::<Vec<_>>();
+
+        for (ident, details) in assigned {
+            // Variables first assigned within the closure are undefined, and
+            // thus null, if it isn't called.
+            let type_def = match outer.get(&ident) {
+                Some(outer) => outer.type_def.clone(),
+                None => TypeDef::new().null(),
+            };
+
+            let details = assignment::Details {
+                type_def: type_def.merge(details.type_def),
+                value: None,
+            };
+
+            self.state.insert_variable(ident, details);
+        }
+
+        Closure::new(variables, block, type_def)
+    }
+
     fn compile_function_argument(&mut self, node: Node<ast::FunctionArgument>) -> FunctionArgument {
         let ast::FunctionArgument { ident, expr } = node.into_inner();
         let expr = Node::new(expr.span(), self.compile_expr(expr));
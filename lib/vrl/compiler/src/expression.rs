@@ -16,6 +16,7 @@ mod unary;
 mod variable;
 
 pub(crate) mod assignment;
+pub(crate) mod closure;
 pub(crate) mod container;
 pub(crate) mod function_call;
 pub(crate) mod literal;
@@ -25,6 +26,7 @@ pub(crate) mod query;
 pub use array::Array;
 pub use assignment::Assignment;
 pub use block::Block;
+pub use closure::Closure;
 pub use container::Container;
 pub use function_argument::FunctionArgument;
 pub use function_call::FunctionCall;
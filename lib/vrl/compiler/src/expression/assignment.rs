@@ -49,6 +49,8 @@ impl Assignment {
 
                 let expr = expr.into_inner();
                 let target = Target::try_from(target.into_inner())?;
+                target.check_type_def(state, &type_def, target_span, expr_span, span)?;
+
                 let value = match &expr {
                     Expr::Literal(v) => Some(v.to_value()),
                     _ => None,
@@ -106,6 +108,7 @@ impl Assignment {
                 // "err" target.
                 let ok = Target::try_from(ok.into_inner())?;
                 let type_def = type_def.infallible();
+                ok.check_type_def(state, &type_def, ok_span, expr_span, span)?;
                 let default = type_def.kind().default_value();
                 let value = match &expr {
                     Expr::Literal(v) => Some(v.to_value()),
@@ -118,6 +121,7 @@ impl Assignment {
                 // error message.
                 let err = Target::try_from(err.into_inner())?;
                 let type_def = TypeDef::new().bytes().add_null().infallible();
+                err.check_type_def(state, &type_def, err_span, expr_span, span)?;
 
                 err.insert_type_def(state, type_def, None);
 
@@ -186,6 +190,43 @@ pub enum Target {
 }
 
 impl Target {
+    /// Reject values that can never be assigned to a target with a fixed
+    /// shape, such as a metric.
+    fn check_type_def(
+        &self,
+        state: &State,
+        type_def: &TypeDef,
+        target_span: Span,
+        expr_span: Span,
+        span: Span,
+    ) -> Result<(), Error> {
+        let path = match self {
+            Target::External(path) => path,
+            _ => return Ok(()),
+        };
+
+        let expected = match (state.target_schema(), path) {
+            (None, _) => return Ok(()),
+            (Some(schema), None) => schema.clone(),
+            (Some(schema), Some(path)) => schema.at_path(path.clone()),
+        };
+
+        if expected.accepts(type_def) {
+            return Ok(());
+        }
+
+        Err(Error {
+            variant: ErrorVariant::InvalidType(
+                target_span,
+                expected.kind().to_string(),
+                type_def.kind().to_string(),
+            ),
+            span,
+            expr_span,
+            assignment_span: target_span,
+        })
+    }
+
     fn insert_type_def(&self, state: &mut State, type_def: TypeDef, value: Option<Value>) {
         use Target::*;
 
@@ -452,6 +493,9 @@ pub enum ErrorVariant {
 
     #[error("invalid assignment target")]
     InvalidTarget(Span),
+
+    #[error("invalid assignment type")]
+    InvalidType(Span, String, String),
 }
 
 impl fmt::Display for Error {
@@ -475,6 +519,7 @@ impl DiagnosticError for Error {
             UnhandledError(..) => 103,
             InfallibleAssignment(..) => 104,
             InvalidTarget(..) => 641,
+            InvalidType(..) => 642,
         }
     }
 
@@ -505,6 +550,13 @@ impl DiagnosticError for Error {
                 Label::primary("invalid assignment target", span),
                 Label::context("use one of variable or path", span),
             ],
+            InvalidType(target_span, expected, got) => vec![
+                Label::primary(format!("this path only accepts {}", expected), target_span),
+                Label::context(
+                    format!("but this expression resolves to {}", got),
+                    self.expr_span,
+                ),
+            ],
         }
     }
 
@@ -512,7 +564,9 @@ impl DiagnosticError for Error {
         use ErrorVariant::*;
 
         match &self.variant {
-            UnhandledError(..) | InfallibleAssignment(..) => vec![Note::SeeErrorDocs],
+            UnhandledError(..) | InfallibleAssignment(..) | InvalidType(..) => {
+                vec![Note::SeeErrorDocs]
+            }
             _ => vec![],
         }
     }
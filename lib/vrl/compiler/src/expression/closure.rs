@@ -0,0 +1,123 @@
+use crate::expression::{Block, Resolved};
+use crate::parser::Ident;
+use crate::{Context, Expression, State, TypeDef, Value};
+use std::fmt;
+
+/// A closure passed to a function call.
+///
+/// The function calling the closure provides a value for each of the closure
+/// variables, which are only visible within the closure block.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Closure {
+    variables: Vec<Ident>,
+    block: Block,
+
+    // The closure variables are only known to the compiler while compiling the
+    // block, so the type definition of the block is stored at that time.
+    type_def: TypeDef,
+}
+
+impl Closure {
+    pub(crate) fn new(variables: Vec<Ident>, block: Block, type_def: TypeDef) -> Self {
+        Self {
+            variables,
+            block,
+            type_def,
+        }
+    }
+
+    pub fn variables(&self) -> &[Ident] {
+        &self.variables
+    }
+
+    /// Resolve the closure block, with the closure variables bound to the
+    /// given values.
+    ///
+    /// Any existing variables shadowed by the closure variables are restored
+    /// once the block is resolved.
+    pub fn call(&self, ctx: &mut Context, values: Vec<Value>) -> Resolved {
+        let shadowed = self
+            .variables
+            .iter()
+            .map(|ident| (ident, ctx.state_mut().remove_variable(ident)))
+            .collect::<Vec<_>>();
+
+        for (ident, value) in self.variables.iter().zip(values) {
+            ctx.state_mut().insert_variable(ident.clone(), value);
+        }
+
+        let resolved = self.block.resolve(ctx);
+
+        for (ident, value) in shadowed {
+            match value {
+                Some(value) => ctx.state_mut().insert_variable(ident.clone(), value),
+                None => {
+                    ctx.state_mut().remove_variable(ident);
+                }
+            }
+        }
+
+        resolved
+    }
+}
+
+impl Expression for Closure {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        self.block.resolve(ctx)
+    }
+
+    fn type_def(&self, _: &State) -> TypeDef {
+        self.type_def.clone()
+    }
+}
+
+impl fmt::Display for Closure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("-> |")?;
+
+        let mut iter = self.variables.iter().peekable();
+        while let Some(ident) = iter.next() {
+            ident.fmt(f)?;
+
+            if iter.peek().is_some() {
+                f.write_str(", ")?;
+            }
+        }
+
+        f.write_str("| ")?;
+        self.block.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::expression::{Expr, Variable};
+    use crate::{state, value};
+
+    #[test]
+    fn call_restores_shadowed_variables() {
+        let key = Ident::new("key");
+        let value = Ident::new("value");
+
+        let block = Block::new(vec![Expr::Variable(Variable::new(
+            value.clone(),
+            &State::default(),
+        ))]);
+        let closure = Closure::new(vec![key.clone(), value.clone()], block, TypeDef::new());
+
+        let mut target = value!({});
+        let mut runtime = state::Runtime::default();
+        runtime.insert_variable(value.clone(), value!("outer"));
+        let mut ctx = Context::new(&mut target, &mut runtime);
+
+        assert_eq!(
+            closure
+                .call(&mut ctx, vec![value!("foo"), value!("inner")])
+                .unwrap(),
+            value!("inner")
+        );
+        assert_eq!(ctx.state().variable(&key), None);
+        assert_eq!(ctx.state().variable(&value), Some(&value!("outer")));
+    }
+}
@@ -1,4 +1,4 @@
-use crate::expression::{FunctionArgument, Noop};
+use crate::expression::{Closure, FunctionArgument, Noop};
 use crate::function::{ArgumentList, Parameter};
 use crate::parser::{Ident, Node};
 use crate::{value::Kind, Context, Expression, Function, Resolved, Span, State, TypeDef};
@@ -11,6 +11,7 @@ pub struct FunctionCall {
     abort_on_error: bool,
     expr: Box<dyn Expression>,
     maybe_fallible_arguments: bool,
+    maybe_fallible_closure: bool,
 
     // used for enhancing runtime error messages (using abort-instruction).
     //
@@ -22,6 +23,8 @@ pub struct FunctionCall {
     // This allows us to keep the arguments non-cloneable.
     arguments_fmt: Vec<String>,
     arguments_dbg: Vec<String>,
+    closure_fmt: Option<String>,
+    closure_dbg: Option<String>,
 
     // used for equality check
     ident: &'static str,
@@ -33,6 +36,7 @@ impl FunctionCall {
         ident: Node<Ident>,
         abort_on_error: bool,
         arguments: Vec<Node<FunctionArgument>>,
+        closure: Option<Node<Closure>>,
         funcs: &[Box<dyn Function>],
         state: &State,
     ) -> Result<Self, Error> {
@@ -88,6 +92,9 @@ impl FunctionCall {
             .map(|arg| format!("{:?}", arg))
             .collect::<Vec<_>>();
 
+        let closure_fmt = closure.as_ref().map(|closure| closure.to_string());
+        let closure_dbg = closure.as_ref().map(|closure| format!("{:?}", closure));
+
         let mut maybe_fallible_arguments = false;
         for node in arguments {
             let (argument_span, argument) = node.take();
@@ -144,7 +151,8 @@ impl FunctionCall {
                 });
             }
 
-            list.insert(parameter.keyword, argument.into_inner());
+            let expr_span = argument.span();
+            list.insert(parameter.keyword, argument.into_inner(), expr_span);
         }
 
         // Check missing required arguments.
@@ -162,6 +170,48 @@ impl FunctionCall {
                 })
             })?;
 
+        // Check the closure against the closure the function expects.
+        let mut maybe_fallible_closure = false;
+        match (function.closure(), closure) {
+            (Some(definition), Some(closure)) => {
+                let (closure_span, closure) = closure.take();
+
+                if closure.variables().len() != definition.variables.len() {
+                    return Err(Error::ClosureArityMismatch {
+                        closure_span,
+                        expected: definition.variables.len(),
+                        got: closure.variables().len(),
+                    });
+                }
+
+                // Similar to arguments, a closure block resolving to a kind
+                // that only partially matches the expected output kind, makes
+                // the function call fallible. The same goes for a block that
+                // can fail, since its error is returned by the function call.
+                let type_def = closure.type_def(state);
+                let output = definition.output();
+
+                if !output.intersects(type_def.kind()) {
+                    return Err(Error::ClosureReturnTypeMismatch {
+                        closure_span,
+                        expected: output,
+                        got: type_def.kind(),
+                    });
+                } else if !output.contains(type_def.kind()) || type_def.is_fallible() {
+                    maybe_fallible_closure = true;
+                }
+
+                list.insert_closure(closure);
+            }
+            (Some(_), None) => return Err(Error::MissingClosure { call_span }),
+            (None, Some(closure)) => {
+                return Err(Error::UnexpectedClosure {
+                    closure_span: closure.span(),
+                })
+            }
+            (None, None) => {}
+        }
+
         let expr = function
             .compile(list)
             .map_err(|error| Error::Compilation { call_span, error })?;
@@ -169,7 +219,11 @@ impl FunctionCall {
         // Asking for an infallible function to abort on error makes no sense.
         // We consider this an error at compile-time, because it makes the
         // resulting program incorrectly convey this function call might fail.
-        if abort_on_error && !maybe_fallible_arguments && !expr.type_def(state).is_fallible() {
+        if abort_on_error
+            && !maybe_fallible_arguments
+            && !maybe_fallible_closure
+            && !expr.type_def(state).is_fallible()
+        {
             return Err(Error::AbortInfallible {
                 ident_span,
                 abort_span: Span::new(ident_span.end(), ident_span.end() + 1),
@@ -180,9 +234,12 @@ impl FunctionCall {
             abort_on_error,
             expr,
             maybe_fallible_arguments,
+            maybe_fallible_closure,
             span: call_span,
             arguments_fmt,
             arguments_dbg,
+            closure_fmt,
+            closure_dbg,
             ident: function.identifier(),
         })
     }
@@ -194,9 +251,12 @@ impl FunctionCall {
             abort_on_error: false,
             expr,
             maybe_fallible_arguments: false,
+            maybe_fallible_closure: false,
             span: Span::default(),
             arguments_fmt: vec![],
             arguments_dbg: vec![],
+            closure_fmt: None,
+            closure_dbg: None,
             ident: "noop",
         }
     }
@@ -278,7 +338,7 @@ impl Expression for FunctionCall {
         // For the second event, only the `slice` function succeeds.
         // For the third event, both functions fail.
         //
-        if self.maybe_fallible_arguments {
+        if self.maybe_fallible_arguments || self.maybe_fallible_closure {
             type_def.fallible = true;
         }
 
@@ -304,7 +364,14 @@ impl fmt::Display for FunctionCall {
             }
         }
 
-        f.write_str(")")
+        f.write_str(")")?;
+
+        if let Some(closure) = &self.closure_fmt {
+            f.write_str(" ")?;
+            f.write_str(closure)?;
+        }
+
+        Ok(())
     }
 }
 
@@ -324,7 +391,14 @@ impl fmt::Debug for FunctionCall {
             }
         }
 
-        f.write_str("))")
+        f.write_str(")")?;
+
+        if let Some(closure) = &self.closure_dbg {
+            f.write_str(" ")?;
+            f.write_str(closure)?;
+        }
+
+        f.write_str(")")
     }
 }
 
@@ -385,6 +459,26 @@ pub enum Error {
 
     #[error("fallible argument")]
     FallibleArgument { expr_span: Span },
+
+    #[error("missing function closure")]
+    MissingClosure { call_span: Span },
+
+    #[error("unexpected function closure")]
+    UnexpectedClosure { closure_span: Span },
+
+    #[error("wrong number of closure variables")]
+    ClosureArityMismatch {
+        closure_span: Span,
+        expected: usize,
+        got: usize,
+    },
+
+    #[error("invalid closure return type")]
+    ClosureReturnTypeMismatch {
+        closure_span: Span,
+        expected: Kind,
+        got: Kind,
+    },
 }
 
 impl DiagnosticError for Error {
@@ -400,6 +494,10 @@ impl DiagnosticError for Error {
             AbortInfallible { .. } => 620,
             InvalidArgumentKind { .. } => 110,
             FallibleArgument { .. } => 630,
+            MissingClosure { .. } => 111,
+            UnexpectedClosure { .. } => 112,
+            ClosureArityMismatch { .. } => 113,
+            ClosureReturnTypeMismatch { .. } => 114,
         }
     }
 
@@ -467,11 +565,15 @@ impl DiagnosticError for Error {
                 ),
             ],
 
+            // Labels without a span of their own, such as one pointing at an
+            // argument, point at the function call.
             Compilation { call_span, error } => error
                 .labels()
                 .into_iter()
                 .map(|mut label| {
-                    label.span = *call_span;
+                    if label.span == Span::default() {
+                        label.span = *call_span;
+                    }
                     label
                 })
                 .collect(),
@@ -545,6 +647,54 @@ impl DiagnosticError for Error {
                     expr_span,
                 ),
             ],
+
+            MissingClosure { call_span } => vec![
+                Label::primary("this function expects a closure", call_span),
+                Label::context(
+                    "add a closure after the function arguments: -> |...| { ... }",
+                    call_span,
+                ),
+            ],
+
+            UnexpectedClosure { closure_span } => vec![
+                Label::primary("unexpected closure", closure_span),
+                Label::context("this function doesn't accept a closure", closure_span),
+            ],
+
+            ClosureArityMismatch {
+                closure_span,
+                expected,
+                got,
+            } => {
+                let variable = |n: usize| if n == 1 { "variable" } else { "variables" };
+
+                vec![
+                    Label::primary(
+                        format!("this closure defines {} {}", got, variable(*got)),
+                        closure_span,
+                    ),
+                    Label::context(
+                        format!(
+                            "but the function calls it with {} {}",
+                            expected,
+                            variable(*expected)
+                        ),
+                        closure_span,
+                    ),
+                ]
+            }
+
+            ClosureReturnTypeMismatch {
+                closure_span,
+                expected,
+                got,
+            } => vec![
+                Label::primary(format!("this closure resolves to {}", got), closure_span),
+                Label::context(
+                    format!("but the function expects it to resolve to {}", expected),
+                    closure_span,
+                ),
+            ],
         }
     }
 
@@ -557,6 +707,13 @@ impl DiagnosticError for Error {
                 Urls::expression_docs_url("#arguments"),
             )],
             AbortInfallible { .. } | FallibleArgument { .. } => vec![Note::SeeErrorDocs],
+            MissingClosure { .. }
+            | UnexpectedClosure { .. }
+            | ClosureArityMismatch { .. }
+            | ClosureReturnTypeMismatch { .. } => vec![Note::SeeDocs(
+                "function closures".to_owned(),
+                Urls::expression_docs_url("#closures"),
+            )],
             InvalidArgumentKind {
                 function_ident,
                 abort_on_error,
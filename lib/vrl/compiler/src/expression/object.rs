@@ -12,6 +12,10 @@ impl Object {
     pub fn new(inner: BTreeMap<String, Expr>) -> Self {
         Self { inner }
     }
+
+    pub fn into_inner(self) -> BTreeMap<String, Expr> {
+        self.inner
+    }
 }
 
 impl Expression for Object {
@@ -1,9 +1,11 @@
-use crate::expression::{Expr, Expression, FunctionArgument, Literal, Query};
+use crate::expression::{
+    container::Variant, Closure, Container, Expr, Expression, FunctionArgument, Literal, Query,
+};
 use crate::parser::Node;
 use crate::value::Kind;
-use crate::{Span, Value};
+use crate::{Span, TypeDef, Value};
 use diagnostic::{DiagnosticError, Label};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fmt;
 
 pub type Compiled = Result<Box<dyn Expression>, Box<dyn DiagnosticError>>;
@@ -46,6 +48,14 @@ pub trait Function: Sync + fmt::Debug {
     fn parameters(&self) -> &'static [Parameter] {
         &[]
     }
+
+    /// The closure the function expects to receive, if any.
+    ///
+    /// This definition is used at compile-time to type the closure variables,
+    /// and to check the closure arity and return type.
+    fn closure(&self) -> Option<ClosureDefinition> {
+        None
+    }
 }
 
 // -----------------------------------------------------------------------------
@@ -88,8 +98,64 @@ impl Parameter {
 
 // -----------------------------------------------------------------------------
 
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct ClosureDefinition {
+    /// The keyword of the parameter holding the collection the closure
+    /// iterates over.
+    ///
+    /// The closure variables are typed based on the type definition of the
+    /// argument passed to this parameter.
+    pub parameter_keyword: &'static str,
+
+    /// The variables the closure receives, in order.
+    ///
+    /// If the closure defines a different number of variables, the compiler
+    /// will return a compile-time error.
+    pub variables: &'static [ClosureVariable],
+
+    /// The type kind(s) the closure block is expected to resolve to.
+    ///
+    /// If the block can never resolve to one of these kinds, the compiler will
+    /// return a compile-time error.
+    pub output: u16,
+}
+
+impl ClosureDefinition {
+    pub fn output(&self) -> Kind {
+        Kind::new(self.output)
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum ClosureVariable {
+    /// The key (for objects) or index (for arrays) of the element.
+    Key,
+
+    /// The value of the element.
+    Value,
+}
+
+impl ClosureVariable {
+    /// The type definition of the variable, given the type definition of the
+    /// collection the closure iterates over.
+    pub fn type_def(self, collection: &TypeDef) -> TypeDef {
+        match self {
+            ClosureVariable::Key if collection.is_array() => TypeDef::new().integer(),
+            ClosureVariable::Key if collection.is_object() => TypeDef::new().bytes(),
+            ClosureVariable::Key => TypeDef::new().bytes().add_integer(),
+            ClosureVariable::Value => collection.collection_element(),
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+
 #[derive(Debug, Default)]
-pub struct ArgumentList(HashMap<&'static str, Expr>);
+pub struct ArgumentList {
+    arguments: HashMap<&'static str, Expr>,
+    spans: HashMap<&'static str, Span>,
+    closure: Option<Closure>,
+}
 
 impl ArgumentList {
     pub fn optional(&mut self, keyword: &'static str) -> Option<Box<dyn Expression>> {
@@ -190,16 +256,64 @@ impl ArgumentList {
         Ok(required(self.optional_regex(keyword)?))
     }
 
+    /// Get the fields of an object literal argument.
+    ///
+    /// This allows functions to inspect the keys of the object at
+    /// compile-time, while resolving the values of the fields at runtime.
+    pub fn optional_object(
+        &mut self,
+        keyword: &'static str,
+    ) -> Result<Option<BTreeMap<String, Expr>>, Error> {
+        self.optional_expr(keyword)
+            .map(|expr| match expr {
+                Expr::Container(Container {
+                    variant: Variant::Object(object),
+                }) => Ok(object.into_inner()),
+                expr => Err(Error::UnexpectedExpression {
+                    keyword,
+                    expected: "object",
+                    expr,
+                }),
+            })
+            .transpose()
+    }
+
+    pub fn required_object(
+        &mut self,
+        keyword: &'static str,
+    ) -> Result<BTreeMap<String, Expr>, Error> {
+        Ok(required(self.optional_object(keyword)?))
+    }
+
+    /// Get the span of an argument in the program source, so that errors
+    /// about it can point at the argument rather than the whole call.
+    pub fn span(&self, keyword: &'static str) -> Option<Span> {
+        self.spans.get(keyword).copied()
+    }
+
+    pub fn optional_closure(&mut self) -> Option<Closure> {
+        self.closure.take()
+    }
+
+    pub fn required_closure(&mut self) -> Closure {
+        required(self.optional_closure())
+    }
+
     pub(crate) fn keywords(&self) -> Vec<&'static str> {
-        self.0.keys().copied().collect::<Vec<_>>()
+        self.arguments.keys().copied().collect::<Vec<_>>()
     }
 
-    pub(crate) fn insert(&mut self, k: &'static str, v: Expr) {
-        self.0.insert(k, v);
+    pub(crate) fn insert(&mut self, k: &'static str, v: Expr, span: Span) {
+        self.arguments.insert(k, v);
+        self.spans.insert(k, span);
+    }
+
+    pub(crate) fn insert_closure(&mut self, closure: Closure) {
+        self.closure = Some(closure);
     }
 
     fn optional_expr(&mut self, keyword: &'static str) -> Option<Expr> {
-        self.0.remove(keyword)
+        self.arguments.remove(keyword)
     }
 
     fn required_expr(&mut self, keyword: &'static str) -> Expr {
@@ -213,29 +327,33 @@ fn required<T>(argument: Option<T>) -> T {
 
 impl From<HashMap<&'static str, Value>> for ArgumentList {
     fn from(map: HashMap<&'static str, Value>) -> Self {
-        Self(
-            map.into_iter()
-                .map(|(k, v)| (k, v.into_expr()))
-                .collect::<HashMap<_, _>>(),
-        )
+        let arguments = map
+            .into_iter()
+            .map(|(k, v)| (k, v.into_expr()))
+            .collect::<HashMap<_, _>>();
+
+        Self {
+            arguments,
+            spans: HashMap::default(),
+            closure: None,
+        }
     }
 }
 
 impl From<Vec<Node<FunctionArgument>>> for ArgumentList {
     fn from(arguments: Vec<Node<FunctionArgument>>) -> Self {
-        let arguments = arguments
-            .into_iter()
-            .map(|arg| {
-                let arg = arg.into_inner();
-                // TODO: find a better API design that doesn't require unwrapping.
-                let key = arg.parameter().expect("exists").keyword;
-                let expr = arg.into_inner();
+        let mut list = Self::default();
 
-                (key, expr)
-            })
-            .collect::<HashMap<_, _>>();
+        for arg in arguments {
+            let arg = arg.into_inner();
+            // TODO: find a better API design that doesn't require unwrapping.
+            let key = arg.parameter().expect("exists").keyword;
+            let span = arg.span();
+
+            list.insert(key, arg.into_inner(), span);
+        }
 
-        Self(arguments)
+        list
     }
 }
 
@@ -1,5 +1,5 @@
 use crate::expression::assignment;
-use crate::{parser::ast::Ident, Value};
+use crate::{parser::ast::Ident, TypeDef, Value};
 use std::collections::HashMap;
 
 /// The state held by the compiler.
@@ -14,6 +14,10 @@ pub struct Compiler {
     // stored internal variable type definitions
     variables: HashMap<Ident, assignment::Details>,
 
+    // the fixed shape of the external target, if any, which values assigned
+    // to the target are checked against
+    target_schema: Option<TypeDef>,
+
     /// On request, the compiler can store its state in this field, which can
     /// later be used to revert the compiler state to the previously stored
     /// state.
@@ -28,6 +32,16 @@ pub struct Compiler {
 }
 
 impl Compiler {
+    /// Create a compiler state for a target whose fields have a fixed type,
+    /// such as a metric. Assignments to those fields are then checked against
+    /// their type at compile time.
+    pub fn new_with_target_schema(schema: TypeDef) -> Self {
+        Self {
+            target_schema: Some(schema),
+            ..Default::default()
+        }
+    }
+
     pub(crate) fn variable(&self, ident: &Ident) -> Option<&assignment::Details> {
         self.variables.get(ident)
     }
@@ -36,6 +50,14 @@ impl Compiler {
         self.variables.insert(ident, details);
     }
 
+    pub(crate) fn remove_variable(&mut self, ident: &Ident) {
+        self.variables.remove(ident);
+    }
+
+    pub(crate) fn variables(&self) -> &HashMap<Ident, assignment::Details> {
+        &self.variables
+    }
+
     pub(crate) fn target(&self) -> Option<&assignment::Details> {
         self.target.as_ref()
     }
@@ -44,16 +66,22 @@ impl Compiler {
         self.target = Some(details);
     }
 
+    pub(crate) fn target_schema(&self) -> Option<&TypeDef> {
+        self.target_schema.as_ref()
+    }
+
     /// Take a snapshot of the current state of the compiler.
     ///
     /// This overwrites any existing snapshot currently stored.
     pub(crate) fn snapshot(&mut self) {
         let target = self.target.clone();
         let variables = self.variables.clone();
+        let target_schema = self.target_schema.clone();
 
         let snapshot = Self {
             target,
             variables,
+            target_schema,
             snapshot: None,
         };
 
@@ -87,4 +115,8 @@ impl Runtime {
     pub(crate) fn insert_variable(&mut self, ident: Ident, value: Value) {
         self.variables.insert(ident, value);
     }
+
+    pub(crate) fn remove_variable(&mut self, ident: &Ident) -> Option<Value> {
+        self.variables.remove(ident)
+    }
 }
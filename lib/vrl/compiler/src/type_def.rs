@@ -280,6 +280,18 @@ impl KindInfo {
         info.at_path(iter.collect())
     }
 
+    /// Returns `true` if a value described by `other` may be valid where a
+    /// value described by this kind info is expected. Unknown kinds are always
+    /// accepted, since they can only be checked at runtime.
+    fn accepts(&self, other: &Self) -> bool {
+        match (self, other) {
+            (KindInfo::Known(lhs), KindInfo::Known(rhs)) => {
+                rhs.iter().any(|rhs| lhs.iter().any(|lhs| lhs.accepts(rhs)))
+            }
+            _ => true,
+        }
+    }
+
     fn merge(self, rhs: Self, shallow: bool, overwrite: bool) -> Self {
         use KindInfo::*;
 
@@ -436,6 +448,27 @@ impl TypeKind {
             Object(_) => Kind::Object,
         }
     }
+
+    /// Returns `true` if a value of kind `other` may be valid where this kind
+    /// is expected. Collections are compared element by element, for the
+    /// fields and indices known on both sides.
+    fn accepts(&self, other: &Self) -> bool {
+        use TypeKind::*;
+
+        match (self, other) {
+            (Array(lhs), Array(rhs)) => rhs.iter().all(|(index, rhs)| {
+                lhs.get(index)
+                    .or_else(|| lhs.get(&Index::Any))
+                    .map_or(true, |lhs| lhs.accepts(rhs))
+            }),
+            (Object(lhs), Object(rhs)) => rhs.iter().all(|(field, rhs)| {
+                lhs.get(field)
+                    .or_else(|| lhs.get(&Field::Any))
+                    .map_or(true, |lhs| lhs.accepts(rhs))
+            }),
+            (lhs, rhs) => lhs.to_kind() == rhs.to_kind(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq, PartialOrd, Ord)]
@@ -521,6 +554,45 @@ impl TypeDef {
         Self { fallible, kind }
     }
 
+    /// Get the type definition of the elements stored in the array or object
+    /// kinds of this type definition.
+    ///
+    /// This is used to type the variables of a closure iterating over a
+    /// collection. If any of the elements is of an unknown type, or there are
+    /// no collection kinds to iterate over, the element type is unknown.
+    pub fn collection_element(&self) -> TypeDef {
+        let set = match &self.kind {
+            KindInfo::Unknown => return TypeDef::new(),
+            KindInfo::Known(set) => set,
+        };
+
+        let mut elements = set
+            .iter()
+            .flat_map(|kind| match kind {
+                TypeKind::Array(map) => map.values().cloned().collect::<Vec<_>>(),
+                TypeKind::Object(map) => map.values().cloned().collect(),
+                _ => vec![],
+            })
+            .peekable();
+
+        if elements.peek().is_none() {
+            return TypeDef::new();
+        }
+
+        let kind = elements.try_fold(
+            KindInfo::Known(BTreeSet::default()),
+            |acc, kind| match kind {
+                KindInfo::Unknown => None,
+                kind => Some(acc.merge(kind, false, false)),
+            },
+        );
+
+        Self {
+            fallible: false,
+            kind: kind.unwrap_or(KindInfo::Unknown),
+        }
+    }
+
     pub fn kind(&self) -> Kind {
         match &self.kind {
             KindInfo::Unknown => Kind::all(),
@@ -872,6 +944,16 @@ impl TypeDef {
         !self.is_fallible()
     }
 
+    /// Returns `true` if a value described by `other` may be assigned where a
+    /// value described by this type definition is expected.
+    ///
+    /// This is used to check assignments to targets with a fixed shape, such as
+    /// metrics. Only assignments that can never succeed are rejected, anything
+    /// else is left to be validated by the target at runtime.
+    pub fn accepts(&self, other: &Self) -> bool {
+        self.kind.accepts(&other.kind)
+    }
+
     /// Set the type definition to be fallible if its kind is not contained
     /// within the provided kind.
     pub fn fallible_unless(mut self, kind: impl Into<Kind>) -> Self {
@@ -1046,4 +1128,83 @@ mod tests {
             }
         }
     }
+
+    mod type_def {
+        use super::*;
+
+        #[test]
+        fn collection_element() {
+            struct TestCase {
+                type_def: TypeDef,
+                want: TypeDef,
+            }
+
+            let cases: Vec<TestCase> = vec![
+                // unknown collection
+                TestCase {
+                    type_def: TypeDef::new(),
+                    want: TypeDef::new(),
+                },
+                // no collection
+                TestCase {
+                    type_def: TypeDef::new().bytes(),
+                    want: TypeDef::new(),
+                },
+                // array elements
+                TestCase {
+                    type_def: TypeDef::new()
+                        .array(vec![TypeDef::new().integer(), TypeDef::new().bytes()]),
+                    want: TypeDef::new().integer().add_bytes(),
+                },
+                // object and array elements
+                TestCase {
+                    type_def: TypeDef::new()
+                        .object::<&'static str, TypeDef>(map! { "foo": TypeDef::new().float() })
+                        .add_array(vec![TypeDef::new().boolean()]),
+                    want: TypeDef::new().float().add_boolean(),
+                },
+                // unknown element
+                TestCase {
+                    type_def: TypeDef::new().array(vec![TypeDef::new().integer(), TypeDef::new()]),
+                    want: TypeDef::new(),
+                },
+                // fallibility is not carried over
+                TestCase {
+                    type_def: TypeDef::new().array(vec![TypeDef::new().null()]).fallible(),
+                    want: TypeDef::new().null(),
+                },
+            ];
+
+            for TestCase { type_def, want } in cases {
+                assert_eq!(type_def.collection_element(), want);
+            }
+        }
+
+        #[test]
+        fn accepts() {
+            let value = TypeDef::new().float().add_integer();
+            let counter =
+                TypeDef::new().object::<&'static str, TypeDef>(map! { "value": value.clone() });
+
+            // scalars
+            assert!(value.accepts(&TypeDef::new().integer()));
+            assert!(value.accepts(&TypeDef::new().bytes().add_float()));
+            assert!(!value.accepts(&TypeDef::new().bytes()));
+
+            // unknown kinds are left to the runtime
+            assert!(value.accepts(&TypeDef::new()));
+            assert!(TypeDef::new().accepts(&TypeDef::new().bytes()));
+
+            // object fields
+            assert!(counter.accepts(
+                &TypeDef::new()
+                    .object::<&'static str, TypeDef>(map! { "value": TypeDef::new().float() })
+            ));
+            assert!(!counter.accepts(
+                &TypeDef::new()
+                    .object::<&'static str, TypeDef>(map! { "value": TypeDef::new().bytes() })
+            ));
+            assert!(!counter.accepts(&TypeDef::new().array(vec![value])));
+        }
+    }
 }
@@ -25,7 +25,9 @@ pub use std::fmt;
 
 // commonly used function types
 
-pub use compiler::function::{ArgumentList, Compiled, Example, Parameter};
+pub use compiler::function::{
+    ArgumentList, ClosureDefinition, ClosureVariable, Compiled, Example, Parameter,
+};
 
 // commonly used macros
 pub use compiler::{bench_function, expr, func_args, map, test_function, test_type_def, value};
@@ -1011,13 +1011,14 @@ impl fmt::Debug for Field {
 
 /// A function call expression.
 ///
-/// It contains the identifier of the function, and any arguments passed into
-/// the function call.
+/// It contains the identifier of the function, any arguments passed into the
+/// function call, and an optional closure for functions that take one.
 #[derive(Clone, PartialEq)]
 pub struct FunctionCall {
     pub ident: Node<Ident>,
     pub abort_on_error: bool,
     pub arguments: Vec<Node<FunctionArgument>>,
+    pub closure: Option<Node<FunctionClosure>>,
 }
 
 impl fmt::Display for FunctionCall {
@@ -1034,7 +1035,13 @@ impl fmt::Display for FunctionCall {
             }
         }
 
-        f.write_str(")")
+        f.write_str(")")?;
+
+        if let Some(closure) = &self.closure {
+            write!(f, " {}", closure)?;
+        }
+
+        Ok(())
     }
 }
 
@@ -1054,7 +1061,13 @@ impl fmt::Debug for FunctionCall {
             }
         }
 
-        f.write_str("))")
+        f.write_str(")")?;
+
+        if let Some(closure) = &self.closure {
+            write!(f, " {:?}", closure)?;
+        }
+
+        f.write_str(")")
     }
 }
 
@@ -1090,6 +1103,52 @@ impl fmt::Debug for FunctionArgument {
     }
 }
 
+/// A closure passed to a function call.
+///
+/// The function calls the block of the closure with the listed variables bound
+/// to values of its choosing, such as the keys and values of an object:
+///
+///   for_each(.) -> |key, value| { ... }
+#[derive(Clone, PartialEq)]
+pub struct FunctionClosure {
+    pub variables: Vec<Node<Ident>>,
+    pub block: Node<Block>,
+}
+
+impl fmt::Display for FunctionClosure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("-> |")?;
+
+        let mut iter = self.variables.iter().peekable();
+        while let Some(variable) = iter.next() {
+            variable.fmt(f)?;
+
+            if iter.peek().is_some() {
+                f.write_str(", ")?;
+            }
+        }
+
+        write!(f, "| {}", self.block)
+    }
+}
+
+impl fmt::Debug for FunctionClosure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Closure(")?;
+
+        let mut iter = self.variables.iter().peekable();
+        while let Some(variable) = iter.next() {
+            variable.fmt(f)?;
+
+            if iter.peek().is_some() {
+                f.write_str(", ")?;
+            }
+        }
+
+        write!(f, ": {:?})", self.block)
+    }
+}
+
 // -----------------------------------------------------------------------------
 // unary
 // -----------------------------------------------------------------------------
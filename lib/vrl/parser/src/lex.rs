@@ -265,6 +265,7 @@ pub enum Token<S> {
     MergeEquals,
     Bang,
     Question,
+    Arrow,
 
     /// The {L,R}Query token is an "instruction" token. It does not represent
     /// any character in the source, instead it represents the start or end of a
@@ -345,6 +346,7 @@ impl<S> Token<S> {
             MergeEquals => MergeEquals,
             Bang => Bang,
             Question => Question,
+            Arrow => Arrow,
 
             LQuery => LQuery,
             RQuery => RQuery,
@@ -398,6 +400,7 @@ where
             MergeEquals => "MergeEquals",
             Bang => "Bang",
             Question => "Question",
+            Arrow => "Arrow",
 
             LQuery => "LQuery",
             RQuery => "RQuery",
@@ -512,6 +515,13 @@ impl<'input> Iterator for Lexer<'input> {
                         Some(Ok(self.token(start, Bang)))
                     }
 
+                    // The arrow is lexed on its own, so that it doesn't merge
+                    // with the pipe opening a closure, as in `->|value|`.
+                    '-' if self.test_peek(|ch| ch == '>') => {
+                        self.bump();
+                        Some(Ok(self.token(start, Arrow)))
+                    }
+
                     '#' => {
                         self.take_until(start, |ch| ch == '\n');
                         continue;
@@ -1627,6 +1637,26 @@ mod test {
         );
     }
 
+    #[test]
+    fn function_call_closure() {
+        test(
+            data("f(a) -> |b| { b }"),
+            vec![
+                ("~                ", FunctionCall("f")),
+                (" ~               ", LParen),
+                ("  ~              ", Identifier("a")),
+                ("   ~             ", RParen),
+                ("     ~~          ", Arrow),
+                ("        ~        ", Operator("|")),
+                ("         ~       ", Identifier("b")),
+                ("          ~      ", Operator("|")),
+                ("            ~    ", LBrace),
+                ("              ~  ", Identifier("b")),
+                ("                ~", RBrace),
+            ],
+        );
+    }
+
     #[test]
     fn queries_in_array() {
         test(
@@ -14,11 +14,19 @@ criterion_group!(
               compact,
               contains,
               decode_base64,
+              decode_gzip,
+              decode_snappy,
+              decode_zlib,
+              decode_zstd,
               // TODO: Cannot pass a Path to bench_function
               //del,
               downcase,
               encode_base64,
+              encode_csv,
               encode_json,
+              encode_key_value,
+              encode_logfmt,
+              encode_snappy,
               ends_with,
               // TODO: Cannot pass a Path to bench_function
               //exists
@@ -47,6 +55,7 @@ criterion_group!(
               parse_aws_cloudwatch_log_subscription_message,
               parse_aws_vpc_flow_log,
               parse_common_log,
+              parse_csv,
               parse_duration,
               parse_glog,
               parse_grok,
@@ -58,6 +67,8 @@ criterion_group!(
               parse_timestamp,
               parse_tokens,
               parse_url,
+              parse_user_agent,
+              parse_xml,
               push,
               // TODO: Has not been ported to vrl/stdlib yet
               //redact,
@@ -155,6 +166,42 @@ bench_function! {
     }
 }
 
+bench_function! {
+    decode_gzip => vrl_stdlib::DecodeGzip;
+
+    literal {
+        args: func_args![value: &b"\x1f\x8b\x08\x00\x00\x00\x00\x00\x02\x03\xab\xcc\x2f\x55\xc8\x48\x2c\x4b\x55\x28\x2e\x4d\x4e\x4e\x2d\x2e\x4e\x2b\xcd\xc9\xa9\x54\x48\x49\x4d\xce\x4f\x49\x4d\x51\xc8\x4d\x05\x00\xfb\x04\x60\xda\x20\x00\x00\x00"[..]],
+        want: Ok("you have successfully decoded me"),
+    }
+}
+
+bench_function! {
+    decode_snappy => vrl_stdlib::DecodeSnappy;
+
+    literal {
+        args: func_args![value: &b"\x20\x7cyou have successfully decoded me"[..]],
+        want: Ok("you have successfully decoded me"),
+    }
+}
+
+bench_function! {
+    decode_zlib => vrl_stdlib::DecodeZlib;
+
+    literal {
+        args: func_args![value: &b"\x78\x9c\xab\xcc\x2f\x55\xc8\x48\x2c\x4b\x55\x28\x2e\x4d\x4e\x4e\x2d\x2e\x4e\x2b\xcd\xc9\xa9\x54\x48\x49\x4d\xce\x4f\x49\x4d\x51\xc8\x4d\x05\x00\xcc\xb2\x0c\x41"[..]],
+        want: Ok("you have successfully decoded me"),
+    }
+}
+
+bench_function! {
+    decode_zstd => vrl_stdlib::DecodeZstd;
+
+    literal {
+        args: func_args![value: &b"\x28\xb5\x2f\xfd\x04\x58\x01\x01\x00\x79\x6f\x75\x20\x68\x61\x76\x65\x20\x73\x75\x63\x63\x65\x73\x73\x66\x75\x6c\x6c\x79\x20\x64\x65\x63\x6f\x64\x65\x64\x20\x6d\x65\xc3\x10\x95\x71"[..]],
+        want: Ok("you have successfully decoded me"),
+    }
+}
+
 bench_function! {
     downcase => vrl_stdlib::Downcase;
 
@@ -173,6 +220,15 @@ bench_function! {
     }
 }
 
+bench_function! {
+    encode_csv => vrl_stdlib::EncodeCsv;
+
+    literal {
+        args: func_args![value: value!(["foo", "bar", "foo \", bar"])],
+        want: Ok(r#"foo,bar,"foo "", bar""#),
+    }
+}
+
 bench_function! {
     encode_json => vrl_stdlib::EncodeJson;
 
@@ -182,6 +238,33 @@ bench_function! {
     }
 }
 
+bench_function! {
+    encode_key_value => vrl_stdlib::EncodeKeyValue;
+
+    literal {
+        args: func_args![value: value!({"lvl": "info", "msg": "This is a message", "log_id": 12345})],
+        want: Ok(r#"log_id=12345 lvl=info msg="This is a message""#),
+    }
+}
+
+bench_function! {
+    encode_logfmt => vrl_stdlib::EncodeLogfmt;
+
+    literal {
+        args: func_args![value: value!({"lvl": "info", "msg": "This is a message", "log_id": 12345})],
+        want: Ok(r#"log_id=12345 lvl=info msg="This is a message""#),
+    }
+}
+
+bench_function! {
+    encode_snappy => vrl_stdlib::EncodeSnappy;
+
+    literal {
+        args: func_args![value: "you have successfully decoded me"],
+        want: Ok(&b"\x20\x7cyou have successfully decoded me"[..]),
+    }
+}
+
 bench_function! {
     ends_with => vrl_stdlib::EndsWith;
 
@@ -685,6 +768,15 @@ bench_function! {
     }
 }
 
+bench_function! {
+    parse_csv => vrl_stdlib::ParseCsv;
+
+    literal {
+        args: func_args![value: "foo,bar,\"foo \"\", bar\""],
+        want: Ok(value!(["foo", "bar", "foo \", bar"])),
+    }
+}
+
 bench_function! {
     parse_duration => vrl_stdlib::ParseDuration;
 
@@ -907,6 +999,34 @@ bench_function! {
     }
 }
 
+bench_function! {
+    parse_user_agent => vrl_stdlib::ParseUserAgent;
+
+    literal {
+        args: func_args![value: "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/91.0.4472.124 Safari/537.36"],
+        want: Ok(value!({
+            "browser": { "family": "Chrome", "version": "91.0.4472.124" },
+            "os": { "family": "Mac OSX", "version": "10.15.7" },
+            "device": { "category": "pc" },
+        })),
+    }
+}
+
+bench_function! {
+    parse_xml => vrl_stdlib::ParseXml;
+
+    literal {
+        args: func_args![value: r#"<book category="fiction"><title lang="en">Dune</title><year>1965</year></book>"#],
+        want: Ok(value!({
+            "book": {
+                "@category": "fiction",
+                "title": { "@lang": "en", "text": "Dune" },
+                "year": 1965,
+            },
+        })),
+    }
+}
+
 bench_function! {
     push => vrl_stdlib::Push;
 
@@ -0,0 +1,91 @@
+use crate::util::read_decompressed;
+use flate2::read::MultiGzDecoder;
+use vrl::prelude::*;
+
+#[derive(Clone, Copy, Debug)]
+pub struct DecodeGzip;
+
+impl Function for DecodeGzip {
+    fn identifier(&self) -> &'static str {
+        "decode_gzip"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+
+    fn compile(&self, mut arguments: ArgumentList) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(Box::new(DecodeGzipFn { value }))
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "demo string",
+            source: r#"decode_gzip!(decode_base64!("H4sIAAAAAAACA6vML1XISCxLVSguTU5OLS5OK83JqVRISU3OT0lNUchNBQD7BGDaIAAAAA=="))"#,
+            result: Ok("you have successfully decoded me"),
+        }]
+    }
+}
+
+#[derive(Clone, Debug)]
+struct DecodeGzipFn {
+    value: Box<dyn Expression>,
+}
+
+impl Expression for DecodeGzipFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?.try_bytes()?;
+
+        // Payloads such as CloudWatch Logs subscriptions may hold several concatenated gzip
+        // members, which are decoded as a single value.
+        Ok(read_decompressed(MultiGzDecoder::new(&value[..]), "gzip")?.into())
+    }
+
+    fn type_def(&self, _: &state::Compiler) -> TypeDef {
+        // Always fallible due to the possibility of decoding errors that VRL can't detect in
+        // advance.
+        TypeDef::new().fallible().bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::MAX_DECOMPRESSED_SIZE;
+    use flate2::read::GzEncoder;
+    use std::io::Read;
+
+    test_function![
+        decode_gzip => DecodeGzip;
+
+        success {
+            args: func_args![value: &b"\x1f\x8b\x08\x00\x00\x00\x00\x00\x02\x03\xab\xcc\x2f\x55\xc8\x48\x2c\x4b\x55\x28\x2e\x4d\x4e\x4e\x2d\x2e\x4e\x2b\xcd\xc9\xa9\x54\x48\x49\x4d\xce\x4f\x49\x4d\x51\xc8\x4d\x05\x00\xfb\x04\x60\xda\x20\x00\x00\x00"[..]],
+            want: Ok("you have successfully decoded me"),
+            tdef: TypeDef::new().fallible().bytes(),
+        }
+
+        invalid {
+            args: func_args![value: "not gzip compressed"],
+            want: Err("unable to decode value with gzip decoder: invalid gzip header"),
+            tdef: TypeDef::new().fallible().bytes(),
+        }
+
+        too_large {
+            args: func_args![value: {
+                let mut buf = Vec::new();
+                GzEncoder::new(&vec![0u8; MAX_DECOMPRESSED_SIZE + 1][..], flate2::Compression::fast())
+                    .read_to_end(&mut buf)
+                    .unwrap();
+                buf
+            }],
+            want: Err(format!("unable to decode value with gzip decoder: decoded value is larger than {} bytes", MAX_DECOMPRESSED_SIZE)),
+            tdef: TypeDef::new().fallible().bytes(),
+        }
+    ];
+}
@@ -0,0 +1,92 @@
+use crate::util::{decompressed_too_large, MAX_DECOMPRESSED_SIZE};
+use vrl::prelude::*;
+
+#[derive(Clone, Copy, Debug)]
+pub struct DecodeSnappy;
+
+impl Function for DecodeSnappy {
+    fn identifier(&self) -> &'static str {
+        "decode_snappy"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+
+    fn compile(&self, mut arguments: ArgumentList) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(Box::new(DecodeSnappyFn { value }))
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "demo string",
+            source: r#"decode_snappy!(decode_base64!("IHx5b3UgaGF2ZSBzdWNjZXNzZnVsbHkgZGVjb2RlZCBtZQ=="))"#,
+            result: Ok("you have successfully decoded me"),
+        }]
+    }
+}
+
+#[derive(Clone, Debug)]
+struct DecodeSnappyFn {
+    value: Box<dyn Expression>,
+}
+
+impl Expression for DecodeSnappyFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?.try_bytes()?;
+
+        // The decompressed size is stored in the header, so oversized values are rejected before
+        // decompressing them.
+        let decoded = snap::raw::decompress_len(&value)
+            .and_then(|len| {
+                if len > MAX_DECOMPRESSED_SIZE {
+                    return Ok(None);
+                }
+                snap::raw::Decoder::new().decompress_vec(&value).map(Some)
+            })
+            .map_err(|err| format!("unable to decode value with snappy decoder: {}", err))?
+            .ok_or_else(|| decompressed_too_large("snappy"))?;
+
+        Ok(decoded.into())
+    }
+
+    fn type_def(&self, _: &state::Compiler) -> TypeDef {
+        // Always fallible due to the possibility of decoding errors that VRL can't detect in
+        // advance.
+        TypeDef::new().fallible().bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        decode_snappy => DecodeSnappy;
+
+        success {
+            args: func_args![value: &b"\x20\x7cyou have successfully decoded me"[..]],
+            want: Ok("you have successfully decoded me"),
+            tdef: TypeDef::new().fallible().bytes(),
+        }
+
+        invalid {
+            args: func_args![value: ""],
+            want: Err("unable to decode value with snappy decoder: snappy: corrupt input (empty)"),
+            tdef: TypeDef::new().fallible().bytes(),
+        }
+
+        too_large {
+            // Only the header, holding the decompressed size, is read.
+            args: func_args![value: &b"\x81\x80\x80\x20"[..]],
+            want: Err(format!("unable to decode value with snappy decoder: decoded value is larger than {} bytes", MAX_DECOMPRESSED_SIZE)),
+            tdef: TypeDef::new().fallible().bytes(),
+        }
+    ];
+}
@@ -0,0 +1,89 @@
+use crate::util::read_decompressed;
+use flate2::read::ZlibDecoder;
+use vrl::prelude::*;
+
+#[derive(Clone, Copy, Debug)]
+pub struct DecodeZlib;
+
+impl Function for DecodeZlib {
+    fn identifier(&self) -> &'static str {
+        "decode_zlib"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+
+    fn compile(&self, mut arguments: ArgumentList) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(Box::new(DecodeZlibFn { value }))
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "demo string",
+            source: r#"decode_zlib!(decode_base64!("eJyrzC9VyEgsS1UoLk1OTi0uTivNyalUSElNzk9JTVHITQUAzLIMQQ=="))"#,
+            result: Ok("you have successfully decoded me"),
+        }]
+    }
+}
+
+#[derive(Clone, Debug)]
+struct DecodeZlibFn {
+    value: Box<dyn Expression>,
+}
+
+impl Expression for DecodeZlibFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?.try_bytes()?;
+
+        Ok(read_decompressed(ZlibDecoder::new(&value[..]), "zlib")?.into())
+    }
+
+    fn type_def(&self, _: &state::Compiler) -> TypeDef {
+        // Always fallible due to the possibility of decoding errors that VRL can't detect in
+        // advance.
+        TypeDef::new().fallible().bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::MAX_DECOMPRESSED_SIZE;
+    use flate2::read::ZlibEncoder;
+    use std::io::Read;
+
+    test_function![
+        decode_zlib => DecodeZlib;
+
+        success {
+            args: func_args![value: &b"\x78\x9c\xab\xcc\x2f\x55\xc8\x48\x2c\x4b\x55\x28\x2e\x4d\x4e\x4e\x2d\x2e\x4e\x2b\xcd\xc9\xa9\x54\x48\x49\x4d\xce\x4f\x49\x4d\x51\xc8\x4d\x05\x00\xcc\xb2\x0c\x41"[..]],
+            want: Ok("you have successfully decoded me"),
+            tdef: TypeDef::new().fallible().bytes(),
+        }
+
+        invalid {
+            args: func_args![value: "not zlib compressed"],
+            want: Err("unable to decode value with zlib decoder: corrupt deflate stream"),
+            tdef: TypeDef::new().fallible().bytes(),
+        }
+
+        too_large {
+            args: func_args![value: {
+                let mut buf = Vec::new();
+                ZlibEncoder::new(&vec![0u8; MAX_DECOMPRESSED_SIZE + 1][..], flate2::Compression::fast())
+                    .read_to_end(&mut buf)
+                    .unwrap();
+                buf
+            }],
+            want: Err(format!("unable to decode value with zlib decoder: decoded value is larger than {} bytes", MAX_DECOMPRESSED_SIZE)),
+            tdef: TypeDef::new().fallible().bytes(),
+        }
+    ];
+}
@@ -0,0 +1,83 @@
+use crate::util::read_decompressed;
+use vrl::prelude::*;
+
+#[derive(Clone, Copy, Debug)]
+pub struct DecodeZstd;
+
+impl Function for DecodeZstd {
+    fn identifier(&self) -> &'static str {
+        "decode_zstd"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+
+    fn compile(&self, mut arguments: ArgumentList) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(Box::new(DecodeZstdFn { value }))
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "demo string",
+            source: r#"decode_zstd!(decode_base64!("KLUv/QRYAQEAeW91IGhhdmUgc3VjY2Vzc2Z1bGx5IGRlY29kZWQgbWXDEJVx"))"#,
+            result: Ok("you have successfully decoded me"),
+        }]
+    }
+}
+
+#[derive(Clone, Debug)]
+struct DecodeZstdFn {
+    value: Box<dyn Expression>,
+}
+
+impl Expression for DecodeZstdFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?.try_bytes()?;
+
+        let decoder = zstd::stream::read::Decoder::new(&value[..])
+            .map_err(|err| format!("unable to decode value with zstd decoder: {}", err))?;
+
+        Ok(read_decompressed(decoder, "zstd")?.into())
+    }
+
+    fn type_def(&self, _: &state::Compiler) -> TypeDef {
+        // Always fallible due to the possibility of decoding errors that VRL can't detect in
+        // advance.
+        TypeDef::new().fallible().bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::MAX_DECOMPRESSED_SIZE;
+
+    test_function![
+        decode_zstd => DecodeZstd;
+
+        success {
+            args: func_args![value: &b"\x28\xb5\x2f\xfd\x04\x58\x01\x01\x00\x79\x6f\x75\x20\x68\x61\x76\x65\x20\x73\x75\x63\x63\x65\x73\x73\x66\x75\x6c\x6c\x79\x20\x64\x65\x63\x6f\x64\x65\x64\x20\x6d\x65\xc3\x10\x95\x71"[..]],
+            want: Ok("you have successfully decoded me"),
+            tdef: TypeDef::new().fallible().bytes(),
+        }
+
+        invalid {
+            args: func_args![value: "not zstd compressed"],
+            want: Err("unable to decode value with zstd decoder: Unknown frame descriptor"),
+            tdef: TypeDef::new().fallible().bytes(),
+        }
+
+        too_large {
+            args: func_args![value: zstd::stream::encode_all(&vec![0u8; MAX_DECOMPRESSED_SIZE + 1][..], 1).unwrap()],
+            want: Err(format!("unable to decode value with zstd decoder: decoded value is larger than {} bytes", MAX_DECOMPRESSED_SIZE)),
+            tdef: TypeDef::new().fallible().bytes(),
+        }
+    ];
+}
@@ -0,0 +1,181 @@
+use crate::util::{encode_scalar, fields_ordering};
+use vrl::prelude::*;
+
+#[derive(Clone, Copy, Debug)]
+pub struct EncodeCsv;
+
+impl Function for EncodeCsv {
+    fn identifier(&self) -> &'static str {
+        "encode_csv"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::ARRAY | kind::OBJECT,
+                required: true,
+            },
+            Parameter {
+                keyword: "fields_ordering",
+                kind: kind::ARRAY,
+                required: false,
+            },
+            Parameter {
+                keyword: "delimiter",
+                kind: kind::BYTES,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "array",
+                source: r#"encode_csv!(["foo", 1, true, "foo \", bar"])"#,
+                result: Ok(r#"s'foo,1,true,"foo "", bar"'"#),
+            },
+            Example {
+                title: "object with ordering",
+                source: r#"encode_csv!({"host": "web-1", "port": 80, "team": "frontend"}, fields_ordering: ["team", "host"], delimiter: ";")"#,
+                result: Ok(r#"s'frontend;web-1'"#),
+            },
+        ]
+    }
+
+    fn compile(&self, mut arguments: ArgumentList) -> Compiled {
+        let value = arguments.required("value");
+        let fields_ordering = arguments.optional("fields_ordering");
+        let delimiter = arguments
+            .optional("delimiter")
+            .unwrap_or_else(|| expr!(","));
+
+        Ok(Box::new(EncodeCsvFn {
+            value,
+            fields_ordering,
+            delimiter,
+        }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct EncodeCsvFn {
+    value: Box<dyn Expression>,
+    fields_ordering: Option<Box<dyn Expression>>,
+    delimiter: Box<dyn Expression>,
+}
+
+impl Expression for EncodeCsvFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        let delimiter = self.delimiter.resolve(ctx)?.try_bytes()?;
+        if delimiter.len() != 1 {
+            return Err("delimiter must be a single character".into());
+        }
+
+        // Objects are encoded as the values of the fields named in `fields_ordering`, so that
+        // every row has the same columns, or as all of their values in alphabetical order of
+        // their keys otherwise.
+        let fields = match value {
+            Value::Object(mut object) => match &self.fields_ordering {
+                Some(expr) => fields_ordering(expr.resolve(ctx)?)?
+                    .iter()
+                    .map(|key| object.remove(key).unwrap_or(Value::Null))
+                    .collect(),
+                None => object.into_iter().map(|(_, value)| value).collect(),
+            },
+            value => value.try_array()?,
+        };
+
+        let fields = fields
+            .into_iter()
+            .enumerate()
+            .map(|(index, field)| match field {
+                Value::Object(_) | Value::Array(_) => Err(format!(
+                    "unable to encode {} field at position {}",
+                    field.kind(),
+                    index
+                )),
+                field => Ok(encode_scalar(field)),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut writer = csv::WriterBuilder::new()
+            .delimiter(delimiter[0])
+            .terminator(csv::Terminator::Any(b'\n'))
+            .from_writer(vec![]);
+
+        writer
+            .write_record(&fields)
+            .map_err(|err| format!("unable to encode csv: {}", err))?;
+
+        let mut encoded = writer
+            .into_inner()
+            .map_err(|err| format!("unable to encode csv: {}", err))?;
+
+        // Only a single row is encoded, so the record terminator is dropped.
+        encoded.pop();
+
+        Ok(encoded.into())
+    }
+
+    fn type_def(&self, _: &state::Compiler) -> TypeDef {
+        TypeDef::new().fallible().bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        encode_csv => EncodeCsv;
+
+        array {
+            args: func_args![value: value!(["foo", 1, 1.5, true, null])],
+            want: Ok("foo,1,1.5,true,"),
+            tdef: TypeDef::new().fallible().bytes(),
+        }
+
+        quoted_fields {
+            args: func_args![value: value!(["foo", "foo \", bar", "a\nb"])],
+            want: Ok("foo,\"foo \"\", bar\",\"a\nb\""),
+            tdef: TypeDef::new().fallible().bytes(),
+        }
+
+        object {
+            args: func_args![value: value!({ "team": "frontend", "host": "web-1" })],
+            want: Ok("web-1,frontend"),
+            tdef: TypeDef::new().fallible().bytes(),
+        }
+
+        object_with_fields_ordering {
+            args: func_args![
+                value: value!({ "team": "frontend", "host": "web-1", "port": 80 }),
+                fields_ordering: value!(["port", "missing", "host"]),
+            ],
+            want: Ok("80,,web-1"),
+            tdef: TypeDef::new().fallible().bytes(),
+        }
+
+        custom_delimiter {
+            args: func_args![value: value!(["foo", "bar baz"]), delimiter: " "],
+            want: Ok("foo \"bar baz\""),
+            tdef: TypeDef::new().fallible().bytes(),
+        }
+
+        invalid_delimiter {
+            args: func_args![value: value!(["foo"]), delimiter: ",,"],
+            want: Err("delimiter must be a single character"),
+            tdef: TypeDef::new().fallible().bytes(),
+        }
+
+        nested_field {
+            args: func_args![value: value!(["foo", ["bar"]])],
+            want: Err(r#"unable to encode "array" field at position 1"#),
+            tdef: TypeDef::new().fallible().bytes(),
+        }
+    ];
+}
@@ -0,0 +1,237 @@
+use crate::util::{encode_scalar, fields_ordering};
+use std::collections::BTreeMap;
+use vrl::prelude::*;
+
+#[derive(Clone, Copy, Debug)]
+pub struct EncodeKeyValue;
+
+impl Function for EncodeKeyValue {
+    fn identifier(&self) -> &'static str {
+        "encode_key_value"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::OBJECT,
+                required: true,
+            },
+            Parameter {
+                keyword: "fields_ordering",
+                kind: kind::ARRAY,
+                required: false,
+            },
+            Parameter {
+                keyword: "key_value_delimiter",
+                kind: kind::BYTES,
+                required: false,
+            },
+            Parameter {
+                keyword: "field_delimiter",
+                kind: kind::BYTES,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "encode object",
+                source: r#"encode_key_value({"lvl": "info", "msg": "This is a message", "log_id": 12345})"#,
+                result: Ok(r#"s'log_id=12345 lvl=info msg="This is a message"'"#),
+            },
+            Example {
+                title: "custom delimiters and ordering",
+                source: r#"encode_key_value!({"lvl": "info", "msg": "hello", "log_id": 12345}, fields_ordering: ["msg", "lvl"], key_value_delimiter: ":", field_delimiter: ",")"#,
+                result: Ok(r#"s'msg:hello,lvl:info,log_id:12345'"#),
+            },
+            Example {
+                title: "nested fields",
+                source: r#"encode_key_value({"agent": {"name": "vector"}, "tags": ["a", "b"]})"#,
+                result: Ok(r#"s'agent.name=vector tags[0]=a tags[1]=b'"#),
+            },
+        ]
+    }
+
+    fn compile(&self, mut arguments: ArgumentList) -> Compiled {
+        let value = arguments.required("value");
+        let fields_ordering = arguments.optional("fields_ordering");
+
+        let key_value_delimiter = arguments
+            .optional("key_value_delimiter")
+            .unwrap_or_else(|| expr!("="));
+
+        let field_delimiter = arguments
+            .optional("field_delimiter")
+            .unwrap_or_else(|| expr!(" "));
+
+        Ok(Box::new(EncodeKeyValueFn {
+            value,
+            fields_ordering,
+            key_value_delimiter,
+            field_delimiter,
+        }))
+    }
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct EncodeKeyValueFn {
+    pub(crate) value: Box<dyn Expression>,
+    pub(crate) fields_ordering: Option<Box<dyn Expression>>,
+    pub(crate) key_value_delimiter: Box<dyn Expression>,
+    pub(crate) field_delimiter: Box<dyn Expression>,
+}
+
+impl Expression for EncodeKeyValueFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let object = self.value.resolve(ctx)?.try_object()?;
+
+        let fields_ordering = match &self.fields_ordering {
+            Some(expr) => fields_ordering(expr.resolve(ctx)?)?,
+            None => vec![],
+        };
+
+        let key_value_delimiter = self.key_value_delimiter.resolve(ctx)?;
+        let key_value_delimiter = key_value_delimiter.try_bytes_utf8_lossy()?;
+
+        let field_delimiter = self.field_delimiter.resolve(ctx)?;
+        let field_delimiter = field_delimiter.try_bytes_utf8_lossy()?;
+
+        let mut fields = BTreeMap::new();
+        flatten(None, Value::Object(object), &mut fields);
+
+        // Fields named in `fields_ordering` come first, in the given order, followed by the
+        // remaining fields in alphabetical order.
+        let ordered = fields_ordering
+            .iter()
+            .filter_map(|key| fields.remove_entry(key.as_str()))
+            .collect::<Vec<_>>();
+
+        let encoded = ordered
+            .into_iter()
+            .chain(fields.into_iter())
+            .map(|(key, value)| {
+                format!(
+                    "{}{}{}",
+                    key,
+                    key_value_delimiter,
+                    quote(&value, &key_value_delimiter, &field_delimiter)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(&field_delimiter);
+
+        Ok(encoded.into())
+    }
+
+    fn type_def(&self, _: &state::Compiler) -> TypeDef {
+        TypeDef::new()
+            .with_fallibility(self.fields_ordering.is_some())
+            .bytes()
+    }
+}
+
+/// Flatten nested objects and arrays into dotted (`parent.child`) and indexed (`parent[0]`)
+/// keys, encoding each leaf value as a string.
+fn flatten(prefix: Option<String>, value: Value, fields: &mut BTreeMap<String, String>) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map {
+                let key = match &prefix {
+                    Some(prefix) => format!("{}.{}", prefix, key),
+                    None => key,
+                };
+                flatten(Some(key), value, fields);
+            }
+        }
+        Value::Array(array) => {
+            for (index, value) in array.into_iter().enumerate() {
+                let key = format!("{}[{}]", prefix.as_deref().unwrap_or_default(), index);
+                flatten(Some(key), value, fields);
+            }
+        }
+        value => {
+            if let Some(key) = prefix {
+                fields.insert(key, encode_scalar(value));
+            }
+        }
+    }
+}
+
+/// Wrap the value in quotes if it can't be parsed back unambiguously, escaping any quotes and
+/// backslashes it contains.
+fn quote(value: &str, key_value_delimiter: &str, field_delimiter: &str) -> String {
+    let needs_quotes = value.contains(char::is_whitespace)
+        || value.contains('"')
+        || (!key_value_delimiter.is_empty() && value.contains(key_value_delimiter))
+        || (!field_delimiter.is_empty() && value.contains(field_delimiter));
+
+    if needs_quotes {
+        format!(r#""{}""#, value.replace('\\', r"\\").replace('"', r#"\""#))
+    } else {
+        value.to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        encode_key_value => EncodeKeyValue;
+
+        single_element {
+            args: func_args![value: value!({ "lvl": "info" })],
+            want: Ok("lvl=info"),
+            tdef: TypeDef::new().bytes().infallible(),
+        }
+
+        multiple_elements {
+            args: func_args![value: value!({ "lvl": "info", "log_id": 12345, "ok": true, "ratio": 1.5 })],
+            want: Ok("log_id=12345 lvl=info ok=true ratio=1.5"),
+            tdef: TypeDef::new().bytes().infallible(),
+        }
+
+        quoted_values {
+            args: func_args![value: value!({ "msg": r#"a "quoted" message"#, "path": "a=b", "empty": "" })],
+            want: Ok(r#"empty= msg="a \"quoted\" message" path="a=b""#),
+            tdef: TypeDef::new().bytes().infallible(),
+        }
+
+        nested_fields {
+            args: func_args![value: value!({ "agent": { "name": "vector", "id": null }, "tags": ["a", "b"] })],
+            want: Ok("agent.id= agent.name=vector tags[0]=a tags[1]=b"),
+            tdef: TypeDef::new().bytes().infallible(),
+        }
+
+        fields_ordering {
+            args: func_args![
+                value: value!({ "lvl": "info", "msg": "hello", "ts": "2021-06-05T17:20:00Z" }),
+                fields_ordering: value!(["ts", "missing", "lvl"]),
+            ],
+            want: Ok("ts=2021-06-05T17:20:00Z lvl=info msg=hello"),
+            tdef: TypeDef::new().bytes().fallible(),
+        }
+
+        invalid_fields_ordering {
+            args: func_args![
+                value: value!({ "lvl": "info" }),
+                fields_ordering: value!(["lvl", 1]),
+            ],
+            want: Err(r#"fields_ordering contains a non-string element: "integer""#),
+            tdef: TypeDef::new().bytes().fallible(),
+        }
+
+        custom_delimiters {
+            args: func_args![
+                value: value!({ "lvl": "info", "msg": "hello world" }),
+                key_value_delimiter: ":",
+                field_delimiter: ",",
+            ],
+            want: Ok(r#"lvl:info,msg:"hello world""#),
+            tdef: TypeDef::new().bytes().infallible(),
+        }
+    ];
+}
@@ -0,0 +1,58 @@
+use crate::encode_key_value::EncodeKeyValueFn;
+use vrl::prelude::*;
+
+#[derive(Clone, Copy, Debug)]
+pub struct EncodeLogfmt;
+
+impl Function for EncodeLogfmt {
+    fn identifier(&self) -> &'static str {
+        "encode_logfmt"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::OBJECT,
+                required: true,
+            },
+            Parameter {
+                keyword: "fields_ordering",
+                kind: kind::ARRAY,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "encode object",
+                source: r#"encode_logfmt({"lvl": "info", "msg": "This is a message", "log_id": 12345})"#,
+                result: Ok(r#"s'log_id=12345 lvl=info msg="This is a message"'"#),
+            },
+            Example {
+                title: "fields ordering",
+                source: r#"encode_logfmt!({"lvl": "info", "msg": "This is a message", "log_id": 12345}, ["lvl", "msg"])"#,
+                result: Ok(r#"s'lvl=info msg="This is a message" log_id=12345'"#),
+            },
+        ]
+    }
+
+    fn compile(&self, mut arguments: ArgumentList) -> Compiled {
+        let value = arguments.required("value");
+        let fields_ordering = arguments.optional("fields_ordering");
+
+        // The encode_logfmt function is just an alias for `encode_key_value` with the following
+        // parameters for the delimiters.
+        let key_value_delimiter = expr!("=");
+        let field_delimiter = expr!(" ");
+
+        Ok(Box::new(EncodeKeyValueFn {
+            value,
+            fields_ordering,
+            key_value_delimiter,
+            field_delimiter,
+        }))
+    }
+}
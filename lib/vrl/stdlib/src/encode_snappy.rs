@@ -0,0 +1,68 @@
+use vrl::prelude::*;
+
+#[derive(Clone, Copy, Debug)]
+pub struct EncodeSnappy;
+
+impl Function for EncodeSnappy {
+    fn identifier(&self) -> &'static str {
+        "encode_snappy"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "round trip",
+            source: r#"decode_snappy!(encode_snappy!("please encode me"))"#,
+            result: Ok("please encode me"),
+        }]
+    }
+
+    fn compile(&self, mut arguments: ArgumentList) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(Box::new(EncodeSnappyFn { value }))
+    }
+}
+
+#[derive(Clone, Debug)]
+struct EncodeSnappyFn {
+    value: Box<dyn Expression>,
+}
+
+impl Expression for EncodeSnappyFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?.try_bytes()?;
+
+        match snap::raw::Encoder::new().compress_vec(&value) {
+            Ok(encoded) => Ok(encoded.into()),
+            Err(err) => Err(format!("unable to encode value with snappy: {}", err).into()),
+        }
+    }
+
+    fn type_def(&self, _: &state::Compiler) -> TypeDef {
+        // Snappy can't compress values larger than about 4GiB.
+        TypeDef::new().fallible().bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        encode_snappy => EncodeSnappy;
+
+        success {
+            args: func_args![value: "you have successfully decoded me"],
+            want: Ok(&b"\x20\x7cyou have successfully decoded me"[..]),
+            tdef: TypeDef::new().fallible().bytes(),
+        }
+    ];
+}
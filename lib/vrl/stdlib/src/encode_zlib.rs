@@ -0,0 +1,101 @@
+use flate2::read::ZlibEncoder;
+use std::io::Read;
+use vrl::prelude::*;
+
+#[derive(Clone, Copy, Debug)]
+pub struct EncodeZlib;
+
+impl Function for EncodeZlib {
+    fn identifier(&self) -> &'static str {
+        "encode_zlib"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "compression_level",
+                kind: kind::INTEGER,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "round trip",
+            source: r#"decode_zlib!(encode_zlib("please encode me"))"#,
+            result: Ok("please encode me"),
+        }]
+    }
+
+    fn compile(&self, mut arguments: ArgumentList) -> Compiled {
+        let value = arguments.required("value");
+        let compression_level = arguments.optional("compression_level");
+
+        Ok(Box::new(EncodeZlibFn {
+            value,
+            compression_level,
+        }))
+    }
+}
+
+#[derive(Clone, Debug)]
+struct EncodeZlibFn {
+    value: Box<dyn Expression>,
+    compression_level: Option<Box<dyn Expression>>,
+}
+
+impl Expression for EncodeZlibFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?.try_bytes()?;
+
+        let level = match &self.compression_level {
+            Some(level) => match level.resolve(ctx)?.try_integer()? {
+                level @ 0..=9 => flate2::Compression::new(level as u32),
+                _ => return Err("compression level must be between 0 and 9".into()),
+            },
+            None => flate2::Compression::default(),
+        };
+
+        let mut buf = Vec::new();
+        // Reading from an in-memory buffer can't fail.
+        ZlibEncoder::new(&value[..], level)
+            .read_to_end(&mut buf)
+            .expect("in-memory compression");
+
+        Ok(buf.into())
+    }
+
+    fn type_def(&self, _: &state::Compiler) -> TypeDef {
+        // Only an invalid compression level can make compression fail.
+        TypeDef::new()
+            .with_fallibility(self.compression_level.is_some())
+            .bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        encode_zlib => EncodeZlib;
+
+        invalid_compression_level {
+            args: func_args![value: "please encode me", compression_level: 10],
+            want: Err("compression level must be between 0 and 9"),
+            tdef: TypeDef::new().fallible().bytes(),
+        }
+
+        negative_compression_level {
+            args: func_args![value: "please encode me", compression_level: -1],
+            want: Err("compression level must be between 0 and 9"),
+            tdef: TypeDef::new().fallible().bytes(),
+        }
+    ];
+}
@@ -0,0 +1,96 @@
+use vrl::prelude::*;
+
+#[derive(Clone, Copy, Debug)]
+pub struct EncodeZstd;
+
+impl Function for EncodeZstd {
+    fn identifier(&self) -> &'static str {
+        "encode_zstd"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "compression_level",
+                kind: kind::INTEGER,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "round trip",
+            source: r#"decode_zstd!(encode_zstd("please encode me"))"#,
+            result: Ok("please encode me"),
+        }]
+    }
+
+    fn compile(&self, mut arguments: ArgumentList) -> Compiled {
+        let value = arguments.required("value");
+        let compression_level = arguments.optional("compression_level");
+
+        Ok(Box::new(EncodeZstdFn {
+            value,
+            compression_level,
+        }))
+    }
+}
+
+#[derive(Clone, Debug)]
+struct EncodeZstdFn {
+    value: Box<dyn Expression>,
+    compression_level: Option<Box<dyn Expression>>,
+}
+
+impl Expression for EncodeZstdFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?.try_bytes()?;
+
+        let level = match &self.compression_level {
+            Some(level) => match level.resolve(ctx)?.try_integer()? {
+                level @ 1..=21 => level as i32,
+                _ => return Err("compression level must be between 1 and 21".into()),
+            },
+            None => zstd::DEFAULT_COMPRESSION_LEVEL,
+        };
+
+        // Reading from an in-memory buffer can't fail.
+        let encoded = zstd::stream::encode_all(&value[..], level).expect("in-memory compression");
+
+        Ok(encoded.into())
+    }
+
+    fn type_def(&self, _: &state::Compiler) -> TypeDef {
+        // Only an invalid compression level can make compression fail.
+        TypeDef::new()
+            .with_fallibility(self.compression_level.is_some())
+            .bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        encode_zstd => EncodeZstd;
+
+        invalid_compression_level {
+            args: func_args![value: "please encode me", compression_level: 22],
+            want: Err("compression level must be between 1 and 21"),
+            tdef: TypeDef::new().fallible().bytes(),
+        }
+
+        zero_compression_level {
+            args: func_args![value: "please encode me", compression_level: 0],
+            want: Err("compression level must be between 1 and 21"),
+            tdef: TypeDef::new().fallible().bytes(),
+        }
+    ];
+}
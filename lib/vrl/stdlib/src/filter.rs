@@ -0,0 +1,109 @@
+use std::collections::BTreeMap;
+use vrl::prelude::*;
+
+#[derive(Clone, Copy, Debug)]
+pub struct Filter;
+
+impl Function for Filter {
+    fn identifier(&self) -> &'static str {
+        "filter"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::OBJECT | kind::ARRAY,
+            required: true,
+        }]
+    }
+
+    fn closure(&self) -> Option<ClosureDefinition> {
+        Some(ClosureDefinition {
+            parameter_keyword: "value",
+            variables: &[ClosureVariable::Key, ClosureVariable::Value],
+            output: kind::BOOLEAN,
+        })
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "object",
+                source: r#"filter({ "a": 1, "b": 2 }) -> |_key, value| { value > 1 }"#,
+                result: Ok(r#"{ "b": 2 }"#),
+            },
+            Example {
+                title: "array",
+                source: r#"filter(["foo", "bar", "baz"]) -> |index, _value| { index != 1 }"#,
+                result: Ok(r#"["foo", "baz"]"#),
+            },
+        ]
+    }
+
+    fn compile(&self, mut arguments: ArgumentList) -> Compiled {
+        let value = arguments.required("value");
+        let closure = arguments.required_closure();
+
+        Ok(Box::new(FilterFn { value, closure }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct FilterFn {
+    value: Box<dyn Expression>,
+    closure: expression::Closure,
+}
+
+impl Expression for FilterFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        match self.value.resolve(ctx)? {
+            Value::Object(map) => {
+                let mut filtered = BTreeMap::new();
+
+                for (key, value) in map {
+                    let keep = self
+                        .closure
+                        .call(ctx, vec![key.clone().into(), value.clone()])?
+                        .try_boolean()?;
+
+                    if keep {
+                        filtered.insert(key, value);
+                    }
+                }
+
+                Ok(Value::Object(filtered))
+            }
+            Value::Array(array) => {
+                let mut filtered = Vec::with_capacity(array.len());
+
+                for (index, value) in array.into_iter().enumerate() {
+                    let keep = self
+                        .closure
+                        .call(ctx, vec![(index as i64).into(), value.clone()])?
+                        .try_boolean()?;
+
+                    if keep {
+                        filtered.push(value);
+                    }
+                }
+
+                Ok(Value::Array(filtered))
+            }
+            value => Err(value::Error::Expected {
+                got: value.kind(),
+                expected: Kind::Array | Kind::Object,
+            }
+            .into()),
+        }
+    }
+
+    fn type_def(&self, state: &state::Compiler) -> TypeDef {
+        let td = self.value.type_def(state);
+
+        if td.is_array() {
+            TypeDef::new().array_mapped::<(), Kind>(map! { (): Kind::all() })
+        } else {
+            TypeDef::new().object::<(), Kind>(map! { (): Kind::all() })
+        }
+    }
+}
@@ -0,0 +1,92 @@
+use vrl::prelude::*;
+
+#[derive(Clone, Copy, Debug)]
+pub struct ForEach;
+
+impl Function for ForEach {
+    fn identifier(&self) -> &'static str {
+        "for_each"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::OBJECT | kind::ARRAY,
+            required: true,
+        }]
+    }
+
+    fn closure(&self) -> Option<ClosureDefinition> {
+        Some(ClosureDefinition {
+            parameter_keyword: "value",
+            variables: &[ClosureVariable::Key, ClosureVariable::Value],
+            output: kind::ANY,
+        })
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "object",
+                source: indoc! {r#"
+                    count = 0
+                    for_each({ "a": 1, "b": 2 }) -> |_key, value| { count = count + value }
+                    count
+                "#},
+                result: Ok("3"),
+            },
+            Example {
+                title: "array",
+                source: indoc! {r#"
+                    last = null
+                    for_each(["foo", "bar"]) -> |index, _value| { last = index }
+                    last
+                "#},
+                result: Ok("1"),
+            },
+        ]
+    }
+
+    fn compile(&self, mut arguments: ArgumentList) -> Compiled {
+        let value = arguments.required("value");
+        let closure = arguments.required_closure();
+
+        Ok(Box::new(ForEachFn { value, closure }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ForEachFn {
+    value: Box<dyn Expression>,
+    closure: expression::Closure,
+}
+
+impl Expression for ForEachFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        match self.value.resolve(ctx)? {
+            Value::Object(map) => {
+                for (key, value) in map {
+                    self.closure.call(ctx, vec![key.into(), value])?;
+                }
+            }
+            Value::Array(array) => {
+                for (index, value) in array.into_iter().enumerate() {
+                    self.closure.call(ctx, vec![(index as i64).into(), value])?;
+                }
+            }
+            value => {
+                return Err(value::Error::Expected {
+                    got: value.kind(),
+                    expected: Kind::Array | Kind::Object,
+                }
+                .into())
+            }
+        }
+
+        Ok(Value::Null)
+    }
+
+    fn type_def(&self, _: &state::Compiler) -> TypeDef {
+        TypeDef::new().null()
+    }
+}
@@ -16,24 +16,50 @@ mod compact;
 mod contains;
 #[cfg(feature = "decode_base64")]
 mod decode_base64;
+#[cfg(feature = "decode_gzip")]
+mod decode_gzip;
+#[cfg(feature = "decode_snappy")]
+mod decode_snappy;
+#[cfg(feature = "decode_zlib")]
+mod decode_zlib;
+#[cfg(feature = "decode_zstd")]
+mod decode_zstd;
 #[cfg(feature = "del")]
 mod del;
 #[cfg(feature = "downcase")]
 mod downcase;
 #[cfg(feature = "encode_base64")]
 mod encode_base64;
+#[cfg(feature = "encode_csv")]
+mod encode_csv;
+#[cfg(feature = "encode_gzip")]
+mod encode_gzip;
 #[cfg(feature = "encode_json")]
 mod encode_json;
+#[cfg(feature = "encode_key_value")]
+mod encode_key_value;
+#[cfg(feature = "encode_logfmt")]
+mod encode_logfmt;
+#[cfg(feature = "encode_snappy")]
+mod encode_snappy;
+#[cfg(feature = "encode_zlib")]
+mod encode_zlib;
+#[cfg(feature = "encode_zstd")]
+mod encode_zstd;
 #[cfg(feature = "ends_with")]
 mod ends_with;
 #[cfg(feature = "exists")]
 mod exists;
+#[cfg(feature = "filter")]
+mod filter;
 #[cfg(feature = "flatten")]
 mod flatten;
 #[cfg(feature = "float")]
 mod float;
 #[cfg(feature = "floor")]
 mod floor;
+#[cfg(feature = "for_each")]
+mod for_each;
 #[cfg(feature = "format_number")]
 mod format_number;
 #[cfg(feature = "format_timestamp")]
@@ -64,6 +90,10 @@ mod length;
 mod log;
 #[cfg(any(feature = "parse_common_log", feature = "parse_apache_log"))]
 mod log_util;
+#[cfg(feature = "map_keys")]
+mod map_keys;
+#[cfg(feature = "map_values")]
+mod map_values;
 #[cfg(feature = "match")]
 mod r#match;
 #[cfg(feature = "md5")]
@@ -86,6 +116,8 @@ mod parse_aws_cloudwatch_log_subscription_message;
 mod parse_aws_vpc_flow_log;
 #[cfg(feature = "parse_common_log")]
 mod parse_common_log;
+#[cfg(feature = "parse_csv")]
+mod parse_csv;
 #[cfg(feature = "parse_duration")]
 mod parse_duration;
 #[cfg(feature = "parse_glog")]
@@ -110,6 +142,10 @@ mod parse_timestamp;
 mod parse_tokens;
 #[cfg(feature = "parse_url")]
 mod parse_url;
+#[cfg(feature = "parse_user_agent")]
+mod parse_user_agent;
+#[cfg(feature = "parse_xml")]
+mod parse_xml;
 #[cfg(feature = "push")]
 mod push;
 //#[cfg(feature = "redact")]
@@ -187,24 +223,50 @@ pub use compact::Compact;
 pub use contains::Contains;
 #[cfg(feature = "decode_base64")]
 pub use decode_base64::DecodeBase64;
+#[cfg(feature = "decode_gzip")]
+pub use decode_gzip::DecodeGzip;
+#[cfg(feature = "decode_snappy")]
+pub use decode_snappy::DecodeSnappy;
+#[cfg(feature = "decode_zlib")]
+pub use decode_zlib::DecodeZlib;
+#[cfg(feature = "decode_zstd")]
+pub use decode_zstd::DecodeZstd;
 #[cfg(feature = "del")]
 pub use del::Del;
 #[cfg(feature = "downcase")]
 pub use downcase::Downcase;
 #[cfg(feature = "encode_base64")]
 pub use encode_base64::EncodeBase64;
+#[cfg(feature = "encode_csv")]
+pub use encode_csv::EncodeCsv;
+#[cfg(feature = "encode_gzip")]
+pub use encode_gzip::EncodeGzip;
 #[cfg(feature = "encode_json")]
 pub use encode_json::EncodeJson;
+#[cfg(feature = "encode_key_value")]
+pub use encode_key_value::EncodeKeyValue;
+#[cfg(feature = "encode_logfmt")]
+pub use encode_logfmt::EncodeLogfmt;
+#[cfg(feature = "encode_snappy")]
+pub use encode_snappy::EncodeSnappy;
+#[cfg(feature = "encode_zlib")]
+pub use encode_zlib::EncodeZlib;
+#[cfg(feature = "encode_zstd")]
+pub use encode_zstd::EncodeZstd;
 #[cfg(feature = "ends_with")]
 pub use ends_with::EndsWith;
 #[cfg(feature = "exists")]
 pub use exists::Exists;
+#[cfg(feature = "filter")]
+pub use filter::Filter;
 #[cfg(feature = "flatten")]
 pub use flatten::Flatten;
 #[cfg(feature = "float")]
 pub use float::Float;
 #[cfg(feature = "floor")]
 pub use floor::Floor;
+#[cfg(feature = "for_each")]
+pub use for_each::ForEach;
 #[cfg(feature = "format_number")]
 pub use format_number::FormatNumber;
 #[cfg(feature = "format_timestamp")]
@@ -233,6 +295,10 @@ pub use join::Join;
 pub use length::Length;
 #[cfg(feature = "log")]
 pub use log::Log;
+#[cfg(feature = "map_keys")]
+pub use map_keys::MapKeys;
+#[cfg(feature = "map_values")]
+pub use map_values::MapValues;
 #[cfg(feature = "merge")]
 pub use merge::Merge;
 #[cfg(feature = "now")]
@@ -251,6 +317,8 @@ pub use parse_aws_cloudwatch_log_subscription_message::ParseAwsCloudWatchLogSubs
 pub use parse_aws_vpc_flow_log::ParseAwsVpcFlowLog;
 #[cfg(feature = "parse_common_log")]
 pub use parse_common_log::ParseCommonLog;
+#[cfg(feature = "parse_csv")]
+pub use parse_csv::ParseCsv;
 #[cfg(feature = "parse_duration")]
 pub use parse_duration::ParseDuration;
 #[cfg(feature = "parse_glog")]
@@ -275,6 +343,10 @@ pub use parse_timestamp::ParseTimestamp;
 pub use parse_tokens::ParseTokens;
 #[cfg(feature = "parse_url")]
 pub use parse_url::ParseUrl;
+#[cfg(feature = "parse_user_agent")]
+pub use parse_user_agent::ParseUserAgent;
+#[cfg(feature = "parse_xml")]
+pub use parse_xml::ParseXml;
 #[cfg(feature = "push")]
 pub use push::Push;
 #[cfg(feature = "match")]
@@ -348,24 +420,50 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(Contains),
         #[cfg(feature = "decode_base64")]
         Box::new(DecodeBase64),
+        #[cfg(feature = "decode_gzip")]
+        Box::new(DecodeGzip),
+        #[cfg(feature = "decode_snappy")]
+        Box::new(DecodeSnappy),
+        #[cfg(feature = "decode_zlib")]
+        Box::new(DecodeZlib),
+        #[cfg(feature = "decode_zstd")]
+        Box::new(DecodeZstd),
         #[cfg(feature = "del")]
         Box::new(Del),
         #[cfg(feature = "downcase")]
         Box::new(Downcase),
         #[cfg(feature = "encode_base64")]
         Box::new(EncodeBase64),
+        #[cfg(feature = "encode_csv")]
+        Box::new(EncodeCsv),
+        #[cfg(feature = "encode_gzip")]
+        Box::new(EncodeGzip),
         #[cfg(feature = "encode_json")]
         Box::new(EncodeJson),
+        #[cfg(feature = "encode_key_value")]
+        Box::new(EncodeKeyValue),
+        #[cfg(feature = "encode_logfmt")]
+        Box::new(EncodeLogfmt),
+        #[cfg(feature = "encode_snappy")]
+        Box::new(EncodeSnappy),
+        #[cfg(feature = "encode_zlib")]
+        Box::new(EncodeZlib),
+        #[cfg(feature = "encode_zstd")]
+        Box::new(EncodeZstd),
         #[cfg(feature = "ends_with")]
         Box::new(EndsWith),
         #[cfg(feature = "exists")]
         Box::new(Exists),
+        #[cfg(feature = "filter")]
+        Box::new(Filter),
         #[cfg(feature = "flatten")]
         Box::new(Flatten),
         #[cfg(feature = "float")]
         Box::new(Float),
         #[cfg(feature = "floor")]
         Box::new(Floor),
+        #[cfg(feature = "for_each")]
+        Box::new(ForEach),
         #[cfg(feature = "format_number")]
         Box::new(FormatNumber),
         #[cfg(feature = "format_timestamp")]
@@ -394,6 +492,10 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(Length),
         #[cfg(feature = "log")]
         Box::new(Log),
+        #[cfg(feature = "map_keys")]
+        Box::new(MapKeys),
+        #[cfg(feature = "map_values")]
+        Box::new(MapValues),
         #[cfg(feature = "match")]
         Box::new(Match),
         #[cfg(feature = "md5")]
@@ -414,6 +516,8 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(ParseAwsCloudWatchLogSubscriptionMessage),
         #[cfg(feature = "parse_aws_vpc_flow_log")]
         Box::new(ParseAwsVpcFlowLog),
+        #[cfg(feature = "parse_csv")]
+        Box::new(ParseCsv),
         #[cfg(feature = "parse_duration")]
         Box::new(ParseDuration),
         #[cfg(feature = "parse_glog")]
@@ -442,6 +546,10 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(ParseTokens),
         #[cfg(feature = "parse_url")]
         Box::new(ParseUrl),
+        #[cfg(feature = "parse_user_agent")]
+        Box::new(ParseUserAgent),
+        #[cfg(feature = "parse_xml")]
+        Box::new(ParseXml),
         #[cfg(feature = "push")]
         Box::new(Push),
         #[cfg(feature = "match")]
@@ -0,0 +1,126 @@
+use std::collections::BTreeMap;
+use vrl::prelude::*;
+
+#[derive(Clone, Copy, Debug)]
+pub struct MapKeys;
+
+impl Function for MapKeys {
+    fn identifier(&self) -> &'static str {
+        "map_keys"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::OBJECT,
+                required: true,
+            },
+            Parameter {
+                keyword: "recursive",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
+        ]
+    }
+
+    fn closure(&self) -> Option<ClosureDefinition> {
+        Some(ClosureDefinition {
+            parameter_keyword: "value",
+            variables: &[ClosureVariable::Key],
+            output: kind::BYTES,
+        })
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "upcase keys",
+                source: r#"map_keys({ "foo": 1, "bar": 2 }) -> |key| { upcase(key) }"#,
+                result: Ok(r#"{ "FOO": 1, "BAR": 2 }"#),
+            },
+            Example {
+                title: "recursive",
+                source: r#"map_keys({ "a": { "b": true } }, recursive: true) -> |key| { "x_" + key }"#,
+                result: Ok(r#"{ "x_a": { "x_b": true } }"#),
+            },
+        ]
+    }
+
+    fn compile(&self, mut arguments: ArgumentList) -> Compiled {
+        let value = arguments.required("value");
+        let recursive = arguments.optional("recursive");
+        let closure = arguments.required_closure();
+
+        Ok(Box::new(MapKeysFn {
+            value,
+            recursive,
+            closure,
+        }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct MapKeysFn {
+    value: Box<dyn Expression>,
+    recursive: Option<Box<dyn Expression>>,
+    closure: expression::Closure,
+}
+
+impl MapKeysFn {
+    fn map_keys(
+        &self,
+        ctx: &mut Context,
+        map: BTreeMap<String, Value>,
+        recursive: bool,
+    ) -> Result<BTreeMap<String, Value>> {
+        map.into_iter()
+            .map(|(key, value)| {
+                let value = if recursive {
+                    self.map_nested_keys(ctx, value)?
+                } else {
+                    value
+                };
+
+                let key = self
+                    .closure
+                    .call(ctx, vec![key.into()])?
+                    .try_bytes_utf8_lossy()?
+                    .into_owned();
+
+                Ok((key, value))
+            })
+            .collect()
+    }
+
+    /// Map the keys of any objects nested in the value, including objects
+    /// stored in arrays.
+    fn map_nested_keys(&self, ctx: &mut Context, value: Value) -> Resolved {
+        match value {
+            Value::Object(map) => self.map_keys(ctx, map, true).map(Value::Object),
+            Value::Array(array) => array
+                .into_iter()
+                .map(|value| self.map_nested_keys(ctx, value))
+                .collect::<Result<Vec<_>>>()
+                .map(Value::Array),
+            value => Ok(value),
+        }
+    }
+}
+
+impl Expression for MapKeysFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let recursive = match &self.recursive {
+            Some(expr) => expr.resolve(ctx)?.try_boolean()?,
+            None => false,
+        };
+
+        let map = self.value.resolve(ctx)?.try_object()?;
+
+        self.map_keys(ctx, map, recursive).map(Value::Object)
+    }
+
+    fn type_def(&self, _: &state::Compiler) -> TypeDef {
+        TypeDef::new().object::<(), Kind>(map! { (): Kind::all() })
+    }
+}
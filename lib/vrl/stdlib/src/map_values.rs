@@ -0,0 +1,125 @@
+use std::collections::BTreeMap;
+use vrl::prelude::*;
+
+#[derive(Clone, Copy, Debug)]
+pub struct MapValues;
+
+impl Function for MapValues {
+    fn identifier(&self) -> &'static str {
+        "map_values"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::OBJECT | kind::ARRAY,
+                required: true,
+            },
+            Parameter {
+                keyword: "recursive",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
+        ]
+    }
+
+    fn closure(&self) -> Option<ClosureDefinition> {
+        Some(ClosureDefinition {
+            parameter_keyword: "value",
+            variables: &[ClosureVariable::Value],
+            output: kind::ANY,
+        })
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "object",
+                source: r#"map_values({ "a": 1, "b": 2 }) -> |value| { value * 10 }"#,
+                result: Ok(r#"{ "a": 10, "b": 20 }"#),
+            },
+            Example {
+                title: "recursive",
+                source: r#"map_values(["foo", ["bar"]], recursive: true) -> |value| { upcase!(value) }"#,
+                result: Ok(r#"["FOO", ["BAR"]]"#),
+            },
+        ]
+    }
+
+    fn compile(&self, mut arguments: ArgumentList) -> Compiled {
+        let value = arguments.required("value");
+        let recursive = arguments.optional("recursive");
+        let closure = arguments.required_closure();
+
+        Ok(Box::new(MapValuesFn {
+            value,
+            recursive,
+            closure,
+        }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct MapValuesFn {
+    value: Box<dyn Expression>,
+    recursive: Option<Box<dyn Expression>>,
+    closure: expression::Closure,
+}
+
+impl MapValuesFn {
+    /// Map the elements of the given collection.
+    fn map_values(&self, ctx: &mut Context, value: Value, recursive: bool) -> Resolved {
+        match value {
+            Value::Object(map) => map
+                .into_iter()
+                .map(|(key, value)| Ok((key, self.map_element(ctx, value, recursive)?)))
+                .collect::<Result<BTreeMap<_, _>>>()
+                .map(Value::Object),
+            Value::Array(array) => array
+                .into_iter()
+                .map(|value| self.map_element(ctx, value, recursive))
+                .collect::<Result<Vec<_>>>()
+                .map(Value::Array),
+            value => Err(value::Error::Expected {
+                got: value.kind(),
+                expected: Kind::Array | Kind::Object,
+            }
+            .into()),
+        }
+    }
+
+    /// Map a single element, or the elements of a nested collection if
+    /// mapping recursively.
+    fn map_element(&self, ctx: &mut Context, value: Value, recursive: bool) -> Resolved {
+        match value {
+            Value::Object(_) | Value::Array(_) if recursive => {
+                self.map_values(ctx, value, recursive)
+            }
+            value => self.closure.call(ctx, vec![value]),
+        }
+    }
+}
+
+impl Expression for MapValuesFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let recursive = match &self.recursive {
+            Some(expr) => expr.resolve(ctx)?.try_boolean()?,
+            None => false,
+        };
+
+        let value = self.value.resolve(ctx)?;
+
+        self.map_values(ctx, value, recursive)
+    }
+
+    fn type_def(&self, state: &state::Compiler) -> TypeDef {
+        let td = self.value.type_def(state);
+
+        if td.is_array() {
+            TypeDef::new().array_mapped::<(), Kind>(map! { (): Kind::all() })
+        } else {
+            TypeDef::new().object::<(), Kind>(map! { (): Kind::all() })
+        }
+    }
+}
@@ -0,0 +1,133 @@
+use vrl::prelude::*;
+
+#[derive(Clone, Copy, Debug)]
+pub struct ParseCsv;
+
+impl Function for ParseCsv {
+    fn identifier(&self) -> &'static str {
+        "parse_csv"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "delimiter",
+                kind: kind::BYTES,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "row",
+                source: r#"parse_csv!(s'foo,bar,"foo "", bar"')"#,
+                result: Ok(r#"["foo", "bar", "foo \", bar"]"#),
+            },
+            Example {
+                title: "custom delimiter",
+                source: r#"parse_csv!("foo bar", delimiter: " ")"#,
+                result: Ok(r#"["foo", "bar"]"#),
+            },
+        ]
+    }
+
+    fn compile(&self, mut arguments: ArgumentList) -> Compiled {
+        let value = arguments.required("value");
+        let delimiter = arguments
+            .optional("delimiter")
+            .unwrap_or_else(|| expr!(","));
+
+        Ok(Box::new(ParseCsvFn { value, delimiter }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ParseCsvFn {
+    value: Box<dyn Expression>,
+    delimiter: Box<dyn Expression>,
+}
+
+impl Expression for ParseCsvFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let bytes = self.value.resolve(ctx)?.try_bytes()?;
+
+        let delimiter = self.delimiter.resolve(ctx)?.try_bytes()?;
+        if delimiter.len() != 1 {
+            return Err("delimiter must be a single character".into());
+        }
+
+        let mut reader = csv::ReaderBuilder::new()
+            .has_headers(false)
+            .delimiter(delimiter[0])
+            .from_reader(&*bytes);
+
+        let record = match reader.byte_records().next() {
+            Some(record) => record.map_err(|err| format!("invalid csv record: {}", err))?,
+            None => return Ok(Value::Array(vec![])),
+        };
+
+        Ok(record
+            .iter()
+            .map(|field| Value::from(Bytes::copy_from_slice(field)))
+            .collect::<Vec<_>>()
+            .into())
+    }
+
+    fn type_def(&self, _: &state::Compiler) -> TypeDef {
+        TypeDef::new()
+            .fallible()
+            .array_mapped::<(), Kind>(map! { (): Kind::Bytes })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        parse_csv => ParseCsv;
+
+        valid {
+            args: func_args![value: "foo,bar,\"foo \"\", bar\""],
+            want: Ok(value!(["foo", "bar", "foo \", bar"])),
+            tdef: TypeDef::new().fallible().array_mapped::<(), Kind>(map! { (): Kind::Bytes }),
+        }
+
+        empty_fields {
+            args: func_args![value: "foo,,bar"],
+            want: Ok(value!(["foo", "", "bar"])),
+            tdef: TypeDef::new().fallible().array_mapped::<(), Kind>(map! { (): Kind::Bytes }),
+        }
+
+        empty_string {
+            args: func_args![value: ""],
+            want: Ok(value!([])),
+            tdef: TypeDef::new().fallible().array_mapped::<(), Kind>(map! { (): Kind::Bytes }),
+        }
+
+        only_first_row {
+            args: func_args![value: "foo,bar\nbaz,qux"],
+            want: Ok(value!(["foo", "bar"])),
+            tdef: TypeDef::new().fallible().array_mapped::<(), Kind>(map! { (): Kind::Bytes }),
+        }
+
+        custom_delimiter {
+            args: func_args![value: "foo bar", delimiter: " "],
+            want: Ok(value!(["foo", "bar"])),
+            tdef: TypeDef::new().fallible().array_mapped::<(), Kind>(map! { (): Kind::Bytes }),
+        }
+
+        invalid_delimiter {
+            args: func_args![value: "foo bar", delimiter: ",,"],
+            want: Err("delimiter must be a single character"),
+            tdef: TypeDef::new().fallible().array_mapped::<(), Kind>(map! { (): Kind::Bytes }),
+        }
+    ];
+}
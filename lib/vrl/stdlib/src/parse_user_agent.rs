@@ -0,0 +1,143 @@
+use std::collections::BTreeMap;
+use vrl::prelude::*;
+use woothee::parser::Parser;
+
+#[derive(Clone, Copy, Debug)]
+pub struct ParseUserAgent;
+
+impl Function for ParseUserAgent {
+    fn identifier(&self) -> &'static str {
+        "parse_user_agent"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "browser",
+                source: r#"parse_user_agent("Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/91.0.4472.124 Safari/537.36")"#,
+                result: Ok(
+                    r#"{ "browser": { "family": "Chrome", "version": "91.0.4472.124" }, "os": { "family": "Mac OSX", "version": "10.15.7" }, "device": { "category": "pc" } }"#,
+                ),
+            },
+            Example {
+                title: "unknown",
+                source: r#"parse_user_agent("foo")"#,
+                result: Ok(
+                    r#"{ "browser": { "family": null, "version": null }, "os": { "family": null, "version": null }, "device": { "category": null } }"#,
+                ),
+            },
+        ]
+    }
+
+    fn compile(&self, mut arguments: ArgumentList) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(Box::new(ParseUserAgentFn { value }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ParseUserAgentFn {
+    value: Box<dyn Expression>,
+}
+
+impl Expression for ParseUserAgentFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let agent = value.try_bytes_utf8_lossy()?;
+
+        let parsed = Parser::new().parse(&agent);
+        let field = |f: fn(&woothee::parser::WootheeResult<'_>) -> String| -> Value {
+            parsed
+                .as_ref()
+                .map(f)
+                .filter(|value| !value.is_empty() && value != "UNKNOWN")
+                .into()
+        };
+
+        let mut browser = BTreeMap::new();
+        browser.insert("family".to_owned(), field(|r| r.name.to_string()));
+        browser.insert("version".to_owned(), field(|r| r.version.to_string()));
+
+        let mut os = BTreeMap::new();
+        os.insert("family".to_owned(), field(|r| r.os.to_string()));
+        os.insert("version".to_owned(), field(|r| r.os_version.to_string()));
+
+        let mut device = BTreeMap::new();
+        device.insert("category".to_owned(), field(|r| r.category.to_string()));
+
+        let mut map = BTreeMap::new();
+        map.insert("browser".to_owned(), browser.into());
+        map.insert("os".to_owned(), os.into());
+        map.insert("device".to_owned(), device.into());
+
+        Ok(map.into())
+    }
+
+    fn type_def(&self, _: &state::Compiler) -> TypeDef {
+        TypeDef::new().infallible().object(type_def())
+    }
+}
+
+fn type_def() -> BTreeMap<&'static str, TypeDef> {
+    map! {
+        "browser": TypeDef::new().object::<&'static str, Kind>(map! {
+            "family": Kind::Bytes | Kind::Null,
+            "version": Kind::Bytes | Kind::Null,
+        }),
+        "os": TypeDef::new().object::<&'static str, Kind>(map! {
+            "family": Kind::Bytes | Kind::Null,
+            "version": Kind::Bytes | Kind::Null,
+        }),
+        "device": TypeDef::new().object::<&'static str, Kind>(map! {
+            "category": Kind::Bytes | Kind::Null,
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        parse_user_agent => ParseUserAgent;
+
+        desktop {
+            args: func_args![value: "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/91.0.4472.124 Safari/537.36"],
+            want: Ok(value!({
+                browser: { family: "Chrome", version: "91.0.4472.124" },
+                os: { family: "Mac OSX", version: "10.15.7" },
+                device: { category: "pc" },
+            })),
+            tdef: TypeDef::new().infallible().object::<&'static str, TypeDef>(type_def()),
+        }
+
+        mobile {
+            args: func_args![value: "Mozilla/5.0 (iPhone; CPU iPhone OS 14_6 like Mac OS X) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/14.1.1 Mobile/15E148 Safari/604.1"],
+            want: Ok(value!({
+                browser: { family: "Safari", version: "14.1.1" },
+                os: { family: "iPhone", version: "14.6" },
+                device: { category: "smartphone" },
+            })),
+            tdef: TypeDef::new().infallible().object::<&'static str, TypeDef>(type_def()),
+        }
+
+        unknown {
+            args: func_args![value: "foo"],
+            want: Ok(value!({
+                browser: { family: null, version: null },
+                os: { family: null, version: null },
+                device: { category: null },
+            })),
+            tdef: TypeDef::new().infallible().object::<&'static str, TypeDef>(type_def()),
+        }
+    ];
+}
@@ -0,0 +1,380 @@
+use roxmltree::{Document, Node, NodeType};
+use std::collections::BTreeMap;
+use vrl::prelude::*;
+
+/// The deepest elements are nested, so that converting a document doesn't
+/// overflow the stack.
+const MAX_DEPTH: usize = 128;
+
+#[derive(Clone, Copy, Debug)]
+pub struct ParseXml;
+
+impl Function for ParseXml {
+    fn identifier(&self) -> &'static str {
+        "parse_xml"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "include_attr",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
+            Parameter {
+                keyword: "attr_prefix",
+                kind: kind::BYTES,
+                required: false,
+            },
+            Parameter {
+                keyword: "text_key",
+                kind: kind::BYTES,
+                required: false,
+            },
+            Parameter {
+                keyword: "always_use_text_key",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
+            Parameter {
+                keyword: "parse_bool",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
+            Parameter {
+                keyword: "parse_null",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
+            Parameter {
+                keyword: "parse_number",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "document",
+                source: r#"parse_xml!(s'<book category="fiction"><title lang="en">Dune</title><year>1965</year></book>')"#,
+                result: Ok(
+                    r#"{ "book": { "@category": "fiction", "title": { "@lang": "en", "text": "Dune" }, "year": 1965 } }"#,
+                ),
+            },
+            Example {
+                title: "custom keys",
+                source: r#"parse_xml!(s'<title lang="en">Dune</title>', attr_prefix: "_", text_key: "value")"#,
+                result: Ok(r#"{ "title": { "_lang": "en", "value": "Dune" } }"#),
+            },
+        ]
+    }
+
+    fn compile(&self, mut arguments: ArgumentList) -> Compiled {
+        let value = arguments.required("value");
+        let include_attr = arguments
+            .optional("include_attr")
+            .unwrap_or_else(|| expr!(true));
+        let attr_prefix = arguments
+            .optional("attr_prefix")
+            .unwrap_or_else(|| expr!("@"));
+        let text_key = arguments
+            .optional("text_key")
+            .unwrap_or_else(|| expr!("text"));
+        let always_use_text_key = arguments
+            .optional("always_use_text_key")
+            .unwrap_or_else(|| expr!(false));
+        let parse_bool = arguments
+            .optional("parse_bool")
+            .unwrap_or_else(|| expr!(true));
+        let parse_null = arguments
+            .optional("parse_null")
+            .unwrap_or_else(|| expr!(true));
+        let parse_number = arguments
+            .optional("parse_number")
+            .unwrap_or_else(|| expr!(true));
+
+        Ok(Box::new(ParseXmlFn {
+            value,
+            include_attr,
+            attr_prefix,
+            text_key,
+            always_use_text_key,
+            parse_bool,
+            parse_null,
+            parse_number,
+        }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ParseXmlFn {
+    value: Box<dyn Expression>,
+    include_attr: Box<dyn Expression>,
+    attr_prefix: Box<dyn Expression>,
+    text_key: Box<dyn Expression>,
+    always_use_text_key: Box<dyn Expression>,
+    parse_bool: Box<dyn Expression>,
+    parse_null: Box<dyn Expression>,
+    parse_number: Box<dyn Expression>,
+}
+
+impl Expression for ParseXmlFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let xml = value.try_bytes_utf8_lossy()?;
+
+        let options = Options {
+            include_attr: self.include_attr.resolve(ctx)?.try_boolean()?,
+            attr_prefix: self
+                .attr_prefix
+                .resolve(ctx)?
+                .try_bytes_utf8_lossy()?
+                .into_owned(),
+            text_key: self
+                .text_key
+                .resolve(ctx)?
+                .try_bytes_utf8_lossy()?
+                .into_owned(),
+            always_use_text_key: self.always_use_text_key.resolve(ctx)?.try_boolean()?,
+            parse_bool: self.parse_bool.resolve(ctx)?.try_boolean()?,
+            parse_null: self.parse_null.resolve(ctx)?.try_boolean()?,
+            parse_number: self.parse_number.resolve(ctx)?.try_boolean()?,
+        };
+
+        let document =
+            Document::parse(&xml).map_err(|err| format!("unable to parse xml: {}", err))?;
+        let root = document.root_element();
+
+        let mut map = BTreeMap::new();
+        map.insert(
+            root.tag_name().name().to_owned(),
+            element_to_value(root, &options, 1)?,
+        );
+
+        Ok(map.into())
+    }
+
+    fn type_def(&self, _: &state::Compiler) -> TypeDef {
+        TypeDef::new().fallible().object::<(), Kind>(map! {
+            (): Kind::all(),
+        })
+    }
+}
+
+struct Options {
+    include_attr: bool,
+    attr_prefix: String,
+    text_key: String,
+    always_use_text_key: bool,
+    parse_bool: bool,
+    parse_null: bool,
+    parse_number: bool,
+}
+
+/// Convert an element to a value.
+///
+/// Elements holding only text resolve to the (coerced) text, unless
+/// `always_use_text_key` is set. All other elements resolve to an object of
+/// their attributes, child elements (grouped into an array if a tag name is
+/// repeated), and text, stored under the text key.
+fn element_to_value(node: Node<'_, '_>, options: &Options, depth: usize) -> Result<Value> {
+    if depth > MAX_DEPTH {
+        return Err(format!(
+            "unable to parse xml: elements are nested more than {} levels deep",
+            MAX_DEPTH
+        )
+        .into());
+    }
+
+    let attributes = if options.include_attr {
+        node.attributes()
+    } else {
+        &[]
+    };
+
+    let children = node
+        .children()
+        .filter(|child| child.is_element() || is_text(child))
+        .collect::<Vec<_>>();
+
+    if attributes.is_empty() && !options.always_use_text_key {
+        match children.as_slice() {
+            [] => return Ok(Value::Null),
+            [child] if is_text(child) => {
+                return Ok(coerce(child.text().unwrap_or_default(), options))
+            }
+            _ => {}
+        }
+    }
+
+    let mut map = BTreeMap::new();
+
+    for attribute in attributes {
+        map.insert(
+            format!("{}{}", options.attr_prefix, attribute.name()),
+            coerce(attribute.value(), options),
+        );
+    }
+
+    let mut text = vec![];
+    for child in children {
+        if child.is_element() {
+            let key = child.tag_name().name().to_owned();
+            let value = element_to_value(child, options, depth + 1)?;
+
+            match map.remove(&key) {
+                Some(Value::Array(mut values)) => {
+                    values.push(value);
+                    map.insert(key, Value::Array(values));
+                }
+                Some(existing) => {
+                    map.insert(key, Value::Array(vec![existing, value]));
+                }
+                None => {
+                    map.insert(key, value);
+                }
+            }
+        } else if let Some(t) = child.text() {
+            text.push(t.trim());
+        }
+    }
+
+    if !text.is_empty() {
+        map.insert(options.text_key.clone(), coerce(&text.join(" "), options));
+    }
+
+    Ok(map.into())
+}
+
+/// Whether the node is text that isn't only formatting whitespace.
+fn is_text(node: &Node<'_, '_>) -> bool {
+    node.node_type() == NodeType::Text && !node.text().unwrap_or_default().trim().is_empty()
+}
+
+/// Coerce text to a boolean, null or number, if enabled.
+fn coerce(text: &str, options: &Options) -> Value {
+    let text = text.trim();
+
+    match text {
+        "true" if options.parse_bool => true.into(),
+        "false" if options.parse_bool => false.into(),
+        "null" if options.parse_null => Value::Null,
+        _ if options.parse_number => {
+            if let Ok(integer) = text.parse::<i64>() {
+                integer.into()
+            } else if let Ok(float) = text.parse::<f64>() {
+                if float.is_finite() {
+                    float.into()
+                } else {
+                    text.into()
+                }
+            } else {
+                text.into()
+            }
+        }
+        _ => text.into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        parse_xml => ParseXml;
+
+        simple_text {
+            args: func_args![value: "<root>foo</root>"],
+            want: Ok(value!({ "root": "foo" })),
+            tdef: TypeDef::new().fallible().object::<(), Kind>(map! { (): Kind::all() }),
+        }
+
+        nested_elements {
+            args: func_args![value: r#"<book category="fiction"><title lang="en">Dune</title><year>1965</year></book>"#],
+            want: Ok(value!({
+                "book": {
+                    "@category": "fiction",
+                    "title": { "@lang": "en", "text": "Dune" },
+                    "year": 1965,
+                },
+            })),
+            tdef: TypeDef::new().fallible().object::<(), Kind>(map! { (): Kind::all() }),
+        }
+
+        repeated_elements {
+            args: func_args![value: "<list><item>1</item><item>2</item><item>3</item></list>"],
+            want: Ok(value!({ "list": { "item": [1, 2, 3] } })),
+            tdef: TypeDef::new().fallible().object::<(), Kind>(map! { (): Kind::all() }),
+        }
+
+        mixed_content {
+            args: func_args![value: "<p>\n  hello <b>world</b>\n</p>"],
+            want: Ok(value!({ "p": { "b": "world", "text": "hello" } })),
+            tdef: TypeDef::new().fallible().object::<(), Kind>(map! { (): Kind::all() }),
+        }
+
+        empty_element {
+            args: func_args![value: "<root><empty/></root>"],
+            want: Ok(value!({ "root": { "empty": null } })),
+            tdef: TypeDef::new().fallible().object::<(), Kind>(map! { (): Kind::all() }),
+        }
+
+        coercion {
+            args: func_args![value: "<root><a>true</a><b>null</b><c>1.5</c><d>007</d></root>"],
+            want: Ok(value!({ "root": { "a": true, "b": null, "c": 1.5, "d": 7 } })),
+            tdef: TypeDef::new().fallible().object::<(), Kind>(map! { (): Kind::all() }),
+        }
+
+        no_coercion {
+            args: func_args![
+                value: "<root><a>true</a><b>null</b><c>1.5</c></root>",
+                parse_bool: false,
+                parse_null: false,
+                parse_number: false,
+            ],
+            want: Ok(value!({ "root": { "a": "true", "b": "null", "c": "1.5" } })),
+            tdef: TypeDef::new().fallible().object::<(), Kind>(map! { (): Kind::all() }),
+        }
+
+        custom_keys {
+            args: func_args![
+                value: r#"<title lang="en">Dune</title>"#,
+                attr_prefix: "_",
+                text_key: "value",
+            ],
+            want: Ok(value!({ "title": { "_lang": "en", "value": "Dune" } })),
+            tdef: TypeDef::new().fallible().object::<(), Kind>(map! { (): Kind::all() }),
+        }
+
+        exclude_attributes {
+            args: func_args![value: r#"<title lang="en">Dune</title>"#, include_attr: false],
+            want: Ok(value!({ "title": "Dune" })),
+            tdef: TypeDef::new().fallible().object::<(), Kind>(map! { (): Kind::all() }),
+        }
+
+        always_use_text_key {
+            args: func_args![value: "<title>Dune</title>", always_use_text_key: true],
+            want: Ok(value!({ "title": { "text": "Dune" } })),
+            tdef: TypeDef::new().fallible().object::<(), Kind>(map! { (): Kind::all() }),
+        }
+
+        too_deeply_nested {
+            args: func_args![value: format!("{}{}", "<a>".repeat(MAX_DEPTH + 1), "</a>".repeat(MAX_DEPTH + 1))],
+            want: Err("unable to parse xml: elements are nested more than 128 levels deep"),
+            tdef: TypeDef::new().fallible().object::<(), Kind>(map! { (): Kind::all() }),
+        }
+
+        invalid {
+            args: func_args![value: "<root>"],
+            want: Err("unable to parse xml: the root node was opened but never closed"),
+            tdef: TypeDef::new().fallible().object::<(), Kind>(map! { (): Kind::all() }),
+        }
+    ];
+}
@@ -117,3 +117,82 @@ impl FromStr for Base64Charset {
         }
     }
 }
+
+/// Resolve the `fields_ordering` argument to the list of field names it holds.
+#[cfg(any(feature = "encode_csv", feature = "encode_key_value"))]
+pub(crate) fn fields_ordering(value: Value) -> Result<Vec<String>, vrl::prelude::ExpressionError> {
+    value
+        .try_array()?
+        .into_iter()
+        .map(|field| match field {
+            Value::Bytes(bytes) => Ok(String::from_utf8_lossy(&bytes).into_owned()),
+            field => Err(format!(
+                "fields_ordering contains a non-string element: {}",
+                field.kind()
+            )
+            .into()),
+        })
+        .collect()
+}
+
+/// Encode a non-container value the same way `to_string` would.
+#[cfg(any(feature = "encode_csv", feature = "encode_key_value"))]
+pub(crate) fn encode_scalar(value: Value) -> String {
+    use chrono::SecondsFormat;
+
+    match value {
+        Value::Bytes(bytes) => String::from_utf8_lossy(&bytes).into_owned(),
+        Value::Timestamp(timestamp) => timestamp.to_rfc3339_opts(SecondsFormat::AutoSi, true),
+        Value::Null => String::new(),
+        value => value.to_string(),
+    }
+}
+
+/// The maximum size of the values decompressed by the `decode_*` functions, so that a small
+/// payload can't expand into a value exhausting the memory.
+#[cfg(any(
+    feature = "decode_gzip",
+    feature = "decode_snappy",
+    feature = "decode_zlib",
+    feature = "decode_zstd"
+))]
+pub(crate) const MAX_DECOMPRESSED_SIZE: usize = 64 * 1024 * 1024;
+
+/// Read the value decompressed by the given codec's decoder, failing if it's larger than
+/// `MAX_DECOMPRESSED_SIZE`.
+#[cfg(any(
+    feature = "decode_gzip",
+    feature = "decode_zlib",
+    feature = "decode_zstd"
+))]
+pub(crate) fn read_decompressed(
+    decoder: impl std::io::Read,
+    codec: &str,
+) -> Result<Vec<u8>, String> {
+    use std::io::Read;
+
+    let mut buf = Vec::new();
+    decoder
+        .take(MAX_DECOMPRESSED_SIZE as u64 + 1)
+        .read_to_end(&mut buf)
+        .map_err(|err| format!("unable to decode value with {} decoder: {}", codec, err))?;
+
+    if buf.len() > MAX_DECOMPRESSED_SIZE {
+        return Err(decompressed_too_large(codec));
+    }
+
+    Ok(buf)
+}
+
+#[cfg(any(
+    feature = "decode_gzip",
+    feature = "decode_snappy",
+    feature = "decode_zlib",
+    feature = "decode_zstd"
+))]
+pub(crate) fn decompressed_too_large(codec: &str) -> String {
+    format!(
+        "unable to decode value with {} decoder: decoded value is larger than {} bytes",
+        codec, MAX_DECOMPRESSED_SIZE
+    )
+}
@@ -1,4 +1,6 @@
 pub mod docs;
+mod run;
 mod test;
 
+pub use run::{Outcome, Stage};
 pub use test::Test;
@@ -1,11 +1,8 @@
 use ansi_term::Colour;
-use chrono::{DateTime, SecondsFormat, Utc};
 use glob::glob;
-use std::str::FromStr;
 use structopt::StructOpt;
-use vrl::{diagnostic::Formatter, state, Runtime, Value};
 
-use vrl_tests::{docs, Test};
+use vrl_tests::{docs, Outcome, Test};
 
 #[derive(Debug, StructOpt)]
 #[structopt(name = "VRL Tests", about = "Vector Remap Language Tests")]
@@ -86,11 +83,11 @@ fn main() {
 
     for mut test in tests {
         if category != test.category {
-            category = test.category;
+            category = test.category.clone();
             println!("{}", Colour::Fixed(3).bold().paint(category.to_string()));
         }
 
-        if let Some(err) = test.error {
+        if let Some(err) = &test.error {
             println!("{}", Colour::Purple.bold().paint("INVALID"));
             println!("{}", Colour::Red.paint(err));
             failed_count += 1;
@@ -108,153 +105,35 @@ fn main() {
             println!("{}", Colour::Yellow.bold().paint("SKIPPED"));
         }
 
-        let state = state::Runtime::default();
-        let mut runtime = Runtime::new(state);
-        let program = vrl::compile(&test.source, &stdlib::all());
+        let outcome = test.run(&stdlib::all());
+        let failed = !test.skip && outcome.is_failed();
 
-        let want = test.result;
+        if !test.skip {
+            match &outcome {
+                Outcome::Passed { .. } => println!("{}", Colour::Green.bold().paint("OK")),
+                Outcome::Failed { stage, .. } => {
+                    println!("{} ({})", Colour::Red.bold().paint("FAILED"), stage);
+                    failed_count += 1;
 
-        match program {
-            Ok(program) => {
-                let result = runtime.resolve(&mut test.object, &program);
-
-                match result {
-                    Ok(got) => {
-                        let got = vrl_value_to_json_value(got);
-                        let mut failed = false;
-
-                        if !test.skip {
-                            let want = if want.starts_with("r'") && want.ends_with('\'') {
-                                match regex::Regex::new(
-                                    &want[2..want.len() - 1].replace("\\'", "'"),
-                                ) {
-                                    Ok(want) => want.to_string().into(),
-                                    Err(_) => want.into(),
-                                }
-                            } else if want.starts_with("t'") && want.ends_with('\'') {
-                                match DateTime::<Utc>::from_str(&want[2..want.len() - 1]) {
-                                    Ok(want) => {
-                                        want.to_rfc3339_opts(SecondsFormat::AutoSi, true).into()
-                                    }
-                                    Err(_) => want.into(),
-                                }
-                            } else if want.starts_with("s'") && want.ends_with('\'') {
-                                want[2..want.len() - 1].into()
-                            } else {
-                                match serde_json::from_str::<'_, serde_json::Value>(&want.trim()) {
-                                    Ok(want) => want,
-                                    Err(err) => {
-                                        eprintln!("{}", err);
-                                        want.into()
-                                    }
-                                }
-                            };
-
-                            if got == want {
-                                println!("{}", Colour::Green.bold().paint("OK"));
-                            } else {
-                                println!("{} (expectation)", Colour::Red.bold().paint("FAILED"));
-                                failed_count += 1;
-
-                                if !cmd.no_diff {
-                                    let want = serde_json::to_string_pretty(&want).unwrap();
-                                    let got = serde_json::to_string_pretty(&got).unwrap();
-
-                                    let diff = prettydiff::diff_lines(&want, &got);
-                                    println!("  {}", diff);
-                                }
-
-                                failed = true;
-                            }
-                        }
-
-                        if cmd.verbose {
-                            println!("{:#}", got);
-                        }
-
-                        if failed && cmd.fail_early {
-                            std::process::exit(1)
-                        }
-                    }
-                    Err(err) => {
-                        let mut failed = false;
-                        if !test.skip {
-                            let got = err.to_string().trim().to_owned();
-                            let want = want.trim().to_owned();
-
-                            if (test.result_approx && compare_partial_diagnostic(&got, &want))
-                                || got == want
-                            {
-                                println!("{}", Colour::Green.bold().paint("OK"));
-                            } else {
-                                println!("{} (runtime)", Colour::Red.bold().paint("FAILED"));
-                                failed_count += 1;
-
-                                if !cmd.no_diff {
-                                    let diff = prettydiff::diff_lines(&want, &got);
-                                    println!("{}", diff);
-                                }
-
-                                failed = true;
-                            }
-                        }
-
-                        if cmd.verbose {
-                            println!("{:#}", err);
-                        }
-
-                        if failed && cmd.fail_early {
-                            std::process::exit(1)
-                        }
+                    if !cmd.no_diff {
+                        println!("{}", outcome.diff().unwrap_or_default());
                     }
                 }
             }
-            Err(diagnostics) => {
-                let mut failed = false;
-                let mut formatter = Formatter::new(&test.source, diagnostics);
-                if !test.skip {
-                    let got = formatter.to_string().trim().to_owned();
-                    let want = want.trim().to_owned();
-
-                    if (test.result_approx && compare_partial_diagnostic(&got, &want))
-                        || got == want
-                    {
-                        println!("{}", Colour::Green.bold().paint("OK"));
-                    } else {
-                        println!("{} (compilation)", Colour::Red.bold().paint("FAILED"));
-                        failed_count += 1;
-
-                        if !cmd.no_diff {
-                            let diff = prettydiff::diff_lines(&want, &got);
-                            println!("{}", diff);
-                        }
-
-                        failed = true;
-                    }
-                }
+        }
 
-                if cmd.verbose {
-                    formatter.enable_colors(true);
-                    println!("{:#}", formatter);
-                }
+        if cmd.verbose {
+            println!("{}", outcome.output());
+        }
 
-                if failed && cmd.fail_early {
-                    std::process::exit(1)
-                }
-            }
+        if failed && cmd.fail_early {
+            std::process::exit(1)
         }
     }
 
     print_result(failed_count)
 }
 
-fn compare_partial_diagnostic(got: &str, want: &str) -> bool {
-    got.lines()
-        .filter(|line| line.trim().starts_with("error[E"))
-        .zip(want.trim().lines())
-        .all(|(got, want)| got.contains(want))
-}
-
 fn print_result(failed_count: usize) {
     let code = if failed_count > 0 { 1 } else { 0 };
 
@@ -275,22 +154,3 @@ fn print_result(failed_count: usize) {
 
     std::process::exit(code)
 }
-
-fn vrl_value_to_json_value(value: Value) -> serde_json::Value {
-    use serde_json::Value::*;
-    use std::iter::FromIterator;
-
-    match value {
-        v @ Value::Bytes(_) => String(v.try_bytes_utf8_lossy().unwrap().into_owned()),
-        Value::Integer(v) => v.into(),
-        Value::Float(v) => v.into_inner().into(),
-        Value::Boolean(v) => v.into(),
-        Value::Object(v) => serde_json::Value::from_iter(
-            v.into_iter().map(|(k, v)| (k, vrl_value_to_json_value(v))),
-        ),
-        Value::Array(v) => serde_json::Value::from_iter(v.into_iter().map(vrl_value_to_json_value)),
-        Value::Timestamp(v) => v.to_rfc3339_opts(SecondsFormat::AutoSi, true).into(),
-        Value::Regex(v) => v.to_string().into(),
-        Value::Null => Null,
-    }
-}
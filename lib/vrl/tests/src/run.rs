@@ -0,0 +1,184 @@
+use crate::Test;
+use chrono::{DateTime, SecondsFormat, Utc};
+use std::fmt;
+use std::str::FromStr;
+use vrl::{diagnostic::Formatter, state, Function, Runtime, Value};
+
+/// The outcome of running a [`Test`].
+#[derive(Debug)]
+pub enum Outcome {
+    Passed {
+        /// The result of the program, for verbose reporting.
+        output: String,
+    },
+    Failed {
+        stage: Stage,
+        want: String,
+        got: String,
+
+        /// The result of the program, for verbose reporting.
+        output: String,
+    },
+}
+
+impl Outcome {
+    pub fn is_failed(&self) -> bool {
+        matches!(self, Outcome::Failed { .. })
+    }
+
+    pub fn output(&self) -> &str {
+        match self {
+            Outcome::Passed { output } | Outcome::Failed { output, .. } => output,
+        }
+    }
+
+    /// The line diff between the expected and the actual result of a failed
+    /// test.
+    pub fn diff(&self) -> Option<String> {
+        match self {
+            Outcome::Passed { .. } => None,
+            Outcome::Failed { want, got, .. } => {
+                Some(prettydiff::diff_lines(want, got).to_string())
+            }
+        }
+    }
+}
+
+/// The stage at which a test failed.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Stage {
+    /// The program resolved to an unexpected value.
+    Expectation,
+
+    /// The program failed at runtime with an unexpected error.
+    Runtime,
+
+    /// The program failed to compile with unexpected diagnostics.
+    Compilation,
+}
+
+impl fmt::Display for Stage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Stage::Expectation => f.write_str("expectation"),
+            Stage::Runtime => f.write_str("runtime"),
+            Stage::Compilation => f.write_str("compilation"),
+        }
+    }
+}
+
+impl Test {
+    /// Compile and run the test program against the test object, and compare
+    /// the result with the expected result.
+    pub fn run(&mut self, functions: &[Box<dyn Function>]) -> Outcome {
+        let state = state::Runtime::default();
+        let mut runtime = Runtime::new(state);
+        let program = vrl::compile(&self.source, functions);
+
+        match program {
+            Ok(program) => match runtime.resolve(&mut self.object, &program) {
+                Ok(got) => {
+                    let got = vrl_value_to_json_value(got);
+                    let want = expected_json_value(&self.result);
+                    let output = format!("{:#}", got);
+
+                    if got == want {
+                        Outcome::Passed { output }
+                    } else {
+                        Outcome::Failed {
+                            stage: Stage::Expectation,
+                            want: serde_json::to_string_pretty(&want).unwrap(),
+                            got: serde_json::to_string_pretty(&got).unwrap(),
+                            output,
+                        }
+                    }
+                }
+                Err(err) => {
+                    let output = format!("{:#}", err);
+
+                    self.compare_error(Stage::Runtime, err.to_string(), output)
+                }
+            },
+            Err(diagnostics) => {
+                let mut formatter = Formatter::new(&self.source, diagnostics);
+                let got = formatter.to_string();
+
+                formatter.enable_colors(true);
+                let output = format!("{:#}", formatter);
+
+                self.compare_error(Stage::Compilation, got, output)
+            }
+        }
+    }
+
+    fn compare_error(&self, stage: Stage, got: String, output: String) -> Outcome {
+        let got = got.trim().to_owned();
+        let want = self.result.trim().to_owned();
+
+        if (self.result_approx && compare_partial_diagnostic(&got, &want)) || got == want {
+            Outcome::Passed { output }
+        } else {
+            Outcome::Failed {
+                stage,
+                want,
+                got,
+                output,
+            }
+        }
+    }
+}
+
+/// Parse the expected result of a test.
+///
+/// Regular expressions (`r'...'`), timestamps (`t'...'`) and raw strings
+/// (`s'...'`) are supported, next to JSON values. Any other result is compared
+/// as a string.
+fn expected_json_value(want: &str) -> serde_json::Value {
+    if want.starts_with("r'") && want.ends_with('\'') {
+        match regex::Regex::new(&want[2..want.len() - 1].replace("\\'", "'")) {
+            Ok(want) => want.to_string().into(),
+            Err(_) => want.into(),
+        }
+    } else if want.starts_with("t'") && want.ends_with('\'') {
+        match DateTime::<Utc>::from_str(&want[2..want.len() - 1]) {
+            Ok(want) => want.to_rfc3339_opts(SecondsFormat::AutoSi, true).into(),
+            Err(_) => want.into(),
+        }
+    } else if want.starts_with("s'") && want.ends_with('\'') {
+        want[2..want.len() - 1].into()
+    } else {
+        match serde_json::from_str::<'_, serde_json::Value>(&want.trim()) {
+            Ok(want) => want,
+            Err(err) => {
+                eprintln!("{}", err);
+                want.into()
+            }
+        }
+    }
+}
+
+fn compare_partial_diagnostic(got: &str, want: &str) -> bool {
+    got.lines()
+        .filter(|line| line.trim().starts_with("error[E"))
+        .zip(want.trim().lines())
+        .all(|(got, want)| got.contains(want))
+}
+
+fn vrl_value_to_json_value(value: Value) -> serde_json::Value {
+    use serde_json::Value::*;
+    use std::iter::FromIterator;
+
+    match value {
+        v @ Value::Bytes(_) => String(v.try_bytes_utf8_lossy().unwrap().into_owned()),
+        Value::Integer(v) => v.into(),
+        Value::Float(v) => v.into_inner().into(),
+        Value::Boolean(v) => v.into(),
+        Value::Object(v) => serde_json::Value::from_iter(
+            v.into_iter().map(|(k, v)| (k, vrl_value_to_json_value(v))),
+        ),
+        Value::Array(v) => serde_json::Value::from_iter(v.into_iter().map(vrl_value_to_json_value)),
+        Value::Timestamp(v) => v.to_rfc3339_opts(SecondsFormat::AutoSi, true).into(),
+        Value::Regex(v) => v.to_string().into(),
+        Value::Null => Null,
+    }
+}
@@ -18,18 +18,46 @@ pub struct Test {
 enum CaptureMode {
     Result,
     Object,
+    Program,
     None,
     Done,
 }
 
 impl Test {
     pub fn from_path(path: &Path) -> Self {
+        Self::from_path_in(Path::new("tests"), path)
+    }
+
+    /// Load a test case from a file, stored within the given root directory.
+    ///
+    /// The category of the test is the directory of the file, relative to the
+    /// root directory.
+    pub fn from_path_in(root: &Path, path: &Path) -> Self {
         let name = test_name(path);
-        let category = test_category(&path);
-        let content = fs::read_to_string(path).expect("content");
+        let category = test_category(root, path);
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(err) => {
+                return Self {
+                    name,
+                    category,
+                    error: Some(format!(
+                        "unable to read test file {}: {}",
+                        path.display(),
+                        err
+                    )),
+                    source: String::new(),
+                    object: Value::Null,
+                    result: String::new(),
+                    result_approx: false,
+                    skip: false,
+                }
+            }
+        };
 
         let mut source = String::new();
         let mut object = String::new();
+        let mut program = String::new();
         let mut result = String::new();
         let mut result_approx = false;
         let mut skip = false;
@@ -54,10 +82,17 @@ impl Test {
                 } else if line.starts_with("result:") {
                     capture_mode = CaptureMode::Result;
                     line = line.strip_prefix("result:").expect("result").trim_start();
+                } else if line.starts_with("program:") {
+                    capture_mode = CaptureMode::Program;
+                    line = line.strip_prefix("program:").expect("program").trim_start();
                 }
 
                 match capture_mode {
                     CaptureMode::None | CaptureMode::Done => continue,
+                    CaptureMode::Program => {
+                        program.push_str(line);
+                        capture_mode = CaptureMode::None;
+                    }
                     CaptureMode::Result => {
                         result.push_str(line);
                         result.push('\n');
@@ -75,6 +110,28 @@ impl Test {
         }
 
         let mut error = None;
+
+        // The program can be stored in a separate file, relative to the test
+        // case, so that existing programs can be tested as-is.
+        if !program.is_empty() {
+            if !source.trim().is_empty() {
+                error = Some("test case has both a program file and a program source".to_owned());
+            } else {
+                let program = path.parent().unwrap_or(root).join(program.trim());
+
+                match fs::read_to_string(&program) {
+                    Ok(program) => source = program,
+                    Err(err) => {
+                        error = Some(format!(
+                            "unable to read program file {}: {}",
+                            program.display(),
+                            err
+                        ))
+                    }
+                }
+            }
+        }
+
         let object = if object.is_empty() {
             Value::Object(BTreeMap::default())
         } else {
@@ -121,18 +178,12 @@ impl Test {
     }
 }
 
-fn test_category(path: &Path) -> String {
-    if path.as_os_str() == "tests/example.vrl" {
-        return "".to_owned();
-    }
-
-    path.to_string_lossy()
-        .strip_prefix("tests/")
-        .expect("test")
-        .rsplitn(2, '/')
-        .nth(1)
-        .unwrap()
-        .to_owned()
+fn test_category(root: &Path, path: &Path) -> String {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .parent()
+        .map(|dir| dir.to_string_lossy().into_owned())
+        .unwrap_or_default()
 }
 
 fn test_name(path: &Path) -> String {
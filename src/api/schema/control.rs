@@ -0,0 +1,131 @@
+use crate::{
+    config::{load_builder_from_str, Format},
+    topology::control::{self, ControlRequest, ControlSender},
+};
+use async_graphql::{Context, Enum, Error, Object, Result, SimpleObject};
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq)]
+pub enum ConfigFormat {
+    Toml,
+    Json,
+    Yaml,
+}
+
+impl From<ConfigFormat> for Format {
+    fn from(format: ConfigFormat) -> Self {
+        match format {
+            ConfigFormat::Toml => Format::TOML,
+            ConfigFormat::Json => Format::JSON,
+            ConfigFormat::Yaml => Format::YAML,
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct ConfigValidation {
+    /// Whether the config could be loaded
+    valid: bool,
+
+    /// Errors that prevent the config from being loaded
+    errors: Vec<String>,
+
+    /// Warnings about the config, which don't prevent it from being loaded
+    warnings: Vec<String>,
+}
+
+impl ConfigValidation {
+    fn new(input: &str, format: Format) -> Self {
+        let result = load_builder_from_str(input, Some(format)).and_then(|(builder, warnings)| {
+            let (_, build_warnings) = builder.build_with_warnings()?;
+            Ok(warnings.into_iter().chain(build_warnings).collect())
+        });
+
+        match result {
+            Ok(warnings) => Self {
+                valid: true,
+                errors: Vec::new(),
+                warnings,
+            },
+            Err(errors) => Self {
+                valid: false,
+                errors,
+                warnings: Vec::new(),
+            },
+        }
+    }
+}
+
+/// Sends `request` to the running topology, and waits for it to be handled
+async fn send(ctx: &Context<'_>, request: ControlRequest) -> Result<bool> {
+    let sender = ctx.data::<ControlSender>()?;
+    control::send(sender, request)
+        .await
+        .map(|_| true)
+        .map_err(Error::new)
+}
+
+#[derive(Default)]
+pub struct ControlMutation;
+
+#[Object]
+impl ControlMutation {
+    /// Stops forwarding the events of a source, until it's resumed. The source is back
+    /// pressured in the meantime
+    async fn pause_source(&self, ctx: &Context<'_>, name: String) -> Result<bool> {
+        send(ctx, ControlRequest::PauseSource(name)).await
+    }
+
+    /// Resumes forwarding the events of a paused source
+    async fn resume_source(&self, ctx: &Context<'_>, name: String) -> Result<bool> {
+        send(ctx, ControlRequest::ResumeSource(name)).await
+    }
+
+    /// Reloads the config from disk and applies it, as when Vector receives SIGHUP. Fails
+    /// if the new config couldn't be applied, in which case the previous one is kept
+    async fn reload_config(&self, ctx: &Context<'_>) -> Result<bool> {
+        send(ctx, ControlRequest::ReloadConfig).await
+    }
+
+    /// Checks that a config can be loaded, without applying it. Components aren't built,
+    /// so errors that only occur when starting them aren't reported
+    async fn validate_config(
+        &self,
+        config: String,
+        #[graphql(default_with = "ConfigFormat::Toml")] format: ConfigFormat,
+    ) -> ConfigValidation {
+        ConfigValidation::new(&config, format.into())
+    }
+}
+
+#[cfg(all(test, feature = "sources-stdin", feature = "sinks-blackhole"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validates_config() {
+        let validation = ConfigValidation::new(
+            r#"
+            [sources.in]
+            type = "stdin"
+
+            [sinks.out]
+            type = "blackhole"
+            inputs = ["in"]
+            "#,
+            Format::TOML,
+        );
+        assert!(validation.valid);
+        assert!(validation.errors.is_empty());
+
+        let validation = ConfigValidation::new(
+            r#"
+            [sinks.out]
+            type = "blackhole"
+            inputs = ["missing"]
+            "#,
+            Format::TOML,
+        );
+        assert!(!validation.valid);
+        assert!(!validation.errors.is_empty());
+    }
+}
@@ -0,0 +1,118 @@
+use crate::{
+    conditions::{remap::RemapConfig, ConditionConfig},
+    event::Event,
+    topology::{
+        control::{self, ControlRequest, ControlSender},
+        Tap,
+    },
+};
+use async_graphql::{validators::IntRange, Context, Error, Result, SimpleObject, Subscription};
+use async_stream::stream;
+use tokio::{
+    stream::Stream,
+    sync::mpsc::{self, error::TryRecvError},
+    time::Duration,
+};
+
+#[derive(SimpleObject)]
+pub struct OutputEvent {
+    /// Name of the component that output the event
+    component_name: String,
+
+    /// The event, encoded as JSON
+    json: String,
+}
+
+impl OutputEvent {
+    fn new(component_name: &str, event: &Event) -> Option<Self> {
+        let json = match event {
+            Event::Log(log) => serde_json::to_string(log),
+            Event::Metric(metric) => serde_json::to_string(metric),
+        };
+
+        match json {
+            Ok(json) => Some(Self {
+                component_name: component_name.to_owned(),
+                json,
+            }),
+            Err(error) => {
+                warn!(message = "Failed to encode tapped event.", %error);
+                None
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct TapSubscription;
+
+#[Subscription]
+impl TapSubscription {
+    /// Events output by the given components, as they flow through them. Every `interval`
+    /// milliseconds, up to `limit` events are sampled per component, and the others are
+    /// dropped. If a VRL `filter` condition is given, only the events for which it's true
+    /// are sampled. The stream ends once the components are removed or changed
+    async fn output_events(
+        &self,
+        ctx: &Context<'_>,
+        component_names: Vec<String>,
+        #[graphql(default = 100, validator(IntRange(min = "1", max = "10_000")))] limit: i32,
+        #[graphql(default = 500, validator(IntRange(min = "10", max = "60_000")))] interval: i32,
+        filter: Option<String>,
+    ) -> Result<impl Stream<Item = OutputEvent>> {
+        let condition = match filter {
+            Some(source) => Some(
+                RemapConfig { source }
+                    .build()
+                    .map_err(|error| Error::new(error.to_string()))?,
+            ),
+            None => None,
+        };
+
+        let limit = limit as usize;
+        let sender = ctx.data::<ControlSender>()?;
+        let mut taps = Vec::with_capacity(component_names.len());
+        for name in component_names {
+            // The channel only has room for the events sampled during an interval.
+            let (tx, rx) = mpsc::channel(limit);
+            let tap = Tap::new(tx, condition.clone());
+            control::send(sender, ControlRequest::TapOutput(name.clone(), tap))
+                .await
+                .map_err(Error::new)?;
+            taps.push((name, rx));
+        }
+
+        let mut interval = tokio::time::interval(Duration::from_millis(interval as u64));
+        Ok(stream! {
+            while !taps.is_empty() {
+                interval.tick().await;
+
+                let mut i = 0;
+                while i < taps.len() {
+                    let (name, rx) = &mut taps[i];
+                    let mut closed = false;
+                    for _ in 0..limit {
+                        match rx.try_recv() {
+                            Ok(event) => {
+                                if let Some(event) = OutputEvent::new(name, &event) {
+                                    yield event;
+                                }
+                            }
+                            Err(TryRecvError::Empty) => break,
+                            Err(TryRecvError::Closed) => {
+                                closed = true;
+                                break;
+                            }
+                        }
+                    }
+
+                    if closed {
+                        taps.swap_remove(i);
+                    } else {
+                        i += 1;
+                    }
+                }
+            }
+        })
+    }
+}
@@ -1,5 +1,8 @@
 use super::{handler, schema};
-use crate::config;
+use crate::{
+    config,
+    topology::control::{self, ControlSender},
+};
 use async_graphql::{
     http::{playground_source, GraphQLPlaygroundConfig},
     Request, Schema,
@@ -17,9 +20,17 @@ pub struct Server {
 
 impl Server {
     /// Start the API server. This creates the routes and spawns a Warp server. The server is
-    /// gracefully shut down when Self falls out of scope by way of the oneshot sender closing
+    /// gracefully shut down when Self falls out of scope by way of the oneshot sender closing.
+    /// Mutations that change the topology will fail, see `start_with_control`
     pub fn start(config: &config::Config) -> Self {
-        let routes = make_routes(config.api.playground);
+        let (control, _) = control::control_channel();
+        Self::start_with_control(config, control)
+    }
+
+    /// Start the API server, forwarding the requests of mutations that change the topology
+    /// to `control`
+    pub fn start_with_control(config: &config::Config, control: ControlSender) -> Self {
+        let routes = make_routes(config.api.playground, control);
 
         let (_shutdown, rx) = oneshot::channel();
         let (addr, server) = warp::serve(routes).bind_with_graceful_shutdown(
@@ -51,9 +62,9 @@ impl Server {
     }
 }
 
-fn make_routes(playground: bool) -> BoxedFilter<(impl Reply,)> {
+fn make_routes(playground: bool, control: ControlSender) -> BoxedFilter<(impl Reply,)> {
     // Build the GraphQL schema
-    let schema = schema::build_schema().finish();
+    let schema = schema::build_schema().data(control).finish();
 
     // Routes...
 
@@ -1,6 +1,9 @@
 use crate::cli::{handle_config_errors, Color, LogFormat, Opts, RootOpts, SubCommand};
 use crate::signal::SignalTo;
-use crate::topology::RunningTopology;
+use crate::topology::{
+    control::{self, ControlRequest},
+    RunningTopology,
+};
 use crate::{
     config, generate, heartbeat, list, metrics, signal, topology, trace, unit_test, validate,
 };
@@ -13,10 +16,10 @@ use tokio::sync::mpsc;
 
 #[cfg(feature = "sources-host_metrics")]
 use crate::sources::host_metrics;
-#[cfg(feature = "api-client")]
-use crate::top;
 #[cfg(feature = "api")]
 use crate::{api, internal_events::ApiStarted};
+#[cfg(feature = "api-client")]
+use crate::{tap, top};
 
 #[cfg(windows)]
 use crate::service;
@@ -117,6 +120,8 @@ impl Application {
                         SubCommand::Generate(g) => generate::cmd(&g),
                         #[cfg(feature = "api-client")]
                         SubCommand::Top(t) => top::cmd(&t).await,
+                        #[cfg(feature = "api-client")]
+                        SubCommand::Tap(t) => tap::cmd(&t).await,
                         #[cfg(windows)]
                         SubCommand::Service(s) => service::cmd(&s),
                         #[cfg(feature = "vrl-cli")]
@@ -206,6 +211,10 @@ impl Application {
             emit!(VectorStarted);
             tokio::spawn(heartbeat::heartbeat());
 
+            // Requests to change the topology, made through the API.
+            #[cfg_attr(not(feature = "api"), allow(unused_variables))]
+            let (control_tx, mut control_rx) = control::control_channel();
+
             #[cfg(feature = "api")]
             // assigned to prevent the API terminating when falling out of scope
             let api_server = if api_config.enabled {
@@ -214,7 +223,7 @@ impl Application {
                     playground: api_config.playground
                 });
 
-                Some(api::Server::start(topology.config()))
+                Some(api::Server::start_with_control(topology.config(), control_tx))
             } else {
                 info!(message="API is disabled, enable by setting `api.enabled` to `true` and use commands like `vector top`.");
                 None
@@ -228,40 +237,60 @@ impl Application {
                 tokio::select! {
                 Some(signal) = signals.next() => {
                     if signal == SignalTo::Reload {
-                        // Reload paths
-                        config_paths = config::process_paths(&opts.config_paths_with_formats()).unwrap_or(config_paths);
-                        // Reload config
-                        let new_config = config::load_from_paths(&config_paths).map_err(handle_config_errors).ok();
-
-                        if let Some(mut new_config) = new_config {
-                            new_config.healthchecks.set_require_healthy(opts.require_healthy);
-                            match topology
-                                .reload_config_and_respawn(new_config)
-                                .await
-                            {
+                        match reload_config(&mut topology, &mut config_paths, &opts).await {
+                            Ok(true) => {
+                                #[cfg(feature="api")]
+                                if let Some(ref api_server) = api_server {
+                                    api_server.update_config(topology.config())
+                                }
+                            }
+                            Ok(false) => (),
+                            // Trigger graceful shutdown for what remains of the topology
+                            Err(()) => break SignalTo::Shutdown,
+                        }
+                        sources_finished = topology.sources_finished();
+                    } else {
+                        break signal;
+                    }
+                }
+                Some((request, respond)) = control_rx.recv() => {
+                    let result = match request {
+                        ControlRequest::PauseSource(name) => if topology.pause_source(&name) {
+                            Ok(())
+                        } else {
+                            Err(format!("Source {:?} doesn't exist.", name))
+                        },
+                        ControlRequest::ResumeSource(name) => if topology.resume_source(&name) {
+                            Ok(())
+                        } else {
+                            Err(format!("Source {:?} doesn't exist.", name))
+                        },
+                        ControlRequest::TapOutput(name, tap) => if topology.tap_output(&name, tap) {
+                            Ok(())
+                        } else {
+                            Err(format!("Component {:?} doesn't exist or has no output.", name))
+                        },
+                        ControlRequest::ReloadConfig => {
+                            let reloaded = reload_config(&mut topology, &mut config_paths, &opts).await;
+                            sources_finished = topology.sources_finished();
+                            match reloaded {
                                 Ok(true) => {
                                     #[cfg(feature="api")]
                                     if let Some(ref api_server) = api_server {
                                         api_server.update_config(topology.config())
                                     }
-
-                                    emit!(VectorReloaded { config_paths: &config_paths })
-                                },
-                                Ok(false) => emit!(VectorReloadFailed),
-                                // Trigger graceful shutdown for what remains of the topology
+                                    Ok(())
+                                }
+                                Ok(false) => Err("Failed to reload the config, the previous one is still in use.".to_owned()),
                                 Err(()) => {
-                                    emit!(VectorReloadFailed);
-                                    emit!(VectorRecoveryFailed);
+                                    let _ = respond.send(Err("Failed to recover from a failed reload, shutting down.".to_owned()));
                                     break SignalTo::Shutdown;
                                 }
                             }
-                            sources_finished = topology.sources_finished();
-                        } else {
-                            emit!(VectorConfigLoadFailed);
                         }
-                    } else {
-                        break signal;
-                    }
+                    };
+                    // The requester may have gone away, which is fine.
+                    let _ = respond.send(result);
                 }
                 // Trigger graceful shutdown if a component crashed, or all sources have ended.
                 _ = graceful_crash.next() => break SignalTo::Shutdown,
@@ -292,3 +321,45 @@ impl Application {
         });
     }
 }
+
+/// Reloads the config from the config paths and applies it to the topology.
+/// Returns `Ok(false)` if the previous config is still in use, and `Err` if
+/// the topology couldn't recover from a failed reload.
+async fn reload_config(
+    topology: &mut RunningTopology,
+    config_paths: &mut Vec<(PathBuf, config::FormatHint)>,
+    opts: &RootOpts,
+) -> Result<bool, ()> {
+    // Reload paths
+    if let Some(paths) = config::process_paths(&opts.config_paths_with_formats()) {
+        *config_paths = paths;
+    }
+    // Reload config
+    let new_config = config::load_from_paths(config_paths)
+        .map_err(handle_config_errors)
+        .ok();
+
+    if let Some(mut new_config) = new_config {
+        new_config
+            .healthchecks
+            .set_require_healthy(opts.require_healthy);
+        match topology.reload_config_and_respawn(new_config).await {
+            Ok(true) => {
+                emit!(VectorReloaded { config_paths });
+                Ok(true)
+            }
+            Ok(false) => {
+                emit!(VectorReloadFailed);
+                Ok(false)
+            }
+            Err(()) => {
+                emit!(VectorReloadFailed);
+                emit!(VectorRecoveryFailed);
+                Err(())
+            }
+        }
+    } else {
+        emit!(VectorConfigLoadFailed);
+        Ok(false)
+    }
+}
@@ -1,4 +1,4 @@
-use crate::event::{proto, Event};
+use crate::event::{proto, Event, EventFinalizers, EventStatus};
 use bytes::Bytes;
 use futures01::{
     task::{self, AtomicTask, Task},
@@ -54,6 +54,7 @@ pub struct Writer {
     blocked_write_tasks: Arc<Mutex<Vec<Task>>>,
     writebatch: Writebatch<Key>,
     batch_size: usize,
+    batch_finalizers: EventFinalizers,
     max_size: usize,
     current_size: Arc<AtomicUsize>,
 }
@@ -70,6 +71,7 @@ impl Clone for Writer {
             blocked_write_tasks: Arc::clone(&self.blocked_write_tasks),
             writebatch: Writebatch::new(),
             batch_size: 0,
+            batch_finalizers: EventFinalizers::default(),
             max_size: self.max_size,
             current_size: Arc::clone(&self.current_size),
         }
@@ -82,8 +84,11 @@ impl Sink for Writer {
 
     fn start_send(
         &mut self,
-        event: Self::SinkItem,
+        mut event: Self::SinkItem,
     ) -> Result<AsyncSink<Self::SinkItem>, Self::SinkError> {
+        // Finalizers can't be persisted, so an event is considered delivered
+        // once the batch containing it has been written to the buffer.
+        let finalizers = event.metadata_mut().take_finalizers();
         let mut value = vec![];
         proto::EventWrapper::from(event).encode(&mut value).unwrap(); // This will not error when writing to a Vec
         let event_size = value.len();
@@ -101,7 +106,8 @@ impl Sink for Writer {
             self.poll_complete()?;
 
             let buf = Bytes::from(value);
-            let event = proto::EventWrapper::decode(buf).unwrap().into();
+            let mut event: Event = proto::EventWrapper::decode(buf).unwrap().into();
+            event.metadata_mut().merge_finalizers(finalizers);
             return Ok(AsyncSink::NotReady(event));
         }
 
@@ -109,6 +115,7 @@ impl Sink for Writer {
 
         self.writebatch.put(Key(key), &value);
         self.batch_size += 1;
+        self.batch_finalizers.merge(finalizers);
 
         if self.batch_size >= 100 {
             self.poll_complete()?;
@@ -136,6 +143,7 @@ impl Writer {
             .unwrap();
         self.writebatch = Writebatch::new();
         self.batch_size = 0;
+        std::mem::take(&mut self.batch_finalizers).update_status(EventStatus::Delivered);
         self.write_notifier.notify();
     }
 }
@@ -318,6 +326,7 @@ impl super::DiskBuffer for Buffer {
             offset: Arc::new(AtomicUsize::new(tail)),
             writebatch: Writebatch::new(),
             batch_size: 0,
+            batch_finalizers: EventFinalizers::default(),
             max_size,
             current_size: Arc::clone(&current_size),
         };
@@ -1,12 +1,14 @@
-#![cfg(feature = "leveldb")]
-
+use super::SyncPolicy;
 use crate::event::Event;
 use futures01::{Async, AsyncSink, Poll, Sink, Stream};
 use snafu::Snafu;
 use std::io;
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 
+#[cfg(feature = "leveldb")]
 pub mod leveldb_buffer;
+pub mod wal_buffer;
 
 #[derive(Debug, Snafu)]
 pub enum Error {
@@ -19,11 +21,14 @@ pub enum Error {
         data_dir: PathBuf,
         source: std::io::Error,
     },
+    #[cfg(feature = "leveldb")]
     #[snafu(display("Unable to open data_dir {:?}", data_dir))]
     DataDirOpenError {
         data_dir: PathBuf,
         source: leveldb::database::error::Error,
     },
+    #[snafu(display("Unable to open disk buffer {:?}: {}", path, source))]
+    BufferOpenError { path: PathBuf, source: io::Error },
 }
 
 pub trait DiskBuffer {
@@ -37,8 +42,10 @@ pub trait DiskBuffer {
 }
 
 #[derive(Clone)]
-pub struct Writer {
-    inner: leveldb_buffer::Writer,
+pub enum Writer {
+    #[cfg(feature = "leveldb")]
+    LevelDb(leveldb_buffer::Writer),
+    Wal(wal_buffer::Writer),
 }
 
 impl Sink for Writer {
@@ -49,18 +56,31 @@ impl Sink for Writer {
         &mut self,
         event: Self::SinkItem,
     ) -> Result<AsyncSink<Self::SinkItem>, Self::SinkError> {
-        self.inner.start_send(event)
+        match self {
+            #[cfg(feature = "leveldb")]
+            Writer::LevelDb(inner) => inner.start_send(event),
+            Writer::Wal(inner) => inner.start_send(event),
+        }
     }
 
     fn poll_complete(&mut self) -> Result<Async<()>, Self::SinkError> {
-        self.inner.poll_complete()
+        match self {
+            #[cfg(feature = "leveldb")]
+            Writer::LevelDb(inner) => inner.poll_complete(),
+            Writer::Wal(inner) => inner.poll_complete(),
+        }
     }
 
     fn close(&mut self) -> Poll<(), Self::SinkError> {
-        self.inner.close()
+        match self {
+            #[cfg(feature = "leveldb")]
+            Writer::LevelDb(inner) => inner.close(),
+            Writer::Wal(inner) => inner.close(),
+        }
     }
 }
 
+#[cfg(feature = "leveldb")]
 pub fn open(
     data_dir: &Path,
     name: &str,
@@ -73,9 +93,36 @@ pub fn open(
     ),
     Error,
 > {
+    check_data_dir(data_dir)?;
+
+    let path = data_dir.join(name);
+    let (writer, reader, acker) = leveldb_buffer::Buffer::build(path, max_size)?;
+    Ok((Writer::LevelDb(writer), Box::new(reader), acker))
+}
+
+/// Open the native write-ahead-log buffer stored in `data_dir/name`.
+pub fn open_v2(
+    data_dir: &Path,
+    name: &str,
+    max_size: usize,
+    sync: SyncPolicy,
+    sync_interval: Duration,
+) -> Result<
+    (
+        Writer,
+        Box<dyn Stream<Item = Event, Error = ()> + Send>,
+        super::Acker,
+    ),
+    Error,
+> {
+    check_data_dir(data_dir)?;
+
     let path = data_dir.join(name);
+    let (writer, reader, acker) = wal_buffer::Buffer::build(path, max_size, sync, sync_interval)?;
+    Ok((Writer::Wal(writer), Box::new(reader), acker))
+}
 
-    // Check data dir
+fn check_data_dir(data_dir: &Path) -> Result<(), Error> {
     std::fs::metadata(&data_dir)
         .map_err(|e| match e.kind() {
             io::ErrorKind::PermissionDenied => Error::DataDirNotWritable {
@@ -97,8 +144,5 @@ pub fn open(
             } else {
                 Ok(())
             }
-        })?;
-
-    let (writer, reader, acker) = leveldb_buffer::Buffer::build(path, max_size)?;
-    Ok((Writer { inner: writer }, Box::new(reader), acker))
+        })
 }
@@ -0,0 +1,723 @@
+//! A disk buffer backed by a write-ahead log of append-only segment files.
+//!
+//! Each record is a little-endian `u32` payload length, the CRC32 of the
+//! payload, and the payload itself, an encoded `proto::EventWrapper`. Writers
+//! append to the newest segment and roll over to a new one once it grows past
+//! the segment size. The reader persists the position after the last
+//! acknowledged record in a checkpoint file, and deletes a segment once every
+//! record in it has been acknowledged.
+//!
+//! A new segment is started every time the buffer is opened, so a record torn
+//! by a crash can only be found at the end of a segment that is no longer
+//! written to. Records failing their checksum are skipped, and a corrupted
+//! length makes the reader skip the rest of the segment.
+
+use super::{BufferOpenError, Error};
+use crate::{
+    buffers::{Acker, SyncPolicy},
+    event::{proto, Event, EventFinalizers, EventStatus},
+    internal_events::{DiskBufferIoError, DiskBufferRecordCorrupted},
+};
+use bytes::Bytes;
+use futures01::{
+    task::{self, AtomicTask, Task},
+    Async, AsyncSink, Poll, Sink, Stream,
+};
+use prost::Message;
+use snafu::ResultExt;
+use std::{
+    collections::VecDeque,
+    convert::TryInto,
+    fs::{self, File, OpenOptions},
+    io::{self, BufReader, BufWriter, Read, Seek, SeekFrom, Write},
+    mem,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+/// Length of the record header: payload length and checksum.
+const HEADER_LEN: u64 = 8;
+/// Number of records after which writes are flushed to the segment file.
+const MAX_BATCH_SIZE: usize = 100;
+const MIN_SEGMENT_SIZE: usize = 64 * 1024;
+const MAX_SEGMENT_SIZE: usize = 128 * 1024 * 1024;
+const SEGMENT_EXTENSION: &str = "log";
+const CHECKPOINT_FILE: &str = "checkpoint";
+const CHECKPOINT_LEN: usize = 20;
+
+fn segment_path(dir: &Path, id: u64) -> PathBuf {
+    dir.join(format!("{:020}.{}", id, SEGMENT_EXTENSION))
+}
+
+/// Ids of the segments stored in `dir`, oldest first.
+fn list_segments(dir: &Path) -> io::Result<Vec<u64>> {
+    let mut ids = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some(SEGMENT_EXTENSION) {
+            continue;
+        }
+        if let Some(id) = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .and_then(|stem| stem.parse().ok())
+        {
+            ids.push(id);
+        }
+    }
+    ids.sort_unstable();
+    Ok(ids)
+}
+
+fn open_segment(dir: &Path, id: u64) -> io::Result<BufWriter<File>> {
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(segment_path(dir, id))
+        .map(BufWriter::new)
+}
+
+fn write_record(file: &mut impl Write, payload: &[u8]) -> io::Result<()> {
+    file.write_all(&(payload.len() as u32).to_le_bytes())?;
+    file.write_all(&crc32fast::hash(payload).to_le_bytes())?;
+    file.write_all(payload)
+}
+
+/// Position right after the last acknowledged record.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Checkpoint {
+    segment_id: u64,
+    position: u64,
+}
+
+impl Checkpoint {
+    fn read(dir: &Path) -> Option<Self> {
+        let bytes = fs::read(dir.join(CHECKPOINT_FILE)).ok()?;
+        if bytes.len() != CHECKPOINT_LEN {
+            return None;
+        }
+        let (data, checksum) = bytes.split_at(16);
+        if crc32fast::hash(data).to_le_bytes() != checksum {
+            return None;
+        }
+
+        Some(Self {
+            segment_id: u64::from_le_bytes(data[..8].try_into().unwrap()),
+            position: u64::from_le_bytes(data[8..].try_into().unwrap()),
+        })
+    }
+
+    fn write(&self, dir: &Path) -> io::Result<()> {
+        let mut bytes = Vec::with_capacity(CHECKPOINT_LEN);
+        bytes.extend_from_slice(&self.segment_id.to_le_bytes());
+        bytes.extend_from_slice(&self.position.to_le_bytes());
+        let checksum = crc32fast::hash(&bytes);
+        bytes.extend_from_slice(&checksum.to_le_bytes());
+
+        // Replace the checkpoint atomically so a crash can't leave half of it.
+        let tmp_path = dir.join(format!("{}.tmp", CHECKPOINT_FILE));
+        fs::write(&tmp_path, &bytes)?;
+        fs::rename(tmp_path, dir.join(CHECKPOINT_FILE))
+    }
+}
+
+/// State shared by the writers and the reader.
+struct Shared {
+    dir: PathBuf,
+    max_size: usize,
+    segment_size: u64,
+    sync: SyncPolicy,
+    sync_interval: Duration,
+    /// Size of all the segment files.
+    current_size: AtomicUsize,
+    write_notifier: Arc<AtomicTask>,
+    blocked_write_tasks: Mutex<Vec<Task>>,
+    head: Mutex<Head>,
+}
+
+/// The segment currently being written to.
+struct Head {
+    segment_id: u64,
+    file: BufWriter<File>,
+    /// Bytes appended to the segment, including the ones not flushed yet.
+    written: u64,
+    /// Bytes the reader is allowed to read.
+    flushed: u64,
+    unflushed_records: usize,
+    unflushed_finalizers: EventFinalizers,
+    last_sync: Instant,
+}
+
+impl Shared {
+    fn flush(&self, head: &mut Head) -> io::Result<()> {
+        if head.flushed == head.written {
+            return Ok(());
+        }
+
+        head.file.flush()?;
+        let sync = match self.sync {
+            SyncPolicy::Always => true,
+            SyncPolicy::Interval => head.last_sync.elapsed() >= self.sync_interval,
+            SyncPolicy::Never => false,
+        };
+        if sync {
+            head.file.get_ref().sync_data()?;
+            head.last_sync = Instant::now();
+        }
+
+        head.flushed = head.written;
+        head.unflushed_records = 0;
+        // Finalizers can't be persisted, so an event is considered delivered
+        // once it has been written to the buffer.
+        mem::take(&mut head.unflushed_finalizers).update_status(EventStatus::Delivered);
+        self.write_notifier.notify();
+        Ok(())
+    }
+
+    /// Seal the head segment and start writing to a new one.
+    fn roll(&self, head: &mut Head) -> io::Result<()> {
+        self.flush(head)?;
+        if self.sync != SyncPolicy::Never {
+            head.file.get_ref().sync_data()?;
+        }
+
+        let segment_id = head.segment_id + 1;
+        head.file = open_segment(&self.dir, segment_id)?;
+        head.segment_id = segment_id;
+        head.written = 0;
+        head.flushed = 0;
+        self.write_notifier.notify();
+        Ok(())
+    }
+
+    fn io_error(&self, error: io::Error) {
+        emit!(DiskBufferIoError {
+            path: &self.dir,
+            error
+        });
+    }
+}
+
+#[derive(Clone)]
+pub struct Writer {
+    shared: Arc<Shared>,
+}
+
+impl Sink for Writer {
+    type SinkItem = Event;
+    type SinkError = ();
+
+    fn start_send(
+        &mut self,
+        mut event: Self::SinkItem,
+    ) -> Result<AsyncSink<Self::SinkItem>, Self::SinkError> {
+        let finalizers = event.metadata_mut().take_finalizers();
+        let mut payload = vec![];
+        proto::EventWrapper::from(event)
+            .encode(&mut payload)
+            .unwrap(); // This will not error when writing to a Vec
+        let record_size = HEADER_LEN as usize + payload.len();
+
+        let shared = &*self.shared;
+        let mut head = shared.head.lock().unwrap();
+
+        let is_full = || {
+            let current_size = shared.current_size.load(Ordering::Relaxed);
+            current_size > 0 && current_size + record_size > shared.max_size
+        };
+        if is_full() {
+            shared
+                .blocked_write_tasks
+                .lock()
+                .unwrap()
+                .push(task::current());
+
+            // The reader may have freed up space before the task was registered.
+            if is_full() {
+                // The head segment is never deleted, so seal it to let the
+                // reader reclaim its space once it has been acknowledged.
+                if head.written > 0 {
+                    shared
+                        .roll(&mut head)
+                        .map_err(|error| shared.io_error(error))?;
+                }
+
+                let buf = Bytes::from(payload);
+                let mut event: Event = proto::EventWrapper::decode(buf).unwrap().into();
+                event.metadata_mut().merge_finalizers(finalizers);
+                return Ok(AsyncSink::NotReady(event));
+            }
+        }
+
+        if head.written > 0 && head.written + record_size as u64 > shared.segment_size {
+            shared
+                .roll(&mut head)
+                .map_err(|error| shared.io_error(error))?;
+        }
+
+        write_record(&mut head.file, &payload).map_err(|error| shared.io_error(error))?;
+        head.written += record_size as u64;
+        head.unflushed_records += 1;
+        head.unflushed_finalizers.merge(finalizers);
+        shared
+            .current_size
+            .fetch_add(record_size, Ordering::Relaxed);
+
+        if head.unflushed_records >= MAX_BATCH_SIZE {
+            shared
+                .flush(&mut head)
+                .map_err(|error| shared.io_error(error))?;
+        }
+
+        Ok(AsyncSink::Ready)
+    }
+
+    fn poll_complete(&mut self) -> Result<Async<()>, Self::SinkError> {
+        let shared = &*self.shared;
+        let mut head = shared.head.lock().unwrap();
+        shared
+            .flush(&mut head)
+            .map_err(|error| shared.io_error(error))?;
+
+        Ok(Async::Ready(()))
+    }
+}
+
+impl Drop for Writer {
+    fn drop(&mut self) {
+        if let Ok(mut head) = self.shared.head.lock() {
+            if let Err(error) = self.shared.flush(&mut head) {
+                self.shared.io_error(error);
+            }
+        }
+
+        // We need to wake up the reader so it can return None if there are no more writers
+        self.shared.write_notifier.notify();
+    }
+}
+
+enum ReadResult {
+    Record(Event),
+    Skipped,
+    EndOfSegment,
+}
+
+pub struct Reader {
+    shared: Arc<Shared>,
+    ack_counter: Arc<AtomicUsize>,
+    /// Oldest segment that hasn't been deleted yet.
+    oldest_segment_id: u64,
+    segment_id: u64,
+    position: u64,
+    file: Option<BufReader<File>>,
+    /// Set once the rest of the current segment can't be trusted.
+    skip_segment: bool,
+    /// Position after each record that was read but not acknowledged yet.
+    unacked: VecDeque<Checkpoint>,
+}
+
+impl Stream for Reader {
+    type Item = Event;
+    type Error = ();
+
+    fn poll(&mut self) -> Poll<Option<Self::Item>, Self::Error> {
+        self.delete_acked();
+
+        // If there's nothing left to read, we return NotReady and rely on Writer
+        // using write_notifier to wake this task up after the next flush.
+        self.shared.write_notifier.register();
+
+        loop {
+            // Writers flush before going away, so once there are none left
+            // everything they wrote is readable.
+            let writers_done = Arc::strong_count(&self.shared) == 1;
+            let (head_id, flushed) = {
+                let head = self.shared.head.lock().unwrap();
+                (head.segment_id, head.flushed)
+            };
+            // Sealed segments can be read up to their end.
+            let limit = if self.segment_id < head_id {
+                None
+            } else {
+                Some(flushed)
+            };
+
+            // This will usually complete instantly, but in the case of a large queue (or a fresh launch of
+            // the app), this will have to go to disk.
+            match tokio::task::block_in_place(|| self.read_record(limit)) {
+                Ok(ReadResult::Record(event)) => return Ok(Async::Ready(Some(event))),
+                Ok(ReadResult::Skipped) => {}
+                Ok(ReadResult::EndOfSegment) if self.segment_id < head_id => {
+                    self.next_segment(head_id)
+                }
+                Ok(ReadResult::EndOfSegment) if writers_done => return Ok(Async::Ready(None)),
+                Ok(ReadResult::EndOfSegment) => return Ok(Async::NotReady),
+                Err(error) => {
+                    emit!(DiskBufferIoError {
+                        path: &segment_path(&self.shared.dir, self.segment_id),
+                        error
+                    });
+                    return Err(());
+                }
+            }
+        }
+    }
+}
+
+impl Drop for Reader {
+    fn drop(&mut self) {
+        self.delete_acked();
+    }
+}
+
+impl Reader {
+    fn file(&mut self) -> io::Result<&mut BufReader<File>> {
+        if self.file.is_none() {
+            let mut file = File::open(segment_path(&self.shared.dir, self.segment_id))?;
+            file.seek(SeekFrom::Start(self.position))?;
+            self.file = Some(BufReader::new(file));
+        }
+        Ok(self.file.as_mut().expect("file was just opened"))
+    }
+
+    fn read_record(&mut self, limit: Option<u64>) -> io::Result<ReadResult> {
+        if self.skip_segment {
+            return Ok(ReadResult::EndOfSegment);
+        }
+
+        let end = match limit {
+            Some(end) => end,
+            None => self.file()?.get_ref().metadata()?.len(),
+        };
+        let remaining = end.saturating_sub(self.position);
+        if remaining == 0 {
+            return Ok(ReadResult::EndOfSegment);
+        }
+        if remaining < HEADER_LEN {
+            self.corrupted(self.position, "truncated record header");
+            self.skip_segment = true;
+            return Ok(ReadResult::Skipped);
+        }
+
+        let mut header = [0; HEADER_LEN as usize];
+        self.file()?.read_exact(&mut header)?;
+        let length = u64::from(u32::from_le_bytes(header[..4].try_into().unwrap()));
+        let checksum = u32::from_le_bytes(header[4..].try_into().unwrap());
+        if length > remaining - HEADER_LEN {
+            // Without a valid length there is no way to find the next record.
+            self.corrupted(self.position, "record length exceeds segment");
+            self.skip_segment = true;
+            return Ok(ReadResult::Skipped);
+        }
+
+        let mut payload = vec![0; length as usize];
+        self.file()?.read_exact(&mut payload)?;
+        let start = self.position;
+        self.position += HEADER_LEN + length;
+
+        if crc32fast::hash(&payload) != checksum {
+            self.corrupted(start, "checksum mismatch");
+            return Ok(ReadResult::Skipped);
+        }
+
+        match proto::EventWrapper::decode(Bytes::from(payload)) {
+            Ok(event) => {
+                self.unacked.push_back(Checkpoint {
+                    segment_id: self.segment_id,
+                    position: self.position,
+                });
+                Ok(ReadResult::Record(Event::from(event)))
+            }
+            Err(error) => {
+                error!(message = "Error deserializing proto.", %error);
+                self.corrupted(start, "invalid event encoding");
+                Ok(ReadResult::Skipped)
+            }
+        }
+    }
+
+    fn corrupted(&self, position: u64, reason: &'static str) {
+        emit!(DiskBufferRecordCorrupted {
+            path: &segment_path(&self.shared.dir, self.segment_id),
+            position,
+            reason,
+        });
+    }
+
+    fn next_segment(&mut self, head_id: u64) {
+        // Segment ids are sequential, but some may be missing if they were
+        // removed by hand.
+        self.segment_id += 1;
+        while self.segment_id < head_id && !segment_path(&self.shared.dir, self.segment_id).exists()
+        {
+            self.segment_id += 1;
+        }
+        self.position = 0;
+        self.file = None;
+        self.skip_segment = false;
+
+        self.delete_segments();
+    }
+
+    fn delete_acked(&mut self) {
+        let num_to_delete = self.ack_counter.swap(0, Ordering::Relaxed);
+
+        if num_to_delete > 0 {
+            assert!(
+                num_to_delete <= self.unacked.len(),
+                "Tried to ack beyond read offset"
+            );
+
+            let checkpoint = self
+                .unacked
+                .drain(..num_to_delete)
+                .last()
+                .expect("at least one record was acked");
+            if let Err(error) = checkpoint.write(&self.shared.dir) {
+                self.shared.io_error(error);
+            }
+
+            self.delete_segments();
+        }
+
+        for task in self.shared.blocked_write_tasks.lock().unwrap().drain(..) {
+            task.notify();
+        }
+    }
+
+    /// Delete the segments older than every record that still needs to be
+    /// read or acknowledged.
+    fn delete_segments(&mut self) {
+        let oldest_needed = self
+            .unacked
+            .front()
+            .map_or(self.segment_id, |record| record.segment_id);
+
+        while self.oldest_segment_id < oldest_needed {
+            let path = segment_path(&self.shared.dir, self.oldest_segment_id);
+            match fs::metadata(&path).and_then(|metadata| {
+                fs::remove_file(&path)?;
+                Ok(metadata.len())
+            }) {
+                Ok(size) => {
+                    self.shared
+                        .current_size
+                        .fetch_sub(size as usize, Ordering::Relaxed);
+                }
+                Err(error) if error.kind() == io::ErrorKind::NotFound => {}
+                Err(error) => self.shared.io_error(error),
+            }
+            self.oldest_segment_id += 1;
+        }
+    }
+}
+
+pub struct Buffer;
+
+impl Buffer {
+    pub fn build(
+        path: PathBuf,
+        max_size: usize,
+        sync: SyncPolicy,
+        sync_interval: Duration,
+    ) -> Result<(Writer, Reader, Acker), Error> {
+        Self::open(&path, max_size, sync, sync_interval).context(BufferOpenError { path })
+    }
+
+    fn open(
+        dir: &Path,
+        max_size: usize,
+        sync: SyncPolicy,
+        sync_interval: Duration,
+    ) -> io::Result<(Writer, Reader, Acker)> {
+        fs::create_dir_all(dir)?;
+
+        let segments = list_segments(dir)?;
+        let checkpoint = Checkpoint::read(dir)
+            .filter(|checkpoint| segments.contains(&checkpoint.segment_id))
+            .unwrap_or(Checkpoint {
+                segment_id: segments.first().copied().unwrap_or(0),
+                position: 0,
+            });
+
+        let mut initial_size = 0;
+        for &id in &segments {
+            let path = segment_path(dir, id);
+            // Everything before the checkpoint has already been acknowledged.
+            if id < checkpoint.segment_id {
+                fs::remove_file(path)?;
+            } else {
+                initial_size += fs::metadata(path)?.len() as usize;
+            }
+        }
+
+        // Never append to a segment from a previous run, its last record may
+        // have been torn by a crash.
+        let head_id = segments.last().map_or(0, |id| id + 1);
+        let head = Head {
+            segment_id: head_id,
+            file: open_segment(dir, head_id)?,
+            written: 0,
+            flushed: 0,
+            unflushed_records: 0,
+            unflushed_finalizers: EventFinalizers::default(),
+            last_sync: Instant::now(),
+        };
+
+        let segment_size = (max_size / 8).max(MIN_SEGMENT_SIZE).min(MAX_SEGMENT_SIZE) as u64;
+        let write_notifier = Arc::new(AtomicTask::new());
+        let shared = Arc::new(Shared {
+            dir: dir.into(),
+            max_size,
+            segment_size,
+            sync,
+            sync_interval,
+            current_size: AtomicUsize::new(initial_size),
+            write_notifier: Arc::clone(&write_notifier),
+            blocked_write_tasks: Mutex::new(Vec::new()),
+            head: Mutex::new(head),
+        });
+
+        let ack_counter = Arc::new(AtomicUsize::new(0));
+        let acker = Acker::Disk(Arc::clone(&ack_counter), write_notifier);
+
+        let writer = Writer {
+            shared: Arc::clone(&shared),
+        };
+
+        let reader = Reader {
+            shared,
+            ack_counter,
+            oldest_segment_id: checkpoint.segment_id,
+            segment_id: checkpoint.segment_id,
+            position: checkpoint.position,
+            file: None,
+            skip_segment: false,
+            unacked: VecDeque::new(),
+        };
+
+        Ok((writer, reader, acker))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::log_schema;
+    use futures01::{stream, Future};
+    use tempfile::tempdir;
+
+    fn open(dir: &Path) -> (Writer, Reader, Acker) {
+        Buffer::build(
+            dir.to_path_buf(),
+            1024 * 1024,
+            SyncPolicy::Always,
+            Duration::from_secs(1),
+        )
+        .unwrap()
+    }
+
+    fn write(writer: Writer, messages: &[&str]) {
+        let events: Vec<Event> = messages.iter().map(|&message| message.into()).collect();
+        let (writer, _) = writer
+            .send_all(stream::iter_ok::<_, ()>(events))
+            .wait()
+            .unwrap();
+        drop(writer);
+    }
+
+    fn messages(events: Vec<Event>) -> Vec<String> {
+        events
+            .into_iter()
+            .map(|event| event.as_log()[log_schema().message_key()].to_string_lossy())
+            .collect()
+    }
+
+    fn segment_file(dir: &Path) -> PathBuf {
+        segment_path(dir, list_segments(dir).unwrap()[0])
+    }
+
+    #[test]
+    fn resumes_after_acknowledged_records() {
+        let dir = tempdir().unwrap();
+
+        let (writer, mut reader, acker) = open(dir.path());
+        write(writer, &["first", "second", "third"]);
+        let events = reader.by_ref().take(2).collect().wait().unwrap();
+        assert_eq!(messages(events), vec!["first", "second"]);
+        acker.ack(1);
+        drop(reader);
+
+        let (writer, reader, _acker) = open(dir.path());
+        drop(writer);
+        let events = reader.collect().wait().unwrap();
+        assert_eq!(messages(events), vec!["second", "third"]);
+    }
+
+    #[test]
+    fn deletes_acknowledged_segments() {
+        let dir = tempdir().unwrap();
+
+        let (writer, reader, acker) = open(dir.path());
+        write(writer, &["first"]);
+        drop((reader, acker));
+
+        // Reopening starts a new segment.
+        let (writer, mut reader, acker) = open(dir.path());
+        write(writer, &["second"]);
+        let events = reader.by_ref().take(2).collect().wait().unwrap();
+        assert_eq!(messages(events), vec!["first", "second"]);
+        acker.ack(2);
+        drop(reader);
+        assert!(!segment_path(dir.path(), 0).exists());
+
+        let (writer, reader, _acker) = open(dir.path());
+        drop(writer);
+        assert!(reader.collect().wait().unwrap().is_empty());
+    }
+
+    #[test]
+    fn skips_records_with_invalid_checksum() {
+        let dir = tempdir().unwrap();
+
+        let (writer, reader, _acker) = open(dir.path());
+        write(writer, &["first", "second", "third"]);
+        drop(reader);
+
+        let path = segment_file(dir.path());
+        let mut bytes = fs::read(&path).unwrap();
+        let first_len = u32::from_le_bytes(bytes[..4].try_into().unwrap()) as usize;
+        let second_payload = first_len + 2 * HEADER_LEN as usize;
+        bytes[second_payload] ^= 0xff;
+        fs::write(&path, bytes).unwrap();
+
+        let (writer, reader, _acker) = open(dir.path());
+        drop(writer);
+        let events = reader.collect().wait().unwrap();
+        assert_eq!(messages(events), vec!["first", "third"]);
+    }
+
+    #[test]
+    fn skips_torn_record() {
+        let dir = tempdir().unwrap();
+
+        let (writer, reader, _acker) = open(dir.path());
+        write(writer, &["first", "second"]);
+        drop(reader);
+
+        let path = segment_file(dir.path());
+        let file = OpenOptions::new().write(true).open(&path).unwrap();
+        let len = file.metadata().unwrap().len();
+        file.set_len(len - 3).unwrap();
+        drop(file);
+
+        let (writer, reader, _acker) = open(dir.path());
+        write(writer, &["third"]);
+        let events = reader.collect().wait().unwrap();
+        assert_eq!(messages(events), vec!["first", "third"]);
+    }
+}
@@ -1,24 +1,29 @@
-use crate::{config::Resource, internal_events::EventOut, Event};
-#[cfg(feature = "leveldb")]
+use crate::{
+    config::Resource,
+    event::{EventFinalizers, EventStatus},
+    internal_events::EventOut,
+    Event,
+};
 use futures::compat::{Sink01CompatExt, Stream01CompatExt};
 use futures::{channel::mpsc, Sink, SinkExt, Stream};
 use futures01::task::AtomicTask;
 use pin_project::pin_project;
 use serde::{Deserialize, Serialize};
 use std::{
+    collections::VecDeque,
     path::PathBuf,
     pin::Pin,
     sync::{
         atomic::{AtomicUsize, Ordering},
-        Arc,
+        Arc, Mutex,
     },
     task::{Context, Poll},
+    time::Duration,
 };
-#[cfg(feature = "leveldb")]
 use tokio::stream::StreamExt;
 
-#[cfg(feature = "leveldb")]
 pub mod disk;
+pub mod tiered;
 
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
 #[serde(tag = "type")]
@@ -36,6 +41,27 @@ pub enum BufferConfig {
         #[serde(default)]
         when_full: WhenFull,
     },
+    DiskV2 {
+        max_size: usize,
+        #[serde(default)]
+        when_full: WhenFull,
+        #[serde(default)]
+        sync: SyncPolicy,
+        #[serde(default = "BufferConfig::sync_interval_secs")]
+        sync_interval_secs: u64,
+    },
+    /// Keeps up to `max_events` in memory, and spills the rest to disk.
+    Tiered {
+        #[serde(default = "BufferConfig::memory_max_events")]
+        max_events: usize,
+        max_size: usize,
+        #[serde(default)]
+        when_full: WhenFull,
+        #[serde(default)]
+        sync: SyncPolicy,
+        #[serde(default = "BufferConfig::sync_interval_secs")]
+        sync_interval_secs: u64,
+    },
 }
 
 impl Default for BufferConfig {
@@ -60,11 +86,29 @@ impl Default for WhenFull {
     }
 }
 
+/// When the native disk buffer forces written records to the disk.
+#[derive(Deserialize, Serialize, Debug, PartialEq, Copy, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncPolicy {
+    /// Sync every time the buffered records are flushed.
+    Always,
+    /// Sync at most once every `sync_interval_secs`.
+    Interval,
+    /// Leave it up to the operating system.
+    Never,
+}
+
+impl Default for SyncPolicy {
+    fn default() -> Self {
+        SyncPolicy::Interval
+    }
+}
+
 #[derive(Clone)]
 pub enum BufferInputCloner {
     Memory(mpsc::Sender<Event>, WhenFull),
-    #[cfg(feature = "leveldb")]
     Disk(disk::Writer, WhenFull),
+    Tiered(mpsc::Sender<Event>, disk::Writer, WhenFull),
 }
 
 impl BufferInputCloner {
@@ -81,7 +125,6 @@ impl BufferInputCloner {
                 }
             }
 
-            #[cfg(feature = "leveldb")]
             BufferInputCloner::Disk(writer, when_full) => {
                 let inner = writer.clone().sink_compat();
                 if when_full == &WhenFull::DropNewest {
@@ -90,6 +133,20 @@ impl BufferInputCloner {
                     Box::new(inner)
                 }
             }
+
+            BufferInputCloner::Tiered(tx, writer, when_full) => {
+                let inner = tiered::OverflowSink::new(
+                    tx.clone()
+                        .sink_map_err(|error| error!(message = "Sender error.", %error)),
+                    writer.clone().sink_compat(),
+                );
+                // Only drops events once the disk tier is full as well.
+                if when_full == &WhenFull::DropNewest {
+                    Box::new(DropWhenFull::new(inner))
+                } else {
+                    Box::new(inner)
+                }
+            }
         }
     }
 }
@@ -100,7 +157,11 @@ impl BufferConfig {
         500
     }
 
-    #[cfg_attr(not(feature = "leveldb"), allow(unused))]
+    #[inline]
+    const fn sync_interval_secs() -> u64 {
+        1
+    }
+
     pub fn build(
         &self,
         data_dir: &Option<PathBuf>,
@@ -144,16 +205,82 @@ impl BufferConfig {
                 );
                 Ok((tx, rx, acker))
             }
+
+            BufferConfig::DiskV2 {
+                max_size,
+                when_full,
+                sync,
+                sync_interval_secs,
+            } => {
+                let data_dir = data_dir
+                    .as_ref()
+                    .ok_or_else(|| "Must set data_dir to use on-disk buffering.".to_string())?;
+                let buffer_dir = format!("{}_wal_buffer", sink_name);
+
+                let (tx, rx, acker) = disk::open_v2(
+                    &data_dir,
+                    buffer_dir.as_ref(),
+                    *max_size,
+                    *sync,
+                    Duration::from_secs(*sync_interval_secs),
+                )
+                .map_err(|error| error.to_string())?;
+                let tx = BufferInputCloner::Disk(tx, *when_full);
+                let rx = Box::new(
+                    rx.compat()
+                        .take_while(|event| event.is_ok())
+                        .map(|event| event.unwrap()),
+                );
+                Ok((tx, rx, acker))
+            }
+
+            BufferConfig::Tiered {
+                max_events,
+                max_size,
+                when_full,
+                sync,
+                sync_interval_secs,
+            } => {
+                let data_dir = data_dir
+                    .as_ref()
+                    .ok_or_else(|| "Must set data_dir to use on-disk buffering.".to_string())?;
+                let buffer_dir = format!("{}_wal_buffer", sink_name);
+
+                let (disk_tx, disk_rx, disk_acker) = disk::open_v2(
+                    &data_dir,
+                    buffer_dir.as_ref(),
+                    *max_size,
+                    *sync,
+                    Duration::from_secs(*sync_interval_secs),
+                )
+                .map_err(|error| error.to_string())?;
+                let disk_rx = Box::pin(
+                    disk_rx
+                        .compat()
+                        .take_while(|event| event.is_ok())
+                        .map(|event| event.unwrap()),
+                );
+
+                let (memory_tx, memory_rx) = mpsc::channel(*max_events);
+                let ledger = Arc::new(tiered::TierLedger::default());
+
+                let tx = BufferInputCloner::Tiered(memory_tx, disk_tx, *when_full);
+                let rx = Box::new(tiered::Reader::new(memory_rx, disk_rx, Arc::clone(&ledger)));
+                let acker = Acker::Tiered(ledger, Box::new(disk_acker));
+                Ok((tx, rx, acker))
+            }
         }
     }
 
     /// Resources that the sink is using.
-    #[cfg_attr(not(feature = "leveldb"), allow(unused))]
     pub fn resources(&self, sink_name: &str) -> Vec<Resource> {
         match self {
             BufferConfig::Memory { .. } => Vec::new(),
             #[cfg(feature = "leveldb")]
             BufferConfig::Disk { .. } => vec![Resource::DiskBuffer(sink_name.to_string())],
+            BufferConfig::DiskV2 { .. } | BufferConfig::Tiered { .. } => {
+                vec![Resource::DiskBuffer(sink_name.to_string())]
+            }
         }
     }
 }
@@ -162,9 +289,29 @@ impl BufferConfig {
 pub enum Acker {
     Disk(Arc<AtomicUsize>, Arc<AtomicTask>),
     Null,
+    /// Releases the finalizers of acked events back to their sources before
+    /// passing the ack on to the wrapped acker.
+    Finalizing(Arc<PendingFinalizers>, Box<Acker>),
+    /// Passes on acks for the events that were read from the disk tier of a
+    /// tiered buffer.
+    Tiered(Arc<tiered::TierLedger>, Box<Acker>),
 }
 
 impl Acker {
+    /// Wrap an acker so that it also reports the delivery status of the events
+    /// passed to `track` back to their sources.
+    pub fn finalizing(inner: Acker) -> Self {
+        Acker::Finalizing(Arc::new(PendingFinalizers::default()), Box::new(inner))
+    }
+
+    /// Detach the finalizers from an event entering the sink. They are
+    /// released, in order, as the sink acks events.
+    pub fn track(&self, event: &mut Event) {
+        if let Acker::Finalizing(pending, _) = self {
+            pending.push(event.metadata_mut().take_finalizers());
+        }
+    }
+
     // This method should be called by a sink to indicate that it has successfully
     // flushed the next `num` events from its input stream. If there are events that
     // have flushed, but events that came before them in the stream have not been flushed,
@@ -172,16 +319,36 @@ impl Acker {
     // This is primary used by the on-disk buffer to know which events are okay to
     // delete from disk.
     pub fn ack(&self, num: usize) {
+        self.ack_with_status(num, EventStatus::Delivered);
+    }
+
+    /// Same as `ack`, but lets the sink report that the events could not be
+    /// delivered. The events are still considered flushed from the buffer.
+    pub fn ack_with_status(&self, num: usize, status: EventStatus) {
         // Only ack items if the amount to ack is larger than zero.
         if num > 0 {
-            match self {
-                Acker::Null => {}
-                Acker::Disk(counter, notifier) => {
-                    counter.fetch_add(num, Ordering::Relaxed);
-                    notifier.notify();
+            self.release(num, status);
+            emit!(EventOut { count: num });
+        }
+    }
+
+    fn release(&self, num: usize, status: EventStatus) {
+        match self {
+            Acker::Null => {}
+            Acker::Disk(counter, notifier) => {
+                counter.fetch_add(num, Ordering::Relaxed);
+                notifier.notify();
+            }
+            Acker::Finalizing(pending, inner) => {
+                pending.release(num, status);
+                inner.release(num, status);
+            }
+            Acker::Tiered(ledger, inner) => {
+                let from_disk = ledger.release(num);
+                if from_disk > 0 {
+                    inner.release(from_disk, status);
                 }
             }
-            emit!(EventOut { count: num });
         }
     }
 
@@ -194,6 +361,58 @@ impl Acker {
     }
 }
 
+/// Finalizers of the events a sink has received but not yet acked, along with the position of
+/// their event in the sink's input. Events without finalizers are only counted.
+#[derive(Debug, Default)]
+pub struct PendingFinalizers {
+    received: AtomicUsize,
+    pending: Mutex<Pending>,
+}
+
+#[derive(Debug, Default)]
+struct Pending {
+    acked: usize,
+    finalizers: VecDeque<(usize, EventFinalizers)>,
+}
+
+impl PendingFinalizers {
+    fn push(&self, finalizers: EventFinalizers) {
+        if finalizers.is_empty() {
+            self.received.fetch_add(1, Ordering::Relaxed);
+        } else {
+            // Count the event while holding the lock, so that `release` never sees it
+            // counted before its finalizers are queued.
+            let mut pending = self.pending.lock().unwrap();
+            let position = self.received.fetch_add(1, Ordering::Relaxed);
+            pending.finalizers.push_back((position, finalizers));
+        }
+    }
+
+    fn release(&self, num: usize, status: EventStatus) {
+        let mut pending = self.pending.lock().unwrap();
+        pending.acked = (pending.acked + num).min(self.received.load(Ordering::Relaxed));
+        while let Some((position, _)) = pending.finalizers.front() {
+            if *position >= pending.acked {
+                break;
+            }
+            if let Some((_, finalizers)) = pending.finalizers.pop_front() {
+                finalizers.update_status(status);
+            }
+        }
+    }
+}
+
+impl Drop for PendingFinalizers {
+    fn drop(&mut self) {
+        // The sink went away without acking these events.
+        if let Ok(pending) = self.pending.get_mut() {
+            for (_, finalizers) in pending.finalizers.drain(..) {
+                finalizers.update_status(EventStatus::Errored);
+            }
+        }
+    }
+}
+
 #[pin_project]
 pub struct DropWhenFull<S> {
     #[pin]
@@ -248,8 +467,11 @@ impl<T, S: Sink<T> + Unpin> Sink<T> for DropWhenFull<S> {
 
 #[cfg(test)]
 mod test {
-    use super::{Acker, BufferConfig, DropWhenFull, WhenFull};
-    use crate::sink::BoundedSink;
+    use super::{Acker, BufferConfig, DropWhenFull, SyncPolicy, WhenFull};
+    use crate::{
+        event::{BatchNotifier, BatchStatus, Event, EventStatus},
+        sink::BoundedSink,
+    };
     use futures::{future, Sink, Stream};
     use futures01::task::AtomicTask;
     use std::{
@@ -302,6 +524,67 @@ mod test {
         assert!(mock.is_notified());
     }
 
+    #[tokio::test]
+    async fn finalizing_acker_releases_in_order() {
+        let (acker, counter) = Acker::new_for_testing();
+        let acker = Acker::finalizing(acker);
+
+        let (first_batch, first) = BatchNotifier::new_with_receiver();
+        let (second_batch, second) = BatchNotifier::new_with_receiver();
+        let mut first_event = Event::from("first").with_batch_notifier(&first_batch);
+        let mut second_event = Event::from("second").with_batch_notifier(&second_batch);
+        drop((first_batch, second_batch));
+
+        acker.track(&mut first_event);
+        acker.track(&mut second_event);
+        assert!(first_event.metadata().finalizers().is_empty());
+        drop((first_event, second_event));
+
+        acker.ack_with_status(1, EventStatus::Failed);
+        assert_eq!(first.await, BatchStatus::Failed);
+
+        acker.ack(1);
+        assert_eq!(second.await, BatchStatus::Delivered);
+        assert_eq!(counter.load(std::sync::atomic::Ordering::Relaxed), 2);
+    }
+
+    #[tokio::test]
+    async fn finalizing_acker_errors_unacked_on_drop() {
+        let acker = Acker::finalizing(Acker::Null);
+
+        let (batch, receiver) = BatchNotifier::new_with_receiver();
+        let mut event = Event::from("event").with_batch_notifier(&batch);
+        drop(batch);
+
+        acker.track(&mut event);
+        drop(event);
+        drop(acker);
+
+        assert_eq!(receiver.await, BatchStatus::Errored);
+    }
+
+    #[tokio::test]
+    async fn finalizing_acker_only_queues_tracked_events() {
+        let acker = Acker::finalizing(Acker::Null);
+
+        let (batch, receiver) = BatchNotifier::new_with_receiver();
+        let mut tracked = Event::from("tracked").with_batch_notifier(&batch);
+        drop(batch);
+
+        acker.track(&mut Event::from("first"));
+        acker.track(&mut tracked);
+        acker.track(&mut Event::from("last"));
+        drop(tracked);
+
+        if let Acker::Finalizing(pending, _) = &acker {
+            assert_eq!(pending.pending.lock().unwrap().finalizers.len(), 1);
+        }
+
+        acker.ack_with_status(1, EventStatus::Failed);
+        acker.ack(2);
+        assert_eq!(receiver.await, BatchStatus::Delivered);
+    }
+
     #[test]
     fn config_default_values() {
         fn check(source: &str, config: BufferConfig) {
@@ -352,5 +635,46 @@ mod test {
                 when_full: WhenFull::Block,
             },
         );
+
+        check(
+            r#"
+          type = "disk_v2"
+          max_size = 1024
+          "#,
+            BufferConfig::DiskV2 {
+                max_size: 1024,
+                when_full: WhenFull::Block,
+                sync: SyncPolicy::Interval,
+                sync_interval_secs: 1,
+            },
+        );
+
+        check(
+            r#"
+          type = "disk_v2"
+          max_size = 1024
+          sync = "always"
+          "#,
+            BufferConfig::DiskV2 {
+                max_size: 1024,
+                when_full: WhenFull::Block,
+                sync: SyncPolicy::Always,
+                sync_interval_secs: 1,
+            },
+        );
+
+        check(
+            r#"
+          type = "tiered"
+          max_size = 1024
+          "#,
+            BufferConfig::Tiered {
+                max_events: 500,
+                max_size: 1024,
+                when_full: WhenFull::Block,
+                sync: SyncPolicy::Interval,
+                sync_interval_secs: 1,
+            },
+        );
     }
 }
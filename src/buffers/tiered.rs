@@ -0,0 +1,303 @@
+//! A buffer that keeps events in memory and only spills them to disk once the
+//! in-memory channel is full.
+//!
+//! Events from the two tiers are interleaved when read, so their relative
+//! order is not preserved.
+
+use crate::{
+    internal_events::{TieredBufferEventReceived, TieredBufferEventSent},
+    Event,
+};
+use futures::{channel::mpsc, Sink, Stream};
+use pin_project::pin_project;
+use std::{
+    collections::VecDeque,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Tier {
+    Memory,
+    Disk,
+}
+
+impl Tier {
+    fn as_str(self) -> &'static str {
+        match self {
+            Tier::Memory => "memory",
+            Tier::Disk => "disk",
+        }
+    }
+}
+
+/// Records which tier each event handed to the sink came from, so that acks
+/// can be passed on to the disk tier.
+#[derive(Debug, Default)]
+pub struct TierLedger(Mutex<VecDeque<(Tier, usize)>>);
+
+impl TierLedger {
+    fn push(&self, tier: Tier) {
+        let mut tiers = self.0.lock().unwrap();
+        match tiers.back_mut() {
+            Some((last, count)) if *last == tier => *count += 1,
+            _ => tiers.push_back((tier, 1)),
+        }
+    }
+
+    /// Release the next `num` events, returning how many of them came from
+    /// the disk tier.
+    pub(super) fn release(&self, mut num: usize) -> usize {
+        let mut tiers = self.0.lock().unwrap();
+        let mut from_disk = 0;
+        while num > 0 {
+            let (tier, count) = match tiers.front_mut() {
+                Some(front) => front,
+                None => break,
+            };
+            let released = num.min(*count);
+            if *tier == Tier::Disk {
+                from_disk += released;
+            }
+            *count -= released;
+            num -= released;
+            if *count == 0 {
+                tiers.pop_front();
+            }
+        }
+        from_disk
+    }
+}
+
+/// Sends events to the memory tier, or to the disk tier when the former is
+/// full. Only applies back pressure once both tiers are full.
+#[pin_project]
+pub struct OverflowSink<M, D> {
+    #[pin]
+    memory: M,
+    #[pin]
+    disk: D,
+    target: Tier,
+}
+
+impl<M, D> OverflowSink<M, D> {
+    pub fn new(memory: M, disk: D) -> Self {
+        Self {
+            memory,
+            disk,
+            target: Tier::Memory,
+        }
+    }
+}
+
+impl<M, D> Sink<Event> for OverflowSink<M, D>
+where
+    M: Sink<Event>,
+    D: Sink<Event, Error = M::Error>,
+{
+    type Error = M::Error;
+
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        let this = self.project();
+        if let Poll::Ready(result) = this.memory.poll_ready(cx) {
+            *this.target = Tier::Memory;
+            return Poll::Ready(result);
+        }
+
+        match this.disk.poll_ready(cx) {
+            Poll::Ready(result) => {
+                *this.target = Tier::Disk;
+                Poll::Ready(result)
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+
+    fn start_send(self: Pin<&mut Self>, event: Event) -> Result<(), Self::Error> {
+        let this = self.project();
+        emit!(TieredBufferEventReceived {
+            tier: this.target.as_str()
+        });
+        match this.target {
+            Tier::Memory => this.memory.start_send(event),
+            Tier::Disk => this.disk.start_send(event),
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        let this = self.project();
+        let memory = this.memory.poll_flush(cx)?;
+        let disk = this.disk.poll_flush(cx)?;
+        if memory.is_ready() && disk.is_ready() {
+            Poll::Ready(Ok(()))
+        } else {
+            Poll::Pending
+        }
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        let this = self.project();
+        let memory = this.memory.poll_close(cx)?;
+        let disk = this.disk.poll_close(cx)?;
+        if memory.is_ready() && disk.is_ready() {
+            Poll::Ready(Ok(()))
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+/// Reads from both tiers, taking turns between them so that neither starves.
+pub struct Reader {
+    memory: Option<mpsc::Receiver<Event>>,
+    disk: Option<Pin<Box<dyn Stream<Item = Event> + Send>>>,
+    ledger: Arc<TierLedger>,
+    prefer_disk: bool,
+}
+
+impl Reader {
+    pub fn new(
+        memory: mpsc::Receiver<Event>,
+        disk: Pin<Box<dyn Stream<Item = Event> + Send>>,
+        ledger: Arc<TierLedger>,
+    ) -> Self {
+        Self {
+            memory: Some(memory),
+            disk: Some(disk),
+            ledger,
+            prefer_disk: false,
+        }
+    }
+
+    fn poll_tier(&mut self, tier: Tier, cx: &mut Context<'_>) -> Poll<Option<Event>> {
+        let poll = match tier {
+            Tier::Memory => self
+                .memory
+                .as_mut()
+                .map(|memory| Pin::new(memory).poll_next(cx)),
+            Tier::Disk => self.disk.as_mut().map(|disk| disk.as_mut().poll_next(cx)),
+        };
+
+        match poll {
+            Some(Poll::Ready(Some(event))) => {
+                self.ledger.push(tier);
+                emit!(TieredBufferEventSent {
+                    tier: tier.as_str()
+                });
+                Poll::Ready(Some(event))
+            }
+            Some(Poll::Ready(None)) => {
+                match tier {
+                    Tier::Memory => self.memory = None,
+                    Tier::Disk => self.disk = None,
+                }
+                Poll::Ready(None)
+            }
+            Some(Poll::Pending) => Poll::Pending,
+            None => Poll::Ready(None),
+        }
+    }
+}
+
+impl Stream for Reader {
+    type Item = Event;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let order = if self.prefer_disk {
+            [Tier::Disk, Tier::Memory]
+        } else {
+            [Tier::Memory, Tier::Disk]
+        };
+
+        let mut pending = false;
+        for &tier in &order {
+            match self.poll_tier(tier, cx) {
+                Poll::Ready(Some(event)) => {
+                    self.prefer_disk = tier == Tier::Memory;
+                    return Poll::Ready(Some(event));
+                }
+                Poll::Ready(None) => {}
+                Poll::Pending => pending = true,
+            }
+        }
+
+        if pending {
+            Poll::Pending
+        } else {
+            Poll::Ready(None)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::buffers::{BufferConfig, SyncPolicy, WhenFull};
+    use futures::{stream, SinkExt, StreamExt};
+    use tempfile::tempdir;
+
+    #[test]
+    fn ledger_counts_released_disk_events() {
+        let ledger = TierLedger::default();
+        for &tier in &[
+            Tier::Memory,
+            Tier::Disk,
+            Tier::Disk,
+            Tier::Memory,
+            Tier::Disk,
+        ] {
+            ledger.push(tier);
+        }
+
+        assert_eq!(ledger.release(2), 1);
+        assert_eq!(ledger.release(2), 1);
+        assert_eq!(ledger.release(5), 1);
+        assert_eq!(ledger.release(1), 0);
+    }
+
+    #[tokio::test(threaded_scheduler)]
+    async fn spills_to_disk_when_memory_is_full() {
+        let data_dir = tempdir().unwrap();
+        let config = BufferConfig::Tiered {
+            max_events: 2,
+            max_size: 1024 * 1024,
+            when_full: WhenFull::Block,
+            sync: SyncPolicy::Never,
+            sync_interval_secs: 1,
+        };
+        let (tx, rx, acker) = config
+            .build(&Some(data_dir.path().to_path_buf()), "tiered")
+            .unwrap();
+
+        let events: Vec<Event> = (0..10).map(|i| Event::from(i.to_string())).collect();
+        let mut sink = Pin::from(tx.get());
+        sink.send_all(&mut stream::iter(events.clone()).map(Ok))
+            .await
+            .unwrap();
+        drop((sink, tx));
+
+        // Nothing has been read yet, so the events that didn't fit in memory
+        // must be waiting in the disk tier.
+        let buffer_dir = data_dir.path().join("tiered_wal_buffer");
+        let disk_size: u64 = std::fs::read_dir(&buffer_dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .filter(|path| path.extension().map_or(false, |ext| ext == "log"))
+            .map(|path| std::fs::metadata(path).unwrap().len())
+            .sum();
+        assert!(disk_size > 0, "expected events in the disk tier");
+
+        let mut received: Vec<Event> = Pin::from(rx).collect().await;
+        acker.ack(received.len());
+
+        let key = crate::config::log_schema().message_key();
+        received.sort_by_key(|event| {
+            event.as_log()[key]
+                .to_string_lossy()
+                .parse::<usize>()
+                .unwrap()
+        });
+        assert_eq!(received, events);
+    }
+}
@@ -3,7 +3,7 @@ use std::path::PathBuf;
 use structopt::{clap::AppSettings, StructOpt};
 
 #[cfg(feature = "api-client")]
-use crate::top;
+use crate::{tap, top};
 
 #[cfg(windows)]
 use crate::service;
@@ -147,6 +147,10 @@ pub enum SubCommand {
     #[cfg(feature = "api-client")]
     Top(top::Opts),
 
+    /// Print the events output by components, for a local or remote Vector instance
+    #[cfg(feature = "api-client")]
+    Tap(tap::Opts),
+
     /// Manage the vector service.
     #[cfg(windows)]
     Service(service::Opts),
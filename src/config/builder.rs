@@ -4,6 +4,7 @@ use super::{
     compiler, default_data_dir, Config, GlobalOptions, HealthcheckOptions, SinkConfig, SinkOuter,
     SourceConfig, TestDefinition, TransformConfig, TransformOuter,
 };
+use crate::enrichment_tables::EnrichmentTableConfig;
 use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 
@@ -18,6 +19,8 @@ pub struct ConfigBuilder {
     #[serde(default)]
     pub healthchecks: HealthcheckOptions,
     #[serde(default)]
+    pub enrichment_tables: IndexMap<String, Box<dyn EnrichmentTableConfig>>,
+    #[serde(default)]
     pub sources: IndexMap<String, Box<dyn SourceConfig>>,
     #[serde(default)]
     pub sinks: IndexMap<String, SinkOuter>,
@@ -46,6 +49,7 @@ impl From<Config> for ConfigBuilder {
             #[cfg(feature = "api")]
             api: c.api,
             healthchecks: c.healthchecks,
+            enrichment_tables: c.enrichment_tables,
             sources: c.sources,
             sinks: c.sinks,
             transforms: c.transforms,
@@ -126,6 +130,11 @@ impl ConfigBuilder {
 
         self.healthchecks.merge(with.healthchecks);
 
+        with.enrichment_tables.keys().for_each(|k| {
+            if self.enrichment_tables.contains_key(k) {
+                errors.push(format!("duplicate enrichment table name found: {}", k));
+            }
+        });
         with.sources.keys().for_each(|k| {
             if self.sources.contains_key(k) {
                 errors.push(format!("duplicate source name found: {}", k));
@@ -150,6 +159,7 @@ impl ConfigBuilder {
             return Err(errors);
         }
 
+        self.enrichment_tables.extend(with.enrichment_tables);
         self.sources.extend(with.sources);
         self.sinks.extend(with.sinks);
         self.transforms.extend(with.transforms);
@@ -14,9 +14,23 @@ impl ConfigDiff {
     }
 
     pub fn new(old: &Config, new: &Config) -> Self {
+        let mut transforms = Difference::new(&old.transforms, &new.transforms);
+
+        // Transforms look up records in the enrichment tables they were built with, so they're
+        // all rebuilt when any of the tables change.
+        let enrichment_tables = Difference::new(&old.enrichment_tables, &new.enrichment_tables);
+        if enrichment_tables.any() {
+            transforms.to_change.extend(
+                old.transforms
+                    .keys()
+                    .filter(|name| new.transforms.contains_key(*name))
+                    .cloned(),
+            );
+        }
+
         ConfigDiff {
             sources: Difference::new(&old.sources, &new.sources),
-            transforms: Difference::new(&old.transforms, &new.transforms),
+            transforms,
             sinks: Difference::new(&old.sinks, &new.sinks),
         }
     }
@@ -72,6 +86,11 @@ impl Difference {
         self.to_add.contains(name) || self.to_change.contains(name)
     }
 
+    /// True if any component was removed, changed, or added.
+    fn any(&self) -> bool {
+        !(self.to_remove.is_empty() && self.to_change.is_empty() && self.to_add.is_empty())
+    }
+
     fn flip(&mut self) {
         std::mem::swap(&mut self.to_remove, &mut self.to_add);
     }
@@ -109,7 +109,7 @@ pub fn load_builder_from_paths(
 }
 
 pub fn load_from_str(input: &str, format: FormatHint) -> Result<Config, Vec<String>> {
-    let (builder, load_warnings) = load_from_inputs(std::iter::once((input.as_bytes(), format)))?;
+    let (builder, load_warnings) = load_builder_from_str(input, format)?;
     let (config, build_warnings) = builder.build_with_warnings()?;
 
     for warning in load_warnings.into_iter().chain(build_warnings) {
@@ -119,6 +119,13 @@ pub fn load_from_str(input: &str, format: FormatHint) -> Result<Config, Vec<Stri
     Ok(config)
 }
 
+pub fn load_builder_from_str(
+    input: &str,
+    format: FormatHint,
+) -> Result<(ConfigBuilder, Vec<String>), Vec<String>> {
+    load_from_inputs(std::iter::once((input.as_bytes(), format)))
+}
+
 fn load_from_inputs(
     inputs: impl IntoIterator<Item = (impl std::io::Read, FormatHint)>,
 ) -> Result<(ConfigBuilder, Vec<String>), Vec<String>> {
@@ -1,6 +1,7 @@
 use crate::{
     buffers::Acker,
     conditions,
+    enrichment_tables::{EnrichmentTableConfig, TableRegistry},
     event::Metric,
     shutdown::ShutdownSignal,
     sinks::{self, util::UriSerde},
@@ -36,8 +37,8 @@ pub use builder::ConfigBuilder;
 pub use diff::ConfigDiff;
 pub use format::{Format, FormatHint};
 pub use loading::{
-    load_builder_from_paths, load_from_paths, load_from_str, merge_path_lists, process_paths,
-    CONFIG_PATHS,
+    load_builder_from_paths, load_builder_from_str, load_from_paths, load_from_str,
+    merge_path_lists, process_paths, CONFIG_PATHS,
 };
 pub use log_schema::{init_log_schema, log_schema, LogSchema};
 pub use unit_test::build_unit_tests_main as build_unit_tests;
@@ -49,6 +50,7 @@ pub struct Config {
     #[cfg(feature = "api")]
     pub api: api::Options,
     pub healthchecks: HealthcheckOptions,
+    pub enrichment_tables: IndexMap<String, Box<dyn EnrichmentTableConfig>>,
     pub sources: IndexMap<String, Box<dyn SourceConfig>>,
     pub sinks: IndexMap<String, SinkOuter>,
     pub transforms: IndexMap<String, TransformOuter>,
@@ -333,6 +335,14 @@ impl SinkContext {
         }
     }
 
+    #[cfg(test)]
+    pub fn new_test_with_acker(acker: Acker) -> Self {
+        Self {
+            acker,
+            ..Self::new_test()
+        }
+    }
+
     pub fn acker(&self) -> Acker {
         self.acker.clone()
     }
@@ -353,11 +363,45 @@ pub struct TransformOuter {
     pub inner: Box<dyn TransformConfig>,
 }
 
+impl TransformOuter {
+    /// The full names of this transform's named outputs, given the transform's own name.
+    pub fn named_outputs(&self, name: &str) -> Vec<String> {
+        self.inner
+            .named_outputs()
+            .into_iter()
+            .map(|output| format!("{}.{}", name, output))
+            .collect()
+    }
+}
+
 #[async_trait]
 #[typetag::serde(tag = "type")]
 pub trait TransformConfig: core::fmt::Debug + Send + Sync + dyn_clone::DynClone {
     async fn build(&self, globals: &GlobalOptions) -> crate::Result<transforms::Transform>;
 
+    /// Builds the transform with access to the configured enrichment tables. Only transforms
+    /// that look up records in enrichment tables need to implement this.
+    async fn build_with_enrichment_tables(
+        &self,
+        globals: &GlobalOptions,
+        _enrichment_tables: &TableRegistry,
+    ) -> crate::Result<transforms::Transform> {
+        self.build(globals).await
+    }
+
+    /// Builds the transform knowing the type of the events its inputs actually produce, which
+    /// may be narrower than `input_type`. Only transforms that check their configuration
+    /// against the events they'll receive need to implement this.
+    async fn build_for_input_type(
+        &self,
+        globals: &GlobalOptions,
+        enrichment_tables: &TableRegistry,
+        _input_type: DataType,
+    ) -> crate::Result<transforms::Transform> {
+        self.build_with_enrichment_tables(globals, enrichment_tables)
+            .await
+    }
+
     fn input_type(&self) -> DataType;
 
     fn output_type(&self) -> DataType;
@@ -370,6 +414,12 @@ pub trait TransformConfig: core::fmt::Debug + Send + Sync + dyn_clone::DynClone
     fn expand(&mut self) -> crate::Result<Option<IndexMap<String, Box<dyn TransformConfig>>>> {
         Ok(None)
     }
+
+    /// The named outputs this transform can write events to, besides its default output. Other
+    /// components consume them as `<transform name>.<output name>`.
+    fn named_outputs(&self) -> Vec<String> {
+        Vec::new()
+    }
 }
 
 dyn_clone::clone_trait_object!(TransformConfig);
@@ -522,6 +572,46 @@ impl Config {
             .cloned()
             .unwrap_or_else(|| vec![String::from(identifier)])
     }
+
+    /// The full names of the named outputs of the transform `name`, in the form
+    /// `<transform name>.<output name>`.
+    pub fn transform_outputs(&self, name: &str) -> Vec<String> {
+        self.transforms
+            .get(name)
+            .map(|transform| transform.named_outputs(name))
+            .unwrap_or_default()
+    }
+
+    /// The type of the events produced by the given inputs, or `DataType::Any` if they don't
+    /// all produce the same type.
+    pub fn inputs_type(&self, inputs: &[String]) -> DataType {
+        let mut types = inputs.iter().map(|input| self.output_type(input));
+        let first = types.next().unwrap_or(DataType::Any);
+        if types.all(|data_type| data_type == first) {
+            first
+        } else {
+            DataType::Any
+        }
+    }
+
+    /// The type of the events produced by a source, transform or named transform output.
+    fn output_type(&self, name: &str) -> DataType {
+        if let Some(source) = self.sources.get(name) {
+            return source.output_type();
+        }
+
+        self.transforms
+            .iter()
+            .find(|(transform_name, transform)| {
+                *transform_name == name
+                    || transform
+                        .named_outputs(transform_name)
+                        .iter()
+                        .any(|output| output == name)
+            })
+            .map(|(_, transform)| transform.inner.output_type())
+            .unwrap_or(DataType::Any)
+    }
 }
 
 #[cfg(all(
@@ -2,8 +2,9 @@ use super::{Config, ConfigBuilder, TestDefinition, TestInput, TestInputValue};
 use crate::config::{self, GlobalOptions, TransformConfig};
 use crate::{
     conditions::Condition,
+    enrichment_tables::TableRegistry,
     event::{Event, Value},
-    transforms::Transform,
+    transforms::{Transform, TransformOutputs},
 };
 use indexmap::IndexMap;
 use std::{collections::HashMap, path::PathBuf};
@@ -30,6 +31,7 @@ async fn build_unit_tests(mut builder: ConfigBuilder) -> Result<Vec<UnitTest>, V
         #[cfg(feature = "api")]
         api: builder.api,
         healthchecks: builder.healthchecks,
+        enrichment_tables: builder.enrichment_tables,
         sources: builder.sources,
         sinks: builder.sinks,
         transforms: builder.transforms,
@@ -61,6 +63,9 @@ pub struct UnitTest {
     pub name: String,
     inputs: Vec<(Vec<String>, Event)>,
     transforms: IndexMap<String, UnitTestTransform>,
+    /// The named outputs of the transforms, along with the transforms reading
+    /// from each of them.
+    named_outputs: IndexMap<String, Vec<String>>,
     checks: Vec<UnitTestCheck>,
     no_outputs_from: Vec<String>,
     globals: GlobalOptions,
@@ -107,11 +112,13 @@ fn walk(
     node: &str,
     mut inputs: Vec<Event>,
     transforms: &mut IndexMap<String, UnitTestTransform>,
+    named_outputs: &IndexMap<String, Vec<String>>,
     aggregated_results: &mut HashMap<String, (Vec<Event>, Vec<Event>)>,
     globals: &GlobalOptions,
 ) {
     let mut results = Vec::new();
     let mut targets = Vec::new();
+    let mut named_results = Vec::new();
 
     // Use `remove` to take ownership.
     if let Some((key, mut target)) = transforms.remove_entry(node) {
@@ -123,6 +130,23 @@ fn walk(
                 targets = target.next.clone();
                 transforms.insert(key, target);
             }
+            Transform::MultiOutput(ref mut t) => {
+                let mut outputs =
+                    TransformOutputs::new(target.config.named_outputs()).with_component(&key);
+                for input in inputs.clone() {
+                    t.transform(&mut outputs, input);
+                }
+                results.append(outputs.primary());
+                // Named outputs nothing reads from were reduced away.
+                for (name, events) in outputs.named_outputs() {
+                    let output = format!("{}.{}", key, name);
+                    if named_outputs.contains_key(&output) {
+                        named_results.push((output, std::mem::take(events)));
+                    }
+                }
+                targets = target.next.clone();
+                transforms.insert(key, target);
+            }
             Transform::Task(t) => {
                 error!("Using a recently refactored `TaskTransform` in a unit test. You may experience limited support for multiple inputs.");
                 let in_stream = futures::stream::iter(inputs.clone());
@@ -141,6 +165,10 @@ fn walk(
                 });
             }
         }
+    } else if let Some(children) = named_outputs.get(node) {
+        // A named output passes on the events its transform wrote to it.
+        results = inputs.clone();
+        targets = children.clone();
     }
 
     for child in targets {
@@ -148,6 +176,18 @@ fn walk(
             &child,
             results.clone(),
             transforms,
+            named_outputs,
+            aggregated_results,
+            globals,
+        );
+    }
+
+    for (output, events) in named_results {
+        walk(
+            &output,
+            events,
+            transforms,
+            named_outputs,
             aggregated_results,
             globals,
         );
@@ -182,6 +222,7 @@ impl UnitTest {
                 &target,
                 inputs,
                 &mut self.transforms,
+                &self.named_outputs,
                 &mut results,
                 &self.globals,
             );
@@ -410,6 +451,16 @@ async fn build_unit_test(
         .map(|(k, _)| (k.clone(), IndexMap::new()))
         .collect();
 
+    // Named outputs are nodes of their own, fed by their transform.
+    for (name, transform) in &config.transforms {
+        for output in transform.named_outputs(name) {
+            if let Some(outputs) = transform_outputs.get_mut(name) {
+                outputs.insert(output.clone(), ());
+            }
+            transform_outputs.insert(output, IndexMap::new());
+        }
+    }
+
     config.transforms.iter().for_each(|(k, t)| {
         t.inputs.iter().for_each(|i| {
             if let Some(outputs) = transform_outputs.get_mut(i) {
@@ -453,18 +504,38 @@ async fn build_unit_test(
         &mut transform_outputs,
     );
 
+    let enrichment_tables = TableRegistry::build(&config.enrichment_tables, &config.global)
+        .unwrap_or_else(|mut errs| {
+            errors.append(&mut errs);
+            TableRegistry::default()
+        });
+
     // Build reduced transforms.
     let mut transforms: IndexMap<String, UnitTestTransform> = IndexMap::new();
     for (name, transform_config) in &config.transforms {
         if let Some(outputs) = transform_outputs.remove(name) {
-            match transform_config.inner.build(&config.global).await {
+            match transform_config
+                .inner
+                .build_for_input_type(
+                    &config.global,
+                    &enrichment_tables,
+                    config.inputs_type(&transform_config.inputs),
+                )
+                .await
+            {
                 Ok(transform) => {
+                    // Named outputs are walked separately, with their own events.
+                    let named = transform_config.named_outputs(name);
                     transforms.insert(
                         name.clone(),
                         UnitTestTransform {
                             transform,
                             config: transform_config.inner.clone(),
-                            next: outputs.into_iter().map(|(k, _)| k).collect(),
+                            next: outputs
+                                .into_iter()
+                                .map(|(k, _)| k)
+                                .filter(|k| !named.contains(k))
+                                .collect(),
                         },
                     );
                 }
@@ -483,8 +554,15 @@ async fn build_unit_test(
         return Err(errors);
     }
 
+    // Only the named outputs are left.
+    let named_outputs: IndexMap<String, Vec<String>> = transform_outputs
+        .into_iter()
+        .map(|(name, children)| (name, children.into_iter().map(|(k, _)| k).collect()))
+        .collect();
+
     definition.outputs.iter().for_each(|o| {
-        if !transforms.contains_key(&o.extract_from) {
+        if !transforms.contains_key(&o.extract_from) && !named_outputs.contains_key(&o.extract_from)
+        {
             let targets = inputs.iter().map(|(i, _)| i).flatten().collect::<Vec<_>>();
             if targets.len() == 1 {
                 errors.push(format!(
@@ -542,6 +620,7 @@ async fn build_unit_test(
             name: definition.name.clone(),
             inputs,
             transforms,
+            named_outputs,
             checks,
             no_outputs_from: definition.no_outputs_from.clone(),
             globals: config.global.clone(),
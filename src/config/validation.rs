@@ -30,7 +30,7 @@ pub fn check_shape(config: &ConfigBuilder) -> Result<(), Vec<String>> {
         uses.push(ctype);
     }
 
-    for (name, uses) in name_uses.into_iter().filter(|(_name, uses)| uses.len() > 1) {
+    for (name, uses) in name_uses.iter().filter(|(_name, uses)| uses.len() > 1) {
         errors.push(format!(
             "More than one component with name \"{}\" ({}).",
             name,
@@ -38,6 +38,17 @@ pub fn check_shape(config: &ConfigBuilder) -> Result<(), Vec<String>> {
         ));
     }
 
+    // Check that named transform outputs don't shadow other components
+    let outputs = named_outputs(config);
+    for (output, transform) in &outputs {
+        if name_uses.contains_key(output.as_str()) {
+            errors.push(format!(
+                "Output \"{}\" of transform \"{}\" has the same name as another component.",
+                output, transform
+            ));
+        }
+    }
+
     // Warnings and errors
     let sink_inputs = config
         .sinks
@@ -57,7 +68,10 @@ pub fn check_shape(config: &ConfigBuilder) -> Result<(), Vec<String>> {
         }
 
         for input in inputs {
-            if !config.sources.contains_key(&input) && !config.transforms.contains_key(&input) {
+            if !config.sources.contains_key(&input)
+                && !config.transforms.contains_key(&input)
+                && !outputs.contains_key(&input)
+            {
                 errors.push(format!(
                     "Input {:?} for {} {:?} doesn't exist.",
                     input, output_type, name
@@ -73,6 +87,20 @@ pub fn check_shape(config: &ConfigBuilder) -> Result<(), Vec<String>> {
     }
 }
 
+/// Maps the full name of every named transform output to the transform it belongs to.
+fn named_outputs(config: &ConfigBuilder) -> HashMap<String, String> {
+    config
+        .transforms
+        .iter()
+        .flat_map(|(name, transform)| {
+            transform
+                .named_outputs(name)
+                .into_iter()
+                .map(move |output| (output, name.clone()))
+        })
+        .collect()
+}
+
 pub fn check_resources(config: &ConfigBuilder) -> Result<(), Vec<String>> {
     let source_resources = config
         .sources
@@ -254,6 +282,16 @@ impl From<&ConfigBuilder> for Graph {
     fn from(config: &ConfigBuilder) -> Self {
         let mut graph = Graph::default();
 
+        // Named outputs carry the same type as the transform's default output,
+        // so they are checked as the transform itself.
+        let outputs = named_outputs(config);
+        let resolve = |inputs: &[String]| {
+            inputs
+                .iter()
+                .map(|input| outputs.get(input).unwrap_or(input).clone())
+                .collect::<Vec<_>>()
+        };
+
         // TODO: validate that node names are unique across sources/transforms/sinks?
         for (name, config) in config.sources.iter() {
             graph.add_source(name, config.output_type());
@@ -264,12 +302,12 @@ impl From<&ConfigBuilder> for Graph {
                 name,
                 config.inner.input_type(),
                 config.inner.output_type(),
-                config.inputs.clone(),
+                resolve(&config.inputs),
             );
         }
 
         for (name, config) in config.sinks.iter() {
-            graph.add_sink(name, config.inner.input_type(), config.inputs.clone());
+            graph.add_sink(name, config.inner.input_type(), resolve(&config.inputs));
         }
 
         graph
@@ -0,0 +1,477 @@
+use super::{
+    Case, Condition, EnrichmentTableConfig, EnrichmentTableDescription, IndexHandle, Table,
+};
+use crate::{
+    config::{GenerateConfig, GlobalOptions},
+    types::{parse_conversion_map, Conversion},
+};
+use bytes::Bytes;
+use serde::{Deserialize, Serialize};
+use shared::TimeZone;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::hash::Hasher;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime};
+use vrl::Value;
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct FileConfig {
+    /// The path of the CSV file to load.
+    pub path: PathBuf,
+    #[serde(default = "default_delimiter")]
+    pub delimiter: char,
+    /// Whether the first row of the file holds the column names. If not, the columns are named
+    /// after their position, starting at `0`.
+    #[serde(default = "crate::serde::default_true")]
+    pub include_headers: bool,
+    /// The types to convert the columns to, keyed by column name. Columns without a type are
+    /// loaded as strings.
+    #[serde(default)]
+    pub schema: HashMap<String, String>,
+    /// How often to check the file for changes, reloading it when it's modified. Set to `0` to
+    /// never reload the file.
+    #[serde(default = "default_reload_interval_secs")]
+    pub reload_interval_secs: u64,
+}
+
+fn default_delimiter() -> char {
+    ','
+}
+
+fn default_reload_interval_secs() -> u64 {
+    60
+}
+
+inventory::submit! {
+    EnrichmentTableDescription::new::<FileConfig>("file")
+}
+
+impl GenerateConfig for FileConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"path = "/etc/vector/hosts.csv"
+            schema.port = "integer""#,
+        )
+        .unwrap()
+    }
+}
+
+#[typetag::serde(name = "file")]
+impl EnrichmentTableConfig for FileConfig {
+    fn build(&self, globals: &GlobalOptions) -> crate::Result<Box<dyn Table + Send + Sync>> {
+        if !self.delimiter.is_ascii() {
+            return Err("`delimiter` must be a single ASCII character".into());
+        }
+
+        let conversions = parse_conversion_map(&self.schema, globals.timezone)?;
+
+        Ok(Box::new(File::load(
+            self.clone(),
+            globals.timezone,
+            conversions,
+            &[],
+        )?))
+    }
+
+    fn table_type(&self) -> &'static str {
+        "file"
+    }
+}
+
+#[derive(Debug)]
+struct Index {
+    case: Case,
+    /// The positions of the indexed columns, sorted by column name.
+    columns: Vec<usize>,
+    rows: HashMap<u64, Vec<usize>>,
+}
+
+/// An enrichment table loaded from a CSV file.
+#[derive(Debug)]
+pub struct File {
+    config: FileConfig,
+    timezone: TimeZone,
+    conversions: HashMap<String, Conversion>,
+    modified: Option<SystemTime>,
+    last_checked: Mutex<Instant>,
+    headers: Vec<String>,
+    data: Vec<Vec<Value>>,
+    indexes: Vec<Index>,
+}
+
+impl File {
+    fn load(
+        config: FileConfig,
+        timezone: TimeZone,
+        conversions: HashMap<String, Conversion>,
+        indexes: &[(Case, Vec<String>)],
+    ) -> crate::Result<Self> {
+        let modified = fs::metadata(&config.path)
+            .and_then(|metadata| metadata.modified())
+            .ok();
+
+        let mut reader = csv::ReaderBuilder::new()
+            .delimiter(config.delimiter as u8)
+            .has_headers(config.include_headers)
+            .from_path(&config.path)?;
+
+        let records = reader.byte_records().collect::<Result<Vec<_>, _>>()?;
+
+        let headers = if config.include_headers {
+            reader
+                .headers()?
+                .iter()
+                .map(ToOwned::to_owned)
+                .collect::<Vec<_>>()
+        } else {
+            let width = records.first().map_or(0, |record| record.len());
+            (0..width).map(|i| i.to_string()).collect()
+        };
+
+        let data = records
+            .iter()
+            .map(|record| {
+                headers
+                    .iter()
+                    .zip(record.iter())
+                    .map(|(header, field)| {
+                        let field = Bytes::copy_from_slice(field);
+                        match conversions.get(header) {
+                            Some(conversion) => conversion
+                                .convert::<Value>(field)
+                                .map_err(|error| format!("column \"{}\": {}", header, error)),
+                            None => Ok(field.into()),
+                        }
+                    })
+                    .collect::<Result<Vec<Value>, _>>()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut file = Self {
+            config,
+            timezone,
+            conversions,
+            modified,
+            last_checked: Mutex::new(Instant::now()),
+            headers,
+            data,
+            indexes: vec![],
+        };
+
+        for (case, fields) in indexes {
+            let fields = fields.iter().map(String::as_str).collect::<Vec<_>>();
+            file.add_index(*case, &fields)?;
+        }
+
+        Ok(file)
+    }
+
+    fn column(&self, field: &str) -> Result<usize, String> {
+        self.headers
+            .iter()
+            .position(|header| header == field)
+            .ok_or_else(|| format!("field \"{}\" not found in table", field))
+    }
+
+    fn row(&self, row: &[Value]) -> BTreeMap<String, Value> {
+        self.headers
+            .iter()
+            .cloned()
+            .zip(row.iter().cloned())
+            .collect()
+    }
+}
+
+/// Hash the given values, so that values matching each other with the given case sensitivity
+/// hash to the same key.
+fn hash_values<'a>(case: Case, values: impl Iterator<Item = &'a Value>) -> u64 {
+    let mut hasher = DefaultHasher::new();
+
+    for value in values {
+        match value {
+            Value::Bytes(bytes) if case == Case::Insensitive => {
+                hasher.write(&bytes.to_ascii_lowercase())
+            }
+            Value::Bytes(bytes) => hasher.write(bytes),
+            value => hasher.write(value.to_string().as_bytes()),
+        }
+
+        // Separate the values, so that `"ab", "c"` and `"a", "bc"` don't hash to the same key.
+        hasher.write_u8(0);
+    }
+
+    hasher.finish()
+}
+
+impl Table for File {
+    fn find_table_rows(
+        &self,
+        case: Case,
+        conditions: &[Condition<'_>],
+        index: Option<IndexHandle>,
+    ) -> Result<Vec<BTreeMap<String, Value>>, String> {
+        let conditions = conditions
+            .iter()
+            .map(|condition| Ok((self.column(condition.field)?, &condition.value)))
+            .collect::<Result<Vec<_>, String>>()?;
+
+        let matches = |row: &&Vec<Value>| {
+            conditions
+                .iter()
+                .all(|(column, value)| case.matches(&row[*column], value))
+        };
+
+        let rows = match index.and_then(|IndexHandle(handle)| self.indexes.get(handle)) {
+            Some(index) => {
+                // The conditions are keyed by field name, so they're hashed in the same order as
+                // the sorted index columns.
+                let mut values = conditions.clone();
+                values.sort_by_key(|(column, _)| &self.headers[*column]);
+                let key = hash_values(index.case, values.iter().map(|(_, value)| *value));
+
+                index
+                    .rows
+                    .get(&key)
+                    .into_iter()
+                    .flatten()
+                    .map(|&row| &self.data[row])
+                    .filter(matches)
+                    .map(|row| self.row(row))
+                    .collect()
+            }
+            None => self
+                .data
+                .iter()
+                .filter(matches)
+                .map(|row| self.row(row))
+                .collect(),
+        };
+
+        Ok(rows)
+    }
+
+    fn add_index(&mut self, case: Case, fields: &[&str]) -> Result<IndexHandle, String> {
+        let mut fields = fields.to_vec();
+        fields.sort_unstable();
+
+        let columns = fields
+            .iter()
+            .map(|field| self.column(field))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if let Some(handle) = self
+            .indexes
+            .iter()
+            .position(|index| index.case == case && index.columns == columns)
+        {
+            return Ok(IndexHandle(handle));
+        }
+
+        let mut rows = HashMap::<_, Vec<_>>::new();
+        for (position, row) in self.data.iter().enumerate() {
+            let key = hash_values(case, columns.iter().map(|column| &row[*column]));
+            rows.entry(key).or_default().push(position);
+        }
+
+        self.indexes.push(Index {
+            case,
+            columns,
+            rows,
+        });
+
+        Ok(IndexHandle(self.indexes.len() - 1))
+    }
+
+    fn needs_reload(&self) -> bool {
+        if self.config.reload_interval_secs == 0 {
+            return false;
+        }
+
+        let mut last_checked = self.last_checked.lock().expect("lock poisoned");
+        if last_checked.elapsed() < Duration::from_secs(self.config.reload_interval_secs) {
+            return false;
+        }
+        *last_checked = Instant::now();
+
+        let modified = fs::metadata(&self.config.path)
+            .and_then(|metadata| metadata.modified())
+            .ok();
+
+        modified.is_some() && modified != self.modified
+    }
+
+    fn reload(&self) -> Result<Box<dyn Table + Send + Sync>, String> {
+        let indexes = self
+            .indexes
+            .iter()
+            .map(|index| {
+                let fields = index
+                    .columns
+                    .iter()
+                    .map(|column| self.headers[*column].clone())
+                    .collect();
+
+                (index.case, fields)
+            })
+            .collect::<Vec<_>>();
+
+        File::load(
+            self.config.clone(),
+            self.timezone,
+            self.conversions.clone(),
+            &indexes,
+        )
+        .map(|file| Box::new(file) as _)
+        .map_err(|error| error.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enrichment_tables::TableRegistry;
+    use indexmap::IndexMap;
+    use std::io::Write;
+
+    fn load(contents: &str, schema: &[(&str, &str)]) -> (tempfile::NamedTempFile, File) {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(contents.as_bytes()).unwrap();
+
+        let config = FileConfig {
+            path: file.path().to_owned(),
+            delimiter: ',',
+            include_headers: true,
+            schema: schema
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+            reload_interval_secs: 0,
+        };
+        let conversions = parse_conversion_map(&config.schema, TimeZone::Local).unwrap();
+        let table = File::load(config, TimeZone::Local, conversions, &[]).unwrap();
+
+        (file, table)
+    }
+
+    fn condition<'a>(field: &'a str, value: impl Into<Value>) -> Condition<'a> {
+        Condition {
+            field,
+            value: value.into(),
+        }
+    }
+
+    #[test]
+    fn finds_rows_with_typed_columns() {
+        let (_file, table) = load(
+            "host,team,port\nweb-1,frontend,80\nweb-2,frontend,8080\ndb-1,storage,5432\n",
+            &[("port", "integer")],
+        );
+
+        let rows = table
+            .find_table_rows(Case::Sensitive, &[condition("team", "frontend")], None)
+            .unwrap();
+
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[1]["host"], Value::from("web-2"));
+        assert_eq!(rows[1]["port"], Value::from(8080));
+    }
+
+    #[test]
+    fn finds_rows_using_index() {
+        let (_file, mut table) = load(
+            "host,team\nweb-1,frontend\nWEB-1,storage\ndb-1,storage\n",
+            &[],
+        );
+
+        let sensitive = table.add_index(Case::Sensitive, &["host"]).unwrap();
+        let insensitive = table.add_index(Case::Insensitive, &["host"]).unwrap();
+        assert_ne!(sensitive, insensitive);
+        assert_eq!(
+            table.add_index(Case::Sensitive, &["host"]).unwrap(),
+            sensitive
+        );
+
+        let rows = table
+            .find_table_rows(
+                Case::Sensitive,
+                &[condition("host", "web-1")],
+                Some(sensitive),
+            )
+            .unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0]["team"], Value::from("frontend"));
+
+        let rows = table
+            .find_table_rows(
+                Case::Insensitive,
+                &[condition("host", "Web-1")],
+                Some(insensitive),
+            )
+            .unwrap();
+        assert_eq!(rows.len(), 2);
+    }
+
+    #[test]
+    fn rejects_unknown_fields() {
+        let (_file, mut table) = load("host,team\nweb-1,frontend\n", &[]);
+
+        assert!(table.add_index(Case::Sensitive, &["datacenter"]).is_err());
+        assert!(table
+            .find_table_rows(Case::Sensitive, &[condition("datacenter", "eu")], None)
+            .is_err());
+    }
+
+    #[test]
+    fn registry_reloads_modified_files() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(b"host,team\nweb-1,frontend\n").unwrap();
+
+        let mut configs = IndexMap::<String, Box<dyn EnrichmentTableConfig>>::new();
+        configs.insert(
+            "hosts".to_owned(),
+            Box::new(FileConfig {
+                path: file.path().to_owned(),
+                delimiter: ',',
+                include_headers: true,
+                schema: HashMap::new(),
+                reload_interval_secs: 1,
+            }),
+        );
+        let registry = TableRegistry::build(&configs, &GlobalOptions::default()).unwrap();
+        let index = registry
+            .add_index("hosts", Case::Sensitive, &["host"])
+            .unwrap();
+        let team = |host: &str| {
+            registry
+                .find_table_row(
+                    "hosts",
+                    Case::Sensitive,
+                    &[condition("host", host)],
+                    Some(index),
+                )
+                .map(|row| row["team"].clone())
+        };
+        assert_eq!(team("web-1"), Ok(Value::from("frontend")));
+
+        // Files are only checked once their reload interval elapsed, and the
+        // modification time of the rewritten file needs to differ.
+        let rewrite = |contents: &str| {
+            std::thread::sleep(Duration::from_millis(1100));
+            fs::write(file.path(), contents).unwrap();
+            registry.reload_changed();
+        };
+
+        rewrite("host,team\nweb-1,storage\nweb-2,frontend\n");
+        assert_eq!(team("web-1"), Ok(Value::from("storage")));
+        assert_eq!(team("web-2"), Ok(Value::from("frontend")));
+
+        // Rows of varying lengths fail to load, so the previous data is kept.
+        rewrite("host,team\nweb-3\n");
+        assert_eq!(team("web-1"), Ok(Value::from("storage")));
+        assert!(team("web-3").is_err());
+    }
+}
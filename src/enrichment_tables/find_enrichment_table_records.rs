@@ -0,0 +1,62 @@
+use super::{vrl_util::Lookup, TableRegistry};
+use vrl::prelude::*;
+
+#[derive(Clone, Debug)]
+pub struct FindEnrichmentTableRecords {
+    tables: TableRegistry,
+}
+
+impl FindEnrichmentTableRecords {
+    pub fn new(tables: TableRegistry) -> Self {
+        Self { tables }
+    }
+}
+
+impl Function for FindEnrichmentTableRecords {
+    fn identifier(&self) -> &'static str {
+        "find_enrichment_table_records"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        super::vrl_util::PARAMETERS
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[]
+    }
+
+    fn compile(&self, mut arguments: ArgumentList) -> Compiled {
+        let lookup = Lookup::compile(&self.tables, &mut arguments)?;
+
+        Ok(Box::new(FindEnrichmentTableRecordsFn {
+            lookup,
+            tables: self.tables.clone(),
+        }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct FindEnrichmentTableRecordsFn {
+    lookup: Lookup,
+    tables: TableRegistry,
+}
+
+impl Expression for FindEnrichmentTableRecordsFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let conditions = self.lookup.conditions(ctx)?;
+
+        self.tables
+            .find_table_rows(
+                &self.lookup.table,
+                self.lookup.case,
+                &conditions,
+                Some(self.lookup.index),
+            )
+            .map(|rows| Value::Array(rows.into_iter().map(Value::Object).collect()))
+            .map_err(Into::into)
+    }
+
+    fn type_def(&self, _: &state::Compiler) -> TypeDef {
+        TypeDef::new().array_mapped::<(), Kind>(map! { (): Kind::Object })
+    }
+}
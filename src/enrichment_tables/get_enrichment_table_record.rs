@@ -0,0 +1,64 @@
+use super::{vrl_util::Lookup, TableRegistry};
+use vrl::prelude::*;
+
+#[derive(Clone, Debug)]
+pub struct GetEnrichmentTableRecord {
+    tables: TableRegistry,
+}
+
+impl GetEnrichmentTableRecord {
+    pub fn new(tables: TableRegistry) -> Self {
+        Self { tables }
+    }
+}
+
+impl Function for GetEnrichmentTableRecord {
+    fn identifier(&self) -> &'static str {
+        "get_enrichment_table_record"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        super::vrl_util::PARAMETERS
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[]
+    }
+
+    fn compile(&self, mut arguments: ArgumentList) -> Compiled {
+        let lookup = Lookup::compile(&self.tables, &mut arguments)?;
+
+        Ok(Box::new(GetEnrichmentTableRecordFn {
+            lookup,
+            tables: self.tables.clone(),
+        }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct GetEnrichmentTableRecordFn {
+    lookup: Lookup,
+    tables: TableRegistry,
+}
+
+impl Expression for GetEnrichmentTableRecordFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let conditions = self.lookup.conditions(ctx)?;
+
+        self.tables
+            .find_table_row(
+                &self.lookup.table,
+                self.lookup.case,
+                &conditions,
+                Some(self.lookup.index),
+            )
+            .map(Value::Object)
+            .map_err(Into::into)
+    }
+
+    fn type_def(&self, _: &state::Compiler) -> TypeDef {
+        TypeDef::new().fallible().object::<(), Kind>(map! {
+            (): Kind::all(),
+        })
+    }
+}
@@ -0,0 +1,260 @@
+//! Enrichment tables hold external data, such as CSV files, that VRL programs can look up records
+//! in to enrich events.
+//!
+//! The tables are configured in the top-level `enrichment_tables` section, loaded into a
+//! [`TableRegistry`] when the topology is built, and exposed to the `remap` transform through the
+//! `get_enrichment_table_record` and `find_enrichment_table_records` functions.
+
+use crate::{
+    config::{component::ComponentDescription, GlobalOptions},
+    internal_events::{EnrichmentTableReloadFailed, EnrichmentTableReloaded},
+};
+use indexmap::IndexMap;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use vrl::Value;
+
+#[cfg(feature = "enrichment-tables-file")]
+pub mod file;
+mod find_enrichment_table_records;
+mod get_enrichment_table_record;
+mod vrl_util;
+
+pub use find_enrichment_table_records::FindEnrichmentTableRecords;
+pub use get_enrichment_table_record::GetEnrichmentTableRecord;
+
+/// How often the registry checks whether any of its tables need to be reloaded.
+const RELOAD_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+#[typetag::serde(tag = "type")]
+pub trait EnrichmentTableConfig: fmt::Debug + Send + Sync {
+    fn build(&self, globals: &GlobalOptions) -> crate::Result<Box<dyn Table + Send + Sync>>;
+
+    fn table_type(&self) -> &'static str;
+}
+
+pub type EnrichmentTableDescription = ComponentDescription<Box<dyn EnrichmentTableConfig>>;
+
+inventory::collect!(EnrichmentTableDescription);
+
+/// Whether string values are compared case sensitively when looking up records.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Case {
+    Sensitive,
+    Insensitive,
+}
+
+impl Case {
+    /// Compare a value stored in a table with a value from a lookup condition.
+    pub fn matches(self, table_value: &Value, value: &Value) -> bool {
+        match (self, table_value, value) {
+            (Case::Insensitive, Value::Bytes(a), Value::Bytes(b)) => a.eq_ignore_ascii_case(b),
+            _ => table_value == value,
+        }
+    }
+}
+
+/// A condition a record has to meet to be returned by a lookup.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Condition<'a> {
+    pub field: &'a str,
+    pub value: Value,
+}
+
+/// A handle to an index created on a table, to speed up lookups on a given set of fields.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct IndexHandle(pub usize);
+
+pub trait Table: fmt::Debug {
+    /// Find all records matching every one of the given conditions.
+    ///
+    /// If an index handle is given, the conditions must be on the fields the index was created
+    /// for, with the same case sensitivity.
+    fn find_table_rows(
+        &self,
+        case: Case,
+        conditions: &[Condition<'_>],
+        index: Option<IndexHandle>,
+    ) -> Result<Vec<BTreeMap<String, Value>>, String>;
+
+    /// Create an index on the given fields, or return the handle of an existing identical index.
+    fn add_index(&mut self, case: Case, fields: &[&str]) -> Result<IndexHandle, String>;
+
+    /// Whether the data backing this table changed since it was loaded.
+    fn needs_reload(&self) -> bool {
+        false
+    }
+
+    /// Load a fresh copy of this table, keeping all of its indexes at the same handles.
+    fn reload(&self) -> Result<Box<dyn Table + Send + Sync>, String>;
+}
+
+type Tables = HashMap<String, Box<dyn Table + Send + Sync>>;
+
+/// The enrichment tables available to VRL programs, shared between all transforms built from a
+/// config.
+#[derive(Clone, Default)]
+pub struct TableRegistry {
+    tables: Arc<RwLock<Tables>>,
+}
+
+impl TableRegistry {
+    /// Build all the configured enrichment tables.
+    pub fn build(
+        configs: &IndexMap<String, Box<dyn EnrichmentTableConfig>>,
+        globals: &GlobalOptions,
+    ) -> Result<Self, Vec<String>> {
+        let mut tables = HashMap::new();
+        let mut errors = vec![];
+
+        for (name, config) in configs {
+            match config.build(globals) {
+                Ok(table) => {
+                    tables.insert(name.clone(), table);
+                }
+                Err(error) => errors.push(format!("Enrichment table \"{}\": {}", name, error)),
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(Self {
+                tables: Arc::new(RwLock::new(tables)),
+            })
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// The names of all the tables in the registry.
+    pub fn table_ids(&self) -> Vec<String> {
+        let mut ids = self.read().keys().cloned().collect::<Vec<_>>();
+        ids.sort();
+        ids
+    }
+
+    pub fn add_index(
+        &self,
+        table: &str,
+        case: Case,
+        fields: &[&str],
+    ) -> Result<IndexHandle, String> {
+        match self.write().get_mut(table) {
+            Some(table) => table.add_index(case, fields),
+            None => Err(format!("enrichment table \"{}\" not loaded", table)),
+        }
+    }
+
+    /// Find the single record matching the conditions, failing if none or more than one record
+    /// matches.
+    pub fn find_table_row(
+        &self,
+        table: &str,
+        case: Case,
+        conditions: &[Condition<'_>],
+        index: Option<IndexHandle>,
+    ) -> Result<BTreeMap<String, Value>, String> {
+        let mut rows = self.find_table_rows(table, case, conditions, index)?;
+
+        match rows.len() {
+            1 => Ok(rows.pop().expect("one row")),
+            0 => Err("no rows found".to_owned()),
+            _ => Err("more than one row found".to_owned()),
+        }
+    }
+
+    pub fn find_table_rows(
+        &self,
+        table: &str,
+        case: Case,
+        conditions: &[Condition<'_>],
+        index: Option<IndexHandle>,
+    ) -> Result<Vec<BTreeMap<String, Value>>, String> {
+        match self.read().get(table) {
+            Some(table) => table.find_table_rows(case, conditions, index),
+            None => Err(format!("enrichment table \"{}\" not loaded", table)),
+        }
+    }
+
+    /// Reload all the tables whose data changed since they were loaded. Tables that fail to
+    /// reload keep serving their previous data.
+    pub fn reload_changed(&self) {
+        // The new tables are loaded without holding the write lock, so lookups aren't blocked
+        // while reading large files.
+        let reloaded = self
+            .read()
+            .iter()
+            .filter(|(_, table)| table.needs_reload())
+            .filter_map(|(name, table)| match table.reload() {
+                Ok(table) => Some((name.clone(), table)),
+                Err(error) => {
+                    emit!(EnrichmentTableReloadFailed { table: name, error });
+                    None
+                }
+            })
+            .collect::<Vec<_>>();
+
+        if reloaded.is_empty() {
+            return;
+        }
+
+        let mut tables = self.write();
+        for (name, table) in reloaded {
+            emit!(EnrichmentTableReloaded { table: &name });
+            tables.insert(name, table);
+        }
+    }
+
+    /// Periodically reload the tables whose data changed, for as long as any transform holds on
+    /// to this registry.
+    pub fn watch(&self) {
+        if self.read().is_empty() {
+            return;
+        }
+
+        let tables = Arc::downgrade(&self.tables);
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(RELOAD_CHECK_INTERVAL);
+            loop {
+                interval.tick().await;
+
+                let registry = match tables.upgrade() {
+                    Some(tables) => TableRegistry { tables },
+                    None => break,
+                };
+
+                // Reading the data of the tables blocks, so it's kept off the runtime threads.
+                if let Err(error) =
+                    tokio::task::spawn_blocking(move || registry.reload_changed()).await
+                {
+                    error!(message = "Reloading enrichment tables failed.", %error);
+                }
+            }
+        });
+    }
+
+    /// The VRL functions looking up records in the tables of this registry.
+    pub fn vrl_functions(&self) -> Vec<Box<dyn vrl::Function>> {
+        vec![
+            Box::new(GetEnrichmentTableRecord::new(self.clone())),
+            Box::new(FindEnrichmentTableRecords::new(self.clone())),
+        ]
+    }
+
+    fn read(&self) -> std::sync::RwLockReadGuard<'_, Tables> {
+        self.tables.read().expect("enrichment table lock poisoned")
+    }
+
+    fn write(&self) -> std::sync::RwLockWriteGuard<'_, Tables> {
+        self.tables.write().expect("enrichment table lock poisoned")
+    }
+}
+
+impl fmt::Debug for TableRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TableRegistry")
+            .field("tables", &self.table_ids())
+            .finish()
+    }
+}
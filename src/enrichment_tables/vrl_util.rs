@@ -0,0 +1,152 @@
+//! Helpers shared by the enrichment table VRL functions.
+
+use super::{Case, Condition, IndexHandle, TableRegistry};
+use std::collections::BTreeMap;
+use std::fmt;
+use vrl::{
+    diagnostic::{Label, Span},
+    prelude::*,
+};
+
+pub(super) const PARAMETERS: &[Parameter] = &[
+    Parameter {
+        keyword: "table",
+        kind: kind::BYTES,
+        required: true,
+    },
+    Parameter {
+        keyword: "condition",
+        kind: kind::OBJECT,
+        required: true,
+    },
+    Parameter {
+        keyword: "case_sensitive",
+        kind: kind::BOOLEAN,
+        required: false,
+    },
+];
+
+#[derive(Debug)]
+pub(super) enum Error {
+    TableNotFound {
+        table: String,
+        available: Vec<String>,
+        span: Span,
+    },
+    InvalidIndex {
+        error: String,
+        span: Span,
+    },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::TableNotFound { table, .. } => {
+                write!(f, "enrichment table \"{}\" not found", table)
+            }
+            Error::InvalidIndex { error, .. } => {
+                write!(f, "invalid enrichment table lookup: {}", error)
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl DiagnosticError for Error {
+    fn code(&self) -> usize {
+        match self {
+            Error::TableNotFound { .. } => 115,
+            Error::InvalidIndex { .. } => 116,
+        }
+    }
+
+    fn labels(&self) -> Vec<Label> {
+        match self {
+            Error::TableNotFound {
+                table,
+                available,
+                span,
+            } => vec![
+                Label::primary(format!("enrichment table \"{}\" not found", table), *span),
+                Label::context(format!("available tables: {}", available.join(", ")), *span),
+            ],
+            Error::InvalidIndex { error, span } => vec![Label::primary(
+                format!("invalid enrichment table lookup: {}", error),
+                *span,
+            )],
+        }
+    }
+}
+
+/// A lookup in an enrichment table, as compiled from the function arguments.
+#[derive(Debug, Clone)]
+pub(super) struct Lookup {
+    pub table: String,
+    pub condition: BTreeMap<String, expression::Expr>,
+    pub case: Case,
+    pub index: IndexHandle,
+}
+
+impl Lookup {
+    /// Compile the lookup arguments, creating an index on the condition fields so the lookups
+    /// don't have to scan the whole table at runtime.
+    pub fn compile(
+        tables: &TableRegistry,
+        arguments: &mut ArgumentList,
+    ) -> std::result::Result<Self, Box<dyn DiagnosticError>> {
+        let table_span = arguments.span("table").unwrap_or_default();
+        let table = arguments
+            .required_literal("table")?
+            .to_value()
+            .try_bytes_utf8_lossy()
+            .expect("table not bytes")
+            .into_owned();
+
+        let available = tables.table_ids();
+        if !available.contains(&table) {
+            return Err(Box::new(Error::TableNotFound {
+                table,
+                available,
+                span: table_span,
+            }));
+        }
+
+        let condition_span = arguments.span("condition").unwrap_or_default();
+        let condition = arguments.required_object("condition")?;
+
+        let case = match arguments.optional_enum("case_sensitive", &[true.into(), false.into()])? {
+            Some(Value::Boolean(false)) => Case::Insensitive,
+            _ => Case::Sensitive,
+        };
+
+        let fields = condition.keys().map(String::as_str).collect::<Vec<_>>();
+        let index = tables.add_index(&table, case, &fields).map_err(|error| {
+            Box::new(Error::InvalidIndex {
+                error,
+                span: condition_span,
+            }) as Box<dyn DiagnosticError>
+        })?;
+
+        Ok(Self {
+            table,
+            condition,
+            case,
+            index,
+        })
+    }
+
+    /// Resolve the condition values against the current event.
+    pub fn conditions(&self, ctx: &mut Context) -> Result<Vec<Condition<'_>>> {
+        self.condition
+            .iter()
+            .map(|(field, expr)| {
+                Ok(Condition {
+                    field,
+                    value: expr.resolve(ctx)?,
+                })
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,262 @@
+//! End-to-end acknowledgement of events.
+//!
+//! A source that wants to know when its events have been delivered creates a
+//! `BatchNotifier` and attaches it to every event it emits. Each event then
+//! carries an `EventFinalizer` pointing back at that batch. Finalizers are
+//! shared by every clone of an event (for example when `Fanout` hands the
+//! event to several sinks), so the batch is only notified once every clone has
+//! been dropped, with the most severe status reported by any of the sinks.
+
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+};
+use tokio::sync::oneshot;
+
+/// The delivery status of a single event, as reported by a sink.
+///
+/// Variants are ordered by severity; combining two statuses keeps the more
+/// severe one.
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum EventStatus {
+    /// No sink has reported on the event. This is also the status of events
+    /// that were intentionally dropped by a transform.
+    Dropped,
+    /// The event was accepted by the sink's destination.
+    Delivered,
+    /// The sink failed to deliver the event, but a retry may succeed.
+    Errored,
+    /// The event was permanently rejected by the sink's destination.
+    Failed,
+}
+
+impl EventStatus {
+    /// Combine this status with another one, keeping the most severe.
+    pub fn update(self, status: Self) -> Self {
+        self.max(status)
+    }
+}
+
+/// The delivery status of a whole batch of events, as seen by the source.
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum BatchStatus {
+    /// Every event in the batch was delivered or intentionally dropped.
+    Delivered,
+    /// At least one event in the batch could not be delivered.
+    Errored,
+    /// At least one event in the batch was permanently rejected.
+    Failed,
+}
+
+impl BatchStatus {
+    fn update(self, status: EventStatus) -> Self {
+        self.max(match status {
+            EventStatus::Dropped | EventStatus::Delivered => BatchStatus::Delivered,
+            EventStatus::Errored => BatchStatus::Errored,
+            EventStatus::Failed => BatchStatus::Failed,
+        })
+    }
+}
+
+/// Collects the statuses of all the events of a batch and reports the
+/// combined status to the source once every event has been finalized.
+#[derive(Debug)]
+pub struct BatchNotifier {
+    status: Mutex<BatchStatus>,
+    notifier: Option<oneshot::Sender<BatchStatus>>,
+}
+
+impl BatchNotifier {
+    /// Create a new notifier along with the receiver the source should await
+    /// before acknowledging the batch upstream.
+    pub fn new_with_receiver() -> (Arc<Self>, BatchStatusReceiver) {
+        let (sender, receiver) = oneshot::channel();
+        let notifier = Self {
+            status: Mutex::new(BatchStatus::Delivered),
+            notifier: Some(sender),
+        };
+        (Arc::new(notifier), BatchStatusReceiver(receiver))
+    }
+
+    /// Like `new_with_receiver`, but only when acknowledgements are enabled.
+    pub fn maybe_new_with_receiver(
+        enabled: bool,
+    ) -> (Option<Arc<Self>>, Option<BatchStatusReceiver>) {
+        if enabled {
+            let (batch, receiver) = Self::new_with_receiver();
+            (Some(batch), Some(receiver))
+        } else {
+            (None, None)
+        }
+    }
+
+    fn update_status(&self, status: EventStatus) {
+        let mut current = self.status.lock().unwrap();
+        *current = current.update(status);
+    }
+}
+
+impl Drop for BatchNotifier {
+    fn drop(&mut self) {
+        if let Some(notifier) = self.notifier.take() {
+            let status = *self.status.lock().unwrap();
+            // The source may have stopped waiting, e.g. during shutdown.
+            let _ = notifier.send(status);
+        }
+    }
+}
+
+/// Resolves to the combined status of a batch once all of its events have
+/// been finalized.
+#[derive(Debug)]
+pub struct BatchStatusReceiver(oneshot::Receiver<BatchStatus>);
+
+impl Future for BatchStatusReceiver {
+    type Output = BatchStatus;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match Pin::new(&mut self.0).poll(cx) {
+            Poll::Pending => Poll::Pending,
+            Poll::Ready(Ok(status)) => Poll::Ready(status),
+            // The notifier always reports before going away, so this can only
+            // happen if the notifier was leaked; treat it as a failure.
+            Poll::Ready(Err(_)) => Poll::Ready(BatchStatus::Failed),
+        }
+    }
+}
+
+/// Ties a single event to its batch. When the last reference is dropped, the
+/// recorded status is folded into the batch status.
+#[derive(Debug)]
+pub struct EventFinalizer {
+    status: Mutex<EventStatus>,
+    batch: Arc<BatchNotifier>,
+}
+
+impl EventFinalizer {
+    pub fn new(batch: Arc<BatchNotifier>) -> Self {
+        Self {
+            status: Mutex::new(EventStatus::Dropped),
+            batch,
+        }
+    }
+
+    pub fn update_status(&self, status: EventStatus) {
+        let mut current = self.status.lock().unwrap();
+        *current = current.update(status);
+    }
+}
+
+impl Drop for EventFinalizer {
+    fn drop(&mut self) {
+        let status = *self.status.lock().unwrap();
+        self.batch.update_status(status);
+    }
+}
+
+/// The set of finalizers carried by an event. An event usually has at most
+/// one, but merging events (e.g. in `reduce`) combines their finalizers.
+#[derive(Clone, Debug, Default)]
+pub struct EventFinalizers(Vec<Arc<EventFinalizer>>);
+
+impl PartialEq for EventFinalizers {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.len() == other.0.len()
+            && self
+                .0
+                .iter()
+                .zip(other.0.iter())
+                .all(|(a, b)| Arc::ptr_eq(a, b))
+    }
+}
+
+impl EventFinalizers {
+    pub fn new(finalizer: EventFinalizer) -> Self {
+        Self(vec![Arc::new(finalizer)])
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn add(&mut self, finalizer: EventFinalizer) {
+        self.0.push(Arc::new(finalizer));
+    }
+
+    pub fn merge(&mut self, other: Self) {
+        self.0.extend(other.0.into_iter());
+    }
+
+    /// Record the status reported by a sink on every finalizer. The batch is
+    /// notified once all references to the finalizers have been dropped.
+    pub fn update_status(&self, status: EventStatus) {
+        for finalizer in &self.0 {
+            finalizer.update_status(status);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn defaults_to_delivered() {
+        let (batch, receiver) = BatchNotifier::new_with_receiver();
+        let finalizers = EventFinalizers::new(EventFinalizer::new(batch));
+        drop(finalizers);
+        assert_eq!(receiver.await, BatchStatus::Delivered);
+    }
+
+    #[tokio::test]
+    async fn waits_for_all_clones() {
+        let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+        let finalizers = EventFinalizers::new(EventFinalizer::new(batch));
+        let cloned = finalizers.clone();
+
+        finalizers.update_status(EventStatus::Delivered);
+        drop(finalizers);
+        assert_eq!(futures::poll!(&mut receiver), Poll::Pending);
+
+        cloned.update_status(EventStatus::Errored);
+        drop(cloned);
+        assert_eq!(receiver.await, BatchStatus::Errored);
+    }
+
+    #[tokio::test]
+    async fn keeps_worst_status_of_batch() {
+        let (batch, receiver) = BatchNotifier::new_with_receiver();
+        let first = EventFinalizers::new(EventFinalizer::new(Arc::clone(&batch)));
+        let second = EventFinalizers::new(EventFinalizer::new(Arc::clone(&batch)));
+        let third = EventFinalizers::new(EventFinalizer::new(batch));
+
+        first.update_status(EventStatus::Failed);
+        second.update_status(EventStatus::Delivered);
+        third.update_status(EventStatus::Errored);
+        drop((first, second, third));
+
+        assert_eq!(receiver.await, BatchStatus::Failed);
+    }
+
+    #[test]
+    fn event_status_update_keeps_most_severe() {
+        assert_eq!(
+            EventStatus::Dropped.update(EventStatus::Delivered),
+            EventStatus::Delivered
+        );
+        assert_eq!(
+            EventStatus::Failed.update(EventStatus::Delivered),
+            EventStatus::Failed
+        );
+        assert_eq!(
+            EventStatus::Errored.update(EventStatus::Failed),
+            EventStatus::Failed
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use crate::event::{lookup::Segment, util, Lookup, PathComponent, Value};
+use crate::event::{lookup::Segment, util, EventMetadata, Lookup, PathComponent, Value};
 use serde::{Serialize, Serializer};
 use std::{
     collections::{btree_map::Entry, BTreeMap, HashMap},
@@ -10,9 +10,18 @@ use std::{
 #[derive(PartialEq, Debug, Clone, Default)]
 pub struct LogEvent {
     fields: BTreeMap<String, Value>,
+    metadata: EventMetadata,
 }
 
 impl LogEvent {
+    pub fn metadata(&self) -> &EventMetadata {
+        &self.metadata
+    }
+
+    pub fn metadata_mut(&mut self) -> &mut EventMetadata {
+        &mut self.metadata
+    }
+
     #[instrument(level = "trace", skip(self, key), fields(key = %key.as_ref()))]
     pub fn get(&self, key: impl AsRef<str>) -> Option<&Value> {
         util::log::get(&self.fields, key.as_ref())
@@ -139,13 +148,16 @@ impl LogEvent {
 
 impl From<BTreeMap<String, Value>> for LogEvent {
     fn from(map: BTreeMap<String, Value>) -> Self {
-        LogEvent { fields: map }
+        LogEvent {
+            fields: map,
+            metadata: EventMetadata::default(),
+        }
     }
 }
 
 impl Into<BTreeMap<String, Value>> for LogEvent {
     fn into(self) -> BTreeMap<String, Value> {
-        let Self { fields } = self;
+        let Self { fields, .. } = self;
         fields
     }
 }
@@ -154,6 +166,7 @@ impl From<HashMap<String, Value>> for LogEvent {
     fn from(map: HashMap<String, Value>) -> Self {
         LogEvent {
             fields: map.into_iter().collect(),
+            metadata: EventMetadata::default(),
         }
     }
 }
@@ -186,7 +199,7 @@ impl TryInto<serde_json::Value> for LogEvent {
     type Error = crate::Error;
 
     fn try_into(self) -> Result<serde_json::Value, Self::Error> {
-        let Self { fields } = self;
+        let Self { fields, .. } = self;
         Ok(serde_json::to_value(fields)?)
     }
 }
@@ -292,11 +305,10 @@ impl vrl::Target for LogEvent {
         if path.is_root() {
             match value {
                 vrl::Value::Object(map) => {
-                    *self = map
+                    self.fields = map
                         .into_iter()
                         .map(|(k, v)| (k, v.into()))
-                        .collect::<BTreeMap<_, _>>()
-                        .into();
+                        .collect::<BTreeMap<_, _>>();
 
                     return Ok(());
                 }
@@ -0,0 +1,32 @@
+use super::finalization::{BatchNotifier, EventFinalizer, EventFinalizers};
+use std::{mem, sync::Arc};
+
+/// Data carried alongside an event that is not part of its contents.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct EventMetadata {
+    finalizers: EventFinalizers,
+}
+
+impl EventMetadata {
+    pub fn add_batch_notifier(&mut self, batch: Arc<BatchNotifier>) {
+        self.finalizers.add(EventFinalizer::new(batch));
+    }
+
+    pub fn finalizers(&self) -> &EventFinalizers {
+        &self.finalizers
+    }
+
+    /// Detach the finalizers from this event, leaving it untracked. The
+    /// caller becomes responsible for reporting the delivery status.
+    pub fn take_finalizers(&mut self) -> EventFinalizers {
+        mem::take(&mut self.finalizers)
+    }
+
+    pub fn merge_finalizers(&mut self, finalizers: EventFinalizers) {
+        self.finalizers.merge(finalizers);
+    }
+
+    pub fn merge(&mut self, other: Self) {
+        self.merge_finalizers(other.finalizers);
+    }
+}
@@ -1,3 +1,4 @@
+use super::EventMetadata;
 use chrono::{DateTime, Utc};
 use derive_is_enum_variant::is_enum_variant;
 use serde::{Deserialize, Serialize};
@@ -8,7 +9,11 @@ use std::{
     fmt::{self, Display, Formatter},
     iter::FromIterator,
 };
-use vrl::{path::Segment, Target};
+use vrl::{
+    path::Segment,
+    prelude::{map, TypeDef},
+    Target,
+};
 
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct Metric {
@@ -16,6 +21,8 @@ pub struct Metric {
     pub series: MetricSeries,
     #[serde(flatten)]
     pub data: MetricData,
+    #[serde(skip)]
+    metadata: EventMetadata,
 }
 
 #[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
@@ -198,12 +205,10 @@ pub fn zip_quantiles(
         .collect()
 }
 
-/// Convert the Metric value into a vrl value.
-/// Currently vrl can only read the type of the value and doesn't consider
-/// any actual metric values.
-impl From<MetricValue> for vrl::Value {
-    fn from(value: MetricValue) -> Self {
-        match value {
+impl MetricValue {
+    /// The name of the type of this value, as exposed by the `type` field in VRL.
+    pub fn type_name(&self) -> &'static str {
+        match self {
             MetricValue::Counter { .. } => "counter",
             MetricValue::Gauge { .. } => "gauge",
             MetricValue::Set { .. } => "set",
@@ -211,7 +216,194 @@ impl From<MetricValue> for vrl::Value {
             MetricValue::AggregatedHistogram { .. } => "aggregated histogram",
             MetricValue::AggregatedSummary { .. } => "aggregated summary",
         }
-        .into()
+    }
+
+    /// The name of the field holding this value in VRL, which matches the
+    /// serialized form of the value.
+    pub fn field_name(&self) -> &'static str {
+        match self {
+            MetricValue::Counter { .. } => "counter",
+            MetricValue::Gauge { .. } => "gauge",
+            MetricValue::Set { .. } => "set",
+            MetricValue::Distribution { .. } => "distribution",
+            MetricValue::AggregatedHistogram { .. } => "aggregated_histogram",
+            MetricValue::AggregatedSummary { .. } => "aggregated_summary",
+        }
+    }
+
+    /// Parse the VRL representation of a value, stored in the field `field`.
+    pub fn from_vrl(field: &str, value: vrl::Value) -> Result<Self, String> {
+        let mut object = value.try_object().map_err(|e| e.to_string())?;
+        let mut take = |key: &str| {
+            object
+                .remove(key)
+                .ok_or_else(|| format!("missing field `{}` in metric {}", key, field))
+        };
+
+        let value = match field {
+            "counter" => MetricValue::Counter {
+                value: vrl_float(take("value")?)?,
+            },
+            "gauge" => MetricValue::Gauge {
+                value: vrl_float(take("value")?)?,
+            },
+            "set" => MetricValue::Set {
+                values: vrl_array(take("values")?, |value| {
+                    value
+                        .try_bytes_utf8_lossy()
+                        .map(|value| value.into_owned())
+                        .map_err(|e| e.to_string())
+                })?,
+            },
+            "distribution" => MetricValue::Distribution {
+                samples: vrl_array(take("samples")?, |sample| {
+                    let mut sample = sample.try_object().map_err(|e| e.to_string())?;
+                    let value = vrl_float(vrl_field(&mut sample, "value")?)?;
+                    let rate = vrl_u32(vrl_field(&mut sample, "rate")?)?;
+                    vrl_no_fields_left(&sample)?;
+                    Ok(Sample { value, rate })
+                })?,
+                statistic: StatisticKind::try_from(take("statistic")?)?,
+            },
+            "aggregated_histogram" => MetricValue::AggregatedHistogram {
+                buckets: vrl_array(take("buckets")?, |bucket| {
+                    let mut bucket = bucket.try_object().map_err(|e| e.to_string())?;
+                    let upper_limit = vrl_float(vrl_field(&mut bucket, "upper_limit")?)?;
+                    let count = vrl_u32(vrl_field(&mut bucket, "count")?)?;
+                    vrl_no_fields_left(&bucket)?;
+                    Ok(Bucket { upper_limit, count })
+                })?,
+                count: vrl_u32(take("count")?)?,
+                sum: vrl_float(take("sum")?)?,
+            },
+            "aggregated_summary" => MetricValue::AggregatedSummary {
+                quantiles: vrl_array(take("quantiles")?, |quantile| {
+                    let mut quantile = quantile.try_object().map_err(|e| e.to_string())?;
+                    let upper_limit = vrl_float(vrl_field(&mut quantile, "upper_limit")?)?;
+                    let value = vrl_float(vrl_field(&mut quantile, "value")?)?;
+                    vrl_no_fields_left(&quantile)?;
+                    Ok(Quantile { upper_limit, value })
+                })?,
+                count: vrl_u32(take("count")?)?,
+                sum: vrl_float(take("sum")?)?,
+            },
+            _ => return Err(format!("unknown metric type {}", field)),
+        };
+
+        vrl_no_fields_left(&object)?;
+        Ok(value)
+    }
+}
+
+fn vrl_no_fields_left(object: &BTreeMap<String, vrl::Value>) -> Result<(), String> {
+    match object.keys().next() {
+        Some(key) => Err(format!("unknown field `{}`", key)),
+        None => Ok(()),
+    }
+}
+
+fn vrl_field(object: &mut BTreeMap<String, vrl::Value>, key: &str) -> Result<vrl::Value, String> {
+    object
+        .remove(key)
+        .ok_or_else(|| format!("missing field `{}`", key))
+}
+
+fn vrl_float(value: vrl::Value) -> Result<f64, String> {
+    f64::try_from(&value).map_err(|e| e.to_string())
+}
+
+fn vrl_u32(value: vrl::Value) -> Result<u32, String> {
+    let value = value.try_integer().map_err(|e| e.to_string())?;
+    u32::try_from(value).map_err(|_| format!("{} is out of range for a count", value))
+}
+
+fn vrl_array<T, C>(
+    value: vrl::Value,
+    parse: impl Fn(vrl::Value) -> Result<T, String>,
+) -> Result<C, String>
+where
+    C: FromIterator<T>,
+{
+    value
+        .try_array()
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .map(parse)
+        .collect()
+}
+
+/// Convert the metric value into a vrl object, shaped like the serialized
+/// form of the value, minus the type name.
+impl From<MetricValue> for vrl::Value {
+    fn from(value: MetricValue) -> Self {
+        let mut map = BTreeMap::<String, vrl::Value>::new();
+        match value {
+            MetricValue::Counter { value } | MetricValue::Gauge { value } => {
+                map.insert("value".to_owned(), value.into());
+            }
+            MetricValue::Set { values } => {
+                map.insert(
+                    "values".to_owned(),
+                    values.into_iter().map(vrl::Value::from).collect(),
+                );
+            }
+            MetricValue::Distribution { samples, statistic } => {
+                map.insert(
+                    "samples".to_owned(),
+                    samples
+                        .into_iter()
+                        .map(|sample| {
+                            let mut map = BTreeMap::<String, vrl::Value>::new();
+                            map.insert("value".to_owned(), sample.value.into());
+                            map.insert("rate".to_owned(), sample.rate.into());
+                            vrl::Value::from(map)
+                        })
+                        .collect(),
+                );
+                map.insert("statistic".to_owned(), statistic.into());
+            }
+            MetricValue::AggregatedHistogram {
+                buckets,
+                count,
+                sum,
+            } => {
+                map.insert(
+                    "buckets".to_owned(),
+                    buckets
+                        .into_iter()
+                        .map(|bucket| {
+                            let mut map = BTreeMap::<String, vrl::Value>::new();
+                            map.insert("upper_limit".to_owned(), bucket.upper_limit.into());
+                            map.insert("count".to_owned(), bucket.count.into());
+                            vrl::Value::from(map)
+                        })
+                        .collect(),
+                );
+                map.insert("count".to_owned(), count.into());
+                map.insert("sum".to_owned(), sum.into());
+            }
+            MetricValue::AggregatedSummary {
+                quantiles,
+                count,
+                sum,
+            } => {
+                map.insert(
+                    "quantiles".to_owned(),
+                    quantiles
+                        .into_iter()
+                        .map(|quantile| {
+                            let mut map = BTreeMap::<String, vrl::Value>::new();
+                            map.insert("upper_limit".to_owned(), quantile.upper_limit.into());
+                            map.insert("value".to_owned(), quantile.value.into());
+                            vrl::Value::from(map)
+                        })
+                        .collect(),
+                );
+                map.insert("count".to_owned(), count.into());
+                map.insert("sum".to_owned(), sum.into());
+            }
+        }
+        map.into()
     }
 }
 
@@ -224,6 +416,31 @@ pub enum StatisticKind {
     Summary,
 }
 
+impl TryFrom<vrl::Value> for StatisticKind {
+    type Error = String;
+
+    fn try_from(value: vrl::Value) -> Result<Self, Self::Error> {
+        let value = value.try_bytes().map_err(|e| e.to_string())?;
+        match std::str::from_utf8(&value).map_err(|e| e.to_string())? {
+            "histogram" => Ok(Self::Histogram),
+            "summary" => Ok(Self::Summary),
+            value => Err(format!(
+                "invalid statistic kind {}, statistic kind must be `histogram` or `summary`",
+                value
+            )),
+        }
+    }
+}
+
+impl From<StatisticKind> for vrl::Value {
+    fn from(kind: StatisticKind) -> Self {
+        match kind {
+            StatisticKind::Histogram => "histogram".into(),
+            StatisticKind::Summary => "summary".into(),
+        }
+    }
+}
+
 impl Metric {
     pub fn new<T: Into<String>>(name: T, kind: MetricKind, value: MetricValue) -> Self {
         Self {
@@ -239,9 +456,30 @@ impl Metric {
                 kind,
                 value,
             },
+            metadata: EventMetadata::default(),
+        }
+    }
+
+    pub fn from_parts(series: MetricSeries, data: MetricData) -> Self {
+        Self {
+            series,
+            data,
+            metadata: EventMetadata::default(),
         }
     }
 
+    pub fn into_parts(self) -> (MetricSeries, MetricData, EventMetadata) {
+        (self.series, self.data, self.metadata)
+    }
+
+    pub fn metadata(&self) -> &EventMetadata {
+        &self.metadata
+    }
+
+    pub fn metadata_mut(&mut self) -> &mut EventMetadata {
+        &mut self.metadata
+    }
+
     pub fn with_namespace<T: Into<String>>(mut self, namespace: Option<T>) -> Self {
         self.series.name.namespace = namespace.map(Into::into);
         self
@@ -262,6 +500,7 @@ impl Metric {
         Self {
             series: self.series,
             data: self.data.into_absolute(),
+            metadata: self.metadata,
         }
     }
 
@@ -270,6 +509,7 @@ impl Metric {
         Self {
             series: self.series,
             data: self.data.into_incremental(),
+            metadata: self.metadata,
         }
     }
 
@@ -358,10 +598,7 @@ impl Metric {
 
     /// Create a new metric from this with the data zeroed.
     pub fn zero(&self) -> Self {
-        Self {
-            series: self.series.clone(),
-            data: self.data.zero(),
-        }
+        Self::from_parts(self.series.clone(), self.data.zero())
     }
 }
 
@@ -707,10 +944,72 @@ impl Display for Metric {
     }
 }
 
-const VALID_METRIC_PATHS_SET: &str = ".name, .namespace, .timestamp, .kind, .tags";
+impl Metric {
+    /// The shape of a metric as seen by VRL. Programs compiled for metrics use
+    /// it to reject assignments that can never be valid, e.g. a string to
+    /// `.counter.value`, before any event is processed.
+    pub fn vrl_type_def() -> TypeDef {
+        // Integers are accepted wherever a float is expected.
+        let number = || TypeDef::new().float().add_integer();
+        let integer = || TypeDef::new().integer();
+        let object = |fields: BTreeMap<&'static str, TypeDef>| TypeDef::new().object(fields);
+        let array_of =
+            |element: TypeDef| TypeDef::new().array_mapped::<(), TypeDef>(map! { (): element });
+
+        let value: BTreeMap<&'static str, TypeDef> = map! { "value": number() };
+        let buckets: BTreeMap<&'static str, TypeDef> =
+            map! { "upper_limit": number(), "count": integer() };
+        let quantiles: BTreeMap<&'static str, TypeDef> =
+            map! { "upper_limit": number(), "value": number() };
+        let samples: BTreeMap<&'static str, TypeDef> =
+            map! { "value": number(), "rate": integer() };
+
+        object(map! {
+            "name": TypeDef::new().bytes(),
+            "namespace": TypeDef::new().bytes(),
+            "timestamp": TypeDef::new().timestamp(),
+            "kind": TypeDef::new().bytes(),
+            "tags": TypeDef::new().object::<(), TypeDef>(map! { (): TypeDef::new().bytes() }),
+            "type": TypeDef::new().bytes(),
+            "counter": object(value.clone()),
+            "gauge": object(value),
+            "set": object(map! { "values": array_of(TypeDef::new().bytes()) }),
+            "distribution": object(map! {
+                "samples": array_of(object(samples)),
+                "statistic": TypeDef::new().bytes(),
+            }),
+            "aggregated_histogram": object(map! {
+                "buckets": array_of(object(buckets)),
+                "count": integer(),
+                "sum": number(),
+            }),
+            "aggregated_summary": object(map! {
+                "quantiles": array_of(object(quantiles)),
+                "count": integer(),
+                "sum": number(),
+            }),
+        })
+    }
+}
+
+const VALID_METRIC_PATHS_SET: &str = ".name, .namespace, .timestamp, .kind, .tags, .counter, .gauge, .set, .distribution, .aggregated_histogram, .aggregated_summary";
 
 /// We can get the `type` of the metric in Remap, but can't set  it.
-const VALID_METRIC_PATHS_GET: &str = ".name, .namespace, .timestamp, .kind, .tags, .type";
+const VALID_METRIC_PATHS_GET: &str = ".name, .namespace, .timestamp, .kind, .tags, .type, .counter, .gauge, .set, .distribution, .aggregated_histogram, .aggregated_summary";
+
+/// The value of a metric can't be removed, only replaced.
+const VALID_METRIC_PATHS_REMOVE: &str = ".namespace, .timestamp, .tags";
+
+/// The fields holding the value of the metric, one per type of value. Only
+/// the one matching the current type of the metric is present.
+const METRIC_VALUE_FIELDS: [&str; 6] = [
+    "counter",
+    "gauge",
+    "set",
+    "distribution",
+    "aggregated_histogram",
+    "aggregated_summary",
+];
 
 #[derive(Debug, Snafu)]
 enum MetricPathError<'a> {
@@ -719,6 +1018,9 @@ enum MetricPathError<'a> {
 
     #[snafu(display("invalid path {}: expected one of {}", path, expected))]
     InvalidPath { path: &'a str, expected: &'a str },
+
+    #[snafu(display("cannot set path {} on a metric of type {}", path, type_name))]
+    ValueTypeError { path: &'a str, type_name: &'a str },
 }
 
 impl Target for Metric {
@@ -768,6 +1070,24 @@ impl Target for Metric {
                 self.data.kind = MetricKind::try_from(value)?;
                 Ok(())
             }
+            [Segment::Field(field)] if METRIC_VALUE_FIELDS.contains(&field.as_str()) => {
+                self.data.value = MetricValue::from_vrl(field.as_str(), value)?;
+                Ok(())
+            }
+            [Segment::Field(field), rest @ ..] if METRIC_VALUE_FIELDS.contains(&field.as_str()) => {
+                if field.as_str() != self.data.value.field_name() {
+                    return Err(MetricPathError::ValueTypeError {
+                        path: &path.to_string(),
+                        type_name: self.data.value.type_name(),
+                    }
+                    .to_string());
+                }
+
+                let mut current = vrl::Value::from(self.data.value.clone());
+                current.insert(&vrl::Path::new_unchecked(rest.to_vec()), value)?;
+                self.data.value = MetricValue::from_vrl(field.as_str(), current)?;
+                Ok(())
+            }
             _ => Err(MetricPathError::InvalidPath {
                 path: &path.to_string(),
                 expected: VALID_METRIC_PATHS_SET,
@@ -796,7 +1116,11 @@ impl Target for Metric {
                         .into(),
                 );
             }
-            map.insert("type".to_string(), self.data.value.clone().into());
+            map.insert("type".to_string(), self.data.value.type_name().into());
+            map.insert(
+                self.data.value.field_name().to_string(),
+                self.data.value.clone().into(),
+            );
 
             return Ok(Some(map.into()));
         }
@@ -822,7 +1146,15 @@ impl Target for Metric {
                 Ok(self.tag_value(field.as_str()).map(|value| value.into()))
             }
             [Segment::Field(type_)] if type_.as_str() == "type" => {
-                Ok(Some(self.data.value.clone().into()))
+                Ok(Some(self.data.value.type_name().into()))
+            }
+            [Segment::Field(field), rest @ ..] if METRIC_VALUE_FIELDS.contains(&field.as_str()) => {
+                if field.as_str() == self.data.value.field_name() {
+                    vrl::Value::from(self.data.value.clone())
+                        .get(&vrl::Path::new_unchecked(rest.to_vec()))
+                } else {
+                    Ok(None)
+                }
             }
             _ => Err(MetricPathError::InvalidPath {
                 path: &path.to_string(),
@@ -855,7 +1187,7 @@ impl Target for Metric {
             }
             _ => Err(MetricPathError::InvalidPath {
                 path: &path.to_string(),
-                expected: VALID_METRIC_PATHS_SET,
+                expected: VALID_METRIC_PATHS_REMOVE,
             }
             .to_string()),
         }
@@ -1181,6 +1513,7 @@ mod test {
                     "tags" => btreemap! { "tig" => "tog" },
                     "kind" => "absolute",
                     "type" => "counter",
+                    "counter" => btreemap! { "value" => 1.23 },
                 }
                 .into()
             )),
@@ -1246,7 +1579,16 @@ mod test {
             MetricValue::Counter { value: 1.23 },
         );
 
-        let validpaths_get = vec![
+        let value_paths = vec![
+            ".counter",
+            ".gauge",
+            ".set",
+            ".distribution",
+            ".aggregated_histogram",
+            ".aggregated_summary",
+        ];
+
+        let mut validpaths_get = vec![
             ".name",
             ".namespace",
             ".timestamp",
@@ -1254,8 +1596,12 @@ mod test {
             ".tags",
             ".type",
         ];
+        validpaths_get.extend(&value_paths);
+
+        let mut validpaths_set = vec![".name", ".namespace", ".timestamp", ".kind", ".tags"];
+        validpaths_set.extend(&value_paths);
 
-        let validpaths_set = vec![".name", ".namespace", ".timestamp", ".kind", ".tags"];
+        let validpaths_remove = vec![".namespace", ".timestamp", ".tags"];
 
         assert_eq!(
             Err(format!(
@@ -1276,7 +1622,7 @@ mod test {
         assert_eq!(
             Err(format!(
                 "invalid path .zork: expected one of {}",
-                validpaths_set.join(", ")
+                validpaths_remove.join(", ")
             )),
             metric.remove(&Path::from_str("zork").unwrap(), true)
         );
@@ -1288,5 +1634,159 @@ mod test {
             )),
             metric.get(&Path::from_str("tags.foo.flork").unwrap())
         );
+
+        assert_eq!(
+            Err(format!(
+                "invalid path .counter: expected one of {}",
+                validpaths_remove.join(", ")
+            )),
+            metric.remove(&Path::from_str("counter").unwrap(), true)
+        );
+    }
+
+    #[test]
+    fn object_metric_counter_value() {
+        let mut metric = Metric::new(
+            "requests",
+            MetricKind::Incremental,
+            MetricValue::Counter { value: 1.0 },
+        );
+        let path = Path::from_str("counter.value").unwrap();
+
+        assert_eq!(Ok(Some(Value::from(1.0))), metric.get(&path));
+        assert_eq!(Ok(None), metric.get(&Path::from_str("gauge").unwrap()));
+
+        // Integers are accepted wherever a float is expected.
+        assert_eq!(Ok(()), metric.insert(&path, Value::from(5)));
+        assert_eq!(metric.data.value, MetricValue::Counter { value: 5.0 });
+
+        assert!(metric.insert(&path, Value::from("five")).is_err());
+        assert_eq!(
+            Err("cannot set path .gauge.value on a metric of type counter".to_owned()),
+            metric.insert(&Path::from_str("gauge.value").unwrap(), Value::from(1.0))
+        );
+
+        // Assigning a whole value changes the type of the metric.
+        assert_eq!(
+            Ok(()),
+            metric.insert(
+                &Path::from_str("gauge").unwrap(),
+                btreemap! { "value" => 2.5 }.into()
+            )
+        );
+        assert_eq!(metric.data.value, MetricValue::Gauge { value: 2.5 });
+        assert_eq!(
+            Ok(Some(Value::from("gauge"))),
+            metric.get(&Path::from_str("type").unwrap())
+        );
+    }
+
+    #[test]
+    fn object_metric_histogram_buckets() {
+        let mut metric = Metric::new(
+            "latency",
+            MetricKind::Absolute,
+            MetricValue::AggregatedHistogram {
+                buckets: buckets![1.0 => 2, 2.0 => 3],
+                count: 5,
+                sum: 8.0,
+            },
+        );
+
+        assert_eq!(
+            Ok(Some(
+                btreemap! { "upper_limit" => 2.0, "count" => 3 }.into()
+            )),
+            metric.get(&Path::from_str("aggregated_histogram.buckets[1]").unwrap())
+        );
+
+        assert_eq!(
+            Ok(()),
+            metric.insert(
+                &Path::from_str("aggregated_histogram.buckets[0].count").unwrap(),
+                Value::from(4)
+            )
+        );
+        assert_eq!(
+            Ok(()),
+            metric.insert(
+                &Path::from_str("aggregated_histogram.count").unwrap(),
+                Value::from(7)
+            )
+        );
+        assert_eq!(
+            metric.data.value,
+            MetricValue::AggregatedHistogram {
+                buckets: buckets![1.0 => 4, 2.0 => 3],
+                count: 7,
+                sum: 8.0,
+            }
+        );
+
+        // Counts must be non-negative integers.
+        assert!(metric
+            .insert(
+                &Path::from_str("aggregated_histogram.count").unwrap(),
+                Value::from(-1)
+            )
+            .is_err());
+        assert!(metric
+            .insert(
+                &Path::from_str("aggregated_histogram.buckets[0].bogus").unwrap(),
+                Value::from(1)
+            )
+            .is_err());
+        assert_eq!(
+            metric.data.value,
+            MetricValue::AggregatedHistogram {
+                buckets: buckets![1.0 => 4, 2.0 => 3],
+                count: 7,
+                sum: 8.0,
+            }
+        );
+    }
+
+    #[test]
+    fn object_metric_set_and_distribution() {
+        let mut metric = Metric::new(
+            "users",
+            MetricKind::Incremental,
+            MetricValue::Set {
+                values: vec!["a".to_owned()].into_iter().collect(),
+            },
+        );
+
+        assert_eq!(
+            Ok(()),
+            metric.insert(
+                &Path::from_str("set.values").unwrap(),
+                vec!["b", "c"].into()
+            )
+        );
+        assert_eq!(
+            metric.data.value,
+            MetricValue::Set {
+                values: vec!["b".to_owned(), "c".to_owned()].into_iter().collect(),
+            }
+        );
+
+        assert_eq!(
+            Ok(()),
+            metric.insert(
+                &Path::from_str("distribution").unwrap(),
+                btreemap! {
+                    "samples" => vec![Value::from(btreemap! { "value" => 1.5, "rate" => 2 })],
+                    "statistic" => "summary",
+                }
+                .into()
+            )
+        );
+        assert_eq!(
+            metric.data.value,
+            MetricValue::Distribution {
+                samples: samples![1.5 => 2],
+                statistic: StatisticKind::Summary,
+            }
+        );
     }
 }
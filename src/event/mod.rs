@@ -2,9 +2,13 @@ use self::proto::{event_wrapper::Event as EventProto, metric::Value as MetricPro
 use crate::config::log_schema;
 use bytes::Bytes;
 use chrono::{DateTime, SecondsFormat, TimeZone, Utc};
-use std::collections::{BTreeMap, HashMap};
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+};
 
 pub mod discriminant;
+pub mod finalization;
 pub mod merge;
 pub mod merge_state;
 pub mod metric;
@@ -12,10 +16,15 @@ pub mod util;
 
 mod log_event;
 mod lookup;
+mod metadata;
 mod value;
 
+pub use finalization::{
+    BatchNotifier, BatchStatus, BatchStatusReceiver, EventFinalizers, EventStatus,
+};
 pub use log_event::LogEvent;
 pub use lookup::Lookup;
+pub use metadata::EventMetadata;
 pub use metric::{Metric, MetricKind, MetricValue, StatisticKind};
 use std::convert::{TryFrom, TryInto};
 pub(crate) use util::log::PathComponent;
@@ -80,6 +89,38 @@ impl Event {
             _ => panic!("Failed type coercion, {:?} is not a metric", self),
         }
     }
+
+    pub fn metadata(&self) -> &EventMetadata {
+        match self {
+            Event::Log(log) => log.metadata(),
+            Event::Metric(metric) => metric.metadata(),
+        }
+    }
+
+    pub fn metadata_mut(&mut self) -> &mut EventMetadata {
+        match self {
+            Event::Log(log) => log.metadata_mut(),
+            Event::Metric(metric) => metric.metadata_mut(),
+        }
+    }
+
+    /// Track this event as part of the given batch, so the source is notified
+    /// once every sink that received it has reported its delivery status.
+    pub fn add_batch_notifier(&mut self, batch: Arc<BatchNotifier>) {
+        self.metadata_mut().add_batch_notifier(batch);
+    }
+
+    pub fn with_batch_notifier(mut self, batch: &Arc<BatchNotifier>) -> Self {
+        self.add_batch_notifier(Arc::clone(batch));
+        self
+    }
+
+    pub fn with_batch_notifier_option(self, batch: &Option<Arc<BatchNotifier>>) -> Self {
+        match batch {
+            Some(batch) => self.with_batch_notifier(batch),
+            None => self,
+        }
+    }
 }
 
 fn timestamp_to_string(timestamp: &DateTime<Utc>) -> String {
@@ -303,7 +344,8 @@ impl From<Event> for proto::EventWrapper {
 
                 proto::EventWrapper { event: Some(event) }
             }
-            Event::Metric(Metric { series, data }) => {
+            Event::Metric(metric) => {
+                let (series, data, _) = metric.into_parts();
                 let name = series.name.name;
                 let namespace = series.name.namespace.unwrap_or_default();
 
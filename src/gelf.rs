@@ -0,0 +1,469 @@
+//! Encoding and decoding of [GELF](https://docs.graylog.org/en/latest/pages/gelf.html)
+//! messages, as sent to and received from Graylog.
+//!
+//! The well known fields map to the log schema, `short_message` becoming the
+//! message, while additional fields lose or gain their `_` prefix.
+
+use crate::{
+    config::log_schema,
+    event::{LogEvent, Value},
+};
+use bytes::Bytes;
+use chrono::{TimeZone, Utc};
+use flate2::read::{GzDecoder, ZlibDecoder};
+use serde_json::{Map, Number};
+use snafu::{ResultExt, Snafu};
+use std::{
+    collections::HashMap,
+    io::{self, Read},
+    time::{Duration, Instant},
+};
+
+pub const GELF_VERSION: &str = "1.1";
+
+const FULL_MESSAGE: &str = "full_message";
+const LEVEL: &str = "level";
+
+const CHUNK_MAGIC: &[u8] = &[0x1e, 0x0f];
+const CHUNK_HEADER_LEN: usize = 12;
+const MAX_CHUNKS: u8 = 128;
+/// Chunked messages which weren't completed within this period are dropped.
+const CHUNK_TIMEOUT: Duration = Duration::from_secs(5);
+/// The maximum number of chunked messages being reassembled at once.
+const MAX_PENDING_MESSAGES: usize = 1000;
+/// The maximum size of a compressed message once decompressed.
+const MAX_DECOMPRESSED_SIZE: u64 = 8 * 1024 * 1024;
+
+const GZIP_MAGIC: &[u8] = &[0x1f, 0x8b];
+const ZLIB_MAGIC: u8 = 0x78;
+
+#[derive(Debug, Snafu)]
+pub enum GelfError {
+    #[snafu(display("Invalid JSON: {}", source))]
+    InvalidJson { source: serde_json::Error },
+    #[snafu(display("Message is not a JSON object"))]
+    NotAnObject,
+    #[snafu(display("Required field {:?} is missing", field))]
+    MissingField { field: &'static str },
+    #[snafu(display("Field {:?} has an invalid value", field))]
+    InvalidField { field: &'static str },
+    #[snafu(display("Unable to decompress message: {}", source))]
+    Decompress { source: io::Error },
+    #[snafu(display(
+        "Message exceeds the maximum size of {} bytes once decompressed",
+        MAX_DECOMPRESSED_SIZE
+    ))]
+    DecompressedTooLarge,
+    #[snafu(display("Invalid chunk, sequence {} of {}", number, count))]
+    InvalidChunk { number: u8, count: u8 },
+    #[snafu(display(
+        "Too many chunked messages are being reassembled, at most {} are allowed",
+        MAX_PENDING_MESSAGES
+    ))]
+    TooManyChunkedMessages,
+}
+
+/// Decodes a GELF message, which may be compressed with gzip or zlib.
+pub fn decode(payload: &[u8]) -> Result<LogEvent, GelfError> {
+    let decompressed;
+    let payload = if payload.starts_with(GZIP_MAGIC) {
+        decompressed = decompress(GzDecoder::new(payload))?;
+        &decompressed[..]
+    } else if payload.first() == Some(&ZLIB_MAGIC) {
+        decompressed = decompress(ZlibDecoder::new(payload))?;
+        &decompressed[..]
+    } else {
+        payload
+    };
+
+    let fields = match serde_json::from_slice(payload).context(InvalidJson)? {
+        serde_json::Value::Object(fields) => fields,
+        _ => return Err(GelfError::NotAnObject),
+    };
+
+    let mut log = LogEvent::default();
+    let mut message = None;
+    for (name, value) in fields {
+        match name.as_str() {
+            "version" => (),
+            "host" => {
+                log.insert(log_schema().host_key(), Value::from(value));
+            }
+            "short_message" => message = Some(value),
+            "timestamp" => {
+                let timestamp = value
+                    .as_f64()
+                    .ok_or(GelfError::InvalidField { field: "timestamp" })?;
+                let secs = timestamp.trunc() as i64;
+                // Timestamps are only precise to the microsecond as floats.
+                let micros = (timestamp.fract() * 1e6).round() as u32;
+                let timestamp = Utc
+                    .timestamp_opt(secs, micros.min(999_999) * 1000)
+                    .single()
+                    .ok_or(GelfError::InvalidField { field: "timestamp" })?;
+                log.insert(log_schema().timestamp_key(), timestamp);
+            }
+            _ => {
+                let name = name.strip_prefix('_').unwrap_or(&name);
+                log.insert_flat(name, Value::from(value));
+            }
+        };
+    }
+
+    let message = message.ok_or(GelfError::MissingField {
+        field: "short_message",
+    })?;
+    log.insert(log_schema().message_key(), Value::from(message));
+    if !log.contains(log_schema().timestamp_key()) {
+        log.insert(log_schema().timestamp_key(), Utc::now());
+    }
+
+    Ok(log)
+}
+
+fn decompress(reader: impl Read) -> Result<Vec<u8>, GelfError> {
+    let mut decompressed = Vec::new();
+    reader
+        .take(MAX_DECOMPRESSED_SIZE + 1)
+        .read_to_end(&mut decompressed)
+        .context(Decompress)?;
+    if decompressed.len() as u64 > MAX_DECOMPRESSED_SIZE {
+        return Err(GelfError::DecompressedTooLarge);
+    }
+    Ok(decompressed)
+}
+
+/// Encodes a log as an uncompressed GELF message. Fields other than the
+/// well known ones are sent as additional fields, nested fields being
+/// flattened.
+pub fn encode(mut log: LogEvent) -> Result<Vec<u8>, GelfError> {
+    let mut message = Map::new();
+    message.insert("version".into(), GELF_VERSION.into());
+
+    let host = log
+        .remove(log_schema().host_key())
+        .ok_or(GelfError::MissingField { field: "host" })?;
+    message.insert("host".into(), host.to_string_lossy().into());
+    let short_message = log
+        .remove(log_schema().message_key())
+        .ok_or(GelfError::MissingField {
+            field: "short_message",
+        })?;
+    message.insert(
+        "short_message".into(),
+        short_message.to_string_lossy().into(),
+    );
+
+    match log.remove(log_schema().timestamp_key()) {
+        Some(Value::Timestamp(timestamp)) => {
+            let timestamp = timestamp.timestamp_millis() as f64 / 1000.0;
+            if let Some(timestamp) = Number::from_f64(timestamp) {
+                message.insert("timestamp".into(), timestamp.into());
+            }
+        }
+        Some(timestamp) => log.insert_flat(log_schema().timestamp_key(), timestamp),
+        None => (),
+    }
+    if let Some(full_message) = log.remove(FULL_MESSAGE) {
+        message.insert(FULL_MESSAGE.into(), full_message.to_string_lossy().into());
+    }
+    match log.remove(LEVEL) {
+        Some(Value::Integer(level)) => {
+            message.insert(LEVEL.into(), level.into());
+        }
+        Some(level) => log.insert_flat(LEVEL, level),
+        None => (),
+    }
+
+    for (name, value) in log.all_fields() {
+        let value: serde_json::Value = match value {
+            Value::Integer(value) => (*value).into(),
+            Value::Float(value) => match Number::from_f64(*value) {
+                Some(value) => value.into(),
+                None => continue,
+            },
+            Value::Null => continue,
+            value => value.to_string_lossy().into(),
+        };
+        message.insert(additional_field_name(&name), value);
+    }
+
+    serde_json::to_vec(&message).context(InvalidJson)
+}
+
+/// Additional field names may only contain word characters, dots and dashes.
+fn additional_field_name(name: &str) -> String {
+    let name = name.replace(
+        |c: char| !(c.is_ascii_alphanumeric() || c == '_' || c == '.' || c == '-'),
+        "_",
+    );
+    format!("_{}", name)
+}
+
+#[derive(Debug)]
+struct ChunkedMessage {
+    chunks: Vec<Option<Bytes>>,
+    received: usize,
+    started: Instant,
+}
+
+/// Reassembles the chunked messages received over UDP.
+#[derive(Debug, Default)]
+pub struct ChunkAssembler {
+    messages: HashMap<[u8; 8], ChunkedMessage>,
+}
+
+impl ChunkAssembler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the message a datagram completed, which is the datagram
+    /// itself when it isn't chunked.
+    pub fn push(&mut self, datagram: Bytes) -> Result<Option<Bytes>, GelfError> {
+        if !datagram.starts_with(CHUNK_MAGIC) {
+            return Ok(Some(datagram));
+        }
+        if datagram.len() < CHUNK_HEADER_LEN {
+            return Err(GelfError::InvalidChunk {
+                number: 0,
+                count: 0,
+            });
+        }
+
+        let now = Instant::now();
+        self.messages
+            .retain(|_, message| now.duration_since(message.started) < CHUNK_TIMEOUT);
+
+        let mut id = [0; 8];
+        id.copy_from_slice(&datagram[2..10]);
+        let (number, count) = (datagram[10], datagram[11]);
+        if count == 0 || count > MAX_CHUNKS || number >= count {
+            return Err(GelfError::InvalidChunk { number, count });
+        }
+        if self.messages.len() >= MAX_PENDING_MESSAGES && !self.messages.contains_key(&id) {
+            return Err(GelfError::TooManyChunkedMessages);
+        }
+
+        let message = self.messages.entry(id).or_insert_with(|| ChunkedMessage {
+            chunks: vec![None; count as usize],
+            received: 0,
+            started: now,
+        });
+        if message.chunks.len() != count as usize {
+            return Err(GelfError::InvalidChunk { number, count });
+        }
+        let chunk = &mut message.chunks[number as usize];
+        if chunk.is_none() {
+            *chunk = Some(datagram.slice(CHUNK_HEADER_LEN..));
+            message.received += 1;
+        }
+        if message.received < message.chunks.len() {
+            return Ok(None);
+        }
+
+        let message = self.messages.remove(&id).expect("Message was just found.");
+        let payload =
+            message
+                .chunks
+                .into_iter()
+                .flatten()
+                .fold(Vec::new(), |mut payload, chunk| {
+                    payload.extend_from_slice(&chunk);
+                    payload
+                });
+        Ok(Some(payload.into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::{write::ZlibEncoder, Compression};
+    use serde_json::json;
+    use std::io::Write;
+
+    fn message() -> serde_json::Value {
+        json!({
+            "version": "1.1",
+            "host": "example.org",
+            "short_message": "A short message",
+            "full_message": "Backtrace here\n\nmore stuff",
+            "timestamp": 1385053862.307,
+            "level": 1,
+            "_user_id": 9001,
+            "_some_info": "foo",
+        })
+    }
+
+    fn chunk(id: u8, number: u8, count: u8, data: &[u8]) -> Bytes {
+        let mut chunk = vec![0x1e, 0x0f, id, 0, 0, 0, 0, 0, 0, 0, number, count];
+        chunk.extend_from_slice(data);
+        chunk.into()
+    }
+
+    #[test]
+    fn decodes_message() {
+        let log = decode(message().to_string().as_bytes()).unwrap();
+
+        assert_eq!(log[log_schema().message_key()], "A short message".into());
+        assert_eq!(log[log_schema().host_key()], "example.org".into());
+        assert_eq!(
+            log[log_schema().timestamp_key()],
+            Utc.timestamp(1385053862, 307_000_000).into()
+        );
+        assert_eq!(log[FULL_MESSAGE], "Backtrace here\n\nmore stuff".into());
+        assert_eq!(log[LEVEL], 1.into());
+        assert_eq!(log["user_id"], 9001.into());
+        assert_eq!(log["some_info"], "foo".into());
+        assert!(!log.contains("version"));
+    }
+
+    #[test]
+    fn decodes_compressed_message() {
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(message().to_string().as_bytes()).unwrap();
+        let log = decode(&encoder.finish().unwrap()).unwrap();
+
+        assert_eq!(log[log_schema().message_key()], "A short message".into());
+    }
+
+    #[test]
+    fn rejects_message_without_short_message() {
+        let error = decode(br#"{"version": "1.1", "host": "example.org"}"#).unwrap_err();
+
+        assert!(matches!(
+            error,
+            GelfError::MissingField {
+                field: "short_message"
+            }
+        ));
+    }
+
+    #[test]
+    fn rejects_out_of_range_timestamp() {
+        let error = decode(br#"{"short_message": "foo", "timestamp": 1e300}"#).unwrap_err();
+
+        assert!(matches!(
+            error,
+            GelfError::InvalidField { field: "timestamp" }
+        ));
+    }
+
+    #[test]
+    fn rejects_oversized_compressed_message() {
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::best());
+        encoder
+            .write_all(&vec![b' '; MAX_DECOMPRESSED_SIZE as usize + 1])
+            .unwrap();
+
+        assert!(matches!(
+            decode(&encoder.finish().unwrap()),
+            Err(GelfError::DecompressedTooLarge)
+        ));
+    }
+
+    #[test]
+    fn encodes_message() {
+        let mut log = LogEvent::default();
+        log.insert(log_schema().message_key(), "A short message");
+        log.insert(log_schema().host_key(), "example.org");
+        log.insert(
+            log_schema().timestamp_key(),
+            Utc.timestamp(1385053862, 307_000_000),
+        );
+        log.insert(LEVEL, 1);
+        log.insert("user_id", 9001);
+        log.insert("nested.info", "foo");
+        log.insert("with space", true);
+
+        let message: serde_json::Value = serde_json::from_slice(&encode(log).unwrap()).unwrap();
+
+        assert_eq!(
+            message,
+            json!({
+                "version": "1.1",
+                "host": "example.org",
+                "short_message": "A short message",
+                "timestamp": 1385053862.307,
+                "level": 1,
+                "_user_id": 9001,
+                "_nested.info": "foo",
+                "_with_space": "true",
+            })
+        );
+    }
+
+    #[test]
+    fn encoding_requires_host() {
+        let mut log = LogEvent::default();
+        log.insert(log_schema().message_key(), "message");
+
+        assert!(matches!(
+            encode(log),
+            Err(GelfError::MissingField { field: "host" })
+        ));
+    }
+
+    #[test]
+    fn roundtrips_message() {
+        let log = decode(message().to_string().as_bytes()).unwrap();
+        let decoded = decode(&encode(log.clone()).unwrap()).unwrap();
+
+        assert_eq!(decoded, log);
+    }
+
+    #[test]
+    fn reassembles_chunks() {
+        let payload = message().to_string().into_bytes();
+        let (first, second) = payload.split_at(payload.len() / 2);
+        let mut assembler = ChunkAssembler::new();
+
+        assert_eq!(assembler.push(chunk(1, 1, 2, second)).unwrap(), None);
+        assert_eq!(assembler.push(chunk(2, 0, 2, b"other")).unwrap(), None);
+        let message = assembler.push(chunk(1, 0, 2, first)).unwrap().unwrap();
+
+        assert_eq!(message, Bytes::from(payload));
+        assert_eq!(assembler.messages.len(), 1);
+    }
+
+    #[test]
+    fn passes_unchunked_datagrams_through() {
+        let mut assembler = ChunkAssembler::new();
+
+        assert_eq!(
+            assembler.push(Bytes::from("{}")).unwrap(),
+            Some(Bytes::from("{}"))
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_chunks() {
+        let mut assembler = ChunkAssembler::new();
+
+        assert!(assembler.push(chunk(1, 2, 2, b"data")).is_err());
+        assert!(assembler.push(chunk(1, 0, 129, b"data")).is_err());
+    }
+
+    #[test]
+    fn limits_pending_messages() {
+        let mut assembler = ChunkAssembler::new();
+        for id in 0..MAX_PENDING_MESSAGES {
+            assembler.messages.insert(
+                (id as u64).to_be_bytes(),
+                ChunkedMessage {
+                    chunks: vec![None; 2],
+                    received: 0,
+                    started: Instant::now(),
+                },
+            );
+        }
+
+        assert!(matches!(
+            assembler.push(chunk(0xff, 0, 2, b"data")),
+            Err(GelfError::TooManyChunkedMessages)
+        ));
+        // Chunks of the pending messages are still accepted.
+        assert_eq!(assembler.push(chunk(0, 0, 2, b"data")).unwrap(), None);
+    }
+}
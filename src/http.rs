@@ -49,22 +49,7 @@ where
     B::Error: Into<crate::Error>,
 {
     pub fn new(tls_settings: impl Into<MaybeTlsSettings>) -> Result<HttpClient<B>, HttpError> {
-        let mut http = HttpConnector::new_with_resolver(Resolver);
-        http.enforce_http(false);
-
-        let settings = tls_settings.into();
-        let tls = tls_connector_builder(&settings).context(BuildTlsConnector)?;
-        let mut https = HttpsConnector::with_connector(http, tls).context(MakeHttpsConnector)?;
-
-        let settings = settings.tls().cloned();
-        https.set_callback(move |c, _uri| {
-            if let Some(settings) = &settings {
-                settings.apply_connect_configuration(c);
-            }
-
-            Ok(())
-        });
-
+        let https = build_tls_connector(tls_settings.into())?;
         let client = Client::builder().build(https);
 
         let version = crate::get_version();
@@ -129,6 +114,29 @@ where
     }
 }
 
+/// Builds the connector of the clients made by `HttpClient`, for clients that
+/// need different settings, e.g. HTTP/2 only.
+pub fn build_tls_connector(
+    tls_settings: MaybeTlsSettings,
+) -> Result<HttpsConnector<HttpConnector<Resolver>>, HttpError> {
+    let mut http = HttpConnector::new_with_resolver(Resolver);
+    http.enforce_http(false);
+
+    let tls = tls_connector_builder(&tls_settings).context(BuildTlsConnector)?;
+    let mut https = HttpsConnector::with_connector(http, tls).context(MakeHttpsConnector)?;
+
+    let settings = tls_settings.tls().cloned();
+    https.set_callback(move |c, _uri| {
+        if let Some(settings) = &settings {
+            settings.apply_connect_configuration(c);
+        }
+
+        Ok(())
+    });
+
+    Ok(https)
+}
+
 fn default_request_headers<B>(request: &mut Request<B>, user_agent: &HeaderValue) {
     if !request.headers().contains_key("User-Agent") {
         request
@@ -0,0 +1,24 @@
+use super::InternalEvent;
+use crate::sources::util::DecodingError;
+use metrics::counter;
+
+#[derive(Debug)]
+pub struct DecoderParseFailed<'a> {
+    pub error: &'a DecodingError,
+}
+
+impl<'a> InternalEvent for DecoderParseFailed<'a> {
+    fn emit_logs(&self) {
+        warn!(
+            message = "Failed to parse frame.",
+            error = %self.error,
+            internal_log_rate_secs = 30
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("processing_errors_total", 1,
+            "error_type" => "failed_parse",
+        );
+    }
+}
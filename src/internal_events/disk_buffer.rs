@@ -0,0 +1,46 @@
+use super::InternalEvent;
+use metrics::counter;
+use std::path::Path;
+
+#[derive(Debug)]
+pub struct DiskBufferRecordCorrupted<'a> {
+    pub path: &'a Path,
+    pub position: u64,
+    pub reason: &'static str,
+}
+
+impl<'a> InternalEvent for DiskBufferRecordCorrupted<'a> {
+    fn emit_logs(&self) {
+        error!(
+            message = "Skipping corrupted record in disk buffer.",
+            path = %self.path.display(),
+            position = %self.position,
+            reason = %self.reason,
+            internal_log_rate_secs = 10
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("buffer_corrupted_records_total", 1);
+    }
+}
+
+#[derive(Debug)]
+pub struct DiskBufferIoError<'a> {
+    pub path: &'a Path,
+    pub error: std::io::Error,
+}
+
+impl<'a> InternalEvent for DiskBufferIoError<'a> {
+    fn emit_logs(&self) {
+        error!(
+            message = "Disk buffer I/O error.",
+            path = %self.path.display(),
+            error = %self.error,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("buffer_io_errors_total", 1);
+    }
+}
@@ -0,0 +1,24 @@
+use super::InternalEvent;
+use crate::sinks::util::encoding::EncodingError;
+use metrics::counter;
+
+#[derive(Debug)]
+pub struct EncoderEncodeFailed<'a> {
+    pub error: &'a EncodingError,
+}
+
+impl<'a> InternalEvent for EncoderEncodeFailed<'a> {
+    fn emit_logs(&self) {
+        warn!(
+            message = "Failed to encode event.",
+            error = %self.error,
+            internal_log_rate_secs = 30
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("processing_errors_total", 1,
+            "error_type" => "failed_serialize",
+        );
+    }
+}
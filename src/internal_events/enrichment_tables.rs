@@ -0,0 +1,38 @@
+use super::InternalEvent;
+use metrics::counter;
+
+#[derive(Debug)]
+pub struct EnrichmentTableReloaded<'a> {
+    pub table: &'a str,
+}
+
+impl<'a> InternalEvent for EnrichmentTableReloaded<'a> {
+    fn emit_logs(&self) {
+        info!(message = "Reloaded enrichment table.", table = %self.table);
+    }
+
+    fn emit_metrics(&self) {
+        counter!("enrichment_table_reloads_total", 1, "table" => self.table.to_owned());
+    }
+}
+
+#[derive(Debug)]
+pub struct EnrichmentTableReloadFailed<'a> {
+    pub table: &'a str,
+    pub error: String,
+}
+
+impl<'a> InternalEvent for EnrichmentTableReloadFailed<'a> {
+    fn emit_logs(&self) {
+        error!(
+            message = "Failed to reload enrichment table; keeping previous data.",
+            table = %self.table,
+            error = %self.error,
+            internal_log_rate_secs = 30
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("enrichment_table_reload_errors_total", 1, "table" => self.table.to_owned());
+    }
+}
@@ -0,0 +1,80 @@
+use super::InternalEvent;
+use metrics::counter;
+use std::process::ExitStatus;
+
+#[derive(Debug)]
+pub struct ExecEventReceived<'a> {
+    pub command: &'a str,
+    pub byte_size: usize,
+}
+
+impl InternalEvent for ExecEventReceived<'_> {
+    fn emit_logs(&self) {
+        trace!(
+            message = "Received one event.",
+            command = %self.command,
+            byte_size = %self.byte_size
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("processed_events_total", 1);
+        counter!(
+            "events_in_total", 1,
+            "command" => self.command.to_owned()
+        );
+        counter!(
+            "processed_bytes_total", self.byte_size as u64,
+            "command" => self.command.to_owned()
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct ExecCommandFailed<'a> {
+    pub command: &'a str,
+    pub error: std::io::Error,
+}
+
+impl InternalEvent for ExecCommandFailed<'_> {
+    fn emit_logs(&self) {
+        error!(
+            message = "Unable to execute command.",
+            command = %self.command,
+            error = %self.error,
+            internal_log_rate_secs = 30
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!(
+            "processing_errors_total", 1,
+            "command" => self.command.to_owned(),
+            "error_type" => "command_failed"
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct ExecCommandExited<'a> {
+    pub command: &'a str,
+    pub status: ExitStatus,
+}
+
+impl InternalEvent for ExecCommandExited<'_> {
+    fn emit_logs(&self) {
+        debug!(
+            message = "Command exited.",
+            command = %self.command,
+            status = %self.status
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!(
+            "command_executed_total", 1,
+            "command" => self.command.to_owned(),
+            "success" => self.status.success().to_string()
+        );
+    }
+}
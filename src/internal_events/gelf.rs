@@ -0,0 +1,45 @@
+use super::InternalEvent;
+use crate::gelf::GelfError;
+use metrics::counter;
+
+#[derive(Debug)]
+pub struct GelfDecodeFailed<'a> {
+    pub error: &'a GelfError,
+}
+
+impl<'a> InternalEvent for GelfDecodeFailed<'a> {
+    fn emit_logs(&self) {
+        warn!(
+            message = "Failed to decode GELF message.",
+            error = %self.error,
+            internal_log_rate_secs = 30
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("processing_errors_total", 1,
+            "error_type" => "failed_parse",
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct GelfEncodeFailed<'a> {
+    pub error: &'a GelfError,
+}
+
+impl<'a> InternalEvent for GelfEncodeFailed<'a> {
+    fn emit_logs(&self) {
+        warn!(
+            message = "Failed to encode event as GELF message.",
+            error = %self.error,
+            internal_log_rate_secs = 30
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("processing_errors_total", 1,
+            "error_type" => "failed_serialize",
+        );
+    }
+}
@@ -1,4 +1,5 @@
 use super::InternalEvent;
+use crate::event::BatchStatus;
 use metrics::counter;
 
 #[derive(Debug)]
@@ -33,6 +34,30 @@ impl InternalEvent for KafkaOffsetUpdateFailed {
     }
 }
 
+#[derive(Debug)]
+pub struct KafkaEventNotAcknowledged<'a> {
+    pub status: BatchStatus,
+    pub topic: &'a str,
+    pub partition: i32,
+    pub offset: i64,
+}
+
+impl InternalEvent for KafkaEventNotAcknowledged<'_> {
+    fn emit_logs(&self) {
+        error!(
+            message = "Event was not delivered; consumer offsets of the partition are no longer stored.",
+            status = ?self.status,
+            topic = %self.topic,
+            partition = %self.partition,
+            offset = %self.offset,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("events_not_acknowledged_total", 1);
+    }
+}
+
 #[derive(Debug)]
 pub struct KafkaEventFailed {
     pub error: rdkafka::error::KafkaError,
@@ -0,0 +1,19 @@
+use super::InternalEvent;
+use metrics::counter;
+
+#[derive(Debug)]
+pub struct LogstashEventReceived {
+    pub byte_size: usize,
+}
+
+impl InternalEvent for LogstashEventReceived {
+    fn emit_logs(&self) {
+        trace!(message = "Received event.", byte_size = %self.byte_size);
+    }
+
+    fn emit_metrics(&self) {
+        counter!("processed_events_total", 1);
+        counter!("events_in_total", 1);
+        counter!("processed_bytes_total", self.byte_size as u64);
+    }
+}
@@ -3,6 +3,8 @@ use std::borrow::Cow;
 mod adaptive_concurrency;
 mod add_fields;
 mod add_tags;
+#[cfg(feature = "transforms-aggregate")]
+mod aggregate;
 mod ansi_stripper;
 #[cfg(feature = "sources-apache_metrics")]
 mod apache_metrics;
@@ -31,14 +33,30 @@ mod concat;
 mod console;
 #[cfg(feature = "sinks-datadog")]
 mod datadog_logs;
+#[cfg(feature = "sources-utils-codecs")]
+mod decoder;
 #[cfg(feature = "transforms-dedupe")]
 mod dedupe;
+mod disk_buffer;
 #[cfg(feature = "sources-docker_logs")]
 mod docker_logs;
 mod elasticsearch;
+#[cfg(feature = "sinks-utils-codecs")]
+mod encoder;
 mod encoding_transcode;
+mod enrichment_tables;
+#[cfg(feature = "sources-exec")]
+mod exec;
 #[cfg(feature = "transforms-filter")]
 mod filter;
+#[cfg(feature = "sources-fluent")]
+mod fluent;
+#[cfg(any(
+    feature = "sources-socket",
+    feature = "sinks-socket",
+    feature = "sinks-http"
+))]
+mod gelf;
 #[cfg(feature = "sources-generator")]
 mod generator;
 #[cfg(feature = "transforms-geoip")]
@@ -65,17 +83,23 @@ mod log_to_metric;
 #[cfg(feature = "transforms-logfmt_parser")]
 mod logfmt_parser;
 mod logplex;
+#[cfg(feature = "sources-logstash")]
+mod logstash;
 #[cfg(feature = "transforms-lua")]
 mod lua;
 #[cfg(feature = "transforms-metric_to_log")]
 mod metric_to_log;
 #[cfg(feature = "sources-mongodb_metrics")]
 mod mongodb_metrics;
+#[cfg(any(feature = "sources-mqtt", feature = "sinks-mqtt"))]
+mod mqtt;
 #[cfg(feature = "sinks-nats")]
 mod nats;
 #[cfg(feature = "sources-nginx_metrics")]
 mod nginx_metrics;
 mod open;
+#[cfg(feature = "sources-opentelemetry")]
+mod opentelemetry;
 #[cfg(feature = "sources-postgresql_metrics")]
 mod postgresql_metrics;
 mod process;
@@ -110,6 +134,9 @@ mod syslog;
 mod tag_cardinality_limit;
 mod tcp;
 mod template;
+#[cfg(feature = "transforms-throttle")]
+mod throttle;
+mod tiered_buffer;
 #[cfg(feature = "transforms-tokenizer")]
 mod tokenizer;
 mod topology;
@@ -124,6 +151,8 @@ pub mod kubernetes;
 pub use self::adaptive_concurrency::*;
 pub use self::add_fields::*;
 pub use self::add_tags::*;
+#[cfg(feature = "transforms-aggregate")]
+pub(crate) use self::aggregate::*;
 pub use self::ansi_stripper::*;
 #[cfg(feature = "sources-apache_metrics")]
 pub use self::apache_metrics::*;
@@ -150,12 +179,20 @@ pub use self::concat::*;
 pub use self::console::*;
 #[cfg(feature = "sinks-datadog")]
 pub use self::datadog_logs::*;
+#[cfg(feature = "sources-utils-codecs")]
+pub(crate) use self::decoder::*;
 #[cfg(feature = "transforms-dedupe")]
 pub(crate) use self::dedupe::*;
+pub use self::disk_buffer::*;
 #[cfg(feature = "sources-docker_logs")]
 pub use self::docker_logs::*;
 pub use self::elasticsearch::*;
+#[cfg(feature = "sinks-utils-codecs")]
+pub use self::encoder::*;
 pub use self::encoding_transcode::*;
+pub use self::enrichment_tables::*;
+#[cfg(feature = "sources-exec")]
+pub use self::exec::*;
 #[cfg(any(
     feature = "sources-file",
     feature = "sources-kubernetes-logs",
@@ -164,6 +201,14 @@ pub use self::encoding_transcode::*;
 pub use self::file::*;
 #[cfg(feature = "transforms-filter")]
 pub use self::filter::*;
+#[cfg(feature = "sources-fluent")]
+pub use self::fluent::*;
+#[cfg(any(
+    feature = "sources-socket",
+    feature = "sinks-socket",
+    feature = "sinks-http"
+))]
+pub use self::gelf::*;
 #[cfg(feature = "sources-generator")]
 pub use self::generator::*;
 #[cfg(feature = "transforms-geoip")]
@@ -190,15 +235,21 @@ pub(crate) use self::log_to_metric::*;
 #[cfg(feature = "transforms-logfmt_parser")]
 pub use self::logfmt_parser::*;
 pub use self::logplex::*;
+#[cfg(feature = "sources-logstash")]
+pub use self::logstash::*;
 #[cfg(feature = "transforms-lua")]
 pub use self::lua::*;
 #[cfg(feature = "transforms-metric_to_log")]
 pub(crate) use self::metric_to_log::*;
+#[cfg(any(feature = "sources-mqtt", feature = "sinks-mqtt"))]
+pub use self::mqtt::*;
 #[cfg(feature = "sinks-nats")]
 pub use self::nats::*;
 #[cfg(feature = "sources-nginx_metrics")]
 pub(crate) use self::nginx_metrics::*;
 pub use self::open::*;
+#[cfg(feature = "sources-opentelemetry")]
+pub(crate) use self::opentelemetry::*;
 #[cfg(feature = "sources-postgresql_metrics")]
 pub(crate) use self::postgresql_metrics::*;
 pub use self::process::*;
@@ -233,6 +284,9 @@ pub use self::syslog::*;
 pub(crate) use self::tag_cardinality_limit::*;
 pub use self::tcp::*;
 pub use self::template::*;
+#[cfg(feature = "transforms-throttle")]
+pub(crate) use self::throttle::*;
+pub use self::tiered_buffer::*;
 #[cfg(feature = "transforms-tokenizer")]
 pub(crate) use self::tokenizer::*;
 pub use self::topology::*;
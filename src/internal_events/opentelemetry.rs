@@ -0,0 +1,24 @@
+use super::InternalEvent;
+use metrics::counter;
+
+#[derive(Debug)]
+pub struct OpenTelemetryEventsReceived {
+    pub events_count: usize,
+    pub byte_size: usize,
+}
+
+impl InternalEvent for OpenTelemetryEventsReceived {
+    fn emit_logs(&self) {
+        trace!(
+            message = "Received events.",
+            events_count = %self.events_count,
+            byte_size = %self.byte_size,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("processed_events_total", self.events_count as u64);
+        counter!("events_in_total", self.events_count as u64);
+        counter!("processed_bytes_total", self.byte_size as u64);
+    }
+}
@@ -0,0 +1,24 @@
+use super::InternalEvent;
+use metrics::counter;
+
+#[derive(Debug)]
+pub struct TieredBufferEventReceived {
+    pub tier: &'static str,
+}
+
+impl InternalEvent for TieredBufferEventReceived {
+    fn emit_metrics(&self) {
+        counter!("buffer_received_events_total", 1, "tier" => self.tier);
+    }
+}
+
+#[derive(Debug)]
+pub struct TieredBufferEventSent {
+    pub tier: &'static str,
+}
+
+impl InternalEvent for TieredBufferEventSent {
+    fn emit_metrics(&self) {
+        counter!("buffer_sent_events_total", 1, "tier" => self.tier);
+    }
+}
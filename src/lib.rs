@@ -42,6 +42,13 @@ pub mod app;
 pub mod async_read;
 pub mod buffers;
 pub mod encoding_transcode;
+pub mod enrichment_tables;
+#[cfg(any(
+    feature = "sources-socket",
+    feature = "sinks-socket",
+    feature = "sinks-http"
+))]
+pub mod gelf;
 pub mod heartbeat;
 pub mod http;
 #[cfg(feature = "rdkafka")]
@@ -51,7 +58,16 @@ pub mod line_agg;
 pub mod list;
 pub mod mapping;
 pub mod metrics;
+#[cfg(feature = "rumqttc")]
+pub(crate) mod mqtt;
 pub(crate) mod pipeline;
+#[cfg(any(
+    feature = "sources-opentelemetry",
+    feature = "sources-vector",
+    feature = "sinks-opentelemetry",
+    feature = "sinks-vector"
+))]
+pub mod proto;
 #[cfg(feature = "rusoto_core")]
 pub mod rusoto;
 pub mod serde;
@@ -63,6 +79,8 @@ pub mod sinks;
 pub mod sources;
 pub(crate) mod stats;
 pub mod stream;
+#[cfg(feature = "api-client")]
+pub mod tap;
 pub mod tcp;
 pub mod template;
 pub mod test_util;
@@ -0,0 +1,167 @@
+use crate::tls::TlsConfig;
+use rumqttc::{Key, MqttOptions, QoS};
+use serde::{Deserialize, Serialize};
+use snafu::{ResultExt, Snafu};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Snafu)]
+enum MqttError {
+    #[snafu(display("Could not read TLS {} file {:?}: {}", note, path, source))]
+    ReadTlsFile {
+        note: &'static str,
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[snafu(display("MQTT over TLS requires the TLS ca_file option"))]
+    MissingCaFile,
+    #[snafu(display("Must specify both TLS key_file and crt_file"))]
+    MissingCrtKeyFile,
+    #[snafu(display("The TLS {} option isn't supported for MQTT", option))]
+    UnsupportedTlsOption { option: &'static str },
+}
+
+/// The quality of service of the MQTT messages.
+#[derive(Clone, Copy, Debug, Derivative, Deserialize, Serialize, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum MqttQos {
+    /// QoS 0, messages are delivered at most once.
+    #[derivative(Default)]
+    AtMostOnce,
+    /// QoS 1, messages are delivered at least once.
+    AtLeastOnce,
+}
+
+impl From<MqttQos> for QoS {
+    fn from(qos: MqttQos) -> Self {
+        match qos {
+            MqttQos::AtMostOnce => QoS::AtMostOnce,
+            MqttQos::AtLeastOnce => QoS::AtLeastOnce,
+        }
+    }
+}
+
+/// The options to connect to an MQTT broker, shared by the source and the sink.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub(crate) struct MqttConnectionConfig {
+    pub host: String,
+    #[serde(default = "default_port")]
+    pub port: u16,
+    pub client_id: String,
+    pub user: Option<String>,
+    pub password: Option<String>,
+    #[serde(default = "default_keep_alive_secs")]
+    pub keep_alive_secs: u16,
+    pub tls: Option<TlsConfig>,
+}
+
+fn default_port() -> u16 {
+    1883
+}
+
+fn default_keep_alive_secs() -> u16 {
+    60
+}
+
+impl MqttConnectionConfig {
+    pub(crate) fn build_options(&self) -> crate::Result<MqttOptions> {
+        let mut options = MqttOptions::new(&self.client_id, &self.host, self.port);
+        options.set_keep_alive(self.keep_alive_secs);
+
+        if let Some(user) = &self.user {
+            options.set_credentials(user, self.password.as_deref().unwrap_or_default());
+        }
+
+        let tls = self.tls.as_ref().filter(|tls| tls.enabled.unwrap_or(false));
+        if let Some(tls) = tls {
+            let tls = &tls.options;
+            // The client verifies the broker's certificate and hostname
+            // against the given authority, without a way to disable it.
+            let unsupported = if tls.verify_certificate == Some(false) {
+                Some("verify_certificate")
+            } else if tls.verify_hostname == Some(false) {
+                Some("verify_hostname")
+            } else if tls.key_pass.is_some() {
+                Some("key_pass")
+            } else {
+                None
+            };
+            if let Some(option) = unsupported {
+                return Err(MqttError::UnsupportedTlsOption { option }.into());
+            }
+
+            let ca_file = tls.ca_file.as_ref().ok_or(MqttError::MissingCaFile)?;
+            options.set_ca(read_tls_file("ca", ca_file)?);
+
+            match (&tls.crt_file, &tls.key_file) {
+                (Some(crt_file), Some(key_file)) => {
+                    let crt = read_tls_file("crt", crt_file)?;
+                    let key = read_tls_file("key", key_file)?;
+                    let key = if String::from_utf8_lossy(&key).contains("BEGIN EC PRIVATE KEY") {
+                        Key::ECC(key)
+                    } else {
+                        Key::RSA(key)
+                    };
+                    options.set_client_auth(crt, key);
+                }
+                (None, None) => (),
+                _ => return Err(MqttError::MissingCrtKeyFile.into()),
+            }
+        }
+
+        Ok(options)
+    }
+}
+
+fn read_tls_file(note: &'static str, path: &Path) -> Result<Vec<u8>, MqttError> {
+    std::fs::read(path).with_context(|| ReadTlsFile { note, path })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tls::{TlsOptions, TEST_PEM_CA_PATH};
+
+    fn config(tls: TlsOptions) -> MqttConnectionConfig {
+        MqttConnectionConfig {
+            host: "localhost".into(),
+            port: default_port(),
+            client_id: "vector".into(),
+            user: None,
+            password: None,
+            keep_alive_secs: default_keep_alive_secs(),
+            tls: Some(TlsConfig {
+                enabled: Some(true),
+                options: tls,
+            }),
+        }
+    }
+
+    #[test]
+    fn builds_tls_options() {
+        assert!(config(TlsOptions::test_options()).build_options().is_ok());
+        assert!(config(TlsOptions {
+            ca_file: Some(TEST_PEM_CA_PATH.into()),
+            ..Default::default()
+        })
+        .build_options()
+        .is_ok());
+    }
+
+    #[test]
+    fn rejects_unsupported_tls_options() {
+        assert!(config(TlsOptions::default()).build_options().is_err());
+        assert!(config(TlsOptions {
+            verify_certificate: Some(false),
+            ..TlsOptions::test_options()
+        })
+        .build_options()
+        .is_err());
+        assert!(config(TlsOptions {
+            key_file: None,
+            ..TlsOptions::test_options()
+        })
+        .build_options()
+        .is_err());
+    }
+}
@@ -0,0 +1,119 @@
+//! gRPC services generated from the definitions in `proto/`.
+
+#[cfg(any(feature = "sources-vector", feature = "sinks-vector"))]
+pub mod vector {
+    include!(concat!(env!("OUT_DIR"), "/vector.rs"));
+}
+
+#[cfg(any(feature = "sources-opentelemetry", feature = "sinks-opentelemetry"))]
+pub mod opentelemetry {
+    pub mod proto {
+        pub mod common {
+            pub mod v1 {
+                include!(concat!(
+                    env!("OUT_DIR"),
+                    "/opentelemetry.proto.common.v1.rs"
+                ));
+            }
+        }
+
+        pub mod resource {
+            pub mod v1 {
+                include!(concat!(
+                    env!("OUT_DIR"),
+                    "/opentelemetry.proto.resource.v1.rs"
+                ));
+            }
+        }
+
+        pub mod logs {
+            pub mod v1 {
+                include!(concat!(env!("OUT_DIR"), "/opentelemetry.proto.logs.v1.rs"));
+            }
+        }
+
+        pub mod metrics {
+            pub mod v1 {
+                include!(concat!(
+                    env!("OUT_DIR"),
+                    "/opentelemetry.proto.metrics.v1.rs"
+                ));
+            }
+        }
+
+        pub mod collector {
+            pub mod logs {
+                pub mod v1 {
+                    include!(concat!(
+                        env!("OUT_DIR"),
+                        "/opentelemetry.proto.collector.logs.v1.rs"
+                    ));
+                }
+            }
+
+            pub mod metrics {
+                pub mod v1 {
+                    include!(concat!(
+                        env!("OUT_DIR"),
+                        "/opentelemetry.proto.collector.metrics.v1.rs"
+                    ));
+                }
+            }
+        }
+    }
+
+    /// Deserializers for the JSON encoding of OTLP/HTTP, which encodes 64 bit
+    /// integers as strings, and trace and span ids as hex strings.
+    mod json {
+        use serde::{de::Error, Deserialize, Deserializer};
+        use std::{fmt::Display, str::FromStr};
+
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Int<T> {
+            Number(T),
+            String(String),
+        }
+
+        impl<T: FromStr> Int<T>
+        where
+            T::Err: Display,
+        {
+            fn parse<E: Error>(self) -> Result<T, E> {
+                match self {
+                    Self::Number(number) => Ok(number),
+                    Self::String(string) => string.parse().map_err(E::custom),
+                }
+            }
+        }
+
+        pub fn int<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+        where
+            D: Deserializer<'de>,
+            T: Deserialize<'de> + FromStr,
+            T::Err: Display,
+        {
+            Int::deserialize(deserializer)?.parse()
+        }
+
+        pub fn ints<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
+        where
+            D: Deserializer<'de>,
+            T: Deserialize<'de> + FromStr,
+            T::Err: Display,
+        {
+            Vec::<Int<T>>::deserialize(deserializer)?
+                .into_iter()
+                .map(Int::parse)
+                .collect()
+        }
+
+        pub fn hex<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+            ::hex::decode(String::deserialize(deserializer)?).map_err(D::Error::custom)
+        }
+
+        pub fn base64<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+            ::base64::decode(String::deserialize(deserializer)?).map_err(D::Error::custom)
+        }
+    }
+}
@@ -1,10 +1,10 @@
 use crate::{
-    config::{log_schema, DataType, GenerateConfig, SinkConfig, SinkContext, SinkDescription},
+    config::{DataType, GenerateConfig, SinkConfig, SinkContext, SinkDescription},
     internal_events::TemplateRenderingFailed,
     rusoto::{self, AWSAuthentication, RegionOrEndpoint},
     serde::to_string,
     sinks::util::{
-        encoding::{EncodingConfig, EncodingConfiguration},
+        encoding::{self, EncodingConfig, FramingConfig, StandardEncodings},
         retries::RetryLogic,
         sink::Response,
         BatchConfig, BatchSettings, Buffer, Compression, Concurrency, PartitionBatchSink,
@@ -50,7 +50,9 @@ pub struct S3SinkConfig {
     options: S3Options,
     #[serde(flatten)]
     pub region: RegionOrEndpoint,
-    pub encoding: EncodingConfig<Encoding>,
+    pub encoding: EncodingConfig<StandardEncodings>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub framing: Option<FramingConfig>,
     #[serde(default = "Compression::gzip_default")]
     pub compression: Compression,
     #[serde(default)]
@@ -125,13 +127,6 @@ lazy_static! {
     };
 }
 
-#[derive(Deserialize, Serialize, Debug, Eq, PartialEq, Clone, Derivative)]
-#[serde(rename_all = "snake_case")]
-pub enum Encoding {
-    Text,
-    Ndjson,
-}
-
 inventory::submit! {
     SinkDescription::new::<S3SinkConfig>("aws_s3")
 }
@@ -146,7 +141,8 @@ impl GenerateConfig for S3SinkConfig {
             filename_extension: None,
             options: S3Options::default(),
             region: RegionOrEndpoint::default(),
-            encoding: Encoding::Text.into(),
+            encoding: StandardEncodings::Text.into(),
+            framing: None,
             compression: Compression::gzip_default(),
             batch: BatchConfig::default(),
             request: TowerRequestConfig::default(),
@@ -193,6 +189,10 @@ impl S3SinkConfig {
     pub fn new(&self, client: S3Client, cx: SinkContext) -> crate::Result<super::VectorSink> {
         let request = self.request.unwrap_with(&REQUEST_DEFAULTS);
         let encoding = self.encoding.clone();
+        let framing = self
+            .framing
+            .clone()
+            .unwrap_or(FramingConfig::NewlineDelimited);
 
         let compression = self.compression;
         let filename_time_format = self
@@ -232,7 +232,9 @@ impl S3SinkConfig {
         let buffer = PartitionBuffer::new(Buffer::new(batch.size, self.compression));
 
         let sink = PartitionBatchSink::new(svc, buffer, batch.timeout, cx.acker())
-            .with_flat_map(move |e| stream::iter(encode_event(e, &key_prefix, &encoding)).map(Ok))
+            .with_flat_map(move |e| {
+                stream::iter(encode_event(e, &key_prefix, &encoding, &framing)).map(Ok)
+            })
             .sink_map_err(|error| error!(message = "Sink failed to flush.", %error));
 
         Ok(super::VectorSink::Sink(Box::new(sink)))
@@ -392,9 +394,10 @@ impl RetryLogic for S3RetryLogic {
 }
 
 fn encode_event(
-    mut event: Event,
+    event: Event,
     key_prefix: &Template,
-    encoding: &EncodingConfig<Encoding>,
+    encoding: &EncodingConfig<StandardEncodings>,
+    framing: &FramingConfig,
 ) -> Option<PartitionInnerBuffer<Vec<u8>, Bytes>> {
     let key = key_prefix
         .render_string(&event)
@@ -407,25 +410,7 @@ fn encode_event(
         })
         .ok()?;
 
-    encoding.apply_rules(&mut event);
-
-    let log = event.into_log();
-    let bytes = match encoding.codec() {
-        Encoding::Ndjson => serde_json::to_vec(&log)
-            .map(|mut b| {
-                b.push(b'\n');
-                b
-            })
-            .expect("Failed to encode event as json, this is a bug!"),
-        Encoding::Text => {
-            let mut bytes = log
-                .get(log_schema().message_key())
-                .map(|v| v.as_bytes().to_vec())
-                .unwrap_or_default();
-            bytes.push(b'\n');
-            bytes
-        }
-    };
+    let bytes = encoding::encode_event(event, encoding, framing)?;
 
     Some(PartitionInnerBuffer::new(bytes, key.into()))
 }
@@ -433,6 +418,7 @@ fn encode_event(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{config::log_schema, event::LogEvent};
 
     #[test]
     fn generate_config() {
@@ -446,7 +432,8 @@ mod tests {
         let bytes = encode_event(
             message.clone().into(),
             &batch_time_format,
-            &Encoding::Text.into(),
+            &StandardEncodings::Text.into(),
+            &FramingConfig::NewlineDelimited,
         )
         .unwrap();
 
@@ -455,6 +442,21 @@ mod tests {
         assert_eq!(&bytes[..], encoded_message.as_bytes());
     }
 
+    #[test]
+    fn s3_encode_event_text_without_message() {
+        let batch_time_format = Template::try_from("date=%F").unwrap();
+        let bytes = encode_event(
+            LogEvent::default().into(),
+            &batch_time_format,
+            &StandardEncodings::Text.into(),
+            &FramingConfig::NewlineDelimited,
+        )
+        .unwrap();
+
+        let (bytes, _) = bytes.into_parts();
+        assert_eq!(&bytes[..], b"\n");
+    }
+
     #[test]
     fn s3_encode_event_ndjson() {
         let message = "hello world".to_string();
@@ -462,7 +464,13 @@ mod tests {
         event.as_mut_log().insert("key", "value");
 
         let batch_time_format = Template::try_from("date=%F").unwrap();
-        let bytes = encode_event(event, &batch_time_format, &Encoding::Ndjson.into()).unwrap();
+        let bytes = encode_event(
+            event,
+            &batch_time_format,
+            &StandardEncodings::Json.into(),
+            &FramingConfig::NewlineDelimited,
+        )
+        .unwrap();
 
         let (bytes, _) = bytes.into_parts();
         let map: BTreeMap<String, String> = serde_json::from_slice(&bytes[..]).unwrap();
@@ -480,14 +488,20 @@ mod tests {
         let key_prefix = Template::try_from("{{ key }}").unwrap();
 
         let encoding_config = EncodingConfig {
-            codec: Encoding::Ndjson,
+            codec: StandardEncodings::Json,
             schema: None,
             only_fields: None,
             except_fields: Some(vec!["key".into()]),
             timestamp_format: None,
         };
 
-        let bytes = encode_event(event, &key_prefix, &encoding_config).unwrap();
+        let bytes = encode_event(
+            event,
+            &key_prefix,
+            &encoding_config,
+            &FramingConfig::NewlineDelimited,
+        )
+        .unwrap();
 
         let (bytes, _) = bytes.into_parts();
         let map: BTreeMap<String, String> = serde_json::from_slice(&bytes[..]).unwrap();
@@ -572,7 +586,9 @@ mod integration_tests {
     use super::*;
     use crate::{
         assert_downcast_matches,
-        test_util::{random_lines_with_stream, random_string},
+        buffers::Acker,
+        event::{BatchNotifier, BatchStatus},
+        test_util::{map_batch_stream, random_lines_with_stream, random_string},
     };
     use bytes::{buf::BufExt, BytesMut};
     use flate2::read::GzDecoder;
@@ -607,6 +623,31 @@ mod integration_tests {
         assert_eq!(lines, response_lines);
     }
 
+    #[tokio::test]
+    async fn s3_insert_message_into_with_acknowledgements() {
+        let acker = Acker::finalizing(Acker::Null);
+        let cx = SinkContext::new_test_with_acker(acker.clone());
+
+        let config = config(1000000).await;
+        let prefix = config.key_prefix.clone();
+        let client = config.create_client().unwrap();
+        let sink = config.new(client, cx).unwrap();
+
+        let (batch, receiver) = BatchNotifier::new_with_receiver();
+        let (lines, events) = random_lines_with_stream(100, 10);
+        sink.run(map_batch_stream(events, batch, &acker))
+            .await
+            .unwrap();
+        drop(acker);
+        assert_eq!(receiver.await, BatchStatus::Delivered);
+
+        let keys = get_keys(prefix.unwrap()).await;
+        assert_eq!(keys.len(), 1);
+
+        let response_lines = get_lines(get_object(keys[0].clone()).await).await;
+        assert_eq!(lines, response_lines);
+    }
+
     #[tokio::test]
     async fn s3_rotate_files_after_the_buffer_size_is_reached() {
         let cx = SinkContext::new_test();
@@ -732,7 +773,8 @@ mod integration_tests {
             filename_extension: None,
             options: S3Options::default(),
             region: RegionOrEndpoint::with_endpoint("http://localhost:4566".to_owned()),
-            encoding: Encoding::Text.into(),
+            encoding: StandardEncodings::Text.into(),
+            framing: None,
             compression: Compression::None,
             batch: BatchConfig {
                 max_bytes: Some(batch_size),
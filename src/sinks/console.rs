@@ -2,9 +2,11 @@ use crate::{
     buffers::Acker,
     config::{DataType, GenerateConfig, SinkConfig, SinkContext, SinkDescription},
     event::Event,
-    internal_events::{ConsoleEventProcessed, ConsoleFieldNotFound},
+    internal_events::{ConsoleEventProcessed, ConsoleFieldNotFound, EncoderEncodeFailed},
     sinks::util::{
-        encoding::{EncodingConfig, EncodingConfiguration},
+        encoding::{
+            EncodingConfig, EncodingConfiguration, EncodingError, FramingConfig, StandardEncodings,
+        },
         StreamSink,
     },
 };
@@ -32,14 +34,9 @@ pub enum Target {
 pub struct ConsoleSinkConfig {
     #[serde(default)]
     pub target: Target,
-    pub encoding: EncodingConfig<Encoding>,
-}
-
-#[derive(Deserialize, Serialize, Debug, Eq, PartialEq, Clone)]
-#[serde(rename_all = "snake_case")]
-pub enum Encoding {
-    Text,
-    Json,
+    pub encoding: EncodingConfig<StandardEncodings>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub framing: Option<FramingConfig>,
 }
 
 inventory::submit! {
@@ -50,7 +47,8 @@ impl GenerateConfig for ConsoleSinkConfig {
     fn generate_config() -> toml::Value {
         toml::Value::try_from(Self {
             target: Target::Stdout,
-            encoding: Encoding::Json.into(),
+            encoding: StandardEncodings::Json.into(),
+            framing: None,
         })
         .unwrap()
     }
@@ -64,6 +62,10 @@ impl SinkConfig for ConsoleSinkConfig {
         cx: SinkContext,
     ) -> crate::Result<(super::VectorSink, super::Healthcheck)> {
         let encoding = self.encoding.clone();
+        let framing = self
+            .framing
+            .clone()
+            .unwrap_or(FramingConfig::NewlineDelimited);
 
         let output: Box<dyn io::AsyncWrite + Send + Sync + Unpin> = match self.target {
             Target::Stdout => Box::new(io::stdout()),
@@ -74,6 +76,7 @@ impl SinkConfig for ConsoleSinkConfig {
             acker: cx.acker(),
             output,
             encoding,
+            framing,
         };
 
         Ok((
@@ -91,43 +94,28 @@ impl SinkConfig for ConsoleSinkConfig {
     }
 }
 
-fn encode_event(mut event: Event, encoding: &EncodingConfig<Encoding>) -> Option<String> {
+fn encode_event(mut event: Event, encoding: &EncodingConfig<StandardEncodings>) -> Option<Vec<u8>> {
     encoding.apply_rules(&mut event);
-    match event {
-        Event::Log(log) => match encoding.codec() {
-            Encoding::Json => serde_json::to_string(&log)
-                .map_err(|error| {
-                    error!(message = "Error encoding json.", %error);
-                })
-                .ok(),
-            Encoding::Text => {
-                let field = crate::config::log_schema().message_key();
-                match log.get(field) {
-                    Some(v) => Some(v.to_string_lossy()),
-                    None => {
-                        emit!(ConsoleFieldNotFound {
-                            missing_field: field,
-                        });
-                        None
-                    }
-                }
-            }
-        },
-        Event::Metric(metric) => match encoding.codec() {
-            Encoding::Json => serde_json::to_string(&metric)
-                .map_err(|error| {
-                    error!(message = "Error encoding json.", %error);
-                })
-                .ok(),
-            Encoding::Text => Some(format!("{}", metric)),
-        },
+    match encoding.codec().encode(event) {
+        Ok(bytes) => Some(bytes),
+        Err(EncodingError::MissingMessage) => {
+            emit!(ConsoleFieldNotFound {
+                missing_field: crate::config::log_schema().message_key(),
+            });
+            None
+        }
+        Err(error) => {
+            emit!(EncoderEncodeFailed { error: &error });
+            None
+        }
     }
 }
 
 struct WriterSink {
     acker: Acker,
     output: Box<dyn io::AsyncWrite + Send + Sync + Unpin>,
-    encoding: EncodingConfig<Encoding>,
+    encoding: EncodingConfig<StandardEncodings>,
+    framing: FramingConfig,
 }
 
 #[async_trait]
@@ -135,9 +123,9 @@ impl StreamSink for WriterSink {
     async fn run(&mut self, mut input: BoxStream<'_, Event>) -> Result<(), ()> {
         while let Some(event) = input.next().await {
             self.acker.ack(1);
-            if let Some(mut buf) = encode_event(event, &self.encoding) {
-                buf.push('\n');
-                if let Err(error) = self.output.write_all(buf.as_bytes()).await {
+            if let Some(buf) = encode_event(event, &self.encoding) {
+                let buf = self.framing.frame(buf);
+                if let Err(error) = self.output.write_all(&buf).await {
                     // Error when writing to stdout/stderr is likely irrecoverable,
                     // so stop the sink.
                     error!(message = "Error writing to output. Stopping sink.", %error);
@@ -155,12 +143,17 @@ impl StreamSink for WriterSink {
 
 #[cfg(test)]
 mod test {
-    use super::{encode_event, ConsoleSinkConfig, Encoding, EncodingConfig};
+    use super::{encode_event, ConsoleSinkConfig, EncodingConfig, StandardEncodings};
     use crate::event::metric::{Metric, MetricKind, MetricValue, StatisticKind};
     use crate::event::{Event, Value};
     use chrono::{offset::TimeZone, Utc};
     use pretty_assertions::assert_eq;
 
+    fn encode(event: Event, codec: StandardEncodings) -> Option<String> {
+        encode_event(event, &EncodingConfig::from(codec))
+            .map(|bytes| String::from_utf8(bytes).unwrap())
+    }
+
     #[test]
     fn generate_config() {
         crate::test_util::test_generate_config::<ConsoleSinkConfig>();
@@ -169,10 +162,7 @@ mod test {
     #[test]
     fn encodes_raw_logs() {
         let event = Event::from("foo");
-        assert_eq!(
-            "foo",
-            encode_event(event, &EncodingConfig::from(Encoding::Text)).unwrap()
-        );
+        assert_eq!("foo", encode(event, StandardEncodings::Text).unwrap());
     }
 
     #[test]
@@ -183,7 +173,7 @@ mod test {
         log.insert("z", Value::from(25));
         log.insert("a", Value::from("0"));
 
-        let encoded = encode_event(event, &EncodingConfig::from(Encoding::Json));
+        let encoded = encode(event, StandardEncodings::Json);
         let expected = r#"{"a":"0","x":"23","z":25}"#;
         assert_eq!(encoded.unwrap(), expected);
     }
@@ -210,7 +200,7 @@ mod test {
         );
         assert_eq!(
             r#"{"name":"foos","namespace":"vector","tags":{"Key3":"Value3","key1":"value1","key2":"value2"},"timestamp":"2018-11-14T08:09:10.000000011Z","kind":"incremental","counter":{"value":100.0}}"#,
-            encode_event(event, &EncodingConfig::from(Encoding::Json)).unwrap()
+            encode(event, StandardEncodings::Json).unwrap()
         );
     }
 
@@ -225,7 +215,7 @@ mod test {
         ));
         assert_eq!(
             r#"{"name":"users","kind":"incremental","set":{"values":["bob"]}}"#,
-            encode_event(event, &EncodingConfig::from(Encoding::Json)).unwrap()
+            encode(event, StandardEncodings::Json).unwrap()
         );
     }
 
@@ -241,7 +231,7 @@ mod test {
         ));
         assert_eq!(
             r#"{"name":"glork","kind":"incremental","distribution":{"samples":[{"value":10.0,"rate":1}],"statistic":"histogram"}}"#,
-            encode_event(event, &EncodingConfig::from(Encoding::Json)).unwrap()
+            encode(event, StandardEncodings::Json).unwrap()
         );
     }
 
@@ -256,7 +246,7 @@ mod test {
         ));
         assert_eq!(
             "users{} + bob",
-            encode_event(event, &EncodingConfig::from(Encoding::Text)).unwrap()
+            encode(event, StandardEncodings::Text).unwrap()
         );
     }
 }
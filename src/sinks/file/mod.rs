@@ -1,12 +1,12 @@
 use crate::expiring_hash_map::ExpiringHashMap;
 use crate::{
     buffers::Acker,
-    config::{log_schema, DataType, GenerateConfig, SinkConfig, SinkContext, SinkDescription},
+    config::{DataType, GenerateConfig, SinkConfig, SinkContext, SinkDescription},
     event::Event,
     internal_events::FileOpen,
     internal_events::TemplateRenderingFailed,
     sinks::util::{
-        encoding::{EncodingConfig, EncodingConfiguration},
+        encoding::{self, EncodingConfig, FramingConfig, StandardEncodings},
         StreamSink,
     },
     template::Template,
@@ -35,7 +35,9 @@ use std::convert::TryFrom;
 pub struct FileSinkConfig {
     pub path: Template,
     pub idle_timeout_secs: Option<u64>,
-    pub encoding: EncodingConfig<Encoding>,
+    pub encoding: EncodingConfig<StandardEncodings>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub framing: Option<FramingConfig>,
     #[serde(
         default,
         skip_serializing_if = "crate::serde::skip_serializing_if_default"
@@ -52,20 +54,14 @@ impl GenerateConfig for FileSinkConfig {
         toml::Value::try_from(Self {
             path: Template::try_from("/tmp/vector-%Y-%m-%d.log").unwrap(),
             idle_timeout_secs: None,
-            encoding: Encoding::Text.into(),
+            encoding: StandardEncodings::Text.into(),
+            framing: None,
             compression: Default::default(),
         })
         .unwrap()
     }
 }
 
-#[derive(Deserialize, Serialize, Debug, Eq, PartialEq, Clone)]
-#[serde(rename_all = "snake_case")]
-pub enum Encoding {
-    Text,
-    Ndjson,
-}
-
 #[derive(Deserialize, Serialize, Debug, Eq, PartialEq, Clone, Copy)]
 #[serde(rename_all = "snake_case")]
 pub enum Compression {
@@ -148,7 +144,8 @@ impl SinkConfig for FileSinkConfig {
 pub struct FileSink {
     acker: Acker,
     path: Template,
-    encoding: EncodingConfig<Encoding>,
+    encoding: EncodingConfig<StandardEncodings>,
+    framing: FramingConfig,
     idle_timeout: Duration,
     files: ExpiringHashMap<Bytes, OutFile>,
     compression: Compression,
@@ -160,6 +157,10 @@ impl FileSink {
             acker,
             path: config.path.clone(),
             encoding: config.encoding.clone(),
+            framing: config
+                .framing
+                .clone()
+                .unwrap_or(FramingConfig::NewlineDelimited),
             idle_timeout: Duration::from_secs(config.idle_timeout_secs.unwrap_or(30)),
             files: ExpiringHashMap::default(),
             compression: config.compression,
@@ -261,6 +262,12 @@ impl FileSink {
             }
         };
 
+        // Events which can't be encoded are reported by the encoder.
+        let bytes = match encoding::encode_event(event, &self.encoding, &self.framing) {
+            Some(bytes) => bytes,
+            None => return,
+        };
+
         let next_deadline = self.deadline_at();
         trace!(message = "Computed next deadline.", next_deadline = ?next_deadline, path = ?path);
 
@@ -290,7 +297,7 @@ impl FileSink {
         };
 
         trace!(message = "Writing an event to file.", path = ?path);
-        if let Err(error) = write_event_to_file(file, event, &self.encoding).await {
+        if let Err(error) = file.write_all(&bytes[..]).await {
             error!(message = "Failed to write file.", path = ?path, %error);
         }
     }
@@ -312,28 +319,6 @@ async fn open_file(path: impl AsRef<std::path::Path>) -> std::io::Result<File> {
         .await
 }
 
-pub fn encode_event(encoding: &EncodingConfig<Encoding>, mut event: Event) -> Vec<u8> {
-    encoding.apply_rules(&mut event);
-    let log = event.into_log();
-    match encoding.codec() {
-        Encoding::Ndjson => serde_json::to_vec(&log).expect("Unable to encode event as JSON."),
-        Encoding::Text => log
-            .get(log_schema().message_key())
-            .map(|v| v.to_string_lossy().into_bytes())
-            .unwrap_or_default(),
-    }
-}
-
-async fn write_event_to_file(
-    file: &mut OutFile,
-    event: Event,
-    encoding: &EncodingConfig<Encoding>,
-) -> Result<(), std::io::Error> {
-    let mut buf = encode_event(encoding, event);
-    buf.push(b'\n');
-    file.write_all(&buf[..]).await
-}
-
 #[async_trait]
 impl StreamSink for FileSink {
     async fn run(&mut self, input: BoxStream<'_, Event>) -> Result<(), ()> {
@@ -345,9 +330,12 @@ impl StreamSink for FileSink {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::test_util::{
-        lines_from_file, lines_from_gzip_file, random_events_with_stream, random_lines_with_stream,
-        temp_dir, temp_file, trace_init,
+    use crate::{
+        config::log_schema,
+        test_util::{
+            lines_from_file, lines_from_gzip_file, random_events_with_stream,
+            random_lines_with_stream, temp_dir, temp_file, trace_init,
+        },
     };
     use futures::stream;
     use std::convert::TryInto;
@@ -366,7 +354,8 @@ mod tests {
         let config = FileSinkConfig {
             path: template.clone().try_into().unwrap(),
             idle_timeout_secs: None,
-            encoding: Encoding::Text.into(),
+            encoding: StandardEncodings::Text.into(),
+            framing: None,
             compression: Compression::None,
         };
 
@@ -391,7 +380,8 @@ mod tests {
         let config = FileSinkConfig {
             path: template.clone().try_into().unwrap(),
             idle_timeout_secs: None,
-            encoding: Encoding::Text.into(),
+            encoding: StandardEncodings::Text.into(),
+            framing: None,
             compression: Compression::Gzip,
         };
 
@@ -421,7 +411,8 @@ mod tests {
         let config = FileSinkConfig {
             path: template.try_into().unwrap(),
             idle_timeout_secs: None,
-            encoding: Encoding::Text.into(),
+            encoding: StandardEncodings::Text.into(),
+            framing: None,
             compression: Compression::None,
         };
 
@@ -502,7 +493,8 @@ mod tests {
         let config = FileSinkConfig {
             path: template.clone().try_into().unwrap(),
             idle_timeout_secs: Some(1),
-            encoding: Encoding::Text.into(),
+            encoding: StandardEncodings::Text.into(),
+            framing: None,
             compression: Compression::None,
         };
 
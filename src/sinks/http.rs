@@ -1,11 +1,16 @@
 use crate::{
     config::{DataType, GenerateConfig, SinkConfig, SinkContext, SinkDescription},
     event::Event,
+    gelf,
     http::{Auth, HttpClient, MaybeAuth},
-    internal_events::{HTTPEventEncoded, HTTPEventMissingMessage},
+    internal_events::{
+        EncoderEncodeFailed, GelfEncodeFailed, HTTPEventEncoded, HTTPEventMissingMessage,
+    },
     sinks::util::{
         buffer::compression::GZIP_DEFAULT,
-        encoding::{EncodingConfig, EncodingConfiguration},
+        encoding::{
+            EncodingConfig, EncodingConfiguration, EncodingError, ExtendedEncoding, FramingConfig,
+        },
         http::{BatchedHttpSink, HttpSink, RequestConfig},
         BatchConfig, BatchSettings, Buffer, Compression, Concurrency, TowerRequestConfig, UriSerde,
     },
@@ -48,7 +53,9 @@ pub struct HttpSinkConfig {
     pub headers: Option<IndexMap<String, String>>,
     #[serde(default)]
     pub compression: Compression,
-    pub encoding: EncodingConfig<Encoding>,
+    pub encoding: EncodingConfig<ExtendedEncoding<Encoding>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub framing: Option<FramingConfig>,
     #[serde(default)]
     pub batch: BatchConfig,
     #[serde(default)]
@@ -57,7 +64,8 @@ pub struct HttpSinkConfig {
 }
 
 #[cfg(test)]
-fn default_config(e: Encoding) -> HttpSinkConfig {
+fn default_config(e: impl Into<ExtendedEncoding<Encoding>>) -> HttpSinkConfig {
+    let e: ExtendedEncoding<Encoding> = e.into();
     HttpSinkConfig {
         uri: Default::default(),
         method: Default::default(),
@@ -66,6 +74,7 @@ fn default_config(e: Encoding) -> HttpSinkConfig {
         compression: Default::default(),
         batch: Default::default(),
         encoding: e.into(),
+        framing: Default::default(),
         request: Default::default(),
         tls: Default::default(),
     }
@@ -89,12 +98,13 @@ pub enum HttpMethod {
     Put,
 }
 
+/// The encodings specific to this sink, on top of the standard ones.
 #[derive(Deserialize, Serialize, Debug, Eq, PartialEq, Clone)]
 #[serde(rename_all = "snake_case")]
 pub enum Encoding {
-    Text,
-    Ndjson,
+    /// Events sent as a JSON array.
     Json,
+    Gelf,
 }
 
 inventory::submit! {
@@ -137,10 +147,14 @@ impl SinkConfig for HttpSinkConfig {
         config.request.add_old_option(config.headers.take());
         validate_headers(&config.request.headers, &config.auth)?;
 
-        let batch = BatchSettings::default()
+        let mut batch = BatchSettings::default()
             .bytes(bytesize::mib(10u64))
             .timeout(1)
             .parse_config(config.batch)?;
+        // Graylog's GELF HTTP input accepts a single message per request.
+        if config.encoding.codec() == &ExtendedEncoding::Extension(Encoding::Gelf) {
+            batch.size.events = 1;
+        }
         let request = config.request.tower.unwrap_with(&REQUEST_DEFAULTS);
 
         let sink = BatchedHttpSink::new(
@@ -174,35 +188,35 @@ impl HttpSink for HttpSinkConfig {
 
     fn encode_event(&self, mut event: Event) -> Option<Self::Input> {
         self.encoding.apply_rules(&mut event);
-        let event = event.into_log();
 
         let body = match &self.encoding.codec() {
-            Encoding::Text => {
-                if let Some(v) = event.get(crate::config::log_schema().message_key()) {
-                    let mut b = v.to_string_lossy().into_bytes();
-                    b.push(b'\n');
-                    b
-                } else {
+            ExtendedEncoding::Standard(codec) => match codec.encode(event) {
+                Ok(bytes) => self
+                    .framing
+                    .as_ref()
+                    .unwrap_or(&FramingConfig::NewlineDelimited)
+                    .frame(bytes),
+                Err(EncodingError::MissingMessage) => {
                     emit!(HTTPEventMissingMessage);
                     return None;
                 }
-            }
-
-            Encoding::Ndjson => {
-                let mut b = serde_json::to_vec(&event)
-                    .map_err(|error| panic!("Unable to encode into JSON: {}", error))
-                    .ok()?;
-                b.push(b'\n');
-                b
-            }
+                Err(error) => {
+                    emit!(EncoderEncodeFailed { error: &error });
+                    return None;
+                }
+            },
 
-            Encoding::Json => {
-                let mut b = serde_json::to_vec(&event)
+            ExtendedEncoding::Extension(Encoding::Json) => {
+                let mut b = serde_json::to_vec(&event.into_log())
                     .map_err(|error| panic!("Unable to encode into JSON: {}", error))
                     .ok()?;
                 b.push(b',');
                 b
             }
+
+            ExtendedEncoding::Extension(Encoding::Gelf) => gelf::encode(event.into_log())
+                .map_err(|error| emit!(GelfEncodeFailed { error: &error }))
+                .ok()?,
         };
 
         emit!(HTTPEventEncoded {
@@ -220,14 +234,14 @@ impl HttpSink for HttpSinkConfig {
         let uri: Uri = self.uri.uri.clone();
 
         let ct = match self.encoding.codec() {
-            Encoding::Text => "text/plain",
-            Encoding::Ndjson => "application/x-ndjson",
-            Encoding::Json => {
+            ExtendedEncoding::Standard(codec) => codec.content_type(),
+            ExtendedEncoding::Extension(Encoding::Json) => {
                 body.insert(0, b'[');
                 body.pop(); // remove trailing comma from last record
                 body.push(b']');
                 "application/json"
             }
+            ExtendedEncoding::Extension(Encoding::Gelf) => "application/json",
         };
 
         let mut builder = Request::builder()
@@ -299,7 +313,7 @@ mod tests {
         config::SinkContext,
         sinks::{
             http::HttpSinkConfig,
-            util::{http::HttpSink, test::build_test_server},
+            util::{encoding::StandardEncodings, http::HttpSink, test::build_test_server},
         },
         test_util::{next_addr, random_lines_with_stream},
     };
@@ -320,11 +334,9 @@ mod tests {
 
     #[test]
     fn http_encode_event_text() {
-        let encoding = EncodingConfig::from(Encoding::Text);
         let event = Event::from("hello world");
 
-        let mut config = default_config(Encoding::Text);
-        config.encoding = encoding;
+        let config = default_config(StandardEncodings::Text);
         let bytes = config.encode_event(event).unwrap();
 
         assert_eq!(bytes, Vec::from(&"hello world\n"[..]));
@@ -332,11 +344,9 @@ mod tests {
 
     #[test]
     fn http_encode_event_json() {
-        let encoding = EncodingConfig::from(Encoding::Ndjson);
         let event = Event::from("hello world");
 
-        let mut config = default_config(Encoding::Json);
-        config.encoding = encoding;
+        let config = default_config(StandardEncodings::Json);
         let bytes = config.encode_event(event).unwrap();
 
         #[derive(Deserialize, Debug)]
@@ -351,6 +361,20 @@ mod tests {
         assert_eq!(output.message, "hello world".to_string());
     }
 
+    #[test]
+    fn http_encode_event_gelf() {
+        let mut event = Event::from("hello world");
+        event.as_mut_log().insert("host", "example.org");
+
+        let config = default_config(ExtendedEncoding::Extension(Encoding::Gelf));
+        let bytes = config.encode_event(event).unwrap();
+
+        let output = serde_json::from_slice::<serde_json::Value>(&bytes[..]).unwrap();
+        assert_eq!(output["version"], "1.1");
+        assert_eq!(output["host"], "example.org");
+        assert_eq!(output["short_message"], "hello world");
+    }
+
     #[test]
     fn http_validates_normal_headers() {
         let config = r#"
@@ -1,11 +1,12 @@
 use crate::{
     buffers::Acker,
     config::{log_schema, DataType, GenerateConfig, SinkConfig, SinkContext, SinkDescription},
+    event::EventStatus,
     internal_events::TemplateRenderingFailed,
     kafka::{KafkaAuthConfig, KafkaCompression},
     serde::to_string,
     sinks::util::{
-        encoding::{EncodingConfig, EncodingConfiguration},
+        encoding::{self, EncodingConfig, FramingConfig, StandardEncodings},
         BatchConfig,
     },
     template::{Template, TemplateParseError},
@@ -24,7 +25,7 @@ use rdkafka::{
 use serde::{Deserialize, Serialize};
 use snafu::{ResultExt, Snafu};
 use std::{
-    collections::{HashMap, HashSet},
+    collections::HashMap,
     convert::TryFrom,
     pin::Pin,
     sync::Arc,
@@ -48,7 +49,9 @@ pub struct KafkaSinkConfig {
     bootstrap_servers: String,
     topic: String,
     key_field: Option<String>,
-    encoding: EncodingConfig<Encoding>,
+    encoding: EncodingConfig<StandardEncodings>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    framing: Option<FramingConfig>,
     /// These batching options will **not** override librdkafka_options values.
     #[serde(default)]
     batch: BatchConfig,
@@ -72,18 +75,12 @@ fn default_message_timeout_ms() -> u64 {
     300000 // default in librdkafka
 }
 
-#[derive(Clone, Copy, Debug, Derivative, Deserialize, Serialize, Eq, PartialEq)]
-#[serde(rename_all = "snake_case")]
-pub enum Encoding {
-    Text,
-    Json,
-}
-
 pub struct KafkaSink {
     producer: Arc<FutureProducer>,
     topic: Template,
     key_field: Option<String>,
-    encoding: EncodingConfig<Encoding>,
+    encoding: EncodingConfig<StandardEncodings>,
+    framing: FramingConfig,
     delivery_fut: FuturesUnordered<BoxFuture<'static, (usize, Result<DeliveryFuture, KafkaError>)>>,
     in_flight: FuturesUnordered<
         BoxFuture<'static, (usize, Result<Result<(i32, i64), KafkaError>, Canceled>)>,
@@ -92,7 +89,7 @@ pub struct KafkaSink {
     acker: Acker,
     seq_head: usize,
     seq_tail: usize,
-    pending_acks: HashSet<usize>,
+    pending_acks: HashMap<usize, EventStatus>,
 }
 
 inventory::submit! {
@@ -232,12 +229,13 @@ impl KafkaSink {
             topic: Template::try_from(config.topic).context(TopicTemplate)?,
             key_field: config.key_field,
             encoding: config.encoding,
+            framing: config.framing.unwrap_or(FramingConfig::Bytes),
             delivery_fut: FuturesUnordered::new(),
             in_flight: FuturesUnordered::new(),
             acker,
             seq_head: 0,
             seq_tail: 0,
-            pending_acks: HashSet::new(),
+            pending_acks: HashMap::new(),
         })
     }
 
@@ -260,6 +258,24 @@ impl KafkaSink {
 
         Poll::Ready(())
     }
+
+    fn ack_pending(&mut self, seqno: usize, status: EventStatus) {
+        self.pending_acks.insert(seqno, status);
+
+        // Consecutive events with the same status are acked together.
+        let mut num_to_ack = 0;
+        let mut ack_status = EventStatus::Delivered;
+        while let Some(status) = self.pending_acks.remove(&self.seq_tail) {
+            if num_to_ack > 0 && status != ack_status {
+                self.acker.ack_with_status(num_to_ack, ack_status);
+                num_to_ack = 0;
+            }
+            ack_status = status;
+            num_to_ack += 1;
+            self.seq_tail += 1
+        }
+        self.acker.ack_with_status(num_to_ack, ack_status);
+    }
 }
 
 impl Sink<Event> for KafkaSink {
@@ -293,11 +309,20 @@ impl Sink<Event> for KafkaSink {
             Event::Metric(metric) => metric.data.timestamp.as_ref(),
         }
         .map(|ts| ts.timestamp_millis());
-        let (key, body) = encode_event(item, &self.key_field, &self.encoding);
+        let encoded = encode_event(item, &self.key_field, &self.encoding, &self.framing);
 
         let seqno = self.seq_head;
         self.seq_head += 1;
 
+        let (key, body) = match encoded {
+            Some(encoded) => encoded,
+            None => {
+                // The event is dropped, but it is acknowledged in order.
+                self.ack_pending(seqno, EventStatus::Failed);
+                return Ok(());
+            }
+        };
+
         let producer = Arc::clone(&self.producer);
         self.delivery_fut.push(Box::pin(async move {
             let mut record = FutureRecord::to(&topic).key(&key).payload(&body[..]);
@@ -336,21 +361,18 @@ impl Sink<Event> for KafkaSink {
         while !this.in_flight.is_empty() {
             match ready!(Pin::new(&mut this.in_flight).poll_next(cx)) {
                 Some((seqno, Ok(result))) => {
-                    match result {
+                    let status = match result {
                         Ok((partition, offset)) => {
-                            trace!(message = "Produced message.", ?partition, ?offset)
+                            trace!(message = "Produced message.", ?partition, ?offset);
+                            EventStatus::Delivered
+                        }
+                        Err(error) => {
+                            error!(message = "Kafka error.", %error);
+                            EventStatus::Errored
                         }
-                        Err(error) => error!(message = "Kafka error.", %error),
                     };
 
-                    this.pending_acks.insert(seqno);
-
-                    let mut num_to_ack = 0;
-                    while this.pending_acks.remove(&this.seq_tail) {
-                        num_to_ack += 1;
-                        this.seq_tail += 1
-                    }
-                    this.acker.ack(num_to_ack);
+                    this.ack_pending(seqno, status);
                 }
                 Some((_, Err(Canceled))) => {
                     error!(message = "Request canceled.");
@@ -399,10 +421,11 @@ async fn healthcheck(config: KafkaSinkConfig) -> crate::Result<()> {
 }
 
 fn encode_event(
-    mut event: Event,
+    event: Event,
     key_field: &Option<String>,
-    encoding: &EncodingConfig<Encoding>,
-) -> (Vec<u8>, Vec<u8>) {
+    encoding: &EncodingConfig<StandardEncodings>,
+    framing: &FramingConfig,
+) -> Option<(Vec<u8>, Vec<u8>)> {
     let key = key_field
         .as_ref()
         .and_then(|f| match &event {
@@ -414,29 +437,15 @@ fn encode_event(
         })
         .unwrap_or_default();
 
-    encoding.apply_rules(&mut event);
-
-    let body = match event {
-        Event::Log(log) => match encoding.codec() {
-            Encoding::Json => serde_json::to_vec(&log).unwrap(),
-            Encoding::Text => log
-                .get(log_schema().message_key())
-                .map(|v| v.as_bytes().to_vec())
-                .unwrap_or_default(),
-        },
-        Event::Metric(metric) => match encoding.codec() {
-            Encoding::Json => serde_json::to_vec(&metric).unwrap(),
-            Encoding::Text => metric.to_string().into_bytes(),
-        },
-    };
+    let body = encoding::encode_event(event, encoding, framing)?;
 
-    (key, body)
+    Some((key, body))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::event::{Metric, MetricKind, MetricValue};
+    use crate::event::{LogEvent, Metric, MetricKind, MetricValue};
     use std::collections::BTreeMap;
 
     #[test]
@@ -452,13 +461,28 @@ mod tests {
         let (key_bytes, bytes) = encode_event(
             message.clone().into(),
             &None,
-            &EncodingConfig::from(Encoding::Text),
-        );
+            &EncodingConfig::from(StandardEncodings::Text),
+            &FramingConfig::Bytes,
+        )
+        .unwrap();
 
         assert_eq!(&key_bytes[..], key.as_bytes());
         assert_eq!(&bytes[..], message.as_bytes());
     }
 
+    #[test]
+    fn kafka_encode_event_log_text_without_message() {
+        let (_, bytes) = encode_event(
+            LogEvent::default().into(),
+            &None,
+            &EncodingConfig::from(StandardEncodings::Text),
+            &FramingConfig::Bytes,
+        )
+        .unwrap();
+
+        assert!(bytes.is_empty());
+    }
+
     #[test]
     fn kafka_encode_event_log_json() {
         crate::test_util::trace_init();
@@ -470,8 +494,10 @@ mod tests {
         let (key, bytes) = encode_event(
             event,
             &Some("key".into()),
-            &EncodingConfig::from(Encoding::Json),
-        );
+            &EncodingConfig::from(StandardEncodings::Json),
+            &FramingConfig::Bytes,
+        )
+        .unwrap();
 
         let map: BTreeMap<String, String> = serde_json::from_slice(&bytes[..]).unwrap();
 
@@ -491,8 +517,10 @@ mod tests {
         let (key_bytes, bytes) = encode_event(
             metric.clone().into(),
             &None,
-            &EncodingConfig::from(Encoding::Text),
-        );
+            &EncodingConfig::from(StandardEncodings::Text),
+            &FramingConfig::Bytes,
+        )
+        .unwrap();
 
         assert_eq!("", String::from_utf8_lossy(&key_bytes));
         assert_eq!(metric.to_string(), String::from_utf8_lossy(&bytes));
@@ -508,8 +536,10 @@ mod tests {
         let (key_bytes, bytes) = encode_event(
             metric.clone().into(),
             &None,
-            &EncodingConfig::from(Encoding::Json),
-        );
+            &EncodingConfig::from(StandardEncodings::Json),
+            &FramingConfig::Bytes,
+        )
+        .unwrap();
 
         assert_eq!("", String::from_utf8_lossy(&key_bytes));
         assert_eq!(
@@ -528,13 +558,15 @@ mod tests {
             event,
             &Some("key".into()),
             &EncodingConfig {
-                codec: Encoding::Json,
+                codec: StandardEncodings::Json,
                 schema: None,
                 only_fields: None,
                 except_fields: Some(vec!["key".into()]),
                 timestamp_format: None,
             },
-        );
+            &FramingConfig::Bytes,
+        )
+        .unwrap();
 
         let map: BTreeMap<String, String> = serde_json::from_slice(&bytes[..]).unwrap();
 
@@ -549,8 +581,9 @@ mod integration_test {
     use super::*;
     use crate::{
         buffers::Acker,
+        event::{BatchNotifier, BatchStatus},
         kafka::{KafkaAuthConfig, KafkaSaslConfig, KafkaTlsConfig},
-        test_util::{random_lines_with_stream, random_string, wait_for},
+        test_util::{map_batch_stream, random_lines_with_stream, random_string, wait_for},
         tls::TlsOptions,
     };
     use futures::StreamExt;
@@ -569,7 +602,8 @@ mod integration_test {
             bootstrap_servers: "localhost:9091".into(),
             topic: topic.clone(),
             key_field: None,
-            encoding: EncodingConfig::from(Encoding::Text),
+            encoding: EncodingConfig::from(StandardEncodings::Text),
+            framing: None,
             batch: BatchConfig::default(),
             compression: KafkaCompression::None,
             auth: KafkaAuthConfig::default(),
@@ -620,7 +654,8 @@ mod integration_test {
             bootstrap_servers: "localhost:9091".to_string(),
             topic: format!("{}-%Y%m%d", topic),
             compression: KafkaCompression::None,
-            encoding: Encoding::Text.into(),
+            encoding: StandardEncodings::Text.into(),
+            framing: None,
             key_field: None,
             auth: KafkaAuthConfig {
                 sasl: None,
@@ -774,7 +809,8 @@ mod integration_test {
             bootstrap_servers: server.to_string(),
             topic: format!("{}-%Y%m%d", topic),
             key_field: None,
-            encoding: EncodingConfig::from(Encoding::Text),
+            encoding: EncodingConfig::from(StandardEncodings::Text),
+            framing: None,
             batch: BatchConfig::default(),
             compression,
             auth: kafka_auth.clone(),
@@ -784,11 +820,16 @@ mod integration_test {
         };
         let topic = format!("{}-{}", topic, chrono::Utc::now().format("%Y%m%d"));
         let (acker, ack_counter) = Acker::new_for_testing();
-        let sink = KafkaSink::new(config, acker).unwrap();
+        let acker = Acker::finalizing(acker);
+        let sink = KafkaSink::new(config, acker.clone()).unwrap();
 
         let num_events = 1000;
+        let (batch, receiver) = BatchNotifier::new_with_receiver();
         let (input, events) = random_lines_with_stream(100, num_events);
+        let events = map_batch_stream(events, batch, &acker);
         events.map(Ok).forward(sink).await.unwrap();
+        drop(acker);
+        assert_eq!(receiver.await, BatchStatus::Delivered);
 
         // read back everything from the beginning
         let mut client_config = rdkafka::ClientConfig::new();
@@ -0,0 +1,432 @@
+use crate::{
+    buffers::Acker,
+    config::{DataType, GenerateConfig, SinkConfig, SinkContext, SinkDescription},
+    emit,
+    event::EventStatus,
+    internal_events::{
+        MqttConnectionFailed, MqttEventSendFail, MqttEventSendSuccess, TemplateRenderingFailed,
+    },
+    mqtt::{MqttConnectionConfig, MqttQos},
+    sinks::util::{
+        encoding::{self, EncodingConfig, FramingConfig, StandardEncodings},
+        StreamSink,
+    },
+    template::{Template, TemplateParseError},
+    Event,
+};
+use async_trait::async_trait;
+use futures::{future, stream::BoxStream, FutureExt, StreamExt};
+use rumqttc::{AsyncClient, Event as MqttEvent, MqttOptions, Outgoing, Packet};
+use serde::{Deserialize, Serialize};
+use snafu::{ResultExt, Snafu};
+use std::{
+    collections::{HashMap, VecDeque},
+    convert::TryFrom,
+    sync::{Arc, Mutex},
+};
+use tokio::time::{delay_for, Duration};
+
+/// The capacity of the channel of requests to the event loop of the client.
+const REQUESTS_CAPACITY: usize = 100;
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("invalid topic template: {}", source))]
+    TopicTemplate { source: TemplateParseError },
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct MqttSinkConfig {
+    #[serde(flatten)]
+    connection: MqttConnectionConfig,
+    topic: String,
+    #[serde(default)]
+    qos: MqttQos,
+    #[serde(default)]
+    retain: bool,
+    encoding: EncodingConfig<StandardEncodings>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    framing: Option<FramingConfig>,
+}
+
+inventory::submit! {
+    SinkDescription::new::<MqttSinkConfig>("mqtt")
+}
+
+impl GenerateConfig for MqttSinkConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"host = "127.0.0.1"
+            client_id = "vector"
+            topic = "vector/{{ host }}"
+            encoding.codec = "json""#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "mqtt")]
+impl SinkConfig for MqttSinkConfig {
+    async fn build(
+        &self,
+        cx: SinkContext,
+    ) -> crate::Result<(super::VectorSink, super::Healthcheck)> {
+        let sink = MqttSink::new(self.clone(), cx.acker())?;
+        // The client connects lazily and reconnects on its own, so there is
+        // no connection to check ahead of time.
+        let healthcheck = future::ok(()).boxed();
+        Ok((super::VectorSink::Stream(Box::new(sink)), healthcheck))
+    }
+
+    fn input_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    fn sink_type(&self) -> &'static str {
+        "mqtt"
+    }
+}
+
+pub struct MqttSink {
+    options: MqttOptions,
+    topic: Template,
+    qos: MqttQos,
+    retain: bool,
+    encoding: EncodingConfig<StandardEncodings>,
+    framing: FramingConfig,
+    acker: Acker,
+}
+
+impl MqttSink {
+    fn new(config: MqttSinkConfig, acker: Acker) -> crate::Result<Self> {
+        Ok(MqttSink {
+            options: config.connection.build_options()?,
+            topic: Template::try_from(config.topic).context(TopicTemplate)?,
+            qos: config.qos,
+            retain: config.retain,
+            encoding: config.encoding,
+            framing: config.framing.unwrap_or(FramingConfig::Bytes),
+            acker,
+        })
+    }
+}
+
+/// Acknowledges the events in order once their messages are sent, with QoS
+/// 0, or confirmed by the broker, with QoS 1.
+struct PendingAcks {
+    acker: Acker,
+    qos: MqttQos,
+    seq_head: usize,
+    seq_tail: usize,
+    pending_acks: HashMap<usize, EventStatus>,
+    /// The events whose messages are queued in the client, in order.
+    queued: VecDeque<usize>,
+    /// The events whose messages await their `PubAck`, by packet id.
+    in_flight: HashMap<u16, usize>,
+}
+
+impl PendingAcks {
+    fn new(acker: Acker, qos: MqttQos) -> Self {
+        Self {
+            acker,
+            qos,
+            seq_head: 0,
+            seq_tail: 0,
+            pending_acks: HashMap::new(),
+            queued: VecDeque::new(),
+            in_flight: HashMap::new(),
+        }
+    }
+
+    fn next_seq(&mut self) -> usize {
+        let seqno = self.seq_head;
+        self.seq_head += 1;
+        seqno
+    }
+
+    /// Whether every event was acknowledged.
+    fn is_empty(&self) -> bool {
+        self.seq_tail == self.seq_head
+    }
+
+    /// Records an event dropped before its message was queued.
+    fn dropped(&mut self, status: EventStatus) {
+        let seqno = self.next_seq();
+        self.ack_pending(seqno, status);
+    }
+
+    /// Records an event whose message is about to be queued in the client.
+    fn queue(&mut self) -> usize {
+        let seqno = self.next_seq();
+        self.queued.push_back(seqno);
+        seqno
+    }
+
+    /// Records that the message of the event couldn't be queued after all.
+    fn unqueue(&mut self, seqno: usize) {
+        if self.queued.back() == Some(&seqno) {
+            self.queued.pop_back();
+        }
+        self.ack_pending(seqno, EventStatus::Errored);
+    }
+
+    /// Records that the oldest queued message was sent with the packet id.
+    /// Messages sent again after a reconnection keep their packet id.
+    fn sent(&mut self, pkid: u16) {
+        if self.in_flight.contains_key(&pkid) {
+            return;
+        }
+        if let Some(seqno) = self.queued.pop_front() {
+            match self.qos {
+                MqttQos::AtMostOnce => self.ack_pending(seqno, EventStatus::Delivered),
+                MqttQos::AtLeastOnce => {
+                    self.in_flight.insert(pkid, seqno);
+                }
+            }
+        }
+    }
+
+    /// Records that the broker confirmed the message with the packet id.
+    fn confirmed(&mut self, pkid: u16) {
+        if let Some(seqno) = self.in_flight.remove(&pkid) {
+            self.ack_pending(seqno, EventStatus::Delivered);
+        }
+    }
+
+    fn ack_pending(&mut self, seqno: usize, status: EventStatus) {
+        self.pending_acks.insert(seqno, status);
+
+        // Consecutive events with the same status are acked together.
+        let mut num_to_ack = 0;
+        let mut ack_status = EventStatus::Delivered;
+        while let Some(status) = self.pending_acks.remove(&self.seq_tail) {
+            if num_to_ack > 0 && status != ack_status {
+                self.acker.ack_with_status(num_to_ack, ack_status);
+                num_to_ack = 0;
+            }
+            ack_status = status;
+            num_to_ack += 1;
+            self.seq_tail += 1
+        }
+        self.acker.ack_with_status(num_to_ack, ack_status);
+    }
+}
+
+#[async_trait]
+impl StreamSink for MqttSink {
+    async fn run(&mut self, mut input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let (client, mut eventloop) = AsyncClient::new(self.options.clone(), REQUESTS_CAPACITY);
+        let pending = Arc::new(Mutex::new(PendingAcks::new(self.acker.clone(), self.qos)));
+
+        // The event loop sends the queued publications to the broker and
+        // stops once the client is dropped at the end of the input.
+        let connection = {
+            let pending = Arc::clone(&pending);
+            tokio::spawn(async move {
+                loop {
+                    match eventloop.poll().await {
+                        Ok(MqttEvent::Outgoing(Outgoing::Publish(pkid))) => {
+                            pending.lock().unwrap().sent(pkid)
+                        }
+                        Ok(MqttEvent::Incoming(Packet::PubAck(ack))) => {
+                            pending.lock().unwrap().confirmed(ack.pkid)
+                        }
+                        Ok(_) => (),
+                        Err(rumqttc::ConnectionError::RequestsDone) => break,
+                        Err(error) => {
+                            emit!(MqttConnectionFailed { error });
+                            delay_for(Duration::from_secs(1)).await;
+                        }
+                    }
+                }
+            })
+        };
+
+        while let Some(event) = input.next().await {
+            let topic = match self.topic.render_string(&event) {
+                Ok(topic) => topic,
+                Err(error) => {
+                    emit!(TemplateRenderingFailed {
+                        error,
+                        field: Some("topic"),
+                        drop_event: true,
+                    });
+                    pending.lock().unwrap().dropped(EventStatus::Delivered);
+                    continue;
+                }
+            };
+
+            let message = match encoding::encode_event(event, &self.encoding, &self.framing) {
+                Some(message) => message,
+                None => {
+                    pending.lock().unwrap().dropped(EventStatus::Delivered);
+                    continue;
+                }
+            };
+            let message_len = message.len();
+
+            let seqno = pending.lock().unwrap().queue();
+            match client
+                .publish(topic, self.qos.into(), self.retain, message)
+                .await
+            {
+                Ok(()) => {
+                    emit!(MqttEventSendSuccess {
+                        byte_size: message_len,
+                    });
+                }
+                Err(error) => {
+                    emit!(MqttEventSendFail { error });
+                    pending.lock().unwrap().unqueue(seqno);
+                }
+            }
+        }
+
+        // Wait for the remaining messages to be sent, or confirmed by the
+        // broker, before stopping the event loop.
+        while !pending.lock().unwrap().is_empty() {
+            delay_for(Duration::from_millis(10)).await;
+        }
+
+        drop(client);
+        let _ = connection.await;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<MqttSinkConfig>();
+    }
+
+    #[test]
+    fn rejects_invalid_topic_template() {
+        let config: MqttSinkConfig = toml::from_str(
+            r#"host = "localhost"
+            client_id = "vector"
+            topic = "vector/{{ host"
+            encoding.codec = "text""#,
+        )
+        .unwrap();
+
+        assert!(MqttSink::new(config, Acker::Null).is_err());
+    }
+
+    #[test]
+    fn acks_confirmed_messages_in_order() {
+        let (acker, ack_counter) = Acker::new_for_testing();
+        let mut pending = PendingAcks::new(acker, MqttQos::AtLeastOnce);
+        let acked = || ack_counter.load(std::sync::atomic::Ordering::Relaxed);
+
+        pending.queue();
+        pending.queue();
+        pending.dropped(EventStatus::Delivered);
+        pending.queue();
+        pending.sent(1);
+        pending.sent(2);
+        assert_eq!(acked(), 0);
+
+        pending.confirmed(2);
+        assert_eq!(acked(), 0);
+        pending.confirmed(1);
+        assert_eq!(acked(), 3);
+
+        // Sending a message again after a reconnection keeps its packet id.
+        pending.sent(3);
+        pending.sent(3);
+        assert_eq!(acked(), 3);
+        pending.confirmed(3);
+        assert_eq!(acked(), 4);
+        assert!(pending.is_empty());
+    }
+
+    #[test]
+    fn acks_sent_messages_at_most_once() {
+        let (acker, ack_counter) = Acker::new_for_testing();
+        let mut pending = PendingAcks::new(acker, MqttQos::AtMostOnce);
+
+        pending.queue();
+        let seqno = pending.queue();
+        pending.unqueue(seqno);
+        assert_eq!(ack_counter.load(std::sync::atomic::Ordering::Relaxed), 0);
+
+        pending.sent(0);
+        assert_eq!(ack_counter.load(std::sync::atomic::Ordering::Relaxed), 2);
+        assert!(pending.is_empty());
+    }
+}
+
+#[cfg(feature = "mqtt-integration-tests")]
+#[cfg(test)]
+mod integration_tests {
+    use super::*;
+    use crate::test_util::{random_lines_with_stream, random_string, trace_init};
+    use rumqttc::{Event as MqttEvent, Packet, QoS};
+
+    #[tokio::test]
+    async fn mqtt_happy() {
+        // Publish `N` messages to the broker and verify with a separate
+        // subscriber that they were all received.
+
+        trace_init();
+
+        let topic = format!("test-{}", random_string(10));
+
+        let options = MqttOptions::new(
+            format!("vector-test-{}", random_string(10)),
+            "localhost",
+            1883,
+        );
+        let (consumer, mut eventloop) = AsyncClient::new(options, 10);
+        consumer.subscribe(&topic, QoS::AtLeastOnce).await.unwrap();
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            while let Ok(notification) = eventloop.poll().await {
+                if let MqttEvent::Incoming(Packet::Publish(publish)) = notification {
+                    let _ = tx.send(String::from_utf8_lossy(&publish.payload).to_string());
+                }
+            }
+        });
+        // Let the consumer subscribe before publishing.
+        delay_for(Duration::from_secs(1)).await;
+
+        let config: MqttSinkConfig = toml::from_str(&format!(
+            r#"host = "localhost"
+            client_id = "vector-sink-{}"
+            topic = "{}"
+            qos = "at_least_once"
+            encoding.codec = "text""#,
+            random_string(10),
+            topic
+        ))
+        .unwrap();
+
+        let (acker, ack_counter) = Acker::new_for_testing();
+        let mut sink = MqttSink::new(config, acker).unwrap();
+        let num_events = 100;
+        let (input, events) = random_lines_with_stream(100, num_events);
+
+        sink.run(Box::pin(events)).await.unwrap();
+
+        let mut output = Vec::new();
+        while output.len() < num_events {
+            let message = tokio::time::timeout(Duration::from_secs(5), rx.recv())
+                .await
+                .unwrap()
+                .unwrap();
+            output.push(message);
+        }
+
+        assert_eq!(output, input);
+        assert_eq!(
+            ack_counter.load(std::sync::atomic::Ordering::Relaxed),
+            num_events
+        );
+    }
+}
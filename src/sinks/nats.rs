@@ -4,7 +4,7 @@ use crate::{
     emit,
     internal_events::{NatsEventSendFail, NatsEventSendSuccess, TemplateRenderingFailed},
     sinks::util::{
-        encoding::{EncodingConfig, EncodingConfiguration},
+        encoding::{self, EncodingConfig, FramingConfig, StandardEncodings},
         StreamSink,
     },
     template::{Template, TemplateParseError},
@@ -28,7 +28,9 @@ enum BuildError {
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct NatsSinkConfig {
-    encoding: EncodingConfig<Encoding>,
+    encoding: EncodingConfig<StandardEncodings>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    framing: Option<FramingConfig>,
     #[serde(default = "default_name")]
     name: String,
     subject: String,
@@ -39,13 +41,6 @@ fn default_name() -> String {
     String::from("vector")
 }
 
-#[derive(Clone, Copy, Debug, Derivative, Deserialize, Serialize, Eq, PartialEq)]
-#[serde(rename_all = "snake_case")]
-pub enum Encoding {
-    Text,
-    Json,
-}
-
 inventory::submit! {
     SinkDescription::new::<NatsSinkConfig>("nats")
 }
@@ -115,7 +110,8 @@ struct NatsOptions {
 }
 
 pub struct NatsSink {
-    encoding: EncodingConfig<Encoding>,
+    encoding: EncodingConfig<StandardEncodings>,
+    framing: FramingConfig,
     options: NatsOptions,
     subject: Template,
     url: String,
@@ -127,6 +123,7 @@ impl NatsSink {
         Ok(NatsSink {
             options: (&config).into(),
             encoding: config.encoding,
+            framing: config.framing.unwrap_or(FramingConfig::Bytes),
             subject: Template::try_from(config.subject).context(SubjectTemplate)?,
             url: config.url,
             acker,
@@ -171,10 +168,16 @@ impl StreamSink for NatsSink {
                 }
             };
 
-            let log = encode_event(event, &self.encoding);
-            let message_len = log.len();
+            let message = match encoding::encode_event(event, &self.encoding, &self.framing) {
+                Some(message) => message,
+                None => {
+                    self.acker.ack(1);
+                    continue;
+                }
+            };
+            let message_len = message.len();
 
-            match nc.publish(&subject, log).await {
+            match nc.publish(&subject, message).await {
                 Ok(_) => {
                     emit!(NatsEventSendSuccess {
                         byte_size: message_len,
@@ -192,25 +195,16 @@ impl StreamSink for NatsSink {
     }
 }
 
-fn encode_event(mut event: Event, encoding: &EncodingConfig<Encoding>) -> String {
-    encoding.apply_rules(&mut event);
-
-    match encoding.codec() {
-        Encoding::Json => serde_json::to_string(event.as_log()).unwrap(),
-        Encoding::Text => event
-            .as_log()
-            .get(crate::config::log_schema().message_key())
-            .map(|v| v.to_string_lossy())
-            .unwrap_or_default(),
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
-    use super::{encode_event, Encoding, EncodingConfig};
     use crate::event::{Event, Value};
 
+    fn encode_event(event: Event, encoding: &EncodingConfig<StandardEncodings>) -> String {
+        let bytes = encoding::encode_event(event, encoding, &FramingConfig::Bytes).unwrap();
+        String::from_utf8(bytes).unwrap()
+    }
+
     #[test]
     fn generate_config() {
         crate::test_util::test_generate_config::<NatsSinkConfig>();
@@ -221,7 +215,7 @@ mod tests {
         let event = Event::from("foo");
         assert_eq!(
             "foo",
-            encode_event(event, &EncodingConfig::from(Encoding::Text))
+            encode_event(event, &EncodingConfig::from(StandardEncodings::Text))
         );
     }
 
@@ -233,7 +227,7 @@ mod tests {
         log.insert("z", Value::from(25));
         log.insert("a", Value::from("0"));
 
-        let encoded = encode_event(event, &EncodingConfig::from(Encoding::Json));
+        let encoded = encode_event(event, &EncodingConfig::from(StandardEncodings::Json));
         let expected = r#"{"a":"0","x":"23","z":25}"#;
         assert_eq!(encoded, expected);
     }
@@ -258,7 +252,8 @@ mod integration_tests {
         let subject = format!("test-{}", random_string(10));
 
         let cnf = NatsSinkConfig {
-            encoding: EncodingConfig::from(Encoding::Text),
+            encoding: EncodingConfig::from(StandardEncodings::Text),
+            framing: None,
             name: "".to_owned(),
             subject: subject.clone(),
             url: "nats://127.0.0.1:4222".to_owned(),
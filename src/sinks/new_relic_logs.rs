@@ -3,8 +3,9 @@ use crate::{
     sinks::{
         http::{HttpMethod, HttpSinkConfig},
         util::{
-            encoding::EncodingConfig, http::RequestConfig, BatchConfig, Compression, Concurrency,
-            TowerRequestConfig,
+            encoding::{EncodingConfig, ExtendedEncoding},
+            http::RequestConfig,
+            BatchConfig, Compression, Concurrency, TowerRequestConfig,
         },
     },
 };
@@ -69,10 +70,10 @@ pub enum Encoding {
     Json,
 }
 
-impl From<Encoding> for crate::sinks::http::Encoding {
-    fn from(v: Encoding) -> crate::sinks::http::Encoding {
+impl From<Encoding> for ExtendedEncoding<crate::sinks::http::Encoding> {
+    fn from(v: Encoding) -> ExtendedEncoding<crate::sinks::http::Encoding> {
         match v {
-            Encoding::Json => crate::sinks::http::Encoding::Json,
+            Encoding::Json => ExtendedEncoding::Extension(crate::sinks::http::Encoding::Json),
         }
     }
 }
@@ -154,6 +155,7 @@ impl NewRelicLogsConfig {
             headers: None,
             compression: self.compression,
             encoding: self.encoding.clone().into_encoding(),
+            framing: None,
 
             batch,
             request,
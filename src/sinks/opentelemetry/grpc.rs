@@ -0,0 +1,111 @@
+use super::{ExportRequest, Record, Signal};
+use crate::{
+    config::SinkContext,
+    event::Event,
+    proto::opentelemetry::proto::collector::{
+        logs::v1::logs_service_client::LogsServiceClient,
+        metrics::v1::{metrics_service_client::MetricsServiceClient, ExportMetricsServiceRequest},
+    },
+    sinks::{
+        util::{
+            grpc::{GrpcRetryLogic, HyperSvc},
+            BatchConfig, BatchSettings, PartitionBuffer, PartitionInnerBuffer,
+            TowerRequestSettings, VecBuffer,
+        },
+        Healthcheck, VectorSink,
+    },
+    tls::MaybeTlsSettings,
+};
+use futures::{future::BoxFuture, stream, FutureExt, SinkExt, StreamExt};
+use http::Uri;
+use std::task::{Context, Poll};
+use tonic::Status;
+use tower::Service;
+use tracing_futures::Instrument;
+
+pub(super) fn build(
+    endpoint: Uri,
+    tls: MaybeTlsSettings,
+    batch: BatchConfig,
+    request: TowerRequestSettings,
+    cx: SinkContext,
+) -> crate::Result<(VectorSink, Healthcheck)> {
+    let batch = BatchSettings::default()
+        .bytes(10_000_000)
+        .events(1000)
+        .timeout(1)
+        .parse_config(batch)?;
+
+    let client = HyperSvc::new(tls, endpoint)?;
+    let service = OpentelemetryService {
+        logs: LogsServiceClient::new(client.clone()),
+        metrics: MetricsServiceClient::new(client),
+    };
+    let healthcheck = healthcheck(service.metrics.clone()).boxed();
+
+    let sink = request
+        .partition_sink(
+            GrpcRetryLogic,
+            service,
+            PartitionBuffer::new(VecBuffer::new(batch.size)),
+            batch.timeout,
+            cx.acker(),
+        )
+        .sink_map_err(|error| error!(message = "Fatal opentelemetry sink error.", %error))
+        .with_flat_map(|event: Event| stream::iter(Some(Record::encode(event))).map(Ok));
+
+    Ok((VectorSink::Sink(Box::new(sink)), healthcheck))
+}
+
+/// Exports an empty batch of metrics, which collectors accept as a no-op.
+async fn healthcheck(mut client: MetricsServiceClient<HyperSvc>) -> crate::Result<()> {
+    client
+        .export(ExportMetricsServiceRequest::default())
+        .await
+        .map(|_| ())
+        .map_err(Into::into)
+}
+
+#[derive(Clone, Debug)]
+struct OpentelemetryService {
+    logs: LogsServiceClient<HyperSvc>,
+    metrics: MetricsServiceClient<HyperSvc>,
+}
+
+impl Service<PartitionInnerBuffer<Vec<Record>, Signal>> for OpentelemetryService {
+    type Response = ();
+    type Error = Status;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, buffer: PartitionInnerBuffer<Vec<Record>, Signal>) -> Self::Future {
+        let (records, signal) = buffer.into_parts();
+        debug!(message = "Sending events.", events = %records.len());
+
+        match ExportRequest::new(signal, records) {
+            ExportRequest::Logs(request) => {
+                let mut client = self.logs.clone();
+                Box::pin(async move {
+                    client
+                        .export(request)
+                        .instrument(info_span!("request"))
+                        .await
+                        .map(|_| ())
+                })
+            }
+            ExportRequest::Metrics(request) => {
+                let mut client = self.metrics.clone();
+                Box::pin(async move {
+                    client
+                        .export(request)
+                        .instrument(info_span!("request"))
+                        .await
+                        .map(|_| ())
+                })
+            }
+        }
+    }
+}
@@ -0,0 +1,177 @@
+use super::{ExportRequest, Record, Signal};
+use crate::{
+    config::SinkContext,
+    event::Event,
+    http::HttpClient,
+    sinks::{
+        util::{
+            buffer::compression::GZIP_DEFAULT,
+            http::{HttpSink, PartitionHttpSink},
+            BatchConfig, BatchSettings, Compression, PartitionBuffer, PartitionInnerBuffer,
+            TowerRequestSettings, VecBuffer,
+        },
+        Healthcheck, HealthcheckError, VectorSink,
+    },
+    tls::MaybeTlsSettings,
+};
+use flate2::write::GzEncoder;
+use futures::{FutureExt, SinkExt};
+use http::{
+    uri::{PathAndQuery, Uri},
+    Request, StatusCode,
+};
+use std::io::Write;
+
+pub(super) fn build(
+    endpoint: Uri,
+    tls: MaybeTlsSettings,
+    compression: Compression,
+    batch: BatchConfig,
+    request: TowerRequestSettings,
+    cx: SinkContext,
+) -> crate::Result<(VectorSink, Healthcheck)> {
+    let batch = BatchSettings::default()
+        .bytes(10_000_000)
+        .events(1000)
+        .timeout(1)
+        .parse_config(batch)?;
+    let client = HttpClient::new(tls)?;
+
+    let sink = OpentelemetryHttpSink {
+        logs_uri: with_path(&endpoint, "/v1/logs"),
+        metrics_uri: with_path(&endpoint, "/v1/metrics"),
+        compression,
+    };
+    let healthcheck = healthcheck(sink.clone(), client.clone()).boxed();
+
+    let sink = PartitionHttpSink::new(
+        sink,
+        PartitionBuffer::new(VecBuffer::new(batch.size)),
+        request,
+        batch.timeout,
+        client,
+        cx.acker(),
+    )
+    .sink_map_err(|error| error!(message = "Fatal opentelemetry sink error.", %error));
+
+    Ok((VectorSink::Sink(Box::new(sink)), healthcheck))
+}
+
+fn with_path(endpoint: &Uri, path: &'static str) -> Uri {
+    let mut parts = endpoint.clone().into_parts();
+    parts.path_and_query = Some(PathAndQuery::from_static(path));
+    Uri::from_parts(parts).expect("The path is a valid URI path.")
+}
+
+#[derive(Clone, Debug)]
+struct OpentelemetryHttpSink {
+    logs_uri: Uri,
+    metrics_uri: Uri,
+    compression: Compression,
+}
+
+#[async_trait::async_trait]
+impl HttpSink for OpentelemetryHttpSink {
+    type Input = PartitionInnerBuffer<Record, Signal>;
+    type Output = PartitionInnerBuffer<Vec<Record>, Signal>;
+
+    fn encode_event(&self, event: Event) -> Option<Self::Input> {
+        Some(Record::encode(event))
+    }
+
+    async fn build_request(&self, output: Self::Output) -> crate::Result<Request<Vec<u8>>> {
+        let (records, signal) = output.into_parts();
+        let uri = match signal {
+            Signal::Logs => self.logs_uri.clone(),
+            Signal::Metrics => self.metrics_uri.clone(),
+        };
+        let mut body = ExportRequest::new(signal, records).encode();
+
+        let mut builder = Request::post(uri).header("Content-Type", "application/x-protobuf");
+        if let Compression::Gzip(level) = self.compression {
+            builder = builder.header("Content-Encoding", "gzip");
+
+            let level = level.unwrap_or(GZIP_DEFAULT) as u32;
+            let mut encoder = GzEncoder::new(Vec::new(), flate2::Compression::new(level));
+            encoder
+                .write_all(&body)
+                .expect("Writing to Vec can't fail.");
+            body = encoder.finish().expect("Writing to Vec can't fail.");
+        }
+
+        builder.body(body).map_err(Into::into)
+    }
+}
+
+/// Exports an empty batch of metrics, which collectors accept as a no-op.
+async fn healthcheck(sink: OpentelemetryHttpSink, client: HttpClient) -> crate::Result<()> {
+    let request = sink
+        .build_request(PartitionInnerBuffer::new(Vec::new(), Signal::Metrics))
+        .await?
+        .map(hyper::Body::from);
+
+    let response = client.send(request).await?;
+
+    match response.status() {
+        StatusCode::OK => Ok(()),
+        status => Err(HealthcheckError::UnexpectedStatus { status }.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        config::SinkConfig,
+        event::{Metric, MetricKind, MetricValue},
+        proto::opentelemetry::proto::collector::{
+            logs::v1::ExportLogsServiceRequest, metrics::v1::ExportMetricsServiceRequest,
+        },
+        sinks::{opentelemetry::OpentelemetrySinkConfig, util::test::build_test_server},
+        test_util::next_addr,
+    };
+    use futures::{stream, StreamExt};
+    use prost::Message;
+
+    #[tokio::test]
+    async fn exports_logs_and_metrics() {
+        let addr = next_addr();
+        let config: OpentelemetrySinkConfig =
+            toml::from_str(&format!(r#"endpoint = "http://{}""#, addr)).unwrap();
+        let (sink, _) = config.build(SinkContext::new_test()).await.unwrap();
+
+        let (rx, trigger, server) = build_test_server(addr);
+        tokio::spawn(server);
+
+        let events = vec![
+            Event::from("first"),
+            Event::Metric(Metric::new(
+                "requests",
+                MetricKind::Incremental,
+                MetricValue::Counter { value: 1.0 },
+            )),
+            Event::from("second"),
+        ];
+        sink.run(stream::iter(events)).await.unwrap();
+        drop(trigger);
+
+        let mut requests = rx.collect::<Vec<_>>().await;
+        requests.sort_by_key(|(parts, _)| parts.uri.path().to_owned());
+        assert_eq!(requests.len(), 2);
+
+        let (parts, body) = &requests[0];
+        assert_eq!(parts.uri.path(), "/v1/logs");
+        assert_eq!(parts.headers["Content-Type"], "application/x-protobuf");
+        let request = ExportLogsServiceRequest::decode(body.clone()).unwrap();
+        assert_eq!(request.resource_logs.len(), 1);
+        assert_eq!(request.resource_logs[0].scope_logs[0].log_records.len(), 2);
+
+        let (parts, body) = &requests[1];
+        assert_eq!(parts.uri.path(), "/v1/metrics");
+        let request = ExportMetricsServiceRequest::decode(body.clone()).unwrap();
+        assert_eq!(
+            request.resource_metrics[0].scope_metrics[0].metrics[0].name,
+            "requests"
+        );
+    }
+}
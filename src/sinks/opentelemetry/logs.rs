@@ -0,0 +1,161 @@
+use super::{attribute, map_to_attributes, nanos, value_to_any};
+use crate::{
+    config::log_schema,
+    event::{LogEvent, Value},
+    proto::opentelemetry::proto::{
+        common::v1::InstrumentationScope,
+        logs::v1::{LogRecord, ResourceLogs, ScopeLogs},
+        resource::v1::Resource,
+    },
+};
+use chrono::Utc;
+use std::collections::BTreeMap;
+
+/// Maps the fields set by the `opentelemetry` source back to the log record,
+/// its resource and its scope. The other fields are added to the attributes
+/// of the record.
+pub(super) fn encode(mut log: LogEvent) -> ResourceLogs {
+    let mut record = LogRecord {
+        body: log.remove(log_schema().message_key()).map(value_to_any),
+        time_unix_nano: match log.remove(log_schema().timestamp_key()) {
+            Some(Value::Timestamp(timestamp)) => nanos(timestamp),
+            _ => nanos(Utc::now()),
+        },
+        ..Default::default()
+    };
+    log.remove(log_schema().source_type_key());
+
+    let mut resource = Resource::default();
+    let mut scope = InstrumentationScope::default();
+    for (key, value) in log {
+        match (key.as_str(), value) {
+            ("observed_timestamp", Value::Timestamp(timestamp)) => {
+                record.observed_time_unix_nano = nanos(timestamp)
+            }
+            ("attributes", Value::Map(map)) => record.attributes.extend(map_to_attributes(map)),
+            ("resources", Value::Map(map)) => resource.attributes = map_to_attributes(map),
+            ("scope", Value::Map(map)) => scope = scope_from_map(map),
+            ("severity_text", Value::Bytes(text)) => {
+                record.severity_text = String::from_utf8_lossy(&text).into_owned()
+            }
+            ("severity_number", Value::Integer(number)) => record.severity_number = number as i32,
+            ("flags", Value::Integer(flags)) => record.flags = flags as u32,
+            ("dropped_attributes_count", Value::Integer(count)) => {
+                record.dropped_attributes_count = count as u32
+            }
+            ("trace_id", Value::Bytes(id)) => {
+                record.trace_id = hex::decode(&id).unwrap_or_default()
+            }
+            ("span_id", Value::Bytes(id)) => record.span_id = hex::decode(&id).unwrap_or_default(),
+            (key, value) => record.attributes.push(attribute(key.to_owned(), value)),
+        }
+    }
+
+    ResourceLogs {
+        resource: Some(resource),
+        scope_logs: vec![ScopeLogs {
+            scope: Some(scope),
+            log_records: vec![record],
+            ..Default::default()
+        }],
+        ..Default::default()
+    }
+}
+
+/// Merges the resource into the list, along with the other records of the
+/// same resource and scope.
+pub(super) fn merge(merged: &mut Vec<ResourceLogs>, resource_logs: ResourceLogs) {
+    match merged
+        .iter_mut()
+        .find(|merged| merged.resource == resource_logs.resource)
+    {
+        Some(merged) => {
+            for scope_logs in resource_logs.scope_logs {
+                match merged
+                    .scope_logs
+                    .iter_mut()
+                    .find(|merged| merged.scope == scope_logs.scope)
+                {
+                    Some(merged) => merged.log_records.extend(scope_logs.log_records),
+                    None => merged.scope_logs.push(scope_logs),
+                }
+            }
+        }
+        None => merged.push(resource_logs),
+    }
+}
+
+fn scope_from_map(mut map: BTreeMap<String, Value>) -> InstrumentationScope {
+    InstrumentationScope {
+        name: map
+            .remove("name")
+            .map(|name| name.to_string_lossy())
+            .unwrap_or_default(),
+        version: map
+            .remove("version")
+            .map(|version| version.to_string_lossy())
+            .unwrap_or_default(),
+        attributes: match map.remove("attributes") {
+            Some(Value::Map(attributes)) => map_to_attributes(attributes),
+            _ => Vec::new(),
+        },
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::Event;
+
+    #[test]
+    fn maps_unknown_fields_to_attributes() {
+        let mut event = Event::from("Order placed.");
+        event
+            .as_mut_log()
+            .insert("resources.service\\.name", "checkout");
+        event.as_mut_log().insert("severity_text", "INFO");
+        event.as_mut_log().insert("order_id", 42);
+
+        let resource_logs = encode(event.into_log());
+
+        assert_eq!(
+            resource_logs.resource.unwrap().attributes,
+            vec![attribute("service.name".to_owned(), "checkout".into())]
+        );
+        let record = &resource_logs.scope_logs[0].log_records[0];
+        assert_eq!(record.body, Some(value_to_any("Order placed.".into())));
+        assert_eq!(record.severity_text, "INFO");
+        assert_eq!(
+            record.attributes,
+            vec![attribute("order_id".to_owned(), 42.into())]
+        );
+    }
+
+    #[cfg(feature = "sources-opentelemetry")]
+    #[test]
+    fn roundtrips_through_the_source() {
+        use crate::{
+            proto::opentelemetry::proto::collector::logs::v1::ExportLogsServiceRequest,
+            sources::opentelemetry::logs::decode,
+        };
+
+        let mut event = Event::from("Order placed.");
+        let log = event.as_mut_log();
+        log.insert("attributes.http\\.status_code", 200);
+        log.insert("resources.service\\.name", "checkout");
+        log.insert("scope.name", "io.vector");
+        log.insert("severity_text", "INFO");
+        log.insert("severity_number", 9);
+        log.insert("trace_id", "5b8efff798038103d269b633813fc60c");
+        log.insert("span_id", "eee19b7ec3c1b174");
+        log.insert(log_schema().source_type_key(), "opentelemetry");
+
+        let request = ExportLogsServiceRequest {
+            resource_logs: vec![encode(event.as_log().clone())],
+        };
+        let events = decode(request);
+
+        assert_eq!(events, vec![event]);
+    }
+}
@@ -0,0 +1,279 @@
+use super::{nanos, string_attribute};
+use crate::{
+    event::{Metric, MetricKind, MetricValue},
+    proto::opentelemetry::proto::{
+        common::v1::InstrumentationScope,
+        metrics::v1::{
+            self as proto, metric::Data, number_data_point, summary_data_point::ValueAtQuantile,
+            AggregationTemporality, Gauge, Histogram, HistogramDataPoint, NumberDataPoint,
+            ResourceMetrics, ScopeMetrics, Sum, Summary, SummaryDataPoint,
+        },
+        resource::v1::Resource,
+    },
+};
+use chrono::Utc;
+
+/// Maps the metric to a data point of its own resource, whose attributes are
+/// the tags of the metric. The `scope.name` and `scope.version` tags, and the
+/// tags prefixed by `scope.`, are set on the scope instead, while the
+/// `resource.` prefix is stripped from the tags, as set by the
+/// `opentelemetry` source.
+pub(super) fn encode(metric: Metric) -> ResourceMetrics {
+    let name = match metric.namespace() {
+        Some(namespace) => format!("{}.{}", namespace, metric.name()),
+        None => metric.name().to_owned(),
+    };
+    let (series, data, _) = metric.into_parts();
+
+    let mut resource = Resource::default();
+    let mut scope = InstrumentationScope::default();
+    for (key, value) in series.tags.unwrap_or_default() {
+        match key.as_str() {
+            "scope.name" => scope.name = value,
+            "scope.version" => scope.version = value,
+            _ => match key.strip_prefix("scope.") {
+                Some(key) => scope.attributes.push(string_attribute(key, value)),
+                None => resource.attributes.push(string_attribute(
+                    key.strip_prefix("resource.").unwrap_or(&key),
+                    value,
+                )),
+            },
+        }
+    }
+
+    let time_unix_nano = nanos(data.timestamp.unwrap_or_else(Utc::now));
+    let aggregation_temporality = match data.kind {
+        MetricKind::Incremental => AggregationTemporality::Delta,
+        MetricKind::Absolute => AggregationTemporality::Cumulative,
+    } as i32;
+    let number_point = |value: f64| NumberDataPoint {
+        time_unix_nano,
+        value: Some(number_data_point::Value::AsDouble(value)),
+        ..Default::default()
+    };
+
+    let data = match data.value {
+        MetricValue::Counter { value } => Data::Sum(Sum {
+            data_points: vec![number_point(value)],
+            aggregation_temporality,
+            is_monotonic: true,
+        }),
+        // Incremental gauges are sums that can decrease.
+        MetricValue::Gauge { value } if data.kind.is_incremental() => Data::Sum(Sum {
+            data_points: vec![number_point(value)],
+            aggregation_temporality,
+            is_monotonic: false,
+        }),
+        MetricValue::Gauge { value } => Data::Gauge(Gauge {
+            data_points: vec![number_point(value)],
+        }),
+        MetricValue::Set { values } => Data::Gauge(Gauge {
+            data_points: vec![number_point(values.len() as f64)],
+        }),
+        // The samples are aggregated to a histogram with a single bucket.
+        MetricValue::Distribution { samples, .. } => {
+            let count = samples.iter().map(|sample| sample.rate as u64).sum();
+            let sum = samples
+                .iter()
+                .map(|sample| sample.value * sample.rate as f64)
+                .sum();
+            Data::Histogram(Histogram {
+                data_points: vec![HistogramDataPoint {
+                    time_unix_nano,
+                    count,
+                    sum,
+                    bucket_counts: vec![count],
+                    ..Default::default()
+                }],
+                aggregation_temporality,
+            })
+        }
+        MetricValue::AggregatedHistogram {
+            buckets,
+            count,
+            sum,
+        } => {
+            // The last bucket holds the values above the last bound.
+            let overflow = count.saturating_sub(buckets.iter().map(|bucket| bucket.count).sum());
+            Data::Histogram(Histogram {
+                data_points: vec![HistogramDataPoint {
+                    time_unix_nano,
+                    count: count as u64,
+                    sum,
+                    explicit_bounds: buckets.iter().map(|bucket| bucket.upper_limit).collect(),
+                    bucket_counts: buckets
+                        .iter()
+                        .map(|bucket| bucket.count as u64)
+                        .chain(Some(overflow as u64))
+                        .collect(),
+                    ..Default::default()
+                }],
+                aggregation_temporality,
+            })
+        }
+        MetricValue::AggregatedSummary {
+            quantiles,
+            count,
+            sum,
+        } => Data::Summary(Summary {
+            data_points: vec![SummaryDataPoint {
+                time_unix_nano,
+                count: count as u64,
+                sum,
+                quantile_values: quantiles
+                    .into_iter()
+                    .map(|quantile| ValueAtQuantile {
+                        quantile: quantile.upper_limit,
+                        value: quantile.value,
+                    })
+                    .collect(),
+                ..Default::default()
+            }],
+        }),
+    };
+
+    ResourceMetrics {
+        resource: Some(resource),
+        scope_metrics: vec![ScopeMetrics {
+            scope: Some(scope),
+            metrics: vec![proto::Metric {
+                name,
+                data: Some(data),
+                ..Default::default()
+            }],
+            ..Default::default()
+        }],
+        ..Default::default()
+    }
+}
+
+/// Merges the resource into the list, along with the other metrics of the
+/// same resource and scope.
+pub(super) fn merge(merged: &mut Vec<ResourceMetrics>, resource_metrics: ResourceMetrics) {
+    match merged
+        .iter_mut()
+        .find(|merged| merged.resource == resource_metrics.resource)
+    {
+        Some(merged) => {
+            for scope_metrics in resource_metrics.scope_metrics {
+                match merged
+                    .scope_metrics
+                    .iter_mut()
+                    .find(|merged| merged.scope == scope_metrics.scope)
+                {
+                    Some(merged) => merged.metrics.extend(scope_metrics.metrics),
+                    None => merged.scope_metrics.push(scope_metrics),
+                }
+            }
+        }
+        None => merged.push(resource_metrics),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[cfg(feature = "sources-opentelemetry")]
+    fn roundtrip(metric: Metric) -> Metric {
+        use crate::{
+            proto::opentelemetry::proto::collector::metrics::v1::ExportMetricsServiceRequest,
+            sources::opentelemetry::metrics::decode,
+        };
+
+        let request = ExportMetricsServiceRequest {
+            resource_metrics: vec![encode(metric)],
+        };
+        let mut events = decode(request);
+        assert_eq!(events.len(), 1);
+        events.remove(0).into_metric()
+    }
+
+    fn metric(kind: MetricKind, value: MetricValue) -> Metric {
+        Metric::new("requests", kind, value)
+            .with_tags(Some(
+                vec![
+                    ("host".to_owned(), "web-1".to_owned()),
+                    ("scope.name".to_owned(), "io.vector".to_owned()),
+                ]
+                .into_iter()
+                .collect(),
+            ))
+            .with_timestamp(Some(Utc.ymd(2021, 3, 1).and_hms(12, 0, 0)))
+    }
+
+    #[test]
+    fn maps_tags_to_resource_attributes() {
+        let resource_metrics = encode(metric(
+            MetricKind::Absolute,
+            MetricValue::Gauge { value: 1.0 },
+        ));
+
+        let resource = resource_metrics.resource.unwrap();
+        assert_eq!(resource.attributes, vec![string_attribute("host", "web-1")]);
+        assert_eq!(
+            resource_metrics.scope_metrics[0]
+                .scope
+                .as_ref()
+                .unwrap()
+                .name,
+            "io.vector"
+        );
+    }
+
+    #[cfg(feature = "sources-opentelemetry")]
+    #[test]
+    fn roundtrips_through_the_source() {
+        for (kind, value) in vec![
+            (MetricKind::Incremental, MetricValue::Counter { value: 2.0 }),
+            (MetricKind::Absolute, MetricValue::Counter { value: 20.0 }),
+            (MetricKind::Absolute, MetricValue::Gauge { value: -1.5 }),
+            (
+                MetricKind::Absolute,
+                MetricValue::AggregatedHistogram {
+                    buckets: crate::buckets![1.0 => 1, 5.0 => 2],
+                    count: 4,
+                    sum: 12.0,
+                },
+            ),
+            (
+                MetricKind::Absolute,
+                MetricValue::AggregatedSummary {
+                    quantiles: crate::quantiles![0.5 => 8.0, 0.99 => 20.0],
+                    count: 10,
+                    sum: 100.0,
+                },
+            ),
+        ] {
+            let metric = metric(kind, value);
+            let mut expected = metric.clone();
+            expected.delete_tag("host");
+            expected.set_tag_value("resource.host".to_owned(), "web-1".to_owned());
+
+            assert_eq!(roundtrip(metric), expected);
+        }
+    }
+
+    #[cfg(feature = "sources-opentelemetry")]
+    #[test]
+    fn aggregates_distributions() {
+        let metric = roundtrip(metric(
+            MetricKind::Incremental,
+            MetricValue::Distribution {
+                samples: crate::samples![1.0 => 2, 4.0 => 1],
+                statistic: crate::event::metric::StatisticKind::Histogram,
+            },
+        ));
+
+        assert_eq!(metric.data.kind, MetricKind::Incremental);
+        assert_eq!(
+            metric.data.value,
+            MetricValue::AggregatedHistogram {
+                buckets: Vec::new(),
+                count: 3,
+                sum: 6.0,
+            }
+        );
+    }
+}
@@ -0,0 +1,391 @@
+use crate::{
+    config::{DataType, GenerateConfig, SinkConfig, SinkContext, SinkDescription},
+    event::{Event, Value},
+    proto::opentelemetry::proto::{
+        collector::{logs::v1::ExportLogsServiceRequest, metrics::v1::ExportMetricsServiceRequest},
+        common::v1::{any_value, AnyValue, ArrayValue, KeyValue, KeyValueList},
+        logs::v1::ResourceLogs,
+        metrics::v1::ResourceMetrics,
+    },
+    sinks::{
+        util::{
+            grpc::with_default_scheme, BatchConfig, Compression, EncodedLength,
+            PartitionInnerBuffer, TowerRequestConfig,
+        },
+        Healthcheck, VectorSink,
+    },
+    tls::{MaybeTlsSettings, TlsConfig},
+};
+use chrono::{DateTime, SecondsFormat, Utc};
+use lazy_static::lazy_static;
+use prost::Message;
+use serde::{Deserialize, Serialize};
+use snafu::Snafu;
+use std::{collections::BTreeMap, convert::TryFrom};
+
+mod grpc;
+mod http;
+mod logs;
+mod metrics;
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct OpentelemetrySinkConfig {
+    endpoint: String,
+    #[serde(default)]
+    protocol: Protocol,
+    #[serde(default)]
+    compression: Compression,
+    tls: Option<TlsConfig>,
+    #[serde(default)]
+    batch: BatchConfig,
+    #[serde(default)]
+    request: TowerRequestConfig,
+}
+
+/// OTLP/HTTP requests are sent to the `/v1/logs` and `/v1/metrics` paths of
+/// the endpoint, while OTLP/gRPC requests are sent to the services of the
+/// collector.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Protocol {
+    Http,
+    Grpc,
+}
+
+impl Default for Protocol {
+    fn default() -> Self {
+        Self::Http
+    }
+}
+
+lazy_static! {
+    static ref REQUEST_DEFAULTS: TowerRequestConfig = Default::default();
+}
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("Compression isn't supported by the gRPC protocol"))]
+    GrpcCompression,
+}
+
+inventory::submit! {
+    SinkDescription::new::<OpentelemetrySinkConfig>("opentelemetry")
+}
+
+impl GenerateConfig for OpentelemetrySinkConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(r#"endpoint = "http://127.0.0.1:4318""#).unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "opentelemetry")]
+impl SinkConfig for OpentelemetrySinkConfig {
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let tls = MaybeTlsSettings::from_config(&self.tls, false)?;
+        let endpoint = with_default_scheme(&self.endpoint, tls.is_tls())?;
+        let request = self.request.unwrap_with(&REQUEST_DEFAULTS);
+
+        match self.protocol {
+            Protocol::Http => http::build(endpoint, tls, self.compression, self.batch, request, cx),
+            Protocol::Grpc => {
+                if self.compression != Compression::None {
+                    return Err(BuildError::GrpcCompression.into());
+                }
+                grpc::build(endpoint, tls, self.batch, request, cx)
+            }
+        }
+    }
+
+    fn input_type(&self) -> DataType {
+        DataType::Any
+    }
+
+    fn sink_type(&self) -> &'static str {
+        "opentelemetry"
+    }
+}
+
+/// Events are batched separately by signal, as they're exported by separate
+/// requests.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+enum Signal {
+    Logs,
+    Metrics,
+}
+
+/// An event, mapped to its own OTLP resource.
+#[derive(Clone, Debug)]
+enum Record {
+    Logs(ResourceLogs),
+    Metrics(ResourceMetrics),
+}
+
+impl Record {
+    fn encode(event: Event) -> PartitionInnerBuffer<Self, Signal> {
+        match event {
+            Event::Log(log) => {
+                PartitionInnerBuffer::new(Self::Logs(logs::encode(log)), Signal::Logs)
+            }
+            Event::Metric(metric) => {
+                PartitionInnerBuffer::new(Self::Metrics(metrics::encode(metric)), Signal::Metrics)
+            }
+        }
+    }
+}
+
+impl EncodedLength for Record {
+    fn encoded_length(&self) -> usize {
+        match self {
+            Self::Logs(resource_logs) => resource_logs.encoded_len(),
+            Self::Metrics(resource_metrics) => resource_metrics.encoded_len(),
+        }
+    }
+}
+
+/// The export request of a batch, in which the records of the same resource
+/// and scope are merged.
+enum ExportRequest {
+    Logs(ExportLogsServiceRequest),
+    Metrics(ExportMetricsServiceRequest),
+}
+
+impl ExportRequest {
+    fn new(signal: Signal, records: Vec<Record>) -> Self {
+        match signal {
+            Signal::Logs => {
+                let mut request = ExportLogsServiceRequest::default();
+                for record in records {
+                    if let Record::Logs(resource_logs) = record {
+                        logs::merge(&mut request.resource_logs, resource_logs);
+                    }
+                }
+                Self::Logs(request)
+            }
+            Signal::Metrics => {
+                let mut request = ExportMetricsServiceRequest::default();
+                for record in records {
+                    if let Record::Metrics(resource_metrics) = record {
+                        metrics::merge(&mut request.resource_metrics, resource_metrics);
+                    }
+                }
+                Self::Metrics(request)
+            }
+        }
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        let mut body = Vec::new();
+        match self {
+            Self::Logs(request) => request.encode(&mut body),
+            Self::Metrics(request) => request.encode(&mut body),
+        }
+        .expect("Vec grows as needed.");
+        body
+    }
+}
+
+fn value_to_any(value: Value) -> AnyValue {
+    let value = match value {
+        Value::Bytes(bytes) => {
+            any_value::Value::StringValue(String::from_utf8_lossy(&bytes).into_owned())
+        }
+        Value::Integer(value) => any_value::Value::IntValue(value),
+        Value::Float(value) => any_value::Value::DoubleValue(value),
+        Value::Boolean(value) => any_value::Value::BoolValue(value),
+        Value::Timestamp(timestamp) => {
+            any_value::Value::StringValue(timestamp.to_rfc3339_opts(SecondsFormat::AutoSi, true))
+        }
+        Value::Map(map) => any_value::Value::KvlistValue(KeyValueList {
+            values: map_to_attributes(map),
+        }),
+        Value::Array(array) => any_value::Value::ArrayValue(ArrayValue {
+            values: array.into_iter().map(value_to_any).collect(),
+        }),
+        Value::Null => return AnyValue { value: None },
+    };
+    AnyValue { value: Some(value) }
+}
+
+fn attribute(key: String, value: Value) -> KeyValue {
+    KeyValue {
+        key,
+        value: Some(value_to_any(value)),
+    }
+}
+
+fn string_attribute(key: impl Into<String>, value: impl Into<String>) -> KeyValue {
+    KeyValue {
+        key: key.into(),
+        value: Some(AnyValue {
+            value: Some(any_value::Value::StringValue(value.into())),
+        }),
+    }
+}
+
+fn map_to_attributes(map: BTreeMap<String, Value>) -> Vec<KeyValue> {
+    map.into_iter()
+        .map(|(key, value)| attribute(key, value))
+        .collect()
+}
+
+/// OTLP timestamps are nanoseconds since the epoch.
+fn nanos(timestamp: DateTime<Utc>) -> u64 {
+    u64::try_from(timestamp.timestamp_nanos()).unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::{Metric, MetricKind, MetricValue};
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<OpentelemetrySinkConfig>();
+    }
+
+    #[test]
+    fn parses_protocols() {
+        let config: OpentelemetrySinkConfig =
+            toml::from_str(r#"endpoint = "localhost:4318""#).unwrap();
+        assert_eq!(config.protocol, Protocol::Http);
+
+        let config: OpentelemetrySinkConfig = toml::from_str(
+            r#"endpoint = "localhost:4317"
+            protocol = "grpc""#,
+        )
+        .unwrap();
+        assert_eq!(config.protocol, Protocol::Grpc);
+    }
+
+    #[test]
+    fn merges_records_of_the_same_resource() {
+        let gauge = |name: &str, host: &str| {
+            Event::Metric(
+                Metric::new(
+                    name,
+                    MetricKind::Absolute,
+                    MetricValue::Gauge { value: 1.0 },
+                )
+                .with_tags(Some(
+                    vec![("host".to_owned(), host.to_owned())]
+                        .into_iter()
+                        .collect(),
+                )),
+            )
+        };
+        let records = vec![
+            gauge("cpu", "web-1"),
+            gauge("memory", "web-2"),
+            gauge("disk", "web-1"),
+        ]
+        .into_iter()
+        .map(|event| Record::encode(event).into_parts().0)
+        .collect();
+
+        let request = match ExportRequest::new(Signal::Metrics, records) {
+            ExportRequest::Metrics(request) => request,
+            ExportRequest::Logs(_) => panic!("Expected a metrics request."),
+        };
+        let names = request
+            .resource_metrics
+            .iter()
+            .map(|resource_metrics| {
+                resource_metrics.scope_metrics[0]
+                    .metrics
+                    .iter()
+                    .map(|metric| metric.name.as_str())
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec![vec!["cpu", "disk"], vec!["memory"]]);
+    }
+
+    #[cfg(feature = "sources-opentelemetry")]
+    mod roundtrip {
+        use super::*;
+        use crate::{
+            config::{GlobalOptions, SourceConfig},
+            shutdown::ShutdownSignal,
+            sources::opentelemetry::OpentelemetryConfig,
+            test_util::{collect_ready, next_addr, wait_for_tcp},
+            Pipeline,
+        };
+        use futures::stream;
+        use tokio::time::{delay_for, Duration};
+
+        async fn roundtrip(protocol: &str) {
+            let (grpc, http) = (next_addr(), next_addr());
+            let (tx, rx) = Pipeline::new_test();
+
+            let source: OpentelemetryConfig = toml::from_str(&format!(
+                r#"grpc.address = "{}"
+                http.address = "{}""#,
+                grpc, http
+            ))
+            .unwrap();
+            let server = source
+                .build(
+                    "default",
+                    &GlobalOptions::default(),
+                    ShutdownSignal::noop(),
+                    tx,
+                )
+                .await
+                .unwrap();
+            tokio::spawn(server);
+            wait_for_tcp(grpc).await;
+            wait_for_tcp(http).await;
+
+            let endpoint = if protocol == "grpc" { grpc } else { http };
+            let sink: OpentelemetrySinkConfig = toml::from_str(&format!(
+                r#"endpoint = "{}"
+                protocol = "{}""#,
+                endpoint, protocol
+            ))
+            .unwrap();
+            let (sink, healthcheck) = sink.build(SinkContext::new_test()).await.unwrap();
+            healthcheck.await.unwrap();
+
+            let mut log = Event::from("roundtrip");
+            log.as_mut_log().insert(
+                crate::config::log_schema().source_type_key(),
+                "opentelemetry",
+            );
+            let metric = Event::Metric(
+                Metric::new(
+                    "requests",
+                    MetricKind::Incremental,
+                    MetricValue::Counter { value: 1.0 },
+                )
+                .with_timestamp(Some(Utc::now())),
+            );
+            sink.run(stream::iter(vec![log.clone(), metric]))
+                .await
+                .unwrap();
+
+            delay_for(Duration::from_millis(50)).await;
+
+            let mut output = collect_ready(rx).await;
+            output.sort_by_key(|event| matches!(event, Event::Metric(_)));
+            assert_eq!(output.len(), 2);
+            assert_eq!(output[0], log);
+            assert_eq!(output[1].as_metric().name(), "requests");
+            assert_eq!(
+                output[1].as_metric().data.value,
+                MetricValue::Counter { value: 1.0 }
+            );
+        }
+
+        #[tokio::test]
+        async fn http_works_with_opentelemetry_source() {
+            roundtrip("http").await;
+        }
+
+        #[tokio::test]
+        async fn grpc_works_with_opentelemetry_source() {
+            roundtrip("grpc").await;
+        }
+    }
+}
@@ -505,8 +505,8 @@ mod tests {
                 .collect(),
         ));
 
-        let m2 = Metric {
-            series: MetricSeries {
+        let m2 = Metric::from_parts(
+            MetricSeries {
                 tags: Some(
                     vec![("tag1".to_owned(), "value2".to_owned())]
                         .into_iter()
@@ -514,31 +514,31 @@ mod tests {
                 ),
                 ..m1.series.clone()
             },
-            data: m1.data.clone(),
-        };
+            m1.data.clone(),
+        );
 
         let metrics = vec![
-            Event::Metric(Metric {
-                series: m1.series.clone(),
-                data: MetricData {
+            Event::Metric(Metric::from_parts(
+                m1.series.clone(),
+                MetricData {
                     value: MetricValue::Counter { value: 32. },
                     ..m1.data.clone()
                 },
-            }),
-            Event::Metric(Metric {
-                series: m2.series.clone(),
-                data: MetricData {
+            )),
+            Event::Metric(Metric::from_parts(
+                m2.series.clone(),
+                MetricData {
                     value: MetricValue::Counter { value: 33. },
                     ..m2.data.clone()
                 },
-            }),
-            Event::Metric(Metric {
-                series: m1.series.clone(),
-                data: MetricData {
+            )),
+            Event::Metric(Metric::from_parts(
+                m1.series.clone(),
+                MetricData {
                     value: MetricValue::Counter { value: 40. },
                     ..m1.data.clone()
                 },
-            }),
+            )),
         ];
 
         sink.run(Box::pin(futures::stream::iter(metrics)))
@@ -1,9 +1,11 @@
 use crate::{
     buffers::Acker,
-    config::{log_schema, DataType, GenerateConfig, SinkConfig, SinkContext, SinkDescription},
+    config::{DataType, GenerateConfig, SinkConfig, SinkContext, SinkDescription},
     event::Event,
     internal_events::PulsarEncodeEventFailed,
-    sinks::util::encoding::{EncodingConfig, EncodingConfiguration},
+    sinks::util::encoding::{
+        EncodingConfig, EncodingConfiguration, ExtendedEncoding, FramingConfig, StandardEncodings,
+    },
 };
 use futures::{future::BoxFuture, ready, stream::FuturesUnordered, FutureExt, Sink, Stream};
 use pulsar::{
@@ -30,7 +32,9 @@ pub struct PulsarSinkConfig {
     #[serde(alias = "address")]
     endpoint: String,
     topic: String,
-    encoding: EncodingConfig<Encoding>,
+    encoding: EncodingConfig<ExtendedEncoding<Encoding>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    framing: Option<FramingConfig>,
     auth: Option<AuthConfig>,
 }
 
@@ -40,11 +44,10 @@ pub struct AuthConfig {
     token: String, // <jwt token>
 }
 
+/// The encodings specific to this sink, on top of the standard ones.
 #[derive(Clone, Copy, Debug, Derivative, Deserialize, Serialize, Eq, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub enum Encoding {
-    Text,
-    Json,
     Avro,
 }
 
@@ -58,7 +61,8 @@ enum PulsarSinkState {
 }
 
 struct PulsarSink {
-    encoding: EncodingConfig<Encoding>,
+    encoding: EncodingConfig<ExtendedEncoding<Encoding>>,
+    framing: FramingConfig,
     avro_schema: Option<avro_rs::Schema>,
     state: PulsarSinkState,
     in_flight:
@@ -79,7 +83,8 @@ impl GenerateConfig for PulsarSinkConfig {
         toml::Value::try_from(Self {
             endpoint: "pulsar://127.0.0.1:6650".to_string(),
             topic: "topic-1234".to_string(),
-            encoding: Encoding::Text.into(),
+            encoding: StandardEncodings::Text.into(),
+            framing: None,
             auth: None,
         })
         .unwrap()
@@ -97,7 +102,12 @@ impl SinkConfig for PulsarSinkConfig {
             .create_pulsar_producer()
             .await
             .context(CreatePulsarSink)?;
-        let sink = PulsarSink::new(producer, self.encoding.clone(), cx.acker())?;
+        let sink = PulsarSink::new(
+            producer,
+            self.encoding.clone(),
+            self.framing.clone(),
+            cx.acker(),
+        )?;
 
         let producer = self
             .create_pulsar_producer()
@@ -156,11 +166,12 @@ async fn healthcheck(producer: PulsarProducer) -> crate::Result<()> {
 impl PulsarSink {
     fn new(
         producer: PulsarProducer,
-        encoding: EncodingConfig<Encoding>,
+        encoding: EncodingConfig<ExtendedEncoding<Encoding>>,
+        framing: Option<FramingConfig>,
         acker: Acker,
     ) -> crate::Result<Self> {
         let schema = match &encoding.codec() {
-            Encoding::Avro => {
+            ExtendedEncoding::Extension(Encoding::Avro) => {
                 if let Some(schema) = &encoding.schema() {
                     avro_rs::Schema::parse_str(schema).ok()
                 } else {
@@ -175,6 +186,7 @@ impl PulsarSink {
 
         Ok(Self {
             encoding,
+            framing: framing.unwrap_or(FramingConfig::Bytes),
             avro_schema: schema,
             state: PulsarSinkState::Ready(Box::new(producer)),
             in_flight: FuturesUnordered::new(),
@@ -204,6 +216,17 @@ impl PulsarSink {
 
         Poll::Ready(())
     }
+
+    fn ack_pending(&mut self, seqno: usize) {
+        self.pending_acks.insert(seqno);
+
+        let mut num_to_ack = 0;
+        while self.pending_acks.remove(&self.seq_tail) {
+            num_to_ack += 1;
+            self.seq_tail += 1
+        }
+        self.acker.ack(num_to_ack);
+    }
 }
 
 impl Sink<Event> for PulsarSink {
@@ -220,11 +243,19 @@ impl Sink<Event> for PulsarSink {
             "Expected `poll_ready` to be called first."
         );
 
-        let message = encode_event(item, &self.encoding, &self.avro_schema).map_err(|e| {
-            emit!(PulsarEncodeEventFailed {
-                error: &*e.to_string()
-            })
-        })?;
+        let message = match encode_event(item, &self.encoding, &self.framing, &self.avro_schema) {
+            Ok(message) => message,
+            Err(error) => {
+                emit!(PulsarEncodeEventFailed {
+                    error: &*error.to_string()
+                });
+                // The event is dropped, but it is acknowledged in order.
+                let seqno = self.seq_head;
+                self.seq_head += 1;
+                self.ack_pending(seqno);
+                return Ok(());
+            }
+        };
 
         let mut producer = match std::mem::replace(&mut self.state, PulsarSinkState::None) {
             PulsarSinkState::Ready(producer) => producer,
@@ -256,14 +287,7 @@ impl Sink<Event> for PulsarSink {
                         sequence_id = %result.sequence_id,
                     );
 
-                    this.pending_acks.insert(seqno);
-
-                    let mut num_to_ack = 0;
-                    while this.pending_acks.remove(&this.seq_tail) {
-                        num_to_ack += 1;
-                        this.seq_tail += 1
-                    }
-                    this.acker.ack(num_to_ack);
+                    this.ack_pending(seqno);
                 }
                 Some((_, Err(error))) => {
                     error!(message = "Pulsar sink generated an error.", %error);
@@ -283,20 +307,16 @@ impl Sink<Event> for PulsarSink {
 
 fn encode_event(
     mut item: Event,
-    encoding: &EncodingConfig<Encoding>,
+    encoding: &EncodingConfig<ExtendedEncoding<Encoding>>,
+    framing: &FramingConfig,
     avro_schema: &Option<avro_rs::Schema>,
 ) -> crate::Result<Vec<u8>> {
     encoding.apply_rules(&mut item);
-    let log = item.into_log();
-
-    Ok(match encoding.codec() {
-        Encoding::Json => serde_json::to_vec(&log)?,
-        Encoding::Text => log
-            .get(log_schema().message_key())
-            .map(|v| v.as_bytes().to_vec())
-            .unwrap_or_default(),
-        Encoding::Avro => {
-            let value = avro_rs::to_value(log)?;
+
+    let bytes = match encoding.codec() {
+        ExtendedEncoding::Standard(codec) => codec.encode(item)?,
+        ExtendedEncoding::Extension(Encoding::Avro) => {
+            let value = avro_rs::to_value(item.into_log())?;
             let resolved_value =
                 avro_rs::types::Value::resolve(value, avro_schema.as_ref().unwrap())?;
             avro_rs::to_avro_datum(
@@ -306,12 +326,15 @@ fn encode_event(
                 resolved_value,
             )?
         }
-    })
+    };
+
+    Ok(framing.frame(bytes))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::log_schema;
     use std::collections::HashMap;
 
     #[test]
@@ -324,7 +347,13 @@ mod tests {
         let msg = "hello_world".to_owned();
         let mut evt = Event::from(msg.clone());
         evt.as_mut_log().insert("key", "value");
-        let result = encode_event(evt, &EncodingConfig::from(Encoding::Json), &None).unwrap();
+        let result = encode_event(
+            evt,
+            &EncodingConfig::from(StandardEncodings::Json),
+            &FramingConfig::Bytes,
+            &None,
+        )
+        .unwrap();
         let map: HashMap<String, String> = serde_json::from_slice(&result[..]).unwrap();
         assert_eq!(msg, map[&log_schema().message_key().to_string()]);
     }
@@ -333,7 +362,13 @@ mod tests {
     fn pulsar_event_text() {
         let msg = "hello_world".to_owned();
         let evt = Event::from(msg.clone());
-        let event = encode_event(evt, &EncodingConfig::from(Encoding::Text), &None).unwrap();
+        let event = encode_event(
+            evt,
+            &EncodingConfig::from(StandardEncodings::Text),
+            &FramingConfig::Bytes,
+            &None,
+        )
+        .unwrap();
 
         assert_eq!(&event[..], msg.as_bytes());
     }
@@ -353,10 +388,16 @@ mod tests {
         let msg = "hello_world".to_owned();
         let mut evt = Event::from(msg);
         evt.as_mut_log().insert("key", "value");
-        let mut encoding = EncodingConfig::from(Encoding::Avro);
+        let mut encoding = EncodingConfig::from(ExtendedEncoding::Extension(Encoding::Avro));
         encoding.schema = Some(raw_schema.to_string());
         let schema = avro_rs::Schema::parse_str(&raw_schema).unwrap();
-        let result = encode_event(evt.clone(), &encoding, &Some(schema.clone())).unwrap();
+        let result = encode_event(
+            evt.clone(),
+            &encoding,
+            &FramingConfig::Bytes,
+            &Some(schema.clone()),
+        )
+        .unwrap();
 
         let value = avro_rs::to_value(evt.into_log()).unwrap();
         let resolved_value = avro_rs::types::Value::resolve(value, &schema).unwrap();
@@ -375,12 +416,13 @@ mod tests {
         let event = encode_event(
             evt,
             &EncodingConfig {
-                codec: Encoding::Json,
+                codec: StandardEncodings::Json.into(),
                 schema: None,
                 only_fields: None,
                 except_fields: Some(vec!["key".into()]),
                 timestamp_format: None,
             },
+            &FramingConfig::Bytes,
             &None,
         )
         .unwrap();
@@ -409,7 +451,8 @@ mod integration_tests {
         let cnf = PulsarSinkConfig {
             endpoint: "pulsar://127.0.0.1:6650".to_owned(),
             topic: topic.clone(),
-            encoding: Encoding::Text.into(),
+            encoding: StandardEncodings::Text.into(),
+            framing: None,
             auth: None,
         };
 
@@ -429,7 +472,7 @@ mod integration_tests {
 
         let (acker, ack_counter) = Acker::new_for_testing();
         let producer = cnf.create_pulsar_producer().await.unwrap();
-        let sink = PulsarSink::new(producer, cnf.encoding, acker).unwrap();
+        let sink = PulsarSink::new(producer, cnf.encoding, cnf.framing, acker).unwrap();
         events.map(Ok).forward(sink).await.unwrap();
 
         assert_eq!(
@@ -2,10 +2,19 @@
 use crate::sinks::util::unix::UnixSinkConfig;
 use crate::{
     config::{DataType, GenerateConfig, SinkConfig, SinkContext, SinkDescription},
+    event::Event,
+    gelf,
+    internal_events::{EncoderEncodeFailed, GelfEncodeFailed},
     sinks::util::{
-        encode_event, encoding::EncodingConfig, tcp::TcpSinkConfig, udp::UdpSinkConfig, Encoding,
+        encoding::{
+            EncodingConfig, EncodingConfiguration, ExtendedEncoding, FramingConfig,
+            StandardEncodings,
+        },
+        tcp::TcpSinkConfig,
+        udp::UdpSinkConfig,
     },
 };
+use bytes::Bytes;
 use serde::{Deserialize, Serialize};
 
 #[derive(Deserialize, Serialize, Debug)]
@@ -14,7 +23,9 @@ use serde::{Deserialize, Serialize};
 pub struct SocketSinkConfig {
     #[serde(flatten)]
     pub mode: Mode,
-    pub encoding: EncodingConfig<Encoding>,
+    pub encoding: EncodingConfig<ExtendedEncoding<Encoding>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub framing: Option<FramingConfig>,
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -26,6 +37,13 @@ pub enum Mode {
     Unix(UnixSinkConfig),
 }
 
+/// The encodings specific to this sink, on top of the standard ones.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Encoding {
+    Gelf,
+}
+
 inventory::submit! {
     SinkDescription::new::<SocketSinkConfig>("socket")
 }
@@ -42,14 +60,18 @@ impl GenerateConfig for SocketSinkConfig {
 }
 
 impl SocketSinkConfig {
-    pub fn new(mode: Mode, encoding: EncodingConfig<Encoding>) -> Self {
-        SocketSinkConfig { mode, encoding }
+    pub fn new(mode: Mode, encoding: EncodingConfig<ExtendedEncoding<Encoding>>) -> Self {
+        SocketSinkConfig {
+            mode,
+            encoding,
+            framing: None,
+        }
     }
 
     pub fn make_basic_tcp_config(address: String) -> Self {
         Self::new(
             Mode::Tcp(TcpSinkConfig::from_address(address)),
-            EncodingConfig::from(Encoding::Text),
+            EncodingConfig::from(StandardEncodings::Text),
         )
     }
 }
@@ -62,7 +84,18 @@ impl SinkConfig for SocketSinkConfig {
         cx: SinkContext,
     ) -> crate::Result<(super::VectorSink, super::Healthcheck)> {
         let encoding = self.encoding.clone();
-        let encode_event = move |event| encode_event(event, &encoding);
+        let framing = match (&self.mode, encoding.codec()) {
+            // Each datagram holds a single message, which doesn't need delimiting.
+            (Mode::Udp(_), ExtendedEncoding::Extension(Encoding::Gelf)) => FramingConfig::Bytes,
+            (_, ExtendedEncoding::Extension(Encoding::Gelf)) => {
+                FramingConfig::CharacterDelimited { delimiter: b'\0' }
+            }
+            _ => self
+                .framing
+                .clone()
+                .unwrap_or(FramingConfig::NewlineDelimited),
+        };
+        let encode_event = move |event| encode_event(event, &encoding, &framing);
         match &self.mode {
             Mode::Tcp(config) => config.build(cx, encode_event),
             Mode::Udp(config) => config.build(cx, encode_event),
@@ -80,6 +113,25 @@ impl SinkConfig for SocketSinkConfig {
     }
 }
 
+fn encode_event(
+    mut event: Event,
+    encoding: &EncodingConfig<ExtendedEncoding<Encoding>>,
+    framing: &FramingConfig,
+) -> Option<Bytes> {
+    encoding.apply_rules(&mut event);
+
+    let bytes = match encoding.codec() {
+        ExtendedEncoding::Standard(codec) => codec
+            .encode(event)
+            .map_err(|error| emit!(EncoderEncodeFailed { error: &error }))
+            .ok()?,
+        ExtendedEncoding::Extension(Encoding::Gelf) => gelf::encode(event.into_log())
+            .map_err(|error| emit!(GelfEncodeFailed { error: &error }))
+            .ok()?,
+    };
+    Some(framing.frame(bytes).into())
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -110,7 +162,8 @@ mod test {
 
         let config = SocketSinkConfig {
             mode: Mode::Udp(UdpSinkConfig::from_address(addr.to_string())),
-            encoding: Encoding::Json.into(),
+            encoding: StandardEncodings::Json.into(),
+            framing: None,
         };
         let context = SinkContext::new_test();
         let (sink, _healthcheck) = config.build(context).await.unwrap();
@@ -145,6 +198,39 @@ mod test {
         test_udp(next_addr_v6()).await;
     }
 
+    #[tokio::test]
+    async fn udp_gelf() {
+        trace_init();
+
+        let addr = next_addr();
+        let receiver = UdpSocket::bind(addr).unwrap();
+
+        let config = SocketSinkConfig {
+            mode: Mode::Udp(UdpSinkConfig::from_address(addr.to_string())),
+            encoding: ExtendedEncoding::Extension(Encoding::Gelf).into(),
+            framing: None,
+        };
+        let context = SinkContext::new_test();
+        let (sink, _healthcheck) = config.build(context).await.unwrap();
+
+        let mut event = Event::from("raw log line");
+        event.as_mut_log().insert("host", "example.org");
+        event.as_mut_log().insert("user_id", 9001);
+        sink.run(stream::once(ready(event))).await.unwrap();
+
+        let mut buf = [0; 256];
+        let (size, _src_addr) = receiver
+            .recv_from(&mut buf)
+            .expect("Did not receive message");
+
+        let data = serde_json::from_slice::<Value>(&buf[..size]).expect("Invalid JSON received");
+        assert_eq!(data["version"], "1.1");
+        assert_eq!(data["host"], "example.org");
+        assert_eq!(data["short_message"], "raw log line");
+        assert_eq!(data["_user_id"], 9001);
+        assert!(data["timestamp"].is_f64());
+    }
+
     #[tokio::test]
     async fn tcp_stream() {
         trace_init();
@@ -152,7 +238,8 @@ mod test {
         let addr = next_addr();
         let config = SocketSinkConfig {
             mode: Mode::Tcp(TcpSinkConfig::from_address(addr.to_string())),
-            encoding: Encoding::Json.into(),
+            encoding: StandardEncodings::Json.into(),
+            framing: None,
         };
 
         let context = SinkContext::new_test();
@@ -224,7 +311,8 @@ mod test {
                 }),
                 None,
             )),
-            encoding: Encoding::Text.into(),
+            encoding: StandardEncodings::Text.into(),
+            framing: None,
         };
         let context = SinkContext::new_test();
         let (sink, _healthcheck) = config.build(context).await.unwrap();
@@ -331,7 +419,8 @@ mod test {
         let addr = next_addr();
         let config = SocketSinkConfig {
             mode: Mode::Tcp(TcpSinkConfig::from_address(addr.to_string())),
-            encoding: Encoding::Text.into(),
+            encoding: StandardEncodings::Text.into(),
+            framing: None,
         };
 
         let context = SinkContext::new_test();
@@ -8,7 +8,7 @@ use std::{
     collections::HashSet,
     hash::{Hash, Hasher},
     marker::PhantomData,
-    mem::discriminant,
+    mem::{self, discriminant},
     ops::{Deref, DerefMut},
 };
 
@@ -144,22 +144,7 @@ impl Batch for MetricsBuffer {
         if self.num_items() >= self.max_events {
             PushResult::Overflow(item)
         } else {
-            let item = item.into_metric();
-            let new_entry = match item.data.kind {
-                MetricKind::Absolute => MetricEntry(item),
-                MetricKind::Incremental => {
-                    // Incremental metrics update existing entries, if present
-                    let entry = MetricEntry(item);
-                    self.metrics
-                        .take(&entry)
-                        .map(|mut existing| {
-                            existing.data.update(&entry.data);
-                            existing
-                        })
-                        .unwrap_or(entry)
-                }
-            };
-            self.metrics.replace(new_entry);
+            self.metrics.insert_update(item.into_metric());
             PushResult::Ok(self.num_items() >= self.max_events)
         }
     }
@@ -173,7 +158,7 @@ impl Batch for MetricsBuffer {
     }
 
     fn finish(self) -> Self::Output {
-        self.metrics.0.into_iter().map(finish_metric).collect()
+        self.metrics.into_metrics()
     }
 
     fn num_items(&self) -> usize {
@@ -249,6 +234,34 @@ impl MetricSet {
         Self(HashSet::with_capacity(capacity))
     }
 
+    /// Insert the metric into the set. Incremental metrics are added to
+    /// any existing entry of the same series, while absolute metrics
+    /// replace it.
+    pub fn insert_update(&mut self, metric: Metric) {
+        let mut entry = MetricEntry(metric);
+        if let Some(mut existing) = self.0.take(&entry) {
+            match entry.data.kind {
+                MetricKind::Absolute => {
+                    let metadata = mem::take(existing.metadata_mut());
+                    entry.metadata_mut().merge(metadata);
+                }
+                MetricKind::Incremental => {
+                    existing.data.update(&entry.data);
+                    let metadata = mem::take(entry.metadata_mut());
+                    existing.metadata_mut().merge(metadata);
+                    entry = existing;
+                }
+            }
+        }
+        self.0.insert(entry);
+    }
+
+    /// Consume the set, returning the contained metrics with the samples
+    /// of distributions compressed.
+    pub fn into_metrics(self) -> Vec<Metric> {
+        self.0.into_iter().map(finish_metric).collect()
+    }
+
     /// Either pass the metric through as-is if absolute, or convert it
     /// to absolute if incremental.
     pub fn make_absolute(&mut self, metric: Metric) -> Option<Metric> {
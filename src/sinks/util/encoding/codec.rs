@@ -0,0 +1,411 @@
+use crate::{
+    config::log_schema,
+    event::{proto, Event, LogEvent, Value},
+    internal_events::EncoderEncodeFailed,
+    sinks::util::encoding::{EncodingConfig, EncodingConfiguration},
+};
+use prost::Message as _;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use snafu::{ResultExt, Snafu};
+
+#[derive(Debug, Snafu)]
+pub enum EncodingError {
+    #[snafu(display("Missing message field"))]
+    MissingMessage,
+    #[snafu(display("Message field isn't made of bytes"))]
+    MessageNotBytes,
+    #[snafu(display("The {} codec only encodes logs", codec))]
+    NotALog { codec: &'static str },
+    #[snafu(display("Unable to encode JSON: {}", source))]
+    Json { source: serde_json::Error },
+    #[snafu(display("Unable to encode native event: {}", source))]
+    Protobuf { source: prost::EncodeError },
+}
+
+/// The encodings shared by the sinks sending events one at a time.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum StandardEncodings {
+    /// The `message` field of logs, converted to UTF-8 text, or the text
+    /// representation of metrics.
+    Text,
+    /// Events serialized as JSON objects.
+    #[serde(alias = "ndjson")]
+    Json,
+    /// The fields of logs as `key=value` pairs.
+    Logfmt,
+    /// The given fields of logs as a CSV record.
+    Csv { fields: Vec<String> },
+    /// Events encoded with Vector's native protobuf format.
+    NativeProtobuf,
+    /// The `message` field of logs, as is.
+    RawMessage,
+}
+
+impl StandardEncodings {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Text => "text",
+            Self::Json => "json",
+            Self::Logfmt => "logfmt",
+            Self::Csv { .. } => "csv",
+            Self::NativeProtobuf => "native_protobuf",
+            Self::RawMessage => "raw_message",
+        }
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Self::Text | Self::Logfmt | Self::RawMessage => "text/plain",
+            Self::Json => "application/x-ndjson",
+            Self::Csv { .. } => "text/csv",
+            Self::NativeProtobuf => "application/octet-stream",
+        }
+    }
+
+    /// Encodes an event, without framing it.
+    pub fn encode(&self, event: Event) -> Result<Vec<u8>, EncodingError> {
+        match (self, event) {
+            (Self::Json, Event::Log(log)) => serde_json::to_vec(&log).context(Json),
+            (Self::Json, Event::Metric(metric)) => serde_json::to_vec(&metric).context(Json),
+            (Self::Text, Event::Log(log)) => log
+                .get(log_schema().message_key())
+                .map(|message| message.to_string_lossy().into_bytes())
+                .ok_or(EncodingError::MissingMessage),
+            (Self::Text, Event::Metric(metric)) => Ok(metric.to_string().into_bytes()),
+            (Self::NativeProtobuf, event) => {
+                let event = proto::EventWrapper::from(event);
+                let mut bytes = Vec::with_capacity(event.encoded_len());
+                event.encode(&mut bytes).context(Protobuf)?;
+                Ok(bytes)
+            }
+            (Self::Logfmt, Event::Log(log)) => Ok(encode_logfmt(&log)),
+            (Self::Csv { fields }, Event::Log(log)) => Ok(encode_csv(&log, fields)),
+            (Self::RawMessage, Event::Log(log)) => match log.get(log_schema().message_key()) {
+                Some(Value::Bytes(bytes)) => Ok(bytes.to_vec()),
+                Some(_) => Err(EncodingError::MessageNotBytes),
+                None => Err(EncodingError::MissingMessage),
+            },
+            (codec, Event::Metric(_)) => Err(EncodingError::NotALog {
+                codec: codec.name(),
+            }),
+        }
+    }
+}
+
+fn encode_logfmt(log: &LogEvent) -> Vec<u8> {
+    let pairs = log
+        .all_fields()
+        .map(|(key, value)| {
+            format!(
+                "{}={}",
+                quote_logfmt(&key),
+                quote_logfmt(&value.to_string_lossy())
+            )
+        })
+        .collect::<Vec<_>>();
+    pairs.join(" ").into_bytes()
+}
+
+fn quote_logfmt(text: &str) -> String {
+    if !text.is_empty() && !text.contains(|c: char| c == ' ' || c == '=' || c == '"') {
+        return text.to_owned();
+    }
+    format!("{:?}", text)
+}
+
+fn encode_csv(log: &LogEvent, fields: &[String]) -> Vec<u8> {
+    let record = fields
+        .iter()
+        .map(|field| {
+            let value = log
+                .get(field)
+                .map(Value::to_string_lossy)
+                .unwrap_or_default();
+            if value.contains(|c: char| c == ',' || c == '"' || c == '\n' || c == '\r') {
+                format!("\"{}\"", value.replace('"', "\"\""))
+            } else {
+                value
+            }
+        })
+        .collect::<Vec<_>>();
+    record.join(",").into_bytes()
+}
+
+/// The encodings of a sink, which are either specific to it or standard.
+///
+/// The encodings of the sink take precedence when their names clash with
+/// the standard ones.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum ExtendedEncoding<E> {
+    Extension(E),
+    Standard(StandardEncodings),
+}
+
+impl<E> From<StandardEncodings> for ExtendedEncoding<E> {
+    fn from(encoding: StandardEncodings) -> Self {
+        Self::Standard(encoding)
+    }
+}
+
+impl<E> From<StandardEncodings> for EncodingConfig<ExtendedEncoding<E>> {
+    fn from(encoding: StandardEncodings) -> Self {
+        ExtendedEncoding::Standard(encoding).into()
+    }
+}
+
+/// How the encoded events are delimited from one another.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(tag = "method", rename_all = "snake_case")]
+pub enum FramingConfig {
+    /// Events aren't delimited, as when each is sent in its own message.
+    Bytes,
+    /// Events are followed by a newline.
+    NewlineDelimited,
+    /// Events are followed by a single byte character.
+    CharacterDelimited {
+        #[serde(with = "delimiter")]
+        delimiter: u8,
+    },
+    /// Events are prefixed by their length, as a big endian 32 bits integer.
+    LengthDelimited,
+}
+
+impl FramingConfig {
+    pub fn frame(&self, mut bytes: Vec<u8>) -> Vec<u8> {
+        match self {
+            Self::Bytes => bytes,
+            Self::NewlineDelimited => {
+                bytes.push(b'\n');
+                bytes
+            }
+            Self::CharacterDelimited { delimiter } => {
+                bytes.push(*delimiter);
+                bytes
+            }
+            Self::LengthDelimited => {
+                let mut framed = Vec::with_capacity(4 + bytes.len());
+                framed.extend_from_slice(&(bytes.len() as u32).to_be_bytes());
+                framed.extend_from_slice(&bytes);
+                framed
+            }
+        }
+    }
+}
+
+mod delimiter {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(delimiter: &u8, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_char(*delimiter as char)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u8, D::Error> {
+        let delimiter = char::deserialize(deserializer)?;
+        if delimiter.is_ascii() {
+            Ok(delimiter as u8)
+        } else {
+            Err(de::Error::custom(format!(
+                "Delimiter {:?} is not a single byte character",
+                delimiter
+            )))
+        }
+    }
+}
+
+/// Applies the rules of the encoding to an event, then encodes and frames it.
+///
+/// Logs without a `message` field are encoded as an empty payload by the
+/// `text` codec. Events which fail to encode otherwise are dropped.
+pub fn encode_event(
+    mut event: Event,
+    encoding: &EncodingConfig<StandardEncodings>,
+    framing: &FramingConfig,
+) -> Option<Vec<u8>> {
+    encoding.apply_rules(&mut event);
+    match encoding.codec().encode(event) {
+        Ok(bytes) => Some(framing.frame(bytes)),
+        Err(EncodingError::MissingMessage) if *encoding.codec() == StandardEncodings::Text => {
+            Some(framing.frame(Vec::new()))
+        }
+        Err(error) => {
+            emit!(EncoderEncodeFailed { error: &error });
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::{Metric, MetricKind, MetricValue};
+
+    fn encode(codec: StandardEncodings, event: Event) -> Result<Vec<u8>, EncodingError> {
+        codec.encode(event)
+    }
+
+    fn log() -> Event {
+        let mut log = LogEvent::default();
+        log.insert(log_schema().message_key(), "hello, \"world\"");
+        log.insert("a.b", 1);
+        Event::from(log)
+    }
+
+    #[test]
+    fn parses_config() {
+        let encoding: EncodingConfig<StandardEncodings> =
+            toml::from_str(r#"codec.csv.fields = ["a", "b"]"#).unwrap();
+        assert_eq!(
+            encoding.codec(),
+            &StandardEncodings::Csv {
+                fields: vec!["a".into(), "b".into()]
+            }
+        );
+
+        let encoding: EncodingConfig<StandardEncodings> =
+            toml::from_str(r#"codec = "ndjson""#).unwrap();
+        assert_eq!(encoding.codec(), &StandardEncodings::Json);
+    }
+
+    #[test]
+    fn sink_encodings_take_precedence() {
+        #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+        #[serde(rename_all = "snake_case")]
+        enum Encoding {
+            Json,
+        }
+
+        let encoding: EncodingConfig<ExtendedEncoding<Encoding>> =
+            toml::from_str(r#"codec = "json""#).unwrap();
+        assert_eq!(
+            encoding.codec(),
+            &ExtendedEncoding::Extension(Encoding::Json)
+        );
+
+        let encoding: EncodingConfig<ExtendedEncoding<Encoding>> =
+            toml::from_str(r#"codec = "logfmt""#).unwrap();
+        assert_eq!(
+            encoding.codec(),
+            &ExtendedEncoding::Standard(StandardEncodings::Logfmt)
+        );
+    }
+
+    #[test]
+    fn encodes_text() {
+        assert_eq!(
+            encode(StandardEncodings::Text, log()).unwrap(),
+            b"hello, \"world\""
+        );
+        assert!(matches!(
+            encode(StandardEncodings::Text, Event::from(LogEvent::default())),
+            Err(EncodingError::MissingMessage)
+        ));
+    }
+
+    #[test]
+    fn encodes_logfmt() {
+        assert_eq!(
+            String::from_utf8(encode(StandardEncodings::Logfmt, log()).unwrap()).unwrap(),
+            r#"a.b=1 message="hello, \"world\"""#
+        );
+    }
+
+    #[test]
+    fn encodes_csv() {
+        let codec = StandardEncodings::Csv {
+            fields: vec!["a.b".into(), "missing".into(), "message".into()],
+        };
+
+        assert_eq!(
+            String::from_utf8(encode(codec, log()).unwrap()).unwrap(),
+            r#"1,,"hello, ""world""""#
+        );
+    }
+
+    #[test]
+    fn encodes_native_protobuf() {
+        let event = Event::Metric(Metric::new(
+            "counter",
+            MetricKind::Incremental,
+            MetricValue::Counter { value: 1.0 },
+        ));
+
+        let bytes = encode(StandardEncodings::NativeProtobuf, event.clone()).unwrap();
+        let decoded = proto::EventWrapper::decode(&bytes[..]).map(Event::from);
+        assert_eq!(decoded.unwrap(), event);
+    }
+
+    #[test]
+    fn encodes_raw_message() {
+        let mut log = LogEvent::default();
+        log.insert(log_schema().message_key(), 1);
+
+        assert_eq!(
+            encode(StandardEncodings::RawMessage, self::log()).unwrap(),
+            b"hello, \"world\""
+        );
+        assert!(matches!(
+            encode(StandardEncodings::RawMessage, Event::from(log)),
+            Err(EncodingError::MessageNotBytes)
+        ));
+    }
+
+    #[test]
+    fn rejects_metrics_for_log_codecs() {
+        let event = Event::Metric(Metric::new(
+            "counter",
+            MetricKind::Incremental,
+            MetricValue::Counter { value: 1.0 },
+        ));
+
+        assert!(matches!(
+            encode(StandardEncodings::Logfmt, event),
+            Err(EncodingError::NotALog { codec: "logfmt" })
+        ));
+    }
+
+    #[test]
+    fn encodes_missing_text_message_as_empty_payload() {
+        let event = || Event::from(LogEvent::default());
+        let framing = FramingConfig::NewlineDelimited;
+
+        assert_eq!(
+            encode_event(event(), &StandardEncodings::Text.into(), &framing),
+            Some(b"\n".to_vec())
+        );
+        assert_eq!(
+            encode_event(event(), &StandardEncodings::RawMessage.into(), &framing),
+            None
+        );
+    }
+
+    #[test]
+    fn frames_events() {
+        let frame = |framing: &str| {
+            let framing: FramingConfig = toml::from_str(framing).unwrap();
+            framing.frame(b"one".to_vec())
+        };
+
+        assert_eq!(frame(r#"method = "bytes""#), b"one");
+        assert_eq!(frame(r#"method = "newline_delimited""#), b"one\n");
+        assert_eq!(
+            frame(
+                r#"method = "character_delimited"
+                delimiter = ",""#
+            ),
+            b"one,"
+        );
+        assert_eq!(frame(r#"method = "length_delimited""#), b"\0\0\0\x03one");
+    }
+
+    #[test]
+    fn rejects_multibyte_delimiters() {
+        let framing = toml::from_str::<FramingConfig>(
+            r#"method = "character_delimited"
+            delimiter = "é""#,
+        );
+
+        assert!(framing.is_err());
+    }
+}
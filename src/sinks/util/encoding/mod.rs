@@ -4,7 +4,9 @@
 //!   * `EncodingConfig<E>`: For sinks without a default `Encoding`.
 //!   * `EncodingConfigWithDefault<E: Default>`: For sinks that have a default `Encoding`.
 //!
-//! Your sink should define some `Encoding` enum that is used as the `E` parameter.
+//! Your sink should define some `Encoding` enum that is used as the `E` parameter, or use the
+//! `StandardEncodings` shared by sinks, extending them with its own through `ExtendedEncoding`
+//! if needed. These are framed with a `FramingConfig`.
 //!
 //! You can use either of these for a sink! They both implement `EncodingConfiguration`, which you
 //! will need to import as well.
@@ -29,6 +31,10 @@
 //       `Encoder` that defines some `encode` function which this config then calls internally as
 //       part of it's own (yet to be written) `encode() -> Vec<u8>` function.
 
+#[cfg(feature = "sinks-utils-codecs")]
+mod codec;
+#[cfg(feature = "sinks-utils-codecs")]
+pub use codec::{encode_event, EncodingError, ExtendedEncoding, FramingConfig, StandardEncodings};
 mod config;
 pub use config::EncodingConfig;
 mod with_default;
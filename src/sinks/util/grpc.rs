@@ -0,0 +1,126 @@
+use super::retries::RetryLogic;
+use crate::{http::build_tls_connector, tls::MaybeTlsSettings};
+use http::uri::{PathAndQuery, Scheme, Uri};
+use hyper::client::{HttpConnector, ResponseFuture};
+use hyper_openssl::HttpsConnector;
+use snafu::{ResultExt, Snafu};
+use std::task::{Context, Poll};
+use tonic::{body::BoxBody, Code, Status};
+use tower::Service;
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("Invalid address {:?}: {}", address, source))]
+    InvalidAddress {
+        address: String,
+        source: http::uri::InvalidUri,
+    },
+    #[snafu(display("Address {:?} is missing a host", address))]
+    MissingHost { address: String },
+}
+
+/// Addresses may be given as `host:port`, so the scheme is filled in from
+/// the TLS settings.
+pub fn with_default_scheme(address: &str, tls: bool) -> crate::Result<Uri> {
+    let uri = address.parse::<Uri>().context(InvalidAddress { address })?;
+    if uri.authority().is_none() {
+        return Err(BuildError::MissingHost {
+            address: address.to_owned(),
+        }
+        .into());
+    }
+
+    let mut parts = uri.into_parts();
+    if parts.scheme.is_none() {
+        parts.scheme = Some(if tls { Scheme::HTTPS } else { Scheme::HTTP });
+    }
+    parts.path_and_query = Some(PathAndQuery::from_static("/"));
+
+    Ok(Uri::from_parts(parts)?)
+}
+
+/// Sends the requests of the gRPC client through a hyper client, as the
+/// requests made by the generated client only hold the path of the method.
+#[derive(Clone, Debug)]
+pub struct HyperSvc {
+    uri: Uri,
+    client: hyper::Client<HttpsConnector<HttpConnector<crate::dns::Resolver>>, BoxBody>,
+}
+
+impl HyperSvc {
+    pub fn new(tls: MaybeTlsSettings, uri: Uri) -> crate::Result<Self> {
+        let connector = build_tls_connector(tls)?;
+        let client = hyper::Client::builder().http2_only(true).build(connector);
+
+        Ok(Self { uri, client })
+    }
+}
+
+impl Service<hyper::Request<BoxBody>> for HyperSvc {
+    type Response = hyper::Response<hyper::Body>;
+    type Error = hyper::Error;
+    type Future = ResponseFuture;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.client.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut request: hyper::Request<BoxBody>) -> Self::Future {
+        let mut parts = self.uri.clone().into_parts();
+        parts.path_and_query = request.uri().path_and_query().cloned();
+        *request.uri_mut() = Uri::from_parts(parts).expect("The gRPC path is a valid URI path.");
+
+        self.client.request(request)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct GrpcRetryLogic;
+
+impl RetryLogic for GrpcRetryLogic {
+    type Error = Status;
+    type Response = ();
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        // Errors of the transport, such as the server being unreachable,
+        // are reported with the `Unknown` code.
+        matches!(
+            error.code(),
+            Code::Unknown
+                | Code::Unavailable
+                | Code::DeadlineExceeded
+                | Code::ResourceExhausted
+                | Code::Aborted
+                | Code::Cancelled
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fills_in_default_scheme() {
+        assert_eq!(
+            with_default_scheme("localhost:6000", false).unwrap(),
+            "http://localhost:6000/".parse::<Uri>().unwrap()
+        );
+        assert_eq!(
+            with_default_scheme("localhost:6000", true).unwrap(),
+            "https://localhost:6000/".parse::<Uri>().unwrap()
+        );
+        assert_eq!(
+            with_default_scheme("http://localhost:6000", true).unwrap(),
+            "http://localhost:6000/".parse::<Uri>().unwrap()
+        );
+    }
+
+    #[test]
+    fn retries_unavailable_server() {
+        let logic = GrpcRetryLogic;
+        assert!(logic.is_retriable_error(&Status::unavailable("down")));
+        assert!(logic.is_retriable_error(&Status::unknown("connection refused")));
+        assert!(!logic.is_retriable_error(&Status::invalid_argument("bad batch")));
+    }
+}
@@ -2,6 +2,8 @@ pub mod adaptive_concurrency;
 pub mod batch;
 pub mod buffer;
 pub mod encoding;
+#[cfg(any(feature = "sinks-opentelemetry", feature = "sinks-vector"))]
+pub mod grpc;
 pub mod http;
 pub mod retries;
 pub mod service;
@@ -0,0 +1,80 @@
+use crate::config::{DataType, GenerateConfig, SinkConfig, SinkContext, SinkDescription};
+use serde::{Deserialize, Serialize};
+
+pub mod v1;
+pub mod v2;
+
+/// Version 1 of the protocol streams events over TCP, and is used when no `version` is set.
+/// Version 2 sends batches of events over gRPC, which are acked by the receiving Vector.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(untagged)]
+pub enum VectorSinkConfig {
+    V1(v1::VectorSinkConfig),
+    V2(v2::VectorSinkConfig),
+}
+
+inventory::submit! {
+    SinkDescription::new::<VectorSinkConfig>("vector")
+}
+
+impl GenerateConfig for VectorSinkConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self::V1(v1::VectorSinkConfig::from_address(
+            "127.0.0.1:5000".to_string(),
+        )))
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "vector")]
+impl SinkConfig for VectorSinkConfig {
+    async fn build(
+        &self,
+        cx: SinkContext,
+    ) -> crate::Result<(super::VectorSink, super::Healthcheck)> {
+        match self {
+            Self::V1(config) => config.build(cx),
+            Self::V2(config) => config.build(cx),
+        }
+    }
+
+    fn input_type(&self) -> DataType {
+        DataType::Any
+    }
+
+    fn sink_type(&self) -> &'static str {
+        "vector"
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::VectorSinkConfig;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<VectorSinkConfig>();
+    }
+
+    #[test]
+    fn parses_versions() {
+        let config: VectorSinkConfig = toml::from_str(r#"address = "127.0.0.1:5000""#).unwrap();
+        assert!(matches!(config, VectorSinkConfig::V1(_)));
+
+        let config: VectorSinkConfig = toml::from_str(
+            r#"version = "1"
+            address = "127.0.0.1:5000""#,
+        )
+        .unwrap();
+        assert!(matches!(config, VectorSinkConfig::V1(_)));
+
+        let config: VectorSinkConfig = toml::from_str(
+            r#"version = "2"
+            address = "127.0.0.1:6000"
+            compression = "gzip""#,
+        )
+        .unwrap();
+        assert!(matches!(config, VectorSinkConfig::V2(_)));
+    }
+}
@@ -1,7 +1,7 @@
 use crate::{
-    config::{DataType, GenerateConfig, SinkConfig, SinkContext, SinkDescription},
+    config::SinkContext,
     event::proto,
-    sinks::util::tcp::TcpSinkConfig,
+    sinks::{util::tcp::TcpSinkConfig, Healthcheck, VectorSink},
     tcp::TcpKeepaliveConfig,
     tls::TlsConfig,
     Event,
@@ -10,11 +10,18 @@ use bytes::{BufMut, Bytes, BytesMut};
 use getset::Setters;
 use prost::Message;
 use serde::{Deserialize, Serialize};
-use snafu::Snafu;
 
-#[derive(Deserialize, Serialize, Debug, Setters)]
+#[derive(Deserialize, Serialize, Debug, Clone, Copy)]
+enum Version {
+    #[serde(rename = "1")]
+    V1,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Setters)]
 #[serde(deny_unknown_fields)]
 pub struct VectorSinkConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    version: Option<Version>,
     address: String,
     keepalive: Option<TcpKeepaliveConfig>,
     #[set = "pub"]
@@ -30,6 +37,7 @@ impl VectorSinkConfig {
         send_buffer_bytes: Option<usize>,
     ) -> Self {
         Self {
+            version: None,
             address,
             keepalive,
             tls,
@@ -40,33 +48,8 @@ impl VectorSinkConfig {
     pub fn from_address(address: String) -> Self {
         Self::new(address, None, None, None)
     }
-}
-
-#[derive(Debug, Snafu)]
-enum BuildError {
-    #[snafu(display("Missing host in address field"))]
-    MissingHost,
-    #[snafu(display("Missing port in address field"))]
-    MissingPort,
-}
-
-inventory::submit! {
-    SinkDescription::new::<VectorSinkConfig>("vector")
-}
-
-impl GenerateConfig for VectorSinkConfig {
-    fn generate_config() -> toml::Value {
-        toml::Value::try_from(Self::new("127.0.0.1:5000".to_string(), None, None, None)).unwrap()
-    }
-}
 
-#[async_trait::async_trait]
-#[typetag::serde(name = "vector")]
-impl SinkConfig for VectorSinkConfig {
-    async fn build(
-        &self,
-        cx: SinkContext,
-    ) -> crate::Result<(super::VectorSink, super::Healthcheck)> {
+    pub(super) fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
         let sink_config = TcpSinkConfig::new(
             self.address.clone(),
             self.keepalive,
@@ -76,20 +59,6 @@ impl SinkConfig for VectorSinkConfig {
 
         sink_config.build(cx, |event| Some(encode_event(event)))
     }
-
-    fn input_type(&self) -> DataType {
-        DataType::Any
-    }
-
-    fn sink_type(&self) -> &'static str {
-        "vector"
-    }
-}
-
-#[derive(Debug, Snafu)]
-enum HealthcheckError {
-    #[snafu(display("Connect error: {}", source))]
-    ConnectError { source: std::io::Error },
 }
 
 fn encode_event(event: Event) -> Bytes {
@@ -103,11 +72,3 @@ fn encode_event(event: Event) -> Bytes {
 
     out.into()
 }
-
-#[cfg(test)]
-mod test {
-    #[test]
-    fn generate_config() {
-        crate::test_util::test_generate_config::<super::VectorSinkConfig>();
-    }
-}
@@ -0,0 +1,191 @@
+use crate::{
+    config::SinkContext,
+    event::{proto as event_proto, Event},
+    proto::vector as proto,
+    sinks::{
+        util::{
+            buffer::compression::GZIP_DEFAULT,
+            grpc::{with_default_scheme, GrpcRetryLogic, HyperSvc},
+            BatchConfig, BatchSettings, Compression, EncodedLength, TowerRequestConfig, VecBuffer,
+        },
+        Healthcheck, VectorSink,
+    },
+    tls::{MaybeTlsSettings, TlsConfig},
+};
+use flate2::write::GzEncoder;
+use futures::{future::BoxFuture, stream, FutureExt, SinkExt, StreamExt};
+use lazy_static::lazy_static;
+use prost::Message;
+use serde::{Deserialize, Serialize};
+use snafu::{ResultExt, Snafu};
+use std::{
+    io::Write,
+    task::{Context, Poll},
+};
+use tonic::Status;
+use tower::Service;
+use tracing_futures::Instrument;
+
+type Client = proto::vector_client::VectorClient<HyperSvc>;
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy)]
+enum Version {
+    #[serde(rename = "2")]
+    V2,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct VectorSinkConfig {
+    version: Version,
+    address: String,
+    #[serde(default)]
+    compression: Compression,
+    tls: Option<TlsConfig>,
+    #[serde(default)]
+    batch: BatchConfig,
+    #[serde(default)]
+    request: TowerRequestConfig,
+}
+
+lazy_static! {
+    static ref REQUEST_DEFAULTS: TowerRequestConfig = Default::default();
+}
+
+#[derive(Debug, Snafu)]
+enum HealthcheckError {
+    #[snafu(display("Request failed: {}", source))]
+    Request { source: Status },
+    #[snafu(display("The receiving Vector isn't serving"))]
+    NotServing,
+}
+
+impl VectorSinkConfig {
+    pub fn from_address(address: String) -> Self {
+        Self {
+            version: Version::V2,
+            address,
+            compression: Compression::None,
+            tls: None,
+            batch: BatchConfig::default(),
+            request: TowerRequestConfig::default(),
+        }
+    }
+
+    pub fn set_tls(&mut self, tls: Option<TlsConfig>) {
+        self.tls = tls;
+    }
+
+    pub fn set_compression(&mut self, compression: Compression) {
+        self.compression = compression;
+    }
+
+    pub(super) fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let tls = MaybeTlsSettings::from_config(&self.tls, false)?;
+        let uri = with_default_scheme(&self.address, tls.is_tls())?;
+
+        let client = Client::new(HyperSvc::new(tls, uri)?);
+        let healthcheck = healthcheck(client.clone()).boxed();
+
+        let batch = BatchSettings::default()
+            .bytes(10_000_000)
+            .events(1000)
+            .timeout(1)
+            .parse_config(self.batch)?;
+        let request = self.request.unwrap_with(&REQUEST_DEFAULTS);
+        let service = VectorService {
+            client,
+            compression: self.compression,
+        };
+
+        let sink = request
+            .batch_sink(
+                GrpcRetryLogic,
+                service,
+                VecBuffer::new(batch.size),
+                batch.timeout,
+                cx.acker(),
+            )
+            .sink_map_err(|error| error!(message = "Fatal vector sink error.", %error))
+            .with_flat_map(|event: Event| {
+                stream::iter(Some(event_proto::EventWrapper::from(event))).map(Ok)
+            });
+
+        Ok((VectorSink::Sink(Box::new(sink)), healthcheck))
+    }
+}
+
+async fn healthcheck(mut client: Client) -> crate::Result<()> {
+    let response = client
+        .health_check(proto::HealthCheckRequest {})
+        .await
+        .context(Request)?;
+
+    if response.into_inner().status == proto::ServingStatus::Serving as i32 {
+        Ok(())
+    } else {
+        Err(HealthcheckError::NotServing.into())
+    }
+}
+
+impl EncodedLength for event_proto::EventWrapper {
+    fn encoded_length(&self) -> usize {
+        self.encoded_len()
+    }
+}
+
+#[derive(Clone, Debug)]
+struct VectorService {
+    client: Client,
+    compression: Compression,
+}
+
+impl VectorService {
+    fn encode_request(&self, events: Vec<event_proto::EventWrapper>) -> proto::PushEventsRequest {
+        let batch = proto::EventBatch { events };
+        let batch = match self.compression {
+            Compression::None => proto::push_events_request::Batch::Events(batch),
+            Compression::Gzip(level) => {
+                let mut encoded = Vec::with_capacity(batch.encoded_len());
+                batch
+                    .encode(&mut encoded)
+                    .expect("Vec has enough capacity.");
+
+                let level = level.unwrap_or(GZIP_DEFAULT) as u32;
+                let mut encoder = GzEncoder::new(Vec::new(), flate2::Compression::new(level));
+                encoder
+                    .write_all(&encoded)
+                    .and_then(|_| encoder.finish())
+                    .map(proto::push_events_request::Batch::GzipEvents)
+                    .expect("Writing to Vec can't fail.")
+            }
+        };
+
+        proto::PushEventsRequest { batch: Some(batch) }
+    }
+}
+
+impl Service<Vec<event_proto::EventWrapper>> for VectorService {
+    type Response = ();
+    type Error = Status;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, events: Vec<event_proto::EventWrapper>) -> Self::Future {
+        debug!(message = "Sending events.", events = %events.len());
+
+        let mut client = self.client.clone();
+        let request = self.encode_request(events);
+
+        Box::pin(async move {
+            client
+                .push_events(request)
+                .instrument(info_span!("request"))
+                .await
+                .map(|_| ())
+        })
+    }
+}
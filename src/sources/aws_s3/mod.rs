@@ -52,6 +52,8 @@ struct AwsS3Config {
     auth: AWSAuthentication,
 
     multiline: Option<MultilineConfig>,
+
+    acknowledgements: bool,
 }
 
 inventory::submit! {
@@ -130,6 +132,7 @@ impl AwsS3Config {
                     sqs.clone(),
                     self.compression,
                     multiline,
+                    self.acknowledgements,
                 )
                 .await
                 .context(Initialize {})
@@ -1,6 +1,6 @@
 use crate::{
     config::log_schema,
-    event::Event,
+    event::{BatchNotifier, BatchStatus, Event},
     internal_events::aws_s3::source::{
         SqsMessageDeleteFailed, SqsMessageDeleteSucceeded, SqsMessageProcessingFailed,
         SqsMessageProcessingSucceeded, SqsMessageReceiveFailed, SqsMessageReceiveSucceeded,
@@ -23,7 +23,7 @@ use rusoto_sqs::{
 };
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use snafu::{ResultExt, Snafu};
-use std::{future::ready, time::Duration};
+use std::{future::ready, sync::Arc, time::Duration};
 use tokio::time;
 use tokio_util::codec::FramedRead;
 
@@ -111,6 +111,15 @@ pub enum ProcessingError {
     },
     #[snafu(display("Unsupported S3 event version: {}.", version,))]
     UnsupportedS3EventVersion { version: semver::Version },
+    #[snafu(display(
+        "Events from SQS message with id {} were not delivered: {:?}",
+        message_id,
+        status
+    ))]
+    EventsNotDelivered {
+        status: BatchStatus,
+        message_id: String,
+    },
 }
 
 pub(super) struct Ingestor {
@@ -126,6 +135,7 @@ pub(super) struct Ingestor {
     poll_interval: Duration,
     visibility_timeout_secs: i64,
     delete_message: bool,
+    acknowledgements: bool,
 }
 
 impl Ingestor {
@@ -136,6 +146,7 @@ impl Ingestor {
         config: Config,
         compression: super::Compression,
         multiline: Option<line_agg::Config>,
+        acknowledgements: bool,
     ) -> Result<Ingestor, IngestorNewError> {
         let visibility_timeout_secs: i64 = config.visibility_timeout_secs.into();
 
@@ -152,6 +163,7 @@ impl Ingestor {
             poll_interval: Duration::from_secs(config.poll_secs),
             visibility_timeout_secs,
             delete_message: config.delete_message,
+            acknowledgements,
         })
     }
 
@@ -231,21 +243,35 @@ impl Ingestor {
         message: Message,
         out: Pipeline,
     ) -> Result<(), ProcessingError> {
+        let message_id = message.message_id.unwrap_or_else(|| "<empty>".to_owned());
         let s3_event: S3Event = serde_json::from_str(message.body.unwrap_or_default().as_ref())
             .context(InvalidSqsMessage {
-                message_id: message.message_id.unwrap_or_else(|| "<empty>".to_owned()),
+                message_id: message_id.clone(),
             })?;
 
-        self.handle_s3_event(s3_event, out).await
+        if !self.acknowledgements {
+            return self.handle_s3_event(s3_event, out, None).await;
+        }
+
+        // The message is only considered processed, and so deleted, once every
+        // event read from its objects has been delivered.
+        let (batch, receiver) = BatchNotifier::new_with_receiver();
+        self.handle_s3_event(s3_event, out, Some(batch)).await?;
+        match receiver.await {
+            BatchStatus::Delivered => Ok(()),
+            status => Err(ProcessingError::EventsNotDelivered { status, message_id }),
+        }
     }
 
     async fn handle_s3_event(
         &self,
         s3_event: S3Event,
         mut out: Pipeline,
+        batch: Option<Arc<BatchNotifier>>,
     ) -> Result<(), ProcessingError> {
         for record in s3_event.records {
-            self.handle_s3_event_record(record, &mut out).await?
+            self.handle_s3_event_record(record, &mut out, batch.as_ref())
+                .await?
         }
         Ok(())
     }
@@ -254,6 +280,7 @@ impl Ingestor {
         &self,
         s3_event: S3EventRecord,
         out: &mut Pipeline,
+        batch: Option<&Arc<BatchNotifier>>,
     ) -> Result<(), ProcessingError> {
         let event_version: semver::Version = s3_event.event_version.clone().into();
         if !SUPPORTED_S3S_EVENT_VERSION.matches(&event_version) {
@@ -357,6 +384,9 @@ impl Ingestor {
                     });
 
                     let mut event = Event::from(line);
+                    if let Some(batch) = batch {
+                        event.add_batch_notifier(Arc::clone(batch));
+                    }
 
                     let log = event.as_mut_log();
                     log.insert("bucket", s3_event.s3.bucket.name.clone());
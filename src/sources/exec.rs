@@ -0,0 +1,488 @@
+use crate::{
+    config::{
+        log_schema, DataType, GenerateConfig, GlobalOptions, SourceConfig, SourceDescription,
+    },
+    event::{Event, Value},
+    internal_events::{ExecCommandExited, ExecCommandFailed, ExecEventReceived},
+    shutdown::{ShutdownSignal, ShutdownSignalToken},
+    sinks::util::retries::ExponentialBackoff,
+    Pipeline,
+};
+use bytes::Bytes;
+use codec::BytesDelimitedCodec;
+use futures::{stream, SinkExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use snafu::Snafu;
+use std::{path::PathBuf, process::Stdio};
+use tokio::{
+    process::{Child, Command},
+    time::{self, delay_for, Duration, Instant},
+};
+use tokio_util::codec::FramedRead;
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+// TODO: add back when serde-rs/serde#1358 is addressed
+// #[serde(deny_unknown_fields)]
+pub struct ExecConfig {
+    #[serde(flatten)]
+    mode: Mode,
+    command: Vec<String>,
+    working_directory: Option<PathBuf>,
+    #[serde(default = "default_include_stderr")]
+    include_stderr: bool,
+    #[serde(default = "default_maximum_buffer_size")]
+    maximum_buffer_size_bytes: usize,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum Mode {
+    /// The command is run every `exec_interval_secs`.
+    Scheduled {
+        #[serde(default = "default_exec_interval_secs")]
+        exec_interval_secs: u64,
+    },
+    /// The command is long running, and is respawned with an exponential
+    /// backoff when it exits.
+    Streaming {
+        #[serde(default = "default_respawn_on_exit")]
+        respawn_on_exit: bool,
+        #[serde(default = "default_respawn_max_interval_secs")]
+        respawn_max_interval_secs: u64,
+    },
+}
+
+fn default_exec_interval_secs() -> u64 {
+    60
+}
+
+fn default_respawn_on_exit() -> bool {
+    true
+}
+
+fn default_respawn_max_interval_secs() -> u64 {
+    60
+}
+
+fn default_include_stderr() -> bool {
+    true
+}
+
+fn default_maximum_buffer_size() -> usize {
+    // 1MB
+    1_000_000
+}
+
+/// Children are killed if they're still running this long after being
+/// asked to terminate on shutdown.
+const TERMINATION_GRACE_PERIOD: Duration = Duration::from_secs(5);
+
+const STDOUT: &str = "stdout";
+const STDERR: &str = "stderr";
+
+#[derive(Debug, PartialEq, Snafu)]
+pub enum ExecConfigError {
+    #[snafu(display("A non-empty command is required"))]
+    CommandEmpty,
+    #[snafu(display("`exec_interval_secs` must be greater than zero"))]
+    ExecIntervalZero,
+}
+
+inventory::submit! {
+    SourceDescription::new::<ExecConfig>("exec")
+}
+
+impl GenerateConfig for ExecConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            mode: Mode::Scheduled {
+                exec_interval_secs: default_exec_interval_secs(),
+            },
+            command: vec!["echo".to_owned(), "Hello World!".to_owned()],
+            working_directory: None,
+            include_stderr: default_include_stderr(),
+            maximum_buffer_size_bytes: default_maximum_buffer_size(),
+        })
+        .unwrap()
+    }
+}
+
+impl ExecConfig {
+    fn validate(&self) -> Result<(), ExecConfigError> {
+        if self.command.is_empty() {
+            return Err(ExecConfigError::CommandEmpty);
+        }
+        if self.mode
+            == (Mode::Scheduled {
+                exec_interval_secs: 0,
+            })
+        {
+            return Err(ExecConfigError::ExecIntervalZero);
+        }
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "exec")]
+impl SourceConfig for ExecConfig {
+    async fn build(
+        &self,
+        _name: &str,
+        _globals: &GlobalOptions,
+        shutdown: ShutdownSignal,
+        out: Pipeline,
+    ) -> crate::Result<super::Source> {
+        self.validate()?;
+        let source = ExecSource {
+            command_line: self.command.join(" "),
+            config: self.clone(),
+            hostname: crate::get_hostname().ok(),
+        };
+        Ok(Box::pin(source.run(shutdown, out)))
+    }
+
+    fn output_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    fn source_type(&self) -> &'static str {
+        "exec"
+    }
+}
+
+/// The tick of the schedule following `tick`. The ticks missed while the
+/// command was running longer than the interval are skipped, instead of
+/// running the command again right away for each of them.
+fn next_tick(tick: Instant, period: Duration, now: Instant) -> Instant {
+    let mut next = tick + period;
+    while next <= now {
+        next += period;
+    }
+    next
+}
+
+struct ExecSource {
+    config: ExecConfig,
+    command_line: String,
+    hostname: Option<String>,
+}
+
+impl ExecSource {
+    async fn run(self, mut shutdown: ShutdownSignal, mut out: Pipeline) -> Result<(), ()> {
+        match self.config.mode {
+            Mode::Scheduled { exec_interval_secs } => {
+                let period = Duration::from_secs(exec_interval_secs);
+                let mut tick = Instant::now();
+                loop {
+                    tokio::select! {
+                        _ = &mut shutdown => break,
+                        _ = time::delay_until(tick) => (),
+                    }
+                    if self.run_command(&mut shutdown, &mut out).await? {
+                        break;
+                    }
+                    tick = next_tick(tick, period, Instant::now());
+                }
+            }
+            Mode::Streaming {
+                respawn_on_exit,
+                respawn_max_interval_secs,
+            } => {
+                let max_interval = Duration::from_secs(respawn_max_interval_secs);
+                let new_backoff = || {
+                    ExponentialBackoff::from_millis(2)
+                        .factor(500)
+                        .max_delay(max_interval)
+                };
+                let mut backoff = new_backoff();
+                loop {
+                    let started = Instant::now();
+                    if self.run_command(&mut shutdown, &mut out).await? || !respawn_on_exit {
+                        break;
+                    }
+
+                    // Commands which ran for a while aren't failing repeatedly.
+                    if started.elapsed() > max_interval {
+                        backoff = new_backoff();
+                    }
+                    let delay = backoff.next().expect("Backoff is infinite.");
+                    tokio::select! {
+                        _ = &mut shutdown => break,
+                        _ = delay_for(delay) => (),
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Runs the command until it exits, returning whether the source is
+    /// shutting down.
+    async fn run_command(
+        &self,
+        shutdown: &mut ShutdownSignal,
+        out: &mut Pipeline,
+    ) -> Result<bool, ()> {
+        let mut command = Command::new(&self.config.command[0]);
+        command
+            .args(&self.config.command[1..])
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(if self.config.include_stderr {
+                Stdio::piped()
+            } else {
+                Stdio::null()
+            })
+            .kill_on_drop(true);
+        if let Some(working_directory) = &self.config.working_directory {
+            command.current_dir(working_directory);
+        }
+
+        let mut child = match command.spawn() {
+            Ok(child) => child,
+            Err(error) => {
+                emit!(ExecCommandFailed {
+                    command: &self.command_line,
+                    error,
+                });
+                return Ok(false);
+            }
+        };
+        let pid = child.id();
+
+        let codec = || {
+            BytesDelimitedCodec::new_with_max_length(b'\n', self.config.maximum_buffer_size_bytes)
+        };
+        let stdout =
+            FramedRead::new(child.stdout.take().unwrap(), codec()).map(|line| (STDOUT, line));
+        let mut output = match child.stderr.take() {
+            Some(stderr) => stream::select(
+                stdout,
+                FramedRead::new(stderr, codec()).map(|line| (STDERR, line)),
+            )
+            .boxed(),
+            None => stdout.boxed(),
+        };
+
+        // The token is held until the child exited, delaying the shutdown.
+        let mut token: Option<ShutdownSignalToken> = None;
+        loop {
+            tokio::select! {
+                shutdown_token = &mut *shutdown => {
+                    token = Some(shutdown_token);
+                    break;
+                }
+                line = output.next() => match line {
+                    Some((stream, Ok(line))) => {
+                        let event = self.build_event(line, stream, pid);
+                        out.send(event)
+                            .await
+                            .map_err(|error| error!(message = "Error sending event.", %error))?;
+                    }
+                    Some((_, Err(error))) => emit!(ExecCommandFailed {
+                        command: &self.command_line,
+                        error,
+                    }),
+                    None => break,
+                },
+            }
+        }
+        drop(output);
+
+        if token.is_some() {
+            terminate(&mut child);
+        }
+        let status = match time::timeout(TERMINATION_GRACE_PERIOD, &mut child).await {
+            Ok(status) => status,
+            Err(_) => {
+                let _ = child.kill();
+                child.await
+            }
+        };
+        match status {
+            Ok(status) => emit!(ExecCommandExited {
+                command: &self.command_line,
+                status,
+            }),
+            Err(error) => emit!(ExecCommandFailed {
+                command: &self.command_line,
+                error,
+            }),
+        }
+
+        Ok(token.is_some())
+    }
+
+    fn build_event(&self, line: Bytes, stream: &'static str, pid: u32) -> Event {
+        emit!(ExecEventReceived {
+            command: &self.command_line,
+            byte_size: line.len(),
+        });
+
+        let mut event = Event::from(line);
+        let log = event.as_mut_log();
+        log.insert(log_schema().source_type_key(), Bytes::from("exec"));
+        if let Some(hostname) = &self.hostname {
+            log.insert(log_schema().host_key(), hostname.clone());
+        }
+        log.insert(
+            "command",
+            Value::Array(
+                self.config
+                    .command
+                    .iter()
+                    .map(|arg| Value::from(arg.clone()))
+                    .collect(),
+            ),
+        );
+        log.insert("pid", pid as i64);
+        log.insert("stream", Bytes::from(stream));
+        event
+    }
+}
+
+/// Asks the child to terminate, which gives it a chance to clean up.
+#[cfg(unix)]
+fn terminate(child: &mut Child) {
+    use nix::{
+        sys::signal::{kill, Signal},
+        unistd::Pid,
+    };
+
+    let _ = kill(Pid::from_raw(child.id() as i32), Signal::SIGTERM);
+}
+
+#[cfg(not(unix))]
+fn terminate(child: &mut Child) {
+    let _ = child.kill();
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::test_util::collect_ready;
+    use std::time::Duration as StdDuration;
+
+    fn config(mode: Mode, command: &str) -> ExecConfig {
+        ExecConfig {
+            mode,
+            command: vec!["sh".to_owned(), "-c".to_owned(), command.to_owned()],
+            working_directory: None,
+            include_stderr: true,
+            maximum_buffer_size_bytes: default_maximum_buffer_size(),
+        }
+    }
+
+    async fn run(config: ExecConfig, shutdown: ShutdownSignal) -> Vec<Event> {
+        let (tx, rx) = Pipeline::new_test();
+        config
+            .build("default", &GlobalOptions::default(), shutdown, tx)
+            .await
+            .unwrap()
+            .await
+            .unwrap();
+        collect_ready(rx).await
+    }
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<ExecConfig>();
+    }
+
+    #[test]
+    fn parses_modes() {
+        let config: ExecConfig = toml::from_str(
+            r#"mode = "streaming"
+            command = ["tail", "-f", "/var/log/syslog"]"#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.mode,
+            Mode::Streaming {
+                respawn_on_exit: true,
+                respawn_max_interval_secs: 60,
+            }
+        );
+    }
+
+    #[test]
+    fn rejects_empty_commands() {
+        let config = ExecConfig {
+            command: Vec::new(),
+            ..config(
+                Mode::Scheduled {
+                    exec_interval_secs: 1,
+                },
+                "",
+            )
+        };
+        assert_eq!(config.validate(), Err(ExecConfigError::CommandEmpty));
+    }
+
+    #[test]
+    fn skips_missed_ticks() {
+        let start = Instant::now();
+        let period = Duration::from_secs(10);
+
+        assert_eq!(
+            next_tick(start, period, start + Duration::from_secs(1)),
+            start + period
+        );
+        assert_eq!(
+            next_tick(start, period, start + Duration::from_secs(25)),
+            start + Duration::from_secs(30)
+        );
+    }
+
+    #[tokio::test]
+    async fn captures_stdout_and_stderr() {
+        let config = config(
+            Mode::Streaming {
+                respawn_on_exit: false,
+                respawn_max_interval_secs: 1,
+            },
+            "echo out; echo err >&2",
+        );
+
+        let mut events = run(config, ShutdownSignal::noop()).await;
+        events.sort_by_key(|event| event.as_log()["stream"].to_string_lossy());
+
+        assert_eq!(events.len(), 2);
+        let log = events[0].as_log();
+        assert_eq!(log[log_schema().message_key()], "err".into());
+        assert_eq!(log["stream"], STDERR.into());
+        let log = events[1].as_log();
+        assert_eq!(log[log_schema().message_key()], "out".into());
+        assert_eq!(log["stream"], STDOUT.into());
+        assert_eq!(log[log_schema().source_type_key()], "exec".into());
+        assert_eq!(log["command[0]"], "sh".into());
+        assert!(matches!(log["pid"], Value::Integer(_)));
+    }
+
+    #[tokio::test]
+    async fn kills_the_command_on_shutdown() {
+        let (trigger, shutdown, _) = ShutdownSignal::new_wired();
+        let config = config(
+            Mode::Streaming {
+                respawn_on_exit: true,
+                respawn_max_interval_secs: 1,
+            },
+            "echo started; sleep 60",
+        );
+
+        tokio::spawn(async move {
+            delay_for(Duration::from_millis(500)).await;
+            drop(trigger);
+        });
+        let events = time::timeout(StdDuration::from_secs(10), run(config, shutdown))
+            .await
+            .expect("The command wasn't killed.");
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(
+            events[0].as_log()[log_schema().message_key()],
+            "started".into()
+        );
+    }
+}
@@ -1,9 +1,11 @@
-use super::util::{EncodingConfig, MultilineConfig};
+use super::util::{DecodingConfig, EncodingConfig, MultilineConfig};
 use crate::{
     config::{log_schema, DataType, GlobalOptions, SourceConfig, SourceDescription},
     encoding_transcode::{Decoder, Encoder},
     event::Event,
-    internal_events::{FileEventReceived, FileOpen, FileSourceInternalEventsEmitter},
+    internal_events::{
+        DecoderParseFailed, FileEventReceived, FileOpen, FileSourceInternalEventsEmitter,
+    },
     line_agg::{self, LineAgg},
     shutdown::ShutdownSignal,
     trace::{current_span, Instrument},
@@ -88,6 +90,7 @@ pub struct FileConfig {
     pub remove_after_secs: Option<u64>,
     pub line_delimiter: String,
     pub encoding: Option<EncodingConfig>,
+    pub decoding: Option<DecodingConfig>,
 }
 
 #[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
@@ -174,6 +177,7 @@ impl Default for FileConfig {
             remove_after_secs: None,
             line_delimiter: "\n".to_string(),
             encoding: None,
+            decoding: None,
         }
     }
 }
@@ -217,7 +221,8 @@ impl SourceConfig for FileConfig {
     }
 
     fn output_type(&self) -> DataType {
-        DataType::Log
+        self.decoding
+            .map_or(DataType::Log, |decoding| decoding.output_type())
     }
 
     fn source_type(&self) -> &'static str {
@@ -291,6 +296,7 @@ pub fn file_source(
     let multiline_config = config.multiline.clone();
     let message_start_indicator = config.message_start_indicator.clone();
     let multi_line_timeout = config.multi_line_timeout;
+    let decoding = config.decoding.unwrap_or_default();
 
     Box::pin(async move {
         info!(message = "Starting file server.", include = ?include, exclude = ?exclude);
@@ -335,8 +341,23 @@ pub fn file_source(
         let mut messages = messages
             .map(move |(msg, file): (Bytes, String)| {
                 let _enter = span2.enter();
-                create_event(msg, file, &host_key, &hostname, &file_key)
+                emit!(FileEventReceived {
+                    file: &file,
+                    byte_size: msg.len(),
+                });
+
+                // Each line, once aggregated, is parsed on its own.
+                let events = decoding.parse(msg).unwrap_or_else(|error| {
+                    emit!(DecoderParseFailed { error: &error });
+                    Vec::new()
+                });
+                events
+                    .into_iter()
+                    .map(|event| create_event(event, file.clone(), &host_key, &hostname, &file_key))
+                    .collect::<Vec<_>>()
             })
+            .map(futures::stream::iter)
+            .flatten()
             .map(Ok);
         tokio::spawn(async move { out.send_all(&mut messages).instrument(span).await });
 
@@ -390,30 +411,23 @@ fn wrap_with_line_agg(
 }
 
 fn create_event(
-    line: Bytes,
+    mut event: Event,
     file: String,
     host_key: &str,
     hostname: &Option<String>,
     file_key: &Option<String>,
 ) -> Event {
-    emit!(FileEventReceived {
-        file: &file,
-        byte_size: line.len(),
-    });
-
-    let mut event = Event::from(line);
-
-    // Add source type
-    event
-        .as_mut_log()
-        .insert(log_schema().source_type_key(), Bytes::from("file"));
+    if let Event::Log(log) = &mut event {
+        // Add source type
+        log.insert(log_schema().source_type_key(), Bytes::from("file"));
 
-    if let Some(file_key) = &file_key {
-        event.as_mut_log().insert(file_key.clone(), file);
-    }
+        if let Some(file_key) = &file_key {
+            log.insert(file_key.clone(), file);
+        }
 
-    if let Some(hostname) = &hostname {
-        event.as_mut_log().insert(host_key, hostname.clone());
+        if let Some(hostname) = &hostname {
+            log.try_insert(host_key, hostname.clone());
+        }
     }
 
     event
@@ -534,6 +548,24 @@ mod tests {
         )
         .unwrap();
         assert_eq!(config.read_from, Some(ReadFromConfig::End));
+
+        let config: FileConfig = toml::from_str(
+            r#"
+        [decoding]
+        codec = "json"
+        "#,
+        )
+        .unwrap();
+        assert_eq!(config.decoding, Some(DecodingConfig::Json));
+
+        // Lines are framed by `line_delimiter` and the multiline options.
+        assert!(toml::from_str::<FileConfig>(
+            r#"
+        [framing]
+        method = "bytes"
+        "#,
+        )
+        .is_err());
     }
 
     #[test]
@@ -564,7 +596,7 @@ mod tests {
         let hostname = Some("Some.Machine".to_string());
         let file_key = Some("file".to_string());
 
-        let event = create_event(line, file, &host_key, &hostname, &file_key);
+        let event = create_event(Event::from(line), file, &host_key, &hostname, &file_key);
         let log = event.into_log();
 
         assert_eq!(log["file"], "some_file.rs".into());
@@ -1210,6 +1242,45 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn file_decodes_json() {
+        let (tx, rx) = Pipeline::new_test();
+        let (trigger_shutdown, shutdown, _) = ShutdownSignal::new_wired();
+
+        let dir = tempdir().unwrap();
+        let config = file::FileConfig {
+            include: vec![dir.path().join("*")],
+            decoding: Some(DecodingConfig::Json),
+            ..test_default_file_config(&dir)
+        };
+
+        let source = file::file_source(&config, config.data_dir.clone().unwrap(), shutdown, tx);
+        tokio::spawn(source);
+
+        let path = dir.path().join("file");
+        let mut file = File::create(&path).unwrap();
+
+        sleep_500_millis().await; // The files must be observed at their original lengths before writing to them
+
+        writeln!(&mut file, r#"{{"message": "one", "n": 1}}"#).unwrap();
+        writeln!(&mut file, "not json").unwrap();
+        writeln!(&mut file, r#"{{"message": "two", "n": 2}}"#).unwrap();
+
+        sleep_500_millis().await;
+
+        drop(trigger_shutdown);
+
+        let received = wait_with_timeout(rx.collect::<Vec<_>>()).await;
+
+        assert_eq!(received.len(), 2);
+        let log = received[0].as_log();
+        assert_eq!(log[log_schema().message_key()], "one".into());
+        assert_eq!(log["n"], 1.into());
+        assert_eq!(log["file"], path.to_str().unwrap().into());
+        assert_eq!(log[log_schema().source_type_key()], "file".into());
+        assert_eq!(received[1].as_log()["n"], 2.into());
+    }
+
     #[tokio::test]
     async fn test_multi_line_aggregation_legacy() {
         let (tx, rx) = Pipeline::new_test();
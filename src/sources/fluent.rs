@@ -0,0 +1,836 @@
+use super::util::{IsErrorFatal, SocketListenAddr, TcpSource};
+#[cfg(unix)]
+use crate::sources::util::build_unix_stream_source_with_ack;
+use crate::{
+    config::{
+        log_schema, DataType, GenerateConfig, GlobalOptions, Resource, SourceConfig,
+        SourceDescription,
+    },
+    event::{Event, LogEvent, Value},
+    internal_events::FluentMessageReceived,
+    shutdown::ShutdownSignal,
+    tcp::TcpKeepaliveConfig,
+    tls::{MaybeTlsSettings, TlsConfig},
+    Pipeline,
+};
+use bytes::{Buf, Bytes, BytesMut};
+use chrono::{DateTime, TimeZone, Utc};
+use flate2::read::MultiGzDecoder;
+use serde::{Deserialize, Serialize};
+use snafu::{ResultExt, Snafu};
+#[cfg(unix)]
+use std::path::PathBuf;
+use std::{
+    collections::{BTreeMap, VecDeque},
+    convert::TryInto,
+    io::{self, Read},
+};
+use tokio_util::codec::Decoder;
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+// TODO: add back when serde-rs/serde#1358 is addressed
+// #[serde(deny_unknown_fields)]
+pub struct FluentConfig {
+    #[serde(flatten)]
+    mode: Mode,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum Mode {
+    Tcp {
+        address: SocketListenAddr,
+        keepalive: Option<TcpKeepaliveConfig>,
+        tls: Option<TlsConfig>,
+        receive_buffer_bytes: Option<usize>,
+    },
+    #[cfg(unix)]
+    Unix { path: PathBuf },
+}
+
+inventory::submit! {
+    SourceDescription::new::<FluentConfig>("fluent")
+}
+
+impl GenerateConfig for FluentConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            mode: Mode::Tcp {
+                address: SocketListenAddr::SocketAddr("0.0.0.0:24224".parse().unwrap()),
+                keepalive: None,
+                tls: None,
+                receive_buffer_bytes: None,
+            },
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "fluent")]
+impl SourceConfig for FluentConfig {
+    async fn build(
+        &self,
+        _name: &str,
+        _globals: &GlobalOptions,
+        shutdown: ShutdownSignal,
+        out: Pipeline,
+    ) -> crate::Result<super::Source> {
+        match self.mode.clone() {
+            Mode::Tcp {
+                address,
+                keepalive,
+                tls,
+                receive_buffer_bytes,
+            } => {
+                let shutdown_secs = 30;
+                let tls = MaybeTlsSettings::from_config(&tls, true)?;
+                FluentTcpSource.run(
+                    address,
+                    keepalive,
+                    shutdown_secs,
+                    tls,
+                    receive_buffer_bytes,
+                    shutdown,
+                    out,
+                )
+            }
+            #[cfg(unix)]
+            Mode::Unix { path } => Ok(build_unix_stream_source_with_ack(
+                path,
+                FluentDecoder::default(),
+                log_schema().host_key().to_owned(),
+                shutdown,
+                out,
+                |_, received_from, frame| event_from_frame(frame, received_from),
+                build_ack,
+            )),
+        }
+    }
+
+    fn output_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    fn source_type(&self) -> &'static str {
+        "fluent"
+    }
+
+    fn resources(&self) -> Vec<Resource> {
+        match self.mode.clone() {
+            Mode::Tcp { address, .. } => vec![address.into()],
+            #[cfg(unix)]
+            Mode::Unix { .. } => vec![],
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct FluentTcpSource;
+
+impl TcpSource for FluentTcpSource {
+    type Error = DecodeError;
+    type Decoder = FluentDecoder;
+
+    fn decoder(&self) -> Self::Decoder {
+        FluentDecoder::default()
+    }
+
+    fn build_event(&self, frame: FluentFrame, host: Bytes) -> Option<Event> {
+        event_from_frame(frame, Some(host))
+    }
+
+    fn build_ack(&self, frame: &FluentFrame) -> Option<Bytes> {
+        build_ack(frame)
+    }
+}
+
+fn event_from_frame(frame: FluentFrame, host: Option<Bytes>) -> Option<Event> {
+    let entry = match frame {
+        FluentFrame::Entry(entry) => entry,
+        FluentFrame::Ack(_) => return None,
+    };
+
+    let mut log = LogEvent::from(entry.record);
+    log.insert(log_schema().timestamp_key(), entry.timestamp);
+    log.insert("tag", entry.tag);
+    log.insert(log_schema().source_type_key(), Bytes::from("fluent"));
+    if let Some(host) = host {
+        // A host set by the client in the record takes precedence.
+        if !log.contains(log_schema().host_key()) {
+            log.insert(log_schema().host_key(), host);
+        }
+    }
+    Some(Event::Log(log))
+}
+
+fn build_ack(frame: &FluentFrame) -> Option<Bytes> {
+    match frame {
+        FluentFrame::Entry(_) => None,
+        FluentFrame::Ack(chunk) => Some(encode_ack(chunk)),
+    }
+}
+
+/// The response to a message whose `chunk` option is set, once its entries
+/// were received.
+fn encode_ack(chunk: &str) -> Bytes {
+    let ack = rmpv::Value::Map(vec![(rmpv::Value::from("ack"), rmpv::Value::from(chunk))]);
+    let mut buf = Vec::new();
+    rmpv::encode::write_value(&mut buf, &ack).expect("Writing to Vec can't fail.");
+    buf.into()
+}
+
+/// The maximum size of a message, so that a client can't make us buffer
+/// arbitrary amounts of data.
+const MAX_MESSAGE_SIZE: usize = 16 * 1024 * 1024;
+/// The maximum size of the entries of a message in the
+/// CompressedPackedForward mode, once decompressed.
+const MAX_DECOMPRESSED_SIZE: u64 = 64 * 1024 * 1024;
+
+#[derive(Debug, Snafu)]
+enum DecodeError {
+    #[snafu(display("Failed to read from the connection: {}", source))]
+    Io { source: io::Error },
+    #[snafu(display("Invalid MessagePack: {}", source))]
+    Decode { source: rmpv::decode::Error },
+    #[snafu(display(
+        "Message of at least {} bytes exceeds the maximum size of {} bytes",
+        size,
+        max_length
+    ))]
+    MessageTooLarge { size: usize, max_length: usize },
+    #[snafu(display("Unexpected {}: {}", field, value))]
+    UnexpectedValue {
+        field: &'static str,
+        value: rmpv::Value,
+    },
+    #[snafu(display("Unknown compression of the entries: {}", compression))]
+    UnknownCompression { compression: String },
+    #[snafu(display("Failed to decompress the entries: {}", source))]
+    Decompress { source: io::Error },
+    #[snafu(display(
+        "The compressed entries exceed the maximum size of {} bytes once decompressed",
+        MAX_DECOMPRESSED_SIZE
+    ))]
+    DecompressedTooLarge,
+}
+
+impl From<io::Error> for DecodeError {
+    fn from(source: io::Error) -> Self {
+        Self::Io { source }
+    }
+}
+
+impl IsErrorFatal for DecodeError {
+    fn is_error_fatal() -> bool {
+        false
+    }
+}
+
+fn unexpected(field: &'static str, value: Option<rmpv::Value>) -> DecodeError {
+    DecodeError::UnexpectedValue {
+        field,
+        value: value.unwrap_or(rmpv::Value::Nil),
+    }
+}
+
+/// An entry of a message, or the request to acknowledge a message, which
+/// follows its entries.
+#[derive(Clone, Debug, PartialEq)]
+enum FluentFrame {
+    Entry(FluentEntry),
+    /// The `chunk` option of the message, to acknowledge it with. Messages
+    /// without entries are acknowledged too.
+    Ack(String),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct FluentEntry {
+    tag: String,
+    timestamp: DateTime<Utc>,
+    record: BTreeMap<String, Value>,
+}
+
+/// Decodes the messages of the Forward protocol, in any of its Message,
+/// Forward, PackedForward and CompressedPackedForward modes, into their
+/// entries.
+///
+/// See https://github.com/fluent/fluentd/wiki/Forward-Protocol-Specification-v1
+#[derive(Clone, Debug)]
+struct FluentDecoder {
+    unread_frames: VecDeque<FluentFrame>,
+    /// The maximum number of bytes buffered while waiting for the rest of a
+    /// message.
+    max_length: usize,
+}
+
+impl Default for FluentDecoder {
+    fn default() -> Self {
+        Self {
+            unread_frames: VecDeque::new(),
+            max_length: MAX_MESSAGE_SIZE,
+        }
+    }
+}
+
+impl FluentDecoder {
+    /// Queues the entries of the message, returning their count.
+    fn handle_message(&mut self, message: rmpv::Value) -> Result<usize, DecodeError> {
+        let mut fields = match message {
+            rmpv::Value::Array(fields) => fields.into_iter(),
+            message => return Err(unexpected("message", Some(message))),
+        };
+
+        let tag = match fields.next() {
+            Some(rmpv::Value::String(tag)) if tag.is_str() => tag.into_str().unwrap_or_default(),
+            tag => return Err(unexpected("tag", tag)),
+        };
+
+        let (entries, options) = match fields.next() {
+            // Forward mode: [tag, [[time, record], ...], option]
+            Some(rmpv::Value::Array(entries)) => {
+                let entries = entries
+                    .into_iter()
+                    .map(|entry| match entry {
+                        rmpv::Value::Array(entry) if entry.len() == 2 => {
+                            let mut entry = entry.into_iter();
+                            Ok((entry.next().unwrap(), entry.next().unwrap()))
+                        }
+                        entry => Err(unexpected("entry", Some(entry))),
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                (entries, Options::decode(fields.next())?)
+            }
+            // (Compressed)PackedForward mode: [tag, packed entries, option]
+            Some(rmpv::Value::String(entries)) => {
+                let options = Options::decode(fields.next())?;
+                (options.unpack(entries.as_bytes())?, options)
+            }
+            Some(rmpv::Value::Binary(entries)) => {
+                let options = Options::decode(fields.next())?;
+                (options.unpack(&entries)?, options)
+            }
+            // Message mode: [tag, time, record, option]
+            Some(time) => {
+                let record = fields.next().ok_or_else(|| unexpected("record", None))?;
+                (vec![(time, record)], Options::decode(fields.next())?)
+            }
+            None => return Err(unexpected("entries", None)),
+        };
+
+        let frames = entries
+            .into_iter()
+            .map(|(time, record)| {
+                Ok(FluentFrame::Entry(FluentEntry {
+                    tag: tag.clone(),
+                    timestamp: decode_time(time)?,
+                    record: decode_record(record)?,
+                }))
+            })
+            .collect::<Result<Vec<_>, DecodeError>>()?;
+
+        let count = frames.len();
+        self.unread_frames.extend(frames);
+        self.unread_frames
+            .extend(options.chunk.map(FluentFrame::Ack));
+        Ok(count)
+    }
+}
+
+impl Decoder for FluentDecoder {
+    type Item = FluentFrame;
+    type Error = DecodeError;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        loop {
+            if let Some(frame) = self.unread_frames.pop_front() {
+                return Ok(Some(frame));
+            }
+            if src.is_empty() {
+                return Ok(None);
+            }
+
+            let mut cursor = io::Cursor::new(&src[..]);
+            let result = rmpv::decode::read_value(&mut cursor);
+            let byte_size = cursor.position() as usize;
+            let message = match result {
+                Ok(message) => message,
+                // The rest of the message wasn't received yet.
+                Err(rmpv::decode::Error::InvalidMarkerRead(error))
+                | Err(rmpv::decode::Error::InvalidDataRead(error))
+                    if error.kind() == io::ErrorKind::UnexpectedEof =>
+                {
+                    if src.len() > self.max_length {
+                        // There's no telling where the message ends either.
+                        let size = src.len();
+                        src.clear();
+                        return Err(DecodeError::MessageTooLarge {
+                            size,
+                            max_length: self.max_length,
+                        });
+                    }
+                    return Ok(None);
+                }
+                // There's no telling where the next message starts.
+                Err(source) => {
+                    src.clear();
+                    return Err(DecodeError::Decode { source });
+                }
+            };
+            src.advance(byte_size);
+
+            let events_count = self.handle_message(message)?;
+            emit!(FluentMessageReceived {
+                events_count,
+                byte_size
+            });
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct Options {
+    chunk: Option<String>,
+    compressed: Option<String>,
+}
+
+impl Options {
+    fn decode(value: Option<rmpv::Value>) -> Result<Self, DecodeError> {
+        let mut options = Self::default();
+        match value {
+            None | Some(rmpv::Value::Nil) => (),
+            Some(rmpv::Value::Map(entries)) => {
+                for (key, value) in entries {
+                    match (key.as_str(), value) {
+                        (Some("chunk"), rmpv::Value::String(chunk)) => {
+                            options.chunk = chunk.into_str()
+                        }
+                        (Some("compressed"), rmpv::Value::String(compressed)) => {
+                            options.compressed = compressed.into_str()
+                        }
+                        _ => (),
+                    }
+                }
+            }
+            value => return Err(unexpected("options", value)),
+        }
+        Ok(options)
+    }
+
+    /// Decodes the MessagePack stream of entries, decompressing it first in
+    /// the CompressedPackedForward mode.
+    fn unpack(&self, entries: &[u8]) -> Result<Vec<(rmpv::Value, rmpv::Value)>, DecodeError> {
+        let decompressed;
+        let entries = match self.compressed.as_deref() {
+            None | Some("text") => entries,
+            Some("gzip") => {
+                let mut buf = Vec::new();
+                MultiGzDecoder::new(entries)
+                    .take(MAX_DECOMPRESSED_SIZE + 1)
+                    .read_to_end(&mut buf)
+                    .context(Decompress)?;
+                if buf.len() as u64 > MAX_DECOMPRESSED_SIZE {
+                    return Err(DecodeError::DecompressedTooLarge);
+                }
+                decompressed = buf;
+                &decompressed
+            }
+            Some(compression) => {
+                return Err(DecodeError::UnknownCompression {
+                    compression: compression.to_owned(),
+                })
+            }
+        };
+
+        let mut cursor = io::Cursor::new(entries);
+        let mut unpacked = Vec::new();
+        while (cursor.position() as usize) < entries.len() {
+            match rmpv::decode::read_value(&mut cursor).context(Decode)? {
+                rmpv::Value::Array(entry) if entry.len() == 2 => {
+                    let mut entry = entry.into_iter();
+                    unpacked.push((entry.next().unwrap(), entry.next().unwrap()));
+                }
+                entry => return Err(unexpected("entry", Some(entry))),
+            }
+        }
+        Ok(unpacked)
+    }
+}
+
+/// Times are either seconds since the epoch, or an `EventTime` extension
+/// holding the seconds and nanoseconds as big endian 32 bit integers.
+fn decode_time(time: rmpv::Value) -> Result<DateTime<Utc>, DecodeError> {
+    let timestamp = match &time {
+        rmpv::Value::Integer(seconds) => seconds
+            .as_i64()
+            .and_then(|seconds| Utc.timestamp_opt(seconds, 0).single()),
+        rmpv::Value::F64(seconds) => Utc
+            .timestamp_opt(
+                seconds.trunc() as i64,
+                (seconds.fract() * 1_000_000_000.0) as u32,
+            )
+            .single(),
+        rmpv::Value::Ext(0, bytes) if bytes.len() == 8 => {
+            let seconds = u32::from_be_bytes(bytes[..4].try_into().unwrap());
+            let nanos = u32::from_be_bytes(bytes[4..].try_into().unwrap());
+            Utc.timestamp_opt(seconds as i64, nanos).single()
+        }
+        _ => None,
+    };
+    timestamp.ok_or_else(|| unexpected("time", Some(time)))
+}
+
+fn decode_record(record: rmpv::Value) -> Result<BTreeMap<String, Value>, DecodeError> {
+    match record {
+        rmpv::Value::Map(fields) => Ok(decode_map(fields)),
+        record => Err(unexpected("record", Some(record))),
+    }
+}
+
+fn decode_map(fields: Vec<(rmpv::Value, rmpv::Value)>) -> BTreeMap<String, Value> {
+    fields
+        .into_iter()
+        .map(|(key, value)| {
+            let key = match key {
+                rmpv::Value::String(key) => String::from_utf8_lossy(key.as_bytes()).into_owned(),
+                key => key.to_string(),
+            };
+            (key, decode_value(value))
+        })
+        .collect()
+}
+
+fn decode_value(value: rmpv::Value) -> Value {
+    match value {
+        rmpv::Value::Nil => Value::Null,
+        rmpv::Value::Boolean(value) => Value::Boolean(value),
+        rmpv::Value::Integer(value) => value
+            .as_i64()
+            .map(Value::Integer)
+            .unwrap_or_else(|| Value::Float(value.as_f64().unwrap_or_default())),
+        rmpv::Value::F32(value) => Value::Float(value as f64),
+        rmpv::Value::F64(value) => Value::Float(value),
+        rmpv::Value::String(value) => Value::Bytes(value.into_bytes().into()),
+        rmpv::Value::Binary(value) => Value::Bytes(value.into()),
+        rmpv::Value::Array(values) => Value::Array(values.into_iter().map(decode_value).collect()),
+        rmpv::Value::Map(fields) => Value::Map(decode_map(fields)),
+        rmpv::Value::Ext(_, value) => Value::Bytes(value.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{next_addr, wait_for_tcp};
+    use flate2::{write::GzEncoder, Compression};
+    use std::io::Write;
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpStream,
+    };
+
+    fn encode(value: &rmpv::Value) -> Vec<u8> {
+        let mut buf = Vec::new();
+        rmpv::encode::write_value(&mut buf, value).unwrap();
+        buf
+    }
+
+    fn record(message: &str) -> rmpv::Value {
+        rmpv::Value::Map(vec![("message".into(), message.into())])
+    }
+
+    fn entry(time: u64, message: &str) -> rmpv::Value {
+        rmpv::Value::Array(vec![time.into(), record(message)])
+    }
+
+    fn chunk(id: &str) -> rmpv::Value {
+        rmpv::Value::Map(vec![("chunk".into(), id.into())])
+    }
+
+    fn decode_all(bytes: &[u8]) -> Vec<FluentFrame> {
+        let mut decoder = FluentDecoder::default();
+        let mut src = BytesMut::from(bytes);
+        let mut frames = Vec::new();
+        while let Some(frame) = decoder.decode(&mut src).unwrap() {
+            frames.push(frame);
+        }
+        assert!(src.is_empty());
+        frames
+    }
+
+    fn entry_frame(time: i64, message: &str) -> FluentEntry {
+        FluentEntry {
+            tag: "app.logs".to_owned(),
+            timestamp: Utc.timestamp(time, 0),
+            record: vec![("message".to_owned(), message.into())]
+                .into_iter()
+                .collect(),
+        }
+    }
+
+    fn frame(time: i64, message: &str) -> FluentFrame {
+        FluentFrame::Entry(entry_frame(time, message))
+    }
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<FluentConfig>();
+    }
+
+    #[test]
+    fn decodes_message_mode() {
+        let message = rmpv::Value::Array(vec![
+            "app.logs".into(),
+            rmpv::Value::Ext(0, vec![0, 0, 0, 1, 0, 0, 0, 2]),
+            record("hello"),
+            chunk("p8n9gmxTQVC8/nh2wlKKeQ=="),
+        ]);
+
+        let frames = decode_all(&encode(&message));
+
+        assert_eq!(
+            frames,
+            vec![
+                FluentFrame::Entry(FluentEntry {
+                    timestamp: Utc.timestamp(1, 2),
+                    ..entry_frame(0, "hello")
+                }),
+                FluentFrame::Ack("p8n9gmxTQVC8/nh2wlKKeQ==".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn decodes_forward_mode() {
+        let message = rmpv::Value::Array(vec![
+            "app.logs".into(),
+            rmpv::Value::Array(vec![entry(1, "first"), entry(2, "second")]),
+            chunk("chunk"),
+        ]);
+
+        let frames = decode_all(&encode(&message));
+
+        assert_eq!(
+            frames,
+            vec![
+                frame(1, "first"),
+                frame(2, "second"),
+                FluentFrame::Ack("chunk".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn acknowledges_messages_without_entries() {
+        let message = rmpv::Value::Array(vec![
+            "app.logs".into(),
+            rmpv::Value::Array(vec![]),
+            chunk("chunk"),
+        ]);
+
+        let frames = decode_all(&encode(&message));
+
+        assert_eq!(frames, vec![FluentFrame::Ack("chunk".to_owned())]);
+        assert_eq!(build_ack(&frames[0]), Some(encode_ack("chunk")));
+        assert_eq!(event_from_frame(frames[0].clone(), None), None);
+    }
+
+    #[test]
+    fn keeps_host_from_record() {
+        let frame = FluentFrame::Entry(FluentEntry {
+            record: vec![(log_schema().host_key().to_owned(), "client".into())]
+                .into_iter()
+                .collect(),
+            ..entry_frame(1, "hello")
+        });
+
+        let event = event_from_frame(frame, Some("127.0.0.1".into())).unwrap();
+        assert_eq!(event.as_log()[log_schema().host_key()], "client".into());
+    }
+
+    #[test]
+    fn decodes_packed_forward_modes() {
+        let mut entries = encode(&entry(1, "first"));
+        entries.extend(encode(&entry(2, "second")));
+        let packed = rmpv::Value::Array(vec![
+            "app.logs".into(),
+            rmpv::Value::Binary(entries.clone()),
+        ]);
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&entries).unwrap();
+        let compressed = rmpv::Value::Array(vec![
+            "app.logs".into(),
+            rmpv::Value::Binary(encoder.finish().unwrap()),
+            rmpv::Value::Map(vec![("compressed".into(), "gzip".into())]),
+        ]);
+
+        for message in &[packed, compressed] {
+            assert_eq!(
+                decode_all(&encode(message)),
+                vec![frame(1, "first"), frame(2, "second")]
+            );
+        }
+    }
+
+    #[test]
+    fn waits_for_the_whole_message() {
+        let message = encode(&rmpv::Value::Array(vec![
+            "app.logs".into(),
+            1.into(),
+            record("hello"),
+        ]));
+
+        let mut decoder = FluentDecoder::default();
+        let mut src = BytesMut::from(&message[..message.len() - 3]);
+        assert_eq!(decoder.decode(&mut src).unwrap(), None);
+
+        src.extend_from_slice(&message[message.len() - 3..]);
+        assert_eq!(decoder.decode(&mut src).unwrap(), Some(frame(1, "hello")));
+    }
+
+    #[test]
+    fn rejects_messages_that_are_too_large() {
+        let message = encode(&rmpv::Value::Array(vec![
+            "app.logs".into(),
+            1.into(),
+            record("hello"),
+        ]));
+
+        let mut decoder = FluentDecoder {
+            max_length: 8,
+            ..Default::default()
+        };
+        let mut src = BytesMut::from(&message[..message.len() - 1]);
+        assert!(matches!(
+            decoder.decode(&mut src),
+            Err(DecodeError::MessageTooLarge { max_length: 8, .. })
+        ));
+        assert!(src.is_empty());
+
+        // Whole messages are still decoded.
+        let mut src = BytesMut::from(&message[..]);
+        assert_eq!(decoder.decode(&mut src).unwrap(), Some(frame(1, "hello")));
+    }
+
+    #[test]
+    fn rejects_entries_that_decompress_too_large() {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        let zeros = vec![0; 1024 * 1024];
+        for _ in 0..=MAX_DECOMPRESSED_SIZE / zeros.len() as u64 {
+            encoder.write_all(&zeros).unwrap();
+        }
+        let message = encode(&rmpv::Value::Array(vec![
+            "app.logs".into(),
+            rmpv::Value::Binary(encoder.finish().unwrap()),
+            rmpv::Value::Map(vec![("compressed".into(), "gzip".into())]),
+        ]));
+
+        let mut decoder = FluentDecoder::default();
+        assert!(matches!(
+            decoder.decode(&mut BytesMut::from(&message[..])),
+            Err(DecodeError::DecompressedTooLarge)
+        ));
+    }
+
+    #[test]
+    fn rejects_invalid_records() {
+        let message = encode(&rmpv::Value::Array(vec![
+            "app.logs".into(),
+            1.into(),
+            "not a record".into(),
+        ]));
+
+        let mut decoder = FluentDecoder::default();
+        assert!(decoder.decode(&mut BytesMut::from(&message[..])).is_err());
+    }
+
+    #[tokio::test]
+    async fn acknowledges_chunks_over_tcp() {
+        let (tx, mut rx) = Pipeline::new_test();
+        let addr = next_addr();
+
+        let config: FluentConfig = toml::from_str(&format!(
+            r#"mode = "tcp"
+            address = "{}""#,
+            addr
+        ))
+        .unwrap();
+        let server = config
+            .build(
+                "default",
+                &GlobalOptions::default(),
+                ShutdownSignal::noop(),
+                tx,
+            )
+            .await
+            .unwrap();
+        tokio::spawn(server);
+        wait_for_tcp(addr).await;
+
+        let message = rmpv::Value::Array(vec![
+            "app.logs".into(),
+            rmpv::Value::Array(vec![entry(1, "first"), entry(2, "second")]),
+            chunk("chunk"),
+        ]);
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        stream.write_all(&encode(&message)).await.unwrap();
+
+        let expected = encode_ack("chunk");
+        let mut ack = vec![0; expected.len()];
+        stream.read_exact(&mut ack).await.unwrap();
+        assert_eq!(ack, expected);
+
+        for message in &["first", "second"] {
+            let event = rx.recv().await.unwrap();
+            let log = event.as_log();
+            assert_eq!(log[log_schema().message_key()], (*message).into());
+            assert_eq!(log["tag"], "app.logs".into());
+            assert_eq!(log[log_schema().host_key()], "127.0.0.1".into());
+            assert_eq!(log[log_schema().source_type_key()], "fluent".into());
+        }
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn acknowledges_chunks_over_unix() {
+        let (tx, mut rx) = Pipeline::new_test();
+        let path = tempfile::tempdir().unwrap().into_path().join("fluent");
+
+        let config: FluentConfig = toml::from_str(&format!(
+            r#"mode = "unix"
+            path = "{}""#,
+            path.display()
+        ))
+        .unwrap();
+        let server = config
+            .build(
+                "default",
+                &GlobalOptions::default(),
+                ShutdownSignal::noop(),
+                tx,
+            )
+            .await
+            .unwrap();
+        tokio::spawn(server);
+        while std::os::unix::net::UnixStream::connect(&path).is_err() {
+            tokio::task::yield_now().await;
+        }
+
+        let message = rmpv::Value::Array(vec![
+            "app.logs".into(),
+            rmpv::Value::Array(vec![entry(1, "first")]),
+            chunk("chunk"),
+        ]);
+        let mut stream = tokio::net::UnixStream::connect(&path).await.unwrap();
+        stream.write_all(&encode(&message)).await.unwrap();
+
+        let expected = encode_ack("chunk");
+        let mut ack = vec![0; expected.len()];
+        stream.read_exact(&mut ack).await.unwrap();
+        assert_eq!(ack, expected);
+
+        let event = rx.recv().await.unwrap();
+        assert_eq!(event.as_log()[log_schema().message_key()], "first".into());
+    }
+}
@@ -6,7 +6,8 @@ use crate::{
     event::{Event, Value},
     shutdown::ShutdownSignal,
     sources::util::{
-        add_query_parameters, decode_body, Encoding, ErrorMessage, HttpSource, HttpSourceAuthConfig,
+        add_query_parameters, decode_body, Decoder, DecodingConfig, Encoding, ErrorMessage,
+        FramingConfig, HttpSource, HttpSourceAuthConfig,
     },
     tls::TlsConfig,
     Pipeline,
@@ -34,6 +35,8 @@ pub struct SimpleHttpConfig {
     path: String,
     #[serde(default = "default_path_key")]
     path_key: String,
+    framing: Option<FramingConfig>,
+    decoding: Option<DecodingConfig>,
 }
 
 inventory::submit! {
@@ -52,6 +55,8 @@ impl GenerateConfig for SimpleHttpConfig {
             path_key: "path".to_string(),
             path: "/".to_string(),
             strict_path: true,
+            framing: None,
+            decoding: None,
         })
         .unwrap()
     }
@@ -68,6 +73,8 @@ pub fn default_path_key() -> String {
 #[derive(Clone)]
 struct SimpleHttpSource {
     encoding: Encoding,
+    /// Overrides the encoding when either framing or decoding is configured.
+    decoding: Option<(FramingConfig, DecodingConfig)>,
     headers: Vec<String>,
     query_parameters: Vec<String>,
     path_key: String,
@@ -81,7 +88,17 @@ impl HttpSource for SimpleHttpSource {
         query_parameters: HashMap<String, String>,
         request_path: &str,
     ) -> Result<Vec<Event>, ErrorMessage> {
-        decode_body(body, self.encoding)
+        let events = match &self.decoding {
+            Some((framing, decoding)) => {
+                let framer = framing
+                    .build()
+                    .expect("Framing is validated when the source is built.");
+                Ok(Decoder::new(framer, *decoding).decode_all(body))
+            }
+            None => decode_body(body, self.encoding),
+        };
+
+        events
             .map(|events| add_headers(events, &self.headers, header_map))
             .map(|events| add_query_parameters(events, &self.query_parameters, query_parameters))
             .map(|events| add_path(events, self.path_key.as_str(), request_path))
@@ -89,7 +106,9 @@ impl HttpSource for SimpleHttpSource {
                 // Add source type
                 let key = log_schema().source_type_key();
                 for event in events.iter_mut() {
-                    event.as_mut_log().try_insert(key, Bytes::from("http"));
+                    if let Event::Log(log) = event {
+                        log.try_insert(key, Bytes::from("http"));
+                    }
                 }
                 events
             })
@@ -106,8 +125,18 @@ impl SourceConfig for SimpleHttpConfig {
         shutdown: ShutdownSignal,
         out: Pipeline,
     ) -> crate::Result<super::Source> {
+        let decoding = match (&self.framing, self.decoding) {
+            (None, None) => None,
+            // The whole body is a single frame unless configured otherwise.
+            (framing, decoding) => {
+                let framing = framing.clone().unwrap_or(FramingConfig::Bytes);
+                framing.build()?;
+                Some((framing, decoding.unwrap_or_default()))
+            }
+        };
         let source = SimpleHttpSource {
             encoding: self.encoding,
+            decoding,
             headers: self.headers.clone(),
             query_parameters: self.query_parameters.clone(),
             path_key: self.path_key.clone(),
@@ -124,7 +153,8 @@ impl SourceConfig for SimpleHttpConfig {
     }
 
     fn output_type(&self) -> DataType {
-        DataType::Log
+        self.decoding
+            .map_or(DataType::Log, |decoding| decoding.output_type())
     }
 
     fn source_type(&self) -> &'static str {
@@ -138,9 +168,9 @@ impl SourceConfig for SimpleHttpConfig {
 
 fn add_path(mut events: Vec<Event>, key: &str, path: &str) -> Vec<Event> {
     for event in events.iter_mut() {
-        event
-            .as_mut_log()
-            .insert(key, Value::from(path.to_string()));
+        if let Event::Log(log) = event {
+            log.insert(key, Value::from(path.to_string()));
+        }
     }
 
     events
@@ -155,10 +185,12 @@ fn add_headers(
         let value = headers.get(header_name).map(HeaderValue::as_bytes);
 
         for event in events.iter_mut() {
-            event.as_mut_log().insert(
-                header_name as &str,
-                Value::from(value.map(Bytes::copy_from_slice)),
-            );
+            if let Event::Log(log) = event {
+                log.insert(
+                    header_name as &str,
+                    Value::from(value.map(Bytes::copy_from_slice)),
+                );
+            }
         }
     }
 
@@ -168,6 +200,7 @@ fn add_headers(
 #[cfg(test)]
 mod tests {
     use super::{Encoding, SimpleHttpConfig};
+    use crate::sources::util::{DecodingConfig, FramingConfig};
 
     use crate::shutdown::ShutdownSignal;
     use crate::{
@@ -215,6 +248,8 @@ mod tests {
                 strict_path,
                 path_key,
                 path,
+                framing: None,
+                decoding: None,
             }
             .build(
                 "default",
@@ -400,6 +435,53 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn http_decodes_json_frames() {
+        trace_init();
+
+        let (sender, rx) = Pipeline::new_test();
+        let address = next_addr();
+        tokio::spawn(async move {
+            SimpleHttpConfig {
+                address,
+                encoding: Encoding::default(),
+                headers: vec![],
+                query_parameters: vec![],
+                tls: None,
+                auth: None,
+                strict_path: true,
+                path_key: "http_path".to_owned(),
+                path: "/".to_owned(),
+                framing: Some(FramingConfig::NewlineDelimited { max_length: None }),
+                decoding: Some(DecodingConfig::Json),
+            }
+            .build(
+                "default",
+                &GlobalOptions::default(),
+                ShutdownSignal::noop(),
+                sender,
+            )
+            .await
+            .unwrap()
+            .await
+            .unwrap();
+        });
+        wait_for_tcp(address).await;
+
+        assert_eq!(
+            200,
+            send(address, "{\"key\":\"one\"}\nnot json\n{\"key\":\"two\"}").await
+        );
+
+        let events = collect_n(rx, 2).await;
+        let log = events[0].as_log();
+        assert_eq!(log["key"], "one".into());
+        assert!(log.get(log_schema().timestamp_key()).is_some());
+        assert_eq!(log[log_schema().source_type_key()], "http".into());
+        assert_eq!(log["http_path"], "/".into());
+        assert_eq!(events[1].as_log()["key"], "two".into());
+    }
+
     #[tokio::test]
     async fn http_json_dotted_keys() {
         trace_init();
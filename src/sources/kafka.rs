@@ -1,9 +1,12 @@
 use crate::{
     config::{log_schema, DataType, GlobalOptions, SourceConfig, SourceDescription},
-    event::{Event, Value},
-    internal_events::{KafkaEventFailed, KafkaEventReceived, KafkaOffsetUpdateFailed},
+    event::{BatchNotifier, BatchStatus, BatchStatusReceiver, Event, Value},
+    internal_events::{
+        KafkaEventFailed, KafkaEventNotAcknowledged, KafkaEventReceived, KafkaOffsetUpdateFailed,
+    },
     kafka::KafkaAuthConfig,
     shutdown::ShutdownSignal,
+    sources::util::{Decoder, DecodingConfig, FramingConfig},
     Pipeline,
 };
 use bytes::Bytes;
@@ -13,10 +16,15 @@ use rdkafka::{
     config::ClientConfig,
     consumer::{Consumer, StreamConsumer},
     message::Message,
+    Offset, TopicPartitionList,
 };
 use serde::{Deserialize, Serialize};
 use snafu::{ResultExt, Snafu};
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
+use tokio::sync::mpsc;
 
 #[derive(Debug, Snafu)]
 enum BuildError {
@@ -49,6 +57,10 @@ pub struct KafkaSourceConfig {
     librdkafka_options: Option<HashMap<String, String>>,
     #[serde(flatten)]
     auth: KafkaAuthConfig,
+    #[serde(default)]
+    acknowledgements: bool,
+    framing: Option<FramingConfig>,
+    decoding: Option<DecodingConfig>,
 }
 
 fn default_session_timeout_ms() -> u64 {
@@ -91,7 +103,8 @@ impl SourceConfig for KafkaSourceConfig {
     }
 
     fn output_type(&self) -> DataType {
-        DataType::Log
+        self.decoding
+            .map_or(DataType::Log, |decoding| decoding.output_type())
     }
 
     fn source_type(&self) -> &'static str {
@@ -108,8 +121,24 @@ fn kafka_source(
     let topic_key = config.topic_key.clone();
     let partition_key = config.partition_key.clone();
     let offset_key = config.offset_key.clone();
+    // Each message is a single frame unless configured otherwise.
+    let framing = config.framing.clone().unwrap_or(FramingConfig::Bytes);
+    framing.build()?;
+    let decoding = config.decoding.unwrap_or_default();
     let consumer = Arc::new(create_consumer(config)?);
 
+    // With acknowledgements enabled, offsets are only stored once the events
+    // read from them have been delivered. Acks are processed in the order the
+    // messages were read, so an offset is never stored ahead of an earlier
+    // message that is still in flight.
+    let acker = if config.acknowledgements {
+        let (acker, pending) = mpsc::unbounded_channel();
+        tokio::spawn(store_acked_offsets(Arc::clone(&consumer), pending));
+        Some(acker)
+    } else {
+        None
+    };
+
     Ok(Box::pin(async move {
         Arc::clone(&consumer)
             .start()
@@ -119,7 +148,9 @@ fn kafka_source(
                 let topic_key = topic_key.clone();
                 let partition_key = partition_key.clone();
                 let offset_key = offset_key.clone();
+                let framing = framing.clone();
                 let consumer = Arc::clone(&consumer);
+                let acker = acker.clone();
 
                 async move {
                     match message {
@@ -136,54 +167,81 @@ fn kafka_source(
                                 None => return Err(()), // skip messages with empty payload
                                 Some(payload) => payload,
                             };
-                            let mut event = Event::new_empty_log();
-                            let log = event.as_mut_log();
-
-                            log.insert(
-                                log_schema().message_key(),
-                                Value::from(Bytes::from(payload.to_owned())),
-                            );
-
                             // Extract timestamp from kafka message
                             let timestamp = msg
                                 .timestamp()
                                 .to_millis()
                                 .and_then(|millis| Utc.timestamp_millis_opt(millis).latest())
                                 .unwrap_or_else(Utc::now);
-                            log.insert(log_schema().timestamp_key(), timestamp);
-
-                            // Add source type
-                            log.insert(log_schema().source_type_key(), Bytes::from("kafka"));
-
-                            if let Some(key_field) = &key_field {
-                                match msg.key() {
-                                    None => (),
-                                    Some(key) => {
-                                        log.insert(
-                                            key_field,
-                                            Value::from(String::from_utf8_lossy(key).to_string()),
-                                        );
+
+                            let framer = framing
+                                .build()
+                                .expect("Framing is validated when the source is built.");
+                            let mut events = Decoder::new(framer, decoding)
+                                .decode_all(Bytes::from(payload.to_owned()));
+
+                            for event in events.iter_mut() {
+                                let log = match event {
+                                    Event::Log(log) => log,
+                                    _ => continue,
+                                };
+
+                                log.insert(log_schema().timestamp_key(), timestamp);
+
+                                // Add source type
+                                log.insert(log_schema().source_type_key(), Bytes::from("kafka"));
+
+                                if let Some(key_field) = &key_field {
+                                    match msg.key() {
+                                        None => (),
+                                        Some(key) => {
+                                            log.insert(
+                                                key_field,
+                                                Value::from(
+                                                    String::from_utf8_lossy(key).to_string(),
+                                                ),
+                                            );
+                                        }
                                     }
                                 }
-                            }
 
-                            if let Some(topic_key) = &topic_key {
-                                log.insert(topic_key, Value::from(msg.topic().to_string()));
-                            }
+                                if let Some(topic_key) = &topic_key {
+                                    log.insert(topic_key, Value::from(msg.topic().to_string()));
+                                }
 
-                            if let Some(partition_key) = &partition_key {
-                                log.insert(partition_key, Value::from(msg.partition()));
-                            }
+                                if let Some(partition_key) = &partition_key {
+                                    log.insert(partition_key, Value::from(msg.partition()));
+                                }
 
-                            if let Some(offset_key) = &offset_key {
-                                log.insert(offset_key, Value::from(msg.offset()));
+                                if let Some(offset_key) = &offset_key {
+                                    log.insert(offset_key, Value::from(msg.offset()));
+                                }
                             }
 
-                            consumer.store_offset(&msg).map_err(|error| {
-                                emit!(KafkaOffsetUpdateFailed { error });
-                            })?;
+                            match acker {
+                                Some(acker) => {
+                                    // The offset is stored once all the events of the
+                                    // message are delivered.
+                                    let (batch, receiver) = BatchNotifier::new_with_receiver();
+                                    for event in events.iter_mut() {
+                                        event.add_batch_notifier(Arc::clone(&batch));
+                                    }
+                                    let offset = PendingOffset {
+                                        topic: msg.topic().to_string(),
+                                        partition: msg.partition(),
+                                        offset: msg.offset(),
+                                    };
+                                    // The ack task only stops once this source is gone.
+                                    let _ = acker.send((receiver, offset));
+                                }
+                                None => {
+                                    consumer.store_offset(&msg).map_err(|error| {
+                                        emit!(KafkaOffsetUpdateFailed { error });
+                                    })?;
+                                }
+                            }
 
-                            Ok(event)
+                            Ok(events)
                         }
                     }
                 }
@@ -197,8 +255,8 @@ fn kafka_source(
             .for_each(|item| {
                 let mut out = out.clone();
                 async move {
-                    if let Ok(item) = item {
-                        if let Err(error) = out.send(item).await {
+                    for event in item.into_iter().flatten() {
+                        if let Err(error) = out.send(event).await {
                             error!(message = "Error sending to sink.", %error);
                         }
                     }
@@ -209,6 +267,68 @@ fn kafka_source(
     }))
 }
 
+#[derive(Debug, PartialEq)]
+struct PendingOffset {
+    topic: String,
+    partition: i32,
+    offset: i64,
+}
+
+async fn store_acked_offsets(
+    consumer: Arc<StreamConsumer>,
+    pending: mpsc::UnboundedReceiver<(BatchStatusReceiver, PendingOffset)>,
+) {
+    let mut offsets = AckedOffsets::default();
+    pending
+        .then(|(receiver, offset)| async move { (receiver.await, offset) })
+        .for_each(|(status, offset)| {
+            if let Some(next) = offsets.next_offset(status, offset) {
+                let mut tpl = TopicPartitionList::new();
+                tpl.add_partition_offset(&next.topic, next.partition, Offset::Offset(next.offset));
+                if let Err(error) = consumer.store_offsets(&tpl) {
+                    emit!(KafkaOffsetUpdateFailed { error });
+                }
+            }
+            futures::future::ready(())
+        })
+        .await;
+}
+
+/// The partitions whose offsets can't be stored anymore. Once a message isn't delivered,
+/// storing the offset of a later message of the same partition would commit past it, so
+/// the partition's offsets stay where they are until the source restarts.
+#[derive(Default)]
+struct AckedOffsets {
+    failed: HashSet<(String, i32)>,
+}
+
+impl AckedOffsets {
+    /// Returns the offset to store once the events of the message at `offset` were processed,
+    /// if any.
+    fn next_offset(&mut self, status: BatchStatus, offset: PendingOffset) -> Option<PendingOffset> {
+        let partition = (offset.topic, offset.partition);
+        match status {
+            BatchStatus::Delivered if !self.failed.contains(&partition) => Some(PendingOffset {
+                topic: partition.0,
+                partition: partition.1,
+                // Like `store_offset`, store the offset of the next message to read.
+                offset: offset.offset + 1,
+            }),
+            BatchStatus::Delivered => None,
+            status => {
+                emit!(KafkaEventNotAcknowledged {
+                    status,
+                    topic: &partition.0,
+                    partition: partition.1,
+                    offset: offset.offset,
+                });
+                self.failed.insert(partition);
+                None
+            }
+        }
+    }
+}
+
 fn create_consumer(config: &KafkaSourceConfig) -> crate::Result<StreamConsumer> {
     let mut client_config = ClientConfig::new();
     client_config
@@ -244,8 +364,10 @@ fn create_consumer(config: &KafkaSourceConfig) -> crate::Result<StreamConsumer>
 
 #[cfg(test)]
 mod test {
-    use super::{kafka_source, KafkaSourceConfig};
-    use crate::{shutdown::ShutdownSignal, Pipeline};
+    use super::{kafka_source, AckedOffsets, KafkaSourceConfig, PendingOffset};
+    use crate::{
+        event::BatchStatus, shutdown::ShutdownSignal, sources::util::FramingConfig, Pipeline,
+    };
 
     #[test]
     fn generate_config() {
@@ -284,6 +406,48 @@ mod test {
         };
         assert!(kafka_source(&config, ShutdownSignal::noop(), Pipeline::new_test().0).is_err());
     }
+
+    #[test]
+    fn kafka_source_create_invalid_framing() {
+        let config = KafkaSourceConfig {
+            framing: Some(FramingConfig::CharacterDelimited {
+                delimiter: 'é',
+                max_length: None,
+            }),
+            ..make_config()
+        };
+        assert!(kafka_source(&config, ShutdownSignal::noop(), Pipeline::new_test().0).is_err());
+    }
+
+    fn pending_offset(partition: i32, offset: i64) -> PendingOffset {
+        PendingOffset {
+            topic: "my-topic".to_string(),
+            partition,
+            offset,
+        }
+    }
+
+    #[test]
+    fn kafka_offsets_not_stored_past_failed_message() {
+        let mut offsets = AckedOffsets::default();
+
+        assert_eq!(
+            offsets.next_offset(BatchStatus::Delivered, pending_offset(0, 1)),
+            Some(pending_offset(0, 2))
+        );
+        assert_eq!(
+            offsets.next_offset(BatchStatus::Failed, pending_offset(0, 2)),
+            None
+        );
+        assert_eq!(
+            offsets.next_offset(BatchStatus::Delivered, pending_offset(0, 3)),
+            None
+        );
+        assert_eq!(
+            offsets.next_offset(BatchStatus::Delivered, pending_offset(1, 3)),
+            Some(pending_offset(1, 4))
+        );
+    }
 }
 
 #[cfg(feature = "kafka-integration-tests")]
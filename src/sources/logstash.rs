@@ -0,0 +1,674 @@
+use super::util::{IsErrorFatal, SocketListenAddr, TcpSource};
+use crate::{
+    config::{
+        log_schema, DataType, GenerateConfig, GlobalOptions, Resource, SourceConfig,
+        SourceDescription,
+    },
+    event::{Event, LogEvent, Value},
+    internal_events::LogstashEventReceived,
+    shutdown::ShutdownSignal,
+    tcp::TcpKeepaliveConfig,
+    tls::{MaybeTlsSettings, TlsConfig},
+    Pipeline,
+};
+use bytes::{Buf, Bytes, BytesMut};
+use chrono::{DateTime, Utc};
+use flate2::read::ZlibDecoder;
+use serde::{Deserialize, Serialize};
+use snafu::{ResultExt, Snafu};
+use std::{
+    collections::{BTreeMap, VecDeque},
+    convert::TryInto,
+    io::{self, Read},
+};
+use tokio_util::codec::Decoder;
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct LogstashConfig {
+    address: SocketListenAddr,
+    keepalive: Option<TcpKeepaliveConfig>,
+    tls: Option<TlsConfig>,
+    receive_buffer_bytes: Option<usize>,
+}
+
+inventory::submit! {
+    SourceDescription::new::<LogstashConfig>("logstash")
+}
+
+impl GenerateConfig for LogstashConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            address: SocketListenAddr::SocketAddr("0.0.0.0:5044".parse().unwrap()),
+            keepalive: None,
+            tls: None,
+            receive_buffer_bytes: None,
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "logstash")]
+impl SourceConfig for LogstashConfig {
+    async fn build(
+        &self,
+        _name: &str,
+        _globals: &GlobalOptions,
+        shutdown: ShutdownSignal,
+        out: Pipeline,
+    ) -> crate::Result<super::Source> {
+        let shutdown_secs = 30;
+        let tls = MaybeTlsSettings::from_config(&self.tls, true)?;
+        LogstashSource.run(
+            self.address,
+            self.keepalive,
+            shutdown_secs,
+            tls,
+            self.receive_buffer_bytes,
+            shutdown,
+            out,
+        )
+    }
+
+    fn output_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    fn source_type(&self) -> &'static str {
+        "logstash"
+    }
+
+    fn resources(&self) -> Vec<Resource> {
+        vec![self.address.into()]
+    }
+}
+
+#[derive(Debug, Clone)]
+struct LogstashSource;
+
+impl TcpSource for LogstashSource {
+    type Error = DecodeError;
+    type Decoder = LogstashDecoder;
+
+    fn decoder(&self) -> Self::Decoder {
+        LogstashDecoder::default()
+    }
+
+    fn build_event(&self, frame: LogstashFrame, host: Bytes) -> Option<Event> {
+        let fields = frame.fields?;
+        emit!(LogstashEventReceived {
+            byte_size: frame.byte_size
+        });
+
+        let mut log = LogEvent::from(fields);
+
+        // Beats events are timestamped by their `@timestamp` field.
+        let timestamp = match log.get("@timestamp") {
+            Some(Value::Bytes(timestamp)) => std::str::from_utf8(timestamp)
+                .ok()
+                .and_then(|timestamp| DateTime::parse_from_rfc3339(timestamp).ok())
+                .map(|timestamp| timestamp.with_timezone(&Utc)),
+            _ => None,
+        };
+        log.insert(
+            log_schema().timestamp_key(),
+            timestamp.unwrap_or_else(Utc::now),
+        );
+        log.insert(log_schema().source_type_key(), Bytes::from("logstash"));
+        // Beats set their own `host` field.
+        log.try_insert(log_schema().host_key(), host);
+
+        Some(Event::Log(log))
+    }
+
+    fn build_ack(&self, frame: &LogstashFrame) -> Option<Bytes> {
+        frame.ack.map(|sequence| {
+            let mut ack = Vec::with_capacity(6);
+            ack.extend_from_slice(&[PROTOCOL_VERSION, ACK_FRAME]);
+            ack.extend_from_slice(&sequence.to_be_bytes());
+            ack.into()
+        })
+    }
+}
+
+const PROTOCOL_VERSION: u8 = b'2';
+const WINDOW_SIZE_FRAME: u8 = b'W';
+const JSON_FRAME: u8 = b'J';
+const DATA_FRAME: u8 = b'D';
+const COMPRESSED_FRAME: u8 = b'C';
+const ACK_FRAME: u8 = b'A';
+
+/// The maximum size of a frame, so that a peer can't make us buffer
+/// arbitrary amounts of data.
+const MAX_FRAME_SIZE: usize = 16 * 1024 * 1024;
+/// The maximum size of the frames held by a compressed frame, once
+/// decompressed.
+const MAX_DECOMPRESSED_SIZE: u64 = 64 * 1024 * 1024;
+/// Larger windows are acknowledged in parts of this many events.
+const MAX_WINDOW_SIZE: u32 = 16 * 1024;
+
+#[derive(Debug, Snafu)]
+enum DecodeError {
+    #[snafu(display("Failed to read from the connection: {}", source))]
+    Io { source: io::Error },
+    #[snafu(display("The frame wasn't received whole"))]
+    Incomplete,
+    #[snafu(display("Unsupported protocol version: {}", version))]
+    UnsupportedVersion { version: u8 },
+    #[snafu(display("Unknown frame type: {}", frame_type))]
+    UnknownFrameType { frame_type: u8 },
+    #[snafu(display("Invalid JSON payload: {}", source))]
+    Json { source: serde_json::Error },
+    #[snafu(display(
+        "Frame of at least {} bytes exceeds the maximum size of {} bytes",
+        size,
+        MAX_FRAME_SIZE
+    ))]
+    FrameTooLarge { size: usize },
+    #[snafu(display("Failed to decompress the frames: {}", source))]
+    Decompress { source: io::Error },
+    #[snafu(display(
+        "The compressed frames exceed the maximum size of {} bytes once decompressed",
+        MAX_DECOMPRESSED_SIZE
+    ))]
+    DecompressedTooLarge,
+    #[snafu(display("The compressed frames end with an incomplete frame"))]
+    TruncatedCompressedFrames,
+}
+
+impl From<io::Error> for DecodeError {
+    fn from(source: io::Error) -> Self {
+        Self::Io { source }
+    }
+}
+
+impl IsErrorFatal for DecodeError {
+    fn is_error_fatal() -> bool {
+        false
+    }
+}
+
+/// An event sent in a data frame.
+#[derive(Clone, Debug, PartialEq)]
+struct LogstashFrame {
+    /// Unset for a data frame whose payload couldn't be decoded, which is
+    /// only passed on to acknowledge its window.
+    fields: Option<BTreeMap<String, Value>>,
+    byte_size: usize,
+    /// Set on the last event of a window, to acknowledge its sequence number.
+    ack: Option<u32>,
+}
+
+#[derive(Debug)]
+enum Frame {
+    WindowSize(u32),
+    Data {
+        sequence: u32,
+        fields: Result<BTreeMap<String, Value>, serde_json::Error>,
+        byte_size: usize,
+    },
+    Compressed(Vec<u8>),
+}
+
+/// Decodes the frames of the Lumberjack v2 protocol. The client sends the
+/// size of the window of events to expect, followed by their data frames,
+/// which are acknowledged once the whole window was received.
+///
+/// See https://github.com/elastic/go-lumber
+#[derive(Clone, Debug, Default)]
+struct LogstashDecoder {
+    window_size: u32,
+    received: u32,
+    unread_frames: VecDeque<LogstashFrame>,
+    /// The remaining bytes of a frame that is too large, to discard.
+    skip: usize,
+}
+
+impl LogstashDecoder {
+    fn handle_frame(&mut self, frame: Frame) -> Result<(), DecodeError> {
+        match frame {
+            Frame::WindowSize(size) => {
+                self.window_size = size.min(MAX_WINDOW_SIZE);
+                self.received = 0;
+            }
+            Frame::Data {
+                sequence,
+                fields,
+                byte_size,
+            } => {
+                self.received += 1;
+                let ack = if self.received >= self.window_size {
+                    self.received = 0;
+                    Some(sequence)
+                } else {
+                    None
+                };
+
+                match fields {
+                    Ok(fields) => self.unread_frames.push_back(LogstashFrame {
+                        fields: Some(fields),
+                        byte_size,
+                        ack,
+                    }),
+                    // Only this frame is skipped, its window is still acknowledged.
+                    Err(source) => {
+                        if ack.is_some() {
+                            self.unread_frames.push_back(LogstashFrame {
+                                fields: None,
+                                byte_size,
+                                ack,
+                            });
+                        }
+                        return Err(source).context(Json);
+                    }
+                }
+            }
+            Frame::Compressed(payload) => {
+                let mut decompressed = Vec::new();
+                ZlibDecoder::new(&payload[..])
+                    .take(MAX_DECOMPRESSED_SIZE + 1)
+                    .read_to_end(&mut decompressed)
+                    .context(Decompress)?;
+                if decompressed.len() as u64 > MAX_DECOMPRESSED_SIZE {
+                    return Err(DecodeError::DecompressedTooLarge);
+                }
+
+                let mut skipped = None;
+                let mut frames = &decompressed[..];
+                while !frames.is_empty() {
+                    let (frame, len) = match parse_frame(frames) {
+                        Err(DecodeError::Incomplete) => {
+                            return Err(DecodeError::TruncatedCompressedFrames)
+                        }
+                        result => result?,
+                    };
+                    frames = &frames[len..];
+                    match self.handle_frame(frame) {
+                        // The following frames are still decoded.
+                        Err(error @ DecodeError::Json { .. }) => {
+                            skipped.get_or_insert(error);
+                        }
+                        result => result?,
+                    }
+                }
+                if let Some(error) = skipped {
+                    return Err(error);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Decoder for LogstashDecoder {
+    type Item = LogstashFrame;
+    type Error = DecodeError;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        loop {
+            if let Some(frame) = self.unread_frames.pop_front() {
+                return Ok(Some(frame));
+            }
+
+            if self.skip > 0 {
+                let len = self.skip.min(src.len());
+                src.advance(len);
+                self.skip -= len;
+                if self.skip > 0 {
+                    return Ok(None);
+                }
+            }
+
+            let (frame, len) = match parse_frame(&src[..]) {
+                Ok(parsed) => parsed,
+                Err(DecodeError::Incomplete) => return Ok(None),
+                // The frame is discarded as it's received, instead of buffered.
+                Err(DecodeError::FrameTooLarge { size }) => {
+                    self.skip = size;
+                    return Err(DecodeError::FrameTooLarge { size });
+                }
+                // There's no telling where the next frame starts.
+                Err(error) => {
+                    src.clear();
+                    return Err(error);
+                }
+            };
+            src.advance(len);
+            self.handle_frame(frame)?;
+        }
+    }
+}
+
+/// Parses the frame at the start of the buffer, returning it along with its
+/// length.
+fn parse_frame(src: &[u8]) -> Result<(Frame, usize), DecodeError> {
+    let mut reader = Reader { src, position: 0 };
+
+    let version = reader.u8()?;
+    if version != PROTOCOL_VERSION {
+        return Err(DecodeError::UnsupportedVersion { version });
+    }
+
+    let frame = match reader.u8()? {
+        WINDOW_SIZE_FRAME => Frame::WindowSize(reader.u32()?),
+        JSON_FRAME => {
+            let sequence = reader.u32()?;
+            let len = reader.u32()? as usize;
+            let payload = reader.bytes(len)?;
+            Frame::Data {
+                sequence,
+                fields: serde_json::from_slice::<BTreeMap<String, serde_json::Value>>(payload).map(
+                    |fields| {
+                        fields
+                            .into_iter()
+                            .map(|(key, value)| (key, value.into()))
+                            .collect()
+                    },
+                ),
+                byte_size: len,
+            }
+        }
+        DATA_FRAME => {
+            let sequence = reader.u32()?;
+            let pairs = reader.u32()?;
+            let start = reader.position;
+            let mut fields = BTreeMap::new();
+            for _ in 0..pairs {
+                let len = reader.u32()? as usize;
+                let key = String::from_utf8_lossy(reader.bytes(len)?).into_owned();
+                let len = reader.u32()? as usize;
+                let value = Bytes::copy_from_slice(reader.bytes(len)?);
+                fields.insert(key, Value::Bytes(value));
+            }
+            Frame::Data {
+                sequence,
+                fields: Ok(fields),
+                byte_size: reader.position - start,
+            }
+        }
+        COMPRESSED_FRAME => {
+            let len = reader.u32()? as usize;
+            Frame::Compressed(reader.bytes(len)?.to_vec())
+        }
+        frame_type => return Err(DecodeError::UnknownFrameType { frame_type }),
+    };
+
+    Ok((frame, reader.position))
+}
+
+struct Reader<'a> {
+    src: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn bytes(&mut self, len: usize) -> Result<&'a [u8], DecodeError> {
+        let size = self.position.saturating_add(len);
+        if size > MAX_FRAME_SIZE {
+            return Err(DecodeError::FrameTooLarge { size });
+        }
+
+        let bytes = self
+            .src
+            .get(self.position..self.position + len)
+            .ok_or(DecodeError::Incomplete)?;
+        self.position += len;
+        Ok(bytes)
+    }
+
+    fn u8(&mut self) -> Result<u8, DecodeError> {
+        Ok(self.bytes(1)?[0])
+    }
+
+    fn u32(&mut self) -> Result<u32, DecodeError> {
+        Ok(u32::from_be_bytes(self.bytes(4)?.try_into().unwrap()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{next_addr, wait_for_tcp};
+    use flate2::{write::ZlibEncoder, Compression};
+    use serde_json::json;
+    use std::io::Write;
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpStream,
+    };
+
+    fn window_size(size: u32) -> Vec<u8> {
+        let mut frame = vec![PROTOCOL_VERSION, WINDOW_SIZE_FRAME];
+        frame.extend_from_slice(&size.to_be_bytes());
+        frame
+    }
+
+    fn json_frame(sequence: u32, payload: serde_json::Value) -> Vec<u8> {
+        let payload = serde_json::to_vec(&payload).unwrap();
+        let mut frame = vec![PROTOCOL_VERSION, JSON_FRAME];
+        frame.extend_from_slice(&sequence.to_be_bytes());
+        frame.extend_from_slice(&(payload.len() as u32).to_be_bytes());
+        frame.extend_from_slice(&payload);
+        frame
+    }
+
+    fn compressed_frame(frames: &[u8]) -> Vec<u8> {
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(frames).unwrap();
+        let payload = encoder.finish().unwrap();
+
+        let mut frame = vec![PROTOCOL_VERSION, COMPRESSED_FRAME];
+        frame.extend_from_slice(&(payload.len() as u32).to_be_bytes());
+        frame.extend_from_slice(&payload);
+        frame
+    }
+
+    fn decode_all(bytes: &[u8]) -> Vec<LogstashFrame> {
+        let mut decoder = LogstashDecoder::default();
+        let mut src = BytesMut::from(bytes);
+        let mut frames = Vec::new();
+        while let Some(frame) = decoder.decode(&mut src).unwrap() {
+            frames.push(frame);
+        }
+        assert!(src.is_empty());
+        frames
+    }
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<LogstashConfig>();
+    }
+
+    #[test]
+    fn acknowledges_the_last_frame_of_each_window() {
+        let mut bytes = window_size(2);
+        bytes.extend(json_frame(1, json!({"message": "first"})));
+        bytes.extend(json_frame(2, json!({"message": "second"})));
+        bytes.extend(window_size(1));
+        bytes.extend(json_frame(1, json!({"message": "third"})));
+
+        let acks = decode_all(&bytes)
+            .into_iter()
+            .map(|frame| frame.ack)
+            .collect::<Vec<_>>();
+
+        assert_eq!(acks, vec![None, Some(2), Some(1)]);
+    }
+
+    #[test]
+    fn decodes_compressed_frames() {
+        let mut frames = json_frame(1, json!({"message": "first"}));
+        frames.extend(json_frame(2, json!({"message": "second"})));
+        let mut bytes = window_size(2);
+        bytes.extend(compressed_frame(&frames));
+
+        let messages = decode_all(&bytes)
+            .into_iter()
+            .map(|frame| frame.fields.unwrap()["message"].clone())
+            .collect::<Vec<_>>();
+
+        assert_eq!(messages, vec!["first".into(), "second".into()]);
+    }
+
+    #[test]
+    fn decodes_key_value_data_frames() {
+        let mut bytes = window_size(1);
+        bytes.extend(&[PROTOCOL_VERSION, DATA_FRAME]);
+        bytes.extend(&1u32.to_be_bytes());
+        bytes.extend(&1u32.to_be_bytes());
+        bytes.extend(&7u32.to_be_bytes());
+        bytes.extend(b"message");
+        bytes.extend(&5u32.to_be_bytes());
+        bytes.extend(b"hello");
+
+        let frames = decode_all(&bytes);
+
+        assert_eq!(frames.len(), 1);
+        assert_eq!(
+            frames[0].fields.as_ref().unwrap()["message"],
+            "hello".into()
+        );
+        assert_eq!(frames[0].ack, Some(1));
+    }
+
+    #[test]
+    fn waits_for_the_whole_frame() {
+        let frame = json_frame(1, json!({"message": "hello"}));
+
+        let mut decoder = LogstashDecoder::default();
+        let mut src = BytesMut::from(&frame[..frame.len() - 1]);
+        assert_eq!(decoder.decode(&mut src).unwrap(), None);
+
+        src.extend_from_slice(&frame[frame.len() - 1..]);
+        assert!(decoder.decode(&mut src).unwrap().is_some());
+    }
+
+    #[test]
+    fn skips_invalid_json_frames() {
+        let mut invalid = vec![PROTOCOL_VERSION, JSON_FRAME];
+        invalid.extend_from_slice(&2u32.to_be_bytes());
+        invalid.extend_from_slice(&3u32.to_be_bytes());
+        invalid.extend_from_slice(b"{{{");
+
+        let mut frames = json_frame(1, json!({"message": "first"}));
+        frames.extend(&invalid);
+        frames.extend(json_frame(3, json!({"message": "third"})));
+
+        let mut bytes = window_size(2);
+        bytes.extend(json_frame(1, json!({"message": "first"})));
+        bytes.extend(&invalid);
+        bytes.extend(window_size(3));
+        bytes.extend(compressed_frame(&frames));
+
+        let mut decoder = LogstashDecoder::default();
+        let mut src = BytesMut::from(&bytes[..]);
+        let mut decoded = Vec::new();
+        let mut errors = 0;
+        loop {
+            match decoder.decode(&mut src) {
+                Ok(Some(frame)) => decoded.push((frame.fields.is_some(), frame.ack)),
+                Ok(None) => break,
+                Err(DecodeError::Json { .. }) => errors += 1,
+                Err(error) => panic!("Unexpected error: {}", error),
+            }
+        }
+
+        assert_eq!(errors, 2);
+        assert_eq!(
+            decoded,
+            vec![
+                (true, None),
+                (false, Some(2)),
+                (true, None),
+                (true, Some(3)),
+            ]
+        );
+    }
+
+    #[test]
+    fn skips_frames_that_are_too_large() {
+        let mut bytes = vec![PROTOCOL_VERSION, JSON_FRAME];
+        bytes.extend_from_slice(&1u32.to_be_bytes());
+        bytes.extend_from_slice(&(MAX_FRAME_SIZE as u32).to_be_bytes());
+
+        let mut decoder = LogstashDecoder::default();
+        let mut src = BytesMut::from(&bytes[..]);
+        assert!(matches!(
+            decoder.decode(&mut src),
+            Err(DecodeError::FrameTooLarge { .. })
+        ));
+
+        // The rest of the frame is discarded as it's received.
+        src.extend_from_slice(&vec![b'x'; MAX_FRAME_SIZE]);
+        src.extend(window_size(1));
+        src.extend(json_frame(2, json!({"message": "hello"})));
+        let frame = decoder.decode(&mut src).unwrap().unwrap();
+        assert_eq!(frame.fields.unwrap()["message"], "hello".into());
+        assert_eq!(frame.ack, Some(2));
+    }
+
+    #[test]
+    fn limits_the_window_size() {
+        let mut decoder = LogstashDecoder::default();
+        let mut src = BytesMut::from(&window_size(u32::MAX)[..]);
+        assert_eq!(decoder.decode(&mut src).unwrap(), None);
+        assert_eq!(decoder.window_size, MAX_WINDOW_SIZE);
+    }
+
+    #[test]
+    fn rejects_unsupported_versions() {
+        let mut decoder = LogstashDecoder::default();
+        let mut src = BytesMut::from(&b"1W\x00\x00\x00\x01"[..]);
+        assert!(decoder.decode(&mut src).is_err());
+    }
+
+    #[tokio::test]
+    async fn receives_beats_events() {
+        let (tx, mut rx) = Pipeline::new_test();
+        let addr = next_addr();
+
+        let config: LogstashConfig = toml::from_str(&format!(r#"address = "{}""#, addr)).unwrap();
+        let server = config
+            .build(
+                "default",
+                &GlobalOptions::default(),
+                ShutdownSignal::noop(),
+                tx,
+            )
+            .await
+            .unwrap();
+        tokio::spawn(server);
+        wait_for_tcp(addr).await;
+
+        let mut bytes = window_size(1);
+        bytes.extend(json_frame(
+            7,
+            json!({
+                "@timestamp": "2021-03-01T12:00:00.000Z",
+                "@metadata": {"beat": "filebeat", "version": "7.12.0"},
+                "message": "hello",
+            }),
+        ));
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        stream.write_all(&bytes).await.unwrap();
+
+        let mut ack = [0; 6];
+        stream.read_exact(&mut ack).await.unwrap();
+        assert_eq!(ack, [PROTOCOL_VERSION, ACK_FRAME, 0, 0, 0, 7]);
+
+        let event = rx.recv().await.unwrap();
+        let log = event.as_log();
+        assert_eq!(log[log_schema().message_key()], "hello".into());
+        assert_eq!(log["@metadata.beat"], "filebeat".into());
+        assert_eq!(
+            log[log_schema().timestamp_key()],
+            DateTime::parse_from_rfc3339("2021-03-01T12:00:00Z")
+                .unwrap()
+                .with_timezone(&Utc)
+                .into()
+        );
+        assert_eq!(log[log_schema().host_key()], "127.0.0.1".into());
+        assert_eq!(log[log_schema().source_type_key()], "logstash".into());
+    }
+}
@@ -13,8 +13,12 @@ pub mod aws_s3;
 pub mod datadog;
 #[cfg(feature = "sources-docker_logs")]
 pub mod docker_logs;
+#[cfg(feature = "sources-exec")]
+pub mod exec;
 #[cfg(feature = "sources-file")]
 pub mod file;
+#[cfg(feature = "sources-fluent")]
+pub mod fluent;
 #[cfg(feature = "sources-generator")]
 pub mod generator;
 #[cfg(feature = "sources-heroku_logs")]
@@ -33,10 +37,16 @@ pub mod journald;
 pub mod kafka;
 #[cfg(feature = "sources-kubernetes-logs")]
 pub mod kubernetes_logs;
+#[cfg(feature = "sources-logstash")]
+pub mod logstash;
 #[cfg(feature = "sources-mongodb_metrics")]
 pub mod mongodb_metrics;
+#[cfg(feature = "sources-mqtt")]
+pub mod mqtt;
 #[cfg(feature = "sources-nginx_metrics")]
 pub mod nginx_metrics;
+#[cfg(feature = "sources-opentelemetry")]
+pub mod opentelemetry;
 #[cfg(feature = "sources-postgresql_metrics")]
 pub mod postgresql_metrics;
 #[cfg(feature = "sources-prometheus")]
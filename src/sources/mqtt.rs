@@ -0,0 +1,256 @@
+use crate::{
+    config::{
+        log_schema, DataType, GenerateConfig, GlobalOptions, SourceConfig, SourceDescription,
+    },
+    event::Event,
+    internal_events::{MqttConnectionFailed, MqttEventReceived},
+    mqtt::{MqttConnectionConfig, MqttQos},
+    shutdown::ShutdownSignal,
+    sources::util::{Decoder, DecodingConfig, FramingConfig},
+    Pipeline,
+};
+use bytes::Bytes;
+use futures::SinkExt;
+use rumqttc::{AsyncClient, Event as MqttEvent, MqttOptions, Packet, SubscribeFilter};
+use serde::{Deserialize, Serialize};
+use tokio::time::{delay_for, Duration};
+
+/// The capacity of the channel of requests to the event loop of the client.
+const REQUESTS_CAPACITY: usize = 100;
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct MqttSourceConfig {
+    #[serde(flatten)]
+    connection: MqttConnectionConfig,
+    topics: Vec<String>,
+    #[serde(default)]
+    qos: MqttQos,
+    #[serde(default)]
+    persistent_session: bool,
+    topic_key: Option<String>,
+    framing: Option<FramingConfig>,
+    decoding: Option<DecodingConfig>,
+}
+
+inventory::submit! {
+    SourceDescription::new::<MqttSourceConfig>("mqtt")
+}
+
+impl GenerateConfig for MqttSourceConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"host = "127.0.0.1"
+            client_id = "vector"
+            topics = ["sensors/#"]"#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "mqtt")]
+impl SourceConfig for MqttSourceConfig {
+    async fn build(
+        &self,
+        _name: &str,
+        _globals: &GlobalOptions,
+        shutdown: ShutdownSignal,
+        out: Pipeline,
+    ) -> crate::Result<super::Source> {
+        // Each message is a single frame unless configured otherwise.
+        let framing = self.framing.clone().unwrap_or(FramingConfig::Bytes);
+        framing.build()?;
+
+        let mut options = self.connection.build_options()?;
+        // The broker keeps the subscriptions and the QoS 1 messages of
+        // persistent sessions while the client is disconnected.
+        options.set_clean_session(!self.persistent_session);
+
+        Ok(Box::pin(mqtt_source(
+            self.clone(),
+            options,
+            framing,
+            shutdown,
+            out,
+        )))
+    }
+
+    fn output_type(&self) -> DataType {
+        self.decoding
+            .map_or(DataType::Log, |decoding| decoding.output_type())
+    }
+
+    fn source_type(&self) -> &'static str {
+        "mqtt"
+    }
+}
+
+async fn mqtt_source(
+    config: MqttSourceConfig,
+    options: MqttOptions,
+    framing: FramingConfig,
+    mut shutdown: ShutdownSignal,
+    mut out: Pipeline,
+) -> Result<(), ()> {
+    let (client, mut eventloop) = AsyncClient::new(options, REQUESTS_CAPACITY);
+    let filters = config
+        .topics
+        .iter()
+        .map(|topic| SubscribeFilter::new(topic.clone(), config.qos.into()))
+        .collect::<Vec<_>>();
+    let decoding = config.decoding.unwrap_or_default();
+
+    loop {
+        let notification = tokio::select! {
+            _ = &mut shutdown => break,
+            notification = eventloop.poll() => notification,
+        };
+
+        match notification {
+            Ok(MqttEvent::Incoming(Packet::ConnAck(connack))) => {
+                // Subscriptions only outlive the connection in persistent
+                // sessions, so they are renewed on each new session.
+                if !connack.session_present {
+                    client
+                        .subscribe_many(filters.clone())
+                        .await
+                        .map_err(|error| error!(message = "Failed to subscribe.", %error))?;
+                }
+            }
+            Ok(MqttEvent::Incoming(Packet::Publish(publish))) => {
+                emit!(MqttEventReceived {
+                    byte_size: publish.payload.len(),
+                    topic: &publish.topic,
+                });
+
+                let framer = framing
+                    .build()
+                    .expect("Framing is validated when the source is built.");
+                let events = Decoder::new(framer, decoding).decode_all(publish.payload);
+
+                for mut event in events {
+                    if let Event::Log(log) = &mut event {
+                        log.insert(log_schema().source_type_key(), Bytes::from("mqtt"));
+                        if let Some(topic_key) = &config.topic_key {
+                            log.insert(topic_key.as_str(), publish.topic.clone());
+                        }
+                    }
+
+                    out.send(event)
+                        .await
+                        .map_err(|_: crate::pipeline::ClosedError| {
+                            error!(message = "Failed to forward events; downstream is closed.");
+                        })?;
+                }
+            }
+            Ok(_) => (),
+            Err(error) => {
+                emit!(MqttConnectionFailed { error });
+                // The next poll reconnects to the broker.
+                tokio::select! {
+                    _ = &mut shutdown => break,
+                    _ = delay_for(Duration::from_secs(1)) => (),
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<MqttSourceConfig>();
+    }
+
+    #[test]
+    fn parses_config() {
+        let config: MqttSourceConfig = toml::from_str(
+            r#"host = "localhost"
+            client_id = "gateway"
+            topics = ["sensors/+/temperature"]
+            qos = "at_least_once"
+            persistent_session = true
+            topic_key = "topic""#,
+        )
+        .unwrap();
+
+        assert_eq!(config.connection.port, 1883);
+        assert_eq!(config.qos, MqttQos::AtLeastOnce);
+        assert!(config.persistent_session);
+        assert_eq!(config.output_type(), DataType::Log);
+    }
+}
+
+#[cfg(feature = "mqtt-integration-tests")]
+#[cfg(test)]
+mod integration_tests {
+    use super::*;
+    use crate::test_util::{collect_n, random_string, trace_init};
+    use rumqttc::QoS;
+
+    #[tokio::test]
+    async fn mqtt_happy() {
+        trace_init();
+
+        let topic = format!("test-{}", random_string(10));
+        let config: MqttSourceConfig = toml::from_str(&format!(
+            r#"host = "localhost"
+            client_id = "vector-source-{}"
+            topics = ["{}/#"]
+            qos = "at_least_once"
+            topic_key = "topic""#,
+            random_string(10),
+            topic
+        ))
+        .unwrap();
+
+        let (tx, rx) = Pipeline::new_test();
+        let source = config
+            .build(
+                "default",
+                &GlobalOptions::default(),
+                ShutdownSignal::noop(),
+                tx,
+            )
+            .await
+            .unwrap();
+        tokio::spawn(source);
+        // Let the source subscribe before publishing.
+        delay_for(Duration::from_secs(1)).await;
+
+        let options = MqttOptions::new(
+            format!("vector-test-{}", random_string(10)),
+            "localhost",
+            1883,
+        );
+        let (client, mut eventloop) = AsyncClient::new(options, 10);
+        tokio::spawn(async move { while eventloop.poll().await.is_ok() {} });
+        for n in 0..10 {
+            client
+                .publish(
+                    format!("{}/sensor", topic),
+                    QoS::AtLeastOnce,
+                    false,
+                    format!("reading {}", n),
+                )
+                .await
+                .unwrap();
+        }
+
+        let events = collect_n(rx, 10).await.unwrap();
+        for (n, event) in events.into_iter().enumerate() {
+            let log = event.as_log();
+            assert_eq!(
+                log[log_schema().message_key()],
+                format!("reading {}", n).into()
+            );
+            assert_eq!(log["topic"], format!("{}/sensor", topic).into());
+            assert_eq!(log[log_schema().source_type_key()], "mqtt".into());
+        }
+    }
+}
@@ -0,0 +1,103 @@
+use super::{logs, metrics};
+use crate::{
+    event::Event,
+    internal_events::OpenTelemetryEventsReceived,
+    proto::opentelemetry::proto::collector::{
+        logs::v1::{
+            logs_service_server::{LogsService, LogsServiceServer},
+            ExportLogsServiceRequest, ExportLogsServiceResponse,
+        },
+        metrics::v1::{
+            metrics_service_server::{MetricsService, MetricsServiceServer},
+            ExportMetricsServiceRequest, ExportMetricsServiceResponse,
+        },
+    },
+    shutdown::ShutdownSignal,
+    sources::Source,
+    tls::{MaybeTlsSettings, TlsConfig},
+    Pipeline,
+};
+use futures::{stream, FutureExt, SinkExt, StreamExt};
+use prost::Message;
+use std::net::SocketAddr;
+use tonic::{transport::Server, Request, Response, Status};
+
+pub(super) async fn build(
+    address: SocketAddr,
+    tls: &Option<TlsConfig>,
+    shutdown: ShutdownSignal,
+    out: Pipeline,
+) -> crate::Result<Source> {
+    let tls = MaybeTlsSettings::from_config(tls, true)?;
+    let listener = tls.bind(&address).await?;
+
+    let service = Service { pipeline: out };
+
+    info!(message = "Building gRPC server.", address = %address);
+
+    Ok(Box::pin(async move {
+        Server::builder()
+            .add_service(LogsServiceServer::new(service.clone()))
+            .add_service(MetricsServiceServer::new(service))
+            .serve_with_incoming_shutdown(listener.accept_stream(), shutdown.clone().map(|_| ()))
+            .await
+            .map_err(|error| error!(message = "gRPC server failed.", %error))?;
+        // We need to drop the last copy of ShutdownSignalToken only after server has shut down.
+        drop(shutdown);
+        Ok(())
+    }))
+}
+
+#[derive(Debug, Clone)]
+struct Service {
+    pipeline: Pipeline,
+}
+
+impl Service {
+    /// Only responds once all of the events are sent to the pipeline, so
+    /// exporters retry the requests that weren't accepted.
+    async fn send(&self, events: Vec<Event>, byte_size: usize) -> Result<(), Status> {
+        emit!(OpenTelemetryEventsReceived {
+            events_count: events.len(),
+            byte_size,
+        });
+
+        self.pipeline
+            .clone()
+            .send_all(&mut stream::iter(events).map(Ok))
+            .await
+            .map_err(|error| {
+                // Can only fail if the receiving end disconnected, so we are shutting down.
+                error!(message = "Failed to forward events, downstream is closed.", %error);
+                Status::unavailable("Vector is shutting down.")
+            })
+    }
+}
+
+#[async_trait::async_trait]
+impl LogsService for Service {
+    async fn export(
+        &self,
+        request: Request<ExportLogsServiceRequest>,
+    ) -> Result<Response<ExportLogsServiceResponse>, Status> {
+        let request = request.into_inner();
+        let byte_size = request.encoded_len();
+        self.send(logs::decode(request), byte_size).await?;
+
+        Ok(Response::new(ExportLogsServiceResponse::default()))
+    }
+}
+
+#[async_trait::async_trait]
+impl MetricsService for Service {
+    async fn export(
+        &self,
+        request: Request<ExportMetricsServiceRequest>,
+    ) -> Result<Response<ExportMetricsServiceResponse>, Status> {
+        let request = request.into_inner();
+        let byte_size = request.encoded_len();
+        self.send(metrics::decode(request), byte_size).await?;
+
+        Ok(Response::new(ExportMetricsServiceResponse::default()))
+    }
+}
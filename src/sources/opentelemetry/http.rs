@@ -0,0 +1,167 @@
+use super::{logs, metrics};
+use crate::{
+    event::Event,
+    proto::opentelemetry::proto::collector::{
+        logs::v1::ExportLogsServiceRequest, metrics::v1::ExportMetricsServiceRequest,
+    },
+    sources::util::{ErrorMessage, HttpSource},
+};
+use bytes::Bytes;
+use prost::Message;
+use serde::de::DeserializeOwned;
+use std::collections::HashMap;
+use warp::http::{header::CONTENT_TYPE, HeaderMap, StatusCode};
+
+/// Receives OTLP/HTTP requests on `/v1/logs` and `/v1/metrics`, encoded as
+/// protobuf, or as JSON when the content type is `application/json`.
+#[derive(Clone)]
+pub(super) struct OpentelemetryHttpSource;
+
+impl HttpSource for OpentelemetryHttpSource {
+    fn build_event(
+        &self,
+        body: Bytes,
+        header_map: HeaderMap,
+        _query_parameters: HashMap<String, String>,
+        path: &str,
+    ) -> Result<Vec<Event>, ErrorMessage> {
+        let json = header_map
+            .get(CONTENT_TYPE)
+            .and_then(|content_type| content_type.to_str().ok())
+            .map_or(false, |content_type| {
+                content_type.starts_with("application/json")
+            });
+
+        match path {
+            "/v1/logs" => decode::<ExportLogsServiceRequest>(body, json).map(logs::decode),
+            "/v1/metrics" => decode::<ExportMetricsServiceRequest>(body, json).map(metrics::decode),
+            _ => Err(ErrorMessage::new(
+                StatusCode::NOT_FOUND,
+                "Not found".to_string(),
+            )),
+        }
+    }
+}
+
+fn decode<T: Message + Default + DeserializeOwned>(
+    body: Bytes,
+    json: bool,
+) -> Result<T, ErrorMessage> {
+    if json {
+        serde_json::from_slice(&body).map_err(|error| {
+            ErrorMessage::new(
+                StatusCode::BAD_REQUEST,
+                format!("Error parsing JSON: {}", error),
+            )
+        })
+    } else {
+        T::decode(body).map_err(|error| {
+            ErrorMessage::new(
+                StatusCode::BAD_REQUEST,
+                format!("Error decoding protobuf: {}", error),
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        config::log_schema,
+        event::{metric::MetricValue, Value},
+    };
+    use warp::http::HeaderValue;
+
+    fn json_headers() -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+        headers
+    }
+
+    #[test]
+    fn decodes_json_logs() {
+        let body = r#"{
+            "resourceLogs": [{
+                "resource": {
+                    "attributes": [{"key": "service.name", "value": {"stringValue": "checkout"}}]
+                },
+                "scopeLogs": [{
+                    "scope": {"name": "io.vector"},
+                    "logRecords": [{
+                        "timeUnixNano": "1579134612000000011",
+                        "severityNumber": 9,
+                        "body": {"stringValue": "Order placed."},
+                        "attributes": [{"key": "retries", "value": {"intValue": "3"}}],
+                        "traceId": "5b8efff798038103d269b633813fc60c",
+                        "spanId": "eee19b7ec3c1b174"
+                    }]
+                }]
+            }]
+        }"#;
+
+        let events = OpentelemetryHttpSource
+            .build_event(body.into(), json_headers(), HashMap::new(), "/v1/logs")
+            .unwrap();
+        assert_eq!(events.len(), 1);
+        let log = events[0].as_log();
+
+        assert_eq!(log[log_schema().message_key()], "Order placed.".into());
+        assert_eq!(log["attributes.retries"], Value::Integer(3));
+        assert_eq!(log["resources.service\\.name"], "checkout".into());
+        assert_eq!(log["trace_id"], "5b8efff798038103d269b633813fc60c".into());
+        assert_eq!(log["span_id"], "eee19b7ec3c1b174".into());
+    }
+
+    #[test]
+    fn decodes_json_metrics() {
+        let body = r#"{
+            "resourceMetrics": [{
+                "scopeMetrics": [{
+                    "metrics": [{
+                        "name": "requests",
+                        "sum": {
+                            "dataPoints": [{"asInt": "4", "timeUnixNano": "1579134612000000000"}],
+                            "aggregationTemporality": 2,
+                            "isMonotonic": true
+                        }
+                    }]
+                }]
+            }]
+        }"#;
+
+        let events = OpentelemetryHttpSource
+            .build_event(body.into(), json_headers(), HashMap::new(), "/v1/metrics")
+            .unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(
+            events[0].as_metric().data.value,
+            MetricValue::Counter { value: 4.0 }
+        );
+    }
+
+    #[test]
+    fn decodes_protobuf_logs() {
+        let request = ExportLogsServiceRequest::default();
+        let mut body = Vec::new();
+        request.encode(&mut body).unwrap();
+
+        let events = OpentelemetryHttpSource
+            .build_event(body.into(), HeaderMap::new(), HashMap::new(), "/v1/logs")
+            .unwrap();
+        assert!(events.is_empty());
+    }
+
+    #[test]
+    fn rejects_invalid_requests() {
+        let error = OpentelemetryHttpSource
+            .build_event("{".into(), json_headers(), HashMap::new(), "/v1/logs")
+            .unwrap_err();
+        assert!(error.to_string().starts_with("400"));
+
+        let error = OpentelemetryHttpSource
+            .build_event(Bytes::new(), HeaderMap::new(), HashMap::new(), "/v1/traces")
+            .unwrap_err();
+        assert!(error.to_string().starts_with("404"));
+    }
+}
@@ -0,0 +1,181 @@
+use super::{attributes_to_value, timestamp, value_from_any};
+use crate::{
+    config::log_schema,
+    event::{Event, LogEvent, Value},
+    proto::opentelemetry::proto::{
+        collector::logs::v1::ExportLogsServiceRequest, common::v1::InstrumentationScope,
+        logs::v1::LogRecord,
+    },
+};
+use bytes::Bytes;
+use chrono::Utc;
+use std::collections::BTreeMap;
+
+/// Maps each log record of the request to a log event, along with the
+/// attributes of its resource and scope.
+pub fn decode(request: ExportLogsServiceRequest) -> Vec<Event> {
+    let mut events = Vec::new();
+    for resource_logs in request.resource_logs {
+        let resource = resource_logs
+            .resource
+            .map(|resource| attributes_to_value(resource.attributes))
+            .filter(|resource| !is_empty_map(resource));
+
+        for scope_logs in resource_logs.scope_logs {
+            let scope = scope_logs.scope.map(scope_to_value);
+            for record in scope_logs.log_records {
+                events.push(record_to_event(record, resource.clone(), scope.clone()));
+            }
+        }
+    }
+    events
+}
+
+fn record_to_event(record: LogRecord, resource: Option<Value>, scope: Option<Value>) -> Event {
+    let mut log = LogEvent::default();
+
+    if let Some(body) = record.body.and_then(value_from_any) {
+        log.insert(log_schema().message_key(), body);
+    }
+
+    let observed_timestamp = timestamp(record.observed_time_unix_nano);
+    let timestamp = timestamp(record.time_unix_nano)
+        .or(observed_timestamp)
+        .unwrap_or_else(Utc::now);
+    log.insert(log_schema().timestamp_key(), timestamp);
+    if let Some(observed_timestamp) = observed_timestamp {
+        log.insert("observed_timestamp", observed_timestamp);
+    }
+
+    let attributes = attributes_to_value(record.attributes);
+    if !is_empty_map(&attributes) {
+        log.insert("attributes", attributes);
+    }
+    if let Some(resource) = resource {
+        log.insert("resources", resource);
+    }
+    if let Some(scope) = scope {
+        log.insert("scope", scope);
+    }
+
+    if !record.severity_text.is_empty() {
+        log.insert("severity_text", record.severity_text);
+    }
+    if record.severity_number != 0 {
+        log.insert("severity_number", record.severity_number as i64);
+    }
+    if !record.trace_id.is_empty() {
+        log.insert("trace_id", hex::encode(record.trace_id));
+    }
+    if !record.span_id.is_empty() {
+        log.insert("span_id", hex::encode(record.span_id));
+    }
+    if record.flags != 0 {
+        log.insert("flags", record.flags as i64);
+    }
+    if record.dropped_attributes_count != 0 {
+        log.insert(
+            "dropped_attributes_count",
+            record.dropped_attributes_count as i64,
+        );
+    }
+
+    log.insert(
+        log_schema().source_type_key(),
+        Bytes::from_static(b"opentelemetry"),
+    );
+
+    Event::Log(log)
+}
+
+fn scope_to_value(scope: InstrumentationScope) -> Value {
+    let mut map = BTreeMap::new();
+    if !scope.name.is_empty() {
+        map.insert("name".to_owned(), Value::from(scope.name));
+    }
+    if !scope.version.is_empty() {
+        map.insert("version".to_owned(), Value::from(scope.version));
+    }
+    let attributes = attributes_to_value(scope.attributes);
+    if !is_empty_map(&attributes) {
+        map.insert("attributes".to_owned(), attributes);
+    }
+    Value::Map(map)
+}
+
+fn is_empty_map(value: &Value) -> bool {
+    matches!(value, Value::Map(map) if map.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proto::opentelemetry::proto::{
+        common::v1::{any_value, AnyValue, KeyValue},
+        logs::v1::{ResourceLogs, ScopeLogs},
+        resource::v1::Resource,
+    };
+
+    fn string(value: &str) -> Option<AnyValue> {
+        Some(AnyValue {
+            value: Some(any_value::Value::StringValue(value.to_owned())),
+        })
+    }
+
+    #[test]
+    fn maps_log_records() {
+        let request = ExportLogsServiceRequest {
+            resource_logs: vec![ResourceLogs {
+                resource: Some(Resource {
+                    attributes: vec![KeyValue {
+                        key: "service.name".to_owned(),
+                        value: string("checkout"),
+                    }],
+                    ..Default::default()
+                }),
+                scope_logs: vec![ScopeLogs {
+                    scope: Some(InstrumentationScope {
+                        name: "io.vector".to_owned(),
+                        version: "1.0".to_owned(),
+                        ..Default::default()
+                    }),
+                    log_records: vec![LogRecord {
+                        time_unix_nano: 1_579_134_612_000_000_011,
+                        severity_number: 9,
+                        severity_text: "INFO".to_owned(),
+                        body: string("Order placed."),
+                        attributes: vec![KeyValue {
+                            key: "http.status_code".to_owned(),
+                            value: Some(AnyValue {
+                                value: Some(any_value::Value::IntValue(200)),
+                            }),
+                        }],
+                        trace_id: vec![1; 16],
+                        span_id: vec![2; 8],
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+        };
+
+        let events = decode(request);
+        assert_eq!(events.len(), 1);
+        let log = events[0].as_log();
+
+        assert_eq!(log[log_schema().message_key()], "Order placed.".into());
+        assert_eq!(
+            log[log_schema().timestamp_key()],
+            timestamp(1_579_134_612_000_000_011).unwrap().into()
+        );
+        assert_eq!(log["attributes.http\\.status_code"], 200.into());
+        assert_eq!(log["resources.service\\.name"], "checkout".into());
+        assert_eq!(log["scope.name"], "io.vector".into());
+        assert_eq!(log["scope.version"], "1.0".into());
+        assert_eq!(log["severity_text"], "INFO".into());
+        assert_eq!(log["severity_number"], 9.into());
+        assert_eq!(log["trace_id"], "01010101010101010101010101010101".into());
+        assert_eq!(log["span_id"], "0202020202020202".into());
+    }
+}
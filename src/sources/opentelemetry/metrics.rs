@@ -0,0 +1,471 @@
+use super::{timestamp, value_from_any};
+use crate::{
+    event::{
+        metric::{Bucket, MetricTags, Quantile},
+        Event, Metric, MetricKind, MetricValue,
+    },
+    proto::opentelemetry::proto::{
+        collector::metrics::v1::ExportMetricsServiceRequest,
+        common::v1::{InstrumentationScope, KeyValue},
+        metrics::v1::{
+            exponential_histogram_data_point::Buckets, metric::Data, number_data_point,
+            AggregationTemporality, ExponentialHistogramDataPoint, HistogramDataPoint,
+            NumberDataPoint, SummaryDataPoint,
+        },
+    },
+};
+use std::{convert::TryFrom, ops::RangeInclusive};
+
+/// The scales of exponential histograms allowed by the protocol.
+const EXPONENTIAL_SCALES: RangeInclusive<i32> = -10..=20;
+
+/// Maps each data point of the request to a metric event, tagged with the
+/// attributes of the data point, and those of its resource and scope.
+pub fn decode(request: ExportMetricsServiceRequest) -> Vec<Event> {
+    let mut events = Vec::new();
+    for resource_metrics in request.resource_metrics {
+        let mut resource_tags = MetricTags::new();
+        if let Some(resource) = resource_metrics.resource {
+            insert_tags(&mut resource_tags, "resource.", resource.attributes);
+        }
+
+        for scope_metrics in resource_metrics.scope_metrics {
+            let mut scope_tags = resource_tags.clone();
+            if let Some(scope) = scope_metrics.scope {
+                insert_scope_tags(&mut scope_tags, scope);
+            }
+
+            for metric in scope_metrics.metrics {
+                let name = metric.name;
+                let metrics: Vec<Metric> = match metric.data {
+                    Some(Data::Gauge(gauge)) => gauge
+                        .data_points
+                        .into_iter()
+                        .filter_map(|point| {
+                            number_metric(&name, MetricKind::Absolute, false, point)
+                        })
+                        .collect(),
+                    Some(Data::Sum(sum)) => {
+                        let kind = kind(sum.aggregation_temporality);
+                        sum.data_points
+                            .into_iter()
+                            .filter_map(|point| number_metric(&name, kind, sum.is_monotonic, point))
+                            .collect()
+                    }
+                    Some(Data::Histogram(histogram)) => {
+                        let kind = kind(histogram.aggregation_temporality);
+                        histogram
+                            .data_points
+                            .into_iter()
+                            .map(|point| histogram_metric(&name, kind, point))
+                            .collect()
+                    }
+                    Some(Data::ExponentialHistogram(histogram)) => {
+                        let kind = kind(histogram.aggregation_temporality);
+                        histogram
+                            .data_points
+                            .into_iter()
+                            .filter_map(|point| exponential_histogram_metric(&name, kind, point))
+                            .collect()
+                    }
+                    Some(Data::Summary(summary)) => summary
+                        .data_points
+                        .into_iter()
+                        .map(|point| summary_metric(&name, point))
+                        .collect(),
+                    None => Vec::new(),
+                };
+
+                events.extend(metrics.into_iter().map(|mut metric| {
+                    if !scope_tags.is_empty() {
+                        let tags = metric.tags_mut().get_or_insert_with(MetricTags::new);
+                        for (key, value) in &scope_tags {
+                            tags.entry(key.clone()).or_insert_with(|| value.clone());
+                        }
+                    }
+                    Event::Metric(metric)
+                }));
+            }
+        }
+    }
+    events
+}
+
+/// Cumulative metrics are absolute values, delta metrics are increments.
+fn kind(aggregation_temporality: i32) -> MetricKind {
+    if aggregation_temporality == AggregationTemporality::Delta as i32 {
+        MetricKind::Incremental
+    } else {
+        MetricKind::Absolute
+    }
+}
+
+fn number_metric(
+    name: &str,
+    kind: MetricKind,
+    monotonic: bool,
+    point: NumberDataPoint,
+) -> Option<Metric> {
+    let value = match point.value? {
+        number_data_point::Value::AsDouble(value) => value,
+        number_data_point::Value::AsInt(value) => value as f64,
+    };
+    // Sums that can decrease are gauges, even when they're incremental.
+    let value = if monotonic {
+        MetricValue::Counter { value }
+    } else {
+        MetricValue::Gauge { value }
+    };
+
+    Some(new_metric(
+        name,
+        kind,
+        value,
+        point.attributes,
+        point.time_unix_nano,
+    ))
+}
+
+fn histogram_metric(name: &str, kind: MetricKind, point: HistogramDataPoint) -> Metric {
+    // The last bucket holds the values above the last bound, which are only
+    // accounted for in the total count, as for Prometheus histograms.
+    let buckets = point
+        .explicit_bounds
+        .iter()
+        .zip(point.bucket_counts.iter())
+        .map(|(&upper_limit, &count)| Bucket {
+            upper_limit,
+            count: saturating_u32(count),
+        })
+        .collect();
+
+    new_metric(
+        name,
+        kind,
+        MetricValue::AggregatedHistogram {
+            buckets,
+            count: saturating_u32(point.count),
+            sum: point.sum,
+        },
+        point.attributes,
+        point.time_unix_nano,
+    )
+}
+
+/// The buckets of exponential histograms have exponentially growing bounds, so
+/// they're mapped to buckets with explicit bounds, in increasing order. Points
+/// with a scale outside of the allowed ones, or with buckets whose bounds can't
+/// be represented, are rejected.
+fn exponential_histogram_metric(
+    name: &str,
+    kind: MetricKind,
+    point: ExponentialHistogramDataPoint,
+) -> Option<Metric> {
+    if !EXPONENTIAL_SCALES.contains(&point.scale) {
+        return None;
+    }
+    let base = 2f64.powf(2f64.powi(-point.scale));
+
+    let mut buckets = Vec::new();
+    if let Some(negative) = point.negative {
+        // The bucket at index `i` holds values in `[-base^(i + 1), -base^i)`.
+        buckets.extend(exponential_buckets(base, negative)?.into_iter().rev().map(
+            |(lower, count)| Bucket {
+                upper_limit: -lower,
+                count,
+            },
+        ));
+    }
+    if point.zero_count > 0 || !buckets.is_empty() {
+        buckets.push(Bucket {
+            upper_limit: point.zero_threshold,
+            count: saturating_u32(point.zero_count),
+        });
+    }
+    if let Some(positive) = point.positive {
+        // The bucket at index `i` holds values in `(base^i, base^(i + 1)]`.
+        buckets.extend(
+            exponential_buckets(base, positive)?
+                .into_iter()
+                .map(|(lower, count)| Bucket {
+                    upper_limit: lower * base,
+                    count,
+                }),
+        );
+    }
+
+    Some(new_metric(
+        name,
+        kind,
+        MetricValue::AggregatedHistogram {
+            buckets,
+            count: saturating_u32(point.count),
+            sum: point.sum,
+        },
+        point.attributes,
+        point.time_unix_nano,
+    ))
+}
+
+/// The lower bounds of the buckets, along with their counts, unless the index
+/// of a bucket overflows or its bounds aren't finite positive numbers.
+fn exponential_buckets(base: f64, buckets: Buckets) -> Option<Vec<(f64, u32)>> {
+    let offset = buckets.offset;
+    buckets
+        .bucket_counts
+        .into_iter()
+        .enumerate()
+        .map(|(i, count)| {
+            let index = i32::try_from(i).ok()?.checked_add(offset)?;
+            let lower = base.powi(index);
+            if lower > 0.0 && (lower * base).is_finite() {
+                Some((lower, saturating_u32(count)))
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+fn summary_metric(name: &str, point: SummaryDataPoint) -> Metric {
+    new_metric(
+        name,
+        MetricKind::Absolute,
+        MetricValue::AggregatedSummary {
+            quantiles: point
+                .quantile_values
+                .into_iter()
+                .map(|quantile| Quantile {
+                    upper_limit: quantile.quantile,
+                    value: quantile.value,
+                })
+                .collect(),
+            count: saturating_u32(point.count),
+            sum: point.sum,
+        },
+        point.attributes,
+        point.time_unix_nano,
+    )
+}
+
+fn new_metric(
+    name: &str,
+    kind: MetricKind,
+    value: MetricValue,
+    attributes: Vec<KeyValue>,
+    time_unix_nano: u64,
+) -> Metric {
+    let mut tags = MetricTags::new();
+    insert_tags(&mut tags, "", attributes);
+
+    Metric::new(name, kind, value)
+        .with_tags(if tags.is_empty() { None } else { Some(tags) })
+        .with_timestamp(timestamp(time_unix_nano))
+}
+
+fn insert_scope_tags(tags: &mut MetricTags, scope: InstrumentationScope) {
+    if !scope.name.is_empty() {
+        tags.insert("scope.name".to_owned(), scope.name);
+    }
+    if !scope.version.is_empty() {
+        tags.insert("scope.version".to_owned(), scope.version);
+    }
+    insert_tags(tags, "scope.", scope.attributes);
+}
+
+fn insert_tags(tags: &mut MetricTags, prefix: &str, attributes: Vec<KeyValue>) {
+    for attribute in attributes {
+        if let Some(value) = attribute.value.and_then(value_from_any) {
+            tags.insert(
+                format!("{}{}", prefix, attribute.key),
+                value.to_string_lossy(),
+            );
+        }
+    }
+}
+
+fn saturating_u32(count: u64) -> u32 {
+    u32::try_from(count).unwrap_or(u32::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proto::opentelemetry::proto::{
+        common::v1::{any_value, AnyValue},
+        metrics::v1::{
+            summary_data_point::ValueAtQuantile, ExponentialHistogram, Histogram, ResourceMetrics,
+            ScopeMetrics, Sum, Summary,
+        },
+        resource::v1::Resource,
+    };
+
+    fn request(metric: Data) -> ExportMetricsServiceRequest {
+        ExportMetricsServiceRequest {
+            resource_metrics: vec![ResourceMetrics {
+                resource: Some(Resource {
+                    attributes: vec![KeyValue {
+                        key: "host.name".to_owned(),
+                        value: Some(AnyValue {
+                            value: Some(any_value::Value::StringValue("web-1".to_owned())),
+                        }),
+                    }],
+                    ..Default::default()
+                }),
+                scope_metrics: vec![ScopeMetrics {
+                    metrics: vec![crate::proto::opentelemetry::proto::metrics::v1::Metric {
+                        name: "requests".to_owned(),
+                        data: Some(metric),
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+        }
+    }
+
+    fn decode_one(metric: Data) -> Metric {
+        let mut events = decode(request(metric));
+        assert_eq!(events.len(), 1);
+        events.remove(0).into_metric()
+    }
+
+    #[test]
+    fn maps_monotonic_delta_sum_to_incremental_counter() {
+        let metric = decode_one(Data::Sum(Sum {
+            data_points: vec![NumberDataPoint {
+                time_unix_nano: 1_579_134_612_000_000_000,
+                value: Some(number_data_point::Value::AsInt(3)),
+                ..Default::default()
+            }],
+            aggregation_temporality: AggregationTemporality::Delta as i32,
+            is_monotonic: true,
+        }));
+
+        assert_eq!(metric.name(), "requests");
+        assert_eq!(metric.data.kind, MetricKind::Incremental);
+        assert_eq!(&metric.data.value, &MetricValue::Counter { value: 3.0 });
+        assert_eq!(
+            metric.tags().unwrap()["resource.host.name"],
+            "web-1".to_owned()
+        );
+        assert_eq!(metric.data.timestamp, timestamp(1_579_134_612_000_000_000));
+    }
+
+    #[test]
+    fn maps_non_monotonic_sum_to_gauge() {
+        let metric = decode_one(Data::Sum(Sum {
+            data_points: vec![NumberDataPoint {
+                value: Some(number_data_point::Value::AsDouble(-1.5)),
+                ..Default::default()
+            }],
+            aggregation_temporality: AggregationTemporality::Cumulative as i32,
+            is_monotonic: false,
+        }));
+
+        assert_eq!(metric.data.kind, MetricKind::Absolute);
+        assert_eq!(&metric.data.value, &MetricValue::Gauge { value: -1.5 });
+    }
+
+    #[test]
+    fn maps_histogram() {
+        let metric = decode_one(Data::Histogram(Histogram {
+            data_points: vec![HistogramDataPoint {
+                count: 6,
+                sum: 12.0,
+                bucket_counts: vec![1, 2, 3],
+                explicit_bounds: vec![1.0, 5.0],
+                ..Default::default()
+            }],
+            aggregation_temporality: AggregationTemporality::Cumulative as i32,
+        }));
+
+        assert_eq!(
+            &metric.data.value,
+            &MetricValue::AggregatedHistogram {
+                buckets: crate::buckets![1.0 => 1, 5.0 => 2],
+                count: 6,
+                sum: 12.0,
+            }
+        );
+    }
+
+    #[test]
+    fn maps_exponential_histogram() {
+        let metric = decode_one(Data::ExponentialHistogram(ExponentialHistogram {
+            data_points: vec![ExponentialHistogramDataPoint {
+                count: 6,
+                sum: 10.0,
+                scale: 0,
+                zero_count: 1,
+                positive: Some(Buckets {
+                    offset: 0,
+                    bucket_counts: vec![2, 3],
+                }),
+                ..Default::default()
+            }],
+            aggregation_temporality: AggregationTemporality::Delta as i32,
+        }));
+
+        assert_eq!(metric.data.kind, MetricKind::Incremental);
+        assert_eq!(
+            &metric.data.value,
+            &MetricValue::AggregatedHistogram {
+                buckets: crate::buckets![0.0 => 1, 2.0 => 2, 4.0 => 3],
+                count: 6,
+                sum: 10.0,
+            }
+        );
+    }
+
+    #[test]
+    fn rejects_exponential_histograms_out_of_range() {
+        let point = |scale, offset| ExponentialHistogramDataPoint {
+            count: 2,
+            scale,
+            positive: Some(Buckets {
+                offset,
+                bucket_counts: vec![1, 1],
+            }),
+            ..Default::default()
+        };
+        let decode_points = |data_points| {
+            decode(request(Data::ExponentialHistogram(ExponentialHistogram {
+                data_points,
+                aggregation_temporality: AggregationTemporality::Delta as i32,
+            })))
+        };
+
+        assert_eq!(decode_points(vec![point(21, 0)]).len(), 0);
+        assert_eq!(decode_points(vec![point(-11, 0)]).len(), 0);
+        assert_eq!(decode_points(vec![point(0, i32::MAX)]).len(), 0);
+        assert_eq!(decode_points(vec![point(0, 2000)]).len(), 0);
+        assert_eq!(decode_points(vec![point(0, -2000)]).len(), 0);
+        assert_eq!(decode_points(vec![point(20, 0), point(0, 1)]).len(), 2);
+    }
+
+    #[test]
+    fn maps_summary() {
+        let metric = decode_one(Data::Summary(Summary {
+            data_points: vec![SummaryDataPoint {
+                count: 10,
+                sum: 100.0,
+                quantile_values: vec![ValueAtQuantile {
+                    quantile: 0.5,
+                    value: 8.0,
+                }],
+                ..Default::default()
+            }],
+        }));
+
+        assert_eq!(
+            &metric.data.value,
+            &MetricValue::AggregatedSummary {
+                quantiles: crate::quantiles![0.5 => 8.0],
+                count: 10,
+                sum: 100.0,
+            }
+        );
+    }
+}
@@ -0,0 +1,219 @@
+use crate::{
+    config::{DataType, GenerateConfig, GlobalOptions, Resource, SourceConfig, SourceDescription},
+    event::Value,
+    proto::opentelemetry::proto::common::v1::{any_value, AnyValue, KeyValue},
+    shutdown::ShutdownSignal,
+    sources::util::HttpSource,
+    tls::TlsConfig,
+    Pipeline,
+};
+use chrono::{DateTime, TimeZone, Utc};
+use futures::{future, TryFutureExt};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, convert::TryFrom, net::SocketAddr};
+
+mod grpc;
+mod http;
+pub mod logs;
+pub mod metrics;
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct OpentelemetryConfig {
+    #[serde(default)]
+    grpc: GrpcConfig,
+    #[serde(default)]
+    http: HttpConfig,
+}
+
+/// Receives OTLP/gRPC requests, on port 4317 by default.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+struct GrpcConfig {
+    #[serde(default = "default_grpc_address")]
+    address: SocketAddr,
+    tls: Option<TlsConfig>,
+}
+
+impl Default for GrpcConfig {
+    fn default() -> Self {
+        Self {
+            address: default_grpc_address(),
+            tls: None,
+        }
+    }
+}
+
+fn default_grpc_address() -> SocketAddr {
+    "0.0.0.0:4317".parse().unwrap()
+}
+
+/// Receives OTLP/HTTP requests, encoded as protobuf or JSON, on port 4318 by default.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+struct HttpConfig {
+    #[serde(default = "default_http_address")]
+    address: SocketAddr,
+    tls: Option<TlsConfig>,
+}
+
+impl Default for HttpConfig {
+    fn default() -> Self {
+        Self {
+            address: default_http_address(),
+            tls: None,
+        }
+    }
+}
+
+fn default_http_address() -> SocketAddr {
+    "0.0.0.0:4318".parse().unwrap()
+}
+
+inventory::submit! {
+    SourceDescription::new::<OpentelemetryConfig>("opentelemetry")
+}
+
+impl GenerateConfig for OpentelemetryConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            grpc: GrpcConfig::default(),
+            http: HttpConfig::default(),
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "opentelemetry")]
+impl SourceConfig for OpentelemetryConfig {
+    async fn build(
+        &self,
+        _name: &str,
+        _globals: &GlobalOptions,
+        shutdown: ShutdownSignal,
+        out: Pipeline,
+    ) -> crate::Result<super::Source> {
+        let grpc = grpc::build(
+            self.grpc.address,
+            &self.grpc.tls,
+            shutdown.clone(),
+            out.clone(),
+        )
+        .await?;
+        let http = http::OpentelemetryHttpSource.run(
+            self.http.address,
+            "v1",
+            false,
+            &self.http.tls,
+            &None,
+            out,
+            shutdown,
+        )?;
+
+        Ok(Box::pin(future::try_join(grpc, http).map_ok(|_| ())))
+    }
+
+    fn output_type(&self) -> DataType {
+        DataType::Any
+    }
+
+    fn source_type(&self) -> &'static str {
+        "opentelemetry"
+    }
+
+    fn resources(&self) -> Vec<Resource> {
+        vec![
+            Resource::tcp(self.grpc.address),
+            Resource::tcp(self.http.address),
+        ]
+    }
+}
+
+/// Converts an OTLP value, which is unset when it holds no value.
+fn value_from_any(value: AnyValue) -> Option<Value> {
+    Some(match value.value? {
+        any_value::Value::StringValue(value) => Value::from(value),
+        any_value::Value::BoolValue(value) => Value::Boolean(value),
+        any_value::Value::IntValue(value) => Value::Integer(value),
+        any_value::Value::DoubleValue(value) => Value::Float(value),
+        any_value::Value::ArrayValue(array) => Value::Array(
+            array
+                .values
+                .into_iter()
+                .map(|value| value_from_any(value).unwrap_or(Value::Null))
+                .collect(),
+        ),
+        any_value::Value::KvlistValue(list) => attributes_to_value(list.values),
+        any_value::Value::BytesValue(bytes) => Value::Bytes(bytes.into()),
+    })
+}
+
+fn attributes_to_value(attributes: Vec<KeyValue>) -> Value {
+    Value::Map(
+        attributes
+            .into_iter()
+            .filter_map(|attribute| {
+                let value = attribute.value.and_then(value_from_any)?;
+                Some((attribute.key, value))
+            })
+            .collect::<BTreeMap<_, _>>(),
+    )
+}
+
+/// OTLP timestamps are nanoseconds since the epoch, with zero meaning unset.
+fn timestamp(nanos: u64) -> Option<DateTime<Utc>> {
+    match i64::try_from(nanos) {
+        Ok(0) | Err(_) => None,
+        Ok(nanos) => Some(Utc.timestamp_nanos(nanos)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<OpentelemetryConfig>();
+    }
+
+    #[test]
+    fn parses_default_addresses() {
+        let config: OpentelemetryConfig = toml::from_str("").unwrap();
+        assert_eq!(config.grpc.address, default_grpc_address());
+        assert_eq!(config.http.address, default_http_address());
+    }
+
+    #[test]
+    fn converts_nested_values() {
+        let value = AnyValue {
+            value: Some(any_value::Value::KvlistValue(
+                crate::proto::opentelemetry::proto::common::v1::KeyValueList {
+                    values: vec![KeyValue {
+                        key: "ids".to_owned(),
+                        value: Some(AnyValue {
+                            value: Some(any_value::Value::ArrayValue(
+                                crate::proto::opentelemetry::proto::common::v1::ArrayValue {
+                                    values: vec![
+                                        AnyValue {
+                                            value: Some(any_value::Value::IntValue(1)),
+                                        },
+                                        AnyValue { value: None },
+                                    ],
+                                },
+                            )),
+                        }),
+                    }],
+                },
+            )),
+        };
+
+        let mut expected = BTreeMap::new();
+        expected.insert(
+            "ids".to_owned(),
+            Value::Array(vec![Value::Integer(1), Value::Null]),
+        );
+        assert_eq!(value_from_any(value), Some(Value::Map(expected)));
+    }
+}
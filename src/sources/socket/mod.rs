@@ -14,6 +14,7 @@ use crate::{
     Pipeline,
 };
 use serde::{Deserialize, Serialize};
+use snafu::Snafu;
 use std::net::SocketAddr;
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -36,6 +37,29 @@ pub enum Mode {
     UnixStream(unix::UnixConfig),
 }
 
+/// How the received bytes are turned into events, for the TCP and UDP modes.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Derivative)]
+#[serde(rename_all = "snake_case")]
+#[derivative(Default)]
+pub enum Codec {
+    /// Each line is an event.
+    #[derivative(Default)]
+    Lines,
+    /// Each GELF message is an event. Messages are delimited by null bytes
+    /// over TCP, and may be chunked and compressed over UDP.
+    Gelf,
+}
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("The `framing` and `decoding` options can't be used with the `gelf` codec"))]
+    GelfCodecOptions,
+    #[snafu(display(
+        "The `bytes` framing can't be used in the `tcp` mode, as connections aren't bounded"
+    ))]
+    UnboundedFraming,
+}
+
 impl SocketConfig {
     pub fn new_tcp(tcp_config: tcp::TcpConfig) -> Self {
         tcp_config.into()
@@ -88,6 +112,7 @@ impl SourceConfig for SocketConfig {
     ) -> crate::Result<super::Source> {
         match self.mode.clone() {
             Mode::Tcp(config) => {
+                config.build_decoder()?;
                 let tcp = tcp::RawTcpSource {
                     config: config.clone(),
                 };
@@ -110,6 +135,7 @@ impl SourceConfig for SocketConfig {
                 Ok(udp::udp(
                     config.address(),
                     config.max_length(),
+                    config.build_decoder()?,
                     host_key,
                     #[cfg(unix)]
                     config.receive_buffer_bytes(),
@@ -147,7 +173,13 @@ impl SourceConfig for SocketConfig {
     }
 
     fn output_type(&self) -> DataType {
-        DataType::Log
+        let decoding = match &self.mode {
+            Mode::Tcp(config) => config.decoding(),
+            Mode::Udp(config) => config.decoding(),
+            #[cfg(unix)]
+            Mode::UnixDatagram(_) | Mode::UnixStream(_) => None,
+        };
+        decoding.map_or(DataType::Log, |decoding| decoding.output_type())
     }
 
     fn source_type(&self) -> &'static str {
@@ -168,11 +200,12 @@ impl SourceConfig for SocketConfig {
 
 #[cfg(test)]
 mod test {
-    use super::{tcp::TcpConfig, udp::UdpConfig, SocketConfig};
+    use super::{tcp::TcpConfig, udp::UdpConfig, Codec, SocketConfig};
     use crate::{
         config::{log_schema, GlobalOptions, SinkContext, SourceConfig},
         shutdown::{ShutdownSignal, SourceShutdownCoordinator},
         sinks::util::tcp::TcpSinkConfig,
+        sources::util::{DecodingConfig, FramingConfig},
         test_util::{
             collect_n, next_addr, random_string, send_lines, send_lines_tls, wait_for_tcp,
         },
@@ -191,6 +224,8 @@ mod test {
     };
 
     use tokio::{
+        io::AsyncWriteExt,
+        net::TcpStream,
         task::JoinHandle,
         time::{Duration, Instant},
     };
@@ -495,6 +530,185 @@ mod test {
         let _ = source_handle.await.unwrap();
     }
 
+    #[tokio::test]
+    async fn tcp_gelf_messages() {
+        let (tx, rx) = Pipeline::new_test();
+        let addr = next_addr();
+        let mut config = TcpConfig::from_address(addr.into());
+        config.set_codec(Codec::Gelf);
+
+        let server = SocketConfig::from(config)
+            .build(
+                "default",
+                &GlobalOptions::default(),
+                ShutdownSignal::noop(),
+                tx,
+            )
+            .await
+            .unwrap();
+        tokio::spawn(server);
+
+        wait_for_tcp(addr).await;
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        stream
+            .write_all(
+                concat!(
+                    r#"{"version":"1.1","host":"example.org","short_message":"one"}"#,
+                    "\0",
+                    r#"{"version":"1.1","host":"example.org","short_message":"two","_a":1}"#,
+                    "\0",
+                )
+                .as_bytes(),
+            )
+            .await
+            .unwrap();
+
+        let events = collect_n(rx, 2).await;
+        let log = events[0].as_log();
+        assert_eq!(log[log_schema().message_key()], "one".into());
+        assert_eq!(log[log_schema().host_key()], "example.org".into());
+        assert_eq!(log[log_schema().source_type_key()], "socket".into());
+        let log = events[1].as_log();
+        assert_eq!(log[log_schema().message_key()], "two".into());
+        assert_eq!(log["a"], 1.into());
+    }
+
+    #[tokio::test]
+    async fn tcp_decodes_json() {
+        let (tx, rx) = Pipeline::new_test();
+        let addr = next_addr();
+        let mut config = TcpConfig::from_address(addr.into());
+        config.set_decoding(Some(DecodingConfig::Json));
+
+        let server = SocketConfig::from(config)
+            .build(
+                "default",
+                &GlobalOptions::default(),
+                ShutdownSignal::noop(),
+                tx,
+            )
+            .await
+            .unwrap();
+        tokio::spawn(server);
+
+        wait_for_tcp(addr).await;
+        send_lines(
+            addr,
+            vec![
+                r#"{"message": "one", "a": 1}"#.to_owned(),
+                "not json".to_owned(),
+                r#"{"message": "two", "host": "example.org"}"#.to_owned(),
+            ]
+            .into_iter(),
+        )
+        .await
+        .unwrap();
+
+        let events = collect_n(rx, 2).await;
+        let log = events[0].as_log();
+        assert_eq!(log[log_schema().message_key()], "one".into());
+        assert_eq!(log["a"], 1.into());
+        assert_eq!(log[log_schema().source_type_key()], "socket".into());
+        assert!(log.contains(log_schema().host_key()));
+        let log = events[1].as_log();
+        assert_eq!(log[log_schema().message_key()], "two".into());
+        assert_eq!(log[log_schema().host_key()], "example.org".into());
+    }
+
+    #[tokio::test]
+    async fn tcp_rejects_invalid_framing() {
+        let mut config = TcpConfig::from_address(next_addr().into());
+        config.set_framing(Some(FramingConfig::CharacterDelimited {
+            delimiter: 'é',
+            max_length: None,
+        }));
+
+        let (tx, _rx) = Pipeline::new_test();
+        assert!(SocketConfig::from(config)
+            .build(
+                "default",
+                &GlobalOptions::default(),
+                ShutdownSignal::noop(),
+                tx,
+            )
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn tcp_rejects_gelf_with_decoding() {
+        let mut config = TcpConfig::from_address(next_addr().into());
+        config.set_codec(Codec::Gelf);
+        config.set_decoding(Some(DecodingConfig::Json));
+
+        let (tx, _rx) = Pipeline::new_test();
+        assert!(SocketConfig::from(config)
+            .build(
+                "default",
+                &GlobalOptions::default(),
+                ShutdownSignal::noop(),
+                tx,
+            )
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn tcp_rejects_bytes_framing() {
+        let mut config = TcpConfig::from_address(next_addr().into());
+        config.set_framing(Some(FramingConfig::Bytes));
+
+        let (tx, _rx) = Pipeline::new_test();
+        assert!(SocketConfig::from(config)
+            .build(
+                "default",
+                &GlobalOptions::default(),
+                ShutdownSignal::noop(),
+                tx,
+            )
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn tcp_applies_max_length_to_framing() {
+        let (tx, mut rx) = Pipeline::new_test();
+        let addr = next_addr();
+
+        let mut config = TcpConfig::from_address(addr.into());
+        config.set_max_length(10);
+        config.set_framing(Some(FramingConfig::NewlineDelimited { max_length: None }));
+
+        let server = SocketConfig::from(config)
+            .build(
+                "default",
+                &GlobalOptions::default(),
+                ShutdownSignal::noop(),
+                tx,
+            )
+            .await
+            .unwrap();
+        tokio::spawn(server);
+
+        let lines = vec![
+            "short".to_owned(),
+            "this is too long".to_owned(),
+            "more short".to_owned(),
+        ];
+
+        wait_for_tcp(addr).await;
+        send_lines(addr, lines.into_iter()).await.unwrap();
+
+        let event = rx.next().await.unwrap();
+        assert_eq!(event.as_log()[log_schema().message_key()], "short".into());
+
+        let event = rx.next().await.unwrap();
+        assert_eq!(
+            event.as_log()[log_schema().message_key()],
+            "more short".into()
+        );
+    }
+
     //////// UDP TESTS ////////
     fn send_lines_udp(addr: SocketAddr, lines: impl IntoIterator<Item = String>) -> SocketAddr {
         let bind = next_addr();
@@ -704,6 +918,68 @@ mod test {
         assert!(pump_handle.join().is_ok());
     }
 
+    #[tokio::test]
+    async fn udp_rejects_gelf_with_framing() {
+        let config: UdpConfig = toml::from_str(&format!(
+            r#"address = "{}"
+            codec = "gelf"
+            framing.method = "bytes""#,
+            next_addr()
+        ))
+        .unwrap();
+
+        let (tx, _rx) = Pipeline::new_test();
+        assert!(SocketConfig::from(config)
+            .build(
+                "default",
+                &GlobalOptions::default(),
+                ShutdownSignal::noop(),
+                tx,
+            )
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn udp_gelf_chunked_message() {
+        let (tx, rx) = Pipeline::new_test();
+        let address = next_addr();
+        let config: UdpConfig = toml::from_str(&format!(
+            r#"address = "{}"
+            codec = "gelf""#,
+            address
+        ))
+        .unwrap();
+
+        let server = SocketConfig::from(config)
+            .build(
+                "default",
+                &GlobalOptions::default(),
+                ShutdownSignal::noop(),
+                tx,
+            )
+            .await
+            .unwrap();
+        tokio::spawn(server);
+        tokio::time::delay_for(Duration::from_millis(100)).await;
+
+        let message = br#"{"version":"1.1","host":"example.org","short_message":"chunked"}"#;
+        let (first, second) = message.split_at(message.len() / 2);
+        let chunk = |number: u8, data: &[u8]| {
+            let mut chunk = vec![0x1e, 0x0f, 1, 2, 3, 4, 5, 6, 7, 8, number, 2];
+            chunk.extend_from_slice(data);
+            chunk
+        };
+        let socket = UdpSocket::bind(next_addr()).unwrap();
+        socket.send_to(&chunk(1, second), address).unwrap();
+        socket.send_to(&chunk(0, first), address).unwrap();
+
+        let events = collect_n(rx, 1).await;
+        let log = events[0].as_log();
+        assert_eq!(log[log_schema().message_key()], "chunked".into());
+        assert_eq!(log[log_schema().host_key()], "example.org".into());
+    }
+
     ////////////// UNIX TEST LIBS //////////////
     #[cfg(unix)]
     async fn init_unix(sender: Pipeline, stream: bool) -> PathBuf {
@@ -817,6 +1093,24 @@ mod test {
         .unwrap()
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn unix_rejects_framing_and_decoding() {
+        for mode in &["unix_datagram", "unix_stream"] {
+            for option in &[r#"framing.method = "bytes""#, r#"decoding.codec = "json""#] {
+                assert!(toml::from_str::<SocketConfig>(&format!(
+                    r#"
+                       mode = "{}"
+                       path = "/does/not/exist"
+                       {}
+                    "#,
+                    mode, option
+                ))
+                .is_err());
+            }
+        }
+    }
+
     ////////////// UNIX DATAGRAM TESTS //////////////
     #[cfg(unix)]
     async fn send_lines_unix_datagram(path: PathBuf, lines: &[&str]) {
@@ -1,14 +1,17 @@
+use super::{BuildError, Codec};
 use crate::{
     event::Event,
-    internal_events::{SocketEventReceived, SocketMode},
-    sources::util::{SocketListenAddr, TcpSource},
+    gelf,
+    internal_events::{GelfDecodeFailed, SocketEventReceived, SocketMode},
+    sources::util::{Decoder, DecodingConfig, FramingConfig, SocketListenAddr, TcpSource},
     tcp::TcpKeepaliveConfig,
     tls::TlsConfig,
 };
-use bytes::Bytes;
+use bytes::{Bytes, BytesMut};
 use codec::BytesDelimitedCodec;
 use getset::{CopyGetters, Getters, Setters};
 use serde::{Deserialize, Serialize};
+use std::io;
 
 #[derive(Deserialize, Serialize, Debug, Clone, Getters, CopyGetters, Setters)]
 pub struct TcpConfig {
@@ -28,6 +31,13 @@ pub struct TcpConfig {
     tls: Option<TlsConfig>,
     #[get_copy = "pub"]
     receive_buffer_bytes: Option<usize>,
+    #[serde(default)]
+    #[getset(get_copy = "pub", set = "pub")]
+    codec: Codec,
+    #[getset(get = "pub", set = "pub")]
+    framing: Option<FramingConfig>,
+    #[getset(get_copy = "pub", set = "pub")]
+    decoding: Option<DecodingConfig>,
 }
 
 fn default_max_length() -> usize {
@@ -47,6 +57,7 @@ impl TcpConfig {
         host_key: Option<String>,
         tls: Option<TlsConfig>,
         receive_buffer_bytes: Option<usize>,
+        codec: Codec,
     ) -> Self {
         Self {
             address,
@@ -56,6 +67,9 @@ impl TcpConfig {
             host_key,
             tls,
             receive_buffer_bytes,
+            codec,
+            framing: None,
+            decoding: None,
         }
     }
 
@@ -68,36 +82,121 @@ impl TcpConfig {
             host_key: None,
             tls: None,
             receive_buffer_bytes: None,
+            codec: Codec::default(),
+            framing: None,
+            decoding: None,
+        }
+    }
+
+    /// Builds the decoder of the connections, which splits lines into
+    /// events unless configured otherwise. Frames are limited to
+    /// `max_length` unless their framing sets a limit of its own.
+    pub fn build_decoder(&self) -> crate::Result<TcpDecoder> {
+        if self.codec == Codec::Gelf && (self.framing.is_some() || self.decoding.is_some()) {
+            return Err(BuildError::GelfCodecOptions.into());
+        }
+        if self.framing == Some(FramingConfig::Bytes) {
+            return Err(BuildError::UnboundedFraming.into());
+        }
+
+        let framing = self
+            .framing
+            .clone()
+            .map(|framing| framing.with_default_max_length(self.max_length));
+        Ok(match self.codec {
+            Codec::Lines => TcpDecoder::Lines(Decoder::from_config(
+                framing.as_ref(),
+                FramingConfig::NewlineDelimited {
+                    max_length: Some(self.max_length),
+                },
+                self.decoding,
+            )?),
+            Codec::Gelf => TcpDecoder::Gelf(BytesDelimitedCodec::new_with_max_length(
+                b'\0',
+                self.max_length,
+            )),
+        })
+    }
+}
+
+/// Decodes the events of a connection, along with the byte size of their frame.
+#[derive(Debug)]
+pub enum TcpDecoder {
+    Lines(Decoder),
+    Gelf(BytesDelimitedCodec),
+}
+
+impl TcpDecoder {
+    fn decode_frames(
+        &mut self,
+        src: &mut BytesMut,
+        eof: bool,
+    ) -> Result<Option<(Event, usize)>, io::Error> {
+        use tokio_util::codec::Decoder as _;
+
+        match self {
+            Self::Lines(decoder) if eof => decoder.decode_eof(src),
+            Self::Lines(decoder) => decoder.decode(src),
+            Self::Gelf(decoder) => loop {
+                let frame = if eof {
+                    decoder.decode_eof(src)?
+                } else {
+                    decoder.decode(src)?
+                };
+                let frame = match frame {
+                    Some(frame) => frame,
+                    None => return Ok(None),
+                };
+                match gelf::decode(&frame) {
+                    Ok(log) => return Ok(Some((Event::from(log), frame.len()))),
+                    Err(error) => emit!(GelfDecodeFailed { error: &error }),
+                }
+            },
         }
     }
 }
 
+impl tokio_util::codec::Decoder for TcpDecoder {
+    type Item = (Event, usize);
+    type Error = io::Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, io::Error> {
+        self.decode_frames(src, false)
+    }
+
+    fn decode_eof(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, io::Error> {
+        self.decode_frames(src, true)
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct RawTcpSource {
     pub config: TcpConfig,
 }
 
 impl TcpSource for RawTcpSource {
-    type Error = std::io::Error;
-    type Decoder = BytesDelimitedCodec;
+    type Error = io::Error;
+    type Decoder = TcpDecoder;
 
     fn decoder(&self) -> Self::Decoder {
-        BytesDelimitedCodec::new_with_max_length(b'\n', self.config.max_length)
+        self.config
+            .build_decoder()
+            .expect("The decoder is validated when the source is built.")
     }
 
-    fn build_event(&self, frame: Bytes, host: Bytes) -> Option<Event> {
-        let byte_size = frame.len();
-        let mut event = Event::from(frame);
+    fn build_event(&self, (mut event, byte_size): (Event, usize), host: Bytes) -> Option<Event> {
+        if let Event::Log(log) = &mut event {
+            log.insert(
+                crate::config::log_schema().source_type_key(),
+                Bytes::from("socket"),
+            );
 
-        event.as_mut_log().insert(
-            crate::config::log_schema().source_type_key(),
-            Bytes::from("socket"),
-        );
+            let host_key = (self.config.host_key.clone())
+                .unwrap_or_else(|| crate::config::log_schema().host_key().to_string());
 
-        let host_key = (self.config.host_key.clone())
-            .unwrap_or_else(|| crate::config::log_schema().host_key().to_string());
-
-        event.as_mut_log().insert(host_key, host);
+            // GELF messages, and decoded events, may carry the host they originate from.
+            log.try_insert(host_key, host);
+        }
 
         emit!(SocketEventReceived {
             byte_size,
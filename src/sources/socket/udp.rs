@@ -1,20 +1,23 @@
+use super::{BuildError, Codec};
 #[cfg(unix)]
 use crate::udp;
 use crate::{
     event::Event,
-    internal_events::{SocketEventReceived, SocketMode, SocketReceiveError},
+    gelf::{self, ChunkAssembler},
+    internal_events::{GelfDecodeFailed, SocketEventReceived, SocketMode, SocketReceiveError},
     shutdown::ShutdownSignal,
-    sources::Source,
+    sources::{
+        util::{Decoder, DecodingConfig, FramingConfig},
+        Source,
+    },
     Pipeline,
 };
 use bytes::{Bytes, BytesMut};
-use codec::BytesDelimitedCodec;
 use futures::SinkExt;
 use getset::{CopyGetters, Getters};
 use serde::{Deserialize, Serialize};
 use std::net::SocketAddr;
 use tokio::net::UdpSocket;
-use tokio_util::codec::Decoder;
 
 /// UDP processes messages per packet, where messages are separated by newline.
 #[derive(Deserialize, Serialize, Debug, Clone, Getters, CopyGetters)]
@@ -30,6 +33,13 @@ pub struct UdpConfig {
     #[cfg(unix)]
     #[get_copy = "pub"]
     receive_buffer_bytes: Option<usize>,
+    #[serde(default)]
+    #[get_copy = "pub"]
+    codec: Codec,
+    #[get = "pub"]
+    framing: Option<FramingConfig>,
+    #[get_copy = "pub"]
+    decoding: Option<DecodingConfig>,
 }
 
 fn default_max_length() -> usize {
@@ -44,6 +54,44 @@ impl UdpConfig {
             host_key: None,
             #[cfg(unix)]
             receive_buffer_bytes: None,
+            codec: Codec::default(),
+            framing: None,
+            decoding: None,
+        }
+    }
+
+    /// Builds the decoder of the datagrams, which splits lines into events
+    /// unless configured otherwise.
+    pub fn build_decoder(&self) -> crate::Result<UdpDecoder> {
+        if self.codec == Codec::Gelf && (self.framing.is_some() || self.decoding.is_some()) {
+            return Err(BuildError::GelfCodecOptions.into());
+        }
+
+        Ok(match self.codec {
+            Codec::Lines => UdpDecoder::Lines(Decoder::from_config(
+                self.framing.as_ref(),
+                FramingConfig::NewlineDelimited { max_length: None },
+                self.decoding,
+            )?),
+            Codec::Gelf => UdpDecoder::Gelf(ChunkAssembler::new()),
+        })
+    }
+}
+
+/// Decodes the events of the datagrams.
+#[derive(Debug)]
+pub enum UdpDecoder {
+    /// Each datagram is decoded on its own, its last frame stretching to its end.
+    Lines(Decoder),
+    /// GELF messages may be chunked across datagrams.
+    Gelf(ChunkAssembler),
+}
+
+impl UdpDecoder {
+    fn decode_datagram(&mut self, datagram: Bytes) -> Vec<Event> {
+        match self {
+            Self::Lines(decoder) => decoder.decode_all(datagram),
+            Self::Gelf(chunks) => decode_gelf(chunks, datagram).into_iter().collect(),
         }
     }
 }
@@ -51,6 +99,7 @@ impl UdpConfig {
 pub fn udp(
     address: SocketAddr,
     max_length: usize,
+    mut decoder: UdpDecoder,
     host_key: String,
     #[cfg(unix)] receive_buffer_bytes: Option<usize>,
     mut shutdown: ShutdownSignal,
@@ -89,20 +138,14 @@ pub fn udp(
                         });
                     })?;
 
-                    let mut payload = buf.split_to(byte_size);
+                    let payload = buf.split_to(byte_size);
 
-                    // UDP processes messages per payload, where messages are separated by newline
-                    // and stretch to end of payload.
-                    let mut decoder = BytesDelimitedCodec::new(b'\n');
-                    while let Ok(Some(line)) = decoder.decode_eof(&mut payload) {
-                        let mut event = Event::from(line);
-
-                        event
-                            .as_mut_log()
-                            .insert(crate::config::log_schema().source_type_key(), Bytes::from("socket"));
-                        event
-                            .as_mut_log()
-                            .insert(host_key.clone(), address.to_string());
+                    for mut event in decoder.decode_datagram(payload.freeze()) {
+                        if let Event::Log(log) = &mut event {
+                            log.insert(crate::config::log_schema().source_type_key(), Bytes::from("socket"));
+                            // GELF messages, and decoded events, may carry the host they originate from.
+                            log.try_insert(&host_key, address.to_string());
+                        }
 
                         emit!(SocketEventReceived { byte_size,mode:SocketMode::Udp });
 
@@ -120,3 +163,16 @@ pub fn udp(
         }
     })
 }
+
+fn decode_gelf(chunks: &mut ChunkAssembler, datagram: Bytes) -> Option<Event> {
+    let message = chunks
+        .push(datagram)
+        .and_then(|message| message.map(|message| gelf::decode(&message)).transpose());
+    match message {
+        Ok(log) => log.map(Event::from),
+        Err(error) => {
+            emit!(GelfDecodeFailed { error: &error });
+            None
+        }
+    }
+}
@@ -88,6 +88,6 @@ pub(super) fn unix_stream(
         host_key,
         shutdown,
         out,
-        |host_key, received_from, line| Some(build_event(host_key, received_from, line)),
+        |host_key, received_from, line| Some(build_event(host_key, received_from, &line)),
     )
 }
@@ -1,6 +1,6 @@
 use crate::{
     config::{log_schema, DataType, GlobalOptions, Resource, SourceConfig, SourceDescription},
-    event::{Event, LogEvent, Value},
+    event::{BatchNotifier, BatchStatus, BatchStatusReceiver, Event, LogEvent, Value},
     internal_events::{
         SplunkHECEventReceived, SplunkHECRequestBodyInvalid, SplunkHECRequestError,
         SplunkHECRequestReceived,
@@ -12,7 +12,7 @@ use crate::{
 use bytes::{buf::BufExt, Bytes};
 use chrono::{DateTime, TimeZone, Utc};
 use flate2::read::GzDecoder;
-use futures::{FutureExt, SinkExt, StreamExt, TryFutureExt};
+use futures::{FutureExt, SinkExt, StreamExt, TryFutureExt, TryStreamExt};
 use futures01::{Async, Stream};
 use http::StatusCode;
 use serde::{de, Deserialize, Serialize};
@@ -42,6 +42,8 @@ pub struct SplunkConfig {
     /// Splunk HEC token
     token: Option<String>,
     tls: Option<TlsConfig>,
+    /// Wait for events to be delivered by the sinks before responding
+    acknowledgements: bool,
 }
 
 inventory::submit! {
@@ -66,6 +68,7 @@ impl Default for SplunkConfig {
             address: default_socket_address(),
             token: None,
             tls: None,
+            acknowledgements: false,
         }
     }
 }
@@ -144,6 +147,7 @@ impl SourceConfig for SplunkConfig {
 /// Shared data for responding to requests.
 struct SplunkSource {
     credentials: Option<Bytes>,
+    acknowledgements: bool,
 }
 
 impl SplunkSource {
@@ -153,10 +157,12 @@ impl SplunkSource {
                 .token
                 .as_ref()
                 .map(|token| format!("Splunk {}", token).into()),
+            acknowledgements: config.acknowledgements,
         }
     }
 
     fn event_service(&self, out: Pipeline) -> BoxedFilter<(Response,)> {
+        let acknowledgements = self.acknowledgements;
         warp::post()
             .and(path!("event").or(path!("event" / "1.0")))
             .and(self.authorization())
@@ -171,7 +177,14 @@ impl SplunkSource {
                       host: Option<String>,
                       gzip: bool,
                       body: Bytes| {
-                    process_service_request(out.clone(), channel, host, gzip, body)
+                    process_service_request(
+                        out.clone(),
+                        channel,
+                        host,
+                        gzip,
+                        body,
+                        acknowledgements,
+                    )
                 },
             )
             .map(finish_ok)
@@ -179,6 +192,7 @@ impl SplunkSource {
     }
 
     fn raw_service(&self, out: Pipeline) -> BoxedFilter<(Response,)> {
+        let acknowledgements = self.acknowledgements;
         warp::post()
             .and(path!("raw" / "1.0").or(path!("raw")))
             .and(self.authorization())
@@ -200,14 +214,17 @@ impl SplunkSource {
                 move |_, _, channel: String, host: Option<String>, gzip: bool, body: Bytes| {
                     let out = out.clone();
                     async move {
+                        let (batch, receiver) =
+                            BatchNotifier::maybe_new_with_receiver(acknowledgements);
                         // Construct event parser
                         let event = future::ready(raw_event(body, gzip, channel, host));
                         futures::stream::once(event)
+                            .map_ok(move |event| event.with_batch_notifier_option(&batch))
                             .forward(
                                 out.sink_map_err(|_| Rejection::from(ApiError::ServerShutdown)),
                             )
-                            .map_ok(|_| ())
-                            .await
+                            .await?;
+                        await_delivery(receiver).await
                     }
                 },
             )
@@ -295,6 +312,7 @@ async fn process_service_request(
     host: Option<String>,
     gzip: bool,
     body: Bytes,
+    acknowledgements: bool,
 ) -> Result<(), Rejection> {
     use futures::compat::Stream01CompatExt;
 
@@ -306,7 +324,10 @@ async fn process_service_request(
         Box::new(body.reader())
     };
 
-    let stream = EventStream::new(reader, channel, host).compat();
+    let (batch, receiver) = BatchNotifier::maybe_new_with_receiver(acknowledgements);
+    let stream = EventStream::new(reader, channel, host)
+        .compat()
+        .map_ok(move |event| event.with_batch_notifier_option(&batch));
 
     let res = stream.forward(&mut out).await;
 
@@ -314,7 +335,22 @@ async fn process_service_request(
         .map_err(|_| Rejection::from(ApiError::ServerShutdown))
         .await?;
 
-    res.map(|_| ())
+    res?;
+    await_delivery(receiver).await
+}
+
+/// Wait until the sinks have reported on every event of the request, if
+/// acknowledgements are enabled.
+async fn await_delivery(receiver: Option<BatchStatusReceiver>) -> Result<(), Rejection> {
+    match receiver {
+        None => Ok(()),
+        Some(receiver) => match receiver.await {
+            BatchStatus::Delivered => Ok(()),
+            BatchStatus::Errored | BatchStatus::Failed => {
+                Err(Rejection::from(ApiError::DeliveryFailed))
+            }
+        },
+    }
 }
 
 /// Constructs one ore more events from json-s coming from reader.
@@ -630,6 +666,7 @@ pub(crate) enum ApiError {
     EmptyEventField { event: usize },
     MissingEventField { event: usize },
     BadRequest,
+    DeliveryFailed,
 }
 
 impl From<ApiError> for Rejection {
@@ -707,6 +744,10 @@ async fn finish_err(rejection: Rejection) -> Result<(Response,), Rejection> {
                 event_error("Event field is required", 12, event)
             }
             ApiError::BadRequest => empty_response(StatusCode::BAD_REQUEST),
+            ApiError::DeliveryFailed => response_json(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                splunk_response::SERVER_ERROR.as_ref(),
+            ),
         },))
     } else {
         Err(rejection)
@@ -751,7 +792,7 @@ mod tests {
     use super::{parse_timestamp, SplunkConfig};
     use crate::{
         config::{log_schema, GlobalOptions, SinkConfig, SinkContext, SourceConfig},
-        event::Event,
+        event::{Event, EventStatus},
         shutdown::ShutdownSignal,
         sinks::{
             splunk_hec::{Encoding, HecSinkConfig},
@@ -775,10 +816,13 @@ mod tests {
     const TOKEN: &str = "token";
 
     async fn source() -> (mpsc::Receiver<Event>, SocketAddr) {
-        source_with(Some(TOKEN.to_owned())).await
+        source_with(Some(TOKEN.to_owned()), false).await
     }
 
-    async fn source_with(token: Option<String>) -> (mpsc::Receiver<Event>, SocketAddr) {
+    async fn source_with(
+        token: Option<String>,
+        acknowledgements: bool,
+    ) -> (mpsc::Receiver<Event>, SocketAddr) {
         let (sender, recv) = Pipeline::new_test();
         let address = next_addr();
         tokio::spawn(async move {
@@ -786,6 +830,7 @@ mod tests {
                 address,
                 token,
                 tls: None,
+                acknowledgements,
             }
             .build(
                 "default",
@@ -1021,6 +1066,44 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn responds_after_delivery() {
+        trace_init();
+
+        let (mut source, address) = source_with(Some(TOKEN.to_owned()), true).await;
+
+        let response = tokio::spawn(post(address, "services/collector/raw", "delivered"));
+        let event = source.next().await.unwrap();
+        event
+            .metadata()
+            .finalizers()
+            .update_status(EventStatus::Delivered);
+        drop(event);
+
+        assert_eq!(200, response.await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn responds_with_error_when_not_delivered() {
+        trace_init();
+
+        let (mut source, address) = source_with(Some(TOKEN.to_owned()), true).await;
+
+        let response = tokio::spawn(post(
+            address,
+            "services/collector/event",
+            r#"{"event":"failed"}"#,
+        ));
+        let event = source.next().await.unwrap();
+        event
+            .metadata()
+            .finalizers()
+            .update_status(EventStatus::Failed);
+        drop(event);
+
+        assert_eq!(500, response.await.unwrap());
+    }
+
     #[tokio::test]
     async fn no_data() {
         trace_init();
@@ -1047,7 +1130,7 @@ mod tests {
         trace_init();
 
         let message = "no_authorization";
-        let (source, address) = source_with(None).await;
+        let (source, address) = source_with(None, false).await;
         let (sink, health) = sink(address, Encoding::Text, Compression::gzip_default()).await;
         assert!(health.await.is_ok());
 
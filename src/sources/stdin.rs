@@ -3,13 +3,15 @@ use crate::{
     event::Event,
     internal_events::{StdinEventReceived, StdinReadFailed},
     shutdown::ShutdownSignal,
+    sources::util::{Decoder, DecodingConfig, FramingConfig},
     Pipeline,
 };
-use bytes::Bytes;
+use bytes::{Bytes, BytesMut};
 use futures::{executor, FutureExt, SinkExt, StreamExt, TryStreamExt};
 use serde::{Deserialize, Serialize};
 use std::{io, thread};
 use tokio::sync::mpsc::channel;
+use tokio_util::codec::Decoder as _;
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(deny_unknown_fields, default)]
@@ -17,6 +19,8 @@ pub struct StdinConfig {
     #[serde(default = "default_max_length")]
     pub max_length: usize,
     pub host_key: Option<String>,
+    pub framing: Option<FramingConfig>,
+    pub decoding: Option<DecodingConfig>,
 }
 
 impl Default for StdinConfig {
@@ -24,6 +28,8 @@ impl Default for StdinConfig {
         StdinConfig {
             max_length: default_max_length(),
             host_key: None,
+            framing: None,
+            decoding: None,
         }
     }
 }
@@ -52,7 +58,8 @@ impl SourceConfig for StdinConfig {
     }
 
     fn output_type(&self) -> DataType {
-        DataType::Log
+        self.decoding
+            .map_or(DataType::Log, |decoding| decoding.output_type())
     }
 
     fn source_type(&self) -> &'static str {
@@ -65,7 +72,7 @@ impl SourceConfig for StdinConfig {
 }
 
 pub fn stdin_source<R>(
-    stdin: R,
+    mut stdin: R,
     config: StdinConfig,
     shutdown: ShutdownSignal,
     out: Pipeline,
@@ -77,6 +84,13 @@ where
         .host_key
         .unwrap_or_else(|| log_schema().host_key().to_string());
     let hostname = crate::get_hostname().ok();
+    let mut decoder = Decoder::from_config(
+        config.framing.as_ref(),
+        FramingConfig::NewlineDelimited {
+            max_length: Some(config.max_length),
+        },
+        config.decoding,
+    )?;
 
     let (mut sender, receiver) = channel(1024);
 
@@ -84,9 +98,43 @@ where
     thread::spawn(move || {
         info!("Capturing STDIN.");
 
-        for line in stdin.lines() {
-            if executor::block_on(sender.send(line)).is_err() {
-                // receiver has closed so we should shutdown
+        let mut buffer = BytesMut::new();
+        loop {
+            let read = stdin.fill_buf().map(|chunk| {
+                buffer.extend_from_slice(chunk);
+                chunk.len()
+            });
+            let eof = match read {
+                Ok(0) => true,
+                Ok(len) => {
+                    stdin.consume(len);
+                    false
+                }
+                Err(error) => {
+                    let _ = executor::block_on(sender.send(Err(error)));
+                    return;
+                }
+            };
+
+            loop {
+                let decoded = if eof {
+                    decoder.decode_eof(&mut buffer)
+                } else {
+                    decoder.decode(&mut buffer)
+                };
+                let decoded = match decoded {
+                    Ok(Some(decoded)) => Ok(decoded),
+                    Ok(None) => break,
+                    Err(error) => Err(error),
+                };
+                let failed = decoded.is_err();
+                if executor::block_on(sender.send(decoded)).is_err() || failed {
+                    // receiver has closed so we should shutdown
+                    return;
+                }
+            }
+
+            if eof {
                 return;
             }
         }
@@ -99,11 +147,9 @@ where
         let res = receiver
             .take_until(shutdown)
             .map_err(|error| emit!(StdinReadFailed { error }))
-            .map_ok(move |line| {
-                emit!(StdinEventReceived {
-                    byte_size: line.len()
-                });
-                create_event(Bytes::from(line), &host_key, &hostname)
+            .map_ok(move |(event, byte_size)| {
+                emit!(StdinEventReceived { byte_size });
+                create_event(event, &host_key, &hostname)
             })
             .forward(&mut out)
             .inspect(|_| info!("Finished sending."))
@@ -115,16 +161,14 @@ where
     }))
 }
 
-fn create_event(line: Bytes, host_key: &str, hostname: &Option<String>) -> Event {
-    let mut event = Event::from(line);
+fn create_event(mut event: Event, host_key: &str, hostname: &Option<String>) -> Event {
+    if let Event::Log(log) = &mut event {
+        // Add source type
+        log.insert(log_schema().source_type_key(), Bytes::from("stdin"));
 
-    // Add source type
-    event
-        .as_mut_log()
-        .insert(log_schema().source_type_key(), Bytes::from("stdin"));
-
-    if let Some(hostname) = &hostname {
-        event.as_mut_log().insert(host_key, hostname.clone());
+        if let Some(hostname) = &hostname {
+            log.try_insert(host_key, hostname.clone());
+        }
     }
 
     event
@@ -148,7 +192,7 @@ mod tests {
         let host_key = "host".to_string();
         let hostname = Some("Some.Machine".to_string());
 
-        let event = create_event(line, &host_key, &hostname);
+        let event = create_event(Event::from(line), &host_key, &hostname);
         let log = event.into_log();
 
         assert_eq!(log["host"], "Some.Machine".into());
@@ -188,4 +232,32 @@ mod tests {
         assert!(event.is_err());
         assert_eq!(Err(mpsc::error::TryRecvError::Closed), event);
     }
+
+    #[tokio::test]
+    async fn stdin_decodes_json() {
+        trace_init();
+
+        let (tx, mut rx) = Pipeline::new_test();
+        let config = StdinConfig {
+            decoding: Some(DecodingConfig::Json),
+            ..StdinConfig::default()
+        };
+        let buf = Cursor::new("{\"message\": \"hello\", \"a\": 1}\nnot json\n[{\"a\": 2}]");
+
+        stdin_source(buf, config, ShutdownSignal::noop(), tx)
+            .unwrap()
+            .await
+            .unwrap();
+
+        let event = rx.try_recv().unwrap();
+        let log = event.as_log();
+        assert_eq!(log[log_schema().message_key()], "hello".into());
+        assert_eq!(log["a"], 1.into());
+        assert_eq!(log[log_schema().source_type_key()], "stdin".into());
+
+        let event = rx.try_recv().unwrap();
+        assert_eq!(event.as_log()["a"], 2.into());
+
+        assert_eq!(Err(mpsc::error::TryRecvError::Closed), rx.try_recv());
+    }
 }
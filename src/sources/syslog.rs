@@ -1,4 +1,4 @@
-use super::util::{SocketListenAddr, TcpSource};
+use super::util::{insert_fields_from_syslog, resolve_year, SocketListenAddr, TcpSource};
 #[cfg(unix)]
 use crate::sources::util::build_unix_stream_source;
 #[cfg(unix)]
@@ -8,7 +8,7 @@ use crate::{
         log_schema, DataType, GenerateConfig, GlobalOptions, Resource, SourceConfig,
         SourceDescription,
     },
-    event::{Event, Value},
+    event::Event,
     internal_events::{SyslogEventReceived, SyslogUdpReadError, SyslogUdpUtf8Error},
     shutdown::ShutdownSignal,
     tcp::TcpKeepaliveConfig,
@@ -16,7 +16,7 @@ use crate::{
     Pipeline,
 };
 use bytes::{Buf, Bytes, BytesMut};
-use chrono::{Datelike, Utc};
+use chrono::Utc;
 use derive_is_enum_variant::is_enum_variant;
 use futures::{SinkExt, StreamExt};
 use serde::{Deserialize, Serialize};
@@ -24,7 +24,6 @@ use std::io;
 use std::net::SocketAddr;
 #[cfg(unix)]
 use std::path::PathBuf;
-use syslog_loose::{IncompleteDate, Message, ProcId, Protocol};
 use tokio::net::UdpSocket;
 use tokio_util::{
     codec::{BytesCodec, Decoder, LinesCodec, LinesCodecError},
@@ -154,7 +153,7 @@ impl SourceConfig for SyslogConfig {
                 host_key,
                 shutdown,
                 out,
-                |host_key, default_host, line| Some(event_from_str(host_key, default_host, line)),
+                |host_key, default_host, line| Some(event_from_str(host_key, default_host, &line)),
             )),
         }
     }
@@ -353,18 +352,6 @@ pub fn udp(
     })
 }
 
-/// Function used to resolve the year for syslog messages that don't include the year.
-/// If the current month is January, and the syslog message is for December, it will take the previous year.
-/// Otherwise, take the current year.
-fn resolve_year((month, _date, _hour, _min, _sec): IncompleteDate) -> i32 {
-    let now = Utc::now();
-    if now.month() == 1 && month == 12 {
-        now.year() - 1
-    } else {
-        now.year()
-    }
-}
-
 /**
 * Function to pass to build_unix_stream_source, specific to the Unix mode of the syslog source.
 * Handles the logic of parsing and decoding the syslog message format.
@@ -413,43 +400,6 @@ fn event_from_str(host_key: &str, default_host: Option<Bytes>, line: &str) -> Ev
     event
 }
 
-fn insert_fields_from_syslog(event: &mut Event, parsed: Message<&str>) {
-    let log = event.as_mut_log();
-
-    if let Some(host) = parsed.hostname {
-        log.insert("hostname", host.to_string());
-    }
-    if let Some(severity) = parsed.severity {
-        log.insert("severity", severity.as_str().to_owned());
-    }
-    if let Some(facility) = parsed.facility {
-        log.insert("facility", facility.as_str().to_owned());
-    }
-    if let Protocol::RFC5424(version) = parsed.protocol {
-        log.insert("version", version as i64);
-    }
-    if let Some(app_name) = parsed.appname {
-        log.insert("appname", app_name.to_owned());
-    }
-    if let Some(msg_id) = parsed.msgid {
-        log.insert("msgid", msg_id.to_owned());
-    }
-    if let Some(procid) = parsed.procid {
-        let value: Value = match procid {
-            ProcId::PID(pid) => pid.into(),
-            ProcId::Name(name) => name.to_string().into(),
-        };
-        log.insert("procid", value);
-    }
-
-    for element in parsed.structured_data.into_iter() {
-        for (name, value) in element.params.into_iter() {
-            let key = format!("{}.{}", element.id, name);
-            log.insert(key, value.to_string());
-        }
-    }
-}
-
 #[cfg(test)]
 mod test {
     use super::{event_from_str, Mode, SyslogConfig};
@@ -0,0 +1,355 @@
+use super::framing::{Framer, FramingConfig, FramingError};
+use crate::{
+    config::{log_schema, DataType},
+    event::{proto, Event, LogEvent, Value},
+    internal_events::DecoderParseFailed,
+};
+use bytes::{Bytes, BytesMut};
+use chrono::{Datelike, Utc};
+use prost::Message as _;
+use serde::{Deserialize, Serialize};
+use snafu::{ResultExt, Snafu};
+use std::{collections::VecDeque, io};
+use syslog_loose::{IncompleteDate, Message, ProcId, Protocol};
+use tokio_util::codec::Decoder as _;
+
+#[derive(Debug, Snafu)]
+pub enum DecodingError {
+    #[snafu(display("Invalid JSON: {}", source))]
+    InvalidJson { source: serde_json::Error },
+    #[snafu(display("Expected a JSON object, or an array of objects"))]
+    NotAnObject,
+    #[snafu(display("Invalid UTF-8: {}", source))]
+    InvalidUtf8 { source: std::str::Utf8Error },
+    #[snafu(display("Invalid native event: {}", source))]
+    InvalidProtobuf { source: prost::DecodeError },
+}
+
+/// How events are parsed from the frames.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Derivative)]
+#[serde(tag = "codec", rename_all = "snake_case")]
+#[derivative(Default)]
+pub enum DecodingConfig {
+    /// The frame is the message of the event.
+    #[derivative(Default)]
+    Bytes,
+    /// The frame is a JSON object, or an array of objects, whose fields are
+    /// those of the events.
+    Json,
+    /// The frame is a syslog message.
+    Syslog,
+    /// The frame is an event encoded with Vector's native protobuf format.
+    NativeProtobuf,
+}
+
+impl DecodingConfig {
+    pub fn output_type(&self) -> DataType {
+        match self {
+            Self::NativeProtobuf => DataType::Any,
+            _ => DataType::Log,
+        }
+    }
+
+    pub fn parse(&self, frame: Bytes) -> Result<Vec<Event>, DecodingError> {
+        match self {
+            Self::Bytes => Ok(vec![Event::from(frame)]),
+            Self::Json => parse_json(&frame),
+            Self::Syslog => {
+                let line = std::str::from_utf8(&frame).context(InvalidUtf8)?;
+                Ok(vec![parse_syslog(line)])
+            }
+            Self::NativeProtobuf => {
+                let event = proto::EventWrapper::decode(frame).context(InvalidProtobuf)?;
+                Ok(vec![Event::from(event)])
+            }
+        }
+    }
+}
+
+fn parse_json(frame: &[u8]) -> Result<Vec<Event>, DecodingError> {
+    let json_to_event = |json: serde_json::Value| match json {
+        serde_json::Value::Object(fields) => {
+            let mut log = LogEvent::default();
+            for (key, value) in fields {
+                log.insert_flat(key, value);
+            }
+            log.try_insert(log_schema().timestamp_key(), Utc::now());
+            Ok(Event::from(log))
+        }
+        _ => Err(DecodingError::NotAnObject),
+    };
+
+    match serde_json::from_slice::<serde_json::Value>(frame).context(InvalidJson)? {
+        serde_json::Value::Array(values) => values.into_iter().map(json_to_event).collect(),
+        value => Ok(vec![json_to_event(value)?]),
+    }
+}
+
+fn parse_syslog(line: &str) -> Event {
+    let parsed = syslog_loose::parse_message_with_year(line.trim(), resolve_year);
+    let mut event = Event::from(parsed.msg);
+    let log = event.as_mut_log();
+
+    if let Some(host) = parsed.hostname {
+        log.insert(log_schema().host_key(), host.to_owned());
+    }
+    let timestamp = parsed.timestamp.map(Into::into).unwrap_or_else(Utc::now);
+    log.insert(log_schema().timestamp_key(), timestamp);
+
+    insert_fields_from_syslog(&mut event, parsed);
+    event
+}
+
+/// Resolves the year of the timestamps missing it, assuming that messages
+/// from December received in January are from the previous year.
+pub(crate) fn resolve_year((month, _date, _hour, _min, _sec): IncompleteDate) -> i32 {
+    let now = Utc::now();
+    if now.month() == 1 && month == 12 {
+        now.year() - 1
+    } else {
+        now.year()
+    }
+}
+
+pub(crate) fn insert_fields_from_syslog(event: &mut Event, parsed: Message<&str>) {
+    let log = event.as_mut_log();
+
+    if let Some(host) = parsed.hostname {
+        log.insert("hostname", host.to_string());
+    }
+    if let Some(severity) = parsed.severity {
+        log.insert("severity", severity.as_str().to_owned());
+    }
+    if let Some(facility) = parsed.facility {
+        log.insert("facility", facility.as_str().to_owned());
+    }
+    if let Protocol::RFC5424(version) = parsed.protocol {
+        log.insert("version", version as i64);
+    }
+    if let Some(app_name) = parsed.appname {
+        log.insert("appname", app_name.to_owned());
+    }
+    if let Some(msg_id) = parsed.msgid {
+        log.insert("msgid", msg_id.to_owned());
+    }
+    if let Some(procid) = parsed.procid {
+        let value: Value = match procid {
+            ProcId::PID(pid) => pid.into(),
+            ProcId::Name(name) => name.to_string().into(),
+        };
+        log.insert("procid", value);
+    }
+
+    for element in parsed.structured_data.into_iter() {
+        for (name, value) in element.params.into_iter() {
+            let key = format!("{}.{}", element.id, name);
+            log.insert(key, value.to_string());
+        }
+    }
+}
+
+/// Splits bytes into frames, and parses the events of each frame.
+///
+/// Frames which fail to parse are skipped. The events of a frame are
+/// returned one at a time, along with the byte size of their frame, which
+/// is only counted on the first of its events.
+#[derive(Debug)]
+pub struct Decoder {
+    framer: Framer,
+    decoding: DecodingConfig,
+    pending: VecDeque<Event>,
+}
+
+impl Decoder {
+    pub fn new(framer: Framer, decoding: DecodingConfig) -> Self {
+        Self {
+            framer,
+            decoding,
+            pending: VecDeque::new(),
+        }
+    }
+
+    /// Builds the decoder of a source, which frames its input with
+    /// `default_framing` unless configured otherwise.
+    pub fn from_config(
+        framing: Option<&FramingConfig>,
+        default_framing: FramingConfig,
+        decoding: Option<DecodingConfig>,
+    ) -> Result<Self, FramingError> {
+        let framer = framing.unwrap_or(&default_framing).build()?;
+        Ok(Self::new(framer, decoding.unwrap_or_default()))
+    }
+
+    /// Decodes the events of a complete input, such as a request body or a
+    /// message.
+    pub fn decode_all(&mut self, input: Bytes) -> Vec<Event> {
+        let mut input = BytesMut::from(&input[..]);
+        let mut events = Vec::new();
+        loop {
+            match self.decode_eof(&mut input) {
+                Ok(Some((event, _))) => events.push(event),
+                Ok(None) => break,
+                Err(error) => {
+                    warn!(message = "Failed framing input.", %error, internal_log_rate_secs = 30);
+                    break;
+                }
+            }
+        }
+        events
+    }
+
+    fn parse(&mut self, frame: Bytes) -> Option<(Event, usize)> {
+        let byte_size = frame.len();
+        match self.decoding.parse(frame) {
+            Ok(events) => {
+                let mut events = events.into_iter();
+                let first = events.next();
+                self.pending.extend(events);
+                first.map(|event| (event, byte_size))
+            }
+            Err(error) => {
+                emit!(DecoderParseFailed { error: &error });
+                None
+            }
+        }
+    }
+}
+
+impl tokio_util::codec::Decoder for Decoder {
+    type Item = (Event, usize);
+    type Error = io::Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, io::Error> {
+        loop {
+            if let Some(event) = self.pending.pop_front() {
+                return Ok(Some((event, 0)));
+            }
+            match self.framer.decode(src)? {
+                Some(frame) => {
+                    if let Some(item) = self.parse(frame) {
+                        return Ok(Some(item));
+                    }
+                }
+                None => return Ok(None),
+            }
+        }
+    }
+
+    fn decode_eof(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, io::Error> {
+        loop {
+            if let Some(event) = self.pending.pop_front() {
+                return Ok(Some((event, 0)));
+            }
+            match self.framer.decode_eof(src)? {
+                Some(frame) => {
+                    if let Some(item) = self.parse(frame) {
+                        return Ok(Some(item));
+                    }
+                }
+                None => return Ok(None),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::Metric;
+
+    fn decode(framing: FramingConfig, decoding: DecodingConfig, input: &[u8]) -> Vec<Event> {
+        Decoder::from_config(Some(&framing), FramingConfig::Bytes, Some(decoding))
+            .unwrap()
+            .decode_all(Bytes::copy_from_slice(input))
+    }
+
+    #[test]
+    fn parses_config() {
+        let config: DecodingConfig = toml::from_str(r#"codec = "native_protobuf""#).unwrap();
+
+        assert_eq!(config, DecodingConfig::NativeProtobuf);
+    }
+
+    #[test]
+    fn decodes_bytes() {
+        let events = decode(
+            FramingConfig::NewlineDelimited { max_length: None },
+            DecodingConfig::Bytes,
+            b"one\ntwo\n",
+        );
+
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].as_log()[log_schema().message_key()], "one".into());
+        assert_eq!(events[1].as_log()[log_schema().message_key()], "two".into());
+    }
+
+    #[test]
+    fn decodes_json() {
+        let events = decode(
+            FramingConfig::NewlineDelimited { max_length: None },
+            DecodingConfig::Json,
+            br#"{"a.b": 1}
+            not json
+            [{"n": 2}, {"n": 3}]"#,
+        );
+
+        assert_eq!(events.len(), 3);
+        let log = events[0].as_log();
+        assert_eq!(log.get_flat("a.b"), Some(&Value::Integer(1)));
+        assert!(log.contains(log_schema().timestamp_key()));
+        assert_eq!(events[1].as_log()["n"], 2.into());
+        assert_eq!(events[2].as_log()["n"], 3.into());
+    }
+
+    #[test]
+    fn decodes_syslog() {
+        let events = decode(
+            FramingConfig::OctetCounting { max_length: None },
+            DecodingConfig::Syslog,
+            b"81 <34>1 2003-10-11T22:14:15.003Z mymachine.example.com su - ID47 - 'su root' failed",
+        );
+
+        assert_eq!(events.len(), 1);
+        let log = events[0].as_log();
+        assert_eq!(log[log_schema().message_key()], "'su root' failed".into());
+        assert_eq!(log[log_schema().host_key()], "mymachine.example.com".into());
+        assert_eq!(log["appname"], "su".into());
+        assert_eq!(log["severity"], "crit".into());
+        assert_eq!(log["facility"], "auth".into());
+    }
+
+    #[test]
+    fn decodes_native_protobuf() {
+        let metric = Event::Metric(Metric::new(
+            "counter",
+            crate::event::MetricKind::Incremental,
+            crate::event::MetricValue::Counter { value: 1.0 },
+        ));
+        let mut input = Vec::new();
+        proto::EventWrapper::from(metric.clone())
+            .encode(&mut input)
+            .unwrap();
+
+        let events = decode(FramingConfig::Bytes, DecodingConfig::NativeProtobuf, &input);
+
+        assert_eq!(events, vec![metric]);
+    }
+
+    #[test]
+    fn yields_events_of_a_frame_one_at_a_time() {
+        let mut decoder = Decoder::new(
+            FramingConfig::NewlineDelimited { max_length: None }
+                .build()
+                .unwrap(),
+            DecodingConfig::Json,
+        );
+        let mut src = BytesMut::from(&b"[{\"n\": 1}, {\"n\": 2}]\n"[..]);
+
+        let (_, byte_size) = decoder.decode(&mut src).unwrap().unwrap();
+        assert_eq!(byte_size, 20);
+        let (event, byte_size) = decoder.decode(&mut src).unwrap().unwrap();
+        assert_eq!(event.as_log()["n"], 2.into());
+        assert_eq!(byte_size, 0);
+        assert!(decoder.decode(&mut src).unwrap().is_none());
+    }
+}
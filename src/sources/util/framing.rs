@@ -0,0 +1,369 @@
+use bytes::{Buf, Bytes, BytesMut};
+use codec::BytesDelimitedCodec;
+use serde::{Deserialize, Serialize};
+use snafu::Snafu;
+use std::{cmp, io};
+use tokio_util::codec::{Decoder, LengthDelimitedCodec};
+
+/// The longest octet count accepted, which bounds how far the count is
+/// searched for.
+const MAX_OCTET_COUNT_DIGITS: usize = 10;
+
+#[derive(Debug, PartialEq, Snafu)]
+pub enum FramingError {
+    #[snafu(display("Delimiter {:?} is not a single byte character", delimiter))]
+    InvalidDelimiter { delimiter: char },
+}
+
+/// How a stream of bytes is split into the frames events are decoded from.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "method", rename_all = "snake_case")]
+pub enum FramingConfig {
+    /// The whole input is a single frame, as for a datagram or a message.
+    Bytes,
+    /// Frames are delimited by a newline.
+    NewlineDelimited { max_length: Option<usize> },
+    /// Frames are delimited by a single byte character.
+    CharacterDelimited {
+        delimiter: char,
+        max_length: Option<usize>,
+    },
+    /// Frames are prefixed by their length, as a big endian 32 bits integer.
+    LengthDelimited { max_length: Option<usize> },
+    /// Frames are prefixed by their length and a space, as per RFC 6587,
+    /// falling back to newline delimited frames otherwise.
+    OctetCounting { max_length: Option<usize> },
+}
+
+impl FramingConfig {
+    pub fn build(&self) -> Result<Framer, FramingError> {
+        Ok(match *self {
+            Self::Bytes => Framer::Bytes,
+            Self::NewlineDelimited { max_length } => {
+                Framer::Delimited(delimited(b'\n', max_length))
+            }
+            Self::CharacterDelimited {
+                delimiter,
+                max_length,
+            } => {
+                if !delimiter.is_ascii() {
+                    return Err(FramingError::InvalidDelimiter { delimiter });
+                }
+                Framer::Delimited(delimited(delimiter as u8, max_length))
+            }
+            Self::LengthDelimited { max_length } => {
+                let mut builder = LengthDelimitedCodec::builder();
+                if let Some(max_length) = max_length {
+                    builder.max_frame_length(max_length);
+                }
+                Framer::LengthDelimited(builder.new_codec())
+            }
+            Self::OctetCounting { max_length } => {
+                Framer::OctetCounting(OctetCountingDecoder::new(max_length))
+            }
+        })
+    }
+
+    /// Limits the size of the frames to `max_length` unless a limit is
+    /// already configured. The `bytes` method isn't limited, as it doesn't
+    /// split its input.
+    pub fn with_default_max_length(self, max_length: usize) -> Self {
+        match self {
+            Self::Bytes => Self::Bytes,
+            Self::NewlineDelimited { max_length: limit } => Self::NewlineDelimited {
+                max_length: limit.or(Some(max_length)),
+            },
+            Self::CharacterDelimited {
+                delimiter,
+                max_length: limit,
+            } => Self::CharacterDelimited {
+                delimiter,
+                max_length: limit.or(Some(max_length)),
+            },
+            Self::LengthDelimited { max_length: limit } => Self::LengthDelimited {
+                max_length: limit.or(Some(max_length)),
+            },
+            Self::OctetCounting { max_length: limit } => Self::OctetCounting {
+                max_length: limit.or(Some(max_length)),
+            },
+        }
+    }
+}
+
+fn delimited(delimiter: u8, max_length: Option<usize>) -> BytesDelimitedCodec {
+    match max_length {
+        Some(max_length) => BytesDelimitedCodec::new_with_max_length(delimiter, max_length),
+        None => BytesDelimitedCodec::new(delimiter),
+    }
+}
+
+#[derive(Debug)]
+pub enum Framer {
+    Bytes,
+    Delimited(BytesDelimitedCodec),
+    LengthDelimited(LengthDelimitedCodec),
+    OctetCounting(OctetCountingDecoder),
+}
+
+impl Decoder for Framer {
+    type Item = Bytes;
+    type Error = io::Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Bytes>, io::Error> {
+        match self {
+            // The input is only complete once it ended.
+            Self::Bytes => Ok(None),
+            Self::Delimited(decoder) => decoder.decode(src),
+            Self::LengthDelimited(decoder) => Ok(decoder.decode(src)?.map(BytesMut::freeze)),
+            Self::OctetCounting(decoder) => decoder.decode(src),
+        }
+    }
+
+    fn decode_eof(&mut self, src: &mut BytesMut) -> Result<Option<Bytes>, io::Error> {
+        match self {
+            Self::Bytes if src.is_empty() => Ok(None),
+            Self::Bytes => Ok(Some(src.split().freeze())),
+            Self::Delimited(decoder) => decoder.decode_eof(src),
+            Self::LengthDelimited(decoder) => Ok(decoder.decode_eof(src)?.map(BytesMut::freeze)),
+            Self::OctetCounting(decoder) => decoder.decode_eof(src),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct OctetCountingDecoder {
+    newline_delimited: BytesDelimitedCodec,
+    max_length: usize,
+    octets_to_discard: usize,
+}
+
+impl OctetCountingDecoder {
+    pub fn new(max_length: Option<usize>) -> Self {
+        let max_length = max_length.unwrap_or(usize::MAX);
+        Self {
+            newline_delimited: BytesDelimitedCodec::new_with_max_length(b'\n', max_length),
+            max_length,
+            octets_to_discard: 0,
+        }
+    }
+}
+
+impl Decoder for OctetCountingDecoder {
+    type Item = Bytes;
+    type Error = io::Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Bytes>, io::Error> {
+        loop {
+            if self.octets_to_discard > 0 {
+                let discarded = cmp::min(self.octets_to_discard, src.len());
+                src.advance(discarded);
+                self.octets_to_discard -= discarded;
+                if self.octets_to_discard > 0 {
+                    return Ok(None);
+                }
+            }
+
+            let digits = src.iter().position(|b| !b.is_ascii_digit());
+            let digits = match digits {
+                // Frames which aren't counted are delimited by newlines.
+                Some(0) => return self.newline_delimited.decode(src),
+                Some(digits) if digits <= MAX_OCTET_COUNT_DIGITS && src[digits] == b' ' => digits,
+                None if src.len() <= MAX_OCTET_COUNT_DIGITS => return Ok(None),
+                _ => return self.newline_delimited.decode(src),
+            };
+
+            let length = std::str::from_utf8(&src[..digits])
+                .ok()
+                .and_then(|digits| digits.parse::<usize>().ok())
+                .expect("Octet count is made of digits.");
+            if length > self.max_length {
+                warn!(
+                    message = "Discarding frame larger than max_length.",
+                    length,
+                    max_length = self.max_length,
+                    internal_log_rate_secs = 30
+                );
+                src.advance(digits + 1);
+                self.octets_to_discard = length;
+                continue;
+            }
+
+            // The buffer grows as the frame is received, instead of up front
+            // to whatever length the peer announced.
+            if src.len() < digits + 1 + length {
+                return Ok(None);
+            }
+            src.advance(digits + 1);
+            return Ok(Some(src.split_to(length).freeze()));
+        }
+    }
+
+    fn decode_eof(&mut self, src: &mut BytesMut) -> Result<Option<Bytes>, io::Error> {
+        if let Some(frame) = self.decode(src)? {
+            return Ok(Some(frame));
+        }
+        match src.first() {
+            None => Ok(None),
+            Some(b) if b.is_ascii_digit() || self.octets_to_discard > 0 => {
+                warn!(
+                    message = "Discarding incomplete frame.",
+                    length = src.len(),
+                    internal_log_rate_secs = 30
+                );
+                src.clear();
+                Ok(None)
+            }
+            Some(_) => self.newline_delimited.decode_eof(src),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frames(config: FramingConfig, input: &[u8]) -> Vec<Bytes> {
+        let mut framer = config.build().unwrap();
+        let mut src = BytesMut::from(input);
+        let mut frames = Vec::new();
+        while let Some(frame) = framer.decode_eof(&mut src).unwrap() {
+            frames.push(frame);
+        }
+        frames
+    }
+
+    #[test]
+    fn parses_config() {
+        let config: FramingConfig = toml::from_str(
+            r#"method = "character_delimited"
+            delimiter = ",""#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config,
+            FramingConfig::CharacterDelimited {
+                delimiter: ',',
+                max_length: None,
+            }
+        );
+    }
+
+    #[test]
+    fn rejects_multibyte_delimiters() {
+        let config = FramingConfig::CharacterDelimited {
+            delimiter: 'é',
+            max_length: None,
+        };
+
+        assert_eq!(
+            config.build().unwrap_err(),
+            FramingError::InvalidDelimiter { delimiter: 'é' }
+        );
+    }
+
+    #[test]
+    fn frames_bytes() {
+        assert_eq!(
+            frames(FramingConfig::Bytes, b"one\ntwo"),
+            vec![Bytes::from("one\ntwo")]
+        );
+    }
+
+    #[test]
+    fn frames_delimited() {
+        let config = FramingConfig::CharacterDelimited {
+            delimiter: ',',
+            max_length: Some(3),
+        };
+
+        assert_eq!(
+            frames(config, b"one,two,toolong"),
+            vec![Bytes::from("one"), Bytes::from("two")]
+        );
+    }
+
+    #[test]
+    fn frames_length_delimited() {
+        assert_eq!(
+            frames(
+                FramingConfig::LengthDelimited { max_length: None },
+                b"\0\0\0\x03one\0\0\0\x03two"
+            ),
+            vec![Bytes::from("one"), Bytes::from("two")]
+        );
+    }
+
+    #[test]
+    fn limits_length_delimited_frames() {
+        let mut framer = FramingConfig::LengthDelimited {
+            max_length: Some(2),
+        }
+        .build()
+        .unwrap();
+        let mut src = BytesMut::from(&b"\0\0\0\x03one"[..]);
+
+        assert!(framer.decode(&mut src).is_err());
+    }
+
+    #[test]
+    fn frames_octet_counting() {
+        let config = FramingConfig::OctetCounting {
+            max_length: Some(10),
+        };
+
+        assert_eq!(
+            frames(
+                config,
+                b"10 <1>one\ntwo20 <1>three is too long3 <1>plain line\n"
+            ),
+            vec![
+                Bytes::from("<1>one\ntwo"),
+                Bytes::from("<1>"),
+                Bytes::from("plain line"),
+            ]
+        );
+    }
+
+    #[test]
+    fn waits_for_complete_octet_counted_frames() {
+        let mut framer = OctetCountingDecoder::new(None);
+        let mut src = BytesMut::from("5 <1>");
+
+        assert_eq!(framer.decode(&mut src).unwrap(), None);
+        src.extend_from_slice(b"ab");
+        assert_eq!(framer.decode(&mut src).unwrap(), Some(Bytes::from("<1>ab")));
+    }
+
+    #[test]
+    fn does_not_reserve_announced_octet_counts() {
+        let mut framer = OctetCountingDecoder::new(None);
+        let mut src = BytesMut::from("1000000000 <1>");
+
+        assert_eq!(framer.decode(&mut src).unwrap(), None);
+        assert!(src.capacity() < 1_000_000);
+    }
+
+    #[test]
+    fn defaults_max_length() {
+        assert_eq!(
+            FramingConfig::OctetCounting { max_length: None }.with_default_max_length(10),
+            FramingConfig::OctetCounting {
+                max_length: Some(10)
+            }
+        );
+        assert_eq!(
+            FramingConfig::NewlineDelimited {
+                max_length: Some(5)
+            }
+            .with_default_max_length(10),
+            FramingConfig::NewlineDelimited {
+                max_length: Some(5)
+            }
+        );
+        assert_eq!(
+            FramingConfig::Bytes.with_default_max_length(10),
+            FramingConfig::Bytes
+        );
+    }
+}
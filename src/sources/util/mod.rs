@@ -1,6 +1,10 @@
 #[cfg(any(feature = "sources-http", feature = "sources-datadog"))]
 mod body_decoding;
+#[cfg(feature = "sources-utils-codecs")]
+mod decoding;
 mod encoding_config;
+#[cfg(feature = "sources-utils-codecs")]
+mod framing;
 #[cfg(feature = "sources-utils-http")]
 mod http;
 pub mod multiline_config;
@@ -13,6 +17,12 @@ mod unix_stream;
 
 #[cfg(any(feature = "sources-http", feature = "sources-datadog"))]
 pub(crate) use self::body_decoding::{decode_body, Encoding};
+#[cfg(feature = "sources-syslog")]
+pub(crate) use self::decoding::{insert_fields_from_syslog, resolve_year};
+#[cfg(feature = "sources-utils-codecs")]
+pub use self::decoding::{Decoder, DecodingConfig, DecodingError};
+#[cfg(feature = "sources-utils-codecs")]
+pub use self::framing::{Framer, FramingConfig, FramingError};
 #[cfg(any(feature = "sources-http", feature = "sources-heroku_logs"))]
 pub(crate) use self::http::add_query_parameters;
 #[cfg(feature = "sources-prometheus")]
@@ -22,8 +32,8 @@ pub(crate) use self::http::{ErrorMessage, HttpSource, HttpSourceAuthConfig};
 pub use encoding_config::EncodingConfig;
 pub use multiline_config::MultilineConfig;
 #[cfg(all(feature = "sources-utils-tls", feature = "listenfd"))]
-pub use tcp::{SocketListenAddr, TcpSource};
+pub use tcp::{IsErrorFatal, SocketListenAddr, TcpSource};
 #[cfg(all(unix, feature = "sources-socket",))]
 pub use unix_datagram::build_unix_datagram_source;
 #[cfg(all(unix, feature = "sources-utils-unix",))]
-pub use unix_stream::build_unix_stream_source;
+pub use unix_stream::{build_unix_stream_source, build_unix_stream_source_with_ack};
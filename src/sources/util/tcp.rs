@@ -6,12 +6,22 @@ use crate::{
     tls::{MaybeTlsIncomingStream, MaybeTlsListener, MaybeTlsSettings},
     Event, Pipeline,
 };
-use bytes::Bytes;
+use bytes::{Buf, Bytes};
 use futures::{future::BoxFuture, stream, FutureExt, Sink, SinkExt, StreamExt, TryFutureExt};
 use listenfd::ListenFd;
 use serde::{de, Deserialize, Deserializer, Serialize};
-use std::{fmt, future::ready, io, mem::drop, net::SocketAddr, task::Poll, time::Duration};
+use std::{
+    fmt,
+    future::ready,
+    io,
+    mem::drop,
+    net::SocketAddr,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
 use tokio::{
+    io::AsyncWrite,
     net::{TcpListener, TcpStream},
     time::delay_for,
 };
@@ -79,6 +89,12 @@ where
 
     fn build_event(&self, frame: <Self::Decoder as Decoder>::Item, host: Bytes) -> Option<Event>;
 
+    /// Builds the response acknowledging the frame to the peer, which is
+    /// written back once the event built from the frame was sent.
+    fn build_ack(&self, _frame: &<Self::Decoder as Decoder>::Item) -> Option<Bytes> {
+        None
+    }
+
     fn run(
         self,
         addr: SocketListenAddr,
@@ -220,6 +236,8 @@ async fn handle_stream<T>(
 
     let mut _token = None;
     let mut shutdown = Some(shutdown);
+    let mut ack: Option<Bytes> = None;
+    let ack_source = source.clone();
     let mut reader = FramedRead::new(socket, source.decoder());
     stream::poll_fn(move |cx| {
         if let Some(fut) = shutdown.as_mut() {
@@ -246,7 +264,27 @@ async fn handle_stream<T>(
             }
         }
 
-        reader.poll_next_unpin(cx)
+        // The frame is only polled again once the event of the previous one
+        // was sent, so its acknowledgement can be written back.
+        if let Some(pending) = ack.as_mut() {
+            match poll_write_ack(reader.get_mut(), pending, cx) {
+                Poll::Ready(result) => {
+                    if let Err(error) = result {
+                        warn!(message = "Failed to send acknowledgement.", %error);
+                    }
+                    ack = None;
+                }
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+
+        match reader.poll_next_unpin(cx) {
+            Poll::Ready(Some(Ok(frame))) => {
+                ack = ack_source.build_ack(&frame);
+                Poll::Ready(Some(Ok(frame)))
+            }
+            poll => poll,
+        }
     })
     .take_until(tripwire)
     .take_while(move |frame| ready(
@@ -273,6 +311,22 @@ async fn handle_stream<T>(
     .await
 }
 
+fn poll_write_ack(
+    socket: &mut MaybeTlsIncomingStream<TcpStream>,
+    ack: &mut Bytes,
+    cx: &mut Context<'_>,
+) -> Poll<io::Result<()>> {
+    while !ack.is_empty() {
+        match Pin::new(&mut *socket).poll_write(cx, &ack[..]) {
+            Poll::Ready(Ok(0)) => return Poll::Ready(Err(io::ErrorKind::WriteZero.into())),
+            Poll::Ready(Ok(written)) => ack.advance(written),
+            Poll::Ready(Err(error)) => return Poll::Ready(Err(error)),
+            Poll::Pending => return Poll::Pending,
+        }
+    }
+    Pin::new(socket).poll_flush(cx)
+}
+
 #[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
 #[serde(untagged)]
 pub enum SocketListenAddr {
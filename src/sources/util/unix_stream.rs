@@ -9,8 +9,11 @@ use crate::{
 };
 use bytes::Bytes;
 use futures::{FutureExt, SinkExt, StreamExt};
-use std::{future::ready, path::PathBuf};
-use tokio::net::{UnixListener, UnixStream};
+use std::path::PathBuf;
+use tokio::{
+    io::AsyncWriteExt,
+    net::{UnixListener, UnixStream},
+};
 use tokio_util::codec::{Decoder, FramedRead};
 use tracing::field;
 use tracing_futures::Instrument;
@@ -25,10 +28,37 @@ pub fn build_unix_stream_source<D>(
     host_key: String,
     shutdown: ShutdownSignal,
     out: Pipeline,
-    build_event: impl Fn(&str, Option<Bytes>, &str) -> Option<Event> + Clone + Send + Sync + 'static,
+    build_event: impl Fn(&str, Option<Bytes>, D::Item) -> Option<Event> + Clone + Send + Sync + 'static,
+) -> Source
+where
+    D: Decoder + Clone + Send + 'static,
+    D::Error: From<std::io::Error> + std::fmt::Debug + std::fmt::Display,
+{
+    build_unix_stream_source_with_ack(
+        listen_path,
+        decoder,
+        host_key,
+        shutdown,
+        out,
+        build_event,
+        |_| None,
+    )
+}
+
+/// Like `build_unix_stream_source`, but `build_ack` can build a response
+/// acknowledging each frame, which is written back to the peer once the
+/// event built from the frame was sent.
+pub fn build_unix_stream_source_with_ack<D>(
+    listen_path: PathBuf,
+    decoder: D,
+    host_key: String,
+    shutdown: ShutdownSignal,
+    out: Pipeline,
+    build_event: impl Fn(&str, Option<Bytes>, D::Item) -> Option<Event> + Clone + Send + Sync + 'static,
+    build_ack: impl Fn(&D::Item) -> Option<Bytes> + Clone + Send + Sync + 'static,
 ) -> Source
 where
-    D: Decoder<Item = String> + Clone + Send + 'static,
+    D: Decoder + Clone + Send + 'static,
     D::Error: From<std::io::Error> + std::fmt::Debug + std::fmt::Display,
 {
     let out = out.sink_map_err(|error| error!(message = "Error sending line.", %error));
@@ -65,32 +95,47 @@ where
             };
 
             let build_event = build_event.clone();
+            let build_ack = build_ack.clone();
             let received_from: Option<Bytes> =
                 path.map(|p| p.to_string_lossy().into_owned().into());
 
             let stream = socket.allow_read_until(shutdown.clone().map(|_| ()));
-            let mut stream = FramedRead::new(stream, decoder.clone()).filter_map(move |line| {
-                ready(match line {
-                    Ok(line) => build_event(&host_key, received_from.clone(), &line).map(Ok),
-                    Err(error) => {
-                        emit!(UnixSocketError {
-                            error,
-                            path: &listen_path
-                        });
-                        None
-                    }
-                })
-            });
+            let mut stream = FramedRead::new(stream, decoder.clone());
 
             let connection_open = connection_open.clone();
             let mut out = out.clone();
             tokio::spawn(
                 async move {
                     let _open_token = connection_open.open(|count| emit!(ConnectionOpen { count }));
-                    let _ = out.send_all(&mut stream).await;
+                    while let Some(frame) = stream.next().await {
+                        let frame = match frame {
+                            Ok(frame) => frame,
+                            Err(error) => {
+                                emit!(UnixSocketError {
+                                    error,
+                                    path: &listen_path
+                                });
+                                continue;
+                            }
+                        };
+
+                        let ack = build_ack(&frame);
+                        if let Some(event) = build_event(&host_key, received_from.clone(), frame) {
+                            if out.send(event).await.is_err() {
+                                break;
+                            }
+                        }
+
+                        if let Some(ack) = ack {
+                            let socket: &mut UnixStream = stream.get_mut().get_mut();
+                            if let Err(error) = socket.write_all(&ack).await {
+                                warn!(message = "Failed to send acknowledgement.", %error);
+                            }
+                        }
+                    }
                     info!("Finished sending.");
 
-                    let socket: &UnixStream = stream.get_ref().get_ref().get_ref();
+                    let socket: &UnixStream = stream.get_ref().get_ref();
                     let _ = socket.shutdown(std::net::Shutdown::Both);
                 }
                 .instrument(span),
@@ -0,0 +1,290 @@
+use super::util::SocketListenAddr;
+use crate::{
+    config::{DataType, GenerateConfig, GlobalOptions, Resource, SourceConfig, SourceDescription},
+    shutdown::ShutdownSignal,
+    Pipeline,
+};
+use serde::{Deserialize, Serialize};
+
+pub mod v1;
+pub mod v2;
+
+/// Version 1 of the protocol streams events over TCP, and is used when no `version` is set.
+/// Version 2 receives batches of events over gRPC, and acks them once they're accepted.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(untagged)]
+pub enum VectorConfig {
+    V1(v1::VectorConfig),
+    V2(v2::VectorConfig),
+}
+
+inventory::submit! {
+    SourceDescription::new::<VectorConfig>("vector")
+}
+
+impl GenerateConfig for VectorConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self::V1(v1::VectorConfig::from_address(
+            SocketListenAddr::SocketAddr("0.0.0.0:9000".parse().unwrap()),
+        )))
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "vector")]
+impl SourceConfig for VectorConfig {
+    async fn build(
+        &self,
+        _name: &str,
+        _globals: &GlobalOptions,
+        shutdown: ShutdownSignal,
+        out: Pipeline,
+    ) -> crate::Result<super::Source> {
+        match self {
+            Self::V1(config) => config.build(shutdown, out),
+            Self::V2(config) => config.build(shutdown, out).await,
+        }
+    }
+
+    fn output_type(&self) -> DataType {
+        DataType::Any
+    }
+
+    fn source_type(&self) -> &'static str {
+        "vector"
+    }
+
+    fn resources(&self) -> Vec<Resource> {
+        match self {
+            Self::V1(config) => vec![config.address.into()],
+            Self::V2(config) => vec![Resource::tcp(config.address)],
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{v1, v2, VectorConfig};
+    use crate::{
+        config::{GlobalOptions, SourceConfig},
+        shutdown::ShutdownSignal,
+        Pipeline,
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<VectorConfig>();
+    }
+
+    #[test]
+    fn parses_versions() {
+        let config: VectorConfig = toml::from_str(r#"address = "0.0.0.0:9000""#).unwrap();
+        assert!(matches!(config, VectorConfig::V1(_)));
+
+        let config: VectorConfig = toml::from_str(
+            r#"version = "2"
+            address = "0.0.0.0:9000""#,
+        )
+        .unwrap();
+        assert!(matches!(config, VectorConfig::V2(_)));
+    }
+
+    #[cfg(feature = "sinks-vector")]
+    mod roundtrip {
+        use super::*;
+        use crate::{
+            config::{SinkConfig, SinkContext},
+            event::{
+                metric::{MetricKind, MetricValue},
+                Metric,
+            },
+            sinks::{util::Compression, vector as sink},
+            test_util::{collect_ready, next_addr, wait_for_tcp},
+            tls::{TlsConfig, TlsOptions},
+            Event,
+        };
+        use futures::stream;
+        use std::net::SocketAddr;
+        use tokio::time::{delay_for, Duration};
+
+        fn sink_tls() -> TlsConfig {
+            TlsConfig {
+                enabled: Some(true),
+                options: TlsOptions {
+                    verify_certificate: Some(false),
+                    ..Default::default()
+                },
+            }
+        }
+
+        async fn stream_test(addr: SocketAddr, source: VectorConfig, sink: sink::VectorSinkConfig) {
+            let (tx, rx) = Pipeline::new_test();
+
+            let server = source
+                .build(
+                    "default",
+                    &GlobalOptions::default(),
+                    ShutdownSignal::noop(),
+                    tx,
+                )
+                .await
+                .unwrap();
+            tokio::spawn(server);
+            wait_for_tcp(addr).await;
+
+            let cx = SinkContext::new_test();
+            let (sink, healthcheck) = sink.build(cx).await.unwrap();
+            healthcheck.await.unwrap();
+
+            let events = vec![
+                Event::from("test"),
+                Event::from("events"),
+                Event::from("to roundtrip"),
+                Event::from("through"),
+                Event::from("the native"),
+                Event::from("sink"),
+                Event::from("and"),
+                Event::from("source"),
+                Event::Metric(Metric::new(
+                    String::from("also test a metric"),
+                    MetricKind::Absolute,
+                    MetricValue::Counter { value: 1.0 },
+                )),
+            ];
+
+            sink.run(stream::iter(events.clone())).await.unwrap();
+
+            delay_for(Duration::from_millis(50)).await;
+
+            let output = collect_ready(rx).await;
+            assert_eq!(events, output);
+        }
+
+        #[tokio::test]
+        async fn v1_works_with_vector_sink() {
+            let addr = next_addr();
+            stream_test(
+                addr,
+                VectorConfig::V1(v1::VectorConfig::from_address(addr.into())),
+                sink::VectorSinkConfig::V1(sink::v1::VectorSinkConfig::from_address(format!(
+                    "localhost:{}",
+                    addr.port()
+                ))),
+            )
+            .await;
+        }
+
+        #[tokio::test]
+        async fn v1_works_with_vector_sink_tls() {
+            let addr = next_addr();
+            stream_test(
+                addr,
+                {
+                    let mut config = v1::VectorConfig::from_address(addr.into());
+                    config.set_tls(Some(TlsConfig::test_config()));
+                    VectorConfig::V1(config)
+                },
+                {
+                    let mut config = sink::v1::VectorSinkConfig::from_address(format!(
+                        "localhost:{}",
+                        addr.port()
+                    ));
+                    config.set_tls(Some(sink_tls()));
+                    sink::VectorSinkConfig::V1(config)
+                },
+            )
+            .await;
+        }
+
+        #[tokio::test]
+        async fn v2_works_with_vector_sink() {
+            let addr = next_addr();
+            stream_test(
+                addr,
+                VectorConfig::V2(v2::VectorConfig::from_address(addr)),
+                sink::VectorSinkConfig::V2(sink::v2::VectorSinkConfig::from_address(format!(
+                    "localhost:{}",
+                    addr.port()
+                ))),
+            )
+            .await;
+        }
+
+        #[tokio::test]
+        async fn v2_works_with_vector_sink_tls() {
+            let addr = next_addr();
+            stream_test(
+                addr,
+                {
+                    let mut config = v2::VectorConfig::from_address(addr);
+                    config.set_tls(Some(TlsConfig::test_config()));
+                    VectorConfig::V2(config)
+                },
+                {
+                    let mut config = sink::v2::VectorSinkConfig::from_address(format!(
+                        "localhost:{}",
+                        addr.port()
+                    ));
+                    config.set_tls(Some(sink_tls()));
+                    sink::VectorSinkConfig::V2(config)
+                },
+            )
+            .await;
+        }
+
+        #[tokio::test]
+        async fn v2_works_with_vector_sink_gzip() {
+            let addr = next_addr();
+            stream_test(
+                addr,
+                VectorConfig::V2(v2::VectorConfig::from_address(addr)),
+                {
+                    let mut config = sink::v2::VectorSinkConfig::from_address(format!(
+                        "localhost:{}",
+                        addr.port()
+                    ));
+                    config.set_compression(Compression::gzip_default());
+                    sink::VectorSinkConfig::V2(config)
+                },
+            )
+            .await;
+        }
+
+        #[tokio::test]
+        async fn v2_sink_retries_until_source_is_up() {
+            let addr = next_addr();
+            let (tx, rx) = Pipeline::new_test();
+
+            let cx = SinkContext::new_test();
+            let (sink, _) = sink::VectorSinkConfig::V2(sink::v2::VectorSinkConfig::from_address(
+                format!("localhost:{}", addr.port()),
+            ))
+            .build(cx)
+            .await
+            .unwrap();
+
+            let events = vec![Event::from("retried"), Event::from("events")];
+            let sent = tokio::spawn(sink.run(stream::iter(events.clone())));
+
+            // The first batches fail, as nothing listens on the address yet.
+            delay_for(Duration::from_millis(500)).await;
+
+            let server = VectorConfig::V2(v2::VectorConfig::from_address(addr))
+                .build(
+                    "default",
+                    &GlobalOptions::default(),
+                    ShutdownSignal::noop(),
+                    tx,
+                )
+                .await
+                .unwrap();
+            tokio::spawn(server);
+
+            sent.await.unwrap().unwrap();
+
+            let output = collect_ready(rx).await;
+            assert_eq!(events, output);
+        }
+    }
+}
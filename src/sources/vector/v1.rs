@@ -1,9 +1,11 @@
-use super::util::{SocketListenAddr, TcpSource};
 use crate::{
-    config::{DataType, GenerateConfig, GlobalOptions, Resource, SourceConfig, SourceDescription},
     event::proto,
     internal_events::{VectorEventReceived, VectorProtoDecodeError},
     shutdown::ShutdownSignal,
+    sources::{
+        util::{SocketListenAddr, TcpSource},
+        Source,
+    },
     tcp::TcpKeepaliveConfig,
     tls::{MaybeTlsSettings, TlsConfig},
     Event, Pipeline,
@@ -14,10 +16,18 @@ use prost::Message;
 use serde::{Deserialize, Serialize};
 use tokio_util::codec::LengthDelimitedCodec;
 
+#[derive(Deserialize, Serialize, Debug, Clone, Copy)]
+enum Version {
+    #[serde(rename = "1")]
+    V1,
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone, Setters)]
 #[serde(deny_unknown_fields)]
 pub struct VectorConfig {
-    address: SocketListenAddr,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    version: Option<Version>,
+    pub(super) address: SocketListenAddr,
     keepalive: Option<TcpKeepaliveConfig>,
     #[serde(default = "default_shutdown_timeout_secs")]
     shutdown_timeout_secs: u64,
@@ -33,6 +43,7 @@ fn default_shutdown_timeout_secs() -> u64 {
 impl VectorConfig {
     pub fn from_address(address: SocketListenAddr) -> Self {
         Self {
+            version: None,
             address,
             keepalive: None,
             shutdown_timeout_secs: default_shutdown_timeout_secs(),
@@ -40,31 +51,8 @@ impl VectorConfig {
             receive_buffer_bytes: None,
         }
     }
-}
 
-inventory::submit! {
-    SourceDescription::new::<VectorConfig>("vector")
-}
-
-impl GenerateConfig for VectorConfig {
-    fn generate_config() -> toml::Value {
-        toml::Value::try_from(Self::from_address(SocketListenAddr::SocketAddr(
-            "0.0.0.0:9000".parse().unwrap(),
-        )))
-        .unwrap()
-    }
-}
-
-#[async_trait::async_trait]
-#[typetag::serde(name = "vector")]
-impl SourceConfig for VectorConfig {
-    async fn build(
-        &self,
-        _name: &str,
-        _globals: &GlobalOptions,
-        shutdown: ShutdownSignal,
-        out: Pipeline,
-    ) -> crate::Result<super::Source> {
+    pub(super) fn build(&self, shutdown: ShutdownSignal, out: Pipeline) -> crate::Result<Source> {
         let vector = VectorSource;
         let tls = MaybeTlsSettings::from_config(&self.tls, true)?;
         vector.run(
@@ -77,18 +65,6 @@ impl SourceConfig for VectorConfig {
             out,
         )
     }
-
-    fn output_type(&self) -> DataType {
-        DataType::Any
-    }
-
-    fn source_type(&self) -> &'static str {
-        "vector"
-    }
-
-    fn resources(&self) -> Vec<Resource> {
-        vec![self.address.into()]
-    }
 }
 
 #[derive(Debug, Clone)]
@@ -117,133 +93,34 @@ impl TcpSource for VectorSource {
     }
 }
 
-#[cfg(feature = "sinks-vector")]
 #[cfg(test)]
 mod test {
-    use super::VectorConfig;
     use crate::shutdown::ShutdownSignal;
     use crate::{
-        config::{GlobalOptions, SinkConfig, SinkContext, SourceConfig},
-        event::{
-            metric::{MetricKind, MetricValue},
-            Metric,
-        },
-        sinks::vector::VectorSinkConfig,
+        config::{GlobalOptions, SourceConfig},
+        sources::vector::{v1, VectorConfig},
         test_util::{collect_ready, next_addr, trace_init, wait_for_tcp},
-        tls::{TlsConfig, TlsOptions},
-        Event, Pipeline,
-    };
-    use futures::stream;
-    use std::{
-        net::{Shutdown, SocketAddr},
-        thread,
-    };
-    use tokio::{
-        io::AsyncWriteExt,
-        net::TcpStream,
-        time::{delay_for, Duration},
+        Pipeline,
     };
+    use std::{net::Shutdown, thread};
+    use tokio::{io::AsyncWriteExt, net::TcpStream, time::Duration};
 
     #[cfg(not(target_os = "windows"))]
     use {
-        crate::event::proto,
+        crate::{event::proto, Event},
         bytes::BytesMut,
         futures::SinkExt,
         prost::Message,
         tokio_util::codec::{FramedWrite, LengthDelimitedCodec},
     };
 
-    #[test]
-    fn generate_config() {
-        crate::test_util::test_generate_config::<VectorConfig>();
-    }
-
-    async fn stream_test(addr: SocketAddr, source: VectorConfig, sink: VectorSinkConfig) {
-        let (tx, rx) = Pipeline::new_test();
-
-        let server = source
-            .build(
-                "default",
-                &GlobalOptions::default(),
-                ShutdownSignal::noop(),
-                tx,
-            )
-            .await
-            .unwrap();
-        tokio::spawn(server);
-        wait_for_tcp(addr).await;
-
-        let cx = SinkContext::new_test();
-        let (sink, _) = sink.build(cx).await.unwrap();
-
-        let events = vec![
-            Event::from("test"),
-            Event::from("events"),
-            Event::from("to roundtrip"),
-            Event::from("through"),
-            Event::from("the native"),
-            Event::from("sink"),
-            Event::from("and"),
-            Event::from("source"),
-            Event::Metric(Metric::new(
-                String::from("also test a metric"),
-                MetricKind::Absolute,
-                MetricValue::Counter { value: 1.0 },
-            )),
-        ];
-
-        sink.run(stream::iter(events.clone())).await.unwrap();
-
-        delay_for(Duration::from_millis(50)).await;
-
-        let output = collect_ready(rx).await;
-        assert_eq!(events, output);
-    }
-
-    #[tokio::test]
-    async fn it_works_with_vector_sink() {
-        let addr = next_addr();
-        stream_test(
-            addr,
-            VectorConfig::from_address(addr.into()),
-            VectorSinkConfig::from_address(format!("localhost:{}", addr.port())),
-        )
-        .await;
-    }
-
-    #[tokio::test]
-    async fn it_works_with_vector_sink_tls() {
-        let addr = next_addr();
-        stream_test(
-            addr,
-            {
-                let mut config = VectorConfig::from_address(addr.into());
-                config.set_tls(Some(TlsConfig::test_config()));
-                config
-            },
-            {
-                let mut config =
-                    VectorSinkConfig::from_address(format!("localhost:{}", addr.port()));
-                config.set_tls(Some(TlsConfig {
-                    enabled: Some(true),
-                    options: TlsOptions {
-                        verify_certificate: Some(false),
-                        ..Default::default()
-                    },
-                }));
-                config
-            },
-        )
-        .await;
-    }
-
     #[tokio::test]
     async fn it_closes_stream_on_garbage_data() {
         trace_init();
         let (tx, rx) = Pipeline::new_test();
         let addr = next_addr();
 
-        let config = VectorConfig::from_address(addr.into());
+        let config = VectorConfig::V1(v1::VectorConfig::from_address(addr.into()));
 
         let (trigger_shutdown, shutdown, shutdown_down) = ShutdownSignal::new_wired();
 
@@ -274,7 +151,7 @@ mod test {
         let (tx, rx) = Pipeline::new_test();
         let addr = next_addr();
 
-        let config = VectorConfig::from_address(addr.into());
+        let config = VectorConfig::V1(v1::VectorConfig::from_address(addr.into()));
 
         let (trigger_shutdown, shutdown, shutdown_down) = ShutdownSignal::new_wired();
 
@@ -0,0 +1,275 @@
+use crate::{
+    event::{proto as event_proto, BatchNotifier, BatchStatus, Event},
+    internal_events::{VectorEventReceived, VectorProtoDecodeError},
+    proto::vector as proto,
+    shutdown::ShutdownSignal,
+    sources::Source,
+    tls::{MaybeTlsSettings, TlsConfig},
+    Pipeline,
+};
+use flate2::read::GzDecoder;
+use futures::{stream, FutureExt, SinkExt, StreamExt};
+use prost::Message;
+use serde::{Deserialize, Serialize};
+use std::{io::Read, net::SocketAddr};
+use tonic::{transport::Server, Request, Response, Status};
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy)]
+enum Version {
+    #[serde(rename = "2")]
+    V2,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct VectorConfig {
+    version: Version,
+    pub(super) address: SocketAddr,
+    tls: Option<TlsConfig>,
+    /// Wait for events to be delivered by the sinks before responding
+    #[serde(default)]
+    acknowledgements: bool,
+}
+
+/// The maximum size of a gzip compressed batch once decompressed, so that a
+/// small request can't exhaust the memory.
+const MAX_DECOMPRESSED_BATCH_SIZE: u64 = 64 * 1024 * 1024;
+
+impl VectorConfig {
+    pub fn from_address(address: SocketAddr) -> Self {
+        Self {
+            version: Version::V2,
+            address,
+            tls: None,
+            acknowledgements: false,
+        }
+    }
+
+    pub fn set_tls(&mut self, tls: Option<TlsConfig>) {
+        self.tls = tls;
+    }
+
+    pub(super) async fn build(
+        &self,
+        shutdown: ShutdownSignal,
+        out: Pipeline,
+    ) -> crate::Result<Source> {
+        let tls = MaybeTlsSettings::from_config(&self.tls, true)?;
+        let listener = tls.bind(&self.address).await?;
+
+        let service = proto::vector_server::VectorServer::new(Service {
+            pipeline: out,
+            acknowledgements: self.acknowledgements,
+        });
+
+        info!(message = "Building gRPC server.", address = %self.address);
+
+        Ok(Box::pin(async move {
+            Server::builder()
+                .add_service(service)
+                .serve_with_incoming_shutdown(
+                    listener.accept_stream(),
+                    shutdown.clone().map(|_| ()),
+                )
+                .await
+                .map_err(|error| error!(message = "gRPC server failed.", %error))?;
+            // We need to drop the last copy of ShutdownSignalToken only after server has shut down.
+            drop(shutdown);
+            Ok(())
+        }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Service {
+    pipeline: Pipeline,
+    acknowledgements: bool,
+}
+
+#[async_trait::async_trait]
+impl proto::vector_server::Vector for Service {
+    /// Only responds once all of the events are sent to the pipeline, or
+    /// delivered by the sinks if acknowledgements are enabled, so the response
+    /// acks the batch to the sink.
+    async fn push_events(
+        &self,
+        request: Request<proto::PushEventsRequest>,
+    ) -> Result<Response<proto::PushEventsResponse>, Status> {
+        let (batch, receiver) = BatchNotifier::maybe_new_with_receiver(self.acknowledgements);
+        let events = decode_batch(request.into_inner())?
+            .into_iter()
+            .map(|event| {
+                emit!(VectorEventReceived {
+                    byte_size: event.encoded_len()
+                });
+                Event::from(event).with_batch_notifier_option(&batch)
+            })
+            .collect::<Vec<_>>();
+        drop(batch);
+
+        self.pipeline
+            .clone()
+            .send_all(&mut stream::iter(events).map(Ok))
+            .await
+            .map_err(|error| {
+                // Can only fail if the receiving end disconnected, so we are shutting down.
+                error!(message = "Failed to forward events, downstream is closed.", %error);
+                Status::unavailable("Vector is shutting down.")
+            })?;
+
+        match receiver {
+            None => Ok(Response::new(proto::PushEventsResponse {})),
+            Some(receiver) => match receiver.await {
+                BatchStatus::Delivered => Ok(Response::new(proto::PushEventsResponse {})),
+                // Errors may be transient, so the sink is told to retry the batch.
+                BatchStatus::Errored => Err(Status::unavailable(
+                    "Failed to deliver the events, retry the batch.",
+                )),
+                BatchStatus::Failed => Err(Status::data_loss("Failed to deliver the events.")),
+            },
+        }
+    }
+
+    async fn health_check(
+        &self,
+        _request: Request<proto::HealthCheckRequest>,
+    ) -> Result<Response<proto::HealthCheckResponse>, Status> {
+        Ok(Response::new(proto::HealthCheckResponse {
+            status: proto::ServingStatus::Serving.into(),
+        }))
+    }
+}
+
+fn decode_batch(
+    request: proto::PushEventsRequest,
+) -> Result<Vec<event_proto::EventWrapper>, Status> {
+    use proto::push_events_request::Batch;
+
+    match request.batch {
+        Some(Batch::Events(batch)) => Ok(batch.events),
+        Some(Batch::GzipEvents(data)) => {
+            let mut decoded = Vec::new();
+            GzDecoder::new(data.as_slice())
+                .take(MAX_DECOMPRESSED_BATCH_SIZE + 1)
+                .read_to_end(&mut decoded)
+                .map_err(|error| {
+                    Status::invalid_argument(format!("Invalid gzip data: {}", error))
+                })?;
+            if decoded.len() as u64 > MAX_DECOMPRESSED_BATCH_SIZE {
+                return Err(Status::invalid_argument(format!(
+                    "Decompressed batch exceeds the maximum size of {} bytes.",
+                    MAX_DECOMPRESSED_BATCH_SIZE
+                )));
+            }
+
+            proto::EventBatch::decode(decoded.as_slice())
+                .map(|batch| batch.events)
+                .map_err(|error| {
+                    let status = Status::invalid_argument(format!("Invalid batch: {}", error));
+                    emit!(VectorProtoDecodeError { error });
+                    status
+                })
+        }
+        None => Ok(Vec::new()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::event::EventStatus;
+    use flate2::write::GzEncoder;
+    use std::io::Write;
+
+    #[test]
+    fn decodes_gzip_batch() {
+        let events = vec![event_proto::EventWrapper::from(Event::from("compressed"))];
+        let batch = proto::EventBatch {
+            events: events.clone(),
+        };
+        let mut encoded = Vec::new();
+        batch.encode(&mut encoded).unwrap();
+
+        let mut encoder = GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(&encoded).unwrap();
+        let request = proto::PushEventsRequest {
+            batch: Some(proto::push_events_request::Batch::GzipEvents(
+                encoder.finish().unwrap(),
+            )),
+        };
+
+        assert_eq!(decode_batch(request).unwrap(), events);
+    }
+
+    #[test]
+    fn rejects_invalid_gzip_batch() {
+        let request = proto::PushEventsRequest {
+            batch: Some(proto::push_events_request::Batch::GzipEvents(
+                b"not gzip".to_vec(),
+            )),
+        };
+
+        assert_eq!(
+            decode_batch(request).unwrap_err().code(),
+            tonic::Code::InvalidArgument
+        );
+    }
+
+    #[test]
+    fn rejects_oversized_gzip_batch() {
+        let mut encoder = GzEncoder::new(Vec::new(), flate2::Compression::best());
+        let zeros = vec![0; 1024 * 1024];
+        for _ in 0..=MAX_DECOMPRESSED_BATCH_SIZE / zeros.len() as u64 {
+            encoder.write_all(&zeros).unwrap();
+        }
+        let request = proto::PushEventsRequest {
+            batch: Some(proto::push_events_request::Batch::GzipEvents(
+                encoder.finish().unwrap(),
+            )),
+        };
+
+        assert_eq!(
+            decode_batch(request).unwrap_err().code(),
+            tonic::Code::InvalidArgument
+        );
+    }
+
+    async fn push_with_status(status: EventStatus) -> Result<(), tonic::Code> {
+        let (pipeline, mut rx) = Pipeline::new_test();
+        let service = Service {
+            pipeline,
+            acknowledgements: true,
+        };
+        let request = proto::PushEventsRequest {
+            batch: Some(proto::push_events_request::Batch::Events(
+                proto::EventBatch {
+                    events: vec![event_proto::EventWrapper::from(Event::from("hello"))],
+                },
+            )),
+        };
+
+        tokio::spawn(async move {
+            let event = rx.next().await.unwrap();
+            event.metadata().finalizers().update_status(status);
+            drop(event);
+        });
+
+        proto::vector_server::Vector::push_events(&service, Request::new(request))
+            .await
+            .map(|_| ())
+            .map_err(|status| status.code())
+    }
+
+    #[tokio::test]
+    async fn responds_with_delivery_status() {
+        assert_eq!(push_with_status(EventStatus::Delivered).await, Ok(()));
+        assert_eq!(
+            push_with_status(EventStatus::Errored).await,
+            Err(tonic::Code::Unavailable)
+        );
+        assert_eq!(
+            push_with_status(EventStatus::Failed).await,
+            Err(tonic::Code::DataLoss)
+        );
+    }
+}
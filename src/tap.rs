@@ -0,0 +1,99 @@
+use crate::config;
+use futures::StreamExt;
+use structopt::StructOpt;
+use url::Url;
+use vector_api_client::{
+    connect_subscription_client,
+    gql::{HealthQueryExt, TapSubscriptionExt},
+    Client,
+};
+
+#[derive(StructOpt, Debug, Clone)]
+#[structopt(rename_all = "kebab-case")]
+pub struct Opts {
+    /// Names of the components to tap, whose output events are printed as JSON
+    #[structopt(required = true)]
+    components: Vec<String>,
+
+    /// Maximum number of events to sample per component, on each interval
+    #[structopt(default_value = "100", short = "l", long)]
+    limit: u32,
+
+    /// Interval to sample events at, in milliseconds
+    #[structopt(default_value = "500", short = "i", long)]
+    interval: u32,
+
+    /// VRL condition that events must satisfy to be sampled, e.g. `.status == 500`
+    #[structopt(short, long)]
+    filter: Option<String>,
+
+    /// Vector GraphQL API server endpoint
+    #[structopt(short, long)]
+    url: Option<Url>,
+}
+
+/// CLI command func for printing the events output by components of a local/remote Vector
+/// instance, as they flow through them
+pub async fn cmd(opts: &Opts) -> exitcode::ExitCode {
+    let url = opts.url.clone().unwrap_or_else(|| {
+        let addr = config::api::default_address().unwrap();
+        Url::parse(&*format!("http://{}/graphql", addr))
+            .expect("Couldn't parse default API URL. Please report this.")
+    });
+
+    // Check that the GraphQL server is reachable
+    if Client::new(url.clone()).health_query().await.is_err() {
+        eprintln!(
+            "Vector API server isn't reachable ({}). Have you enabled the API?",
+            url
+        );
+        return exitcode::UNAVAILABLE;
+    }
+
+    // Change the HTTP schema to WebSockets
+    let mut ws_url = url.clone();
+    ws_url
+        .set_scheme(match url.scheme() {
+            "https" => "wss",
+            _ => "ws",
+        })
+        .expect("Couldn't build WebSocket URL. Please report.");
+
+    let subscription_client = match connect_subscription_client(ws_url).await {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("Couldn't connect to Vector API via WebSockets: {:?}", e);
+            return exitcode::UNAVAILABLE;
+        }
+    };
+
+    let subscription = subscription_client.output_events_subscription(
+        opts.components.clone(),
+        opts.limit as i64,
+        opts.interval as i64,
+        opts.filter.clone(),
+    );
+    let mut events = subscription.stream();
+
+    while let Some(response) = events.next().await {
+        // Responses that don't hold data mean the server ended the subscription.
+        let response = match response {
+            Some(response) => response,
+            None => break,
+        };
+
+        if let Some(errors) = response.errors {
+            for error in errors {
+                eprintln!("{}", error.message);
+            }
+            return exitcode::DATAERR;
+        }
+
+        if let Some(data) = response.data {
+            println!("{}", data.output_events.json);
+        }
+    }
+
+    eprintln!("The tap was closed, the components may have been removed or changed.");
+    exitcode::OK
+}
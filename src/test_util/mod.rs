@@ -1,5 +1,7 @@
 use crate::{
+    buffers::Acker,
     config::{Config, ConfigDiff, GenerateConfig},
+    event::BatchNotifier,
     topology::{self, RunningTopology},
     trace, Event,
 };
@@ -182,6 +184,21 @@ pub fn random_lines_with_stream(
     (lines, stream)
 }
 
+/// Attach `batch` to every event of the stream and track the events with
+/// `acker`, the way the topology does for the events entering a sink.
+pub fn map_batch_stream(
+    stream: impl Stream<Item = Event>,
+    batch: Arc<BatchNotifier>,
+    acker: &Acker,
+) -> impl Stream<Item = Event> {
+    let acker = acker.clone();
+    stream.map(move |event| {
+        let mut event = event.with_batch_notifier(&batch);
+        acker.track(&mut event);
+        event
+    })
+}
+
 fn random_events_with_stream_generic<F>(
     count: usize,
     generator: F,
@@ -266,3 +266,10 @@ impl AsyncWrite for MaybeTlsIncomingStream<TcpStream> {
         self.poll_io(cx, |s, cx| s.poll_write_buf(cx, buf))
     }
 }
+
+#[cfg(any(feature = "sources-opentelemetry", feature = "sources-vector"))]
+impl tonic::transport::server::Connected for MaybeTlsIncomingStream<TcpStream> {
+    fn remote_addr(&self) -> Option<SocketAddr> {
+        Some(self.peer_addr())
+    }
+}
@@ -6,14 +6,16 @@ use super::{
 use crate::{
     buffers,
     config::{DataType, SinkContext},
+    enrichment_tables::TableRegistry,
     event::Event,
     internal_events::{EventIn, EventOut, EventProcessed, EventZeroIn},
     shutdown::SourceShutdownCoordinator,
     stream::VecStreamExt,
-    transforms::Transform,
+    transforms::{Transform, TransformOutputs},
     Pipeline,
 };
 use futures::{future, stream, FutureExt, SinkExt, StreamExt, TryFutureExt};
+use indexmap::IndexMap;
 use std::{
     collections::HashMap,
     future::ready,
@@ -25,6 +27,8 @@ use tokio::time::{timeout, Duration};
 pub struct Pieces {
     pub inputs: HashMap<String, (buffers::BufferInputCloner, Vec<String>)>,
     pub outputs: HashMap<String, fanout::ControlChannel>,
+    /// The full names of each transform's named outputs, which are also present in `outputs`.
+    pub named_outputs: HashMap<String, Vec<String>>,
     pub tasks: HashMap<String, Task>,
     pub source_tasks: HashMap<String, Task>,
     pub healthchecks: HashMap<String, Task>,
@@ -40,6 +44,7 @@ pub async fn build_pieces(
 ) -> Result<Pieces, Vec<String>> {
     let mut inputs = HashMap::new();
     let mut outputs = HashMap::new();
+    let mut named_outputs = HashMap::new();
     let mut tasks = HashMap::new();
     let mut source_tasks = HashMap::new();
     let mut healthchecks = HashMap::new();
@@ -98,6 +103,14 @@ pub async fn build_pieces(
         source_tasks.insert(name.clone(), server);
     }
 
+    // Build enrichment tables
+    let enrichment_tables = TableRegistry::build(&config.enrichment_tables, &config.global)
+        .unwrap_or_else(|mut errs| {
+            errors.append(&mut errs);
+            TableRegistry::default()
+        });
+    enrichment_tables.watch();
+
     // Build transforms
     for (name, transform) in config
         .transforms
@@ -105,11 +118,18 @@ pub async fn build_pieces(
         .filter(|(name, _)| diff.transforms.contains_new(&name))
     {
         let trans_inputs = &transform.inputs;
+        let output_names = transform.inner.named_outputs();
+        let full_output_names = transform.named_outputs(name);
 
         let typetag = transform.inner.transform_type();
 
         let input_type = transform.inner.input_type();
-        let transform = match transform.inner.build(&config.global).await {
+        let inputs_type = config.inputs_type(trans_inputs);
+        let transform = match transform
+            .inner
+            .build_for_input_type(&config.global, &enrichment_tables, inputs_type)
+            .await
+        {
             Err(error) => {
                 errors.push(format!("Transform \"{}\": {}", name, error));
                 continue;
@@ -123,6 +143,15 @@ pub async fn build_pieces(
 
         let (output, control) = Fanout::new();
 
+        let mut named = IndexMap::new();
+        for (output_name, full_name) in output_names.iter().zip(&full_output_names) {
+            let (output, control) = Fanout::new();
+            outputs.insert(full_name.clone(), control);
+            named.insert(output_name.clone(), output);
+        }
+        named_outputs.insert(name.clone(), full_output_names);
+
+        let component = name.clone();
         let transform = match transform {
             Transform::Function(mut t) => input_rx
                 .filter(move |event| ready(filter_event_type(event, input_type)))
@@ -136,6 +165,40 @@ pub async fn build_pieces(
                 })
                 .forward(output)
                 .boxed(),
+            Transform::MultiOutput(mut t) => {
+                let mut input_rx = Box::pin(
+                    input_rx
+                        .filter(move |event| ready(filter_event_type(event, input_type)))
+                        .inspect(|_| emit!(EventIn)),
+                );
+                let mut output = output;
+                async move {
+                    let mut buf = TransformOutputs::new(output_names).with_component(component);
+                    while let Some(event) = input_rx.next().await {
+                        t.transform(&mut buf, event);
+                        emit!(EventOut { count: buf.len() });
+                        emit!(EventProcessed);
+
+                        output
+                            .send_all(&mut stream::iter(buf.primary().drain(..)).map(Ok))
+                            .await?;
+                        for (output_name, events) in buf.named_outputs() {
+                            if let Some(named) = named.get_mut(output_name) {
+                                named
+                                    .send_all(&mut stream::iter(events.drain(..)).map(Ok))
+                                    .await?;
+                            }
+                        }
+                    }
+
+                    output.close().await?;
+                    for (_, mut named) in named {
+                        named.close().await?;
+                    }
+                    Ok::<(), ()>(())
+                }
+                .boxed()
+            }
             Transform::Task(t) => {
                 let filtered = input_rx
                     .filter(move |event| ready(filter_event_type(event, input_type)))
@@ -183,7 +246,11 @@ pub async fn build_pieces(
                     errors.push(format!("Sink \"{}\": {}", name, error));
                     continue;
                 }
-                Ok((tx, rx, acker)) => (tx, Arc::new(Mutex::new(Some(rx.into()))), acker),
+                Ok((tx, rx, acker)) => (
+                    tx,
+                    Arc::new(Mutex::new(Some(rx.into()))),
+                    buffers::Acker::finalizing(acker),
+                ),
             }
         };
 
@@ -203,6 +270,7 @@ pub async fn build_pieces(
 
         let (trigger, tripwire) = Tripwire::new();
 
+        let tracker = acker.clone();
         let sink = async move {
             // Why is this Arc<Mutex<Option<_>>> needed you ask.
             // In case when this function build_pieces errors
@@ -222,6 +290,10 @@ pub async fn build_pieces(
                 rx.by_ref()
                     .filter(|event| ready(filter_event_type(event, input_type)))
                     .inspect(|_| emit!(EventIn))
+                    .map(move |mut event| {
+                        tracker.track(&mut event);
+                        event
+                    })
                     .take_until_if(tripwire),
             )
             .await
@@ -280,6 +352,7 @@ pub async fn build_pieces(
         let pieces = Pieces {
             inputs,
             outputs,
+            named_outputs,
             tasks,
             source_tasks,
             healthchecks,
@@ -0,0 +1,38 @@
+//! Requests to control the running topology from outside of the main loop,
+//! such as from the API.
+
+use super::Tap;
+use tokio::sync::{mpsc, oneshot};
+
+#[derive(Debug)]
+pub enum ControlRequest {
+    /// Stop forwarding the events of a source, applying back pressure to it.
+    PauseSource(String),
+    /// Resume forwarding the events of a paused source.
+    ResumeSource(String),
+    /// Reload the config from disk, same as on SIGHUP.
+    ReloadConfig,
+    /// Observe the events output by a component.
+    TapOutput(String, Tap),
+}
+
+/// `Err` holds a description of why the request failed.
+pub type ControlResult = Result<(), String>;
+
+pub type ControlSender = mpsc::UnboundedSender<(ControlRequest, oneshot::Sender<ControlResult>)>;
+pub type ControlReceiver =
+    mpsc::UnboundedReceiver<(ControlRequest, oneshot::Sender<ControlResult>)>;
+
+pub fn control_channel() -> (ControlSender, ControlReceiver) {
+    mpsc::unbounded_channel()
+}
+
+/// Sends `request` to the main loop, and waits for it to be handled.
+pub async fn send(sender: &ControlSender, request: ControlRequest) -> ControlResult {
+    let (tx, rx) = oneshot::channel();
+    sender
+        .send((request, tx))
+        .map_err(|_| "Vector isn't accepting control requests.".to_owned())?;
+    rx.await
+        .unwrap_or_else(|_| Err("Vector stopped before handling the request.".to_owned()))
+}
@@ -1,4 +1,4 @@
-use crate::Event;
+use crate::{conditions::Condition, Event};
 use futures::{future, Sink, Stream};
 use std::{
     fmt,
@@ -14,6 +14,10 @@ pub enum ControlMessage {
     Remove(String),
     /// Will stop accepting events until Some with given name is replaced.
     Replace(String, Option<RouterSink>),
+    /// Will stop accepting events until resumed.
+    Pause,
+    Resume,
+    Tap(Tap),
 }
 
 impl fmt::Debug for ControlMessage {
@@ -23,15 +27,64 @@ impl fmt::Debug for ControlMessage {
             Self::Add(name, _) => write!(f, "Add({:?})", name),
             Self::Remove(name) => write!(f, "Remove({:?})", name),
             Self::Replace(name, _) => write!(f, "Replace({:?})", name),
+            Self::Pause => write!(f, "Pause"),
+            Self::Resume => write!(f, "Resume"),
+            Self::Tap(_) => write!(f, "Tap"),
         }
     }
 }
 
 pub type ControlChannel = mpsc::UnboundedSender<ControlMessage>;
 
+/// Receives a copy of the events going through a fanout, to observe them.
+/// Events are dropped when the receiver falls behind, so that a tap never
+/// applies back pressure, and the tap is removed once the receiver is gone.
+#[derive(Clone)]
+pub struct Tap {
+    sender: mpsc::Sender<Event>,
+    condition: Option<Box<dyn Condition>>,
+}
+
+impl Tap {
+    /// Only the events for which `condition` is true, if any, are sent.
+    pub fn new(sender: mpsc::Sender<Event>, condition: Option<Box<dyn Condition>>) -> Self {
+        Self { sender, condition }
+    }
+
+    /// Returns `false` once the receiver is gone.
+    fn send(&mut self, event: &Event) -> bool {
+        if let Some(condition) = &self.condition {
+            if !condition.check(event) {
+                return true;
+            }
+        }
+
+        // The copy must not hold on to the event's finalizers, or a slow tap
+        // client would delay acknowledging the event to its source.
+        let mut event = event.clone();
+        drop(event.metadata_mut().take_finalizers());
+
+        !matches!(
+            self.sender.try_send(event),
+            Err(mpsc::error::TrySendError::Closed(_))
+        )
+    }
+}
+
+impl fmt::Debug for Tap {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Tap")
+            .field("sender", &self.sender)
+            .field("condition", &self.condition.is_some())
+            .finish()
+    }
+}
+
 pub struct Fanout {
     sinks: Vec<(String, Option<Pin<RouterSink>>)>,
+    taps: Vec<Tap>,
     i: usize,
+    paused: bool,
     control_channel: mpsc::UnboundedReceiver<ControlMessage>,
 }
 
@@ -41,7 +94,9 @@ impl Fanout {
 
         let fanout = Self {
             sinks: vec![],
+            taps: vec![],
             i: 0,
+            paused: false,
             control_channel: control_rx,
         };
 
@@ -86,6 +141,20 @@ impl Fanout {
                 ControlMessage::Add(name, sink) => self.add(name, sink),
                 ControlMessage::Remove(name) => self.remove(&name),
                 ControlMessage::Replace(name, sink) => self.replace(name, sink),
+                ControlMessage::Pause => self.paused = true,
+                ControlMessage::Resume => self.paused = false,
+                ControlMessage::Tap(tap) => self.taps.push(tap),
+            }
+        }
+    }
+
+    fn send_to_taps(&mut self, event: &Event) {
+        let mut i = 0;
+        while let Some(tap) = self.taps.get_mut(i) {
+            if tap.send(event) {
+                i += 1;
+            } else {
+                self.taps.swap_remove(i);
             }
         }
     }
@@ -138,6 +207,12 @@ impl Sink<Event> for Fanout {
 
         this.process_control_messages(cx);
 
+        // Same as for a sink being replaced, the control channel will notify
+        // the current task once we are resumed.
+        if this.paused {
+            return Poll::Pending;
+        }
+
         while let Some((_, sink)) = this.sinks.get_mut(this.i) {
             match sink.as_mut() {
                 Some(sink) => match sink.as_mut().poll_ready(cx) {
@@ -158,6 +233,8 @@ impl Sink<Event> for Fanout {
     }
 
     fn start_send(mut self: Pin<&mut Self>, item: Event) -> Result<(), ()> {
+        self.send_to_taps(&item);
+
         let mut i = 1;
         while let Some((_, sink)) = self.sinks.get_mut(i) {
             if let Some(sink) = sink.as_mut() {
@@ -191,8 +268,13 @@ impl Sink<Event> for Fanout {
 
 #[cfg(test)]
 mod tests {
-    use super::{ControlMessage, Fanout};
-    use crate::{sink::BoundedSink, test_util::collect_ready, Event};
+    use super::{ControlMessage, Fanout, Tap};
+    use crate::{
+        event::{BatchNotifier, BatchStatus},
+        sink::BoundedSink,
+        test_util::collect_ready,
+        Event,
+    };
     use futures::{stream, Sink, SinkExt, StreamExt};
     use std::{
         pin::Pin,
@@ -307,6 +389,97 @@ mod tests {
         assert_eq!(collect_ready(rx_b).await, &recs[..2]);
     }
 
+    #[tokio::test]
+    async fn fanout_pause() {
+        let (tx_a, mut rx_a) = unbounded_channel();
+        let tx_a = Box::new(tx_a.sink_map_err(|_| unreachable!()));
+
+        let (mut fanout, fanout_control) = Fanout::new();
+
+        fanout.add("a".to_string(), tx_a);
+
+        let recs = make_events(3);
+
+        fanout.send(recs[0].clone()).await.unwrap();
+        assert_eq!(rx_a.try_recv().unwrap(), recs[0]);
+
+        fanout_control.send(ControlMessage::Pause).unwrap();
+
+        let send = stream::iter(recs[1..].to_vec()).map(Ok).forward(fanout);
+        let send = tokio::spawn(send);
+
+        delay_for(Duration::from_millis(50)).await;
+        // The send task is blocked until the fanout is resumed.
+        assert!(rx_a.try_recv().is_err());
+
+        fanout_control.send(ControlMessage::Resume).unwrap();
+        send.await.unwrap().unwrap();
+
+        assert_eq!(collect_ready(rx_a).await, &recs[1..]);
+    }
+
+    #[tokio::test]
+    async fn fanout_tap_strips_finalizers() {
+        let (tx_a, rx_a) = unbounded_channel();
+        let tx_a = Box::new(tx_a.sink_map_err(|_| unreachable!()));
+
+        let (mut fanout, fanout_control) = Fanout::new();
+        fanout.add("a".to_string(), tx_a);
+
+        let (tap_tx, mut tap_rx) = mpsc::channel(1);
+        fanout_control
+            .send(ControlMessage::Tap(Tap::new(tap_tx, None)))
+            .unwrap();
+
+        let (batch, receiver) = BatchNotifier::new_with_receiver();
+        let event = Event::from("hello").with_batch_notifier(&batch);
+        drop(batch);
+        fanout.send(event).await.unwrap();
+
+        let tapped = tap_rx.try_recv().unwrap();
+        assert!(tapped.metadata().finalizers().is_empty());
+
+        // The batch is delivered once the event reaching the sink is dropped,
+        // even though the tapped copy is still held.
+        drop(collect_ready(rx_a).await);
+        assert_eq!(receiver.await, BatchStatus::Delivered);
+        drop(tapped);
+    }
+
+    #[tokio::test]
+    async fn fanout_tap() {
+        let (tx_a, rx_a) = unbounded_channel();
+        let tx_a = Box::new(tx_a.sink_map_err(|_| unreachable!()));
+
+        let (mut fanout, fanout_control) = Fanout::new();
+
+        fanout.add("a".to_string(), tx_a);
+
+        let recs = make_events(4);
+
+        fanout.send(recs[0].clone()).await.unwrap();
+
+        // The tap only has room for a single event, the others are dropped
+        // without holding up the fanout.
+        let (tap_tx, mut tap_rx) = mpsc::channel(1);
+        fanout_control
+            .send(ControlMessage::Tap(Tap::new(tap_tx, None)))
+            .unwrap();
+
+        fanout.send(recs[1].clone()).await.unwrap();
+        fanout.send(recs[2].clone()).await.unwrap();
+
+        assert_eq!(tap_rx.try_recv().unwrap(), recs[1]);
+        assert!(tap_rx.try_recv().is_err());
+
+        // The tap is removed once its receiver is gone.
+        drop(tap_rx);
+        fanout.send(recs[3].clone()).await.unwrap();
+        assert!(fanout.taps.is_empty());
+
+        assert_eq!(collect_ready(rx_a).await, recs);
+    }
+
     #[tokio::test]
     async fn fanout_shrink_after_notready() {
         let (tx_a, rx_a) = channel(2);
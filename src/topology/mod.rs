@@ -7,9 +7,12 @@
 //! each type of component.
 
 pub mod builder;
+pub mod control;
 mod fanout;
 mod task;
 
+pub use fanout::Tap;
+
 use crate::{
     buffers,
     config::{Config, ConfigDiff, HealthcheckOptions, Resource},
@@ -198,6 +201,14 @@ impl RunningTopology {
             Box::pin(success) as future::BoxFuture<'static, ()>,
         ]);
 
+        // Paused sources would never finish shutting down, so let them go.
+        for name in self.config.sources.keys() {
+            if let Some(output) = self.outputs.get(name) {
+                // This can only fail if we are disconnected, which is a valid situation.
+                let _ = output.send(fanout::ControlMessage::Resume);
+            }
+        }
+
         // Now kick off the shutdown process by shutting down the sources.
         let source_shutdown_complete = self.shutdown_coordinator.shutdown_all(deadline);
 
@@ -363,6 +374,11 @@ impl RunningTopology {
             self.remove_inputs(&name);
             self.remove_outputs(&name);
         }
+        // Changed transforms may have renamed or dropped some of their named outputs, so the
+        // old ones are removed here and the new ones set up when connecting the new pieces.
+        for name in &diff.transforms.to_change {
+            self.remove_outputs(&name);
+        }
 
         // Sinks
 
@@ -482,6 +498,9 @@ impl RunningTopology {
         // it as an input
         for name in diff.transforms.changed_and_added() {
             self.setup_outputs(&name, new_pieces);
+            for output in new_pieces.named_outputs.remove(name).unwrap_or_default() {
+                self.setup_outputs(&output, new_pieces);
+            }
         }
 
         for name in &diff.transforms.to_change {
@@ -592,7 +611,14 @@ impl RunningTopology {
     }
 
     fn remove_outputs(&mut self, name: &str) {
-        self.outputs.remove(name);
+        if let Some(output) = self.outputs.remove(name) {
+            // A paused source would otherwise never finish shutting down.
+            // This can only fail if we are disconnected, which is a valid situation.
+            let _ = output.send(fanout::ControlMessage::Resume);
+        }
+        for output in self.config.transform_outputs(name) {
+            self.outputs.remove(&output);
+        }
     }
 
     fn remove_inputs(&mut self, name: &str) {
@@ -724,6 +750,39 @@ impl RunningTopology {
     pub fn config(&self) -> &Config {
         &self.config
     }
+
+    /// Stops forwarding the events of the source `name` until it's resumed,
+    /// which applies back pressure to the source. Returns `false` if there is
+    /// no such source.
+    pub fn pause_source(&self, name: &str) -> bool {
+        self.control_source(name, fanout::ControlMessage::Pause)
+    }
+
+    /// Resumes forwarding the events of the source `name`. Returns `false` if
+    /// there is no such source.
+    pub fn resume_source(&self, name: &str) -> bool {
+        self.control_source(name, fanout::ControlMessage::Resume)
+    }
+
+    /// Sends a copy of the events output by the component `name` to `tap`, until
+    /// its receiver is dropped or the component is changed. Returns `false` if
+    /// there is no such component, or it has no output.
+    pub fn tap_output(&self, name: &str, tap: Tap) -> bool {
+        match self.outputs.get(name) {
+            Some(output) => output.send(fanout::ControlMessage::Tap(tap)).is_ok(),
+            None => false,
+        }
+    }
+
+    fn control_source(&self, name: &str, message: fanout::ControlMessage) -> bool {
+        if !self.config.sources.contains_key(name) {
+            return false;
+        }
+        match self.outputs.get(name) {
+            Some(output) => output.send(message).is_ok(),
+            None => false,
+        }
+    }
 }
 
 async fn handle_errors(
@@ -757,7 +816,10 @@ fn retain<T>(vec: &mut Vec<T>, mut retain_filter: impl FnMut(&mut T) -> bool) {
 mod tests {
     use crate::{
         config::Config,
-        sinks::console::{ConsoleSinkConfig, Encoding, Target},
+        sinks::{
+            console::{ConsoleSinkConfig, Target},
+            util::encoding::StandardEncodings,
+        },
         sources::socket::SocketConfig,
         test_util::{next_addr, start_topology},
     };
@@ -772,7 +834,8 @@ mod tests {
             &[&"in"],
             ConsoleSinkConfig {
                 target: Target::Stdout,
-                encoding: Encoding::Text.into(),
+                encoding: StandardEncodings::Text.into(),
+                framing: None,
             },
         );
         old_config.global.data_dir = Some(Path::new("/asdf").to_path_buf());
@@ -807,8 +870,9 @@ mod tests {
 mod reload_tests {
     use crate::buffers::{BufferConfig, WhenFull};
     use crate::config::Config;
-    use crate::sinks::console::{ConsoleSinkConfig, Encoding, Target};
+    use crate::sinks::console::{ConsoleSinkConfig, Target};
     use crate::sinks::prometheus::exporter::PrometheusExporterConfig;
+    use crate::sinks::util::encoding::StandardEncodings;
     use crate::sources::generator::GeneratorConfig;
     use crate::sources::splunk_hec::SplunkConfig;
     use crate::test_util::{next_addr, start_topology, temp_dir, wait_for_tcp};
@@ -829,7 +893,8 @@ mod reload_tests {
             &[&"in1"],
             ConsoleSinkConfig {
                 target: Target::Stdout,
-                encoding: Encoding::Text.into(),
+                encoding: StandardEncodings::Text.into(),
+                framing: None,
             },
         );
 
@@ -840,7 +905,8 @@ mod reload_tests {
             &[&"in2"],
             ConsoleSinkConfig {
                 target: Target::Stdout,
-                encoding: Encoding::Text.into(),
+                encoding: StandardEncodings::Text.into(),
+                framing: None,
             },
         );
 
@@ -863,7 +929,8 @@ mod reload_tests {
             &[&"in1"],
             ConsoleSinkConfig {
                 target: Target::Stdout,
-                encoding: Encoding::Text.into(),
+                encoding: StandardEncodings::Text.into(),
+                framing: None,
             },
         );
 
@@ -874,7 +941,8 @@ mod reload_tests {
             &[&"in1"],
             ConsoleSinkConfig {
                 target: Target::Stdout,
-                encoding: Encoding::Text.into(),
+                encoding: StandardEncodings::Text.into(),
+                framing: None,
             },
         );
 
@@ -888,6 +956,49 @@ mod reload_tests {
             .unwrap());
     }
 
+    #[cfg(feature = "transforms-route")]
+    #[tokio::test]
+    async fn topology_reload_renamed_output() {
+        use crate::config::{load_from_str, Format};
+
+        let address = next_addr();
+        let config = |lane: &str| {
+            load_from_str(
+                &format!(
+                    r#"
+                    [sources.in]
+                    type = "splunk_hec"
+                    address = "{address}"
+
+                    [transforms.route]
+                    type = "route"
+                    inputs = ["in"]
+                    route.{lane} = '.foo == "bar"'
+
+                    [sinks.out]
+                    type = "console"
+                    inputs = ["route.{lane}"]
+                    encoding = "json"
+                    "#,
+                    address = address,
+                    lane = lane,
+                ),
+                Some(Format::TOML),
+            )
+            .unwrap()
+        };
+
+        let (mut topology, _crash) = start_topology(config("old"), false).await;
+        assert!(topology.outputs.contains_key("route.old"));
+
+        assert!(topology
+            .reload_config_and_respawn(config("new"))
+            .await
+            .unwrap());
+        assert!(!topology.outputs.contains_key("route.old"));
+        assert!(topology.outputs.contains_key("route.new"));
+    }
+
     #[tokio::test]
     async fn topology_old() {
         let address = next_addr();
@@ -899,7 +1010,8 @@ mod reload_tests {
             &[&"in1"],
             ConsoleSinkConfig {
                 target: Target::Stdout,
-                encoding: Encoding::Text.into(),
+                encoding: StandardEncodings::Text.into(),
+                framing: None,
             },
         );
 
@@ -1104,7 +1216,10 @@ mod reload_tests {
 mod source_finished_tests {
     use crate::{
         config::Config,
-        sinks::console::{ConsoleSinkConfig, Encoding, Target},
+        sinks::{
+            console::{ConsoleSinkConfig, Target},
+            util::encoding::StandardEncodings,
+        },
         sources::generator::GeneratorConfig,
         test_util::start_topology,
     };
@@ -1120,7 +1235,8 @@ mod source_finished_tests {
             &[&"in"],
             ConsoleSinkConfig {
                 target: Target::Stdout,
-                encoding: Encoding::Text.into(),
+                encoding: StandardEncodings::Text.into(),
+                framing: None,
             },
         );
 
@@ -0,0 +1,306 @@
+use crate::{
+    config::{DataType, GenerateConfig, GlobalOptions, TransformConfig, TransformDescription},
+    event::Event,
+    internal_events::{AggregateEventRecorded, AggregateFlushed},
+    sinks::util::buffer::metrics::MetricSet,
+    transforms::{TaskTransform, Transform},
+};
+use async_stream::stream;
+use futures::{stream, Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::{mem, pin::Pin, time::Duration};
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct AggregateConfig {
+    /// The interval over which metrics are aggregated, in milliseconds.
+    #[serde(default = "default_interval_ms")]
+    pub interval_ms: u64,
+}
+
+fn default_interval_ms() -> u64 {
+    10 * 1000
+}
+
+inventory::submit! {
+    TransformDescription::new::<AggregateConfig>("aggregate")
+}
+
+impl GenerateConfig for AggregateConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            interval_ms: default_interval_ms(),
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "aggregate")]
+impl TransformConfig for AggregateConfig {
+    async fn build(&self, _globals: &GlobalOptions) -> crate::Result<Transform> {
+        if self.interval_ms == 0 {
+            return Err("`interval_ms` must be greater than zero".into());
+        }
+
+        Ok(Transform::task(Aggregate::new(Duration::from_millis(
+            self.interval_ms,
+        ))))
+    }
+
+    fn input_type(&self) -> DataType {
+        DataType::Metric
+    }
+
+    fn output_type(&self) -> DataType {
+        DataType::Metric
+    }
+
+    fn transform_type(&self) -> &'static str {
+        "aggregate"
+    }
+}
+
+/// Rolls up the metrics received during each interval into a single metric
+/// per series. Incremental metrics are summed, while only the latest value
+/// of absolute metrics is kept.
+pub struct Aggregate {
+    interval: Duration,
+    metrics: MetricSet,
+}
+
+impl Aggregate {
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            metrics: MetricSet::default(),
+        }
+    }
+
+    fn record(&mut self, event: Event) {
+        self.metrics.insert_update(event.into_metric());
+        emit!(AggregateEventRecorded);
+    }
+
+    fn flush_into(&mut self, output: &mut Vec<Event>) {
+        let metrics = mem::take(&mut self.metrics).into_metrics();
+        emit!(AggregateFlushed {
+            count: metrics.len()
+        });
+        output.extend(metrics.into_iter().map(Event::Metric));
+    }
+}
+
+impl TaskTransform for Aggregate {
+    fn transform(
+        self: Box<Self>,
+        mut input_rx: Pin<Box<dyn Stream<Item = Event> + Send>>,
+    ) -> Pin<Box<dyn Stream<Item = Event> + Send>>
+    where
+        Self: 'static,
+    {
+        let mut me = self;
+
+        let start = tokio::time::Instant::now() + me.interval;
+        let mut flush_stream = tokio::time::interval_at(start, me.interval);
+
+        Box::pin(
+            stream! {
+                loop {
+                    let mut output = Vec::new();
+                    let done = tokio::select! {
+                        _ = flush_stream.next() => {
+                            me.flush_into(&mut output);
+                            false
+                        }
+                        maybe_event = input_rx.next() => {
+                            match maybe_event {
+                                None => {
+                                    me.flush_into(&mut output);
+                                    true
+                                }
+                                Some(event) => {
+                                    me.record(event);
+                                    false
+                                }
+                            }
+                        }
+                    };
+                    yield stream::iter(output.into_iter());
+                    if done { break }
+                }
+            }
+            .flatten(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::metric::{Metric, MetricKind, MetricValue, StatisticKind};
+    use futures::channel::mpsc;
+    use std::collections::BTreeSet;
+
+    fn metric(name: &str, kind: MetricKind, value: MetricValue) -> Event {
+        Event::Metric(Metric::new(name, kind, value))
+    }
+
+    fn counter(name: &str, value: f64) -> Event {
+        metric(
+            name,
+            MetricKind::Incremental,
+            MetricValue::Counter { value },
+        )
+    }
+
+    fn gauge(name: &str, value: f64) -> Event {
+        metric(name, MetricKind::Absolute, MetricValue::Gauge { value })
+    }
+
+    fn flush(aggregate: &mut Aggregate) -> Vec<Metric> {
+        let mut output = Vec::new();
+        aggregate.flush_into(&mut output);
+        let mut metrics = output
+            .into_iter()
+            .map(Event::into_metric)
+            .collect::<Vec<_>>();
+        metrics.sort_by(|a, b| a.name().cmp(b.name()));
+        metrics
+    }
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<AggregateConfig>();
+    }
+
+    #[test]
+    fn sums_counters_and_keeps_last_gauge() {
+        let mut aggregate = Aggregate::new(Duration::from_secs(10));
+        aggregate.record(counter("requests", 1.0));
+        aggregate.record(counter("requests", 2.0));
+        aggregate.record(gauge("memory", 10.0));
+        aggregate.record(counter("requests", 3.0));
+        aggregate.record(gauge("memory", 5.0));
+
+        assert_eq!(
+            flush(&mut aggregate),
+            vec![
+                Metric::new(
+                    "memory",
+                    MetricKind::Absolute,
+                    MetricValue::Gauge { value: 5.0 }
+                ),
+                Metric::new(
+                    "requests",
+                    MetricKind::Incremental,
+                    MetricValue::Counter { value: 6.0 }
+                ),
+            ]
+        );
+
+        // Everything was flushed, so the next interval starts out empty.
+        assert!(flush(&mut aggregate).is_empty());
+    }
+
+    #[test]
+    fn merges_sets_and_distributions() {
+        let mut aggregate = Aggregate::new(Duration::from_secs(10));
+        for value in &["a", "b", "a"] {
+            aggregate.record(metric(
+                "users",
+                MetricKind::Incremental,
+                MetricValue::Set {
+                    values: vec![value.to_string()].into_iter().collect(),
+                },
+            ));
+        }
+        for value in &[1.0, 2.0, 1.0] {
+            aggregate.record(metric(
+                "latency",
+                MetricKind::Incremental,
+                MetricValue::Distribution {
+                    samples: crate::samples![*value => 1],
+                    statistic: StatisticKind::Histogram,
+                },
+            ));
+        }
+
+        assert_eq!(
+            flush(&mut aggregate),
+            vec![
+                Metric::new(
+                    "latency",
+                    MetricKind::Incremental,
+                    MetricValue::Distribution {
+                        samples: crate::samples![1.0 => 2, 2.0 => 1],
+                        statistic: StatisticKind::Histogram,
+                    }
+                ),
+                Metric::new(
+                    "users",
+                    MetricKind::Incremental,
+                    MetricValue::Set {
+                        values: vec!["a".to_owned(), "b".to_owned()]
+                            .into_iter()
+                            .collect::<BTreeSet<_>>(),
+                    }
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn keeps_series_with_different_tags_apart() {
+        let mut aggregate = Aggregate::new(Duration::from_secs(10));
+        for host in &["a", "b", "a"] {
+            let mut event = counter("requests", 1.0);
+            event
+                .as_mut_metric()
+                .set_tag_value("host".to_owned(), host.to_string());
+            aggregate.record(event);
+        }
+
+        let mut values = flush(&mut aggregate)
+            .into_iter()
+            .map(|metric| (metric.tag_value("host").unwrap(), metric.data.value))
+            .collect::<Vec<_>>();
+        values.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(
+            values,
+            vec![
+                ("a".to_owned(), MetricValue::Counter { value: 2.0 }),
+                ("b".to_owned(), MetricValue::Counter { value: 1.0 }),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn flushes_on_interval_and_on_shutdown() {
+        tokio::time::pause();
+
+        let (mut tx, rx) = mpsc::channel(10);
+        let aggregate = Box::new(Aggregate::new(Duration::from_secs(10)));
+        let mut out = aggregate.transform(Box::pin(rx));
+
+        tx.try_send(counter("requests", 1.0)).unwrap();
+        tx.try_send(counter("requests", 2.0)).unwrap();
+        assert!(futures::poll!(out.next()).is_pending());
+
+        tokio::time::advance(Duration::from_secs(11)).await;
+        let event = out.next().await.unwrap();
+        assert_eq!(
+            event.as_metric().data.value,
+            MetricValue::Counter { value: 3.0 }
+        );
+
+        tx.try_send(counter("requests", 4.0)).unwrap();
+        drop(tx);
+        let event = out.next().await.unwrap();
+        assert_eq!(
+            event.as_metric().data.value,
+            MetricValue::Counter { value: 4.0 }
+        );
+        assert_eq!(out.next().await, None);
+    }
+}
@@ -2,7 +2,9 @@ use crate::{
     config::{log_schema, DataType, GlobalOptions, TransformConfig, TransformDescription},
     event::{Event, PathComponent, PathIter, Value},
     internal_events::{GrokParserConversionFailed, GrokParserFailedMatch, GrokParserMissingField},
-    transforms::{FunctionTransform, Transform},
+    transforms::{
+        FunctionTransform, MultiOutputTransform, Transform, TransformOutputs, DROPPED_OUTPUT,
+    },
     types::{parse_conversion_map, Conversion},
 };
 use bytes::Bytes;
@@ -29,6 +31,9 @@ pub struct GrokParserConfig {
     pub drop_field: bool,
     pub types: HashMap<String, String>,
     pub timezone: Option<TimeZone>,
+    /// Send events that don't match the pattern to the `dropped` output
+    /// instead of forwarding them unparsed.
+    pub reroute_dropped: bool,
 }
 
 inventory::submit! {
@@ -51,7 +56,7 @@ impl TransformConfig for GrokParserConfig {
         let timezone = self.timezone.unwrap_or(globals.timezone);
         let types = parse_conversion_map(&self.types, timezone)?;
 
-        Ok(grok
+        let parser = grok
             .compile(&self.pattern, true)
             .map(|p| GrokParser {
                 pattern: self.pattern.clone(),
@@ -61,8 +66,13 @@ impl TransformConfig for GrokParserConfig {
                 types,
                 paths: HashMap::new(),
             })
-            .map(Transform::function)
-            .context(InvalidGrok)?)
+            .context(InvalidGrok)?;
+
+        Ok(if self.reroute_dropped {
+            Transform::multi_output(parser)
+        } else {
+            Transform::function(parser)
+        })
     }
 
     fn input_type(&self) -> DataType {
@@ -76,6 +86,14 @@ impl TransformConfig for GrokParserConfig {
     fn transform_type(&self) -> &'static str {
         "grok_parser"
     }
+
+    fn named_outputs(&self) -> Vec<String> {
+        if self.reroute_dropped {
+            vec![DROPPED_OUTPUT.to_owned()]
+        } else {
+            Vec::new()
+        }
+    }
 }
 
 #[derive(Derivative)]
@@ -104,8 +122,10 @@ impl Clone for GrokParser {
     }
 }
 
-impl FunctionTransform for GrokParser {
-    fn transform(&mut self, output: &mut Vec<Event>, event: Event) {
+impl GrokParser {
+    /// Parse the field of `event`. Returns the parsed event, or the unparsed
+    /// event along with the reason it couldn't be parsed.
+    fn parse(&mut self, event: Event) -> Result<Event, (Event, String)> {
         let mut event = event.into_log();
         let value = event.get(&self.field).map(|s| s.to_string_lossy());
 
@@ -131,18 +151,43 @@ impl FunctionTransform for GrokParser {
                 if drop_field {
                     event.remove(&self.field);
                 }
+
+                Ok(Event::Log(event))
             } else {
                 emit!(GrokParserFailedMatch {
                     value: value.as_ref()
                 });
+                Err((
+                    Event::Log(event),
+                    "Value does not match the grok pattern.".to_owned(),
+                ))
             }
         } else {
             emit!(GrokParserMissingField {
                 field: self.field.as_ref()
             });
+            Err((
+                Event::Log(event),
+                format!("Field {:?} does not exist.", self.field),
+            ))
         }
+    }
+}
 
-        output.push(Event::Log(event));
+impl FunctionTransform for GrokParser {
+    fn transform(&mut self, output: &mut Vec<Event>, event: Event) {
+        match self.parse(event) {
+            Ok(event) | Err((event, _)) => output.push(event),
+        }
+    }
+}
+
+impl MultiOutputTransform for GrokParser {
+    fn transform(&mut self, output: &mut TransformOutputs, event: Event) {
+        match self.parse(event) {
+            Ok(event) => output.push(event),
+            Err((event, error)) => output.push_dropped(event, error),
+        }
     }
 }
 
@@ -153,6 +198,7 @@ mod tests {
         config::{log_schema, GlobalOptions, TransformConfig},
         event,
         event::LogEvent,
+        transforms::{TransformOutputs, DROPPED_OUTPUT},
         Event,
     };
     use pretty_assertions::assert_eq;
@@ -177,6 +223,7 @@ mod tests {
             drop_field,
             types: types.iter().map(|&(k, v)| (k.into(), v.into())).collect(),
             timezone: Default::default(),
+            reroute_dropped: false,
         }
         .build(&GlobalOptions::default())
         .await
@@ -281,6 +328,40 @@ mod tests {
             .is_empty());
     }
 
+    #[tokio::test]
+    async fn grok_parser_reroutes_unmatched() {
+        let config = GrokParserConfig {
+            pattern: "%{HTTPD_COMMONLOG}".into(),
+            reroute_dropped: true,
+            ..Default::default()
+        };
+        let mut parser = config.build(&GlobalOptions::default()).await.unwrap();
+        let mut output =
+            TransformOutputs::new(config.named_outputs()).with_component("grok_parser");
+
+        let parser = parser.as_multi_output();
+        parser.transform(
+            &mut output,
+            Event::from(r#"109.184.11.34 - - [12/Dec/2015:18:32:56 +0100] "GET /administrator/ HTTP/1.1" 200 4263"#),
+        );
+        parser.transform(&mut output, Event::from("Help I'm stuck in an HTTP server"));
+
+        assert_eq!(output.primary().len(), 1);
+        assert_eq!(output.primary()[0].as_log()["verb"], "GET".into());
+        let dropped = output.named(DROPPED_OUTPUT).unwrap();
+        assert_eq!(dropped.len(), 1);
+        let dropped = dropped[0].as_log();
+        assert_eq!(
+            dropped[log_schema().message_key()],
+            "Help I'm stuck in an HTTP server".into()
+        );
+        assert_eq!(dropped["dead_letter.component"], "grok_parser".into());
+        assert_eq!(
+            dropped["dead_letter.error"],
+            "Value does not match the grok pattern.".into()
+        );
+    }
+
     #[tokio::test]
     async fn grok_parser_coerces_types() {
         let event = parse_log(
@@ -1,8 +1,10 @@
 use crate::{
     config::{log_schema, DataType, GlobalOptions, TransformConfig, TransformDescription},
-    event::Event,
+    event::{Event, LogEvent},
     internal_events::{JsonParserFailedParse, JsonParserTargetExists},
-    transforms::{FunctionTransform, Transform},
+    transforms::{
+        FunctionTransform, MultiOutputTransform, Transform, TransformOutputs, DROPPED_OUTPUT,
+    },
 };
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -17,6 +19,9 @@ pub struct JsonParserConfig {
     pub drop_field: bool,
     pub target_field: Option<String>,
     pub overwrite_target: Option<bool>,
+    /// Send invalid events to the `dropped` output instead of discarding them.
+    /// Requires `drop_invalid`.
+    pub reroute_dropped: bool,
 }
 
 inventory::submit! {
@@ -29,7 +34,16 @@ impl_generate_config_from_default!(JsonParserConfig);
 #[typetag::serde(name = "json_parser")]
 impl TransformConfig for JsonParserConfig {
     async fn build(&self, _globals: &GlobalOptions) -> crate::Result<Transform> {
-        Ok(Transform::function(JsonParser::from(self.clone())))
+        if self.reroute_dropped && !self.drop_invalid {
+            return Err("`reroute_dropped` requires `drop_invalid` to be enabled".into());
+        }
+
+        let parser = JsonParser::from(self.clone());
+        Ok(if self.reroute_dropped {
+            Transform::multi_output(parser)
+        } else {
+            Transform::function(parser)
+        })
     }
 
     fn input_type(&self) -> DataType {
@@ -43,6 +57,14 @@ impl TransformConfig for JsonParserConfig {
     fn transform_type(&self) -> &'static str {
         "json_parser"
     }
+
+    fn named_outputs(&self) -> Vec<String> {
+        if self.reroute_dropped {
+            vec![DROPPED_OUTPUT.to_owned()]
+        } else {
+            Vec::new()
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -70,63 +92,87 @@ impl From<JsonParserConfig> for JsonParser {
     }
 }
 
-impl FunctionTransform for JsonParser {
-    fn transform(&mut self, output: &mut Vec<Event>, mut event: Event) {
+impl JsonParser {
+    /// Parse the field of `event`. Returns the event to forward, or the event
+    /// to drop along with the reason it is invalid if `drop_invalid` is set.
+    fn parse(&self, mut event: Event) -> Result<Event, (Event, String)> {
         let log = event.as_mut_log();
         let value = log.get(&self.field);
 
         let parsed = value
+            .ok_or_else(|| format!("Field {:?} does not exist.", self.field))
             .and_then(|value| {
                 let to_parse = value.as_bytes();
-                serde_json::from_slice::<Value>(to_parse.as_ref())
-                    .map_err(|error| {
-                        emit!(JsonParserFailedParse {
-                            field: &self.field,
-                            value: value.to_string_lossy().as_str(),
-                            error,
-                            drop_invalid: self.drop_invalid,
-                        })
-                    })
-                    .ok()
+                serde_json::from_slice::<Value>(to_parse.as_ref()).map_err(|error| {
+                    let message = error.to_string();
+                    emit!(JsonParserFailedParse {
+                        field: &self.field,
+                        value: value.to_string_lossy().as_str(),
+                        error,
+                        drop_invalid: self.drop_invalid,
+                    });
+                    message
+                })
             })
             .and_then(|value| {
                 if let Value::Object(object) = value {
-                    Some(object)
+                    Ok(object)
                 } else {
-                    None
+                    Err("Parsed value is not an object.".to_owned())
                 }
             });
 
-        if let Some(object) = parsed {
-            match self.target_field {
-                Some(ref target_field) => {
-                    let contains_target = log.contains(&target_field);
+        match parsed {
+            Ok(object) => self.insert(log, object),
+            Err(error) if self.drop_invalid => return Err((event, error)),
+            Err(_) => {}
+        }
+
+        Ok(event)
+    }
 
-                    if contains_target && !self.overwrite_target {
-                        emit!(JsonParserTargetExists { target_field })
-                    } else {
-                        if self.drop_field {
-                            log.remove(&self.field);
-                        }
+    fn insert(&self, log: &mut LogEvent, object: serde_json::Map<String, Value>) {
+        match self.target_field {
+            Some(ref target_field) => {
+                let contains_target = log.contains(&target_field);
 
-                        log.insert(&target_field, Value::Object(object));
-                    }
-                }
-                None => {
+                if contains_target && !self.overwrite_target {
+                    emit!(JsonParserTargetExists { target_field })
+                } else {
                     if self.drop_field {
                         log.remove(&self.field);
                     }
 
-                    for (key, value) in object {
-                        log.insert_flat(key, value);
-                    }
+                    log.insert(&target_field, Value::Object(object));
+                }
+            }
+            None => {
+                if self.drop_field {
+                    log.remove(&self.field);
+                }
+
+                for (key, value) in object {
+                    log.insert_flat(key, value);
                 }
             }
-        } else if self.drop_invalid {
-            return;
         }
+    }
+}
+
+impl FunctionTransform for JsonParser {
+    fn transform(&mut self, output: &mut Vec<Event>, event: Event) {
+        if let Ok(event) = self.parse(event) {
+            output.push(event);
+        }
+    }
+}
 
-        output.push(event);
+impl MultiOutputTransform for JsonParser {
+    fn transform(&mut self, output: &mut TransformOutputs, event: Event) {
+        match self.parse(event) {
+            Ok(event) => output.push(event),
+            Err((event, error)) => output.push_dropped(event, error),
+        }
     }
 }
 
@@ -515,4 +561,37 @@ mod test {
         assert_eq!(event["message.greeting"], "hello".into());
         assert_eq!(event["message.name"], "bob".into());
     }
+
+    #[tokio::test]
+    async fn json_parser_reroutes_invalid() {
+        let config = JsonParserConfig {
+            drop_invalid: true,
+            reroute_dropped: true,
+            ..Default::default()
+        };
+        let mut parser = config.build(&GlobalOptions::default()).await.unwrap();
+        let mut output =
+            TransformOutputs::new(config.named_outputs()).with_component("json_parser");
+
+        let parser = parser.as_multi_output();
+        parser.transform(&mut output, Event::from(r#"{"greeting": "hello"}"#));
+        parser.transform(&mut output, Event::from("not json"));
+
+        assert_eq!(output.primary().len(), 1);
+        let dropped = output.named(DROPPED_OUTPUT).unwrap();
+        assert_eq!(dropped.len(), 1);
+        let dropped = dropped[0].as_log();
+        assert_eq!(dropped[log_schema().message_key()], "not json".into());
+        assert_eq!(dropped["dead_letter.component"], "json_parser".into());
+        assert!(dropped.contains("dead_letter.error"));
+    }
+
+    #[tokio::test]
+    async fn json_parser_reroute_requires_drop_invalid() {
+        let config = JsonParserConfig {
+            reroute_dropped: true,
+            ..Default::default()
+        };
+        assert!(config.build(&GlobalOptions::default()).await.is_err());
+    }
 }
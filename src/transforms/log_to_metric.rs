@@ -348,7 +348,10 @@ impl FunctionTransform for LogToMetric {
     fn transform(&mut self, output: &mut Vec<Event>, event: Event) {
         for config in self.config.metrics.iter() {
             match to_metric(&config, &event) {
-                Ok(metric) => {
+                Ok(mut metric) => {
+                    // The metric stands in for the log, so the log is only
+                    // finalized once all of its metrics are.
+                    metric.metadata_mut().merge(event.metadata().clone());
                     output.push(Event::Metric(metric));
                 }
                 Err(TransformError::FieldNotFound { field }) => emit!(LogToMetricFieldNotFound {
@@ -381,7 +384,7 @@ mod tests {
     use crate::{
         config::log_schema,
         event::metric::{Metric, MetricKind, MetricValue, StatisticKind},
-        event::Event,
+        event::{BatchNotifier, BatchStatus, Event, EventStatus},
     };
     use chrono::{offset::TimeZone, DateTime, Utc};
 
@@ -649,6 +652,46 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn multiple_metrics_keep_finalizers() {
+        let config = parse_config(
+            r#"
+            [[metrics]]
+            type = "counter"
+            field = "status"
+
+            [[metrics]]
+            type = "counter"
+            field = "backtrace"
+            "#,
+        );
+
+        let (batch, receiver) = BatchNotifier::new_with_receiver();
+        let mut event = Event::from("i am a log").with_batch_notifier(&batch);
+        drop(batch);
+        event.as_mut_log().insert("status", "42");
+        event.as_mut_log().insert("backtrace", "message");
+
+        let mut transform = LogToMetric::new(config);
+
+        let mut output = Vec::new();
+        transform.transform(&mut output, event);
+        assert_eq!(2, output.len());
+
+        let first = output.pop().unwrap();
+        first
+            .metadata()
+            .finalizers()
+            .update_status(EventStatus::Delivered);
+        let second = output.pop().unwrap();
+        second
+            .metadata()
+            .finalizers()
+            .update_status(EventStatus::Errored);
+        drop((first, second));
+        assert_eq!(receiver.await, BatchStatus::Errored);
+    }
+
     #[test]
     fn multiple_metrics_with_multiple_templates() {
         let config = parse_config(
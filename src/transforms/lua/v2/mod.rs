@@ -244,10 +244,18 @@ where
 }
 
 impl RuntimeTransform for Lua {
-    fn hook_process<F>(&mut self, event: Event, emit_fn: F)
+    fn hook_process<F>(&mut self, mut event: Event, mut emit_fn: F)
     where
         F: FnMut(Event),
     {
+        // Events lose their metadata on the way through Lua, so every event
+        // emitted for this one takes over its finalizers.
+        let metadata = std::mem::take(event.metadata_mut());
+        let emit_fn = move |mut event: Event| {
+            event.metadata_mut().merge(metadata.clone());
+            emit_fn(event)
+        };
+
         let _ = self
             .lua
             .context(|ctx: rlua::Context<'_>| {
@@ -345,7 +353,7 @@ mod tests {
     use crate::{
         event::{
             metric::{Metric, MetricKind, MetricValue},
-            Event, Value,
+            BatchNotifier, BatchStatus, Event, EventStatus, Value,
         },
         test_util::trace_init,
         transforms::TaskTransform,
@@ -479,6 +487,40 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn lua_keeps_finalizers() -> crate::Result<()> {
+        trace_init();
+
+        let transform = from_config(
+            r#"
+            hooks.process = """function (event, emit)
+                emit(event)
+                emit(event)
+            end
+            """
+            "#,
+        )
+        .unwrap();
+
+        let (batch, receiver) = BatchNotifier::new_with_receiver();
+        let event = Event::from("program me").with_batch_notifier(&batch);
+        drop(batch);
+        let input = Box::pin(stream::iter(vec![event]));
+        let output = transform.transform(input);
+        let mut out = output.collect::<Vec<_>>().await;
+
+        assert_eq!(out.len(), 2);
+        let first = out.remove(0);
+        assert_eq!(first.metadata().finalizers().len(), 1);
+        first
+            .metadata()
+            .finalizers()
+            .update_status(EventStatus::Failed);
+        drop((first, out));
+        assert_eq!(receiver.await, BatchStatus::Failed);
+        Ok(())
+    }
+
     #[tokio::test]
     async fn lua_read_empty_field() -> crate::Result<()> {
         trace_init();
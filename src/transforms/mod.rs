@@ -1,5 +1,6 @@
 use crate::Event;
 use futures::Stream;
+use indexmap::IndexMap;
 use snafu::Snafu;
 use std::pin::Pin;
 
@@ -9,6 +10,8 @@ pub mod util;
 pub mod add_fields;
 #[cfg(feature = "transforms-add_tags")]
 pub mod add_tags;
+#[cfg(feature = "transforms-aggregate")]
+pub mod aggregate;
 #[cfg(feature = "transforms-ansi_stripper")]
 pub mod ansi_stripper;
 #[cfg(feature = "transforms-aws_cloudwatch_logs_subscription_parser")]
@@ -63,16 +66,21 @@ pub mod sample;
 pub mod split;
 #[cfg(feature = "transforms-tag_cardinality_limit")]
 pub mod tag_cardinality_limit;
+#[cfg(feature = "transforms-throttle")]
+pub mod throttle;
 #[cfg(feature = "transforms-tokenizer")]
 pub mod tokenizer;
 #[cfg(feature = "wasm")]
 pub mod wasm;
 
-/// Transforms come in two variants. Functions, or tasks.
+/// Transforms come in three variants. Functions, multi-output functions, or tasks.
 ///
 /// While function transforms can be run out of order, or concurrently, task transforms act as a coordination or barrier point.
+/// Multi-output functions behave like functions, but can also send events to the named outputs
+/// declared by their config.
 pub enum Transform {
     Function(Box<dyn FunctionTransform>),
+    MultiOutput(Box<dyn MultiOutputTransform>),
     Task(Box<dyn TaskTransform>),
 }
 
@@ -89,11 +97,11 @@ impl Transform {
     ///
     /// # Panics
     ///
-    /// If the transform is a [`TaskTransform`] this will panic.
+    /// If the transform is not a [`FunctionTransform`] this will panic.
     pub fn as_function(&mut self) -> &mut Box<dyn FunctionTransform> {
         match self {
             Transform::Function(t) => t,
-            Transform::Task(_) => panic!(
+            _ => panic!(
                 "Called `Transform::as_function` on something that was not a function variant."
             ),
         }
@@ -102,15 +110,34 @@ impl Transform {
     ///
     /// # Panics
     ///
-    /// If the transform is a [`TaskTransform`] this will panic.
+    /// If the transform is not a [`FunctionTransform`] this will panic.
     pub fn into_function(self) -> Box<dyn FunctionTransform> {
         match self {
             Transform::Function(t) => t,
-            Transform::Task(_) => panic!(
+            _ => panic!(
                 "Called `Transform::into_function` on something that was not a function variant."
             ),
         }
     }
+    /// Create a new multi-output function transform.
+    ///
+    /// These behave like [`FunctionTransform`]s, but can also write events to named outputs.
+    pub fn multi_output(v: impl MultiOutputTransform + 'static) -> Self {
+        Transform::MultiOutput(Box::new(v))
+    }
+    /// Mutably borrow the inner transform as a multi-output transform.
+    ///
+    /// # Panics
+    ///
+    /// If the transform is not a [`MultiOutputTransform`] this will panic.
+    pub fn as_multi_output(&mut self) -> &mut Box<dyn MultiOutputTransform> {
+        match self {
+            Transform::MultiOutput(t) => t,
+            _ => panic!(
+                "Called `Transform::as_multi_output` on something that was not a multi-output variant."
+            ),
+        }
+    }
     /// Create a new task transform.
     ///
     /// These tasks are coordinated, and map a stream of some `U` to some other `T`.
@@ -123,26 +150,22 @@ impl Transform {
     ///
     /// # Panics
     ///
-    /// If the transform is a [`FunctionTransform`] this will panic.
+    /// If the transform is not a [`TaskTransform`] this will panic.
     pub fn as_task(&mut self) -> &mut Box<dyn TaskTransform> {
         match self {
-            Transform::Function(_) => {
-                panic!("Called `Transform::as_task` on something that was not a task variant.")
-            }
             Transform::Task(t) => t,
+            _ => panic!("Called `Transform::as_task` on something that was not a task variant."),
         }
     }
     /// Transmute the inner transform into a task transform.
     ///
     /// # Panics
     ///
-    /// If the transform is a [`FunctionTransform`] this will panic.
+    /// If the transform is not a [`TaskTransform`] this will panic.
     pub fn into_task(self) -> Box<dyn TaskTransform> {
         match self {
-            Transform::Function(_) => {
-                panic!("Called `Transform::into_task` on something that was not a task variant.")
-            }
             Transform::Task(t) => t,
+            _ => panic!("Called `Transform::into_task` on something that was not a task variant."),
         }
     }
 }
@@ -174,6 +197,115 @@ pub trait FunctionTransform: Send + dyn_clone::DynClone + Sync {
 
 dyn_clone::clone_trait_object!(FunctionTransform);
 
+/// Function transforms that can write events to named outputs, in addition to their default
+/// output.
+///
+/// The named outputs are declared by [`TransformConfig::named_outputs`](crate::config::TransformConfig::named_outputs),
+/// and are addressable by other components as `<transform name>.<output name>`.
+pub trait MultiOutputTransform: Send + dyn_clone::DynClone + Sync {
+    fn transform(&mut self, output: &mut TransformOutputs, event: Event);
+}
+
+dyn_clone::clone_trait_object!(MultiOutputTransform);
+
+/// The name of the output transforms send the events they failed to process to, when asked to
+/// keep them rather than drop them.
+pub const DROPPED_OUTPUT: &str = "dropped";
+
+/// Events written by a [`MultiOutputTransform`], grouped by the output they were written to.
+#[derive(Debug, Default)]
+pub struct TransformOutputs {
+    primary: Vec<Event>,
+    named: IndexMap<String, Vec<Event>>,
+    component: Option<String>,
+}
+
+impl TransformOutputs {
+    pub fn new(names: impl IntoIterator<Item = String>) -> Self {
+        Self {
+            primary: Vec::with_capacity(1),
+            named: names.into_iter().map(|name| (name, Vec::new())).collect(),
+            component: None,
+        }
+    }
+
+    /// Set the name of the component writing to these outputs, which is recorded on the events
+    /// written to the [`DROPPED_OUTPUT`].
+    pub fn with_component(mut self, component: impl Into<String>) -> Self {
+        self.component = Some(component.into());
+        self
+    }
+
+    /// Write an event to the default output.
+    pub fn push(&mut self, event: Event) {
+        self.primary.push(event);
+    }
+
+    /// Write an event to the named output `name`.
+    ///
+    /// # Panics
+    ///
+    /// If `name` is not one of the outputs this buffer was created with.
+    pub fn push_named(&mut self, name: &str, event: Event) {
+        self.named
+            .get_mut(name)
+            .unwrap_or_else(|| panic!("Unknown transform output {:?}.", name))
+            .push(event);
+    }
+
+    /// Write an event the transform failed to process to the [`DROPPED_OUTPUT`], annotated with
+    /// the error and the name of the component so it can be reprocessed later.
+    ///
+    /// Log events get a `dead_letter` object with `error` and `component` fields, while metrics
+    /// get `dead_letter_error` and `dead_letter_component` tags.
+    ///
+    /// # Panics
+    ///
+    /// If the [`DROPPED_OUTPUT`] is not one of the outputs this buffer was created with.
+    pub fn push_dropped(&mut self, mut event: Event, error: impl Into<String>) {
+        let error = error.into();
+        match &mut event {
+            Event::Log(log) => {
+                log.insert("dead_letter.error", error);
+                if let Some(component) = &self.component {
+                    log.insert("dead_letter.component", component.clone());
+                }
+            }
+            Event::Metric(metric) => {
+                metric.set_tag_value("dead_letter_error".to_owned(), error);
+                if let Some(component) = &self.component {
+                    metric.set_tag_value("dead_letter_component".to_owned(), component.clone());
+                }
+            }
+        }
+        self.push_named(DROPPED_OUTPUT, event);
+    }
+
+    /// The events written to the default output.
+    pub fn primary(&mut self) -> &mut Vec<Event> {
+        &mut self.primary
+    }
+
+    /// The events written to the named output `name`, if there is such an output.
+    pub fn named(&mut self, name: &str) -> Option<&mut Vec<Event>> {
+        self.named.get_mut(name)
+    }
+
+    /// The events written to each named output.
+    pub fn named_outputs(&mut self) -> impl Iterator<Item = (&String, &mut Vec<Event>)> {
+        self.named.iter_mut()
+    }
+
+    /// The total number of events written across all outputs.
+    pub fn len(&self) -> usize {
+        self.primary.len() + self.named.values().map(Vec::len).sum::<usize>()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
 /// Transforms that tend to be more complicated runtime style components.
 ///
 /// These require coordination and map a stream of some `T` to some `U`.
@@ -2,7 +2,7 @@ use crate::{
     conditions::{AnyCondition, Condition},
     config::{DataType, GlobalOptions, TransformConfig, TransformDescription},
     event::discriminant::Discriminant,
-    event::{Event, LogEvent},
+    event::{Event, EventMetadata, LogEvent},
     internal_events::ReduceStaleEventFlushed,
     transforms::{TaskTransform, Transform},
 };
@@ -12,6 +12,7 @@ use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 use std::{
     collections::{hash_map, HashMap},
+    mem,
     pin::Pin,
     time::{Duration, Instant},
 };
@@ -73,12 +74,14 @@ impl TransformConfig for ReduceConfig {
 struct ReduceState {
     fields: HashMap<String, Box<dyn ReduceValueMerger>>,
     stale_since: Instant,
+    metadata: EventMetadata,
 }
 
 impl ReduceState {
-    fn new(e: LogEvent, strategies: &IndexMap<String, MergeStrategy>) -> Self {
+    fn new(mut e: LogEvent, strategies: &IndexMap<String, MergeStrategy>) -> Self {
         Self {
             stale_since: Instant::now(),
+            metadata: mem::take(e.metadata_mut()),
             fields: e
                 .into_iter()
                 .filter_map(|(k, v)| {
@@ -98,7 +101,8 @@ impl ReduceState {
         }
     }
 
-    fn add_event(&mut self, e: LogEvent, strategies: &IndexMap<String, MergeStrategy>) {
+    fn add_event(&mut self, mut e: LogEvent, strategies: &IndexMap<String, MergeStrategy>) {
+        self.metadata.merge(mem::take(e.metadata_mut()));
         for (k, v) in e.into_iter() {
             let strategy = strategies.get(&k);
             match self.fields.entry(k) {
@@ -133,6 +137,9 @@ impl ReduceState {
                 warn!(message = "Failed to merge values for field.", %error);
             }
         }
+        // The reduced event is only delivered once all of the events merged
+        // into it are, so it carries all of their finalizers.
+        *event.metadata_mut() = self.metadata;
         event
     }
 }
@@ -289,7 +296,11 @@ impl TaskTransform for Reduce {
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::{config::TransformConfig, event::Value, Event};
+    use crate::{
+        config::TransformConfig,
+        event::{BatchNotifier, BatchStatus, EventStatus, Value},
+        Event,
+    };
     use serde_json::json;
 
     #[test]
@@ -351,6 +362,49 @@ group_by = [ "request_id" ]
         assert_eq!(output_2.as_log()["counter"], Value::from(7));
     }
 
+    #[tokio::test]
+    async fn reduce_keeps_finalizers() {
+        let reduce = toml::from_str::<ReduceConfig>(
+            r#"
+group_by = [ "request_id" ]
+
+[ends_when]
+  type = "check_fields"
+  "test_end.exists" = true
+"#,
+        )
+        .unwrap()
+        .build(&GlobalOptions::default())
+        .await
+        .unwrap();
+        let reduce = reduce.into_task();
+
+        let (batch_1, receiver_1) = BatchNotifier::new_with_receiver();
+        let (batch_2, receiver_2) = BatchNotifier::new_with_receiver();
+
+        let mut e_1 = Event::from("test message 1").with_batch_notifier(&batch_1);
+        e_1.as_mut_log().insert("request_id", "1");
+
+        let mut e_2 = Event::from("test message 2").with_batch_notifier(&batch_2);
+        e_2.as_mut_log().insert("request_id", "1");
+        e_2.as_mut_log().insert("test_end", "yep");
+        drop((batch_1, batch_2));
+
+        let in_stream = Box::pin(stream::iter(vec![e_1, e_2]));
+        let mut out_stream = reduce.transform(in_stream);
+
+        let output = out_stream.next().await.unwrap();
+        assert_eq!(output.metadata().finalizers().len(), 2);
+        output
+            .metadata()
+            .finalizers()
+            .update_status(EventStatus::Failed);
+        drop(output);
+
+        assert_eq!(receiver_1.await, BatchStatus::Failed);
+        assert_eq!(receiver_2.await, BatchStatus::Failed);
+    }
+
     #[tokio::test]
     async fn reduce_merge_strategies() {
         let reduce = toml::from_str::<ReduceConfig>(
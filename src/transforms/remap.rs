@@ -1,13 +1,16 @@
 use crate::{
     config::{DataType, GlobalOptions, TransformConfig, TransformDescription},
-    event::Event,
+    enrichment_tables::TableRegistry,
+    event::{Event, Metric},
     internal_events::RemapMappingError,
-    transforms::{FunctionTransform, Transform},
+    transforms::{
+        FunctionTransform, MultiOutputTransform, Transform, TransformOutputs, DROPPED_OUTPUT,
+    },
     Result,
 };
 use serde::{Deserialize, Serialize};
 use vrl::diagnostic::Formatter;
-use vrl::{Program, Runtime};
+use vrl::{state, Program, Runtime};
 
 #[derive(Deserialize, Serialize, Debug, Clone, Derivative)]
 #[serde(deny_unknown_fields, default)]
@@ -15,6 +18,9 @@ use vrl::{Program, Runtime};
 pub struct RemapConfig {
     pub source: String,
     pub drop_on_error: bool,
+    /// Send events the program failed on to the `dropped` output instead of
+    /// discarding them. Requires `drop_on_error`.
+    pub reroute_dropped: bool,
 }
 
 inventory::submit! {
@@ -26,8 +32,36 @@ impl_generate_config_from_default!(RemapConfig);
 #[async_trait::async_trait]
 #[typetag::serde(name = "remap")]
 impl TransformConfig for RemapConfig {
-    async fn build(&self, _globals: &GlobalOptions) -> Result<Transform> {
-        Remap::new(self.clone()).map(Transform::function)
+    async fn build(&self, globals: &GlobalOptions) -> Result<Transform> {
+        self.build_with_enrichment_tables(globals, &TableRegistry::default())
+            .await
+    }
+
+    async fn build_with_enrichment_tables(
+        &self,
+        globals: &GlobalOptions,
+        enrichment_tables: &TableRegistry,
+    ) -> Result<Transform> {
+        self.build_for_input_type(globals, enrichment_tables, DataType::Any)
+            .await
+    }
+
+    async fn build_for_input_type(
+        &self,
+        _globals: &GlobalOptions,
+        enrichment_tables: &TableRegistry,
+        input_type: DataType,
+    ) -> Result<Transform> {
+        if self.reroute_dropped && !self.drop_on_error {
+            return Err("`reroute_dropped` requires `drop_on_error` to be enabled".into());
+        }
+
+        let remap = Remap::new_for_input_type(self.clone(), enrichment_tables, input_type)?;
+        Ok(if self.reroute_dropped {
+            Transform::multi_output(remap)
+        } else {
+            Transform::function(remap)
+        })
     }
 
     fn input_type(&self) -> DataType {
@@ -41,32 +75,79 @@ impl TransformConfig for RemapConfig {
     fn transform_type(&self) -> &'static str {
         "remap"
     }
+
+    fn named_outputs(&self) -> Vec<String> {
+        if self.reroute_dropped {
+            vec![DROPPED_OUTPUT.to_owned()]
+        } else {
+            Vec::new()
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct Remap {
     program: Program,
     drop_on_error: bool,
+    reroute_dropped: bool,
 }
 
 impl Remap {
     pub fn new(config: RemapConfig) -> crate::Result<Self> {
-        let program = vrl::compile(&config.source, &vrl_stdlib::all()).map_err(|diagnostics| {
+        Self::new_with_enrichment_tables(config, &TableRegistry::default())
+    }
+
+    /// Create the transform, allowing the program to look up records in the given enrichment
+    /// tables.
+    pub fn new_with_enrichment_tables(
+        config: RemapConfig,
+        enrichment_tables: &TableRegistry,
+    ) -> crate::Result<Self> {
+        Self::new_for_input_type(config, enrichment_tables, DataType::Any)
+    }
+
+    /// Create the transform for events of the given type. If it only receives metrics, the
+    /// program is also checked against the shape of a metric, so that assignments metrics
+    /// can't hold are rejected up front instead of failing on every event.
+    pub fn new_for_input_type(
+        config: RemapConfig,
+        enrichment_tables: &TableRegistry,
+        input_type: DataType,
+    ) -> crate::Result<Self> {
+        let mut functions = vrl_stdlib::all();
+        functions.extend(enrichment_tables.vrl_functions());
+
+        let program = vrl::compile(&config.source, &functions).map_err(|diagnostics| {
             Formatter::new(&config.source, diagnostics)
                 .colored()
                 .to_string()
         })?;
 
+        if input_type == DataType::Metric {
+            let mut state = state::Compiler::new_with_target_schema(Metric::vrl_type_def());
+            vrl::compile_with_state(&config.source, &functions, &mut state).map_err(
+                |diagnostics| {
+                    format!(
+                        "program is invalid for metric events:\n{}",
+                        Formatter::new(&config.source, diagnostics).colored()
+                    )
+                },
+            )?;
+        }
+
         Ok(Remap {
             program,
             drop_on_error: config.drop_on_error,
+            reroute_dropped: config.reroute_dropped,
         })
     }
-}
 
-impl FunctionTransform for Remap {
-    fn transform(&mut self, output: &mut Vec<Event>, mut event: Event) {
-        let original_event = if !self.drop_on_error && self.program.is_fallible() {
+    /// Run the program on `event`. Returns the event to forward, or the event
+    /// to drop along with the error if the program failed and `drop_on_error`
+    /// is set.
+    fn run(&mut self, mut event: Event) -> std::result::Result<Event, (Event, String)> {
+        let keep_original = !self.drop_on_error || self.reroute_dropped;
+        let original_event = if keep_original && self.program.is_fallible() {
             // We need to clone the original event, since it might be mutated by
             // the program before it aborts, while we want to return the
             // unmodified event when an error occurs.
@@ -83,23 +164,42 @@ impl FunctionTransform for Remap {
         };
 
         match result {
-            Ok(_) => output.push(event),
+            Ok(_) => Ok(event),
             Err(error) => {
+                let error = error.to_string();
                 emit!(RemapMappingError {
-                    error: error.to_string(),
+                    error: error.clone(),
                     event_dropped: self.drop_on_error,
                 });
 
+                let event = original_event.unwrap_or(event);
                 if self.drop_on_error {
-                    return;
+                    Err((event, error))
+                } else {
+                    Ok(event)
                 }
-
-                output.push(original_event.unwrap_or(event))
             }
         }
     }
 }
 
+impl FunctionTransform for Remap {
+    fn transform(&mut self, output: &mut Vec<Event>, event: Event) {
+        if let Ok(event) = self.run(event) {
+            output.push(event);
+        }
+    }
+}
+
+impl MultiOutputTransform for Remap {
+    fn transform(&mut self, output: &mut TransformOutputs, event: Event) {
+        match self.run(event) {
+            Ok(event) => output.push(event),
+            Err((event, error)) => output.push_dropped(event, error),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -134,6 +234,7 @@ mod tests {
 "#
             .to_string(),
             drop_on_error: true,
+            reroute_dropped: false,
         };
         let mut tform = Remap::new(conf).unwrap();
 
@@ -160,6 +261,7 @@ mod tests {
                 .baz = 12
             "#},
             drop_on_error: false,
+            reroute_dropped: false,
         };
         let mut tform = Remap::new(conf).unwrap();
 
@@ -185,6 +287,7 @@ mod tests {
                 .baz = 12
             "#},
             drop_on_error: false,
+            reroute_dropped: false,
         };
         let mut tform = Remap::new(conf).unwrap();
 
@@ -195,6 +298,57 @@ mod tests {
         assert_eq!(event.as_log().get("baz"), Some(&Value::from(12)));
     }
 
+    #[test]
+    fn check_remap_reroute_dropped() {
+        let conf = RemapConfig {
+            source: formatdoc! {r#"
+                .foo = "foo"
+                .not_an_int = int!(.bar)
+            "#},
+            drop_on_error: true,
+            reroute_dropped: true,
+        };
+        let mut output = TransformOutputs::new(conf.named_outputs()).with_component("remap");
+        let mut tform = Remap::new(conf).unwrap();
+
+        let mut ok = Event::from("ok");
+        ok.as_mut_log().insert("bar", 1);
+        let mut failing = Event::from("failing");
+        failing.as_mut_log().insert("bar", "is a string");
+
+        MultiOutputTransform::transform(&mut tform, &mut output, ok);
+        MultiOutputTransform::transform(&mut tform, &mut output, failing);
+
+        assert_eq!(output.primary().len(), 1);
+        assert_eq!(
+            output.primary()[0].as_log().get("foo"),
+            Some(&Value::from("foo"))
+        );
+        let dropped = output.named(DROPPED_OUTPUT).unwrap();
+        assert_eq!(dropped.len(), 1);
+        let dropped = dropped[0].as_log();
+        // The changes made before the error are discarded.
+        assert!(dropped.get("foo").is_none());
+        assert_eq!(dropped.get("bar"), Some(&Value::from("is a string")));
+        assert_eq!(
+            dropped.get("dead_letter.component"),
+            Some(&Value::from("remap"))
+        );
+        assert!(dropped["dead_letter.error"]
+            .to_string_lossy()
+            .contains("function call error"));
+    }
+
+    #[tokio::test]
+    async fn reroute_dropped_requires_drop_on_error() {
+        let conf = RemapConfig {
+            source: ".foo = 1".to_owned(),
+            drop_on_error: false,
+            reroute_dropped: true,
+        };
+        assert!(conf.build(&GlobalOptions::default()).await.is_err());
+    }
+
     #[test]
     fn check_remap_metric() {
         let metric = Event::Metric(Metric::new(
@@ -210,6 +364,7 @@ mod tests {
                        .kind = "incremental""#
                 .to_string(),
             drop_on_error: true,
+            reroute_dropped: false,
         };
         let mut tform = Remap::new(conf).unwrap();
 
@@ -231,4 +386,112 @@ mod tests {
             )
         );
     }
+
+    #[test]
+    fn check_remap_metric_assignment_type() {
+        let conf = RemapConfig {
+            source: r#".counter.value = "foo""#.to_string(),
+            drop_on_error: true,
+            reroute_dropped: false,
+        };
+        let tables = TableRegistry::default();
+
+        assert!(Remap::new_for_input_type(conf.clone(), &tables, DataType::Metric).is_err());
+        assert!(Remap::new_for_input_type(conf.clone(), &tables, DataType::Any).is_ok());
+        assert!(Remap::new_for_input_type(conf, &tables, DataType::Log).is_ok());
+    }
+
+    #[cfg(all(feature = "sources-file", feature = "sinks-console"))]
+    #[test]
+    fn check_remap_log_program_after_remap() {
+        let config = crate::config::load_from_str(
+            indoc::indoc! {r#"
+                [sources.in]
+                  type = "file"
+                  include = ["/var/log/messages"]
+
+                [transforms.first]
+                  type = "remap"
+                  inputs = ["in"]
+                  source = ".first = true"
+
+                [transforms.second]
+                  type = "remap"
+                  inputs = ["first"]
+                  source = ".tags = [\"a\"]; .namespace = 5"
+
+                [sinks.out]
+                  type = "console"
+                  inputs = ["second"]
+                  encoding = "json"
+            "#},
+            Some(crate::config::Format::TOML),
+        )
+        .unwrap();
+        let input_type = config.inputs_type(&["first".to_owned()]);
+
+        let conf = RemapConfig {
+            source: r#".tags = ["a"]; .namespace = 5"#.to_string(),
+            drop_on_error: false,
+            reroute_dropped: false,
+        };
+        let tables = TableRegistry::default();
+
+        assert!(Remap::new_for_input_type(conf, &tables, input_type).is_ok());
+    }
+
+    #[cfg(feature = "enrichment-tables-file")]
+    #[test]
+    fn check_remap_enrichment_table_lookup() {
+        use crate::enrichment_tables::{file::FileConfig, EnrichmentTableConfig};
+        use indexmap::IndexMap;
+        use std::io::Write;
+
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        write!(file, "host,team\nweb-1,frontend\ndb-1,storage\n").unwrap();
+
+        let mut tables = IndexMap::<_, Box<dyn EnrichmentTableConfig>>::new();
+        tables.insert(
+            "teams".to_owned(),
+            Box::new(FileConfig {
+                path: file.path().to_owned(),
+                delimiter: ',',
+                include_headers: true,
+                schema: Default::default(),
+                reload_interval_secs: 0,
+            }),
+        );
+        let tables = TableRegistry::build(&tables, &GlobalOptions::default()).unwrap();
+
+        let conf = RemapConfig {
+            source: formatdoc! {r#"
+                record = get_enrichment_table_record!("teams", { "host": .host }, case_sensitive: false)
+                .team = record.team
+            "#},
+            drop_on_error: true,
+            reroute_dropped: false,
+        };
+        let mut tform = Remap::new_with_enrichment_tables(conf, &tables).unwrap();
+
+        let mut event = Event::from("augment me");
+        event.as_mut_log().insert("host", "DB-1");
+
+        let result = tform.transform_one(event).unwrap();
+        assert_eq!(get_field_string(&result, "team"), "storage");
+
+        let mut functions = vrl_stdlib::all();
+        functions.extend(tables.vrl_functions());
+
+        // The error points at the table argument.
+        let source = r#"get_enrichment_table_record!("unknown", { "host": .host })"#;
+        let diagnostics = vrl::compile(source, &functions).err().unwrap();
+        let error = Formatter::new(source, diagnostics).to_string();
+        assert!(error.contains("error[E115]"));
+        assert!(error.contains(":1:30"));
+
+        let source = r#"get_enrichment_table_record!("teams", { "unknown": .host })"#;
+        let diagnostics = vrl::compile(source, &functions).err().unwrap();
+        let error = Formatter::new(source, diagnostics).to_string();
+        assert!(error.contains("error[E116]"));
+    }
 }
@@ -3,59 +3,36 @@ use crate::{
     config::{DataType, GenerateConfig, GlobalOptions, TransformConfig, TransformDescription},
     event::Event,
     internal_events::RouteEventDiscarded,
-    transforms::{FunctionTransform, Transform},
+    transforms::{MultiOutputTransform, Transform, TransformOutputs},
 };
 use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 
 //------------------------------------------------------------------------------
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
-#[serde(deny_unknown_fields)]
-pub struct LaneConfig {
-    #[serde(flatten)]
-    condition: AnyCondition,
-}
-
-#[async_trait::async_trait]
-#[typetag::serde(name = "lane")]
-impl TransformConfig for LaneConfig {
-    async fn build(&self, _globals: &GlobalOptions) -> crate::Result<Transform> {
-        Ok(Transform::function(Lane::new(self.condition.build()?)))
-    }
-
-    fn input_type(&self) -> DataType {
-        DataType::Log
-    }
-
-    fn output_type(&self) -> DataType {
-        DataType::Log
-    }
-
-    fn transform_type(&self) -> &'static str {
-        "lane"
-    }
-}
-
+/// Sends each event to the output of every lane whose condition it matches,
+/// in a single pass.
 #[derive(Clone, Derivative)]
 #[derivative(Debug)]
-pub struct Lane {
+pub struct Route {
     #[derivative(Debug = "ignore")]
-    condition: Box<dyn Condition>,
+    lanes: Vec<(String, Box<dyn Condition>)>,
 }
 
-impl Lane {
-    pub fn new(condition: Box<dyn Condition>) -> Self {
-        Self { condition }
+impl Route {
+    pub fn new(lanes: Vec<(String, Box<dyn Condition>)>) -> Self {
+        Self { lanes }
     }
 }
 
-impl FunctionTransform for Lane {
-    fn transform(&mut self, output: &mut Vec<Event>, event: Event) {
-        if self.condition.check(&event) {
-            output.push(event);
-        } else {
-            emit!(RouteEventDiscarded);
+impl MultiOutputTransform for Route {
+    fn transform(&mut self, output: &mut TransformOutputs, event: Event) {
+        for (lane, condition) in &self.lanes {
+            if condition.check(&event) {
+                output.push_named(lane, event.clone());
+            } else {
+                emit!(RouteEventDiscarded);
+            }
         }
     }
 }
@@ -91,21 +68,17 @@ impl GenerateConfig for RouteConfig {
 #[typetag::serde(name = "route")]
 impl TransformConfig for RouteConfig {
     async fn build(&self, _globals: &GlobalOptions) -> crate::Result<Transform> {
-        Err("this transform must be expanded".into())
-    }
-
-    fn expand(&mut self) -> crate::Result<Option<IndexMap<String, Box<dyn TransformConfig>>>> {
-        let mut map: IndexMap<String, Box<dyn TransformConfig>> = IndexMap::new();
-
-        while let Some((k, v)) = self.route.pop() {
-            map.insert(k.clone(), Box::new(LaneConfig { condition: v }));
+        if self.route.is_empty() {
+            return Err("must specify at least one lane".into());
         }
 
-        if !map.is_empty() {
-            Ok(Some(map))
-        } else {
-            Err("must specify at least one lane".into())
-        }
+        let lanes = self
+            .route
+            .iter()
+            .map(|(lane, condition)| Ok((lane.clone(), condition.build()?)))
+            .collect::<crate::Result<_>>()?;
+
+        Ok(Transform::multi_output(Route::new(lanes)))
     }
 
     fn input_type(&self) -> DataType {
@@ -119,6 +92,10 @@ impl TransformConfig for RouteConfig {
     fn transform_type(&self) -> &'static str {
         "route"
     }
+
+    fn named_outputs(&self) -> Vec<String> {
+        self.route.keys().cloned().collect()
+    }
 }
 
 // Add a compatibility alias to avoid breaking existing configs
@@ -132,10 +109,6 @@ impl TransformConfig for RouteCompatConfig {
         self.0.build(globals).await
     }
 
-    fn expand(&mut self) -> crate::Result<Option<IndexMap<String, Box<dyn TransformConfig>>>> {
-        self.0.expand()
-    }
-
     fn input_type(&self) -> DataType {
         self.0.input_type()
     }
@@ -147,13 +120,17 @@ impl TransformConfig for RouteCompatConfig {
     fn transform_type(&self) -> &'static str {
         self.0.transform_type()
     }
+
+    fn named_outputs(&self) -> Vec<String> {
+        self.0.named_outputs()
+    }
 }
 
 //------------------------------------------------------------------------------
 
 #[cfg(test)]
 mod test {
-    use super::RouteConfig;
+    use super::*;
 
     #[test]
     fn generate_config() {
@@ -170,4 +147,45 @@ mod test {
         )
         .unwrap();
     }
+
+    #[tokio::test]
+    async fn routes_in_a_single_pass() {
+        let config = toml::from_str::<RouteConfig>(
+            r#"
+            route.first.type = "check_fields"
+            route.first."message.eq" = "foo"
+            route.second.type = "check_fields"
+            route.second."message.contains" = "o"
+        "#,
+        )
+        .unwrap();
+        let mut transform = config.build(&GlobalOptions::default()).await.unwrap();
+
+        let mut output = TransformOutputs::new(config.named_outputs());
+        for message in &["foo", "bar", "boo"] {
+            transform
+                .as_multi_output()
+                .transform(&mut output, Event::from(*message));
+        }
+
+        let messages = |output: &mut TransformOutputs, lane: &str| {
+            output
+                .named(lane)
+                .unwrap()
+                .iter()
+                .map(|event| {
+                    event.as_log()[crate::config::log_schema().message_key()].to_string_lossy()
+                })
+                .collect::<Vec<_>>()
+        };
+        assert!(output.primary().is_empty());
+        assert_eq!(messages(&mut output, "first"), vec!["foo"]);
+        assert_eq!(messages(&mut output, "second"), vec!["foo", "boo"]);
+    }
+
+    #[tokio::test]
+    async fn requires_a_lane() {
+        let config = toml::from_str::<RouteConfig>("route = {}").unwrap();
+        assert!(config.build(&GlobalOptions::default()).await.is_err());
+    }
 }
@@ -0,0 +1,293 @@
+use crate::{
+    config::{DataType, GenerateConfig, GlobalOptions, TransformConfig, TransformDescription},
+    event::Event,
+    internal_events::{TemplateRenderingFailed, ThrottleEventDiscarded},
+    template::Template,
+    transforms::{MultiOutputTransform, Transform, TransformOutputs, DROPPED_OUTPUT},
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct ThrottleConfig {
+    /// The number of events allowed per key within each window.
+    pub threshold: u32,
+    /// The length of the window, in seconds.
+    pub window: f64,
+    pub key_field: Option<Template>,
+    /// Send throttled events to the `dropped` output instead of discarding them.
+    #[serde(default)]
+    pub reroute_dropped: bool,
+}
+
+inventory::submit! {
+    TransformDescription::new::<ThrottleConfig>("throttle")
+}
+
+impl GenerateConfig for ThrottleConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            threshold: 100,
+            window: 1.0,
+            key_field: None,
+            reroute_dropped: false,
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "throttle")]
+impl TransformConfig for ThrottleConfig {
+    async fn build(&self, _globals: &GlobalOptions) -> crate::Result<Transform> {
+        if self.threshold == 0 {
+            return Err("`threshold` must be greater than zero".into());
+        }
+        if self.window <= 0.0 || !self.window.is_finite() {
+            return Err("`window` must be a positive number of seconds".into());
+        }
+
+        Ok(Transform::multi_output(Throttle::new(
+            self.threshold,
+            Duration::from_secs_f64(self.window),
+            self.key_field.clone(),
+            self.reroute_dropped,
+        )))
+    }
+
+    fn input_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    fn output_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    fn transform_type(&self) -> &'static str {
+        "throttle"
+    }
+
+    fn named_outputs(&self) -> Vec<String> {
+        if self.reroute_dropped {
+            vec![DROPPED_OUTPUT.to_owned()]
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+/// A token bucket that holds up to `threshold` tokens and is refilled at a
+/// rate of `threshold` tokens per window.
+#[derive(Clone, Debug)]
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+#[derive(Clone, Debug)]
+pub struct Throttle {
+    threshold: f64,
+    window: Duration,
+    key_field: Option<Template>,
+    reroute_dropped: bool,
+    buckets: HashMap<Option<String>, Bucket>,
+    last_pruned: Option<Instant>,
+}
+
+impl Throttle {
+    pub fn new(
+        threshold: u32,
+        window: Duration,
+        key_field: Option<Template>,
+        reroute_dropped: bool,
+    ) -> Self {
+        Self {
+            threshold: threshold as f64,
+            window,
+            key_field,
+            reroute_dropped,
+            buckets: HashMap::new(),
+            last_pruned: None,
+        }
+    }
+
+    fn key(&self, event: &Event) -> Option<String> {
+        let key_field = self.key_field.as_ref()?;
+        match key_field.render_string(event) {
+            Ok(key) => Some(key),
+            Err(error) => {
+                emit!(TemplateRenderingFailed {
+                    error,
+                    field: Some("key_field"),
+                    drop_event: false,
+                });
+                None
+            }
+        }
+    }
+
+    fn refill(&self, bucket: &mut Bucket, now: Instant) {
+        let elapsed = now.saturating_duration_since(bucket.updated);
+        let refilled = self.threshold * elapsed.as_secs_f64() / self.window.as_secs_f64();
+        bucket.tokens = (bucket.tokens + refilled).min(self.threshold);
+        bucket.updated = now;
+    }
+
+    /// Forget about keys whose bucket has been full for at least a window, so
+    /// that short lived keys don't accumulate.
+    fn prune(&mut self, now: Instant) {
+        match self.last_pruned {
+            Some(last_pruned) if now.saturating_duration_since(last_pruned) < self.window => {}
+            _ => {
+                let window = self.window;
+                self.buckets
+                    .retain(|_, bucket| now.saturating_duration_since(bucket.updated) < window);
+                self.last_pruned = Some(now);
+            }
+        }
+    }
+
+    fn transform_at(&mut self, output: &mut TransformOutputs, event: Event, now: Instant) {
+        self.prune(now);
+
+        let key = self.key(&event);
+        let threshold = self.threshold;
+        let mut bucket = self.buckets.remove(&key).unwrap_or(Bucket {
+            tokens: threshold,
+            updated: now,
+        });
+        self.refill(&mut bucket, now);
+
+        let allowed = bucket.tokens >= 1.0;
+        if allowed {
+            bucket.tokens -= 1.0;
+        }
+        self.buckets.insert(key.clone(), bucket);
+
+        if allowed {
+            output.push(event);
+        } else {
+            emit!(ThrottleEventDiscarded {
+                key: key.as_deref()
+            });
+            if self.reroute_dropped {
+                output.push_named(DROPPED_OUTPUT, event);
+            }
+        }
+    }
+}
+
+impl MultiOutputTransform for Throttle {
+    fn transform(&mut self, output: &mut TransformOutputs, event: Event) {
+        self.transform_at(output, event, Instant::now())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::convert::TryFrom;
+
+    fn event(service: &str) -> Event {
+        let mut event = Event::from("hello");
+        event.as_mut_log().insert("service", service);
+        event
+    }
+
+    fn throttle(threshold: u32, key_field: Option<&str>, reroute_dropped: bool) -> Throttle {
+        Throttle::new(
+            threshold,
+            Duration::from_secs(10),
+            key_field.map(|key_field| Template::try_from(key_field).unwrap()),
+            reroute_dropped,
+        )
+    }
+
+    fn outputs(reroute_dropped: bool) -> TransformOutputs {
+        let config = ThrottleConfig {
+            threshold: 1,
+            window: 1.0,
+            key_field: None,
+            reroute_dropped,
+        };
+        TransformOutputs::new(config.named_outputs())
+    }
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<ThrottleConfig>();
+    }
+
+    #[test]
+    fn limits_events_per_window() {
+        let mut throttle = throttle(2, None, false);
+        let mut output = outputs(false);
+        let start = Instant::now();
+
+        for _ in 0..5 {
+            throttle.transform_at(&mut output, event("a"), start);
+        }
+        assert_eq!(output.primary().len(), 2);
+
+        // Half a window refills half of the threshold.
+        throttle.transform_at(&mut output, event("a"), start + Duration::from_secs(5));
+        throttle.transform_at(&mut output, event("a"), start + Duration::from_secs(5));
+        assert_eq!(output.primary().len(), 3);
+
+        // A full window refills the bucket, but never beyond the threshold.
+        for _ in 0..5 {
+            throttle.transform_at(&mut output, event("a"), start + Duration::from_secs(60));
+        }
+        assert_eq!(output.primary().len(), 5);
+    }
+
+    #[test]
+    fn limits_each_key_separately() {
+        let mut throttle = throttle(1, Some("{{ service }}"), false);
+        let mut output = outputs(false);
+        let now = Instant::now();
+
+        for service in &["a", "a", "a", "b", "c", "c"] {
+            throttle.transform_at(&mut output, event(service), now);
+        }
+
+        let services = output
+            .primary()
+            .iter()
+            .map(|event| event.as_log()["service"].to_string_lossy())
+            .collect::<Vec<_>>();
+        assert_eq!(services, vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn reroutes_dropped_events() {
+        let mut throttle = throttle(1, None, true);
+        let mut output = outputs(true);
+        let now = Instant::now();
+
+        for _ in 0..3 {
+            throttle.transform_at(&mut output, event("a"), now);
+        }
+
+        assert_eq!(output.primary().len(), 1);
+        assert_eq!(output.named(DROPPED_OUTPUT).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn forgets_idle_keys() {
+        let mut throttle = throttle(1, Some("{{ service }}"), false);
+        let mut output = outputs(false);
+        let now = Instant::now();
+
+        throttle.transform_at(&mut output, event("a"), now);
+        throttle.transform_at(&mut output, event("b"), now);
+        assert_eq!(throttle.buckets.len(), 2);
+
+        throttle.transform_at(&mut output, event("b"), now + Duration::from_secs(30));
+        assert_eq!(throttle.buckets.len(), 1);
+    }
+}
@@ -23,12 +23,14 @@ mod tests {
         config::{self, Config, Format},
         internal_events::{emit, GeneratorEventProcessed, Heartbeat},
         test_util::{next_addr, retry_until},
+        topology::control::{self, ControlRequest},
     };
     use vector_api_client::{
         connect_subscription_client,
         gql::{
-            ComponentsSubscriptionExt, HealthQueryExt, HealthSubscriptionExt, MetaQueryExt,
-            MetricsSubscriptionExt,
+            validate_config_mutation, ComponentsSubscriptionExt, ControlMutationExt,
+            HealthQueryExt, HealthSubscriptionExt, MetaQueryExt, MetricsSubscriptionExt,
+            TapSubscriptionExt,
         },
         test::*,
         Client, SubscriptionClient,
@@ -396,6 +398,52 @@ mod tests {
         assert_eq!(res.data.unwrap().meta.version_string, vector::get_version());
     }
 
+    #[tokio::test]
+    /// Tests that control mutations are forwarded to the running topology
+    async fn api_graphql_control_mutations() {
+        let config = api_enabled_config();
+        let (control_tx, mut control_rx) = control::control_channel();
+        let server = api::Server::start_with_control(&config, control_tx);
+        let client = make_client(server.addr());
+
+        tokio::spawn(async move {
+            while let Some((request, respond)) = control_rx.recv().await {
+                let result = match request {
+                    ControlRequest::PauseSource(name) if name == "in1" => Ok(()),
+                    _ => Err("Unexpected request.".to_owned()),
+                };
+                let _ = respond.send(result);
+            }
+        });
+
+        let res = client.pause_source_mutation("in1").await.unwrap();
+        assert!(res.data.unwrap().pause_source);
+        assert_eq!(res.errors, None);
+
+        let res = client.resume_source_mutation("in1").await.unwrap();
+        assert!(res.data.is_none());
+        assert_eq!(res.errors.unwrap()[0].message, "Unexpected request.");
+    }
+
+    #[tokio::test]
+    /// Tests that configs are validated without being applied
+    async fn api_graphql_validate_config() {
+        let server = start_server();
+        let client = make_client(server.addr());
+
+        let res = client
+            .validate_config_mutation(
+                r#"{"sinks": {"out": {"type": "blackhole", "inputs": ["missing"]}}}"#,
+                validate_config_mutation::ConfigFormat::JSON,
+            )
+            .await
+            .unwrap();
+
+        let validation = res.data.unwrap().validate_config;
+        assert!(!validation.valid);
+        assert!(!validation.errors.is_empty());
+    }
+
     #[test]
     /// Tests that the heartbeat subscription returns a UTC payload every 1/2 second
     fn api_graphql_heartbeat() {
@@ -495,6 +543,65 @@ mod tests {
         )
     }
 
+    #[test]
+    /// Tests that the events output by a component can be tapped
+    fn api_graphql_output_events() {
+        metrics_test("tests::api_graphql_output_events", async {
+            let conf = r#"
+                [api]
+                  enabled = true
+
+                [sources.tap_source]
+                  type = "generator"
+                  format = "shuffle"
+                  lines = ["Random line", "And another"]
+                  interval = 0.01
+
+                [sinks.tap_sink]
+                  type = "blackhole"
+                  inputs = ["tap_source"]
+                  print_amount = 100000
+            "#;
+
+            let topology = from_str_config(conf).await;
+
+            let (control_tx, mut control_rx) = control::control_channel();
+            let server = api::Server::start_with_control(topology.config(), control_tx);
+            tokio::spawn(async move {
+                while let Some((request, respond)) = control_rx.recv().await {
+                    let result = match request {
+                        ControlRequest::TapOutput(name, tap) if topology.tap_output(&name, tap) => {
+                            Ok(())
+                        }
+                        _ => Err("Unexpected request.".to_owned()),
+                    };
+                    let _ = respond.send(result);
+                }
+            });
+
+            let client = new_subscription_client(server.addr()).await;
+            let subscription = client.output_events_subscription(
+                vec!["tap_source".to_owned()],
+                10,
+                100,
+                Some(r#".message == "And another""#.to_owned()),
+            );
+
+            let events = subscription
+                .stream()
+                .take(5)
+                .map(|r| r.unwrap().data.unwrap().output_events)
+                .collect::<Vec<_>>()
+                .await;
+
+            for event in events {
+                assert_eq!(event.component_name, "tap_source");
+                let event: serde_json::Value = serde_json::from_str(&event.json).unwrap();
+                assert_eq!(event["message"], "And another");
+            }
+        })
+    }
+
     #[test]
     #[allow(clippy::float_cmp)]
     /// Tests componentProcessedBytesTotals returns increasing metrics, ordered by
@@ -1,11 +1,9 @@
-#![cfg(feature = "leveldb")]
-
 use futures::{SinkExt, StreamExt};
 use tempfile::tempdir;
 use tokio::runtime::Runtime;
 use tracing::trace;
 use vector::{
-    buffers::BufferConfig,
+    buffers::{BufferConfig, SyncPolicy},
     config,
     test_util::{
         random_events_with_stream, runtime, start_topology, trace_init, wait_for_atomic_usize,
@@ -21,8 +19,26 @@ fn terminate_abruptly(rt: Runtime, topology: topology::RunningTopology) {
     drop(topology);
 }
 
+#[cfg(feature = "leveldb")]
 #[test]
 fn test_buffering() {
+    test_buffering_with(|max_size| BufferConfig::Disk {
+        max_size,
+        when_full: Default::default(),
+    });
+}
+
+#[test]
+fn test_buffering_disk_v2() {
+    test_buffering_with(|max_size| BufferConfig::DiskV2 {
+        max_size,
+        when_full: Default::default(),
+        sync: SyncPolicy::Always,
+        sync_interval_secs: 1,
+    });
+}
+
+fn test_buffering_with(buffer: impl Fn(usize) -> BufferConfig) {
     trace_init();
 
     let data_dir = tempdir().unwrap();
@@ -48,10 +64,7 @@ fn test_buffering() {
         let mut config = config::Config::builder();
         config.add_source("in", source_config);
         config.add_sink("out", &["in"], sink_config);
-        config.sinks["out"].buffer = BufferConfig::Disk {
-            max_size,
-            when_full: Default::default(),
-        };
+        config.sinks["out"].buffer = buffer(max_size);
         config.global.data_dir = Some(data_dir.clone());
         config.build().unwrap()
     };
@@ -97,10 +110,7 @@ fn test_buffering() {
         let mut config = config::Config::builder();
         config.add_source("in", source_config);
         config.add_sink("out", &["in"], sink_config);
-        config.sinks["out"].buffer = BufferConfig::Disk {
-            max_size,
-            when_full: Default::default(),
-        };
+        config.sinks["out"].buffer = buffer(max_size);
         config.global.data_dir = Some(data_dir);
         config.build().unwrap()
     };